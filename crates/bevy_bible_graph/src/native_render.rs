@@ -3166,7 +3166,7 @@ fn graph_color_components(color: &str) -> (f32, f32, f32) {
 
 fn graph_hex_color_components(color: &str) -> Option<(f32, f32, f32)> {
     let hex = color.strip_prefix('#')?;
-    if hex.len() != 6 {
+    if hex.len() != 6 || !hex.is_ascii() {
         return None;
     }
 
@@ -36,6 +36,7 @@ pub(crate) fn native_relationship_color_rgb(relationship_type: &RelationshipType
         RelationshipType::Causal => [0.937, 0.384, 0.314],
         RelationshipType::Convergence { .. } => [0.655, 0.463, 0.914],
         RelationshipType::Thematic => [0.933, 0.831, 0.455],
+        RelationshipType::Payoff => [0.310, 0.820, 0.773],
     }
 }
 
@@ -8,6 +8,7 @@ use crate::error::Error;
 use crate::story::arc::StoryArc;
 use crate::timeline::node::{BeatType, NodeId, StoryLevel, StoryNode};
 use crate::timeline::structure::EpisodeStructure;
+use crate::timeline::timing::TimeRange;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(try_from = "String", into = "String")]
@@ -55,8 +56,49 @@ pub enum ChildPlanStatus {
     Rejected,
 }
 
-/// Token-by-token stream of generated text.
-pub type GenerateStream = Pin<Box<dyn Stream<Item = Result<String, Error>> + Send>>;
+/// Token-by-token stream of generated text, ending with the provider's
+/// finish reason when it reports one.
+pub type GenerateStream = Pin<Box<dyn Stream<Item = Result<GenerateChunk, Error>> + Send>>;
+
+/// One item of a [`GenerateStream`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerateChunk {
+    Token(String),
+    Finished(FinishReason),
+}
+
+/// Why the provider stopped generating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FinishReason {
+    /// The model finished naturally or hit a stop sequence.
+    Stop,
+    /// The output hit the token limit and is likely cut off.
+    Length,
+    Other(String),
+}
+
+impl FinishReason {
+    /// Map an OpenAI `finish_reason` or Ollama `done_reason` value.
+    pub fn from_provider(reason: &str) -> Self {
+        match reason {
+            "stop" | "eos" | "stop_sequence" => Self::Stop,
+            "length" | "max_tokens" => Self::Length,
+            other => Self::Other(other.to_owned()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Stop => "stop",
+            Self::Length => "length",
+            Self::Other(reason) => reason,
+        }
+    }
+
+    pub fn is_truncated(&self) -> bool {
+        matches!(self, Self::Length)
+    }
+}
 
 /// Backend-agnostic interface for AI generation.
 ///
@@ -85,6 +127,9 @@ pub trait AiBackend: Send + Sync {
 /// Everything the AI needs to generate content for a single story node.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerateRequest {
+    /// The project premise; empty when the project has none.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub premise: String,
     /// The node to generate content for (at any level).
     pub target_node: StoryNode,
     /// Arcs tagged on this node.
@@ -95,6 +140,9 @@ pub struct GenerateRequest {
     /// Sibling nodes at the same level (for structural context).
     #[serde(default)]
     pub siblings: Vec<StoryNode>,
+    /// Maximum characters of each sibling's text to show in the prompt.
+    #[serde(default = "default_sibling_preview_chars")]
+    pub sibling_preview_chars: usize,
     /// Scripts/content from adjacent nodes.
     pub surrounding_context: SurroundingContext,
     /// Target screen time for this node (milliseconds).
@@ -102,6 +150,10 @@ pub struct GenerateRequest {
     /// Text the user wrote that must appear verbatim.
     pub user_written_anchors: Vec<String>,
     pub style_notes: Option<String>,
+    /// Replaces the built-in system prompt preamble, from the project's AI
+    /// settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
     pub rag_context: Vec<RagChunk>,
     /// Backend-owned bible graph facts relevant to this request, when available.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -111,6 +163,10 @@ pub struct GenerateRequest {
     pub affect_context: Option<ProjectionEnvelope<AffectProjection>>,
 }
 
+fn default_sibling_preview_chars() -> usize {
+    crate::ai::prompt::DEFAULT_SIBLING_PREVIEW_CHARS
+}
+
 /// Adjacent node content for context.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SurroundingContext {
@@ -119,6 +175,9 @@ pub struct SurroundingContext {
     /// Recaps from nodes that temporally precede the target node's start time.
     #[serde(default)]
     pub preceding_recaps: Vec<RecapEntry>,
+    /// Recaps from the nearest later siblings, so output leads into them.
+    #[serde(default)]
+    pub following_recaps: Vec<RecapEntry>,
 }
 
 /// A scene recap from a preceding node, including source identification.
@@ -193,6 +252,64 @@ pub struct ChildPlan {
     pub children: Vec<ChildProposal>,
 }
 
+/// AI suggestions for seeding a project from its premise, returned for review
+/// before anything is created.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PremiseExpansion {
+    /// One-sentence pitch for the episode.
+    pub logline: String,
+    /// Proposed A/B/C arcs, in that order.
+    pub arcs: Vec<StoryArc>,
+    /// Proposed principal characters.
+    pub characters: Vec<CharacterProposal>,
+}
+
+/// An AI judgement of whether one node's content contradicts an entity's
+/// established state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContinuityVerdict {
+    pub contradiction: bool,
+    /// What contradicts the expected state; empty when nothing does.
+    #[serde(default)]
+    pub explanation: String,
+}
+
+/// An AI judgement of whether a node's content still carries out its
+/// planning notes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotesDriftVerdict {
+    pub fulfilled: bool,
+    /// Each part of the notes the content drops or contradicts.
+    #[serde(default)]
+    pub specifics: Vec<String>,
+}
+
+/// An arc the model judges a node to advance, named as the model returned
+/// it; the server resolves the name to an arc id.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArcTagSuggestion {
+    pub arc_name: String,
+    /// How sure the model is, from 0.0 to 1.0.
+    pub confidence: f32,
+}
+
+/// A principal character suggested by a premise expansion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CharacterProposal {
+    pub name: String,
+    /// Short description of who they are and what they want.
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Time ranges an (optionally edited) child plan would occupy if applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChildPlanRangePreview {
+    pub parent_node_id: NodeId,
+    /// One range per child, in plan order.
+    pub ranges: Vec<TimeRange>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChildPlanRecord {
     pub plan: ChildPlan,
@@ -215,6 +332,9 @@ pub struct RejectChildPlanCommand {
 /// Everything the AI needs to plan children for a parent node.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerateChildrenRequest {
+    /// The project premise; empty when the project has none.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub premise: String,
     /// The parent node to decompose.
     pub parent_node: StoryNode,
     /// What level of children to generate.
@@ -227,12 +347,23 @@ pub struct GenerateChildrenRequest {
     /// Premise → Act decomposition so the AI knows the expected act layout.
     #[serde(default)]
     pub episode_structure: Option<EpisodeStructure>,
+    /// Beat types that suit the parent's place in the episode, for
+    /// decomposition into beats.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggested_beat_types: Vec<BeatType>,
     /// Backend-owned bible graph facts relevant to this decomposition, when available.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bible_context: Option<ProjectionEnvelope<AiBibleContextProjection>>,
     /// Backend-owned affect constraints relevant to this decomposition, when available.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub affect_context: Option<ProjectionEnvelope<AffectProjection>>,
+    /// Children kept when only filling gaps, in time order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub existing_children: Vec<StoryNode>,
+    /// Uncovered parts of the parent to fill, in time order; empty when the
+    /// plan replaces every child.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fill_gaps: Vec<TimeRange>,
 }
 
 /// Everything the AI needs to infer a parent from children.
@@ -55,6 +55,7 @@ pub fn gather_surrounding_context(timeline: &Timeline, node_id: NodeId) -> Surro
         preceding_scripts,
         following_scripts,
         preceding_recaps: Vec::new(),
+        following_recaps: Vec::new(),
     }
 }
 
@@ -118,21 +119,8 @@ pub fn gather_recap_context(
         if node.level != StoryLevel::Scene && node.level != StoryLevel::Beat {
             continue;
         }
-        if let Some(ref recap) = node.content.scene_recap {
-            // Find arc names for this node.
-            let arc_ids = timeline.arcs_for_node(node.id);
-            let arc_name = arc_ids
-                .first()
-                .and_then(|aid| arcs.iter().find(|a| a.id == *aid))
-                .map(|a| a.name.as_str())
-                .unwrap_or("Untagged");
-
-            entries.push(RecapEntry {
-                arc_name: arc_name.to_string(),
-                node_name: node.name.clone(),
-                end_time_ms: node.time_range.end_ms,
-                recap: recap.clone(),
-            });
+        if let Some(entry) = recap_entry(timeline, arcs, node) {
+            entries.push(entry);
         }
     }
 
@@ -146,3 +134,50 @@ pub fn gather_recap_context(
 
     entries
 }
+
+/// Gather scene recaps from the nearest later siblings of the target.
+///
+/// Looks at up to `CONTEXT_WINDOW` siblings that start at or after the
+/// target's end, in time order, keeping those that have a recap.
+pub fn gather_following_recaps(
+    timeline: &Timeline,
+    arcs: &[StoryArc],
+    target_node_id: NodeId,
+) -> Vec<RecapEntry> {
+    let Ok(target) = timeline.node(target_node_id) else {
+        return vec![];
+    };
+    let target_end = target.time_range.end_ms;
+
+    let mut following: Vec<&StoryNode> = timeline
+        .siblings_of(target_node_id)
+        .into_iter()
+        .filter(|s| s.time_range.start_ms >= target_end)
+        .collect();
+    following.sort_by_key(|s| s.time_range.start_ms);
+
+    following
+        .into_iter()
+        .take(CONTEXT_WINDOW)
+        .filter_map(|n| recap_entry(timeline, arcs, n))
+        .collect()
+}
+
+/// Build a [`RecapEntry`] for a node with a scene recap, labelled with its
+/// first tagged arc.
+fn recap_entry(timeline: &Timeline, arcs: &[StoryArc], node: &StoryNode) -> Option<RecapEntry> {
+    let recap = node.content.scene_recap.as_ref()?;
+    let arc_name = timeline
+        .arcs_for_node(node.id)
+        .first()
+        .and_then(|aid| arcs.iter().find(|a| a.id == *aid))
+        .map(|a| a.name.as_str())
+        .unwrap_or("Untagged");
+
+    Some(RecapEntry {
+        arc_name: arc_name.to_string(),
+        node_name: node.name.clone(),
+        end_time_ms: node.time_range.end_ms,
+        recap: recap.clone(),
+    })
+}
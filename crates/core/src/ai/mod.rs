@@ -1,4 +1,5 @@
 pub mod backend;
 pub mod consistency;
 pub mod helpers;
+pub mod note_template;
 pub mod prompt;
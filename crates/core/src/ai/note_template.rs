@@ -0,0 +1,94 @@
+//! `{Name}` placeholders in planning notes, expanded into the context they
+//! name before the notes reach a prompt.
+
+use crate::story::arc::StoryArc;
+
+/// Replace each `{Name}` in `notes` with `resolve(key)`, where `key` is the
+/// name normalized by [`placeholder_key`]. Placeholders `resolve` does not
+/// know, empty braces, and unclosed braces are left as written.
+pub fn expand_note_placeholders(
+    notes: &str,
+    mut resolve: impl FnMut(&str) -> Option<String>,
+) -> String {
+    let mut out = String::with_capacity(notes.len());
+    let mut rest = notes;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let close = after.find(['{', '}', '\n']);
+        match close {
+            Some(close) if after[close..].starts_with('}') && !after[..close].trim().is_empty() => {
+                let name = &after[..close];
+                match resolve(&placeholder_key(name)) {
+                    Some(text) => out.push_str(&text),
+                    None => {
+                        out.push('{');
+                        out.push_str(name);
+                        out.push('}');
+                    }
+                }
+                rest = &after[close + 1..];
+            }
+            _ => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The form placeholder names are matched in: trimmed, lower-cased, with
+/// underscores read as spaces, so `{LOCKET}` and `{main_plot}` find "Locket"
+/// and "Main Plot".
+pub fn placeholder_key(name: &str) -> String {
+    name.trim().replace('_', " ").to_lowercase()
+}
+
+/// `Name (text)`, or just the name when there is nothing to add.
+pub fn placeholder_text(name: &str, text: &str) -> String {
+    let text = text.trim();
+    if text.is_empty() {
+        name.to_string()
+    } else {
+        format!("{name} ({text})")
+    }
+}
+
+/// Expand `{ArcName}` placeholders into the arc's name and description.
+pub fn expand_arc_placeholders(notes: &str, arcs: &[StoryArc]) -> String {
+    expand_note_placeholders(notes, |key| {
+        arcs.iter()
+            .find(|arc| placeholder_key(&arc.name) == key)
+            .map(|arc| placeholder_text(&arc.name, &arc.description))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::story::arc::{ArcType, Color};
+
+    #[test]
+    fn placeholders_expand_by_name_and_unknown_ones_pass_through() {
+        let mut arc = StoryArc::new("Main Plot", ArcType::APlot, Color::new(0, 0, 0));
+        arc.description = "Jake hunts the thief".into();
+        let arcs = [arc];
+
+        let notes = "{JAKE} confronts {Amy} about the {LOCKET} ({main_plot}). {} {open";
+        let expanded = expand_note_placeholders(&expand_arc_placeholders(notes, &arcs), |key| {
+            (key == "jake").then(|| placeholder_text("Jake", "detective"))
+        });
+
+        assert_eq!(
+            expanded,
+            "Jake (detective) confronts {Amy} about the {LOCKET} \
+             (Main Plot (Jake hunts the thief)). {} {open"
+        );
+        assert_eq!(
+            expand_note_placeholders("{a{b}}", |_| Some("x".into())),
+            "{ax}"
+        );
+    }
+}
@@ -1,14 +1,26 @@
-use crate::ai::backend::{GenerateChildrenRequest, GenerateRequest};
+use serde_json::{Map, Value};
+
+use crate::ai::backend::{
+    ArcTagSuggestion, CharacterProposal, ChildProposal, ContinuityVerdict, GenerateChildrenRequest,
+    GenerateRequest, NotesDriftVerdict, PremiseExpansion,
+};
 use crate::error::{Error, Result};
 use crate::project::Project;
-use crate::timeline::node::NodeId;
+use crate::story::arc::{ArcType, Color, StoryArc};
+use crate::timeline::node::{BeatType, NodeId, StoryLevel, StoryNode};
+use crate::timeline::timing::TimeRange;
+
+use super::helpers::{gather_following_recaps, gather_recap_context, gather_surrounding_context};
+use super::note_template::expand_arc_placeholders;
 
-use super::helpers::{gather_recap_context, gather_surrounding_context};
+/// Default number of characters shown for each sibling in a prompt.
+pub const DEFAULT_SIBLING_PREVIEW_CHARS: usize = 200;
 
 /// Build a [`GenerateRequest`] for a specific story node from the project state.
 ///
 /// Gathers:
-/// - The target node and its tagged arcs
+/// - The target node, with `{ArcName}` placeholders in its notes expanded,
+///   and its tagged arcs
 /// - Ancestor chain (parent, grandparent, etc.)
 /// - Sibling nodes at the same level
 /// - Story bible context from graph-backed AI context projections when available
@@ -16,7 +28,9 @@ use super::helpers::{gather_recap_context, gather_surrounding_context};
 pub fn build_generate_request(project: &Project, node_id: NodeId) -> Result<GenerateRequest> {
     let timeline = &project.timeline;
 
-    let target_node = timeline.node(node_id)?.clone();
+    let mut target_node = timeline.node(node_id)?.clone();
+    // Entity placeholders are expanded once the bible context is attached.
+    target_node.content.notes = expand_arc_placeholders(&target_node.content.notes, &project.arcs);
 
     // Gather tagged arcs for this node.
     let arc_ids = timeline.arcs_for_node(node_id);
@@ -43,24 +57,91 @@ pub fn build_generate_request(project: &Project, node_id: NodeId) -> Result<Gene
     // Gather cross-node recaps for continuity.
     surrounding_context.preceding_recaps =
         gather_recap_context(&project.timeline, &project.arcs, node_id);
+    surrounding_context.following_recaps =
+        gather_following_recaps(&project.timeline, &project.arcs, node_id);
 
     let time_budget_ms = target_node.time_range.duration_ms();
 
     Ok(GenerateRequest {
+        premise: project.premise.trim().to_string(),
         target_node,
         tagged_arcs,
         ancestor_chain,
         siblings,
+        sibling_preview_chars: DEFAULT_SIBLING_PREVIEW_CHARS,
         surrounding_context,
         time_budget_ms,
         user_written_anchors: vec![],
-        style_notes: None,
+        style_notes: project.ai_settings.style_notes.clone(),
+        system_prompt: project.ai_settings.system_prompt.clone(),
         rag_context: vec![],
         bible_context: None,
         affect_context: None,
     })
 }
 
+/// Check that `node` can be generated from its notes: it must be unlocked and
+/// have notes to work from.
+pub fn ensure_generatable(node: &StoryNode) -> Result<()> {
+    if node.locked {
+        return Err(Error::NodeLocked(node.id.0));
+    }
+    if node.content.notes.trim().is_empty() {
+        return Err(Error::MissingNotes(node.id.0));
+    }
+    Ok(())
+}
+
+/// Check that `node` can have `existing` content continued: it must be
+/// unlocked and the content must not be blank.
+pub fn ensure_continuable(node: &StoryNode, existing: &str) -> Result<()> {
+    if node.locked {
+        return Err(Error::NodeLocked(node.id.0));
+    }
+    if existing.trim().is_empty() {
+        return Err(Error::NodeNotGeneratable {
+            node_id: node.id.0,
+            reason: "no content to continue".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Check that a prompt of `estimated` tokens fits within `limit` tokens.
+pub fn ensure_prompt_fits(estimated: usize, limit: usize) -> Result<()> {
+    if estimated > limit {
+        return Err(Error::PromptTooLarge { estimated, limit });
+    }
+    Ok(())
+}
+
+/// Keep only the `max_siblings` siblings nearest in time to the target node.
+///
+/// The target itself is always included so the prompt can mark it. The result
+/// is ordered by start time, matching the timeline.
+pub fn limit_siblings(request: &mut GenerateRequest, max_siblings: usize) {
+    let target = &request.target_node;
+    let target_range = target.time_range;
+
+    let mut others: Vec<StoryNode> = std::mem::take(&mut request.siblings)
+        .into_iter()
+        .filter(|sibling| sibling.id != target.id)
+        .collect();
+    others.sort_by_key(|sibling| time_distance(&target_range, &sibling.time_range));
+    others.truncate(max_siblings);
+    others.push(target.clone());
+    others.sort_by_key(|sibling| (sibling.time_range.start_ms, sibling.sort_order));
+
+    request.siblings = others;
+}
+
+/// Gap between two ranges in milliseconds (zero when they touch or overlap).
+fn time_distance(a: &TimeRange, b: &TimeRange) -> u64 {
+    a.start_ms
+        .saturating_sub(b.end_ms)
+        .max(b.start_ms.saturating_sub(a.end_ms))
+}
+
 /// Build a [`GenerateChildrenRequest`] for decomposing a parent node into children.
 ///
 /// Gathers the same context as `build_generate_request` but focused on the
@@ -96,17 +177,400 @@ pub fn build_generate_children_request(
         None
     };
 
+    let suggested_beat_types = if target_child_level == StoryLevel::Beat {
+        let range = parent_node.time_range;
+        timeline.suggested_beat_types(range.start_ms + range.duration_ms() / 2)
+    } else {
+        Vec::new()
+    };
+
     Ok(GenerateChildrenRequest {
+        premise: project.premise.trim().to_string(),
         parent_node,
         target_child_level,
         tagged_arcs,
         surrounding_context,
         episode_structure,
+        suggested_beat_types,
         bible_context: None,
         affect_context: None,
+        existing_children: Vec::new(),
+        fill_gaps: Vec::new(),
+    })
+}
+
+/// Why an AI child-plan response could not be turned into proposals.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ChildPlanParseError {
+    #[error("response is not valid JSON: {0}")]
+    InvalidJson(String),
+    #[error("response does not contain a list of children")]
+    UnexpectedShape,
+    #[error("response contains no children")]
+    Empty,
+    #[error("children are missing required fields: {}", describe_missing(.0))]
+    MissingFields(Vec<MissingChildFields>),
+}
+
+/// Required fields absent from one child in an AI response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingChildFields {
+    /// Position of the child in the response.
+    pub index: usize,
+    pub fields: Vec<&'static str>,
+}
+
+fn describe_missing(missing: &[MissingChildFields]) -> String {
+    missing
+        .iter()
+        .map(|m| format!("child {} ({})", m.index, m.fields.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Keys models use to wrap the child list in an object.
+const CHILD_LIST_KEYS: [&str; 6] = ["children", "acts", "sequences", "scenes", "beats", "items"];
+
+/// Parse an AI decomposition response into child proposals for `target_level`.
+///
+/// Tolerates the shapes models actually return: Markdown fences and chatter
+/// around the JSON, a bare array, an object wrapping the array, or a single
+/// child object. Fields are coerced rather than rejected where a sensible
+/// default exists:
+/// - `weight` accepts numbers or numeric strings; missing or non-positive → 1.0
+/// - `beat_type` is only kept for Beat-level children, matched case-insensitively
+/// - an empty `outline` is filled from `name`
+///
+/// Only `name` is required; children without one are reported together.
+pub fn parse_child_plan(
+    raw: &str,
+    target_level: StoryLevel,
+) -> std::result::Result<Vec<ChildProposal>, ChildPlanParseError> {
+    let value: Value = serde_json::from_str(extract_json_payload(raw))
+        .map_err(|e| ChildPlanParseError::InvalidJson(e.to_string()))?;
+
+    let items = match value {
+        Value::Array(items) => items,
+        Value::Object(mut object) => {
+            match CHILD_LIST_KEYS
+                .iter()
+                .find_map(|key| match object.remove(*key) {
+                    Some(Value::Array(items)) => Some(items),
+                    _ => None,
+                }) {
+                Some(items) => items,
+                None => vec![Value::Object(object)],
+            }
+        }
+        _ => return Err(ChildPlanParseError::UnexpectedShape),
+    };
+    if items.is_empty() {
+        return Err(ChildPlanParseError::Empty);
+    }
+
+    let mut children = Vec::with_capacity(items.len());
+    let mut missing = Vec::new();
+    for (index, item) in items.into_iter().enumerate() {
+        let Value::Object(fields) = item else {
+            return Err(ChildPlanParseError::UnexpectedShape);
+        };
+        match coerce_child(&fields, target_level) {
+            Some(child) => children.push(child),
+            None => missing.push(MissingChildFields {
+                index,
+                fields: vec!["name"],
+            }),
+        }
+    }
+
+    if missing.is_empty() {
+        Ok(children)
+    } else {
+        Err(ChildPlanParseError::MissingFields(missing))
+    }
+}
+
+fn coerce_child(fields: &Map<String, Value>, target_level: StoryLevel) -> Option<ChildProposal> {
+    let text = |keys: &[&str]| text_field(fields, keys);
+    let list = |key: &str| match fields.get(key) {
+        Some(Value::Array(values)) => values
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_owned)
+            .collect(),
+        Some(Value::String(joined)) => joined
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_owned)
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let name = text(&["name", "title"])?;
+    let outline = text(&["outline", "description", "summary"]).unwrap_or_else(|| name.clone());
+    let weight = match fields.get("weight") {
+        Some(Value::Number(n)) => n.as_f64().map(|w| w as f32),
+        Some(Value::String(s)) => s.trim().parse::<f32>().ok(),
+        _ => None,
+    }
+    .filter(|w| w.is_finite() && *w > 0.0)
+    .unwrap_or(1.0);
+    let beat_type = (target_level == StoryLevel::Beat)
+        .then(|| text(&["beat_type", "type"]))
+        .flatten()
+        .map(|label| parse_beat_type(&label));
+
+    Some(ChildProposal {
+        name,
+        level: Some(target_level),
+        beat_type,
+        outline,
+        weight,
+        characters: list("characters"),
+        location: text(&["location"]),
+        props: list("props"),
     })
 }
 
+fn parse_beat_type(label: &str) -> BeatType {
+    match label.to_ascii_lowercase().as_str() {
+        "setup" => BeatType::Setup,
+        "complication" => BeatType::Complication,
+        "escalation" => BeatType::Escalation,
+        "climax" => BeatType::Climax,
+        "resolution" => BeatType::Resolution,
+        "payoff" => BeatType::Payoff,
+        "callback" => BeatType::Callback,
+        _ => BeatType::Custom(label.to_owned()),
+    }
+}
+
+/// Why an AI premise-expansion response could not be parsed.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PremiseExpansionParseError {
+    #[error("response is not valid JSON: {0}")]
+    InvalidJson(String),
+    #[error("response is not a JSON object")]
+    UnexpectedShape,
+    #[error("response contains no logline, arcs, or characters")]
+    Empty,
+}
+
+/// Standard arc slots a premise expansion fills, in order.
+const PREMISE_ARC_SLOTS: [(ArcType, Color); 3] = [
+    (ArcType::APlot, Color::A_PLOT),
+    (ArcType::BPlot, Color::B_PLOT),
+    (ArcType::CRunner, Color::C_RUNNER),
+];
+
+/// Parse an AI premise-expansion response.
+///
+/// Expects an object with `logline`, `arcs`, and `characters`, tolerating the
+/// same fences and chatter as [`parse_child_plan`]. Arcs fill the A, B, and C
+/// slots in the order given (extras are dropped) and take the standard arc
+/// colors; arcs and characters without a name are skipped.
+pub fn parse_premise_expansion(
+    raw: &str,
+) -> std::result::Result<PremiseExpansion, PremiseExpansionParseError> {
+    let value: Value = serde_json::from_str(extract_json_payload(raw))
+        .map_err(|e| PremiseExpansionParseError::InvalidJson(e.to_string()))?;
+    let Value::Object(fields) = value else {
+        return Err(PremiseExpansionParseError::UnexpectedShape);
+    };
+    let objects = |key: &str| match fields.get(key) {
+        Some(Value::Array(items)) => items.iter().filter_map(Value::as_object).collect(),
+        _ => Vec::new(),
+    };
+
+    let logline = text_field(&fields, &["logline", "pitch"]).unwrap_or_default();
+    let arcs: Vec<StoryArc> = objects("arcs")
+        .into_iter()
+        .filter_map(|arc| {
+            let name = text_field(arc, &["name", "title"])?;
+            let description = text_field(arc, &["description", "summary"]).unwrap_or_default();
+            Some((name, description))
+        })
+        .zip(PREMISE_ARC_SLOTS)
+        .map(|((name, description), (arc_type, color))| {
+            let mut arc = StoryArc::new(name, arc_type, color);
+            arc.description = description;
+            arc
+        })
+        .collect();
+    let characters: Vec<CharacterProposal> = objects("characters")
+        .into_iter()
+        .filter_map(|character| {
+            Some(CharacterProposal {
+                name: text_field(character, &["name"])?,
+                description: text_field(character, &["description", "summary", "role"])
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+
+    if logline.is_empty() && arcs.is_empty() && characters.is_empty() {
+        return Err(PremiseExpansionParseError::Empty);
+    }
+    Ok(PremiseExpansion {
+        logline,
+        arcs,
+        characters,
+    })
+}
+
+/// Why an AI continuity-audit response could not be parsed.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ContinuityVerdictParseError {
+    #[error("response is not valid JSON: {0}")]
+    InvalidJson(String),
+    #[error("response has no boolean contradiction flag")]
+    UnexpectedShape,
+}
+
+/// Parse an AI continuity-audit response.
+///
+/// Expects `{"contradiction": bool, "explanation": "..."}`, tolerating the
+/// same fences and chatter as [`parse_child_plan`] and a `contradicts` or
+/// `reason` spelling.
+pub fn parse_continuity_verdict(
+    raw: &str,
+) -> std::result::Result<ContinuityVerdict, ContinuityVerdictParseError> {
+    let value: Value = serde_json::from_str(extract_json_payload(raw))
+        .map_err(|e| ContinuityVerdictParseError::InvalidJson(e.to_string()))?;
+    let Value::Object(fields) = value else {
+        return Err(ContinuityVerdictParseError::UnexpectedShape);
+    };
+    let contradiction = ["contradiction", "contradicts"]
+        .iter()
+        .find_map(|key| fields.get(*key).and_then(Value::as_bool))
+        .ok_or(ContinuityVerdictParseError::UnexpectedShape)?;
+
+    Ok(ContinuityVerdict {
+        contradiction,
+        explanation: text_field(&fields, &["explanation", "reason"]).unwrap_or_default(),
+    })
+}
+
+/// Why an AI notes-drift response could not be parsed.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum NotesDriftVerdictParseError {
+    #[error("response is not valid JSON: {0}")]
+    InvalidJson(String),
+    #[error("response has no boolean fulfilled flag")]
+    UnexpectedShape,
+}
+
+/// Parse an AI notes-drift response.
+///
+/// Expects `{"fulfilled": bool, "specifics": ["..."]}`, tolerating the same
+/// fences and chatter as [`parse_child_plan`], a `drifted` flag in place of
+/// `fulfilled`, and a single string in place of the list.
+pub fn parse_notes_drift_verdict(
+    raw: &str,
+) -> std::result::Result<NotesDriftVerdict, NotesDriftVerdictParseError> {
+    let value: Value = serde_json::from_str(extract_json_payload(raw))
+        .map_err(|e| NotesDriftVerdictParseError::InvalidJson(e.to_string()))?;
+    let Value::Object(fields) = value else {
+        return Err(NotesDriftVerdictParseError::UnexpectedShape);
+    };
+    let fulfilled = fields
+        .get("fulfilled")
+        .and_then(Value::as_bool)
+        .or_else(|| fields.get("drifted").and_then(Value::as_bool).map(|d| !d))
+        .ok_or(NotesDriftVerdictParseError::UnexpectedShape)?;
+    let specifics = match fields.get("specifics") {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_owned)
+            .collect(),
+        _ => text_field(&fields, &["specifics", "explanation"])
+            .into_iter()
+            .collect(),
+    };
+
+    Ok(NotesDriftVerdict {
+        fulfilled,
+        specifics,
+    })
+}
+
+/// Why an AI arc-tag suggestion response could not be parsed.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ArcTagSuggestionParseError {
+    #[error("response is not valid JSON: {0}")]
+    InvalidJson(String),
+    #[error("response has no arcs list")]
+    UnexpectedShape,
+}
+
+/// Parse an AI arc-tag suggestion response.
+///
+/// Expects `{"arcs": [{"name": "...", "confidence": 0.8}]}`, tolerating the
+/// same fences and chatter as [`parse_child_plan`], a bare array, and plain
+/// arc-name strings. Confidence is clamped to 0.0..=1.0 and defaults to 0.5
+/// when missing.
+pub fn parse_arc_tag_suggestions(
+    raw: &str,
+) -> std::result::Result<Vec<ArcTagSuggestion>, ArcTagSuggestionParseError> {
+    let value: Value = serde_json::from_str(extract_json_payload(raw))
+        .map_err(|e| ArcTagSuggestionParseError::InvalidJson(e.to_string()))?;
+    let items = match &value {
+        Value::Array(items) => items,
+        Value::Object(fields) => match fields.get("arcs").or_else(|| fields.get("suggestions")) {
+            Some(Value::Array(items)) => items,
+            _ => return Err(ArcTagSuggestionParseError::UnexpectedShape),
+        },
+        _ => return Err(ArcTagSuggestionParseError::UnexpectedShape),
+    };
+
+    Ok(items
+        .iter()
+        .filter_map(|item| match item {
+            Value::String(name) => Some((name.trim().to_owned(), None)),
+            Value::Object(fields) => Some((
+                text_field(fields, &["name", "arc", "arc_name"])?,
+                fields.get("confidence").and_then(Value::as_f64),
+            )),
+            _ => None,
+        })
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(arc_name, confidence)| ArcTagSuggestion {
+            arc_name,
+            confidence: confidence.unwrap_or(0.5).clamp(0.0, 1.0) as f32,
+        })
+        .collect())
+}
+
+fn text_field(fields: &Map<String, Value>, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .filter_map(|key| fields.get(*key).and_then(Value::as_str))
+        .map(str::trim)
+        .find(|s| !s.is_empty())
+        .map(str::to_owned)
+}
+
+/// Recover the JSON payload from a model reply that ignored native JSON mode,
+/// e.g. one wrapped in a Markdown fence or surrounded by commentary.
+fn extract_json_payload(text: &str) -> &str {
+    let text = text.trim();
+    let fenced = text
+        .split_once("```")
+        .and_then(|(_, rest)| rest.split_once("```"))
+        .map(|(inner, _)| inner.trim_start_matches(|c: char| c.is_ascii_alphabetic()))
+        .unwrap_or(text);
+
+    let start = fenced.find(['[', '{']);
+    let end = fenced.rfind([']', '}']);
+    match (start, end) {
+        (Some(start), Some(end)) if start < end => &fenced[start..=end],
+        _ => fenced.trim(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,6 +593,55 @@ mod tests {
         assert_eq!(req.time_budget_ms, req.target_node.time_range.duration_ms());
     }
 
+    #[test]
+    fn limit_siblings_keeps_nearest_in_time_plus_target() {
+        let project = Template::MultiCam.build_project("Test");
+        let target_id = project
+            .timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .into_iter()
+            .find(|n| n.name == "B: Setup")
+            .unwrap()
+            .id;
+        let mut req = build_generate_request(&project, target_id).unwrap();
+        assert_eq!(req.siblings.len(), 3);
+
+        limit_siblings(&mut req, 2);
+
+        let names: Vec<&str> = req.siblings.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, ["B: Setup", "A: Complication", "C: Beat"]);
+    }
+
+    #[test]
+    fn build_request_includes_following_sibling_recaps() {
+        let mut project = Template::MultiCam.build_project("Test");
+        let by_name = |project: &Project, name: &str| {
+            project
+                .timeline
+                .nodes_at_level(StoryLevel::Scene)
+                .into_iter()
+                .find(|n| n.name == name)
+                .unwrap()
+                .id
+        };
+        let earlier = by_name(&project, "B: Setup");
+        let target = by_name(&project, "A: Complication");
+        let later = by_name(&project, "B: Complication");
+        for (id, recap) in [(earlier, "Earlier recap."), (later, "Later recap.")] {
+            project.timeline.node_mut(id).unwrap().content.scene_recap = Some(recap.into());
+        }
+
+        let req = build_generate_request(&project, target).unwrap();
+        let following: Vec<&str> = req
+            .surrounding_context
+            .following_recaps
+            .iter()
+            .map(|e| e.recap.as_str())
+            .collect();
+
+        assert_eq!(following, ["Later recap."]);
+    }
+
     #[test]
     fn build_request_node_not_found() {
         let project = Template::MultiCam.build_project("Test");
@@ -136,4 +649,273 @@ mod tests {
         let result = build_generate_request(&project, bogus_id);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn generation_checks_report_structured_errors() {
+        let mut project = Template::MultiCam.build_project("Test");
+        let node_id = project.timeline.nodes_at_level(StoryLevel::Scene)[0].id;
+        let node = project.timeline.node_mut(node_id).unwrap();
+        node.content.notes = "  ".into();
+        assert!(matches!(
+            ensure_generatable(node),
+            Err(Error::MissingNotes(id)) if id == node_id.0
+        ));
+        assert!(matches!(
+            ensure_continuable(node, "\n"),
+            Err(Error::NodeNotGeneratable { node_id: id, .. }) if id == node_id.0
+        ));
+
+        node.content.notes = "Mara arrives.".into();
+        assert!(ensure_generatable(node).is_ok());
+        node.locked = true;
+        assert!(matches!(
+            ensure_generatable(node),
+            Err(Error::NodeLocked(_))
+        ));
+        assert!(matches!(
+            ensure_continuable(node, "Text."),
+            Err(Error::NodeLocked(_))
+        ));
+
+        assert!(ensure_prompt_fits(100, 100).is_ok());
+        assert!(matches!(
+            ensure_prompt_fits(101, 100),
+            Err(Error::PromptTooLarge {
+                estimated: 101,
+                limit: 100
+            })
+        ));
+    }
+
+    fn names(children: &[ChildProposal]) -> Vec<&str> {
+        children.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    #[test]
+    fn parse_child_plan_recovers_common_model_outputs() {
+        let samples: [(&str, &[&str]); 10] = [
+            (r#"[{"name":"A","outline":"a","weight":1.0}]"#, &["A"]),
+            (
+                "```json\n[{\"name\":\"A\",\"outline\":\"a\",\"weight\":1}]\n```",
+                &["A"],
+            ),
+            (
+                "Here you go:\n```\n{\"beats\":[{\"name\":\"A\",\"outline\":\"a\"}]}\n```",
+                &["A"],
+            ),
+            (
+                r#"Sure! {"scenes":[{"name":"A"},{"name":"B"}]} Let me know."#,
+                &["A", "B"],
+            ),
+            (
+                r#"{"acts":[{"title":"Act 1","description":"d"}]}"#,
+                &["Act 1"],
+            ),
+            (r#"{"sequences":[{"name":"S","weight":"2.5"}]}"#, &["S"]),
+            (r#"{"children":[{"name":"C","outline":""}]}"#, &["C"]),
+            (r#"{"items":[{"name":"I","weight":null}]}"#, &["I"]),
+            (r#"{"name":"Solo","outline":"only one"}"#, &["Solo"]),
+            (
+                r#"[{"name":"X","characters":"Ana, Ben","props":["cup"]}]"#,
+                &["X"],
+            ),
+        ];
+
+        for (raw, expected) in samples {
+            let children =
+                parse_child_plan(raw, StoryLevel::Scene).unwrap_or_else(|e| panic!("{raw}: {e}"));
+            assert_eq!(names(&children), expected, "{raw}");
+        }
+    }
+
+    #[test]
+    fn parse_child_plan_coerces_fields() {
+        let raw = r#"[
+            {"name":"One","weight":"2.5","beat_type":"setup"},
+            {"name":"Two","outline":"  ","weight":-3,"beat_type":"Cold Open"},
+            {"name":"Three","characters":"Ana, Ben"}
+        ]"#;
+
+        let beats = parse_child_plan(raw, StoryLevel::Beat).unwrap();
+        assert_eq!(beats[0].weight, 2.5);
+        assert_eq!(beats[0].beat_type, Some(BeatType::Setup));
+        assert_eq!(beats[0].outline, "One");
+        assert_eq!(beats[0].level, Some(StoryLevel::Beat));
+        assert_eq!(beats[1].weight, 1.0);
+        assert_eq!(beats[1].outline, "Two");
+        assert_eq!(
+            beats[1].beat_type,
+            Some(BeatType::Custom("Cold Open".into()))
+        );
+        assert_eq!(beats[2].characters, ["Ana", "Ben"]);
+
+        let scenes = parse_child_plan(raw, StoryLevel::Scene).unwrap();
+        assert!(scenes.iter().all(|c| c.beat_type.is_none()));
+    }
+
+    #[test]
+    fn parse_child_plan_reports_missing_names() {
+        let raw = r#"[{"name":"Ok"},{"outline":"no name"},{"weight":2}]"#;
+
+        let error = parse_child_plan(raw, StoryLevel::Scene).unwrap_err();
+
+        assert_eq!(
+            error,
+            ChildPlanParseError::MissingFields(vec![
+                MissingChildFields {
+                    index: 1,
+                    fields: vec!["name"],
+                },
+                MissingChildFields {
+                    index: 2,
+                    fields: vec!["name"],
+                },
+            ])
+        );
+        assert!(error.to_string().contains("child 1 (name)"));
+    }
+
+    #[test]
+    fn parse_child_plan_rejects_unrecoverable_output() {
+        assert!(matches!(
+            parse_child_plan("I can't do that.", StoryLevel::Scene),
+            Err(ChildPlanParseError::InvalidJson(_))
+        ));
+        assert!(matches!(
+            parse_child_plan("[]", StoryLevel::Scene),
+            Err(ChildPlanParseError::Empty)
+        ));
+        assert!(matches!(
+            parse_child_plan(r#"["just a string"]"#, StoryLevel::Scene),
+            Err(ChildPlanParseError::UnexpectedShape)
+        ));
+    }
+
+    #[test]
+    fn parse_premise_expansion_fills_arc_slots_in_order() {
+        let raw = r#"```json
+{"logline":"A keeper fights to save her lighthouse.",
+ "arcs":[{"name":"Save the Light","description":"Mara fights the sale."},
+         {"title":"Jonah Returns"},
+         {"name":""},
+         {"name":"Gull Wars","summary":"The gulls escalate."},
+         {"name":"Extra"}],
+ "characters":[{"name":"Mara","role":"The keeper"},{"description":"nameless"}]}
+```"#;
+
+        let expansion = parse_premise_expansion(raw).unwrap();
+
+        assert_eq!(expansion.logline, "A keeper fights to save her lighthouse.");
+        let arcs: Vec<_> = expansion
+            .arcs
+            .iter()
+            .map(|arc| (arc.name.as_str(), arc.arc_type.clone(), arc.color))
+            .collect();
+        assert_eq!(
+            arcs,
+            vec![
+                ("Save the Light", ArcType::APlot, Color::A_PLOT),
+                ("Jonah Returns", ArcType::BPlot, Color::B_PLOT),
+                ("Gull Wars", ArcType::CRunner, Color::C_RUNNER),
+            ]
+        );
+        assert_eq!(expansion.arcs[0].description, "Mara fights the sale.");
+        assert_eq!(
+            expansion.characters,
+            vec![CharacterProposal {
+                name: "Mara".into(),
+                description: "The keeper".into(),
+            }]
+        );
+        assert_eq!(
+            parse_premise_expansion(r#"{"arcs":[]}"#).unwrap_err(),
+            PremiseExpansionParseError::Empty
+        );
+    }
+
+    #[test]
+    fn parse_continuity_verdict_reads_flag_and_explanation() {
+        let verdict = parse_continuity_verdict(
+            "Here you go:\n```json\n{\"contradicts\": true, \"reason\": \"Mara is on the mainland.\"}\n```",
+        )
+        .unwrap();
+
+        assert_eq!(
+            verdict,
+            ContinuityVerdict {
+                contradiction: true,
+                explanation: "Mara is on the mainland.".into(),
+            }
+        );
+        assert!(
+            !parse_continuity_verdict(r#"{"contradiction": false}"#)
+                .unwrap()
+                .contradiction
+        );
+        assert_eq!(
+            parse_continuity_verdict(r#"{"explanation": "unsure"}"#).unwrap_err(),
+            ContinuityVerdictParseError::UnexpectedShape
+        );
+    }
+
+    #[test]
+    fn parse_notes_drift_verdict_reads_flag_and_specifics() {
+        let verdict = parse_notes_drift_verdict(
+            "```json\n{\"fulfilled\": false, \"specifics\": [\"Jake never finds the locket.\", \"\"]}\n```",
+        )
+        .unwrap();
+
+        assert_eq!(
+            verdict,
+            NotesDriftVerdict {
+                fulfilled: false,
+                specifics: vec!["Jake never finds the locket.".into()],
+            }
+        );
+        assert_eq!(
+            parse_notes_drift_verdict(r#"{"drifted": true, "explanation": "No chase."}"#).unwrap(),
+            NotesDriftVerdict {
+                fulfilled: false,
+                specifics: vec!["No chase.".into()],
+            }
+        );
+        assert_eq!(
+            parse_notes_drift_verdict(r#"{"specifics": []}"#).unwrap_err(),
+            NotesDriftVerdictParseError::UnexpectedShape
+        );
+    }
+
+    #[test]
+    fn parse_arc_tag_suggestions_reads_names_and_clamps_confidence() {
+        let suggestions = parse_arc_tag_suggestions(
+            "Here you go:\n{\"arcs\": [{\"name\": \"The Heist\", \"confidence\": 1.4}, \
+             {\"arc\": \"Amy's Guilt\"}, {\"confidence\": 0.9}]}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            suggestions,
+            vec![
+                ArcTagSuggestion {
+                    arc_name: "The Heist".into(),
+                    confidence: 1.0,
+                },
+                ArcTagSuggestion {
+                    arc_name: "Amy's Guilt".into(),
+                    confidence: 0.5,
+                },
+            ]
+        );
+        assert_eq!(
+            parse_arc_tag_suggestions(r#"["B Plot", ""]"#).unwrap(),
+            vec![ArcTagSuggestion {
+                arc_name: "B Plot".into(),
+                confidence: 0.5,
+            }]
+        );
+        assert_eq!(
+            parse_arc_tag_suggestions(r#"{"verdict": "none"}"#).unwrap_err(),
+            ArcTagSuggestionParseError::UnexpectedShape
+        );
+    }
 }
@@ -18,7 +18,7 @@ pub struct AffectValueId(pub Uuid);
 
 impl AffectValueId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
@@ -33,7 +33,7 @@ pub struct AffectDependencyId(pub Uuid);
 
 impl AffectDependencyId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
@@ -18,7 +18,7 @@ pub struct AgentRunId(pub Uuid);
 
 impl AgentRunId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
@@ -33,7 +33,7 @@ pub struct AgentToolCallId(pub Uuid);
 
 impl AgentToolCallId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
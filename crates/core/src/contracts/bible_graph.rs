@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 
 use super::FieldValue;
+use crate::timeline::node::NodeId;
+use crate::timeline::timing::TimeRange;
 
 macro_rules! non_empty_string_id {
     ($name:ident) => {
@@ -304,6 +306,9 @@ pub struct SetBibleGraphSnapshotFieldCommand {
     pub label: String,
     #[serde(default)]
     pub snapshot_sort_order: u32,
+    /// Timeline node the snapshot time was taken from, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_node_id: Option<NodeId>,
     pub field_id: BibleGraphSnapshotFieldId,
     pub part_key: BibleGraphPartKey,
     pub part_name: String,
@@ -372,6 +377,7 @@ impl SetBibleGraphSnapshotFieldCommand {
             at_ms: self.at_ms,
             label: self.label.clone(),
             sort_order: self.snapshot_sort_order,
+            source_node_id: self.source_node_id,
         }
     }
 
@@ -457,6 +463,10 @@ pub struct BibleGraphSnapshot {
     pub label: String,
     #[serde(default)]
     pub sort_order: u32,
+    /// Timeline node the snapshot is anchored to; moving that node can
+    /// re-anchor `at_ms` to the node's new midpoint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_node_id: Option<NodeId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -499,12 +509,55 @@ pub struct BibleNodeDetailProjection {
     pub snapshots: Vec<BibleGraphSnapshotProjection>,
 }
 
+/// When an entity appears on the timeline: the merged spans of the timeline
+/// nodes that reference it, with its snapshots as point markers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BibleEntityPresenceProjection {
+    pub node: BibleGraphNode,
+    /// Contiguous, non-touching spans in time order.
+    #[serde(default)]
+    pub intervals: Vec<TimeRange>,
+    /// Oldest first.
+    #[serde(default)]
+    pub snapshots: Vec<BibleGraphSnapshot>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BibleGraphNodeListProjection {
     #[serde(default)]
     pub nodes: Vec<BibleGraphNode>,
 }
 
+/// The entity state in effect at `at_ms`: every snapshot field recorded at or
+/// before that time, later snapshots overriding earlier values of the same
+/// part field. Fields whose latest value is cleared are omitted.
+pub fn resolved_state_at(
+    snapshots: &[BibleGraphSnapshotProjection],
+    at_ms: u64,
+) -> Vec<BibleGraphSnapshotField> {
+    let mut ordered: Vec<_> = snapshots
+        .iter()
+        .filter(|projection| projection.snapshot.at_ms <= at_ms)
+        .collect();
+    ordered.sort_by_key(|projection| (projection.snapshot.at_ms, projection.snapshot.sort_order));
+
+    let mut resolved: Vec<BibleGraphSnapshotField> = Vec::new();
+    for field in ordered
+        .into_iter()
+        .flat_map(|projection| &projection.fields)
+    {
+        let existing = resolved.iter().position(|current| {
+            current.part_key == field.part_key && current.field_key == field.field_key
+        });
+        match existing {
+            Some(index) => resolved[index] = field.clone(),
+            None => resolved.push(field.clone()),
+        }
+    }
+    resolved.retain(|field| field.value.is_some());
+    resolved
+}
+
 pub fn canonical_bible_root_nodes() -> Vec<BibleGraphNode> {
     [
         CanonicalBibleRoot::Characters,
@@ -613,6 +666,7 @@ mod tests {
                     at_ms: 12_000,
                     label: "Sequence 1 state".to_string(),
                     sort_order: 0,
+                    source_node_id: None,
                 },
                 fields: vec![BibleGraphSnapshotField {
                     id: BibleGraphSnapshotFieldId::new("snapshot-field.beach.weather.current")
@@ -687,6 +741,7 @@ mod tests {
             at_ms: 12_000,
             label: "Sequence 1 state".to_string(),
             snapshot_sort_order: 3,
+            source_node_id: None,
             field_id: BibleGraphSnapshotFieldId::new("snapshot-field.place.beach.weather.current")
                 .unwrap(),
             part_key: BibleGraphPartKey::new("weather").unwrap(),
@@ -755,4 +810,60 @@ mod tests {
 
         assert_eq!(round_trip, projection);
     }
+
+    #[test]
+    fn resolved_state_at_layers_snapshots_up_to_the_time() {
+        let snapshot =
+            |id: &str, at_ms, fields: Vec<(&str, Option<&str>)>| BibleGraphSnapshotProjection {
+                snapshot: BibleGraphSnapshot {
+                    id: BibleGraphSnapshotId::new(id).unwrap(),
+                    node_id: BibleGraphNodeId::new("node.character.mara").unwrap(),
+                    at_ms,
+                    label: id.to_string(),
+                    sort_order: 0,
+                    source_node_id: None,
+                },
+                fields: fields
+                    .into_iter()
+                    .map(|(key, value)| BibleGraphSnapshotField {
+                        id: BibleGraphSnapshotFieldId::new(format!("{id}.{key}")).unwrap(),
+                        snapshot_id: BibleGraphSnapshotId::new(id).unwrap(),
+                        part_key: BibleGraphPartKey::new("state").unwrap(),
+                        part_name: "State".to_string(),
+                        field_key: BibleGraphFieldKey::new(key).unwrap(),
+                        value: value.map(|text| FieldValue::Text(text.to_string())),
+                        sort_order: 0,
+                    })
+                    .collect(),
+            };
+        let snapshots = vec![
+            snapshot("late", 90_000, vec![("location", Some("lighthouse"))]),
+            snapshot(
+                "early",
+                10_000,
+                vec![("location", Some("harbor")), ("mood", Some("hopeful"))],
+            ),
+            snapshot("middle", 50_000, vec![("mood", None)]),
+        ];
+        let values = |at_ms| {
+            resolved_state_at(&snapshots, at_ms)
+                .into_iter()
+                .map(|field| (field.field_key.as_str().to_string(), field.value))
+                .collect::<Vec<_>>()
+        };
+        let text = |value: &str| Some(FieldValue::Text(value.to_string()));
+
+        assert!(values(5_000).is_empty());
+        assert_eq!(
+            values(20_000),
+            vec![
+                ("location".to_string(), text("harbor")),
+                ("mood".to_string(), text("hopeful")),
+            ]
+        );
+        assert_eq!(
+            values(90_000),
+            vec![("location".to_string(), text("lighthouse"))]
+        );
+    }
 }
@@ -10,7 +10,7 @@ pub struct ContextEvaluationId(pub Uuid);
 
 impl ContextEvaluationId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
@@ -25,7 +25,7 @@ pub struct ContextInfluenceId(pub Uuid);
 
 impl ContextInfluenceId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
@@ -15,6 +15,8 @@ mod semantic_dependency;
 mod semantic_proposal;
 mod story_arc;
 mod timeline_command;
+mod timeline_context;
+mod timeline_overview;
 mod timeline_render;
 
 use serde::{Deserialize, Serialize};
@@ -42,16 +44,17 @@ pub use ai_context::{
 pub use bible_graph::{
     BIBLE_GRAPH_NODE_TEXT_FIELD_KEY, BIBLE_GRAPH_NODE_TEXT_FIELD_SORT_ORDER,
     BIBLE_GRAPH_NODE_TEXT_PART_KEY, BIBLE_GRAPH_NODE_TEXT_PART_NAME,
-    BIBLE_GRAPH_NODE_TEXT_PART_SORT_ORDER, BibleGraphCategoryVisualStyle, BibleGraphContractError,
-    BibleGraphEdge, BibleGraphEdgeId, BibleGraphEdgeKind, BibleGraphField, BibleGraphFieldId,
-    BibleGraphFieldKey, BibleGraphNode, BibleGraphNodeCategory, BibleGraphNodeId,
-    BibleGraphNodeListProjection, BibleGraphPart, BibleGraphPartId, BibleGraphPartKey,
-    BibleGraphPartProjection, BibleGraphSchemaKey, BibleGraphSnapshot, BibleGraphSnapshotField,
-    BibleGraphSnapshotFieldId, BibleGraphSnapshotId, BibleGraphSnapshotProjection,
-    BibleNodeDetailProjection, CanonicalBibleRoot, CreateBibleGraphNodeCommand,
-    DeleteBibleGraphEdgeCommand, DeleteBibleGraphNodeCommand, EnsureCanonicalBibleRootsCommand,
-    SetBibleGraphEdgeCommand, SetBibleGraphFieldCommand, SetBibleGraphNodeNameCommand,
-    SetBibleGraphNodeTextCommand, SetBibleGraphSnapshotFieldCommand, canonical_bible_root_nodes,
+    BIBLE_GRAPH_NODE_TEXT_PART_SORT_ORDER, BibleEntityPresenceProjection,
+    BibleGraphCategoryVisualStyle, BibleGraphContractError, BibleGraphEdge, BibleGraphEdgeId,
+    BibleGraphEdgeKind, BibleGraphField, BibleGraphFieldId, BibleGraphFieldKey, BibleGraphNode,
+    BibleGraphNodeCategory, BibleGraphNodeId, BibleGraphNodeListProjection, BibleGraphPart,
+    BibleGraphPartId, BibleGraphPartKey, BibleGraphPartProjection, BibleGraphSchemaKey,
+    BibleGraphSnapshot, BibleGraphSnapshotField, BibleGraphSnapshotFieldId, BibleGraphSnapshotId,
+    BibleGraphSnapshotProjection, BibleNodeDetailProjection, CanonicalBibleRoot,
+    CreateBibleGraphNodeCommand, DeleteBibleGraphEdgeCommand, DeleteBibleGraphNodeCommand,
+    EnsureCanonicalBibleRootsCommand, SetBibleGraphEdgeCommand, SetBibleGraphFieldCommand,
+    SetBibleGraphNodeNameCommand, SetBibleGraphNodeTextCommand, SetBibleGraphSnapshotFieldCommand,
+    canonical_bible_root_nodes, resolved_state_at,
 };
 pub use bible_graph_defaults::{
     BUILTIN_BIBLE_GRAPH_SCHEMAS, BibleGraphCategoryProjection, BibleGraphFieldDefault,
@@ -105,15 +108,23 @@ pub use semantic_proposal::{
     SemanticProposalStatus,
 };
 pub use story_arc::{
-    CreateStoryArcCommand, DeleteStoryArcCommand, SetStoryArcMetadataCommand,
-    StoryArcListProjection, StoryArcProgressionProjection,
+    ArcCoverageProjection, ArcEntityEvolution, ArcEntityEvolutionProjection, CreateStoryArcCommand,
+    DeleteStoryArcCommand, SetStoryArcMetadataCommand, StoryArcListProjection,
+    StoryArcProgressionProjection,
 };
 pub use timeline_command::{
-    ApplyTimelineChildCommand, ApplyTimelineChildrenCommand, CreateTimelineChildFromParentCommand,
-    CreateTimelineNodeCommand, CreateTimelineRelationshipCommand, DeleteTimelineNodeCommand,
-    DeleteTimelineRelationshipCommand, SetTimelineNodeLockCommand, SetTimelineNodeNotesCommand,
-    SetTimelineNodeRangeCommand, SplitTimelineNodeCommand,
+    ApplyTimelineChildCommand, ApplyTimelineChildPreview, ApplyTimelineChildrenCommand,
+    ApplyTimelineChildrenPreview, ApplyTimelineReferencePreview,
+    CreateTimelineChildFromParentCommand, CreateTimelineNodeCommand,
+    CreateTimelineRelationshipCommand, DeleteTimelineNodeCommand,
+    DeleteTimelineRelationshipCommand, DuplicateTimelineNodeCommand, NormalizeTimelineLevelCommand,
+    RenumberTimelineScenesCommand, SetTimelineNodeArcTagCommand,
+    SetTimelineNodeGenerationOrderCommand, SetTimelineNodeLockCommand, SetTimelineNodeNotesCommand,
+    SetTimelineNodePinnedCommand, SetTimelineNodeRangeCommand, SetTimelineNodeSceneNumberCommand,
+    SplitTimelineNodeCommand,
 };
+pub use timeline_context::{TimelineContextAtProjection, TimelineContextEntity};
+pub use timeline_overview::{TimelineOverviewBucket, TimelineOverviewProjection};
 pub use timeline_render::{
     TimelineRenderAffectSample, TimelineRenderClip, TimelineRenderGap, TimelineRenderProjection,
     TimelineRenderRelationship, TimelineRenderStructureSegment, TimelineRenderTrack,
@@ -124,7 +135,7 @@ pub struct CommandId(pub Uuid);
 
 impl CommandId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
@@ -139,7 +150,7 @@ pub struct ChangeEventId(pub Uuid);
 
 impl ChangeEventId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
@@ -154,7 +165,7 @@ pub struct ObjectRevisionId(pub Uuid);
 
 impl ObjectRevisionId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
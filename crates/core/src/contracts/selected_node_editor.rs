@@ -35,6 +35,9 @@ pub struct SelectedNodeEditorNode {
     #[serde(default)]
     pub notes: String,
     pub content_status: ContentStatus,
+    /// The notes changed since the content was generated.
+    #[serde(default)]
+    pub stale: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub beat_type: Option<BeatType>,
     #[serde(default)]
@@ -135,6 +138,7 @@ impl From<&StoryNode> for SelectedNodeEditorNode {
             name: node.name.clone(),
             notes: node.content.notes.clone(),
             content_status: node.content.status,
+            stale: node.content.notes_stale(),
             beat_type: node.beat_type.clone(),
             locked: node.locked,
         }
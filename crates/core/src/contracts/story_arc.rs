@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::contracts::{BibleGraphNode, BibleGraphSnapshotProjection};
 use crate::story::arc::{ArcId, ArcType, Color, StoryArc};
 use crate::story::progression::ArcProgression;
+use crate::timeline::timing::TimeRange;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoryArcListProjection {
@@ -27,6 +29,37 @@ impl StoryArcProgressionProjection {
     }
 }
 
+/// How the bible entities an arc's nodes reference develop while the arc is
+/// active.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArcEntityEvolutionProjection {
+    pub arc_id: ArcId,
+    /// See [`Timeline::arc_time_window`](crate::timeline::Timeline::arc_time_window).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<TimeRange>,
+    #[serde(default)]
+    pub entities: Vec<ArcEntityEvolution>,
+}
+
+/// Where an arc is present on the timeline and where it drops out; see
+/// [`Timeline::arc_coverage_gaps`](crate::timeline::Timeline::arc_coverage_gaps).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArcCoverageProjection {
+    pub arc_id: ArcId,
+    #[serde(default)]
+    pub coverage: Vec<TimeRange>,
+    #[serde(default)]
+    pub gaps: Vec<TimeRange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArcEntityEvolution {
+    pub node: BibleGraphNode,
+    /// Snapshots inside the arc's window, oldest first.
+    #[serde(default)]
+    pub snapshots: Vec<BibleGraphSnapshotProjection>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CreateStoryArcCommand {
     pub arc_id: ArcId,
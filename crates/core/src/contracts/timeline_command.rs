@@ -1,14 +1,21 @@
 use serde::{Deserialize, Serialize};
 
+use super::{BibleGraphNodeId, BibleReferenceKind};
 use crate::ai::backend::ChildPlanId;
+use crate::story::arc::ArcId;
 use crate::timeline::node::{BeatType, NodeId, StoryLevel};
 use crate::timeline::relationship::{RelationshipId, RelationshipType};
+use crate::timeline::scene_numbers::SceneNumbering;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SetTimelineNodeRangeCommand {
     pub node_id: NodeId,
     pub start_ms: u64,
     pub end_ms: u64,
+    /// Move bible snapshots anchored to the resized nodes to their new
+    /// midpoints.
+    #[serde(default)]
+    pub reanchor_snapshots: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -19,6 +26,19 @@ pub struct SplitTimelineNodeCommand {
     pub right_node_id: NodeId,
 }
 
+/// Copy a node, optionally with its whole subtree, shifted by `offset_ms`.
+/// Copy ids are derived from the command id, so replays are idempotent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateTimelineNodeCommand {
+    pub node_id: NodeId,
+    #[serde(default)]
+    pub recursive: bool,
+    /// Shift applied to the copy's start and end. Defaults to the node's
+    /// duration, placing the copy immediately after the original.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset_ms: Option<i64>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DeleteTimelineNodeCommand {
     pub node_id: NodeId,
@@ -30,6 +50,59 @@ pub struct SetTimelineNodeLockCommand {
     pub locked: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetTimelineNodePinnedCommand {
+    pub node_id: NodeId,
+    pub pinned: bool,
+}
+
+/// Set or clear (`None` or blank) a scene's production number.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetTimelineNodeSceneNumberCommand {
+    pub node_id: NodeId,
+    #[serde(default)]
+    pub scene_number: Option<String>,
+}
+
+/// Add or remove an arc tag on a node, or with `recursive` on the node and
+/// all its descendants. Nodes already in the requested state are skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetTimelineNodeArcTagCommand {
+    pub node_id: NodeId,
+    pub arc_id: ArcId,
+    pub tagged: bool,
+    #[serde(default)]
+    pub recursive: bool,
+}
+
+/// Number every scene in time order; see [`SceneNumbering`] for the modes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenumberTimelineScenesCommand {
+    #[serde(default)]
+    pub mode: SceneNumbering,
+}
+
+/// Close small gaps and overlaps between the `level` children of
+/// `parent_id`, or the parentless `level` nodes when it is `None`; see
+/// [`crate::timeline::Timeline::normalize_level`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NormalizeTimelineLevelCommand {
+    pub level: StoryLevel,
+    #[serde(default)]
+    pub parent_id: Option<NodeId>,
+}
+
+/// Set how batch generation orders a node among its siblings. Rejected when
+/// `depends_on` would form a cycle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetTimelineNodeGenerationOrderCommand {
+    pub node_id: NodeId,
+    #[serde(default)]
+    pub generation_priority: Option<i32>,
+    #[serde(default)]
+    pub depends_on: Vec<NodeId>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SetTimelineNodeNotesCommand {
     pub node_id: NodeId,
@@ -59,6 +132,10 @@ pub struct ApplyTimelineChildrenCommand {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub child_plan_id: Option<ChildPlanId>,
     pub children: Vec<ApplyTimelineChildCommand>,
+    /// Keep existing children and place the new ones in the time they leave
+    /// uncovered, instead of replacing them.
+    #[serde(default)]
+    pub preserve_existing: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -76,6 +153,36 @@ pub struct ApplyTimelineChildCommand {
     pub props: Vec<String>,
 }
 
+/// What applying a child plan would do, computed without writing anything.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApplyTimelineChildrenPreview {
+    pub children: Vec<ApplyTimelineChildPreview>,
+    /// Existing nodes a replacing apply would delete.
+    #[serde(default)]
+    pub removed_node_ids: Vec<NodeId>,
+    #[serde(default)]
+    pub bible_references: Vec<ApplyTimelineReferencePreview>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApplyTimelineChildPreview {
+    pub node_id: NodeId,
+    pub name: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// A bible reference proposal the apply would raise, and the existing entity
+/// accepting it would link to. `None` means accepting creates a new entity.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApplyTimelineReferencePreview {
+    pub source_node_id: NodeId,
+    pub reference_kind: BibleReferenceKind,
+    pub reference_text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub existing_node_id: Option<BibleGraphNodeId>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CreateTimelineRelationshipCommand {
     pub relationship_id: RelationshipId,
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+use super::{BibleGraphNodeId, BibleGraphSnapshotField};
+use crate::story::arc::StoryArc;
+use crate::timeline::Timeline;
+use crate::timeline::node::{StoryLevel, StoryNode};
+
+/// Everything in effect at one instant, for a scrubbing playhead: the nodes
+/// under it at every level, the arcs tagged on those nodes, and each bible
+/// entity's resolved snapshot state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineContextAtProjection {
+    pub at_ms: u64,
+    /// Active nodes, top level first.
+    #[serde(default)]
+    pub nodes: Vec<StoryNode>,
+    #[serde(default)]
+    pub arcs: Vec<StoryArc>,
+    #[serde(default)]
+    pub entities: Vec<TimelineContextEntity>,
+}
+
+/// A bible entity with its state resolved at the projection's instant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineContextEntity {
+    pub node_id: BibleGraphNodeId,
+    pub name: String,
+    #[serde(default)]
+    pub fields: Vec<BibleGraphSnapshotField>,
+}
+
+impl TimelineContextAtProjection {
+    /// Active nodes and their arcs, in `arcs` order, at `at_ms`.
+    pub fn new(
+        timeline: &Timeline,
+        arcs: &[StoryArc],
+        at_ms: u64,
+        entities: Vec<TimelineContextEntity>,
+    ) -> Self {
+        let nodes: Vec<StoryNode> = StoryLevel::all()
+            .iter()
+            .flat_map(|level| timeline.nodes_at(*level, at_ms))
+            .cloned()
+            .collect();
+        let tagged: Vec<_> = nodes
+            .iter()
+            .flat_map(|node| timeline.arcs_for_node(node.id))
+            .collect();
+        Self {
+            at_ms,
+            nodes,
+            arcs: arcs
+                .iter()
+                .filter(|arc| tagged.contains(&arc.id))
+                .cloned()
+                .collect(),
+            entities,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Template;
+
+    #[test]
+    fn context_at_collects_active_nodes_and_their_arcs() {
+        let project = Template::MultiCam.build_project("Context Test");
+        let scene = project.timeline.nodes_at_level(StoryLevel::Scene)[0];
+        let at_ms = scene.time_range.start_ms;
+
+        let context =
+            TimelineContextAtProjection::new(&project.timeline, &project.arcs, at_ms, Vec::new());
+
+        assert!(context.nodes.iter().any(|node| node.id == scene.id));
+        assert!(
+            context
+                .nodes
+                .iter()
+                .all(|node| node.time_range.contains(at_ms))
+        );
+        assert!(
+            context
+                .nodes
+                .windows(2)
+                .all(|pair| pair[0].level <= pair[1].level)
+        );
+        let expected: Vec<_> = project
+            .arcs
+            .iter()
+            .filter(|arc| {
+                context
+                    .nodes
+                    .iter()
+                    .any(|node| project.timeline.arcs_for_node(node.id).contains(&arc.id))
+            })
+            .map(|arc| arc.id)
+            .collect();
+        let actual: Vec<_> = context.arcs.iter().map(|arc| arc.id).collect();
+        assert_eq!(actual, expected);
+
+        let json = serde_json::to_value(&context).unwrap();
+        let decoded: TimelineContextAtProjection = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), json);
+    }
+}
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+
+use crate::story::arc::{ArcId, Color, StoryArc};
+use crate::timeline::Timeline;
+use crate::timeline::node::StoryLevel;
+
+/// The whole episode at one level, summarized into equal time buckets for a
+/// zoomed-out minimap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineOverviewProjection {
+    pub level: StoryLevel,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub buckets: Vec<TimelineOverviewBucket>,
+}
+
+/// Nodes at the projection's level overlapping one time bucket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimelineOverviewBucket {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// Nodes overlapping the bucket; a long node counts in every bucket it
+    /// spans.
+    pub node_count: usize,
+    /// The arc tagged on the most overlapping time in the bucket.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dominant_arc_id: Option<ArcId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dominant_arc_color: Option<Color>,
+    /// Content words, with each node's words split across buckets by how
+    /// much of the node falls in each.
+    pub word_count: usize,
+}
+
+impl TimelineOverviewProjection {
+    /// Split the timeline's duration into `bucket_count` buckets and
+    /// summarize the nodes at `level` in each. Arc ties go to the arc listed
+    /// first in `arcs`; arcs missing from `arcs` are ignored.
+    pub fn new(
+        timeline: &Timeline,
+        arcs: &[StoryArc],
+        level: StoryLevel,
+        bucket_count: usize,
+    ) -> Self {
+        let duration_ms = timeline.total_duration_ms;
+        let nodes = timeline.nodes_at_level(level);
+        let boundary = |index: usize| {
+            (u128::from(duration_ms) * index as u128 / bucket_count.max(1) as u128) as u64
+        };
+
+        let buckets = (0..bucket_count)
+            .map(|index| {
+                let (start_ms, end_ms) = (boundary(index), boundary(index + 1));
+                let mut node_count = 0;
+                let mut words = 0.0;
+                let mut arc_time = vec![0u64; arcs.len()];
+                for node in &nodes {
+                    let overlap_ms = node
+                        .time_range
+                        .end_ms
+                        .min(end_ms)
+                        .saturating_sub(node.time_range.start_ms.max(start_ms));
+                    if overlap_ms == 0 {
+                        continue;
+                    }
+                    node_count += 1;
+                    let node_words = node.content.content.split_whitespace().count();
+                    words += node_words as f64 * overlap_ms as f64
+                        / node.time_range.duration_ms() as f64;
+                    for arc_id in timeline.arcs_for_node(node.id) {
+                        if let Some(position) = arcs.iter().position(|arc| arc.id == arc_id) {
+                            arc_time[position] += overlap_ms;
+                        }
+                    }
+                }
+                let dominant = arc_time
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, time)| **time > 0)
+                    .max_by(|(a_index, a_time), (b_index, b_time)| {
+                        a_time.cmp(b_time).then(b_index.cmp(a_index))
+                    })
+                    .map(|(position, _)| &arcs[position]);
+                TimelineOverviewBucket {
+                    start_ms,
+                    end_ms,
+                    node_count,
+                    dominant_arc_id: dominant.map(|arc| arc.id),
+                    dominant_arc_color: dominant.map(|arc| arc.color),
+                    word_count: words.round() as usize,
+                }
+            })
+            .collect();
+
+        Self {
+            level,
+            duration_ms,
+            buckets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Template;
+
+    #[test]
+    fn overview_buckets_cover_the_duration_and_summarize_nodes() {
+        let mut project = Template::MultiCam.build_project("Overview Test");
+        let scene = project.timeline.nodes_at_level(StoryLevel::Scene)[0].clone();
+        project.timeline.node_mut(scene.id).unwrap().content.content =
+            "one two three four".to_string();
+        let arc = project.arcs[0].clone();
+        project.timeline.tag_node(scene.id, arc.id);
+        let bucket_count = 4;
+
+        let overview = TimelineOverviewProjection::new(
+            &project.timeline,
+            &project.arcs,
+            StoryLevel::Scene,
+            bucket_count,
+        );
+
+        assert_eq!(overview.buckets.len(), bucket_count);
+        assert_eq!(overview.buckets[0].start_ms, 0);
+        assert_eq!(
+            overview.buckets.last().unwrap().end_ms,
+            project.timeline.total_duration_ms
+        );
+        assert!(
+            overview
+                .buckets
+                .windows(2)
+                .all(|pair| pair[0].end_ms == pair[1].start_ms)
+        );
+        let first = &overview.buckets[0];
+        assert!(first.node_count > 0);
+        assert_eq!(first.dominant_arc_id, Some(arc.id));
+        assert_eq!(first.dominant_arc_color, Some(arc.color));
+        let total_words: usize = overview
+            .buckets
+            .iter()
+            .map(|bucket| bucket.word_count)
+            .sum();
+        assert_eq!(total_words, 4);
+    }
+}
@@ -18,6 +18,9 @@ pub enum Error {
     #[error("relationship not found: {0}")]
     RelationshipNotFound(Uuid),
 
+    #[error("episode not found: {0}")]
+    EpisodeNotFound(Uuid),
+
     #[error("time range is invalid (start {start_ms}ms >= end {end_ms}ms)")]
     InvalidTimeRange { start_ms: u64, end_ms: u64 },
 
@@ -47,7 +50,22 @@ pub enum Error {
     NodeLocked(Uuid),
 
     #[error("node has no notes to generate from: {0}")]
-    NoNotes(Uuid),
+    MissingNotes(Uuid),
+
+    #[error("node cannot be generated ({reason}): {node_id}")]
+    NodeNotGeneratable { node_id: Uuid, reason: String },
+
+    #[error("prompt is ~{estimated} tokens, over the {limit}-token context limit")]
+    PromptTooLarge { estimated: usize, limit: usize },
+
+    #[error("illegal content status transition: {from:?} -> {to:?}")]
+    InvalidStatusTransition {
+        from: crate::timeline::node::ContentStatus,
+        to: crate::timeline::node::ContentStatus,
+    },
+
+    #[error("invalid scene number: {0}")]
+    InvalidSceneNumber(String),
 
     #[error("invalid operation: {0}")]
     InvalidOperation(String),
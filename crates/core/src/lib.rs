@@ -1,10 +1,12 @@
 pub mod ai;
 pub mod contracts;
 pub mod error;
+pub mod id;
 pub mod project;
 pub mod reference;
 pub mod script;
 pub mod story;
+pub mod text;
 pub mod timeline;
 
 mod template;
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+/// Per-project AI overrides layered over the server's global AI config, so
+/// each show can keep its own model and voice. Unset fields use the global.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProjectAiSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Added to every generation prompt, e.g. "dry, deadpan humor".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style_notes: Option<String>,
+    /// Replaces the built-in screenwriter/story-editor system prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+}
+
+impl ProjectAiSettings {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Trim text fields and drop blank ones, so clearing a field in a form
+    /// falls back to the global setting.
+    pub fn normalized(self) -> Self {
+        let clean = |value: Option<String>| {
+            value
+                .map(|text| text.trim().to_string())
+                .filter(|text| !text.is_empty())
+        };
+        Self {
+            model: clean(self.model),
+            temperature: self.temperature,
+            style_notes: clean(self.style_notes),
+            system_prompt: clean(self.system_prompt),
+        }
+    }
+}
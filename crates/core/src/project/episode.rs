@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::Project;
+use crate::error::{Error, Result};
+use crate::story::arc::StoryArc;
+use crate::timeline::Timeline;
+use crate::timeline::node::StoryLevel;
+
+/// Unique identifier for one episode of a project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EpisodeId(pub Uuid);
+
+impl EpisodeId {
+    pub fn new() -> Self {
+        Self(crate::id::new_uuid())
+    }
+}
+
+impl Default for EpisodeId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Identity and ordering of an episode, kept apart from its timeline so the
+/// active episode can carry it alongside [`Project::timeline`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpisodeHeader {
+    pub id: EpisodeId,
+    pub title: String,
+    pub sort_order: u32,
+}
+
+impl Default for EpisodeHeader {
+    fn default() -> Self {
+        Self {
+            id: EpisodeId::new(),
+            title: "Episode 1".to_string(),
+            sort_order: 0,
+        }
+    }
+}
+
+/// An episode that is not being edited, parked with its whole timeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Episode {
+    #[serde(flatten)]
+    pub header: EpisodeHeader,
+    pub timeline: Timeline,
+}
+
+/// One row of the episode list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpisodeSummary {
+    #[serde(flatten)]
+    pub header: EpisodeHeader,
+    pub active: bool,
+    pub scene_count: usize,
+    pub total_duration_ms: u64,
+}
+
+impl EpisodeSummary {
+    fn new(header: &EpisodeHeader, timeline: &Timeline, active: bool) -> Self {
+        Self {
+            header: header.clone(),
+            active,
+            scene_count: timeline.nodes_at_level(StoryLevel::Scene).len(),
+            total_duration_ms: timeline.total_duration_ms,
+        }
+    }
+}
+
+impl Project {
+    /// Every episode, active one included, in sort order.
+    pub fn episode_summaries(&self) -> Vec<EpisodeSummary> {
+        let mut summaries: Vec<EpisodeSummary> = std::iter::once(EpisodeSummary::new(
+            &self.active_episode,
+            &self.timeline,
+            true,
+        ))
+        .chain(
+            self.episodes
+                .iter()
+                .map(|episode| EpisodeSummary::new(&episode.header, &episode.timeline, false)),
+        )
+        .collect();
+        summaries.sort_by_key(|summary| summary.header.sort_order);
+        summaries
+    }
+
+    /// Park `timeline` as a new last episode. Its arc tags are moved onto the
+    /// project arc of the same type from `timeline_arcs` (the arcs it was built
+    /// with); tags with no such project arc are dropped.
+    pub fn add_episode(
+        &mut self,
+        title: impl Into<String>,
+        mut timeline: Timeline,
+        timeline_arcs: &[StoryArc],
+    ) -> EpisodeId {
+        timeline.node_arcs.retain_mut(|node_arc| {
+            let project_arc = timeline_arcs
+                .iter()
+                .find(|arc| arc.id == node_arc.arc_id)
+                .and_then(|source| self.arcs.iter().find(|arc| arc.arc_type == source.arc_type));
+            match project_arc {
+                Some(arc) => {
+                    node_arc.arc_id = arc.id;
+                    true
+                }
+                None => false,
+            }
+        });
+        let sort_order = self
+            .episodes
+            .iter()
+            .map(|episode| episode.header.sort_order)
+            .chain([self.active_episode.sort_order])
+            .max()
+            .map_or(0, |max| max + 1);
+        let header = EpisodeHeader {
+            id: EpisodeId::new(),
+            title: title.into(),
+            sort_order,
+        };
+        let id = header.id;
+        self.episodes.push(Episode { header, timeline });
+        id
+    }
+
+    /// Make `id` the active episode, parking the current one in its place.
+    pub fn switch_episode(&mut self, id: EpisodeId) -> Result<()> {
+        if self.active_episode.id == id {
+            return Ok(());
+        }
+        let index = self
+            .episodes
+            .iter()
+            .position(|episode| episode.header.id == id)
+            .ok_or(Error::EpisodeNotFound(id.0))?;
+        let target = &mut self.episodes[index];
+        std::mem::swap(&mut self.active_episode, &mut target.header);
+        std::mem::swap(&mut self.timeline, &mut target.timeline);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Template;
+
+    #[test]
+    fn episodes_are_added_listed_and_switched() {
+        let mut project = Template::MultiCam.build_project("Episodes Test");
+        let pilot_id = project.active_episode.id;
+        let pilot_nodes = project.timeline.nodes.len();
+        let template = Template::SingleCam.build_project("Template");
+
+        let second_id = project.add_episode("Episode 2", template.timeline, &template.arcs);
+
+        let summaries = project.episode_summaries();
+        assert_eq!(summaries.len(), 2);
+        assert!(summaries[0].active && summaries[0].header.id == pilot_id);
+        assert_eq!(summaries[1].header.title, "Episode 2");
+        assert_eq!(summaries[1].header.sort_order, 1);
+        let second = &project.episodes[0].timeline;
+        assert!(!second.node_arcs.is_empty());
+        assert!(
+            second
+                .node_arcs
+                .iter()
+                .all(|node_arc| project.arcs.iter().any(|arc| arc.id == node_arc.arc_id))
+        );
+
+        project.switch_episode(second_id).unwrap();
+
+        assert_eq!(project.active_episode.id, second_id);
+        assert_eq!(project.episodes[0].header.id, pilot_id);
+        assert_eq!(project.episodes[0].timeline.nodes.len(), pilot_nodes);
+        assert_eq!(project.episode_summaries()[0].header.id, pilot_id);
+        assert!(matches!(
+            project.switch_episode(EpisodeId::new()),
+            Err(Error::EpisodeNotFound(_))
+        ));
+    }
+}
@@ -1,8 +1,13 @@
+pub mod ai_settings;
+pub mod episode;
+
 use serde::{Deserialize, Serialize};
 
 use crate::reference::ReferenceDocument;
 use crate::story::arc::StoryArc;
 use crate::timeline::Timeline;
+pub use ai_settings::ProjectAiSettings;
+pub use episode::{Episode, EpisodeHeader, EpisodeId, EpisodeSummary};
 
 /// A complete Eidetic project, aggregating project metadata and timeline structure.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,10 +16,19 @@ pub struct Project {
     /// The project-level story premise/concept.
     #[serde(default)]
     pub premise: String,
+    /// The active episode's timeline, which every timeline command edits.
     pub timeline: Timeline,
+    #[serde(default)]
+    pub active_episode: EpisodeHeader,
+    /// The other episodes, parked until [`Project::switch_episode`] picks one.
+    #[serde(default)]
+    pub episodes: Vec<Episode>,
     pub arcs: Vec<StoryArc>,
     #[serde(default)]
     pub references: Vec<ReferenceDocument>,
+    /// AI overrides for this project; see [`ProjectAiSettings`].
+    #[serde(default, skip_serializing_if = "ProjectAiSettings::is_empty")]
+    pub ai_settings: ProjectAiSettings,
 }
 
 impl Project {
@@ -23,8 +37,11 @@ impl Project {
             name: name.into(),
             premise: String::new(),
             timeline,
+            active_episode: EpisodeHeader::default(),
+            episodes: Vec::new(),
             arcs: Vec::new(),
             references: Vec::new(),
+            ai_settings: ProjectAiSettings::default(),
         }
     }
 }
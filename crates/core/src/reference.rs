@@ -1,13 +1,18 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::contracts::BibleGraphNodeId;
+use crate::story::arc::ArcId;
+
 /// Unique identifier for a reference document.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ReferenceId(pub Uuid);
 
 impl ReferenceId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
@@ -27,6 +32,33 @@ pub enum ReferenceType {
     Custom(String),
 }
 
+/// Which generations a reference document may inform. An empty scope is
+/// global; otherwise a node must be tagged with one of the arcs or draw one
+/// of the entities into its bible context.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceScope {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arc_ids: Vec<ArcId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entity_ids: Vec<BibleGraphNodeId>,
+}
+
+impl ReferenceScope {
+    pub fn is_global(&self) -> bool {
+        self.arc_ids.is_empty() && self.entity_ids.is_empty()
+    }
+
+    /// Whether a node with these arcs and context entities is in scope.
+    pub fn includes(&self, arc_ids: &[ArcId], entity_ids: &[BibleGraphNodeId]) -> bool {
+        self.is_global()
+            || self.arc_ids.iter().any(|arc_id| arc_ids.contains(arc_id))
+            || self
+                .entity_ids
+                .iter()
+                .any(|entity_id| entity_ids.contains(entity_id))
+    }
+}
+
 /// A reference document uploaded by the user.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReferenceDocument {
@@ -34,6 +66,8 @@ pub struct ReferenceDocument {
     pub name: String,
     pub content: String,
     pub doc_type: ReferenceType,
+    #[serde(default, skip_serializing_if = "ReferenceScope::is_global")]
+    pub scope: ReferenceScope,
 }
 
 impl ReferenceDocument {
@@ -47,6 +81,7 @@ impl ReferenceDocument {
             name: name.into(),
             content: content.into(),
             doc_type,
+            scope: ReferenceScope::default(),
         }
     }
 }
@@ -88,7 +123,7 @@ pub fn chunk_document(
         if !current.is_empty() && current.len() + para_trimmed.len() + 2 > max_chunk_chars {
             // Emit current chunk.
             chunks.push(ReferenceChunk {
-                id: Uuid::new_v4(),
+                id: crate::id::new_uuid(),
                 document_id: doc.id,
                 document_name: doc.name.clone(),
                 content: current.clone(),
@@ -114,7 +149,7 @@ pub fn chunk_document(
     // Emit final chunk.
     if !current.is_empty() {
         chunks.push(ReferenceChunk {
-            id: Uuid::new_v4(),
+            id: crate::id::new_uuid(),
             document_id: doc.id,
             document_name: doc.name.clone(),
             content: current,
@@ -125,6 +160,108 @@ pub fn chunk_document(
     chunks
 }
 
+/// How reference context for a generation was retrieved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetrievalMode {
+    /// The project has no reference material.
+    None,
+    /// Nearest chunks by embedding similarity.
+    Embedding,
+    /// BM25 over chunk words, used when embeddings are unavailable.
+    Lexical,
+}
+
+const BM25_K1: f32 = 1.2;
+const BM25_B: f32 = 0.75;
+
+/// Inverted index over reference chunks for lexical retrieval.
+pub struct LexicalIndex {
+    chunks: Vec<ReferenceChunk>,
+    /// Term to `(chunk index, term frequency)` postings.
+    postings: HashMap<String, Vec<(usize, u32)>>,
+    lengths: Vec<usize>,
+    average_length: f32,
+}
+
+impl LexicalIndex {
+    pub fn new(chunks: Vec<ReferenceChunk>) -> Self {
+        let mut postings: HashMap<String, Vec<(usize, u32)>> = HashMap::new();
+        let mut lengths = Vec::with_capacity(chunks.len());
+        for (index, chunk) in chunks.iter().enumerate() {
+            let terms = tokenize(&chunk.content);
+            lengths.push(terms.len());
+            let mut frequencies: HashMap<String, u32> = HashMap::new();
+            for term in terms {
+                *frequencies.entry(term).or_default() += 1;
+            }
+            for (term, frequency) in frequencies {
+                postings.entry(term).or_default().push((index, frequency));
+            }
+        }
+        let average_length = if lengths.is_empty() {
+            0.0
+        } else {
+            lengths.iter().sum::<usize>() as f32 / lengths.len() as f32
+        };
+        Self {
+            chunks,
+            postings,
+            lengths,
+            average_length,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// The `top_k` chunks by BM25 score. Chunks sharing no query term are
+    /// never returned.
+    pub fn search(&self, query: &str, top_k: usize) -> Vec<(&ReferenceChunk, f32)> {
+        let mut terms = tokenize(query);
+        terms.sort();
+        terms.dedup();
+
+        let chunk_count = self.chunks.len() as f32;
+        let mut scores: HashMap<usize, f32> = HashMap::new();
+        for term in &terms {
+            let Some(postings) = self.postings.get(term) else {
+                continue;
+            };
+            let document_frequency = postings.len() as f32;
+            let idf =
+                (1.0 + (chunk_count - document_frequency + 0.5) / (document_frequency + 0.5)).ln();
+            for &(index, frequency) in postings {
+                let frequency = frequency as f32;
+                let length_ratio = self.lengths[index] as f32 / self.average_length.max(1.0);
+                let saturation = frequency * (BM25_K1 + 1.0)
+                    / (frequency + BM25_K1 * (1.0 - BM25_B + BM25_B * length_ratio));
+                *scores.entry(index).or_default() += idf * saturation;
+            }
+        }
+
+        let mut ranked: Vec<(&ReferenceChunk, f32)> = scores
+            .into_iter()
+            .map(|(index, score)| (&self.chunks[index], score))
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.0.offset.cmp(&b.0.offset))
+        });
+        ranked.truncate(top_k);
+        ranked
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 1)
+        .map(str::to_lowercase)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,4 +286,48 @@ mod tests {
             chunks.len()
         );
     }
+
+    #[test]
+    fn lexical_index_ranks_chunks_by_bm25() {
+        let doc = ReferenceDocument::new(
+            "guide",
+            "The lighthouse keeper rows out at dawn.\n\nMarkets open at noon.\n\nThe keeper hates dawn.",
+            ReferenceType::WorldBuilding,
+        );
+        let index = LexicalIndex::new(chunk_document(&doc, 40, 0));
+
+        let results = index.search("Keeper at the lighthouse", 3);
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].0.content.contains("lighthouse"));
+        assert!(results[0].1 > results[1].1);
+        assert!(results[1].0.content.contains("hates"));
+        assert!(index.search("submarine", 3).is_empty());
+    }
+
+    #[test]
+    fn scoped_documents_only_include_matching_arcs_or_entities() {
+        let villain_arc = ArcId::new();
+        let b_plot = ArcId::new();
+        let villain = BibleGraphNodeId::new("node.character.villain").unwrap();
+        let scope = ReferenceScope {
+            arc_ids: vec![villain_arc],
+            entity_ids: vec![villain.clone()],
+        };
+
+        assert!(ReferenceScope::default().includes(&[b_plot], &[]));
+        assert!(scope.includes(&[b_plot, villain_arc], &[]));
+        assert!(scope.includes(&[b_plot], std::slice::from_ref(&villain)));
+        assert!(!scope.includes(&[b_plot], &[]));
+
+        let json = serde_json::to_value(ReferenceDocument::new(
+            "world",
+            "Lore.",
+            ReferenceType::WorldBuilding,
+        ))
+        .unwrap();
+        assert!(json.get("scope").is_none());
+        let doc: ReferenceDocument = serde_json::from_value(json).unwrap();
+        assert!(doc.scope.is_global());
+    }
 }
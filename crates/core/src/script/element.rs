@@ -10,6 +10,9 @@ pub enum ScriptElement {
     SceneHeading(String),
     Action(String),
     Character(String),
+    /// A character cue whose dialogue runs alongside the previous speaker's,
+    /// written with a trailing `^` in Fountain. Holds the cue without the `^`.
+    DualDialogue(String),
     Parenthetical(String),
     Dialogue(String),
     Transition(String),
@@ -21,7 +24,7 @@ impl ScriptElement {
         match self {
             Self::SceneHeading(s) => s.to_uppercase(),
             Self::Action(s) => s.clone(),
-            Self::Character(s) => format!("          {}", s.to_uppercase()),
+            Self::Character(s) | Self::DualDialogue(s) => format!("          {}", s.to_uppercase()),
             Self::Parenthetical(s) => format!("       ({s})"),
             Self::Dialogue(s) => format!("     {s}"),
             Self::Transition(s) => format!("{:>60}", s.to_uppercase()),
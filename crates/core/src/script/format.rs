@@ -36,6 +36,7 @@ enum ParseState {
 /// Recognizes standard formatting conventions:
 /// - Lines starting with `INT.` or `EXT.` → `SceneHeading`
 /// - ALL CAPS short lines (≤40 chars, mostly uppercase letters) → `Character`
+/// - Character cues ending with `^` → `DualDialogue`
 /// - Lines in parentheses after a Character → `Parenthetical`
 /// - Lines after Character/Parenthetical → `Dialogue` (until blank line)
 /// - Lines ending with `TO:` → `Transition`
@@ -65,7 +66,7 @@ pub fn parse_script_elements(raw: &str) -> Vec<ScriptElement> {
                 } else if is_transition(trimmed) {
                     elements.push(ScriptElement::Transition(trimmed.to_owned()));
                 } else if is_character_cue(trimmed) {
-                    elements.push(ScriptElement::Character(trimmed.to_owned()));
+                    elements.push(character_element(trimmed));
                     state = ParseState::AfterCharacter;
                 } else {
                     elements.push(ScriptElement::Action(trimmed.to_owned()));
@@ -103,7 +104,7 @@ pub fn parse_script_elements(raw: &str) -> Vec<ScriptElement> {
                         elements.push(ScriptElement::Dialogue(dialogue_buf.trim().to_owned()));
                         dialogue_buf.clear();
                     }
-                    elements.push(ScriptElement::Character(trimmed.to_owned()));
+                    elements.push(character_element(trimmed));
                     state = ParseState::AfterCharacter;
                 } else {
                     // Continuation of dialogue.
@@ -136,7 +137,7 @@ pub fn estimate_page_count(elements: &[ScriptElement], rules: &FormatRules) -> f
         total_lines += match element {
             ScriptElement::SceneHeading(_) => 2, // heading + blank line after
             ScriptElement::Action(s) => wrapped_line_count(s, rules.chars_per_line_action) + 1,
-            ScriptElement::Character(_) => 1,
+            ScriptElement::Character(_) | ScriptElement::DualDialogue(_) => 1,
             ScriptElement::Parenthetical(_) => 1,
             ScriptElement::Dialogue(s) => wrapped_line_count(s, rules.chars_per_line_dialogue),
             ScriptElement::Transition(_) => 2, // transition + blank line after
@@ -198,6 +199,59 @@ fn is_character_cue(line: &str) -> bool {
     uppercase_count as f64 / alpha_chars.len() as f64 > 0.8
 }
 
+/// Build the element for a character cue line, splitting off a trailing `^`
+/// dual-dialogue marker.
+fn character_element(line: &str) -> ScriptElement {
+    match line.strip_suffix('^') {
+        Some(cue) => ScriptElement::DualDialogue(cue.trim_end().to_owned()),
+        None => ScriptElement::Character(line.to_owned()),
+    }
+}
+
+/// The speaker's name in a character cue, without extensions such as
+/// `(V.O.)` or `(CONT'D)` and without a `^` dual-dialogue marker.
+pub fn character_cue_name(cue: &str) -> &str {
+    let cue = cue.trim().trim_end_matches('^').trim_end();
+    match cue.find('(') {
+        Some(paren_start) => cue[..paren_start].trim_end(),
+        None => cue,
+    }
+}
+
+/// The parenthesized extensions of a character cue, e.g. `["V.O."]` for
+/// `JAKE (V.O.)`.
+pub fn character_cue_extensions(cue: &str) -> Vec<&str> {
+    let mut extensions = Vec::new();
+    let mut rest = cue;
+    while let Some(open) = rest.find('(') {
+        let Some(close) = rest[open..].find(')') else {
+            break;
+        };
+        let extension = rest[open + 1..open + close].trim();
+        if !extension.is_empty() {
+            extensions.push(extension);
+        }
+        rest = &rest[open + close + 1..];
+    }
+    extensions
+}
+
+/// Whether a character cue marks the speaker as a voice-over.
+pub fn is_voice_over(cue: &str) -> bool {
+    has_extension(cue, "V.O.")
+}
+
+/// Whether a character cue marks the speaker as off screen.
+pub fn is_off_screen(cue: &str) -> bool {
+    has_extension(cue, "O.S.") || has_extension(cue, "O.C.")
+}
+
+fn has_extension(cue: &str, extension: &str) -> bool {
+    character_cue_extensions(cue)
+        .iter()
+        .any(|candidate| candidate.eq_ignore_ascii_case(extension))
+}
+
 /// Check if a line is a parenthetical direction.
 fn is_parenthetical(line: &str) -> bool {
     let trimmed = line.trim();
@@ -262,6 +316,50 @@ Elaine is sitting outside.";
         assert!(matches!(&elements[1], ScriptElement::Dialogue(s) if s.contains("isn't right")));
     }
 
+    #[test]
+    fn parse_overlapping_dual_dialogue() {
+        let input = "\
+BRICK
+(shouting)
+Get down!
+STEEL (O.S.) ^
+No, you get down!
+
+MARGE (V.O.)
+They never did agree.";
+        let elements = parse_script_elements(input);
+
+        assert_eq!(
+            elements,
+            [
+                ScriptElement::Character("BRICK".to_owned()),
+                ScriptElement::Parenthetical("shouting".to_owned()),
+                ScriptElement::Dialogue("Get down!".to_owned()),
+                ScriptElement::DualDialogue("STEEL (O.S.)".to_owned()),
+                ScriptElement::Dialogue("No, you get down!".to_owned()),
+                ScriptElement::Character("MARGE (V.O.)".to_owned()),
+                ScriptElement::Dialogue("They never did agree.".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn character_cue_name_strips_extensions_and_dual_marker() {
+        assert_eq!(character_cue_name("JERRY"), "JERRY");
+        assert_eq!(character_cue_name("JAKE (V.O.)"), "JAKE");
+        assert_eq!(
+            character_cue_name("MR. SMITH (O.S.) (CONT'D) ^"),
+            "MR. SMITH"
+        );
+        assert_eq!(
+            character_cue_extensions("MR. SMITH (O.S.) (CONT'D) ^"),
+            ["O.S.", "CONT'D"]
+        );
+        assert!(is_voice_over("JAKE (v.o.)"));
+        assert!(is_off_screen("STEEL (O.S.)"));
+        assert!(!is_off_screen("JAKE (V.O.)"));
+    }
+
     #[test]
     fn estimate_page_count_roughly_correct() {
         let elements = parse_script_elements(SAMPLE_SCRIPT);
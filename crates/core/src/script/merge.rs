@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
 
 /// A piece of user-written text that must appear verbatim in generated output.
@@ -74,6 +76,99 @@ pub fn merge_with_anchors(generated: &str, anchors: &[Anchor]) -> String {
     result
 }
 
+/// Anchors that do not appear verbatim in `generated`. Runs of whitespace
+/// compare equal, so an anchor the model re-wrapped still counts.
+pub fn missing_anchors<'a>(generated: &str, anchors: &'a [String]) -> Vec<&'a str> {
+    let generated = collapse_whitespace(generated);
+    anchors
+        .iter()
+        .map(String::as_str)
+        .filter(|anchor| {
+            let anchor = collapse_whitespace(anchor);
+            !anchor.is_empty() && !generated.contains(&anchor)
+        })
+        .collect()
+}
+
+/// Splice each anchor missing from `generated` in as its own paragraph after
+/// the paragraph sharing the most words with it, which is likely the model's
+/// paraphrase, or at the end when none does. Returns the spliced text and the
+/// anchors that were missing.
+pub fn splice_missing_anchors(generated: &str, anchors: &[String]) -> (String, Vec<String>) {
+    let missing = missing_anchors(generated, anchors);
+    if missing.is_empty() {
+        return (generated.to_owned(), Vec::new());
+    }
+
+    let paragraphs = paragraph_ends(generated);
+    let end = generated.trim_end().len();
+    let mut insertions: Vec<(usize, &str)> = missing
+        .iter()
+        .map(|anchor| {
+            let anchor_words = significant_words(anchor);
+            let position = paragraphs
+                .iter()
+                .map(|(end, paragraph)| {
+                    let shared = significant_words(paragraph)
+                        .intersection(&anchor_words)
+                        .count();
+                    (shared, *end)
+                })
+                .filter(|(shared, _)| *shared > 0)
+                // Earliest paragraph wins ties.
+                .max_by_key(|(shared, end)| (*shared, std::cmp::Reverse(*end)))
+                .map_or(end, |(_, end)| end);
+            (position, anchor.trim())
+        })
+        .collect();
+    insertions.sort_by_key(|(position, _)| *position);
+
+    let mut result = String::with_capacity(
+        generated.len() + missing.iter().map(|anchor| anchor.len() + 2).sum::<usize>(),
+    );
+    let mut last = 0;
+    for (position, anchor) in insertions {
+        result.push_str(&generated[last..position]);
+        if !result.is_empty() {
+            result.push_str("\n\n");
+        }
+        result.push_str(anchor);
+        last = position;
+    }
+    result.push_str(&generated[last..]);
+    (result, missing.into_iter().map(str::to_owned).collect())
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Blank-line separated paragraphs, each paired with the byte offset where it
+/// ends.
+fn paragraph_ends(text: &str) -> Vec<(usize, &str)> {
+    let mut paragraphs = Vec::new();
+    let mut start = 0;
+    for (index, _) in text.match_indices("\n\n") {
+        if !text[start..index].trim().is_empty() {
+            paragraphs.push((index, &text[start..index]));
+        }
+        start = index + 2;
+    }
+    let end = text.trim_end().len();
+    if end > start && !text[start..end].trim().is_empty() {
+        paragraphs.push((end, &text[start..end]));
+    }
+    paragraphs
+}
+
+/// Lowercased words of three or more letters or digits.
+fn significant_words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
 /// Find the nearest line break boundary to `target` byte position.
 fn find_nearest_line_break(text: &str, target: usize) -> usize {
     if target >= text.len() {
@@ -140,4 +235,39 @@ mod tests {
         let pos = result.find("INSERTED").unwrap();
         assert!(pos > 5 && pos < result.len() - 5);
     }
+
+    #[test]
+    fn verbatim_anchors_survive_rewrapping() {
+        let anchors = vec![
+            "Ada drops the locket.".to_string(),
+            "Nobody answers.".to_string(),
+        ];
+        let generated = "Ada drops\nthe locket.\n\nNobody answers.";
+
+        assert!(missing_anchors(generated, &anchors).is_empty());
+        assert_eq!(
+            splice_missing_anchors(generated, &anchors),
+            (generated.to_string(), Vec::new())
+        );
+    }
+
+    #[test]
+    fn missing_anchor_is_spliced_after_its_paraphrase() {
+        let anchors = vec![
+            "The locket hits the floor and cracks open.".to_string(),
+            "BEN\nWe're not done.".to_string(),
+        ];
+        let generated =
+            "INT. KITCHEN - DAY\n\nAda lets the locket fall. It cracks.\n\nShe leaves.\n";
+
+        let (spliced, missing) = splice_missing_anchors(generated, &anchors);
+
+        assert_eq!(missing, anchors);
+        assert_eq!(
+            spliced,
+            "INT. KITCHEN - DAY\n\nAda lets the locket fall. It cracks.\n\n\
+             The locket hits the floor and cracks open.\n\nShe leaves.\n\n\
+             BEN\nWe're not done.\n"
+        );
+    }
 }
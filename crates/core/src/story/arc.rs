@@ -7,7 +7,7 @@ pub struct ArcId(pub Uuid);
 
 impl ArcId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
@@ -73,7 +73,12 @@ pub enum ArcType {
 }
 
 /// An RGB color used for tracks, nodes, and relationship curves.
+///
+/// Serializes as `{r, g, b}`. Deserialization also accepts an `[r, g, b]`
+/// array or a CSS hex string (`#rgb` / `#rrggbb`) so clients can send
+/// whichever form they already hold.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "ColorInput")]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -85,13 +90,161 @@ impl Color {
         Self { r, g, b }
     }
 
+    /// Parse a CSS hex string in `#rgb` or `#rrggbb` form (the `#` is optional).
+    pub fn from_hex(value: &str) -> Result<Self, ColorParseError> {
+        let trimmed = value.trim();
+        let digits = trimmed.strip_prefix('#').unwrap_or(trimmed);
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ColorParseError::InvalidHex(value.to_string()));
+        }
+        let channel = |hex: &str| {
+            u8::from_str_radix(hex, 16).map_err(|_| ColorParseError::InvalidHex(value.to_string()))
+        };
+        match digits.len() {
+            3 => {
+                // Shorthand: each digit is doubled, so `f` becomes `ff`.
+                let expand = |i: usize| channel(&digits[i..=i]).map(|v| v * 17);
+                Ok(Self::new(expand(0)?, expand(1)?, expand(2)?))
+            }
+            6 => Ok(Self::new(
+                channel(&digits[0..2])?,
+                channel(&digits[2..4])?,
+                channel(&digits[4..6])?,
+            )),
+            _ => Err(ColorParseError::InvalidHex(value.to_string())),
+        }
+    }
+
     /// Format as CSS hex string.
     pub fn to_hex(&self) -> String {
         format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
     }
 
+    /// WCAG relative luminance in `0.0..=1.0`.
+    pub fn relative_luminance(&self) -> f64 {
+        fn linear(channel: u8) -> f64 {
+            let c = f64::from(channel) / 255.0;
+            if c <= 0.040_45 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        }
+        0.2126 * linear(self.r) + 0.7152 * linear(self.g) + 0.0722 * linear(self.b)
+    }
+
+    /// Black or white, whichever has the higher contrast ratio against this
+    /// color. Used for labels drawn on arc/entity swatches.
+    pub fn readable_text_color(&self) -> Self {
+        // Contrast with black equals contrast with white at L ≈ 0.179.
+        if self.relative_luminance() > 0.179 {
+            Self::BLACK
+        } else {
+            Self::WHITE
+        }
+    }
+
+    pub const BLACK: Self = Self::new(0, 0, 0);
+    pub const WHITE: Self = Self::new(255, 255, 255);
+
     // Palette defaults for the three standard arcs.
     pub const A_PLOT: Self = Self::new(100, 149, 237); // cornflower blue
     pub const B_PLOT: Self = Self::new(119, 221, 119); // pastel green
     pub const C_RUNNER: Self = Self::new(255, 179, 71); // pastel orange
 }
+
+/// Error produced when a color cannot be parsed from client input.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ColorParseError {
+    #[error("invalid hex color {0:?}: expected #rgb or #rrggbb")]
+    InvalidHex(String),
+}
+
+/// Wire forms accepted when deserializing a [`Color`].
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ColorInput {
+    Rgb { r: u8, g: u8, b: u8 },
+    Array([u8; 3]),
+    Hex(String),
+}
+
+impl TryFrom<ColorInput> for Color {
+    type Error = ColorParseError;
+
+    fn try_from(value: ColorInput) -> Result<Self, Self::Error> {
+        match value {
+            ColorInput::Rgb { r, g, b } => Ok(Self::new(r, g, b)),
+            ColorInput::Array([r, g, b]) => Ok(Self::new(r, g, b)),
+            ColorInput::Hex(hex) => Self::from_hex(&hex),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_hex_parses_long_and_shorthand_forms() {
+        assert_eq!(Color::from_hex("#6495ed").unwrap(), Color::A_PLOT);
+        assert_eq!(Color::from_hex("6495ED").unwrap(), Color::A_PLOT);
+        assert_eq!(Color::from_hex("#fff").unwrap(), Color::WHITE);
+        assert_eq!(
+            Color::from_hex("#1a2").unwrap(),
+            Color::new(0x11, 0xaa, 0x22)
+        );
+    }
+
+    #[test]
+    fn from_hex_rejects_malformed_input() {
+        for input in ["", "#", "#12", "#1234", "#gggggg", "#12345678", "#+1+2+3"] {
+            assert!(Color::from_hex(input).is_err(), "{input:?} should fail");
+        }
+    }
+
+    #[test]
+    fn hex_round_trips_through_to_hex() {
+        for color in [
+            Color::A_PLOT,
+            Color::B_PLOT,
+            Color::C_RUNNER,
+            Color::new(1, 2, 3),
+        ] {
+            assert_eq!(Color::from_hex(&color.to_hex()).unwrap(), color);
+        }
+        assert_eq!(Color::from_hex("#ABC").unwrap().to_hex(), "#aabbcc");
+    }
+
+    #[test]
+    fn readable_text_color_picks_contrasting_label() {
+        assert_eq!(Color::WHITE.readable_text_color(), Color::BLACK);
+        assert_eq!(Color::BLACK.readable_text_color(), Color::WHITE);
+        assert_eq!(Color::B_PLOT.readable_text_color(), Color::BLACK);
+        assert_eq!(Color::new(0, 0, 128).readable_text_color(), Color::WHITE);
+        // Mid gray sits just above the crossover and gets dark text.
+        assert_eq!(
+            Color::new(128, 128, 128).readable_text_color(),
+            Color::BLACK
+        );
+        assert_eq!(
+            Color::new(110, 110, 110).readable_text_color(),
+            Color::WHITE
+        );
+    }
+
+    #[test]
+    fn deserializes_object_array_and_hex_forms() {
+        let from_object: Color = serde_json::from_str(r#"{"r":1,"g":2,"b":3}"#).unwrap();
+        let from_array: Color = serde_json::from_str("[1,2,3]").unwrap();
+        let from_hex: Color = serde_json::from_str(r##""#010203""##).unwrap();
+        assert_eq!(from_object, Color::new(1, 2, 3));
+        assert_eq!(from_array, Color::new(1, 2, 3));
+        assert_eq!(from_hex, Color::new(1, 2, 3));
+        assert!(serde_json::from_str::<Color>(r#""nope""#).is_err());
+        assert_eq!(
+            serde_json::to_value(Color::new(1, 2, 3)).unwrap(),
+            serde_json::json!({"r": 1, "g": 2, "b": 3})
+        );
+    }
+}
@@ -9,7 +9,7 @@ pub struct CharacterId(pub Uuid);
 
 impl CharacterId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
@@ -1,3 +1,4 @@
+use crate::error::{Error, Result};
 use crate::project::Project;
 use crate::story::arc::{ArcType, Color, StoryArc};
 use crate::timeline::Timeline;
@@ -38,6 +39,10 @@ struct SceneSpec {
 }
 
 impl Template {
+    /// Content length the templates are laid out for: 22 minutes, a 30-minute
+    /// broadcast slot.
+    pub const EPISODE_DURATION_MS: u64 = EPISODE_DURATION_MS;
+
     /// Build a new project from this template with pre-placed acts, scenes, and arcs.
     pub fn build_project(self, name: impl Into<String>) -> Project {
         let structure = EpisodeStructure::standard_30_min();
@@ -99,6 +104,30 @@ impl Template {
         project
     }
 
+    /// Build this template stretched or squeezed to `total_duration_ms`, so
+    /// acts and scenes keep their proportions (e.g. an 11-minute short or a
+    /// 44-minute drama).
+    ///
+    /// Fails if the duration is zero or too short for every scene to keep at
+    /// least a millisecond.
+    pub fn build_project_with_duration(
+        self,
+        name: impl Into<String>,
+        total_duration_ms: u64,
+    ) -> Result<Project> {
+        let mut project = self.build_project(name);
+        if total_duration_ms == 0 {
+            return Err(Error::InvalidOperation(
+                "episode duration must be positive".to_string(),
+            ));
+        }
+        if total_duration_ms != EPISODE_DURATION_MS {
+            rescale_timeline(&mut project.timeline, total_duration_ms)?;
+        }
+        project.timeline.structure.validate(total_duration_ms)?;
+        Ok(project)
+    }
+
     fn build_acts(self, premise_id: NodeId) -> Vec<StoryNode> {
         let names = ["Cold Open", "Act One", "Act Two", "Act Three", "Tag"];
         ACT_TIMES
@@ -445,6 +474,34 @@ fn find_act_for_time(act_ids: &[NodeId], start_ms: u64) -> Option<NodeId> {
     act_ids.first().copied()
 }
 
+/// Scale every node and structure segment proportionally onto a new total.
+fn rescale_timeline(timeline: &mut Timeline, total_duration_ms: u64) -> Result<()> {
+    let from_ms = timeline.total_duration_ms;
+    let scale =
+        |ms: u64| (u128::from(ms) * u128::from(total_duration_ms) / u128::from(from_ms)) as u64;
+    for node in &mut timeline.nodes {
+        let range = TimeRange::new(
+            scale(node.time_range.start_ms),
+            scale(node.time_range.end_ms),
+        )
+        .map_err(|_| {
+            Error::InvalidOperation(format!(
+                "{total_duration_ms}ms is too short to fit '{}'",
+                node.name
+            ))
+        })?;
+        node.time_range = range;
+    }
+    for segment in &mut timeline.structure.segments {
+        segment.time_range = TimeRange {
+            start_ms: scale(segment.time_range.start_ms),
+            end_ms: scale(segment.time_range.end_ms),
+        };
+    }
+    timeline.total_duration_ms = total_duration_ms;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -536,4 +593,46 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_custom_duration_scales_acts_scenes_and_structure() {
+        for total_ms in [660_000, 2_640_000] {
+            let project = Template::SingleCam
+                .build_project_with_duration("Scaled", total_ms)
+                .unwrap();
+            let timeline = &project.timeline;
+            assert_eq!(timeline.total_duration_ms, total_ms);
+            let premise = &timeline.nodes_at_level(StoryLevel::Premise)[0];
+            assert_eq!(premise.time_range.end_ms, total_ms);
+            for node in &timeline.nodes {
+                assert!(node.time_range.validate().is_ok(), "{}", node.name);
+                assert!(node.time_range.end_ms <= total_ms, "{}", node.name);
+            }
+            assert!(timeline.structure.validate(total_ms).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_custom_duration_rejects_zero_and_too_short() {
+        assert!(
+            Template::MultiCam
+                .build_project_with_duration("Zero", 0)
+                .is_err()
+        );
+        assert!(
+            Template::MultiCam
+                .build_project_with_duration("Tiny", 10)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_structure_validate_rejects_segments_past_the_end() {
+        let structure = EpisodeStructure::standard_30_min();
+        assert!(structure.validate(EPISODE_DURATION_MS).is_ok());
+        assert!(matches!(
+            structure.validate(EPISODE_DURATION_MS - 1),
+            Err(Error::NodeExceedsTimeline { .. })
+        ));
+    }
 }
@@ -0,0 +1,26 @@
+//! Text helpers shared by prompt building, logging and previews.
+
+/// The first `max_chars` characters of `text`, cut on a char boundary so
+/// multibyte text (accents, emoji) never panics the way a byte slice would.
+pub fn truncate_chars(text: &str, max_chars: usize) -> &str {
+    match text.char_indices().nth(max_chars) {
+        Some((cut, _)) => &text[..cut],
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::truncate_chars;
+
+    #[test]
+    fn truncation_counts_characters_not_bytes() {
+        let text = "Zoë 🎬 café";
+
+        assert_eq!(truncate_chars(text, 3), "Zoë");
+        assert_eq!(truncate_chars(text, 5), "Zoë 🎬");
+        assert_eq!(truncate_chars(text, 100), text);
+        assert_eq!(truncate_chars(text, 0), "");
+        assert_eq!(truncate_chars("", 5), "");
+    }
+}
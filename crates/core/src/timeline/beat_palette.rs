@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+
+use super::Timeline;
+use super::node::{BeatType, NodeId, StoryLevel, StoryNode};
+use super::structure::StructureSegment;
+use crate::error::Result;
+
+/// A beat whose type is unusual for where it sits in the episode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BeatTypeWarning {
+    pub node_id: NodeId,
+    pub node_name: String,
+    pub beat_type: BeatType,
+    /// Label of the structure segment the beat starts in.
+    pub segment_label: String,
+    /// Beat types expected at this position, most typical first.
+    pub expected: Vec<BeatType>,
+    pub message: String,
+}
+
+impl Timeline {
+    /// The structure segment containing `at_ms`, and how far through the
+    /// episode that is (0.0..=1.0).
+    pub fn structure_position(&self, at_ms: u64) -> Option<(&StructureSegment, f32)> {
+        let segment = self.structure.segments.iter().find(|segment| {
+            segment.time_range.duration_ms() > 0
+                && segment.time_range.start_ms <= at_ms
+                && at_ms < segment.time_range.end_ms
+        })?;
+        let relative_position = if self.total_duration_ms == 0 {
+            0.0
+        } else {
+            (at_ms as f64 / self.total_duration_ms as f64).min(1.0) as f32
+        };
+        Some((segment, relative_position))
+    }
+
+    /// Beat types that suit a beat at `at_ms`; empty when the position has
+    /// no expectation.
+    pub fn suggested_beat_types(&self, at_ms: u64) -> Vec<BeatType> {
+        self.structure_position(at_ms)
+            .map(|(segment, position)| {
+                BeatType::expected_for_position(segment.segment_type, position)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Flag beats at or under `node_id` whose type is unexpected for their
+    /// place in the episode. Untyped and custom beats are never flagged.
+    pub fn beat_type_warnings(&self, node_id: NodeId) -> Result<Vec<BeatTypeWarning>> {
+        let node = self.node(node_id)?;
+        let beats: Vec<&StoryNode> = if node.level == StoryLevel::Beat {
+            vec![node]
+        } else {
+            self.descendants_of(node_id)
+                .into_iter()
+                .filter(|descendant| descendant.level == StoryLevel::Beat)
+                .collect()
+        };
+
+        Ok(beats
+            .into_iter()
+            .filter_map(|beat| self.beat_type_warning(beat))
+            .collect())
+    }
+
+    fn beat_type_warning(&self, beat: &StoryNode) -> Option<BeatTypeWarning> {
+        let beat_type = beat.beat_type.as_ref()?;
+        if matches!(beat_type, BeatType::Custom(_)) {
+            return None;
+        }
+        let (segment, position) = self.structure_position(beat.time_range.start_ms)?;
+        let expected = BeatType::expected_for_position(segment.segment_type, position);
+        if expected.is_empty() || expected.contains(beat_type) {
+            return None;
+        }
+
+        let expected_labels: Vec<String> = expected
+            .iter()
+            .map(|expected| format!("{expected:?}"))
+            .collect();
+        Some(BeatTypeWarning {
+            node_id: beat.id,
+            node_name: beat.name.clone(),
+            beat_type: beat_type.clone(),
+            segment_label: segment.label.clone(),
+            message: format!(
+                "{beat_type:?} beat in {}; expected {} here",
+                segment.label,
+                expected_labels.join(", ")
+            ),
+            expected,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Template;
+    use crate::timeline::structure::SegmentType;
+    use crate::timeline::timing::TimeRange;
+
+    #[test]
+    fn final_act_setup_beats_are_flagged() {
+        let mut project = Template::MultiCam.build_project("Beat Palette Test");
+        let timeline = &mut project.timeline;
+        let final_act = timeline
+            .structure
+            .segments
+            .iter()
+            .rfind(|segment| segment.segment_type == SegmentType::Act)
+            .unwrap()
+            .time_range;
+        let scene_id = timeline.nodes_at_level(StoryLevel::Scene)[0].id;
+        let beat = StoryNode::new_beat(
+            "Late setup",
+            BeatType::Setup,
+            TimeRange {
+                start_ms: final_act.end_ms - 60_000,
+                end_ms: final_act.end_ms,
+            },
+            scene_id,
+        );
+        let final_act_beat = beat.id;
+        timeline.nodes.push(beat);
+
+        let warnings = timeline.beat_type_warnings(final_act_beat).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].expected.contains(&BeatType::Climax));
+        assert!(warnings[0].message.starts_with("Setup beat in "));
+
+        timeline.node_mut(final_act_beat).unwrap().beat_type = Some(BeatType::Climax);
+        assert!(
+            timeline
+                .beat_type_warnings(final_act_beat)
+                .unwrap()
+                .is_empty()
+        );
+
+        timeline.node_mut(final_act_beat).unwrap().beat_type =
+            Some(BeatType::Custom("Button".into()));
+        assert!(
+            timeline
+                .beat_type_warnings(final_act_beat)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn expected_beat_types_shift_toward_resolution() {
+        let opening = BeatType::expected_for_position(SegmentType::Act, 0.1);
+        let ending = BeatType::expected_for_position(SegmentType::Act, 0.9);
+        assert!(opening.contains(&BeatType::Setup) && !opening.contains(&BeatType::Climax));
+        assert!(ending.contains(&BeatType::Climax) && !ending.contains(&BeatType::Setup));
+        assert!(BeatType::expected_for_position(SegmentType::CommercialBreak, 0.5).is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+//! Batch generation order: explicit dependencies first, then priority, then
+//! time, so a flashback's reveal can be written before the scenes that lead
+//! up to it.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use uuid::Uuid;
+
+use super::Timeline;
+use super::node::{NodeId, StoryNode};
+use crate::error::{Error, Result};
+
+/// Sort key among nodes whose dependencies are met: lower priority values
+/// first, unprioritized nodes after prioritized ones, then by time.
+fn order_key(node: &StoryNode) -> (bool, i32, u64, u32, Uuid) {
+    (
+        node.content.generation_priority.is_none(),
+        node.content.generation_priority.unwrap_or(0),
+        node.time_range.start_ms,
+        node.sort_order,
+        node.id.0,
+    )
+}
+
+impl Timeline {
+    /// Order `node_ids` for generation. Each node comes after the nodes in
+    /// its `depends_on` that are also being generated; dependencies outside
+    /// the batch are already written or not part of it. Fails on a cycle.
+    pub fn generation_order(&self, node_ids: &[NodeId]) -> Result<Vec<NodeId>> {
+        let batch: HashSet<NodeId> = node_ids.iter().copied().collect();
+        let mut nodes: HashMap<NodeId, &StoryNode> = HashMap::new();
+        for &id in &batch {
+            nodes.insert(id, self.node(id)?);
+        }
+
+        let mut waiting_on: HashMap<NodeId, usize> = HashMap::new();
+        let mut dependents: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for (&id, node) in &nodes {
+            let dependencies: HashSet<NodeId> = node
+                .content
+                .depends_on
+                .iter()
+                .copied()
+                .filter(|dependency| batch.contains(dependency) && *dependency != id)
+                .collect();
+            waiting_on.insert(id, dependencies.len());
+            for dependency in dependencies {
+                dependents.entry(dependency).or_default().push(id);
+            }
+        }
+
+        let mut ready: BTreeSet<_> = waiting_on
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(id, _)| order_key(nodes[id]))
+            .collect();
+        let mut ordered = Vec::with_capacity(nodes.len());
+        while let Some(key) = ready.pop_first() {
+            let id = NodeId(key.4);
+            ordered.push(id);
+            for dependent in dependents.get(&id).into_iter().flatten() {
+                let count = waiting_on
+                    .get_mut(dependent)
+                    .expect("dependents are batch nodes");
+                *count -= 1;
+                if *count == 0 {
+                    ready.insert(order_key(nodes[dependent]));
+                }
+            }
+        }
+
+        if ordered.len() < nodes.len() {
+            let mut stuck: Vec<&str> = waiting_on
+                .iter()
+                .filter(|(_, count)| **count > 0)
+                .map(|(id, _)| nodes[id].name.as_str())
+                .collect();
+            stuck.sort_unstable();
+            return Err(Error::InvalidOperation(format!(
+                "generation dependencies form a cycle among: {}",
+                stuck.join(", ")
+            )));
+        }
+        Ok(ordered)
+    }
+
+    /// Check that `node_id` may depend on `depends_on`: every dependency
+    /// exists, none is the node itself, and none already depends on the node
+    /// directly or transitively.
+    pub fn validate_generation_dependencies(
+        &self,
+        node_id: NodeId,
+        depends_on: &[NodeId],
+    ) -> Result<()> {
+        self.node(node_id)?;
+        for &dependency in depends_on {
+            if dependency == node_id {
+                return Err(Error::InvalidOperation(
+                    "a node cannot depend on itself for generation".to_string(),
+                ));
+            }
+            self.node(dependency)?;
+        }
+
+        let mut stack: Vec<NodeId> = depends_on.to_vec();
+        let mut seen = HashSet::new();
+        while let Some(id) = stack.pop() {
+            if id == node_id {
+                return Err(Error::InvalidOperation(format!(
+                    "generation dependencies of {} would form a cycle",
+                    self.node(node_id)?.name
+                )));
+            }
+            if seen.insert(id)
+                && let Ok(node) = self.node(id)
+            {
+                stack.extend(node.content.depends_on.iter().copied());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Template;
+    use crate::timeline::node::StoryLevel;
+
+    #[test]
+    fn dependencies_then_priority_then_time_order_a_batch() {
+        let mut timeline = Template::MultiCam.build_project("Order").timeline;
+        let scenes: Vec<_> = timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .iter()
+            .take(4)
+            .map(|node| node.id)
+            .collect();
+        // The first scene is a flashback that needs the reveal in the third.
+        timeline.node_mut(scenes[0]).unwrap().content.depends_on = vec![scenes[2]];
+        timeline
+            .node_mut(scenes[3])
+            .unwrap()
+            .content
+            .generation_priority = Some(1);
+
+        let order = timeline.generation_order(&scenes).unwrap();
+
+        assert_eq!(order, vec![scenes[3], scenes[1], scenes[2], scenes[0]]);
+    }
+
+    #[test]
+    fn cycles_are_rejected() {
+        let mut timeline = Template::MultiCam.build_project("Cycle").timeline;
+        let scenes: Vec<_> = timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .iter()
+            .take(2)
+            .map(|node| node.id)
+            .collect();
+        timeline.node_mut(scenes[0]).unwrap().content.depends_on = vec![scenes[1]];
+
+        assert!(
+            timeline
+                .validate_generation_dependencies(scenes[1], &[scenes[0]])
+                .is_err()
+        );
+        assert!(
+            timeline
+                .validate_generation_dependencies(scenes[1], &[scenes[1]])
+                .is_err()
+        );
+        assert!(
+            timeline
+                .validate_generation_dependencies(scenes[0], &[scenes[1]])
+                .is_ok()
+        );
+
+        timeline.node_mut(scenes[1]).unwrap().content.depends_on = vec![scenes[0]];
+        let error = timeline.generation_order(&scenes).unwrap_err();
+        assert!(error.to_string().contains("cycle"));
+    }
+}
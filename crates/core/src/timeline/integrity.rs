@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+
+use super::Timeline;
+use super::node::{NodeId, StoryLevel, StoryNode};
+
+/// A structural problem in a timeline that bypassed [`Timeline::add_node`],
+/// e.g. one loaded from a hand-edited database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum TimelineIssue {
+    #[error("extra Premise node {node_id:?}; only one is allowed")]
+    ExtraPremise { node_id: NodeId },
+
+    #[error("{level} node {node_id:?} has no parent")]
+    MissingParent { node_id: NodeId, level: StoryLevel },
+
+    #[error("node {node_id:?} points at missing parent {parent_id:?}")]
+    OrphanedNode { node_id: NodeId, parent_id: NodeId },
+
+    #[error("{level} node {node_id:?} sits under {parent_level} parent {parent_id:?}")]
+    LevelMismatch {
+        node_id: NodeId,
+        level: StoryLevel,
+        parent_id: NodeId,
+        parent_level: StoryLevel,
+    },
+}
+
+impl Timeline {
+    /// Check every node against the timeline's hierarchy rules: one Premise,
+    /// every other node under an existing parent at a higher level. Skipped
+    /// levels are allowed, since templates hang scenes directly off acts.
+    pub fn validate_all(&self) -> Vec<TimelineIssue> {
+        let mut issues = Vec::new();
+        let mut premise_seen = false;
+
+        for node in &self.nodes {
+            let Some(parent_id) = node.parent_id else {
+                if node.level != StoryLevel::Premise {
+                    issues.push(TimelineIssue::MissingParent {
+                        node_id: node.id,
+                        level: node.level,
+                    });
+                } else if premise_seen {
+                    issues.push(TimelineIssue::ExtraPremise { node_id: node.id });
+                } else {
+                    premise_seen = true;
+                }
+                continue;
+            };
+
+            match self.nodes.iter().find(|parent| parent.id == parent_id) {
+                None => issues.push(TimelineIssue::OrphanedNode {
+                    node_id: node.id,
+                    parent_id,
+                }),
+                Some(parent) if !sits_below(node.level, parent.level) => {
+                    issues.push(TimelineIssue::LevelMismatch {
+                        node_id: node.id,
+                        level: node.level,
+                        parent_id,
+                        parent_level: parent.level,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        issues
+    }
+
+    /// Merge extra Premise nodes into the earliest one (by start, then sort
+    /// order): their children are re-parented onto it and the extras, with
+    /// their arc tags and relationships, are dropped. Returns the merged IDs.
+    pub fn repair_premise_invariant(&mut self) -> Vec<NodeId> {
+        let premises: Vec<&StoryNode> = self
+            .nodes
+            .iter()
+            .filter(|node| node.level == StoryLevel::Premise && node.parent_id.is_none())
+            .collect();
+        if premises.len() < 2 {
+            return Vec::new();
+        }
+        let kept = premises
+            .iter()
+            .min_by_key(|node| (node.time_range.start_ms, node.sort_order))
+            .map(|node| node.id)
+            .expect("at least two premises");
+        let extras: Vec<NodeId> = premises
+            .iter()
+            .map(|node| node.id)
+            .filter(|&id| id != kept)
+            .collect();
+
+        for node in &mut self.nodes {
+            if node
+                .parent_id
+                .is_some_and(|parent_id| extras.contains(&parent_id))
+            {
+                node.parent_id = Some(kept);
+            }
+        }
+        self.nodes.retain(|node| !extras.contains(&node.id));
+        self.node_arcs
+            .retain(|node_arc| !extras.contains(&node_arc.node_id));
+        self.relationships.retain(|relationship| {
+            !extras.contains(&relationship.from_node) && !extras.contains(&relationship.to_node)
+        });
+        extras
+    }
+}
+
+fn sits_below(level: StoryLevel, parent_level: StoryLevel) -> bool {
+    std::iter::successors(level.parent_level(), StoryLevel::parent_level)
+        .any(|ancestor| ancestor == parent_level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Template;
+    use crate::timeline::timing::TimeRange;
+
+    #[test]
+    fn extra_premise_is_reported_and_merged_into_the_first() {
+        let mut timeline = Template::MultiCam.build_project("Integrity Test").timeline;
+        assert!(timeline.validate_all().is_empty());
+        let kept = timeline.nodes_at_level(StoryLevel::Premise)[0].id;
+
+        let mut extra = StoryNode::new(
+            "Second premise",
+            StoryLevel::Premise,
+            TimeRange {
+                start_ms: 0,
+                end_ms: 60_000,
+            },
+        );
+        extra.sort_order = 1;
+        let extra_id = extra.id;
+        let act = StoryNode::new_child(
+            "Stray act",
+            StoryLevel::Act,
+            TimeRange {
+                start_ms: 0,
+                end_ms: 60_000,
+            },
+            extra_id,
+        );
+        let act_id = act.id;
+        timeline.nodes.push(extra);
+        timeline.nodes.push(act);
+
+        assert_eq!(
+            timeline.validate_all(),
+            [TimelineIssue::ExtraPremise { node_id: extra_id }]
+        );
+
+        assert_eq!(timeline.repair_premise_invariant(), [extra_id]);
+        assert!(timeline.validate_all().is_empty());
+        assert!(timeline.node(extra_id).is_err());
+        assert_eq!(timeline.node(act_id).unwrap().parent_id, Some(kept));
+    }
+
+    #[test]
+    fn hierarchy_problems_are_reported() {
+        let mut timeline = Template::MultiCam.build_project("Integrity Test").timeline;
+        let scene_id = timeline.nodes_at_level(StoryLevel::Scene)[0].id;
+        let range = TimeRange {
+            start_ms: 0,
+            end_ms: 1_000,
+        };
+        let act_under_scene = StoryNode::new_child("Misplaced", StoryLevel::Act, range, scene_id);
+        let orphan = StoryNode::new_child("Orphan", StoryLevel::Beat, range, NodeId::new());
+        let parentless = StoryNode::new("Loose", StoryLevel::Scene, range);
+        let ids = [act_under_scene.id, orphan.id, parentless.id];
+        timeline.nodes.extend([act_under_scene, orphan, parentless]);
+
+        let issues = timeline.validate_all();
+
+        assert_eq!(issues.len(), 3);
+        assert!(matches!(
+            issues[0],
+            TimelineIssue::LevelMismatch { node_id, parent_level: StoryLevel::Scene, .. }
+                if node_id == ids[0]
+        ));
+        assert!(
+            matches!(issues[1], TimelineIssue::OrphanedNode { node_id, .. } if node_id == ids[1])
+        );
+        assert!(
+            matches!(issues[2], TimelineIssue::MissingParent { node_id, .. } if node_id == ids[2])
+        );
+    }
+}
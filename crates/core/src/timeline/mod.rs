@@ -1,19 +1,30 @@
+pub mod beat_palette;
+pub mod generation_order;
+pub mod integrity;
 pub mod node;
+pub mod normalize;
 pub mod relationship;
+pub mod scene_numbers;
 pub mod structure;
 pub mod timing;
 pub mod track;
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use crate::error::{Error, Result};
 use crate::story::arc::ArcId;
-use node::{NodeArc, NodeId, StoryLevel, StoryNode};
-use relationship::{Relationship, RelationshipId};
+use node::{BeatType, NodeArc, NodeId, StoryLevel, StoryNode};
+use relationship::{Relationship, RelationshipId, RelationshipType};
 use structure::EpisodeStructure;
 use timing::TimeRange;
 use track::{Track, TrackId};
 
+/// Shortest uncovered stretch of a parent worth filling with new children.
+pub const MIN_CHILD_GAP_MS: u64 = 1_000;
+
 /// A gap on a track where no story node exists.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimelineGap {
@@ -23,6 +34,17 @@ pub struct TimelineGap {
     pub following_node_id: Option<NodeId>,
 }
 
+/// A story node with its arc tags and children inline, for clients that want
+/// the hierarchy rather than the flat node list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineTreeNode {
+    #[serde(flatten)]
+    pub node: StoryNode,
+    pub arc_ids: Vec<ArcId>,
+    /// Ordered by `sort_order`, then start time.
+    pub children: Vec<TimelineTreeNode>,
+}
+
 /// The central data structure: a timeline with hierarchy-level tracks.
 ///
 /// Represents the full runtime of an episode (~22 min for 30-min TV). Tracks
@@ -117,6 +139,39 @@ impl Timeline {
         children
     }
 
+    /// The node hierarchy as nested trees.
+    ///
+    /// With `root`, returns just that node's subtree. Otherwise returns one
+    /// tree per top-level node (no parent, or a parent that no longer
+    /// exists), ordered by level, `sort_order`, then start time.
+    pub fn node_tree(&self, root: Option<NodeId>) -> Result<Vec<TimelineTreeNode>> {
+        let roots = match root {
+            Some(root_id) => vec![self.node(root_id)?],
+            None => {
+                let mut roots: Vec<&StoryNode> = self
+                    .nodes
+                    .iter()
+                    .filter(|n| n.parent_id.is_none_or(|pid| self.node(pid).is_err()))
+                    .collect();
+                roots.sort_by_key(|n| (n.level, n.sort_order, n.time_range.start_ms));
+                roots
+            }
+        };
+        Ok(roots.into_iter().map(|n| self.tree_from(n)).collect())
+    }
+
+    fn tree_from(&self, node: &StoryNode) -> TimelineTreeNode {
+        TimelineTreeNode {
+            node: node.clone(),
+            arc_ids: self.arcs_for_node(node.id),
+            children: self
+                .children_of(node.id)
+                .into_iter()
+                .map(|child| self.tree_from(child))
+                .collect(),
+        }
+    }
+
     /// Get all descendants of a node (recursive).
     pub fn descendants_of(&self, parent_id: NodeId) -> Vec<&StoryNode> {
         let mut result = Vec::new();
@@ -253,8 +308,8 @@ impl Timeline {
 
         let node = self.node(node_id)?;
         let old_range = node.time_range;
-        let old_duration = old_range.end_ms - old_range.start_ms;
-        let new_duration = new_range.end_ms - new_range.start_ms;
+        let old_duration = old_range.duration_ms();
+        let new_duration = new_range.duration_ms();
 
         // Collect descendant IDs before mutating.
         let descendant_ids: Vec<NodeId> =
@@ -280,6 +335,15 @@ impl Timeline {
                     desc.time_range.end_ms = (new_range.start_ms
                         + (end_ratio * new_duration as f64) as u64)
                         .min(new_range.end_ms);
+
+                    // Shrinking can round a descendant down to nothing; keep
+                    // it at least 1ms long and inside the new range.
+                    if desc.time_range.end_ms <= desc.time_range.start_ms {
+                        desc.time_range.end_ms =
+                            (desc.time_range.start_ms + 1).min(new_range.end_ms);
+                        desc.time_range.start_ms =
+                            desc.time_range.start_ms.min(desc.time_range.end_ms - 1);
+                    }
                 }
             }
         }
@@ -287,8 +351,120 @@ impl Timeline {
         Ok(())
     }
 
+    /// Copy a node under the same parent, shifted by `offset_ms`, and return
+    /// `(source, copy)` id pairs with the copied node first.
+    ///
+    /// With `recursive`, every descendant is copied too, keeping its place in
+    /// the copied hierarchy. The copied node is named "<name> (copy)";
+    /// descendants keep their names. Content, arc tags, and relationships
+    /// whose endpoints are both copied come along; scene numbers do not.
+    /// `new_id` picks the id for each copied node and relationship from its
+    /// source id.
+    ///
+    /// Fails if the copy would leave the timeline or its parent's range.
+    pub fn duplicate_node(
+        &mut self,
+        node_id: NodeId,
+        recursive: bool,
+        offset_ms: i64,
+        mut new_id: impl FnMut(Uuid) -> Uuid,
+    ) -> Result<Vec<(NodeId, NodeId)>> {
+        let node = self.node(node_id)?;
+        if node.level == StoryLevel::Premise {
+            return Err(Error::InvalidHierarchy(
+                "only one Premise node is allowed".to_string(),
+            ));
+        }
+        let copy_range = node
+            .time_range
+            .shifted(offset_ms)
+            .ok_or_else(|| Error::InvalidOperation("duplicate would start before 0:00".into()))?;
+        if copy_range.end_ms > self.total_duration_ms {
+            return Err(Error::NodeExceedsTimeline {
+                node_end_ms: copy_range.end_ms,
+                timeline_ms: self.total_duration_ms,
+            });
+        }
+        if let Some(parent_id) = node.parent_id {
+            let parent = self.node(parent_id)?.time_range;
+            if copy_range.start_ms < parent.start_ms || copy_range.end_ms > parent.end_ms {
+                return Err(Error::InvalidOperation(format!(
+                    "duplicate {}..{}ms falls outside its parent {}..{}ms",
+                    copy_range.start_ms, copy_range.end_ms, parent.start_ms, parent.end_ms
+                )));
+            }
+        }
+
+        let mut sources = vec![node];
+        if recursive {
+            sources.extend(self.descendants_of(node_id));
+        }
+        let id_map: HashMap<NodeId, NodeId> = sources
+            .iter()
+            .map(|source| (source.id, NodeId(new_id(source.id.0))))
+            .collect();
+        if self
+            .nodes
+            .iter()
+            .any(|n| id_map.values().any(|id| *id == n.id))
+        {
+            return Err(Error::InvalidOperation(
+                "duplicate node ids already exist".to_string(),
+            ));
+        }
+
+        let mut copies = Vec::with_capacity(sources.len());
+        let mut pairs = Vec::with_capacity(sources.len());
+        let mut tags = Vec::new();
+        for source in sources {
+            let copy_id = id_map[&source.id];
+            let mut copy = source.clone();
+            copy.id = copy_id;
+            copy.parent_id = source
+                .parent_id
+                .map(|pid| id_map.get(&pid).copied().unwrap_or(pid));
+            copy.time_range = source.time_range.shifted(offset_ms).ok_or_else(|| {
+                Error::InvalidOperation("duplicate would start before 0:00".into())
+            })?;
+            if source.id == node_id {
+                copy.name = format!("{} (copy)", source.name);
+            }
+            copy.content.reconcile_status(false);
+            // A copy is a new scene; renumbering gives it its own number.
+            copy.scene_number = None;
+            tags.extend(
+                self.arcs_for_node(source.id)
+                    .into_iter()
+                    .map(|arc_id| NodeArc {
+                        node_id: copy_id,
+                        arc_id,
+                    }),
+            );
+            pairs.push((source.id, copy_id));
+            copies.push(copy);
+        }
+        let mut relationships: Vec<Relationship> = self
+            .relationships
+            .iter()
+            .filter(|rel| id_map.contains_key(&rel.from_node) && id_map.contains_key(&rel.to_node))
+            .map(|rel| Relationship {
+                id: RelationshipId(new_id(rel.id.0)),
+                ..rel.clone()
+            })
+            .collect();
+        remap_endpoints(&mut relationships, &id_map);
+
+        self.nodes.extend(copies);
+        self.node_arcs.extend(tags);
+        self.relationships.extend(relationships);
+        Ok(pairs)
+    }
+
     /// Split a node at the given time point, producing two nodes.
     /// Returns the IDs of the two resulting nodes.
+    ///
+    /// The left node keeps the original's notes, script and recap; the right
+    /// node starts empty.
     pub fn split_node(
         &mut self,
         node_id: NodeId,
@@ -327,7 +503,10 @@ impl Timeline {
         let beat_type = node.beat_type.clone();
         let name = node.name.clone();
         let locked = node.locked;
+        let pinned = node.pinned;
+        let scene_number = node.scene_number.clone();
         let sort_order = node.sort_order;
+        let content = node.content.clone();
 
         let left = StoryNode {
             id: left_id,
@@ -336,9 +515,11 @@ impl Timeline {
             sort_order,
             time_range: TimeRange::new(range.start_ms, at_ms)?,
             name: format!("{} (L)", name),
-            content: node::NodeContent::default(),
+            content,
             beat_type: beat_type.clone(),
             locked,
+            pinned,
+            scene_number,
         };
 
         let right = StoryNode {
@@ -351,6 +532,8 @@ impl Timeline {
             content: node::NodeContent::default(),
             beat_type,
             locked,
+            pinned: false,
+            scene_number: None,
         };
 
         // Remove the original node (but NOT its descendants — they'll be reassigned).
@@ -364,8 +547,7 @@ impl Timeline {
         // Reassign children to left or right based on midpoint.
         for child in &mut self.nodes {
             if child.parent_id == Some(node_id) {
-                let child_mid = child.time_range.start_ms
-                    + (child.time_range.end_ms - child.time_range.start_ms) / 2;
+                let child_mid = child.time_range.start_ms + child.time_range.duration_ms() / 2;
                 if child_mid < at_ms {
                     child.parent_id = Some(left_id);
                 } else {
@@ -374,20 +556,20 @@ impl Timeline {
             }
         }
 
+        // Repoint relationships to the half nearest the other endpoint.
+        let repointed: Vec<_> = self
+            .relationships
+            .iter()
+            .map(|rel| {
+                Some(self.split_relationship_endpoints(rel, node_id, at_ms, left_id, right_id))
+            })
+            .collect();
+        rewrite_endpoints(&mut self.relationships, repointed);
+
         // Add the two new nodes (bypass validation since we know they fit).
         self.nodes.push(left);
         self.nodes.push(right);
 
-        // Repoint relationships.
-        for rel in &mut self.relationships {
-            if rel.from_node == node_id {
-                rel.from_node = left_id;
-            }
-            if rel.to_node == node_id {
-                rel.to_node = right_id;
-            }
-        }
-
         // Repoint arc tags.
         let arc_ids: Vec<ArcId> = self.arcs_for_node(node_id);
         self.node_arcs.retain(|na| na.node_id != node_id);
@@ -405,6 +587,34 @@ impl Timeline {
         Ok((left_id, right_id))
     }
 
+    /// Endpoints a relationship should have after `node_id` is split at
+    /// `at_ms` into `left_id` and `right_id`.
+    ///
+    /// An endpoint on the split node moves to whichever half the other
+    /// endpoint sits closer to in time (by midpoint, ties going right). A
+    /// relationship from the split node to itself stays on the left half,
+    /// which keeps the original content.
+    pub fn split_relationship_endpoints(
+        &self,
+        rel: &Relationship,
+        node_id: NodeId,
+        at_ms: u64,
+        left_id: NodeId,
+        right_id: NodeId,
+    ) -> (NodeId, NodeId) {
+        let side_near = |other: NodeId| match self.node(other) {
+            Ok(n) if n.time_range.start_ms + n.time_range.duration_ms() / 2 < at_ms => left_id,
+            Ok(_) => right_id,
+            Err(_) => left_id,
+        };
+        match (rel.from_node == node_id, rel.to_node == node_id) {
+            (true, true) => (left_id, left_id),
+            (true, false) => (side_near(rel.to_node), rel.to_node),
+            (false, true) => (rel.from_node, side_near(rel.from_node)),
+            (false, false) => (rel.from_node, rel.to_node),
+        }
+    }
+
     // ────────────────── Arc tagging ──────────────────
 
     /// Get all arc IDs tagged on a node.
@@ -425,6 +635,45 @@ impl Timeline {
             .collect()
     }
 
+    /// The span an arc is active in: from the earliest start to the latest
+    /// end of its tagged nodes. `None` when no node carries the arc.
+    pub fn arc_time_window(&self, arc_id: ArcId) -> Option<TimeRange> {
+        let ranges = self
+            .nodes_for_arc(arc_id)
+            .into_iter()
+            .filter_map(|id| self.node(id).ok())
+            .map(|node| node.time_range);
+        ranges.reduce(|window, range| TimeRange {
+            start_ms: window.start_ms.min(range.start_ms),
+            end_ms: window.end_ms.max(range.end_ms),
+        })
+    }
+
+    /// The merged spans where an arc is present, from its tagged nodes, in
+    /// time order. Touching or overlapping nodes form one span.
+    pub fn arc_coverage(&self, arc_id: ArcId) -> Vec<TimeRange> {
+        timing::merge_time_ranges(
+            self.nodes_for_arc(arc_id)
+                .into_iter()
+                .filter_map(|id| self.node(id).ok())
+                .map(|node| node.time_range),
+        )
+    }
+
+    /// Stretches longer than `min_gap_ms` between an arc's covered spans,
+    /// where the storyline drops out. Time before the arc first appears or
+    /// after it last appears is not a gap.
+    pub fn arc_coverage_gaps(&self, arc_id: ArcId, min_gap_ms: u64) -> Vec<TimeRange> {
+        self.arc_coverage(arc_id)
+            .windows(2)
+            .map(|pair| TimeRange {
+                start_ms: pair[0].end_ms,
+                end_ms: pair[1].start_ms,
+            })
+            .filter(|gap| gap.duration_ms() > min_gap_ms)
+            .collect()
+    }
+
     /// Tag a node with an arc. No-op if already tagged.
     pub fn tag_node(&mut self, node_id: NodeId, arc_id: ArcId) {
         if !self
@@ -442,16 +691,126 @@ impl Timeline {
             .retain(|na| !(na.node_id == node_id && na.arc_id == arc_id));
     }
 
+    /// Tag a node and all its descendants with an arc. Returns the nodes that
+    /// gained the tag; already-tagged nodes are skipped, so repeating the
+    /// call changes nothing.
+    pub fn tag_node_recursive(&mut self, node_id: NodeId, arc_id: ArcId) -> Result<Vec<NodeId>> {
+        let tagged: Vec<NodeId> = self
+            .subtree_ids(node_id)?
+            .into_iter()
+            .filter(|&id| !self.arcs_for_node(id).contains(&arc_id))
+            .collect();
+        self.node_arcs
+            .extend(tagged.iter().map(|&node_id| NodeArc { node_id, arc_id }));
+        Ok(tagged)
+    }
+
+    /// Remove an arc tag from a node and all its descendants. Returns the
+    /// nodes that lost the tag.
+    pub fn untag_node_recursive(&mut self, node_id: NodeId, arc_id: ArcId) -> Result<Vec<NodeId>> {
+        let untagged: Vec<NodeId> = self
+            .subtree_ids(node_id)?
+            .into_iter()
+            .filter(|&id| self.arcs_for_node(id).contains(&arc_id))
+            .collect();
+        self.node_arcs
+            .retain(|na| !(na.arc_id == arc_id && untagged.contains(&na.node_id)));
+        Ok(untagged)
+    }
+
+    /// `node_id` followed by all its descendants.
+    fn subtree_ids(&self, node_id: NodeId) -> Result<Vec<NodeId>> {
+        self.node(node_id)?;
+        Ok(std::iter::once(node_id)
+            .chain(self.descendants_of(node_id).iter().map(|node| node.id))
+            .collect())
+    }
+
     // ────────────────── Relationships ──────────────────
 
     /// Add a relationship between two nodes.
     pub fn add_relationship(&mut self, rel: Relationship) -> Result<()> {
         self.node(rel.from_node)?;
         self.node(rel.to_node)?;
+        if rel.relationship_type == RelationshipType::Payoff {
+            self.validate_payoff(rel.from_node, rel.to_node)?;
+        }
         self.relationships.push(rel);
         Ok(())
     }
 
+    /// Move relationship endpoints off `node_id`: a relationship starting
+    /// there now starts at `to_from`, one ending there now ends at `to_to`.
+    /// A `None` replacement drops the relationships on that side instead.
+    ///
+    /// Follows the rules of [`Self::remap_relationships`] and returns the ids
+    /// of every relationship dropped.
+    pub fn repoint_relationships(
+        &mut self,
+        node_id: NodeId,
+        to_from: Option<NodeId>,
+        to_to: Option<NodeId>,
+    ) -> Vec<RelationshipId> {
+        let endpoints = self
+            .relationships
+            .iter()
+            .map(|rel| {
+                let from_node = match rel.from_node == node_id {
+                    true => to_from?,
+                    false => rel.from_node,
+                };
+                let to_node = match rel.to_node == node_id {
+                    true => to_to?,
+                    false => rel.to_node,
+                };
+                Some((from_node, to_node))
+            })
+            .collect();
+        rewrite_endpoints(&mut self.relationships, endpoints)
+    }
+
+    /// Replace every relationship endpoint that has an entry in `id_map`.
+    ///
+    /// Every operation that moves relationship endpoints (split, repoint,
+    /// duplicate) shares these rules: a relationship the move turns into a
+    /// self-loop is dropped, and so is a moved relationship that now has the
+    /// same endpoints and type as one that did not move or moved before it.
+    /// Relationships that do not move are never dropped. Returns the ids of
+    /// every relationship dropped.
+    pub fn remap_relationships(&mut self, id_map: &HashMap<NodeId, NodeId>) -> Vec<RelationshipId> {
+        remap_endpoints(&mut self.relationships, id_map)
+    }
+
+    /// A payoff can't start before the setup it pays off.
+    pub fn validate_payoff(&self, setup_id: NodeId, payoff_id: NodeId) -> Result<()> {
+        let setup = self.node(setup_id)?;
+        let payoff = self.node(payoff_id)?;
+        if payoff.time_range.start_ms < setup.time_range.start_ms {
+            return Err(Error::InvalidOperation(format!(
+                "payoff {} starts before its setup {}",
+                payoff.name, setup.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Setup beats with no outgoing [`RelationshipType::Payoff`], in time
+    /// order.
+    pub fn unpaid_setups(&self) -> Vec<NodeId> {
+        let mut setups: Vec<&StoryNode> = self
+            .nodes
+            .iter()
+            .filter(|node| node.beat_type == Some(BeatType::Setup))
+            .filter(|node| {
+                !self.relationships.iter().any(|rel| {
+                    rel.from_node == node.id && rel.relationship_type == RelationshipType::Payoff
+                })
+            })
+            .collect();
+        setups.sort_by_key(|node| (node.time_range.start_ms, node.level));
+        setups.into_iter().map(|node| node.id).collect()
+    }
+
     /// Remove a relationship by ID.
     pub fn remove_relationship(&mut self, id: RelationshipId) -> Result<Relationship> {
         let idx = self
@@ -472,47 +831,64 @@ impl Timeline {
             .collect()
     }
 
+    /// Every node, at any level, whose range overlaps `range` (shares at least
+    /// one instant; see [`TimeRange::overlaps`]), sorted by level then start.
+    pub fn nodes_in_range(&self, range: TimeRange) -> Vec<&StoryNode> {
+        let mut nodes: Vec<&StoryNode> = self
+            .nodes
+            .iter()
+            .filter(|n| n.time_range.overlaps(&range))
+            .collect();
+        nodes.sort_by_key(|n| (n.level, n.time_range.start_ms));
+        nodes
+    }
+
+    /// Pinned nodes in timeline order, for quick-jump navigation.
+    pub fn pinned_nodes(&self) -> Vec<&StoryNode> {
+        let mut nodes: Vec<&StoryNode> = self.nodes.iter().filter(|n| n.pinned).collect();
+        nodes.sort_by_key(|n| (n.time_range.start_ms, n.level));
+        nodes
+    }
+
     /// Find gaps at a given level where no nodes exist.
     pub fn find_gaps(&self, level: StoryLevel, min_duration_ms: u64) -> Vec<TimelineGap> {
-        let nodes = self.nodes_at_level(level);
-        let mut gaps = Vec::new();
-        let mut cursor = 0u64;
-        let mut prev_node_id: Option<NodeId> = None;
-
-        for node in &nodes {
-            if node.time_range.start_ms > cursor {
-                let duration = node.time_range.start_ms - cursor;
-                if duration >= min_duration_ms {
-                    if let Ok(range) = TimeRange::new(cursor, node.time_range.start_ms) {
-                        gaps.push(TimelineGap {
-                            level,
-                            time_range: range,
-                            preceding_node_id: prev_node_id,
-                            following_node_id: Some(node.id),
-                        });
-                    }
-                }
-            }
-            cursor = node.time_range.end_ms;
-            prev_node_id = Some(node.id);
-        }
+        let span = TimeRange {
+            start_ms: 0,
+            end_ms: self.total_duration_ms,
+        };
+        collect_gaps(&self.nodes_at_level(level), level, span, min_duration_ms)
+    }
 
-        // Gap between last node and timeline end.
-        if cursor < self.total_duration_ms {
-            let duration = self.total_duration_ms - cursor;
-            if duration >= min_duration_ms {
-                if let Ok(range) = TimeRange::new(cursor, self.total_duration_ms) {
-                    gaps.push(TimelineGap {
-                        level,
-                        time_range: range,
-                        preceding_node_id: prev_node_id,
-                        following_node_id: None,
-                    });
-                }
-            }
+    /// Split `parent`'s range among new children by `weights`. Acts under a
+    /// Premise take their episode structure segments instead when there is
+    /// one per act, so the acts tile the structure exactly.
+    pub fn split_child_ranges(&self, parent: &StoryNode, weights: &[f32]) -> Vec<TimeRange> {
+        if parent.level == StoryLevel::Premise
+            && let Some(ranges) = self.structure.act_ranges(parent.time_range, weights.len())
+        {
+            return ranges;
         }
+        timing::distribute_weighted_ranges(parent.time_range, weights)
+    }
 
-        gaps
+    /// Find the stretches of a parent's range that none of its children cover.
+    pub fn find_child_gaps(
+        &self,
+        parent_id: NodeId,
+        min_duration_ms: u64,
+    ) -> Result<Vec<TimelineGap>> {
+        let parent = self.node(parent_id)?;
+        let level = parent.level.child_level().ok_or_else(|| {
+            Error::InvalidHierarchy(format!("{} nodes cannot have children", parent.level))
+        })?;
+        let mut children = self.children_of(parent_id);
+        children.sort_by_key(|node| node.time_range.start_ms);
+        Ok(collect_gaps(
+            &children,
+            level,
+            parent.time_range,
+            min_duration_ms,
+        ))
     }
 
     /// Remove all children of a specific parent node.
@@ -537,3 +913,564 @@ impl Timeline {
         Ok(())
     }
 }
+
+fn remap_endpoints(
+    relationships: &mut Vec<Relationship>,
+    id_map: &HashMap<NodeId, NodeId>,
+) -> Vec<RelationshipId> {
+    let endpoints = relationships
+        .iter()
+        .map(|rel| {
+            let remap = |id: NodeId| id_map.get(&id).copied().unwrap_or(id);
+            Some((remap(rel.from_node), remap(rel.to_node)))
+        })
+        .collect();
+    rewrite_endpoints(relationships, endpoints)
+}
+
+/// Give each relationship the endpoints at its index, dropping those with
+/// `None` and those the move degenerates (see
+/// [`Timeline::remap_relationships`]). Returns the ids dropped.
+fn rewrite_endpoints(
+    relationships: &mut Vec<Relationship>,
+    endpoints: Vec<Option<(NodeId, NodeId)>>,
+) -> Vec<RelationshipId> {
+    let mut dropped = Vec::new();
+    let mut moved = Vec::new();
+    let mut kept: Vec<Relationship> = Vec::with_capacity(relationships.len());
+    for (rel, endpoints) in relationships.drain(..).zip(endpoints) {
+        match endpoints {
+            None => dropped.push(rel.id),
+            Some(endpoints) if endpoints == (rel.from_node, rel.to_node) => kept.push(rel),
+            Some(endpoints) => moved.push((kept.len(), rel, endpoints)),
+        }
+    }
+    // Moved relationships go back in their original order, after checking
+    // them against everything that stayed put and every earlier move.
+    let mut offset = 0;
+    for (index, mut rel, (from_node, to_node)) in moved {
+        let became_loop = from_node == to_node && rel.from_node != rel.to_node;
+        let duplicate = kept.iter().any(|other| {
+            (other.from_node, other.to_node) == (from_node, to_node)
+                && other.relationship_type == rel.relationship_type
+        });
+        if became_loop || duplicate {
+            dropped.push(rel.id);
+            continue;
+        }
+        rel.from_node = from_node;
+        rel.to_node = to_node;
+        kept.insert(index + offset, rel);
+        offset += 1;
+    }
+    *relationships = kept;
+    dropped
+}
+
+/// Gaps within `span` not covered by `nodes`, which must be sorted by start.
+fn collect_gaps(
+    nodes: &[&StoryNode],
+    level: StoryLevel,
+    span: TimeRange,
+    min_duration_ms: u64,
+) -> Vec<TimelineGap> {
+    let mut gaps = Vec::new();
+    let mut cursor = span.start_ms;
+    let mut prev_node_id: Option<NodeId> = None;
+
+    for node in nodes {
+        if node.time_range.start_ms > cursor {
+            let duration = node.time_range.start_ms - cursor;
+            if duration >= min_duration_ms {
+                if let Ok(range) = TimeRange::new(cursor, node.time_range.start_ms) {
+                    gaps.push(TimelineGap {
+                        level,
+                        time_range: range,
+                        preceding_node_id: prev_node_id,
+                        following_node_id: Some(node.id),
+                    });
+                }
+            }
+        }
+        cursor = cursor.max(node.time_range.end_ms);
+        prev_node_id = Some(node.id);
+    }
+
+    // Gap between the last node and the end of the span.
+    if cursor < span.end_ms {
+        let duration = span.end_ms - cursor;
+        if duration >= min_duration_ms {
+            if let Ok(range) = TimeRange::new(cursor, span.end_ms) {
+                gaps.push(TimelineGap {
+                    level,
+                    time_range: range,
+                    preceding_node_id: prev_node_id,
+                    following_node_id: None,
+                });
+            }
+        }
+    }
+
+    gaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Template;
+    use timing::distribute_weighted_ranges;
+
+    fn assert_all_ranges_valid(timeline: &Timeline) {
+        for node in &timeline.nodes {
+            assert!(
+                node.time_range.validate().is_ok(),
+                "{} has an empty range {:?}",
+                node.name,
+                node.time_range
+            );
+        }
+    }
+
+    #[test]
+    fn split_one_millisecond_from_either_edge_leaves_valid_halves() {
+        let timeline = Template::MultiCam.build_project("Split Bounds").timeline;
+        for node in timeline.nodes_at_level(StoryLevel::Scene) {
+            let range = node.time_range;
+            for at_ms in [range.start_ms + 1, range.end_ms - 1] {
+                let mut split = timeline.clone();
+                let (left, right) = split
+                    .split_node(node.id, at_ms, NodeId::new(), NodeId::new())
+                    .unwrap();
+
+                let left = split.node(left).unwrap().time_range;
+                let right = split.node(right).unwrap().time_range;
+                assert_eq!((left.start_ms, left.end_ms), (range.start_ms, at_ms));
+                assert_eq!((right.start_ms, right.end_ms), (at_ms, range.end_ms));
+                assert_eq!(
+                    left.duration_ms() + right.duration_ms(),
+                    range.duration_ms()
+                );
+                assert_all_ranges_valid(&split);
+            }
+        }
+    }
+
+    #[test]
+    fn split_at_either_edge_is_rejected() {
+        let mut timeline = Template::MultiCam.build_project("Split Edges").timeline;
+        let node = timeline.nodes_at_level(StoryLevel::Scene)[0].clone();
+        for at_ms in [node.time_range.start_ms, node.time_range.end_ms] {
+            assert!(matches!(
+                timeline.split_node(node.id, at_ms, NodeId::new(), NodeId::new()),
+                Err(Error::SplitOutOfRange { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn resize_to_tiny_range_keeps_descendants_non_empty_and_inside() {
+        let timeline = Template::MultiCam.build_project("Resize Bounds").timeline;
+        let act = timeline.nodes_at_level(StoryLevel::Act)[1].clone();
+        let start = act.time_range.start_ms;
+        for new_duration in [1, 2, 3, 7] {
+            let mut resized = timeline.clone();
+            let new_range = TimeRange::new(start, start + new_duration).unwrap();
+            resized.resize_node(act.id, new_range).unwrap();
+
+            for child in resized.descendants_of(act.id) {
+                assert!(child.time_range.start_ms >= new_range.start_ms);
+                assert!(child.time_range.end_ms <= new_range.end_ms);
+            }
+            assert_all_ranges_valid(&resized);
+        }
+    }
+
+    #[test]
+    fn find_child_gaps_reports_only_uncovered_parts_of_the_parent() {
+        let mut timeline = Template::MultiCam.build_project("Child Gaps").timeline;
+        let act = timeline.nodes_at_level(StoryLevel::Act)[0].clone();
+        timeline.clear_children_of(act.id).unwrap();
+        let level = act.level.child_level().unwrap();
+        let ranges = distribute_weighted_ranges(act.time_range, &[1.0, 1.0, 1.0]);
+        let children: Vec<NodeId> = ranges
+            .iter()
+            .map(|range| {
+                let child = StoryNode::new_child("Child", level, *range, act.id);
+                let id = child.id;
+                timeline.add_node(child).unwrap();
+                id
+            })
+            .collect();
+        assert!(timeline.find_child_gaps(act.id, 1).unwrap().is_empty());
+
+        timeline.nodes.retain(|node| node.id != children[1]);
+        let gaps = timeline.find_child_gaps(act.id, 1).unwrap();
+
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].time_range, ranges[1]);
+        assert_eq!(gaps[0].preceding_node_id, Some(children[0]));
+        assert_eq!(gaps[0].following_node_id, Some(children[2]));
+    }
+
+    #[test]
+    fn nodes_in_range_returns_overlapping_nodes_at_every_level() {
+        let timeline = Template::MultiCam.build_project("Range Query").timeline;
+        let names = |range| {
+            timeline
+                .nodes_in_range(range)
+                .into_iter()
+                .map(|n| (n.level, n.name.as_str()))
+                .collect::<Vec<_>>()
+        };
+        let premise = timeline.nodes_at_level(StoryLevel::Premise)[0]
+            .name
+            .as_str();
+
+        // Straddles the boundary between the Cold Open's two scenes.
+        assert_eq!(
+            names(TimeRange::new(30_000, 90_000).unwrap()),
+            vec![
+                (StoryLevel::Premise, premise),
+                (StoryLevel::Act, "Cold Open"),
+                (StoryLevel::Scene, "A: Setup"),
+                (StoryLevel::Scene, "C: Beat"),
+            ]
+        );
+        // A scene that ends exactly where the window starts is excluded.
+        assert_eq!(
+            names(TimeRange::new(60_000, 90_000).unwrap()),
+            vec![
+                (StoryLevel::Premise, premise),
+                (StoryLevel::Act, "Cold Open"),
+                (StoryLevel::Scene, "C: Beat"),
+            ]
+        );
+    }
+
+    #[test]
+    fn arc_time_window_spans_tagged_nodes() {
+        let mut timeline = Template::MultiCam.build_project("Arc Window").timeline;
+        let scenes: Vec<(NodeId, TimeRange)> = timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .iter()
+            .map(|node| (node.id, node.time_range))
+            .collect();
+        let arc_id = ArcId::new();
+        assert_eq!(timeline.arc_time_window(arc_id), None);
+
+        timeline.tag_node(scenes[3].0, arc_id);
+        timeline.tag_node(scenes[1].0, arc_id);
+
+        let window = timeline.arc_time_window(arc_id).unwrap();
+        assert_eq!(window.start_ms, scenes[1].1.start_ms);
+        assert_eq!(window.end_ms, scenes[3].1.end_ms);
+    }
+
+    #[test]
+    fn arc_coverage_merges_tagged_nodes_and_reports_long_gaps() {
+        let mut timeline = Template::MultiCam.build_project("Arc Coverage").timeline;
+        let first_act = timeline.nodes_at_level(StoryLevel::Act)[0].time_range;
+        let scenes: Vec<(NodeId, TimeRange)> = timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .iter()
+            .map(|node| (node.id, node.time_range))
+            .collect();
+        let first_act_scenes: Vec<NodeId> = scenes
+            .iter()
+            .filter(|(_, range)| range.end_ms <= first_act.end_ms)
+            .map(|(id, _)| *id)
+            .collect();
+        let last_scene = scenes.last().unwrap();
+        let arc_id = ArcId::new();
+        assert!(timeline.arc_coverage(arc_id).is_empty());
+
+        for scene in &first_act_scenes {
+            timeline.tag_node(*scene, arc_id);
+        }
+        timeline.tag_node(last_scene.0, arc_id);
+
+        let coverage = timeline.arc_coverage(arc_id);
+        assert_eq!(coverage.len(), 2);
+        assert_eq!(coverage[1], last_scene.1);
+        let gap = TimeRange {
+            start_ms: coverage[0].end_ms,
+            end_ms: last_scene.1.start_ms,
+        };
+        assert_eq!(timeline.arc_coverage_gaps(arc_id, 0), vec![gap]);
+        assert!(
+            timeline
+                .arc_coverage_gaps(arc_id, gap.duration_ms())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn split_child_ranges_snaps_premise_acts_to_structure_segments() {
+        let timeline = Template::MultiCam.build_project("Split").timeline;
+        let premise = timeline.nodes_at_level(StoryLevel::Premise)[0].clone();
+        let act = timeline.nodes_at_level(StoryLevel::Act)[0].clone();
+        let segments: Vec<TimeRange> = timeline
+            .structure
+            .act_segments()
+            .map(|segment| segment.time_range)
+            .collect();
+
+        let weights = vec![1.0; segments.len()];
+        assert_eq!(timeline.split_child_ranges(&premise, &weights), segments);
+        assert_eq!(
+            timeline.split_child_ranges(&premise, &[1.0, 1.0]),
+            distribute_weighted_ranges(premise.time_range, &[1.0, 1.0])
+        );
+        assert_eq!(
+            timeline.split_child_ranges(&act, &weights),
+            distribute_weighted_ranges(act.time_range, &weights)
+        );
+    }
+
+    #[test]
+    fn node_tree_nests_children_in_order_with_arc_tags() {
+        let timeline = Template::MultiCam.build_project("Tree").timeline;
+
+        let trees = timeline.node_tree(None).unwrap();
+        assert_eq!(trees.len(), 1);
+        let premise = &trees[0];
+        assert_eq!(premise.node.level, StoryLevel::Premise);
+        let acts: Vec<&str> = premise
+            .children
+            .iter()
+            .map(|act| act.node.name.as_str())
+            .collect();
+        assert_eq!(acts[0], "Cold Open");
+        assert_eq!(acts.last(), Some(&"Tag"));
+
+        let cold_open = &premise.children[0];
+        let scenes: Vec<&str> = cold_open
+            .children
+            .iter()
+            .map(|scene| scene.node.name.as_str())
+            .collect();
+        assert_eq!(scenes, vec!["A: Setup", "C: Beat"]);
+        assert_eq!(
+            cold_open.children[0].arc_ids,
+            timeline.arcs_for_node(cold_open.children[0].node.id)
+        );
+        assert_eq!(cold_open.children[0].arc_ids.len(), 1);
+
+        let subtree = timeline.node_tree(Some(cold_open.node.id)).unwrap();
+        assert_eq!(subtree.len(), 1);
+        assert_eq!(subtree[0].children.len(), 2);
+        assert!(timeline.node_tree(Some(NodeId::new())).is_err());
+    }
+
+    #[test]
+    fn resize_rejects_empty_range() {
+        let mut timeline = Template::MultiCam.build_project("Resize Empty").timeline;
+        let act = timeline.nodes_at_level(StoryLevel::Act)[0].clone();
+        let empty = TimeRange {
+            start_ms: act.time_range.start_ms,
+            end_ms: act.time_range.start_ms,
+        };
+        assert!(matches!(
+            timeline.resize_node(act.id, empty),
+            Err(Error::InvalidTimeRange { .. })
+        ));
+    }
+
+    #[test]
+    fn duplicate_subtree_remaps_ids_tags_and_internal_relationships() {
+        let mut timeline = Template::MultiCam.build_project("Duplicate").timeline;
+        let act = timeline.nodes_at_level(StoryLevel::Act)[0].clone();
+        let scenes: Vec<NodeId> = timeline.children_of(act.id).iter().map(|n| n.id).collect();
+        timeline
+            .add_relationship(Relationship::new(
+                scenes[0],
+                scenes[1],
+                relationship::RelationshipType::Causal,
+            ))
+            .unwrap();
+        let before = timeline.nodes.len();
+
+        let pairs = timeline
+            .duplicate_node(act.id, true, 0, |_| Uuid::new_v4())
+            .unwrap();
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(pairs[0].0, act.id);
+        assert_eq!(timeline.nodes.len(), before + 3);
+        let ids: HashMap<NodeId, NodeId> = pairs.into_iter().collect();
+
+        let copy = timeline.node(ids[&act.id]).unwrap();
+        assert_eq!(copy.name, format!("{} (copy)", act.name));
+        assert_eq!(copy.parent_id, act.parent_id);
+        let copied_scenes = timeline.children_of(ids[&act.id]);
+        assert_eq!(copied_scenes.len(), 2);
+        for scene in &scenes {
+            assert_eq!(
+                timeline.arcs_for_node(*scene),
+                timeline.arcs_for_node(ids[scene])
+            );
+        }
+        assert!(
+            timeline
+                .relationships
+                .iter()
+                .any(|rel| { rel.from_node == ids[&scenes[0]] && rel.to_node == ids[&scenes[1]] })
+        );
+        assert_eq!(timeline.relationships.len(), 2);
+    }
+
+    #[test]
+    fn repoint_relationships_drops_new_self_loops_and_duplicates() {
+        let mut timeline = Template::MultiCam.build_project("Repoint").timeline;
+        let scenes: Vec<NodeId> = timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .iter()
+            .take(4)
+            .map(|n| n.id)
+            .collect();
+        let [a, b, c, d] = [scenes[0], scenes[1], scenes[2], scenes[3]];
+        let causal = |from, to| Relationship::new(from, to, RelationshipType::Causal);
+        let rels = [
+            causal(a, c),
+            causal(b, c),
+            causal(a, b),
+            Relationship::new(b, d, RelationshipType::Thematic),
+            causal(d, b),
+            causal(b, b),
+        ];
+        timeline.relationships = rels.to_vec();
+
+        // Merging b into a: b->c duplicates a->c, a->b becomes a loop, and
+        // the existing b->b loop is kept as a->a.
+        let dropped = timeline.repoint_relationships(b, Some(a), Some(a));
+
+        assert_eq!(dropped, [rels[1].id, rels[2].id]);
+        let endpoints: Vec<_> = timeline
+            .relationships
+            .iter()
+            .map(|rel| (rel.from_node, rel.to_node))
+            .collect();
+        assert_eq!(endpoints, [(a, c), (a, d), (d, a), (a, a)]);
+
+        let dropped = timeline.repoint_relationships(d, Some(c), None);
+        assert_eq!(dropped, [rels[3].id]);
+        assert_eq!(timeline.relationships[1].from_node, c);
+
+        let id_map = HashMap::from([(a, c)]);
+        assert_eq!(
+            timeline.remap_relationships(&id_map),
+            [rels[0].id, rels[4].id]
+        );
+        assert_eq!(timeline.relationships.len(), 1);
+        assert_eq!(timeline.relationships[0].id, rels[5].id);
+        assert_eq!(timeline.relationships[0].from_node, c);
+    }
+
+    #[test]
+    fn duplicate_outside_parent_or_timeline_is_rejected() {
+        let mut timeline = Template::MultiCam
+            .build_project("Duplicate Bounds")
+            .timeline;
+        let act = timeline.nodes_at_level(StoryLevel::Act)[0].clone();
+        let scene = timeline.children_of(act.id)[1].clone();
+        let before = timeline.nodes.len();
+
+        let past_parent = (act.time_range.end_ms - scene.time_range.start_ms) as i64;
+        assert!(matches!(
+            timeline.duplicate_node(scene.id, false, past_parent, |_| Uuid::new_v4()),
+            Err(Error::InvalidOperation(_))
+        ));
+        assert!(
+            timeline
+                .duplicate_node(
+                    scene.id,
+                    false,
+                    -(scene.time_range.start_ms as i64) - 1,
+                    |_| { Uuid::new_v4() }
+                )
+                .is_err()
+        );
+        let past_end = (timeline.total_duration_ms - act.time_range.start_ms) as i64;
+        assert!(matches!(
+            timeline.duplicate_node(act.id, false, past_end, |_| Uuid::new_v4()),
+            Err(Error::NodeExceedsTimeline { .. })
+        ));
+        assert_eq!(timeline.nodes.len(), before);
+
+        let pairs = timeline
+            .duplicate_node(scene.id, false, -(scene.time_range.start_ms as i64), |_| {
+                Uuid::new_v4()
+            })
+            .unwrap();
+        assert_eq!(pairs.len(), 1);
+        let copy = timeline.node(pairs[0].1).unwrap();
+        assert_eq!(copy.time_range.start_ms, act.time_range.start_ms);
+        assert_eq!(copy.parent_id, Some(act.id));
+    }
+
+    #[test]
+    fn recursive_tagging_covers_the_subtree_once() {
+        let mut timeline = Template::MultiCam.build_project("Recursive Tags").timeline;
+        let act = timeline.nodes_at_level(StoryLevel::Act)[0].id;
+        let scenes: Vec<NodeId> = timeline.children_of(act).iter().map(|n| n.id).collect();
+        let arc_id = timeline.arcs_for_node(scenes[0])[0];
+        let before = timeline.node_arcs.len();
+
+        let tagged = timeline.tag_node_recursive(act, arc_id).unwrap();
+
+        assert!(tagged.contains(&act));
+        assert!(!tagged.contains(&scenes[0]));
+        assert_eq!(timeline.node_arcs.len(), before + tagged.len());
+        assert!(
+            scenes
+                .iter()
+                .all(|&scene| timeline.arcs_for_node(scene).contains(&arc_id))
+        );
+        assert!(timeline.tag_node_recursive(act, arc_id).unwrap().is_empty());
+
+        let untagged = timeline.untag_node_recursive(act, arc_id).unwrap();
+        assert_eq!(untagged.len(), tagged.len() + 1);
+        assert!(
+            timeline
+                .nodes_for_arc(arc_id)
+                .iter()
+                .all(|id| { *id != act && !scenes.contains(id) })
+        );
+        assert!(timeline.tag_node_recursive(NodeId::new(), arc_id).is_err());
+    }
+
+    #[test]
+    fn setups_without_a_payoff_are_reported() {
+        let mut timeline = Template::MultiCam.build_project("Payoffs").timeline;
+        let unpaid = timeline.unpaid_setups();
+        assert!(unpaid.len() >= 2);
+        let setup = unpaid[0];
+        let later_scene = timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .into_iter()
+            .filter(|node| {
+                node.time_range.start_ms > timeline.node(setup).unwrap().time_range.start_ms
+            })
+            .map(|node| node.id)
+            .next()
+            .unwrap();
+
+        assert!(matches!(
+            timeline.add_relationship(Relationship::new(
+                later_scene,
+                setup,
+                RelationshipType::Payoff
+            )),
+            Err(Error::InvalidOperation(_))
+        ));
+        timeline
+            .add_relationship(Relationship::new(
+                setup,
+                later_scene,
+                RelationshipType::Payoff,
+            ))
+            .unwrap();
+
+        let remaining = timeline.unpaid_setups();
+        assert_eq!(remaining, unpaid[1..]);
+    }
+}
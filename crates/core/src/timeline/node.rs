@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::structure::SegmentType;
 use super::timing::TimeRange;
+use crate::error::{Error, Result};
 use crate::story::arc::ArcId;
 
 // ──────────────────────────────────────────────
@@ -14,7 +16,7 @@ pub struct NodeId(pub Uuid);
 
 impl NodeId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
@@ -104,6 +106,19 @@ impl std::fmt::Display for StoryLevel {
     }
 }
 
+impl std::str::FromStr for StoryLevel {
+    type Err = Error;
+
+    /// Parse a level label, ignoring case (`"scene"`, `"Scene"`).
+    fn from_str(value: &str) -> Result<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|level| level.label().eq_ignore_ascii_case(value.trim()))
+            .ok_or_else(|| Error::InvalidOperation(format!("unknown story level: {value}")))
+    }
+}
+
 // ──────────────────────────────────────────────
 // Beat Type (moved from clip.rs)
 // ──────────────────────────────────────────────
@@ -121,6 +136,31 @@ pub enum BeatType {
     Custom(String),
 }
 
+impl BeatType {
+    /// Beat types that suit a beat in `segment_type`, `relative_position` of
+    /// the way through the episode (0.0 at the start, 1.0 at the end).
+    ///
+    /// Openings set things up, middles complicate and escalate, and the final
+    /// stretch climaxes and resolves; a tag pays off. Segments that carry no
+    /// story (titles, commercial breaks) have no expectation.
+    pub fn expected_for_position(segment_type: SegmentType, relative_position: f32) -> Vec<Self> {
+        use BeatType::*;
+
+        match segment_type {
+            SegmentType::MainTitles | SegmentType::CommercialBreak => Vec::new(),
+            SegmentType::ColdOpen => vec![Setup, Complication],
+            SegmentType::Tag => vec![Resolution, Payoff, Callback],
+            SegmentType::Act if relative_position < 1.0 / 3.0 => {
+                vec![Setup, Complication, Escalation]
+            }
+            SegmentType::Act if relative_position < 2.0 / 3.0 => {
+                vec![Complication, Escalation, Payoff, Callback]
+            }
+            SegmentType::Act => vec![Escalation, Climax, Resolution, Payoff, Callback],
+        }
+    }
+}
+
 // ──────────────────────────────────────────────
 // Content Status (moved from clip.rs)
 // ──────────────────────────────────────────────
@@ -164,6 +204,103 @@ pub struct NodeContent {
     /// Server-computed, not CRDT-managed.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scene_recap: Option<String>,
+    /// Fingerprint of the notes the current content was generated from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes_hash_at_generation: Option<String>,
+    /// Batch generation order among nodes with met dependencies; lower goes
+    /// first, and unset goes after every set value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub generation_priority: Option<i32>,
+    /// Nodes whose content must be generated before this one's.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<NodeId>,
+}
+
+impl NodeContent {
+    /// Move to `status`, rejecting transitions the current text can't back.
+    ///
+    /// `Generating` may only start from `NotesOnly` (or `HasContent`, for a
+    /// regeneration). `HasContent` needs cached content unless it follows a
+    /// generation, whose script lands in the script document rather than here.
+    pub fn transition_to(&mut self, status: ContentStatus) -> Result<()> {
+        use ContentStatus::*;
+
+        let legal = match (self.status, status) {
+            (from, to) if from == to => true,
+            (_, Empty) => self.notes.is_empty() && self.content.is_empty(),
+            (_, NotesOnly) => !self.notes.is_empty(),
+            (NotesOnly | HasContent, Generating) => true,
+            (_, Generating) => false,
+            (Generating, HasContent) => true,
+            (_, HasContent) => !self.content.is_empty(),
+        };
+        if !legal {
+            return Err(Error::InvalidStatusTransition {
+                from: self.status,
+                to: status,
+            });
+        }
+        self.status = status;
+        Ok(())
+    }
+
+    /// The status implied by the cached text and whether a generation is in
+    /// flight for this node.
+    ///
+    /// An existing `HasContent` is kept even with an empty cache, since
+    /// generated script is stored in the script document.
+    pub fn derived_status(&self, generating: bool) -> ContentStatus {
+        if generating {
+            ContentStatus::Generating
+        } else if !self.content.is_empty() || self.status == ContentStatus::HasContent {
+            ContentStatus::HasContent
+        } else if !self.notes.is_empty() {
+            ContentStatus::NotesOnly
+        } else {
+            ContentStatus::Empty
+        }
+    }
+
+    /// Reset `status` to [`derived_status`](Self::derived_status). Returns
+    /// whether it changed, e.g. a node left `Generating` by a crash.
+    pub fn reconcile_status(&mut self, generating: bool) -> bool {
+        let status = self.derived_status(generating);
+        let changed = status != self.status;
+        self.status = status;
+        changed
+    }
+
+    /// Re-derive `status` after a text edit without ending an in-flight
+    /// generation.
+    pub fn refresh_status(&mut self) {
+        let generating = self.status == ContentStatus::Generating;
+        self.reconcile_status(generating);
+    }
+
+    /// Remember `notes` as the notes the current content was generated from.
+    pub fn record_generation_notes(&mut self, notes: &str) {
+        self.notes_hash_at_generation = Some(notes_fingerprint(notes));
+    }
+
+    /// Whether the notes changed since the content was generated. Content
+    /// that was never generated is not stale.
+    pub fn notes_stale(&self) -> bool {
+        self.notes_hash_at_generation
+            .as_ref()
+            .is_some_and(|hash| *hash != notes_fingerprint(&self.notes))
+    }
+}
+
+/// Stable FNV-1a fingerprint of notes, ignoring leading and trailing
+/// whitespace.
+fn notes_fingerprint(notes: &str) -> String {
+    let hash = notes
+        .trim()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{hash:016x}")
 }
 
 // ──────────────────────────────────────────────
@@ -193,6 +330,12 @@ pub struct StoryNode {
     pub beat_type: Option<BeatType>,
     /// If true, AI won't regenerate this node's content.
     pub locked: bool,
+    /// Bookmarked for quick navigation in long timelines.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Production scene number ("12", "12A"); only meaningful at Scene level.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scene_number: Option<String>,
 }
 
 impl StoryNode {
@@ -207,6 +350,8 @@ impl StoryNode {
             content: NodeContent::default(),
             beat_type: None,
             locked: false,
+            pinned: false,
+            scene_number: None,
         }
     }
 
@@ -226,6 +371,8 @@ impl StoryNode {
             content: NodeContent::default(),
             beat_type: Some(beat_type),
             locked: false,
+            pinned: false,
+            scene_number: None,
         }
     }
 
@@ -245,6 +392,8 @@ impl StoryNode {
             content: NodeContent::default(),
             beat_type: None,
             locked: false,
+            pinned: false,
+            scene_number: None,
         }
     }
 
@@ -268,3 +417,68 @@ pub struct NodeArc {
     pub node_id: NodeId,
     pub arc_id: ArcId,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(notes: &str, text: &str, status: ContentStatus) -> NodeContent {
+        NodeContent {
+            notes: notes.into(),
+            content: text.into(),
+            status,
+            scene_recap: None,
+            notes_hash_at_generation: None,
+            generation_priority: None,
+            depends_on: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn notes_edited_after_generation_make_content_stale() {
+        let mut node = content("Ada finds the locket.", "", ContentStatus::HasContent);
+        assert!(!node.notes_stale());
+
+        node.record_generation_notes("Ada finds the locket.");
+        node.notes.push('\n');
+        assert!(!node.notes_stale());
+
+        node.notes = "Ada loses the locket.".into();
+        assert!(node.notes_stale());
+        node.record_generation_notes(&node.notes.clone());
+        assert!(!node.notes_stale());
+    }
+
+    #[test]
+    fn transition_to_enforces_legal_moves() {
+        let mut empty = content("", "", ContentStatus::Empty);
+        assert!(empty.transition_to(ContentStatus::HasContent).is_err());
+        assert!(empty.transition_to(ContentStatus::Generating).is_err());
+        assert!(empty.transition_to(ContentStatus::NotesOnly).is_err());
+
+        let mut node = content("notes", "", ContentStatus::Empty);
+        node.transition_to(ContentStatus::NotesOnly).unwrap();
+        node.transition_to(ContentStatus::Generating).unwrap();
+        node.transition_to(ContentStatus::HasContent).unwrap();
+        assert!(node.transition_to(ContentStatus::Empty).is_err());
+        assert_eq!(node.status, ContentStatus::HasContent);
+    }
+
+    #[test]
+    fn reconcile_status_repairs_stuck_generation() {
+        let mut stuck = content("notes", "", ContentStatus::Generating);
+        assert!(stuck.reconcile_status(false));
+        assert_eq!(stuck.status, ContentStatus::NotesOnly);
+
+        let mut in_flight = content("notes", "", ContentStatus::Generating);
+        assert!(!in_flight.reconcile_status(true));
+
+        let mut written = content("notes", "INT. HOUSE", ContentStatus::NotesOnly);
+        assert!(written.reconcile_status(false));
+        assert_eq!(written.status, ContentStatus::HasContent);
+
+        let mut cleared = content("", "", ContentStatus::NotesOnly);
+        cleared.refresh_status();
+        assert_eq!(cleared.status, ContentStatus::Empty);
+    }
+}
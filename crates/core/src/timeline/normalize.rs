@@ -0,0 +1,115 @@
+use super::Timeline;
+use super::node::{NodeId, StoryLevel, StoryNode};
+
+/// Largest gap or overlap between neighbouring siblings that
+/// [`Timeline::normalize_level`] closes. Anything wider is left for review
+/// through the gap and overlap reports.
+pub const NORMALIZE_TOLERANCE_MS: u64 = 100;
+
+impl Timeline {
+    /// Snap sibling boundaries together after rounding drift.
+    ///
+    /// For the `level` nodes under `parent_id` (or the parentless ones when
+    /// `None`), in time order, each node's end moves to the next node's start
+    /// when they are at most [`NORMALIZE_TOLERANCE_MS`] apart. Descendants
+    /// that would then end past their trimmed ancestor are pulled back inside
+    /// it. Returns how many boundaries moved.
+    pub fn normalize_level(&mut self, level: StoryLevel, parent_id: Option<NodeId>) -> usize {
+        let mut siblings: Vec<&StoryNode> = self
+            .nodes
+            .iter()
+            .filter(|node| node.level == level && node.parent_id == parent_id)
+            .collect();
+        siblings.sort_by_key(|node| (node.time_range.start_ms, node.sort_order));
+
+        let snaps: Vec<(NodeId, u64)> = siblings
+            .windows(2)
+            .filter_map(|pair| {
+                let (node, next) = (pair[0], pair[1]);
+                let end_ms = node.time_range.end_ms;
+                let next_start_ms = next.time_range.start_ms;
+                let within_tolerance = end_ms.abs_diff(next_start_ms) <= NORMALIZE_TOLERANCE_MS;
+                (end_ms != next_start_ms
+                    && within_tolerance
+                    && next_start_ms > node.time_range.start_ms)
+                    .then_some((node.id, next_start_ms))
+            })
+            .collect();
+
+        for &(node_id, end_ms) in &snaps {
+            let descendant_ids: Vec<NodeId> =
+                self.descendants_of(node_id).iter().map(|n| n.id).collect();
+            if let Ok(node) = self.node_mut(node_id) {
+                node.time_range.end_ms = end_ms;
+            }
+            for desc_id in descendant_ids {
+                if let Ok(desc) = self.node_mut(desc_id)
+                    && desc.time_range.end_ms > end_ms
+                {
+                    desc.time_range.end_ms = end_ms;
+                    desc.time_range.start_ms = desc.time_range.start_ms.min(end_ms - 1);
+                }
+            }
+        }
+        snaps.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeline::structure::EpisodeStructure;
+    use crate::timeline::timing::TimeRange;
+
+    fn add(timeline: &mut Timeline, node: StoryNode) -> NodeId {
+        let id = node.id;
+        timeline.nodes.push(node);
+        id
+    }
+
+    fn scene(timeline: &mut Timeline, parent: NodeId, start_ms: u64, end_ms: u64) -> NodeId {
+        let range = TimeRange::new(start_ms, end_ms).unwrap();
+        add(
+            timeline,
+            StoryNode::new_child("Scene", StoryLevel::Scene, range, parent),
+        )
+    }
+
+    fn range(timeline: &Timeline, id: NodeId) -> (u64, u64) {
+        let range = timeline.node(id).unwrap().time_range;
+        (range.start_ms, range.end_ms)
+    }
+
+    #[test]
+    fn small_gaps_and_overlaps_snap_but_large_ones_stay() {
+        let mut timeline = Timeline::new(60_000, EpisodeStructure::standard_30_min());
+        let act = add(
+            &mut timeline,
+            StoryNode::new("Act", StoryLevel::Act, TimeRange::new(0, 60_000).unwrap()),
+        );
+        let gap = scene(&mut timeline, act, 0, 9_997);
+        let overlap = scene(&mut timeline, act, 10_000, 20_040);
+        let flush = scene(&mut timeline, act, 20_000, 30_000);
+        let wide_gap = scene(&mut timeline, act, 30_000, 35_000);
+        let last = scene(&mut timeline, act, 40_000, 60_000);
+        let beat = add(
+            &mut timeline,
+            StoryNode::new_child(
+                "Beat",
+                StoryLevel::Beat,
+                TimeRange::new(15_000, 20_040).unwrap(),
+                overlap,
+            ),
+        );
+
+        assert_eq!(timeline.normalize_level(StoryLevel::Scene, Some(act)), 2);
+
+        assert_eq!(range(&timeline, gap), (0, 10_000));
+        assert_eq!(range(&timeline, overlap), (10_000, 20_000));
+        assert_eq!(range(&timeline, beat), (15_000, 20_000));
+        assert_eq!(range(&timeline, flush), (20_000, 30_000));
+        assert_eq!(range(&timeline, wide_gap), (30_000, 35_000));
+        assert_eq!(range(&timeline, last), (40_000, 60_000));
+        assert_eq!(timeline.normalize_level(StoryLevel::Scene, Some(act)), 0);
+    }
+}
@@ -10,7 +10,7 @@ pub struct RelationshipId(pub Uuid);
 
 impl RelationshipId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
@@ -52,4 +52,137 @@ pub enum RelationshipType {
     Convergence { arc_ids: Vec<ArcId> },
     /// User-defined thematic or structural link.
     Thematic,
+    /// `to_node` pays off the setup planted in `from_node`.
+    Payoff,
+}
+
+impl RelationshipType {
+    /// One of each variant, with empty parameters, in picker order.
+    pub fn all() -> Vec<RelationshipType> {
+        vec![
+            Self::Causal,
+            Self::Payoff,
+            Self::Convergence {
+                arc_ids: Vec::new(),
+            },
+            Self::Thematic,
+        ]
+    }
+
+    /// The serialized variant name, e.g. `"Payoff"`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Causal => "Causal",
+            Self::Convergence { .. } => "Convergence",
+            Self::Thematic => "Thematic",
+            Self::Payoff => "Payoff",
+        }
+    }
+
+    /// What this variant means and which parameters it carries.
+    pub fn info(&self) -> RelationshipTypeInfo {
+        let (label, description, directed, parameters) = match self {
+            Self::Causal => (
+                "Causal",
+                "The first node causes the second.",
+                true,
+                Vec::new(),
+            ),
+            Self::Convergence { .. } => (
+                "Convergence",
+                "Story arcs intersect between these nodes.",
+                false,
+                vec![RelationshipTypeParameter {
+                    name: "arc_ids".into(),
+                    kind: RelationshipParameterKind::ArcIds,
+                    description: "The arcs that converge.".into(),
+                }],
+            ),
+            Self::Thematic => (
+                "Thematic",
+                "A user-defined thematic or structural link.",
+                false,
+                Vec::new(),
+            ),
+            Self::Payoff => (
+                "Setup / Payoff",
+                "The second node pays off a setup planted in the first.",
+                true,
+                Vec::new(),
+            ),
+        };
+        RelationshipTypeInfo {
+            id: self.id().into(),
+            label: label.into(),
+            description: description.into(),
+            directed,
+            parameters,
+        }
+    }
+
+    /// [`Self::info`] for every variant, so pickers need not hardcode them.
+    pub fn catalog() -> Vec<RelationshipTypeInfo> {
+        Self::all().iter().map(Self::info).collect()
+    }
+}
+
+/// A relationship type as offered to users creating relationships.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelationshipTypeInfo {
+    /// The serialized variant name: the bare string for variants without
+    /// parameters, otherwise the single key of the object form.
+    pub id: String,
+    pub label: String,
+    pub description: String,
+    /// Whether `from_node` and `to_node` play different roles.
+    pub directed: bool,
+    #[serde(default)]
+    pub parameters: Vec<RelationshipTypeParameter>,
+}
+
+/// A field carried inside a relationship type variant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelationshipTypeParameter {
+    pub name: String,
+    pub kind: RelationshipParameterKind,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationshipParameterKind {
+    /// A list of story arc ids.
+    ArcIds,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_ids_and_parameters_match_the_serialized_variants() {
+        let catalog = RelationshipType::catalog();
+        assert_eq!(catalog.len(), RelationshipType::all().len());
+
+        for (variant, info) in RelationshipType::all().iter().zip(&catalog) {
+            let value = serde_json::to_value(variant).unwrap();
+            match value {
+                serde_json::Value::String(id) => {
+                    assert_eq!(id, info.id);
+                    assert!(info.parameters.is_empty());
+                }
+                serde_json::Value::Object(object) => {
+                    let fields = object[&info.id].as_object().expect("variant fields");
+                    let names: Vec<_> = fields.keys().cloned().collect();
+                    let parameters: Vec<_> = info
+                        .parameters
+                        .iter()
+                        .map(|param| param.name.clone())
+                        .collect();
+                    assert_eq!(names, parameters);
+                }
+                other => panic!("unexpected relationship type encoding: {other}"),
+            }
+        }
+    }
 }
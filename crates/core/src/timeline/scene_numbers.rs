@@ -0,0 +1,221 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::Timeline;
+use super::node::{NodeId, StoryLevel, StoryNode};
+use crate::error::{Error, Result};
+
+/// Longest scene number accepted, in characters.
+pub const MAX_SCENE_NUMBER_LEN: usize = 8;
+
+/// How [`Timeline::plan_scene_numbers`] treats scenes that already have one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SceneNumbering {
+    /// Number every scene 1, 2, 3… in time order, replacing existing numbers.
+    #[default]
+    Sequential,
+    /// Keep existing numbers, as for a locked script, and give unnumbered
+    /// scenes a letter after the scene before them: 12, 12A, 12B, 13.
+    /// Scenes ahead of the first number get a letter prefix: A1, B1, 1.
+    Locked,
+}
+
+/// Trim and upper-case a user-entered scene number; blank clears it.
+pub fn normalize_scene_number(raw: &str) -> Result<Option<String>> {
+    let number = raw.trim().to_uppercase();
+    if number.is_empty() {
+        return Ok(None);
+    }
+    if number.chars().count() > MAX_SCENE_NUMBER_LEN
+        || !number
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+    {
+        return Err(Error::InvalidSceneNumber(format!(
+            "{raw:?}; use up to {MAX_SCENE_NUMBER_LEN} letters, digits, '-' or '.'"
+        )));
+    }
+    Ok(Some(number))
+}
+
+impl Timeline {
+    /// Scene-level nodes in time order, ties broken by sort order.
+    fn scenes_in_order(&self) -> Vec<&StoryNode> {
+        let mut scenes = self.nodes_at_level(StoryLevel::Scene);
+        scenes.sort_by_key(|scene| (scene.time_range.start_ms, scene.sort_order));
+        scenes
+    }
+
+    /// The numbers `mode` would give each scene, for the scenes whose number
+    /// would change. In [`SceneNumbering::Locked`] mode a timeline with no
+    /// numbers yet is numbered sequentially.
+    pub fn plan_scene_numbers(&self, mode: SceneNumbering) -> Vec<(NodeId, String)> {
+        let scenes = self.scenes_in_order();
+        let any_numbered = scenes.iter().any(|scene| scene.scene_number.is_some());
+        let numbers = if mode == SceneNumbering::Locked && any_numbered {
+            locked_numbers(&scenes)
+        } else {
+            (1..=scenes.len()).map(|n| n.to_string()).collect()
+        };
+
+        scenes
+            .into_iter()
+            .zip(numbers)
+            .filter(|(scene, number)| scene.scene_number.as_ref() != Some(number))
+            .map(|(scene, number)| (scene.id, number))
+            .collect()
+    }
+
+    /// The scene number covering `node_id`: its own, or that of the nearest
+    /// Scene above it.
+    pub fn scene_number_for(&self, node_id: NodeId) -> Option<&str> {
+        let mut current = self.node(node_id).ok();
+        while let Some(node) = current {
+            if node.level == StoryLevel::Scene {
+                return node.scene_number.as_deref();
+            }
+            current = node.parent_id.and_then(|parent| self.node(parent).ok());
+        }
+        None
+    }
+}
+
+fn locked_numbers(scenes: &[&StoryNode]) -> Vec<String> {
+    let mut taken: HashSet<String> = scenes
+        .iter()
+        .filter_map(|scene| scene.scene_number.clone())
+        .collect();
+    let mut numbers = Vec::with_capacity(scenes.len());
+    let mut previous: Option<String> = None;
+
+    for (index, scene) in scenes.iter().enumerate() {
+        let number = match (&scene.scene_number, &previous) {
+            (Some(number), _) => number.clone(),
+            (None, Some(previous)) => {
+                let base = suffix_base(previous);
+                first_free(&taken, |letters| format!("{base}{letters}"))
+            }
+            (None, None) => {
+                let next = scenes[index..]
+                    .iter()
+                    .find_map(|scene| scene.scene_number.as_deref())
+                    .unwrap_or("1");
+                first_free(&taken, |letters| format!("{letters}{next}"))
+            }
+        };
+        taken.insert(number.clone());
+        previous = Some(number.clone());
+        numbers.push(number);
+    }
+    numbers
+}
+
+/// "12A" continues as 12B, not 12AA.
+fn suffix_base(number: &str) -> &str {
+    if number.starts_with(|c: char| c.is_ascii_digit()) {
+        number.trim_end_matches(|c: char| c.is_ascii_alphabetic())
+    } else {
+        number
+    }
+}
+
+fn first_free(taken: &HashSet<String>, candidate: impl Fn(&str) -> String) -> String {
+    (0..)
+        .map(|index| candidate(&letters(index)))
+        .find(|number| !taken.contains(number))
+        .expect("letter sequence is unbounded")
+}
+
+/// A, B, … Z, AA, AB, …
+fn letters(mut index: usize) -> String {
+    let mut out = Vec::new();
+    loop {
+        out.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    out.reverse();
+    String::from_utf8(out).expect("ASCII letters")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Template;
+
+    fn apply(timeline: &mut Timeline, mode: SceneNumbering) {
+        for (node_id, number) in timeline.plan_scene_numbers(mode) {
+            timeline.node_mut(node_id).unwrap().scene_number = Some(number);
+        }
+    }
+
+    fn numbers(timeline: &Timeline) -> Vec<String> {
+        timeline
+            .scenes_in_order()
+            .iter()
+            .map(|scene| scene.scene_number.clone().unwrap_or_default())
+            .collect()
+    }
+
+    #[test]
+    fn sequential_numbering_follows_time_order() {
+        let mut timeline = Template::MultiCam.build_project("Numbering Test").timeline;
+        let count = timeline.nodes_at_level(StoryLevel::Scene).len();
+
+        apply(&mut timeline, SceneNumbering::Sequential);
+
+        let expected: Vec<String> = (1..=count).map(|n| n.to_string()).collect();
+        assert_eq!(numbers(&timeline), expected);
+        assert!(
+            timeline
+                .plan_scene_numbers(SceneNumbering::Sequential)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn locked_numbering_letters_inserted_scenes() {
+        let mut timeline = Template::MultiCam.build_project("Numbering Test").timeline;
+        let scenes: Vec<NodeId> = timeline
+            .scenes_in_order()
+            .iter()
+            .map(|scene| scene.id)
+            .collect();
+        apply(&mut timeline, SceneNumbering::Sequential);
+        // Clear three numbers as if those scenes had been inserted after locking.
+        for &index in &[0, 2, 3] {
+            timeline.node_mut(scenes[index]).unwrap().scene_number = None;
+        }
+        timeline.node_mut(scenes[4]).unwrap().scene_number = Some("2A".into());
+
+        apply(&mut timeline, SceneNumbering::Locked);
+
+        assert_eq!(numbers(&timeline)[..6], ["A2", "2", "2B", "2C", "2A", "6"]);
+    }
+
+    #[test]
+    fn scene_number_is_inherited_by_beats_and_normalized() {
+        let mut timeline = Template::MultiCam.build_project("Numbering Test").timeline;
+        let scene_id = timeline.scenes_in_order()[0].id;
+        timeline.node_mut(scene_id).unwrap().scene_number =
+            normalize_scene_number(" 12a ").unwrap();
+        let beat = StoryNode::new_child(
+            "Beat",
+            StoryLevel::Beat,
+            timeline.node(scene_id).unwrap().time_range,
+            scene_id,
+        );
+        let beat_id = beat.id;
+        timeline.nodes.push(beat);
+
+        assert_eq!(timeline.scene_number_for(beat_id), Some("12A"));
+        assert_eq!(normalize_scene_number("  ").unwrap(), None);
+        assert!(normalize_scene_number("12 A").is_err());
+        assert_eq!(letters(25), "Z");
+        assert_eq!(letters(26), "AA");
+    }
+}
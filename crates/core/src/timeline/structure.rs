@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
 
 use super::timing::TimeRange;
+use crate::error::{Error, Result};
 
 /// The episode's act structure (pre-placed, adjustable).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EpisodeStructure {
     /// e.g., "Standard 30-Min Comedy"
     pub template_name: String,
@@ -11,7 +12,7 @@ pub struct EpisodeStructure {
 }
 
 /// A single structural segment (act, cold open, commercial break, etc.).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StructureSegment {
     pub segment_type: SegmentType,
     pub time_range: TimeRange,
@@ -28,7 +29,198 @@ pub enum SegmentType {
     Tag,
 }
 
+/// Where a structure fails to tile the episode once its segments are sorted.
+/// `range` is the uncovered, doubly covered or overrunning span.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StructureIssue {
+    /// Nothing covers `range`; `after` is `None` at the episode start and
+    /// `before` is `None` at its end.
+    Gap {
+        after: Option<String>,
+        before: Option<String>,
+        range: TimeRange,
+    },
+    /// Both segments cover `range`.
+    Overlap {
+        first: String,
+        second: String,
+        range: TimeRange,
+    },
+    /// The segment ends before it starts.
+    Inverted { label: String, range: TimeRange },
+    /// The segment runs past the episode end by `range`.
+    Overrun { label: String, range: TimeRange },
+}
+
+/// A proposed structure checked against the episode length; see
+/// [`EpisodeStructure::normalize`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructureNormalization {
+    /// Problems in start order. Empty when the structure tiles cleanly.
+    pub issues: Vec<StructureIssue>,
+    pub tiles_cleanly: bool,
+    /// The segments sorted with their boundaries snapped so they tile
+    /// `0..total_duration_ms` exactly.
+    pub normalized: EpisodeStructure,
+}
+
 impl EpisodeStructure {
+    /// Sort the segments and report every gap, overlap, inverted segment
+    /// and overrun against `total_duration_ms`, without changing `self`.
+    /// The normalized copy snaps each shared boundary to the middle of the
+    /// gap or overlap between neighbours, pins the ends to the episode and
+    /// keeps segment order, so a preview can show what applying would do.
+    pub fn normalize(&self, total_duration_ms: u64) -> StructureNormalization {
+        let mut segments = self.segments.clone();
+        segments.sort_by_key(|segment| (segment.time_range.start_ms, segment.time_range.end_ms));
+
+        let mut issues = Vec::new();
+        let mut previous: Option<&StructureSegment> = None;
+        for segment in &segments {
+            let range = segment.time_range;
+            if range.end_ms < range.start_ms {
+                issues.push(StructureIssue::Inverted {
+                    label: segment.label.clone(),
+                    range,
+                });
+            }
+            let covered_to = previous.map_or(0, |previous| previous.time_range.end_ms);
+            if range.start_ms > covered_to {
+                issues.push(StructureIssue::Gap {
+                    after: previous.map(|previous| previous.label.clone()),
+                    before: Some(segment.label.clone()),
+                    range: TimeRange {
+                        start_ms: covered_to,
+                        end_ms: range.start_ms,
+                    },
+                });
+            } else if let Some(previous) = previous
+                && range.start_ms < covered_to
+            {
+                issues.push(StructureIssue::Overlap {
+                    first: previous.label.clone(),
+                    second: segment.label.clone(),
+                    range: TimeRange {
+                        start_ms: range.start_ms,
+                        end_ms: covered_to.min(range.end_ms.max(range.start_ms)),
+                    },
+                });
+            }
+            if range.end_ms > total_duration_ms {
+                issues.push(StructureIssue::Overrun {
+                    label: segment.label.clone(),
+                    range: TimeRange {
+                        start_ms: total_duration_ms,
+                        end_ms: range.end_ms,
+                    },
+                });
+            }
+            previous = Some(segment);
+        }
+        let covered_to = previous.map_or(0, |previous| previous.time_range.end_ms);
+        if covered_to < total_duration_ms {
+            issues.push(StructureIssue::Gap {
+                after: previous.map(|previous| previous.label.clone()),
+                before: None,
+                range: TimeRange {
+                    start_ms: covered_to,
+                    end_ms: total_duration_ms,
+                },
+            });
+        }
+
+        let mut start_ms = 0;
+        let last = segments.len().saturating_sub(1);
+        let snapped: Vec<StructureSegment> = segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                let end_ms = if index == last {
+                    total_duration_ms
+                } else {
+                    let next_start = segments[index + 1].time_range.start_ms;
+                    segment
+                        .time_range
+                        .end_ms
+                        .midpoint(next_start)
+                        .clamp(start_ms, total_duration_ms)
+                };
+                let time_range = TimeRange { start_ms, end_ms };
+                start_ms = end_ms;
+                StructureSegment {
+                    time_range,
+                    ..segment.clone()
+                }
+            })
+            .collect();
+
+        StructureNormalization {
+            tiles_cleanly: issues.is_empty(),
+            issues,
+            normalized: EpisodeStructure {
+                template_name: self.template_name.clone(),
+                segments: snapped,
+            },
+        }
+    }
+
+    /// The segments a Premise decomposes into, one act each, in order: cold
+    /// opens, acts and tags. Titles and commercial breaks get no act.
+    pub fn act_segments(&self) -> impl Iterator<Item = &StructureSegment> {
+        self.segments.iter().filter(|segment| {
+            matches!(
+                segment.segment_type,
+                SegmentType::ColdOpen | SegmentType::Act | SegmentType::Tag
+            )
+        })
+    }
+
+    /// The ranges of `child_count` acts under a Premise spanning `parent`,
+    /// taken from the act segments by order so the acts line up with the
+    /// structure exactly. `None` when the counts differ or a segment lies
+    /// outside `parent`.
+    pub fn act_ranges(&self, parent: TimeRange, child_count: usize) -> Option<Vec<TimeRange>> {
+        let ranges: Vec<TimeRange> = self
+            .act_segments()
+            .map(|segment| segment.time_range)
+            .collect();
+        let fits = ranges.len() == child_count
+            && ranges
+                .iter()
+                .all(|range| range.start_ms >= parent.start_ms && range.end_ms <= parent.end_ms);
+        fits.then_some(ranges)
+    }
+
+    /// Check that segments run in order without overlapping and end within
+    /// `total_duration_ms`. Zero-length segments (commercial breaks) are fine.
+    pub fn validate(&self, total_duration_ms: u64) -> Result<()> {
+        let mut cursor = 0;
+        for segment in &self.segments {
+            let range = segment.time_range;
+            if range.end_ms < range.start_ms {
+                return Err(Error::InvalidTimeRange {
+                    start_ms: range.start_ms,
+                    end_ms: range.end_ms,
+                });
+            }
+            if range.end_ms > total_duration_ms {
+                return Err(Error::NodeExceedsTimeline {
+                    node_end_ms: range.end_ms,
+                    timeline_ms: total_duration_ms,
+                });
+            }
+            if range.start_ms < cursor {
+                return Err(Error::InvalidOperation(format!(
+                    "structure segment '{}' starts at {}ms, before the previous segment ends at {}ms",
+                    segment.label, range.start_ms, cursor
+                )));
+            }
+            cursor = range.end_ms;
+        }
+        Ok(())
+    }
+
     /// Standard 30-minute TV episode structure (~22 min content).
     ///
     /// ```text
@@ -114,3 +306,106 @@ impl EpisodeStructure {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn act_ranges_follow_segments_in_order() {
+        let structure = EpisodeStructure::standard_30_min();
+        let parent = TimeRange::new(0, 1_320_000).unwrap();
+
+        let ranges = structure.act_ranges(parent, 5).unwrap();
+
+        assert_eq!(ranges[0], TimeRange::new(0, 120_000).unwrap());
+        assert_eq!(ranges[1], TimeRange::new(150_000, 570_000).unwrap());
+        assert_eq!(ranges[4], TimeRange::new(1_290_000, 1_320_000).unwrap());
+        assert_eq!(structure.act_ranges(parent, 4), None);
+        assert_eq!(
+            structure.act_ranges(TimeRange::new(0, 600_000).unwrap(), 5),
+            None
+        );
+    }
+
+    fn segment(label: &str, start_ms: u64, end_ms: u64) -> StructureSegment {
+        StructureSegment {
+            segment_type: SegmentType::Act,
+            time_range: TimeRange { start_ms, end_ms },
+            label: label.into(),
+        }
+    }
+
+    #[test]
+    fn standard_structure_tiles_cleanly_and_is_unchanged() {
+        let structure = EpisodeStructure::standard_30_min();
+
+        let normalization = structure.normalize(1_320_000);
+
+        assert!(normalization.tiles_cleanly);
+        assert!(normalization.issues.is_empty());
+        assert_eq!(normalization.normalized, structure);
+    }
+
+    #[test]
+    fn gappy_structure_lists_issues_and_snaps_boundaries() {
+        let structure = EpisodeStructure {
+            template_name: "Custom".into(),
+            segments: vec![
+                segment("Act Two", 50_000, 90_000),
+                segment("Act One", 10_000, 40_000),
+                segment("Act Three", 80_000, 120_000),
+            ],
+        };
+
+        let normalization = structure.normalize(100_000);
+
+        assert!(!normalization.tiles_cleanly);
+        assert_eq!(
+            normalization.issues,
+            vec![
+                StructureIssue::Gap {
+                    after: None,
+                    before: Some("Act One".into()),
+                    range: TimeRange::new(0, 10_000).unwrap(),
+                },
+                StructureIssue::Gap {
+                    after: Some("Act One".into()),
+                    before: Some("Act Two".into()),
+                    range: TimeRange::new(40_000, 50_000).unwrap(),
+                },
+                StructureIssue::Overlap {
+                    first: "Act Two".into(),
+                    second: "Act Three".into(),
+                    range: TimeRange::new(80_000, 90_000).unwrap(),
+                },
+                StructureIssue::Overrun {
+                    label: "Act Three".into(),
+                    range: TimeRange::new(100_000, 120_000).unwrap(),
+                },
+            ]
+        );
+        let ranges: Vec<(&str, u64, u64)> = normalization
+            .normalized
+            .segments
+            .iter()
+            .map(|segment| {
+                (
+                    segment.label.as_str(),
+                    segment.time_range.start_ms,
+                    segment.time_range.end_ms,
+                )
+            })
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                ("Act One", 0, 45_000),
+                ("Act Two", 45_000, 85_000),
+                ("Act Three", 85_000, 100_000),
+            ]
+        );
+        assert!(normalization.normalized.validate(100_000).is_ok());
+        assert_eq!(structure.segments[0].label, "Act Two");
+    }
+}
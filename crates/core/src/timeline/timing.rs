@@ -3,6 +3,10 @@ use serde::{Deserialize, Serialize};
 use crate::error::{Error, Result};
 
 /// A time range on the timeline, stored as milliseconds.
+///
+/// Ranges are half-open, `[start_ms, end_ms)`: the start instant belongs to
+/// the range and the end instant does not, so adjacent ranges share a
+/// boundary without overlapping. A valid range is never empty.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TimeRange {
     pub start_ms: u64,
@@ -10,7 +14,8 @@ pub struct TimeRange {
 }
 
 impl TimeRange {
-    /// Create a new time range, validating that start < end.
+    /// Create a new time range, rejecting empty or inverted ranges
+    /// (`end_ms <= start_ms`).
     pub fn new(start_ms: u64, end_ms: u64) -> Result<Self> {
         let range = Self { start_ms, end_ms };
         range.validate()?;
@@ -28,17 +33,28 @@ impl TimeRange {
         Ok(())
     }
 
-    /// Duration in milliseconds.
+    /// Duration in milliseconds; zero for an unvalidated inverted range
+    /// rather than underflowing.
     pub fn duration_ms(&self) -> u64 {
-        self.end_ms - self.start_ms
+        self.end_ms.saturating_sub(self.start_ms)
     }
 
-    /// Check if a time point falls within this range (inclusive start, exclusive end).
+    /// This range moved by `offset_ms`, or `None` if it would start before
+    /// zero or overflow.
+    pub fn shifted(&self, offset_ms: i64) -> Option<TimeRange> {
+        Some(TimeRange {
+            start_ms: self.start_ms.checked_add_signed(offset_ms)?,
+            end_ms: self.end_ms.checked_add_signed(offset_ms)?,
+        })
+    }
+
+    /// Check if a time point falls within `[start_ms, end_ms)`.
     pub fn contains(&self, time_ms: u64) -> bool {
         time_ms >= self.start_ms && time_ms < self.end_ms
     }
 
-    /// Check if two ranges overlap.
+    /// Check if two half-open ranges share any instant; ranges that only
+    /// touch at a boundary do not overlap.
     pub fn overlaps(&self, other: &TimeRange) -> bool {
         self.start_ms < other.end_ms && other.start_ms < self.end_ms
     }
@@ -58,6 +74,101 @@ impl TimeRange {
     }
 }
 
+/// Split `parent` into consecutive ranges proportional to `weights`.
+///
+/// Weights below 0.1 are treated as 0.1 so no child collapses to nothing.
+/// Durations are truncated to whole milliseconds and the last range absorbs
+/// the rounding remainder, so the ranges always end exactly at `parent.end_ms`.
+pub fn distribute_weighted_ranges(parent: TimeRange, weights: &[f32]) -> Vec<TimeRange> {
+    let total_weight: f32 = weights.iter().map(|w| w.max(0.1)).sum();
+    let parent_duration = parent.duration_ms();
+    let mut cursor = parent.start_ms;
+
+    weights
+        .iter()
+        .enumerate()
+        .map(|(index, weight)| {
+            let duration = if index == weights.len() - 1 {
+                parent.end_ms - cursor
+            } else {
+                ((weight.max(0.1) / total_weight) * parent_duration as f32) as u64
+            };
+            let end_ms = (cursor + duration).min(parent.end_ms);
+            let range = TimeRange {
+                start_ms: cursor,
+                end_ms,
+            };
+            cursor = end_ms;
+            range
+        })
+        .collect()
+}
+
+/// Split `gaps` among children by `weights`, treating the gaps as one
+/// continuous range in order.
+///
+/// Each child lands in the gap holding the midpoint of its share, then the
+/// children in each gap split that gap with [`distribute_weighted_ranges`].
+/// A gap no child lands in is left empty.
+pub fn distribute_weighted_ranges_into_gaps(gaps: &[TimeRange], weights: &[f32]) -> Vec<TimeRange> {
+    let total_duration: u64 = gaps.iter().map(TimeRange::duration_ms).sum();
+    if total_duration == 0 {
+        return Vec::new();
+    }
+    let shares = distribute_weighted_ranges(
+        TimeRange {
+            start_ms: 0,
+            end_ms: total_duration,
+        },
+        weights,
+    );
+
+    let mut gap_children: Vec<Vec<usize>> = vec![Vec::new(); gaps.len()];
+    for (index, share) in shares.iter().enumerate() {
+        let midpoint = (share.start_ms + share.end_ms) / 2;
+        let mut offset = 0;
+        let mut gap_index = gaps.len() - 1;
+        for (candidate, gap) in gaps.iter().enumerate() {
+            offset += gap.duration_ms();
+            if midpoint < offset {
+                gap_index = candidate;
+                break;
+            }
+        }
+        gap_children[gap_index].push(index);
+    }
+
+    let mut ranges = vec![None; weights.len()];
+    for (gap, children) in gaps.iter().zip(gap_children) {
+        let gap_weights: Vec<f32> = children.iter().map(|&index| weights[index]).collect();
+        for (index, range) in children
+            .into_iter()
+            .zip(distribute_weighted_ranges(*gap, &gap_weights))
+        {
+            ranges[index] = Some(range);
+        }
+    }
+    ranges.into_iter().flatten().collect()
+}
+
+/// Sort `ranges` and merge those that overlap or touch at a boundary into
+/// contiguous spans, so a gap in the result is time no input range covers.
+pub fn merge_time_ranges(ranges: impl IntoIterator<Item = TimeRange>) -> Vec<TimeRange> {
+    let mut sorted: Vec<TimeRange> = ranges.into_iter().collect();
+    sorted.sort_by_key(|range| (range.start_ms, range.end_ms));
+
+    let mut merged: Vec<TimeRange> = Vec::with_capacity(sorted.len());
+    for range in sorted {
+        match merged.last_mut() {
+            Some(last) if range.start_ms <= last.end_ms => {
+                last.end_ms = last.end_ms.max(range.end_ms);
+            }
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
 /// Format milliseconds as MM:SS for display.
 pub fn format_time(ms: u64) -> String {
     let total_seconds = ms / 1000;
@@ -89,6 +200,24 @@ mod tests {
         assert_eq!(range.duration_ms(), 60_000);
     }
 
+    #[test]
+    fn test_shifted_moves_both_ends_and_rejects_negative_start() {
+        let range = TimeRange::new(10_000, 20_000).unwrap();
+        assert_eq!(range.shifted(5_000), TimeRange::new(15_000, 25_000).ok());
+        assert_eq!(range.shifted(-10_000), TimeRange::new(0, 10_000).ok());
+        assert_eq!(range.shifted(-10_001), None);
+    }
+
+    #[test]
+    fn test_duration_ms_of_inverted_range_is_zero() {
+        let range = TimeRange {
+            start_ms: 70_000,
+            end_ms: 10_000,
+        };
+        assert_eq!(range.duration_ms(), 0);
+        assert!(range.validate().is_err());
+    }
+
     #[test]
     fn test_contains_point_inside_returns_true() {
         let range = TimeRange::new(10_000, 20_000).unwrap();
@@ -129,4 +258,66 @@ mod tests {
         assert_eq!(format_time(150_000), "2:30");
         assert_eq!(format_time(1_320_000), "22:00");
     }
+
+    #[test]
+    fn test_distribute_weighted_ranges_into_gaps_fills_gaps_in_order() {
+        let gaps = [
+            TimeRange::new(0, 10_000).unwrap(),
+            TimeRange::new(40_000, 70_000).unwrap(),
+        ];
+
+        let ranges = distribute_weighted_ranges_into_gaps(&gaps, &[1.0, 1.0, 1.0, 1.0]);
+
+        assert_eq!(
+            ranges,
+            vec![
+                TimeRange::new(0, 10_000).unwrap(),
+                TimeRange::new(40_000, 50_000).unwrap(),
+                TimeRange::new(50_000, 60_000).unwrap(),
+                TimeRange::new(60_000, 70_000).unwrap(),
+            ]
+        );
+        assert!(distribute_weighted_ranges_into_gaps(&[], &[1.0]).is_empty());
+    }
+
+    #[test]
+    fn test_distribute_weighted_ranges_last_child_absorbs_remainder() {
+        let parent = TimeRange::new(1_000, 11_000).unwrap();
+
+        let ranges = distribute_weighted_ranges(parent, &[1.0, 1.0, 1.0]);
+
+        assert_eq!(ranges.len(), 3);
+        assert_eq!(ranges[0], TimeRange::new(1_000, 4_333).unwrap());
+        assert_eq!(ranges[1], TimeRange::new(4_333, 7_666).unwrap());
+        assert_eq!(ranges[2], TimeRange::new(7_666, 11_000).unwrap());
+    }
+
+    #[test]
+    fn test_distribute_weighted_ranges_is_proportional_and_contiguous() {
+        let parent = TimeRange::new(0, 60_000).unwrap();
+
+        let ranges = distribute_weighted_ranges(parent, &[2.0, 1.0, 0.0]);
+
+        assert_eq!(ranges[0].duration_ms(), 38_709);
+        assert_eq!(ranges[1].start_ms, ranges[0].end_ms);
+        assert_eq!(ranges[2].start_ms, ranges[1].end_ms);
+        assert_eq!(ranges[2].end_ms, 60_000);
+        assert!(distribute_weighted_ranges(parent, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_merge_time_ranges_joins_overlapping_and_touching_ranges() {
+        let range = |start_ms, end_ms| TimeRange::new(start_ms, end_ms).unwrap();
+
+        let merged = merge_time_ranges([
+            range(30_000, 40_000),
+            range(0, 10_000),
+            range(5_000, 12_000),
+            range(12_000, 15_000),
+            range(31_000, 35_000),
+        ]);
+
+        assert_eq!(merged, vec![range(0, 15_000), range(30_000, 40_000)]);
+        assert!(merge_time_ranges([]).is_empty());
+    }
 }
@@ -9,7 +9,7 @@ pub struct TrackId(pub Uuid);
 
 impl TrackId {
     pub fn new() -> Self {
-        Self(Uuid::new_v4())
+        Self(crate::id::new_uuid())
     }
 }
 
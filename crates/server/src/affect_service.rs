@@ -18,14 +18,17 @@ pub async fn set_affect_value(
 ) -> Result<ProjectionEnvelope<AffectProjection>, BackendError> {
     let path = active_project_path(state)?;
     let target = command.payload.target.clone();
-    tokio::task::spawn_blocking(move || {
+    let result = tokio::task::spawn_blocking(move || {
         let mut conn = crate::sqlite::open_write_connection(&path)
             .map_err(|error| BackendError::internal(error.to_string()))?;
         affect_store::record_set_affect_value(&mut conn, &command, 0).map_err(map_history_error)?;
         affect_store::load_affect_projection(&conn, target).map_err(map_history_error)
     })
     .await
-    .map_err(|error| BackendError::internal(format!("affect set task failed: {error}")))?
+    .map_err(|error| BackendError::internal(format!("affect set task failed: {error}")))?;
+    // Affect values feed the timeline overlays.
+    state.timeline_projection_cache.invalidate();
+    result
 }
 
 pub async fn delete_affect_value(
@@ -34,7 +37,7 @@ pub async fn delete_affect_value(
     target: AffectTarget,
 ) -> Result<ProjectionEnvelope<AffectProjection>, BackendError> {
     let path = active_project_path(state)?;
-    tokio::task::spawn_blocking(move || {
+    let result = tokio::task::spawn_blocking(move || {
         let mut conn = crate::sqlite::open_write_connection(&path)
             .map_err(|error| BackendError::internal(error.to_string()))?;
         affect_store::record_delete_affect_value(&mut conn, &command, 0)
@@ -42,7 +45,10 @@ pub async fn delete_affect_value(
         affect_store::load_affect_projection(&conn, target).map_err(map_history_error)
     })
     .await
-    .map_err(|error| BackendError::internal(format!("affect delete task failed: {error}")))?
+    .map_err(|error| BackendError::internal(format!("affect delete task failed: {error}")))?;
+    // Affect values feed the timeline overlays.
+    state.timeline_projection_cache.invalidate();
+    result
 }
 
 pub async fn affect_projection(
@@ -150,7 +156,7 @@ pub async fn accept_affect_proposal(
     command: CommandEnvelope<AcceptAffectProposalCommand>,
 ) -> Result<ProjectionEnvelope<AffectProposalListProjection>, BackendError> {
     let path = active_project_path(state)?;
-    tokio::task::spawn_blocking(move || {
+    let result = tokio::task::spawn_blocking(move || {
         let mut conn = crate::sqlite::open_write_connection(&path)
             .map_err(|error| BackendError::internal(error.to_string()))?;
         affect_store::record_accept_affect_proposal(&mut conn, &command, 0)
@@ -160,7 +166,10 @@ pub async fn accept_affect_proposal(
     .await
     .map_err(|error| {
         BackendError::internal(format!("affect proposal accept task failed: {error}"))
-    })?
+    })?;
+    // Affect values feed the timeline overlays.
+    state.timeline_projection_cache.invalidate();
+    result
 }
 
 fn active_project_path(state: &AppState) -> Result<PathBuf, BackendError> {
@@ -0,0 +1,168 @@
+use eidetic_core::ai::backend::ArcTagSuggestion;
+use eidetic_core::ai::prompt::parse_arc_tag_suggestions;
+use eidetic_core::story::arc::StoryArc;
+use eidetic_core::timeline::node::NodeId;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::ai_backends::Backend;
+use crate::ai_service::active_sqlite_project;
+use crate::backend_error::BackendError;
+use crate::prompt_format::build_arc_tag_prompt;
+use crate::state::AppState;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AiArcSuggestionRequest {
+    pub node_id: Uuid,
+}
+
+/// An arc the model judges a node to advance, for the writer to review
+/// before tagging.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SuggestedArcTag {
+    pub arc_id: Uuid,
+    pub arc_name: String,
+    pub confidence: f32,
+    /// Whether the node is already tagged with this arc.
+    pub already_tagged: bool,
+}
+
+/// Ask the model which of the project's arcs one node advances. Nothing is
+/// tagged; the suggestions are returned for review.
+pub async fn suggest_arc_tags(
+    state: &AppState,
+    body: AiArcSuggestionRequest,
+) -> Result<Vec<SuggestedArcTag>, BackendError> {
+    let (node, arcs, tagged) = {
+        let (project, _) = active_sqlite_project(state).await?;
+        let node_id = NodeId(body.node_id);
+        let node = project.timeline.node(node_id)?.clone();
+        let tagged: Vec<Uuid> = project
+            .timeline
+            .arcs_for_node(node_id)
+            .into_iter()
+            .map(|arc_id| arc_id.0)
+            .collect();
+        (node, project.arcs, tagged)
+    };
+    if arcs.is_empty() {
+        return Err(BackendError::bad_request("project has no arcs to suggest"));
+    }
+    if node.content.notes.trim().is_empty() && node.content.content.trim().is_empty() {
+        return Err(BackendError::bad_request(
+            "node needs notes or content to suggest arcs",
+        ));
+    }
+
+    let config = state.active_ai_config();
+    let backend = Backend::from_config(&config);
+    let json_text = backend
+        .generate_json(&build_arc_tag_prompt(&node, &arcs), &config)
+        .await
+        .map_err(|error| {
+            tracing::error!(
+                "Arc tag suggestion failed for node {}: {error}",
+                body.node_id
+            );
+            BackendError::internal(error.to_string())
+        })?;
+    let suggestions = parse_arc_tag_suggestions(&json_text).map_err(|error| {
+        tracing::warn!("Failed to parse arc tag suggestion JSON: {error}\nRaw: {json_text}");
+        BackendError::bad_request(format!("failed to parse AI response: {error}"))
+    })?;
+
+    Ok(resolve_suggestions(suggestions, &arcs, &tagged))
+}
+
+/// Match suggested arc names to the project's arcs, ignoring case and
+/// surrounding whitespace. Unknown names are dropped, repeats keep their
+/// highest confidence, and the result is ordered most confident first.
+fn resolve_suggestions(
+    suggestions: Vec<ArcTagSuggestion>,
+    arcs: &[StoryArc],
+    tagged: &[Uuid],
+) -> Vec<SuggestedArcTag> {
+    let mut resolved: Vec<SuggestedArcTag> = Vec::new();
+    for suggestion in suggestions {
+        let wanted = suggestion.arc_name.trim();
+        let Some(arc) = arcs
+            .iter()
+            .find(|arc| arc.name.trim().eq_ignore_ascii_case(wanted))
+        else {
+            tracing::debug!("Ignoring suggested arc with unknown name {wanted:?}");
+            continue;
+        };
+        match resolved.iter_mut().find(|tag| tag.arc_id == arc.id.0) {
+            Some(existing) => existing.confidence = existing.confidence.max(suggestion.confidence),
+            None => resolved.push(SuggestedArcTag {
+                arc_id: arc.id.0,
+                arc_name: arc.name.clone(),
+                confidence: suggestion.confidence,
+                already_tagged: tagged.contains(&arc.id.0),
+            }),
+        }
+    }
+    resolved.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use eidetic_core::story::arc::{ArcType, Color};
+    use eidetic_core::timeline::node::{StoryLevel, StoryNode};
+    use eidetic_core::timeline::timing::TimeRange;
+
+    use super::*;
+
+    fn suggestion(arc_name: &str, confidence: f32) -> ArcTagSuggestion {
+        ArcTagSuggestion {
+            arc_name: arc_name.into(),
+            confidence,
+        }
+    }
+
+    #[test]
+    fn suggestions_resolve_names_to_arc_ids() {
+        let heist = StoryArc::new("The Heist", ArcType::APlot, Color::new(200, 50, 50));
+        let guilt = StoryArc::new("Amy's Guilt", ArcType::BPlot, Color::new(50, 50, 200));
+        let arcs = vec![heist.clone(), guilt.clone()];
+
+        let resolved = resolve_suggestions(
+            vec![
+                suggestion("amy's guilt", 0.4),
+                suggestion("  The Heist ", 0.9),
+                suggestion("Unknown Arc", 1.0),
+                suggestion("AMY'S GUILT", 0.6),
+            ],
+            &arcs,
+            &[guilt.id.0],
+        );
+
+        assert_eq!(
+            resolved,
+            vec![
+                SuggestedArcTag {
+                    arc_id: heist.id.0,
+                    arc_name: "The Heist".into(),
+                    confidence: 0.9,
+                    already_tagged: false,
+                },
+                SuggestedArcTag {
+                    arc_id: guilt.id.0,
+                    arc_name: "Amy's Guilt".into(),
+                    confidence: 0.6,
+                    already_tagged: true,
+                },
+            ]
+        );
+        let prompt = build_arc_tag_prompt(
+            &StoryNode::new(
+                "Pier",
+                StoryLevel::Scene,
+                TimeRange::new(0, 60_000).unwrap(),
+            ),
+            &arcs,
+        );
+        assert!(prompt.user.contains("- The Heist\n- Amy's Guilt\n"));
+    }
+}
@@ -6,7 +6,7 @@ use crate::state::{AiConfig, BackendType};
 use eidetic_core::ai::backend::GenerateStream;
 use eidetic_core::error::Error;
 
-use super::BackendStatus;
+use super::{BackendStatus, chat_messages, parse_sse_chunks};
 
 pub(crate) struct LlamaCppBackend {
     client: Client,
@@ -27,16 +27,7 @@ impl LlamaCppBackend {
         config: &AiConfig,
     ) -> Result<GenerateStream, Error> {
         let model = self.effective_model(config).await?;
-        let body = serde_json::json!({
-            "model": model,
-            "messages": [
-                { "role": "system", "content": prompt.system },
-                { "role": "user", "content": prompt.user }
-            ],
-            "stream": true,
-            "temperature": config.temperature,
-            "max_tokens": config.max_tokens,
-        });
+        let body = build_request_body(prompt, config, &model);
 
         let response = self
             .authorized(self.client.post(self.chat_completions_url()), config)
@@ -56,7 +47,7 @@ impl LlamaCppBackend {
         let byte_stream = response.bytes_stream();
         let token_stream = byte_stream
             .map(|chunk| match chunk {
-                Ok(bytes) => parse_sse_tokens(&String::from_utf8_lossy(&bytes)),
+                Ok(bytes) => parse_sse_chunks(&String::from_utf8_lossy(&bytes)),
                 Err(e) => {
                     tracing::warn!("llama.cpp stream chunk error: {e}");
                     vec![]
@@ -74,17 +65,7 @@ impl LlamaCppBackend {
         config: &AiConfig,
     ) -> Result<String, Error> {
         let model = self.effective_model(config).await?;
-        let body = serde_json::json!({
-            "model": model,
-            "messages": [
-                { "role": "system", "content": prompt.system },
-                { "role": "user", "content": prompt.user }
-            ],
-            "stream": false,
-            "temperature": config.temperature,
-            "max_tokens": config.max_tokens,
-            "response_format": { "type": "json_object" },
-        });
+        let body = build_json_request_body(prompt, config, &model);
 
         let response = self
             .authorized(self.client.post(self.chat_completions_url()), config)
@@ -116,6 +97,18 @@ impl LlamaCppBackend {
             .to_owned())
     }
 
+    /// The streaming request body `generate` would send, for debugging.
+    ///
+    /// Falls back to the configured model name when the server cannot be
+    /// reached to resolve `auto`, so the payload can still be inspected.
+    pub async fn request_body(&self, prompt: &ChatPrompt, config: &AiConfig) -> serde_json::Value {
+        let model = self
+            .effective_model(config)
+            .await
+            .unwrap_or_else(|_| config.model.clone());
+        build_request_body(prompt, config, &model)
+    }
+
     pub async fn health_check(&self) -> Result<BackendStatus, Error> {
         match self.client.get(self.models_url()).send().await {
             Ok(resp) if resp.status().is_success() => {
@@ -193,6 +186,41 @@ impl LlamaCppBackend {
     }
 }
 
+/// Build the OpenAI-compatible streaming chat-completions body.
+fn build_request_body(prompt: &ChatPrompt, config: &AiConfig, model: &str) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "model": model,
+        "messages": chat_messages(prompt),
+        "stream": true,
+        "temperature": config.temperature,
+        "max_tokens": config.max_tokens,
+    });
+    if !config.stop_sequences.is_empty() {
+        body["stop"] = serde_json::json!(config.stop_sequences);
+    }
+    body
+}
+
+/// Build the non-streaming JSON request body, asking for native structured
+/// output when `json_mode` is enabled.
+fn build_json_request_body(
+    prompt: &ChatPrompt,
+    config: &AiConfig,
+    model: &str,
+) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "model": model,
+        "messages": chat_messages(prompt),
+        "stream": false,
+        "temperature": config.temperature,
+        "max_tokens": config.max_tokens,
+    });
+    if config.json_mode {
+        body["response_format"] = serde_json::json!({ "type": "json_object" });
+    }
+    body
+}
+
 fn first_model_id(body: &serde_json::Value) -> Option<String> {
     body.get("data")
         .and_then(|data| data.as_array())
@@ -202,42 +230,72 @@ fn first_model_id(body: &serde_json::Value) -> Option<String> {
         .map(str::to_owned)
 }
 
-fn parse_sse_tokens(text: &str) -> Vec<String> {
-    let mut tokens = Vec::new();
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
 
-    for line in text.lines() {
-        let line = line.trim();
-        if line == "data: [DONE]" {
-            break;
-        }
+    use super::{build_json_request_body, build_request_body, first_model_id};
+    use crate::prompt_format::ChatPrompt;
+    use crate::state::AiConfig;
 
-        let Some(json_str) = line.strip_prefix("data: ") else {
-            continue;
+    #[test]
+    fn build_request_body_carries_prompt_and_sampling_options() {
+        let prompt = ChatPrompt {
+            system: "sys".into(),
+            user: "usr".into(),
+            assistant: None,
+        };
+        let config = AiConfig {
+            temperature: 0.5,
+            max_tokens: 321,
+            ..AiConfig::default()
         };
 
-        if let Ok(value) = serde_json::from_str::<serde_json::Value>(json_str) {
-            if let Some(content) = value
-                .get("choices")
-                .and_then(|c| c.get(0))
-                .and_then(|c| c.get("delta"))
-                .and_then(|d| d.get("content"))
-                .and_then(|c| c.as_str())
-            {
-                if !content.is_empty() {
-                    tokens.push(content.to_owned());
-                }
-            }
-        }
+        let body = build_request_body(&prompt, &config, "served-model");
+
+        assert_eq!(body["model"], "served-model");
+        assert_eq!(body["messages"][0]["content"], "sys");
+        assert_eq!(body["messages"][1]["content"], "usr");
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["temperature"], 0.5);
+        assert_eq!(body["max_tokens"], 321);
+        assert!(body.get("stop").is_none());
     }
 
-    tokens
-}
+    #[test]
+    fn build_json_request_body_gates_response_format_on_json_mode() {
+        let prompt = ChatPrompt {
+            system: "sys".into(),
+            user: "usr".into(),
+            assistant: None,
+        };
+        let mut config = AiConfig::default();
 
-#[cfg(test)]
-mod tests {
-    use serde_json::json;
+        let native = build_json_request_body(&prompt, &config, "served-model");
+        config.json_mode = false;
+        let prompted = build_json_request_body(&prompt, &config, "served-model");
 
-    use super::{first_model_id, parse_sse_tokens};
+        assert_eq!(native["response_format"]["type"], "json_object");
+        assert_eq!(native["stream"], false);
+        assert!(prompted.get("response_format").is_none());
+    }
+
+    #[test]
+    fn build_request_body_includes_configured_stop_sequences() {
+        let prompt = ChatPrompt {
+            system: "sys".into(),
+            user: "usr".into(),
+            assistant: None,
+        };
+        let config = AiConfig {
+            stop_sequences: vec!["\n\nINT.".into()],
+            ..AiConfig::default()
+        };
+
+        let body = build_request_body(&prompt, &config, "served-model");
+
+        assert_eq!(body["stop"], serde_json::json!(["\n\nINT."]));
+    }
 
     #[test]
     fn first_model_id_reads_openai_compatible_model_list() {
@@ -249,15 +307,4 @@ mod tests {
 
         assert_eq!(first_model_id(&body), Some("local-model".to_owned()));
     }
-
-    #[test]
-    fn parse_sse_tokens_reads_streaming_delta_content() {
-        let tokens = parse_sse_tokens(
-            "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\
-             data: {\"choices\":[{\"delta\":{\"content\":\"lo\"}}]}\n\
-             data: [DONE]\n",
-        );
-
-        assert_eq!(tokens, vec!["Hel".to_owned(), "lo".to_owned()]);
-    }
 }
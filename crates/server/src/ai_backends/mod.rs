@@ -5,34 +5,63 @@ use serde::Serialize;
 
 use crate::prompt_format::ChatPrompt;
 use crate::state::{AiConfig, BackendType};
-use eidetic_core::ai::backend::GenerateStream;
+use eidetic_core::ai::backend::{FinishReason, GenerateChunk, GenerateStream};
 use eidetic_core::error::Error;
 
-/// Unified backend that dispatches to the configured implementation.
-pub(crate) enum Backend {
+/// Unified backend that dispatches to the configured implementation, then
+/// to each configured fallback in turn when a request cannot be started.
+pub(crate) struct Backend {
+    primary: BackendKind,
+    /// Fallbacks in order, each with the connection settings it overrides.
+    fallbacks: Vec<(BackendKind, AiConfig)>,
+}
+
+enum BackendKind {
     LlamaCpp(llamacpp::LlamaCppBackend),
     OpenRouter(openrouter::OpenRouterBackend),
 }
 
 impl Backend {
     pub fn from_config(config: &AiConfig) -> Self {
-        match config.backend_type {
-            BackendType::LlamaCpp => Backend::LlamaCpp(llamacpp::LlamaCppBackend::new(config)),
-            BackendType::OpenRouter => {
-                Backend::OpenRouter(openrouter::OpenRouterBackend::new(config))
-            }
+        let mut fallbacks = Vec::new();
+        let mut next = config.fallback.as_deref();
+        while let Some(fallback) = next {
+            fallbacks.push((BackendKind::from_config(fallback), fallback.clone()));
+            next = fallback.fallback.as_deref();
+        }
+        Self {
+            primary: BackendKind::from_config(config),
+            fallbacks,
         }
     }
 
+    /// Start a streaming generation. Only failures to start the stream (an
+    /// unreachable server, an error status, a missing key) move on to the
+    /// next fallback; errors mid-stream are left to the caller.
     pub async fn generate(
         &self,
         prompt: &ChatPrompt,
         config: &AiConfig,
     ) -> Result<GenerateStream, Error> {
-        match self {
-            Backend::LlamaCpp(b) => b.generate(prompt, config).await,
-            Backend::OpenRouter(b) => b.generate(prompt, config).await,
+        let mut failures = Vec::new();
+        match self.primary.generate(prompt, config).await {
+            Ok(stream) => return Ok(stream),
+            Err(error) => failures.push(failure_message(error)),
+        }
+        for (backend, fallback) in &self.fallbacks {
+            let fallback_config = config.with_connection_of(fallback);
+            tracing::warn!(
+                "AI backend failed ({}), falling back to {:?} model {}",
+                failures.last().map(String::as_str).unwrap_or_default(),
+                fallback_config.backend_type,
+                fallback_config.model
+            );
+            match backend.generate(prompt, &fallback_config).await {
+                Ok(stream) => return Ok(stream),
+                Err(error) => failures.push(failure_message(error)),
+            }
         }
+        Err(chain_failure(failures))
     }
 
     /// Generate and collect the full response into a single string.
@@ -46,7 +75,11 @@ impl Backend {
         let mut full = String::new();
         while let Some(item) = stream.next().await {
             match item {
-                Ok(token) => full.push_str(&token),
+                Ok(GenerateChunk::Token(token)) => full.push_str(&token),
+                Ok(GenerateChunk::Finished(reason)) if reason.is_truncated() => {
+                    tracing::warn!("Full generation stopped at the token limit");
+                }
+                Ok(GenerateChunk::Finished(_)) => {}
                 Err(e) => {
                     tracing::warn!("Stream error during full generation: {e}");
                     break;
@@ -57,26 +90,107 @@ impl Backend {
     }
 
     /// Non-streaming generation with JSON mode — constrains the model to
-    /// produce valid JSON output.  Used for structured extraction results.
+    /// produce valid JSON output when `json_mode` is enabled. Callers should
+    /// still tolerate fenced or chatty output from models that ignore it.
     pub async fn generate_json(
         &self,
         prompt: &ChatPrompt,
         config: &AiConfig,
     ) -> Result<String, Error> {
-        match self {
-            Backend::LlamaCpp(b) => b.generate_json(prompt, config).await,
-            Backend::OpenRouter(b) => b.generate_json(prompt, config).await,
+        let mut failures = Vec::new();
+        match self.primary.generate_json(prompt, config).await {
+            Ok(text) => return Ok(text),
+            Err(error) => failures.push(failure_message(error)),
+        }
+        for (backend, fallback) in &self.fallbacks {
+            let fallback_config = config.with_connection_of(fallback);
+            tracing::warn!(
+                "AI backend failed ({}), falling back to {:?} model {}",
+                failures.last().map(String::as_str).unwrap_or_default(),
+                fallback_config.backend_type,
+                fallback_config.model
+            );
+            match backend.generate_json(prompt, &fallback_config).await {
+                Ok(text) => return Ok(text),
+                Err(error) => failures.push(failure_message(error)),
+            }
+        }
+        Err(chain_failure(failures))
+    }
+
+    /// The exact JSON body `generate` would post to the primary backend, for
+    /// debugging payloads.
+    pub async fn request_body(&self, prompt: &ChatPrompt, config: &AiConfig) -> serde_json::Value {
+        match &self.primary {
+            BackendKind::LlamaCpp(b) => b.request_body(prompt, config).await,
+            BackendKind::OpenRouter(b) => b.request_body(prompt, config),
         }
     }
 
     pub async fn health_check(&self) -> Result<BackendStatus, Error> {
+        self.primary.health_check().await
+    }
+
+    /// Health of each fallback, in the order they would be tried.
+    pub async fn fallback_health_checks(&self) -> Vec<(AiConfig, Result<BackendStatus, Error>)> {
+        let mut statuses = Vec::new();
+        for (backend, fallback) in &self.fallbacks {
+            statuses.push((fallback.clone(), backend.health_check().await));
+        }
+        statuses
+    }
+}
+
+impl BackendKind {
+    fn from_config(config: &AiConfig) -> Self {
+        match config.backend_type {
+            BackendType::LlamaCpp => Self::LlamaCpp(llamacpp::LlamaCppBackend::new(config)),
+            BackendType::OpenRouter => Self::OpenRouter(openrouter::OpenRouterBackend::new(config)),
+        }
+    }
+
+    async fn generate(
+        &self,
+        prompt: &ChatPrompt,
+        config: &AiConfig,
+    ) -> Result<GenerateStream, Error> {
+        match self {
+            Self::LlamaCpp(b) => b.generate(prompt, config).await,
+            Self::OpenRouter(b) => b.generate(prompt, config).await,
+        }
+    }
+
+    async fn generate_json(&self, prompt: &ChatPrompt, config: &AiConfig) -> Result<String, Error> {
+        match self {
+            Self::LlamaCpp(b) => b.generate_json(prompt, config).await,
+            Self::OpenRouter(b) => b.generate_json(prompt, config).await,
+        }
+    }
+
+    async fn health_check(&self) -> Result<BackendStatus, Error> {
         match self {
-            Backend::LlamaCpp(b) => b.health_check().await,
-            Backend::OpenRouter(b) => b.health_check().await,
+            Self::LlamaCpp(b) => b.health_check().await,
+            Self::OpenRouter(b) => b.health_check().await,
         }
     }
 }
 
+fn failure_message(error: Error) -> String {
+    match error {
+        Error::AiBackend(message) => message,
+        other => other.to_string(),
+    }
+}
+
+/// A single backend's error as-is, or every error in the chain once a
+/// fallback has also been tried.
+fn chain_failure(mut failures: Vec<String>) -> Error {
+    if failures.len() == 1 {
+        return Error::AiBackend(failures.remove(0));
+    }
+    Error::AiBackend(format!("all AI backends failed: {}", failures.join("; ")))
+}
+
 /// Status of a backend connection.
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct BackendStatus {
@@ -85,3 +199,144 @@ pub(crate) struct BackendStatus {
     pub backend_type: BackendType,
     pub message: String,
 }
+
+/// Build the OpenAI-compatible `messages` array for a prompt, ending with the
+/// assistant's prior turn when the prompt continues earlier output.
+pub(crate) fn chat_messages(prompt: &ChatPrompt) -> serde_json::Value {
+    let mut messages = vec![
+        serde_json::json!({ "role": "system", "content": prompt.system }),
+        serde_json::json!({ "role": "user", "content": prompt.user }),
+    ];
+    if let Some(assistant) = &prompt.assistant {
+        messages.push(serde_json::json!({ "role": "assistant", "content": assistant }));
+    }
+    serde_json::Value::Array(messages)
+}
+
+/// Parse the server-sent events in one streamed chunk into tokens and the
+/// finish reason. Reads the OpenAI `finish_reason` and Ollama `done_reason`.
+pub(crate) fn parse_sse_chunks(text: &str) -> Vec<GenerateChunk> {
+    let mut chunks = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line == "data: [DONE]" {
+            break;
+        }
+
+        let Some(json_str) = line.strip_prefix("data: ") else {
+            continue;
+        };
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(json_str) else {
+            continue;
+        };
+
+        let choice = value.get("choices").and_then(|c| c.get(0));
+        if let Some(content) = choice
+            .and_then(|c| c.get("delta"))
+            .and_then(|d| d.get("content"))
+            .and_then(|c| c.as_str())
+            .filter(|content| !content.is_empty())
+        {
+            chunks.push(GenerateChunk::Token(content.to_owned()));
+        }
+        if let Some(reason) = choice
+            .and_then(|c| c.get("finish_reason"))
+            .or_else(|| value.get("done_reason"))
+            .and_then(|r| r.as_str())
+        {
+            chunks.push(GenerateChunk::Finished(FinishReason::from_provider(reason)));
+        }
+    }
+
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn generate_tries_each_fallback_and_reports_every_failure() {
+        let config = AiConfig {
+            backend_type: BackendType::OpenRouter,
+            model: "primary-model".into(),
+            fallback: Some(Box::new(AiConfig {
+                backend_type: BackendType::OpenRouter,
+                model: "fallback-model".into(),
+                ..AiConfig::default()
+            })),
+            ..AiConfig::default()
+        };
+        let backend = Backend::from_config(&config);
+        let prompt = ChatPrompt {
+            system: "sys".into(),
+            user: "usr".into(),
+            assistant: None,
+        };
+
+        let Err(error) = backend.generate(&prompt, &config).await else {
+            panic!("every backend lacks an API key");
+        };
+
+        assert_eq!(backend.fallbacks.len(), 1);
+        assert_eq!(
+            error.to_string(),
+            "AI backend error: all AI backends failed: \
+             OpenRouter API key not configured; OpenRouter API key not configured"
+        );
+    }
+
+    #[test]
+    fn chat_messages_end_with_the_assistant_prior_turn() {
+        let prompt = ChatPrompt {
+            system: "sys".into(),
+            user: "usr".into(),
+            assistant: Some("Mara grips the rail".into()),
+        };
+
+        let messages = chat_messages(&prompt);
+
+        assert_eq!(messages.as_array().map(Vec::len), Some(3));
+        assert_eq!(messages[2]["role"], "assistant");
+        assert_eq!(messages[2]["content"], "Mara grips the rail");
+    }
+
+    #[test]
+    fn parse_sse_chunks_reads_streaming_delta_content() {
+        let chunks = parse_sse_chunks(
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\n\
+             data: {\"choices\":[{\"delta\":{\"content\":\"lo\"}}]}\n\
+             data: [DONE]\n",
+        );
+
+        assert_eq!(
+            chunks,
+            vec![
+                GenerateChunk::Token("Hel".to_owned()),
+                GenerateChunk::Token("lo".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_sse_chunks_reports_finish_and_done_reasons() {
+        let openai = parse_sse_chunks(
+            "data: {\"choices\":[{\"delta\":{\"content\":\"cut\"},\"finish_reason\":\"length\"}]}\n",
+        );
+        let ollama = parse_sse_chunks("data: {\"done\":true,\"done_reason\":\"stop\"}\n");
+
+        assert_eq!(
+            openai,
+            vec![
+                GenerateChunk::Token("cut".to_owned()),
+                GenerateChunk::Finished(FinishReason::Length),
+            ]
+        );
+        assert_eq!(ollama, vec![GenerateChunk::Finished(FinishReason::Stop)]);
+        assert!(
+            parse_sse_chunks("data: {\"choices\":[{\"delta\":{},\"finish_reason\":null}]}\n")
+                .is_empty()
+        );
+    }
+}
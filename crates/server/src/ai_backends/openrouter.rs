@@ -6,7 +6,7 @@ use crate::state::{AiConfig, BackendType};
 use eidetic_core::ai::backend::GenerateStream;
 use eidetic_core::error::Error;
 
-use super::BackendStatus;
+use super::{BackendStatus, chat_messages, parse_sse_chunks};
 
 const OPENROUTER_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
 
@@ -32,16 +32,7 @@ impl OpenRouterBackend {
             .filter(|k| !k.is_empty())
             .ok_or_else(|| Error::AiBackend("OpenRouter API key not configured".into()))?;
 
-        let body = serde_json::json!({
-            "model": config.model,
-            "messages": [
-                { "role": "system", "content": prompt.system },
-                { "role": "user", "content": prompt.user }
-            ],
-            "stream": true,
-            "temperature": config.temperature,
-            "max_tokens": config.max_tokens,
-        });
+        let body = build_request_body(prompt, config);
 
         let response = self
             .client
@@ -62,37 +53,10 @@ impl OpenRouterBackend {
             )));
         }
 
-        // Parse SSE stream: lines like `data: {"choices":[{"delta":{"content":"token"}}]}`
         let byte_stream = response.bytes_stream();
         let token_stream = byte_stream
             .map(|chunk| match chunk {
-                Ok(bytes) => {
-                    let text = String::from_utf8_lossy(&bytes);
-                    let mut tokens = Vec::new();
-                    for line in text.lines() {
-                        let line = line.trim();
-                        if line == "data: [DONE]" {
-                            break;
-                        }
-                        let Some(json_str) = line.strip_prefix("data: ") else {
-                            continue;
-                        };
-                        if let Ok(value) = serde_json::from_str::<serde_json::Value>(json_str) {
-                            if let Some(content) = value
-                                .get("choices")
-                                .and_then(|c| c.get(0))
-                                .and_then(|c| c.get("delta"))
-                                .and_then(|d| d.get("content"))
-                                .and_then(|c| c.as_str())
-                            {
-                                if !content.is_empty() {
-                                    tokens.push(content.to_owned());
-                                }
-                            }
-                        }
-                    }
-                    tokens
-                }
+                Ok(bytes) => parse_sse_chunks(&String::from_utf8_lossy(&bytes)),
                 Err(e) => {
                     tracing::warn!("OpenRouter stream chunk error: {e}");
                     vec![]
@@ -104,8 +68,13 @@ impl OpenRouterBackend {
         Ok(Box::pin(token_stream))
     }
 
-    /// Non-streaming generation with JSON mode enabled.
-    /// Uses OpenAI-compatible `response_format` parameter.
+    /// The streaming request body `generate` would send, for debugging.
+    pub fn request_body(&self, prompt: &ChatPrompt, config: &AiConfig) -> serde_json::Value {
+        build_request_body(prompt, config)
+    }
+
+    /// Non-streaming generation for structured output. Uses the
+    /// OpenAI-compatible `response_format` parameter when `json_mode` is set.
     pub async fn generate_json(
         &self,
         prompt: &ChatPrompt,
@@ -117,17 +86,7 @@ impl OpenRouterBackend {
             .filter(|k| !k.is_empty())
             .ok_or_else(|| Error::AiBackend("OpenRouter API key not configured".into()))?;
 
-        let body = serde_json::json!({
-            "model": config.model,
-            "messages": [
-                { "role": "system", "content": prompt.system },
-                { "role": "user", "content": prompt.user }
-            ],
-            "stream": false,
-            "temperature": config.temperature,
-            "max_tokens": config.max_tokens,
-            "response_format": { "type": "json_object" },
-        });
+        let body = build_json_request_body(prompt, config);
 
         let response = self
             .client
@@ -194,3 +153,34 @@ impl OpenRouterBackend {
         }
     }
 }
+
+/// Build the OpenAI-compatible streaming chat-completions body.
+fn build_request_body(prompt: &ChatPrompt, config: &AiConfig) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "model": config.model,
+        "messages": chat_messages(prompt),
+        "stream": true,
+        "temperature": config.temperature,
+        "max_tokens": config.max_tokens,
+    });
+    if !config.stop_sequences.is_empty() {
+        body["stop"] = serde_json::json!(config.stop_sequences);
+    }
+    body
+}
+
+/// Build the non-streaming JSON request body, asking for native structured
+/// output when `json_mode` is enabled.
+fn build_json_request_body(prompt: &ChatPrompt, config: &AiConfig) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "model": config.model,
+        "messages": chat_messages(prompt),
+        "stream": false,
+        "temperature": config.temperature,
+        "max_tokens": config.max_tokens,
+    });
+    if config.json_mode {
+        body["response_format"] = serde_json::json!({ "type": "json_object" });
+    }
+    body
+}
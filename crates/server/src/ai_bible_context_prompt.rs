@@ -1,7 +1,37 @@
+use eidetic_core::ai::note_template::{
+    expand_note_placeholders, placeholder_key, placeholder_text,
+};
 use eidetic_core::contracts::{
     AiBibleContextField, AiBibleContextProjection, FieldValue, ProjectionEnvelope,
 };
 
+/// Expand `{EntityName}` placeholders in notes into the entity's name and
+/// current field values from the attached bible context.
+pub(crate) fn expand_entity_placeholders(
+    notes: &str,
+    context: &AiBibleContextProjection,
+) -> String {
+    expand_note_placeholders(notes, |key| {
+        let node = context
+            .nodes
+            .iter()
+            .find(|node| placeholder_key(&node.name) == key)?;
+        let fields = node
+            .fields
+            .iter()
+            .map(|field| {
+                format!(
+                    "{}: {}",
+                    field.field_key.as_str(),
+                    field_value_label(&field.value)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("; ");
+        Some(placeholder_text(&node.name, &fields))
+    })
+}
+
 pub(crate) fn append_bible_context(
     user: &mut String,
     context: &ProjectionEnvelope<AiBibleContextProjection>,
@@ -67,7 +97,7 @@ fn append_field(user: &mut String, indent: &str, field: &AiBibleContextField) {
     ));
 }
 
-fn field_value_label(value: &FieldValue) -> String {
+pub(crate) fn field_value_label(value: &FieldValue) -> String {
     match value {
         FieldValue::Text(value) => value.clone(),
         FieldValue::Integer(value) => value.to_string(),
@@ -78,7 +108,7 @@ fn field_value_label(value: &FieldValue) -> String {
     }
 }
 
-fn edge_kind_label(value: &eidetic_core::contracts::BibleGraphEdgeKind) -> String {
+pub(crate) fn edge_kind_label(value: &eidetic_core::contracts::BibleGraphEdgeKind) -> String {
     match value {
         eidetic_core::contracts::BibleGraphEdgeKind::References => "references".to_string(),
         eidetic_core::contracts::BibleGraphEdgeKind::LocatedIn => "located_in".to_string(),
@@ -2,7 +2,7 @@ use std::collections::BTreeSet;
 
 use eidetic_core::contracts::{
     AiBibleContextEdge, AiBibleContextField, AiBibleContextNode, AiBibleContextProjection,
-    AiBibleContextSnapshot, BibleGraphEdge, BibleGraphEdgeId, BibleGraphNode,
+    AiBibleContextSnapshot, BibleGraphEdge, BibleGraphNode, BibleGraphNodeId,
     BibleGraphPartProjection, BibleGraphSnapshotProjection, BibleRenderGraphProjectionRequest,
     ChangeEventId, ObjectKind, ProjectionEnvelope, ProjectionVersion,
 };
@@ -32,7 +32,7 @@ pub(crate) fn load_ai_bible_context_projection(
         .nodes
         .into_iter()
         .filter(|node| !node.system_owned)
-        .map(|node| load_context_node(conn, node, &visible_edge_ids))
+        .map(|node| load_context_node(conn, node, |edge| visible_edge_ids.contains(&edge.id)))
         .collect::<Result<Vec<_>, _>>()?;
     let projection = AiBibleContextProjection {
         target_node_id,
@@ -50,10 +50,66 @@ pub(crate) fn load_ai_bible_context_projection(
     }
 }
 
+/// Apply a generate request's entity overrides to a loaded context. Forced
+/// includes missing from the bounded graph are loaded in full, with their
+/// edges to other context nodes; excludes are dropped along with any edge
+/// that points at them. Unknown ids are rejected.
+pub(crate) fn apply_ai_bible_context_overrides(
+    conn: &Connection,
+    projection: &mut AiBibleContextProjection,
+    include_ids: &[BibleGraphNodeId],
+    exclude_ids: &[BibleGraphNodeId],
+) -> Result<(), HistoryStoreError> {
+    if let Some(id) = include_ids.iter().find(|id| exclude_ids.contains(id)) {
+        return Err(HistoryStoreError::InvalidValue(format!(
+            "bible entity both included and excluded: {}",
+            id.as_str()
+        )));
+    }
+    let mut forced = Vec::new();
+    for id in include_ids.iter().chain(exclude_ids) {
+        let Some(node) = bible_graph_store::load_node(conn, id)? else {
+            return Err(HistoryStoreError::InvalidId(format!(
+                "bible entity not found: {}",
+                id.as_str()
+            )));
+        };
+        let present = projection
+            .nodes
+            .iter()
+            .any(|context| &context.node_id == id);
+        if include_ids.contains(id) && !present && !forced.contains(&node) {
+            forced.push(node);
+        }
+    }
+
+    let excluded = |id: &BibleGraphNodeId| exclude_ids.contains(id);
+    projection.nodes.retain(|node| !excluded(&node.node_id));
+    for node in &mut projection.nodes {
+        node.incoming_edges
+            .retain(|edge| !excluded(&edge.from_node_id));
+        node.outgoing_edges
+            .retain(|edge| !excluded(&edge.to_node_id));
+    }
+
+    let context_ids: BTreeSet<BibleGraphNodeId> = projection
+        .nodes
+        .iter()
+        .map(|node| node.node_id.clone())
+        .chain(forced.iter().map(|node| node.id.clone()))
+        .collect();
+    for node in forced {
+        projection.nodes.push(load_context_node(conn, node, |edge| {
+            context_ids.contains(&edge.from_node_id) && context_ids.contains(&edge.to_node_id)
+        })?);
+    }
+    Ok(())
+}
+
 fn load_context_node(
     conn: &Connection,
     node: BibleGraphNode,
-    visible_edge_ids: &BTreeSet<BibleGraphEdgeId>,
+    edge_visible: impl Fn(&BibleGraphEdge) -> bool,
 ) -> Result<AiBibleContextNode, HistoryStoreError> {
     let Some(detail) = bible_graph_store::load_node_detail_projection(conn, &node.id)? else {
         return Err(HistoryStoreError::InvalidValue(format!(
@@ -69,8 +125,8 @@ fn load_context_node(
         name: node.name,
         fields: context_fields(detail.parts),
         snapshots: context_snapshots(detail.snapshots),
-        incoming_edges: context_edges(detail.incoming_edges, visible_edge_ids),
-        outgoing_edges: context_edges(detail.outgoing_edges, visible_edge_ids),
+        incoming_edges: context_edges(detail.incoming_edges, &edge_visible),
+        outgoing_edges: context_edges(detail.outgoing_edges, &edge_visible),
     })
 }
 
@@ -124,11 +180,11 @@ fn context_snapshots(snapshots: Vec<BibleGraphSnapshotProjection>) -> Vec<AiBibl
 
 fn context_edges(
     edges: Vec<BibleGraphEdge>,
-    visible_edge_ids: &BTreeSet<BibleGraphEdgeId>,
+    edge_visible: &impl Fn(&BibleGraphEdge) -> bool,
 ) -> Vec<AiBibleContextEdge> {
     edges
         .into_iter()
-        .filter(|edge| visible_edge_ids.contains(&edge.id))
+        .filter(|edge| edge_visible(edge))
         .map(|edge| AiBibleContextEdge {
             edge_id: edge.id,
             from_node_id: edge.from_node_id,
@@ -7,7 +7,7 @@ use eidetic_core::contracts::{
 use eidetic_core::timeline::node::NodeId;
 use rusqlite::Connection;
 
-use super::load_ai_bible_context_projection;
+use super::{apply_ai_bible_context_overrides, load_ai_bible_context_projection};
 
 #[test]
 fn ai_context_projection_loads_graph_facts_for_prompting() {
@@ -62,6 +62,62 @@ fn ai_context_projection_uses_bounded_render_graph_defaults() {
     );
 }
 
+#[test]
+fn ai_context_overrides_force_includes_and_drop_excludes() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    seed_graph(&mut conn);
+    for index in 0..200 {
+        seed_basic_node(
+            &mut conn,
+            &format!("node.place.{index:03}"),
+            &format!("Place {index:03}"),
+            index,
+        );
+    }
+    let ada = BibleGraphNodeId::new("node.character.ada").unwrap();
+    let beach = BibleGraphNodeId::new("node.place.beach").unwrap();
+    let last_place = BibleGraphNodeId::new("node.place.199").unwrap();
+    let mut projection = load_ai_bible_context_projection(&conn, NodeId::new())
+        .unwrap()
+        .payload;
+    assert!(
+        !projection
+            .nodes
+            .iter()
+            .any(|node| node.node_id == last_place)
+    );
+
+    apply_ai_bible_context_overrides(
+        &conn,
+        &mut projection,
+        std::slice::from_ref(&last_place),
+        std::slice::from_ref(&beach),
+    )
+    .unwrap();
+
+    assert_eq!(projection.nodes.len(), 200);
+    assert!(!projection.nodes.iter().any(|node| node.node_id == beach));
+    assert_eq!(projection.nodes.last().unwrap().node_id, last_place);
+    let ada_context = projection
+        .nodes
+        .iter()
+        .find(|node| node.node_id == ada)
+        .expect("ada context node");
+    assert!(ada_context.outgoing_edges.is_empty());
+
+    let missing = BibleGraphNodeId::new("node.character.nobody").unwrap();
+    assert!(apply_ai_bible_context_overrides(&conn, &mut projection, &[missing], &[]).is_err());
+    assert!(
+        apply_ai_bible_context_overrides(
+            &conn,
+            &mut projection,
+            std::slice::from_ref(&ada),
+            std::slice::from_ref(&ada),
+        )
+        .is_err()
+    );
+}
+
 fn seed_graph(conn: &mut Connection) {
     seed_node(conn, "node.character.ada", "character", "Ada", 10, 100);
     seed_node(conn, "node.place.beach", "place", "Beach", 20, 200);
@@ -103,6 +159,7 @@ fn seed_graph(conn: &mut Connection) {
             at_ms: 1_000,
             label: "Opening".to_string(),
             snapshot_sort_order: 10,
+            source_node_id: None,
             field_id: BibleGraphSnapshotFieldId::new("snapshot-field.character.tagline").unwrap(),
             part_key: BibleGraphPartKey::new("profile").unwrap(),
             part_name: "Profile".to_string(),
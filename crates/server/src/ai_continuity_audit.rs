@@ -0,0 +1,280 @@
+use std::path::PathBuf;
+
+use eidetic_core::Project;
+use eidetic_core::ai::prompt::parse_continuity_verdict;
+use eidetic_core::contracts::{
+    BibleGraphNodeId, BibleGraphSnapshotField, BibleGraphSnapshotProjection, resolved_state_at,
+};
+use eidetic_core::timeline::node::StoryNode;
+use serde::Serialize;
+use tracing::Instrument;
+
+use crate::ai_backends::Backend;
+use crate::ai_service::active_sqlite_project;
+use crate::backend_error::BackendError;
+use crate::history_store::HistoryStoreError;
+use crate::prompt_format::build_continuity_audit_prompt;
+use crate::state::{AppState, ServerEvent};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AiContinuityAuditResponse {
+    pub status: String,
+    /// Entities with at least one snapshot to audit against.
+    pub entity_count: usize,
+    /// Node/entity pairs that will be sent to the model.
+    pub check_count: usize,
+}
+
+/// A bible entity and its snapshots, in time order.
+struct SnapshottedEntity {
+    id: BibleGraphNodeId,
+    name: String,
+    snapshots: Vec<BibleGraphSnapshotProjection>,
+}
+
+/// One node's content to compare against an entity's expected state.
+struct ContinuityCheck {
+    entity_id: BibleGraphNodeId,
+    entity_name: String,
+    expected_state: Vec<BibleGraphSnapshotField>,
+    node: StoryNode,
+}
+
+/// Audit drafted content against every snapshotted bible entity in the
+/// background. Each contradiction is reported as a
+/// [`ServerEvent::ContinuityFinding`], followed by one
+/// [`ServerEvent::ContinuityAuditComplete`].
+pub async fn start_continuity_audit(
+    state: &AppState,
+) -> Result<AiContinuityAuditResponse, BackendError> {
+    let (project, project_path) = active_sqlite_project(state).await?;
+    let entities = load_snapshotted_entities(project_path).await?;
+    let checks = continuity_checks(&project, &entities);
+    if checks.is_empty() {
+        return Err(BackendError::bad_request(
+            "no drafted content mentions an entity after one of its snapshots",
+        ));
+    }
+
+    let response = AiContinuityAuditResponse {
+        status: "started".to_string(),
+        entity_count: entities.len(),
+        check_count: checks.len(),
+    };
+    let state_clone = state.clone();
+    state
+        .task_supervisor
+        .spawn("ai-continuity-audit", async move {
+            run_continuity_audit(state_clone, checks).await;
+        });
+
+    Ok(response)
+}
+
+#[tracing::instrument(name = "continuity_audit", skip_all, fields(model))]
+async fn run_continuity_audit(state: AppState, checks: Vec<ContinuityCheck>) {
+    let config = state.active_ai_config();
+    tracing::Span::current().record("model", config.model.as_str());
+    let backend = Backend::from_config(&config);
+    let checked_count = checks.len();
+    let mut finding_count = 0;
+
+    for check in checks {
+        let prompt =
+            build_continuity_audit_prompt(&check.entity_name, &check.expected_state, &check.node);
+        let span = tracing::info_span!(
+            "continuity_check",
+            node_id = %check.node.id.0,
+            entity_id = check.entity_id.as_str()
+        );
+        let verdict = match backend
+            .generate_json(&prompt, &config)
+            .instrument(span)
+            .await
+        {
+            Ok(json_text) => parse_continuity_verdict(&json_text),
+            Err(error) => {
+                tracing::warn!(
+                    "Continuity audit of node {} against {} failed: {error}",
+                    check.node.id.0,
+                    check.entity_id.as_str()
+                );
+                continue;
+            }
+        };
+        match verdict {
+            Ok(verdict) if verdict.contradiction => {
+                finding_count += 1;
+                let _ = state.events_tx.send(ServerEvent::ContinuityFinding {
+                    node_id: check.node.id.0,
+                    entity_id: check.entity_id,
+                    entity_name: check.entity_name,
+                    explanation: verdict.explanation,
+                });
+            }
+            Ok(_) => {}
+            Err(error) => {
+                tracing::warn!(
+                    "Failed to parse continuity verdict for node {}: {error}",
+                    check.node.id.0
+                );
+            }
+        }
+    }
+
+    let _ = state.events_tx.send(ServerEvent::ContinuityAuditComplete {
+        checked_count,
+        finding_count,
+    });
+}
+
+async fn load_snapshotted_entities(path: PathBuf) -> Result<Vec<SnapshottedEntity>, BackendError> {
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path).map_err(|error| {
+            BackendError::Internal(format!("open continuity audit database failed: {error}"))
+        })?;
+        load_snapshotted_entities_from(&conn)
+            .map_err(|error| BackendError::Internal(error.to_string()))
+    })
+    .await
+    .map_err(|error| {
+        BackendError::Internal(format!("continuity audit load task failed: {error}"))
+    })?
+}
+
+fn load_snapshotted_entities_from(
+    conn: &rusqlite::Connection,
+) -> Result<Vec<SnapshottedEntity>, HistoryStoreError> {
+    crate::bible_graph_store::create_schema(conn)?;
+    let mut entities = Vec::new();
+    for node in crate::bible_graph_store::load_node_list_projection(conn)?.nodes {
+        if node.system_owned {
+            continue;
+        }
+        let snapshots =
+            crate::bible_graph_snapshot_store::load_snapshot_projections(conn, &node.id)?;
+        if snapshots.is_empty() {
+            continue;
+        }
+        entities.push(SnapshottedEntity {
+            id: node.id,
+            name: node.name,
+            snapshots,
+        });
+    }
+    Ok(entities)
+}
+
+/// Pair each entity with the drafted nodes that start at or after its first
+/// snapshot and mention it by name, resolving the state expected there.
+fn continuity_checks(project: &Project, entities: &[SnapshottedEntity]) -> Vec<ContinuityCheck> {
+    let mut drafted: Vec<&StoryNode> = project
+        .timeline
+        .nodes
+        .iter()
+        .filter(|node| !node.content.content.trim().is_empty())
+        .collect();
+    drafted.sort_by_key(|node| (node.time_range.start_ms, node.level));
+
+    let mut checks = Vec::new();
+    for entity in entities {
+        let name = entity.name.to_lowercase();
+        for node in &drafted {
+            if !node.content.content.to_lowercase().contains(&name) {
+                continue;
+            }
+            let expected_state = resolved_state_at(&entity.snapshots, node.time_range.start_ms);
+            if expected_state.is_empty() {
+                continue;
+            }
+            checks.push(ContinuityCheck {
+                entity_id: entity.id.clone(),
+                entity_name: entity.name.clone(),
+                expected_state,
+                node: (*node).clone(),
+            });
+        }
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use eidetic_core::Template;
+    use eidetic_core::contracts::{
+        BibleGraphFieldKey, BibleGraphPartKey, BibleGraphSnapshot, BibleGraphSnapshotFieldId,
+        BibleGraphSnapshotId, FieldValue,
+    };
+    use eidetic_core::timeline::node::StoryLevel;
+
+    use super::*;
+
+    #[test]
+    fn checks_cover_mentioning_nodes_after_the_first_snapshot() {
+        let mut project = Template::MultiCam.build_project("Continuity Audit Test");
+        let scenes: Vec<_> = project
+            .timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .into_iter()
+            .take(3)
+            .map(|node| node.id)
+            .collect();
+        for (index, text) in [
+            "MARA waits at the harbor.",
+            "Mara climbs the lighthouse stairs.",
+            "Jonah sleeps.",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            project
+                .timeline
+                .node_mut(scenes[index])
+                .unwrap()
+                .content
+                .content = text.into();
+        }
+        let snapshot_at = project
+            .timeline
+            .node(scenes[1])
+            .unwrap()
+            .time_range
+            .start_ms;
+        let entity = SnapshottedEntity {
+            id: BibleGraphNodeId::new("node.character.mara").unwrap(),
+            name: "Mara".into(),
+            snapshots: vec![BibleGraphSnapshotProjection {
+                snapshot: BibleGraphSnapshot {
+                    id: BibleGraphSnapshotId::new("snapshot.mara.mainland").unwrap(),
+                    node_id: BibleGraphNodeId::new("node.character.mara").unwrap(),
+                    at_ms: snapshot_at,
+                    label: "On the mainland".into(),
+                    sort_order: 0,
+                    source_node_id: None,
+                },
+                fields: vec![BibleGraphSnapshotField {
+                    id: BibleGraphSnapshotFieldId::new("snapshot-field.mara.location").unwrap(),
+                    snapshot_id: BibleGraphSnapshotId::new("snapshot.mara.mainland").unwrap(),
+                    part_key: BibleGraphPartKey::new("state").unwrap(),
+                    part_name: "State".into(),
+                    field_key: BibleGraphFieldKey::new("location").unwrap(),
+                    value: Some(FieldValue::Text("mainland".into())),
+                    sort_order: 0,
+                }],
+            }],
+        };
+
+        let checks = continuity_checks(&project, &[entity]);
+
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].node.id, scenes[1]);
+        assert_eq!(checks[0].expected_state.len(), 1);
+        let prompt = build_continuity_audit_prompt(
+            &checks[0].entity_name,
+            &checks[0].expected_state,
+            &checks[0].node,
+        );
+        assert!(prompt.user.contains("- State / location: mainland"));
+        assert!(prompt.user.contains("Mara climbs the lighthouse stairs."));
+    }
+}
@@ -0,0 +1,313 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use eidetic_core::timeline::node::NodeId;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::ai_generation_service::generate_queued_node;
+use crate::ai_service::active_sqlite_project;
+use crate::backend_error::BackendError;
+use crate::command_service_support::{active_project_path, map_history_error};
+use crate::generation_queue_store;
+use crate::state::{AppState, ServerEvent};
+
+pub use crate::generation_queue_store::{GenerationQueueItem, GenerationQueueStatus};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AiQueueEnqueueRequest {
+    pub node_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AiQueueCancelRequest {
+    pub node_id: Uuid,
+}
+
+/// Every queued node with its status, in queue order.
+pub async fn generation_queue(state: &AppState) -> Result<Vec<GenerationQueueItem>, BackendError> {
+    let path = active_project_path(state)?;
+    with_queue(path, |conn| generation_queue_store::load_queue(conn)).await
+}
+
+/// Queue nodes for generation and start the worker if it is idle.
+pub async fn enqueue_generation(
+    state: &AppState,
+    body: AiQueueEnqueueRequest,
+) -> Result<Vec<GenerationQueueItem>, BackendError> {
+    if body.node_ids.is_empty() {
+        return Err(BackendError::bad_request("no nodes to queue"));
+    }
+    let node_ids: Vec<NodeId> = {
+        let (project, _) = active_sqlite_project(state).await?;
+        body.node_ids
+            .into_iter()
+            .map(|node_uuid| Ok(project.timeline.node(NodeId(node_uuid))?.id))
+            .collect::<Result<_, eidetic_core::Error>>()?
+    };
+    enqueue_nodes(state, node_ids).await?;
+    generation_queue(state).await
+}
+
+/// Remove a node that has not started generating yet.
+pub async fn cancel_queued_generation(
+    state: &AppState,
+    body: AiQueueCancelRequest,
+) -> Result<Vec<GenerationQueueItem>, BackendError> {
+    let path = active_project_path(state)?;
+    let node_id = NodeId(body.node_id);
+    let cancelled = with_queue(path, move |conn| {
+        generation_queue_store::cancel(conn, node_id)
+    })
+    .await?;
+    if !cancelled {
+        return Err(BackendError::not_found(format!(
+            "no pending queue item for node {}",
+            body.node_id
+        )));
+    }
+    generation_queue(state).await
+}
+
+/// Put items a previous session left running back in line and resume
+/// draining. Called when a project is opened.
+pub async fn resume_generation_queue(state: &AppState) -> Result<(), BackendError> {
+    let path = active_project_path(state)?;
+    with_queue(path, |conn| generation_queue_store::requeue_running(conn)).await?;
+    ensure_queue_worker(state);
+    Ok(())
+}
+
+pub(crate) async fn enqueue_nodes(
+    state: &AppState,
+    node_ids: Vec<NodeId>,
+) -> Result<(), BackendError> {
+    let path = active_project_path(state)?;
+    with_queue(path, move |conn| {
+        generation_queue_store::enqueue(conn, &node_ids)
+    })
+    .await?;
+    ensure_queue_worker(state);
+    Ok(())
+}
+
+/// The right to run the queue worker. Dropping it clears
+/// [`AppState::generation_queue_worker`], so a worker that is aborted or
+/// panics doesn't stop later enqueues from starting a new one.
+struct QueueWorkerClaim(Arc<AtomicBool>);
+
+impl QueueWorkerClaim {
+    fn acquire(flag: &Arc<AtomicBool>) -> Option<Self> {
+        if flag.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+        Some(Self(Arc::clone(flag)))
+    }
+}
+
+impl Drop for QueueWorkerClaim {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+/// Start the single queue worker unless one is already draining. The worker
+/// generates one node at a time and exits when nothing is pending.
+fn ensure_queue_worker(state: &AppState) {
+    let Some(mut claim) = QueueWorkerClaim::acquire(&state.generation_queue_worker) else {
+        return;
+    };
+    let state_clone = state.clone();
+    state
+        .task_supervisor
+        .spawn("ai-generation-queue", async move {
+            loop {
+                drain_queue(&state_clone).await;
+                drop(claim);
+                // An enqueue that saw the worker still running before it
+                // stopped would otherwise be stranded.
+                let pending = match active_project_path(&state_clone) {
+                    Ok(path) => with_queue(path, |conn| generation_queue_store::has_pending(conn))
+                        .await
+                        .unwrap_or(false),
+                    Err(_) => false,
+                };
+                if !pending {
+                    break;
+                }
+                let Some(next) = QueueWorkerClaim::acquire(&state_clone.generation_queue_worker)
+                else {
+                    break;
+                };
+                claim = next;
+            }
+        });
+}
+
+async fn drain_queue(state: &AppState) {
+    loop {
+        let Ok(path) = active_project_path(state) else {
+            return;
+        };
+        let next = match with_queue(path.clone(), |conn| {
+            generation_queue_store::claim_next(conn)
+        })
+        .await
+        {
+            Ok(Some(node_id)) => node_id,
+            Ok(None) => return,
+            Err(error) => {
+                tracing::error!("generation queue claim failed: {}", error.message());
+                return;
+            }
+        };
+        let outcome = generate_queued_node(state.clone(), next.0).await;
+        let error = outcome.err();
+        let finished_error = error.clone();
+        match with_queue(path, move |conn| {
+            generation_queue_store::finish(conn, next, finished_error.as_deref())
+        })
+        .await
+        {
+            Ok(()) => {
+                let _ = state
+                    .events_tx
+                    .send(ServerEvent::GenerationQueueItemFinished {
+                        node_id: next.0,
+                        error,
+                    });
+            }
+            Err(finish_error) => {
+                tracing::error!(
+                    "generation queue update failed for node {}: {}",
+                    next.0,
+                    finish_error.message()
+                );
+            }
+        }
+    }
+}
+
+async fn with_queue<T: Send + 'static>(
+    path: PathBuf,
+    operation: impl FnOnce(
+        &mut rusqlite::Connection,
+    ) -> Result<T, crate::history_store::HistoryStoreError>
+    + Send
+    + 'static,
+) -> Result<T, BackendError> {
+    tokio::task::spawn_blocking(move || {
+        let mut conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|error| BackendError::internal(error.to_string()))?;
+        operation(&mut conn).map_err(map_history_error)
+    })
+    .await
+    .map_err(|error| BackendError::internal(format!("generation queue task failed: {error}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eidetic_core::Template;
+    use eidetic_core::timeline::node::StoryLevel;
+
+    #[tokio::test]
+    async fn queue_rejects_unknown_nodes_and_cancels_only_pending_items() {
+        let path =
+            std::env::temp_dir().join(format!("eidetic-generation-queue-{}.db", Uuid::new_v4()));
+        let state = AppState::new().await;
+        *state.project.lock() = Some(Template::MultiCam.build_project("Queue Test"));
+        *state.project_path.lock() = Some(path.clone());
+        state.trigger_save();
+        state.force_save().await;
+
+        let error = enqueue_generation(
+            &state,
+            AiQueueEnqueueRequest {
+                node_ids: vec![Uuid::new_v4()],
+            },
+        )
+        .await
+        .expect_err("unknown node");
+        assert!(matches!(error, BackendError::NotFound(_)));
+
+        let error = cancel_queued_generation(
+            &state,
+            AiQueueCancelRequest {
+                node_id: Uuid::new_v4(),
+            },
+        )
+        .await
+        .expect_err("not queued");
+        assert!(matches!(error, BackendError::NotFound(_)));
+        assert!(generation_queue(&state).await.unwrap().is_empty());
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn locked_nodes_are_marked_failed_instead_of_done() {
+        let path = std::env::temp_dir().join(format!(
+            "eidetic-generation-queue-locked-{}.db",
+            Uuid::new_v4()
+        ));
+        let state = AppState::new().await;
+        let mut project = Template::MultiCam.build_project("Queue Test");
+        let node_id = project.timeline.nodes_at_level(StoryLevel::Scene)[0].id;
+        project.timeline.node_mut(node_id).unwrap().locked = true;
+        *state.project.lock() = Some(project);
+        *state.project_path.lock() = Some(path.clone());
+        state.trigger_save();
+        state.force_save().await;
+
+        let mut events = state.events_tx.subscribe();
+        enqueue_generation(
+            &state,
+            AiQueueEnqueueRequest {
+                node_ids: vec![node_id.0],
+            },
+        )
+        .await
+        .expect("enqueue locked node");
+        let error = loop {
+            match events.recv().await.expect("queue worker event") {
+                ServerEvent::GenerationQueueItemFinished {
+                    node_id: finished,
+                    error,
+                } if finished == node_id.0 => break error,
+                _ => {}
+            }
+        };
+
+        assert!(error.unwrap().contains("node is locked"));
+        let queue = generation_queue(&state).await.unwrap();
+        assert_eq!(queue[0].status, GenerationQueueStatus::Failed);
+        assert!(
+            queue[0]
+                .error
+                .as_deref()
+                .unwrap()
+                .contains("node is locked")
+        );
+        state.task_supervisor.shutdown_all().await;
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn aborted_queue_worker_releases_its_claim() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let claim = QueueWorkerClaim::acquire(&flag).expect("claim");
+        assert!(QueueWorkerClaim::acquire(&flag).is_none());
+
+        let worker = tokio::spawn(async move {
+            let _claim = claim;
+            std::future::pending::<()>().await;
+        });
+        worker.abort();
+        let _ = worker.await;
+
+        assert!(!flag.load(Ordering::Acquire));
+        assert!(QueueWorkerClaim::acquire(&flag).is_some());
+    }
+}
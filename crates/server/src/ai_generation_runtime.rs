@@ -1,23 +1,45 @@
+use std::collections::HashSet;
 use std::path::PathBuf;
 
-use eidetic_core::ai::backend::{GenerateRequest, RagChunk};
+use eidetic_core::ai::backend::{FinishReason, GenerateChunk, GenerateRequest, RagChunk};
+use eidetic_core::ai::prompt::ensure_prompt_fits;
 use eidetic_core::contracts::{
-    CommandEnvelope, CommandId, ScriptBlockId, ScriptBlockKind, ScriptDocumentId, ScriptSegmentId,
-    ScriptSegmentStatus, ScriptSpanProvenance, SetScriptBlockCommand,
+    BibleGraphNodeId, CommandEnvelope, CommandId, FieldValue, ScriptBlockId, ScriptBlockKind,
+    ScriptDocumentId, ScriptSegmentId, ScriptSegmentStatus, ScriptSpanProvenance,
+    SetScriptBlockCommand,
 };
+use eidetic_core::reference::{
+    LexicalIndex, ReferenceChunk, ReferenceDocument, ReferenceId, RetrievalMode, chunk_document,
+};
+use eidetic_core::script::merge::splice_missing_anchors;
+use eidetic_core::story::arc::ArcId;
 use eidetic_core::timeline::node::{ContentStatus, NodeId};
 use futures::StreamExt;
 use uuid::Uuid;
 
 use crate::ai_backends::Backend;
 use crate::embeddings::EmbeddingClient;
-use crate::prompt_format::build_chat_prompt;
-use crate::script_document_command;
-use crate::state::{AppState, ServerEvent};
-use crate::timeline_node_store;
+use crate::generation_log_store::{self, NewGenerationLogEntry};
+use crate::prompt_format::{
+    ChatPrompt, EmptyOutput, apply_sibling_limits, build_chat_prompt, build_continuation_prompt,
+    build_continue_content_prompt, clean_prose_output, estimate_tokens, strip_code_fence,
+    trim_continuation_overlap, trim_request_to_budget,
+};
+use crate::state::{AiConfig, AppState, ServerEvent, constants};
+use crate::ydoc::{ContentField, DocCommand};
+use crate::{script_document_command, script_store, timeline_node_store};
 
 use crate::ai_service::active_sqlite_project;
 
+/// Most times one generation is re-prompted after stopping at the token limit.
+const MAX_AUTO_CONTINUATIONS: usize = 2;
+
+/// Most times a generation is re-run after leaving no text to save.
+const MAX_EMPTY_OUTPUT_RETRIES: usize = 1;
+
+/// Y.Doc author recorded on text appended by a requested continuation.
+const CONTINUATION_AUTHOR: &str = "ai:continue";
+
 pub(crate) async fn mark_node_generating(
     state: &AppState,
     project_path: PathBuf,
@@ -33,7 +55,9 @@ pub(crate) async fn mark_node_generating(
         let mut project_guard = state.project.lock();
         if let Some(project) = project_guard.as_mut()
             && let Ok(node) = project.timeline.node_mut(node_id)
+            && let Err(error) = node.content.transition_to(ContentStatus::Generating)
         {
+            tracing::warn!("Generation started for node {node_uuid} from an odd status: {error}");
             node.content.status = ContentStatus::Generating;
         }
     }
@@ -42,24 +66,224 @@ pub(crate) async fn mark_node_generating(
         .send(ServerEvent::NodeUpdated { node_id: node_uuid });
 }
 
+/// Generate and save a node's content. Returns whether new text was saved.
+#[tracing::instrument(name = "generate", skip_all, fields(node_id = %node_uuid, model))]
 pub(crate) async fn run_generation(
     state: AppState,
     project_path: PathBuf,
     node_uuid: Uuid,
     mut request: GenerateRequest,
-) {
+) -> bool {
     let node_id = NodeId(node_uuid);
-    let config = state.ai_config.lock().clone();
+    let config = state.active_ai_config().for_request(&request);
+    tracing::Span::current().record("model", config.model.as_str());
     let backend = Backend::from_config(&config);
+    // The request's notes have placeholders expanded; staleness compares the
+    // notes as written.
+    let generation_notes = current_node_notes(&state, node_id);
 
-    attach_rag_context(&state, &config, &mut request).await;
-    let prompt = build_chat_prompt(&request);
+    let (prompt, retrieval) =
+        prepare_generation_prompt(&state, &config, node_uuid, &mut request).await;
+    announce_generation_prompt(&state, &config, node_uuid, &prompt);
+    if let Err(error) = ensure_prompt_fits(estimate_tokens(&prompt), config.context_limit) {
+        handle_generation_failure(&state, project_path, node_id, node_uuid, error.to_string())
+            .await;
+        return false;
+    }
 
-    let _ = state.events_tx.send(ServerEvent::GenerationContext {
+    let mut retries = 0;
+    let (streamed, text) = loop {
+        let streamed =
+            match stream_with_continuations(&state, &backend, &config, &prompt, node_uuid).await {
+                Ok(streamed) => streamed,
+                Err(error) => {
+                    handle_generation_failure(&state, project_path, node_id, node_uuid, error)
+                        .await;
+                    return false;
+                }
+            };
+        record_generation_log(project_path.clone(), node_id, &config, &prompt, &streamed).await;
+        match EmptyOutput::detect(&streamed.text) {
+            None => {
+                let text = clean_prose_output(&streamed.text);
+                break (streamed, text);
+            }
+            Some(empty) if retries < MAX_EMPTY_OUTPUT_RETRIES => {
+                retries += 1;
+                tracing::warn!(
+                    "Generation for node {node_uuid} left no text ({}); retrying",
+                    empty.reason()
+                );
+            }
+            Some(empty) => {
+                handle_empty_generation(&state, project_path, node_id, node_uuid, empty).await;
+                return false;
+            }
+        }
+    };
+
+    let text = enforce_anchors(&state, node_uuid, text, &request.user_written_anchors);
+    report_over_length(&state, &config, node_uuid, &request, &text);
+    if let Some(notes) = generation_notes {
+        record_generation_notes(&state, project_path.clone(), node_id, notes).await;
+    }
+    persist_successful_generation(
+        state,
+        project_path,
+        node_id,
+        node_uuid,
+        text,
+        streamed.finish_reason,
+        retrieval,
+    )
+    .await;
+    true
+}
+
+/// Stream one generation, re-prompting up to [`MAX_AUTO_CONTINUATIONS`]
+/// times while it stops at the token limit and auto-continue is on.
+async fn stream_with_continuations(
+    state: &AppState,
+    backend: &Backend,
+    config: &AiConfig,
+    prompt: &ChatPrompt,
+    node_uuid: Uuid,
+) -> Result<StreamedText, String> {
+    let stream = backend
+        .generate(prompt, config)
+        .await
+        .map_err(|error| error.to_string())?;
+    let mut streamed = stream_generated_text(state, node_uuid, stream, 0).await;
+    let mut continuations = 0;
+    while config.auto_continue_truncated
+        && continuations < MAX_AUTO_CONTINUATIONS
+        && !streamed.text.is_empty()
+        && streamed
+            .finish_reason
+            .as_ref()
+            .is_some_and(FinishReason::is_truncated)
+    {
+        continuations += 1;
+        tracing::info!("Continuing truncated generation for node {node_uuid} ({continuations})");
+        let continuation = build_continuation_prompt(prompt, &streamed.text);
+        let stream = match backend.generate(&continuation, config).await {
+            Ok(stream) => stream,
+            Err(error) => {
+                tracing::warn!("Continuation failed for node {node_uuid}: {error}");
+                break;
+            }
+        };
+        let more = stream_generated_text(state, node_uuid, stream, streamed.tokens_generated).await;
+        streamed.text.push_str(&more.text);
+        streamed.tokens_generated = more.tokens_generated;
+        streamed.finish_reason = more.finish_reason;
+    }
+    Ok(streamed)
+}
+
+fn current_node_notes(state: &AppState, node_id: NodeId) -> Option<String> {
+    let project = state.project.lock();
+    let node = project.as_ref()?.timeline.node(node_id).ok()?;
+    Some(node.content.notes.clone())
+}
+
+/// Remember the notes a generation worked from, so later note edits mark the
+/// content stale.
+async fn record_generation_notes(
+    state: &AppState,
+    project_path: PathBuf,
+    node_id: NodeId,
+    notes: String,
+) {
+    if let Some(project) = state.project.lock().as_mut()
+        && let Ok(node) = project.timeline.node_mut(node_id)
+    {
+        node.content.record_generation_notes(&notes);
+    }
+    let result = tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&project_path)
+            .map_err(|error| error.to_string())?;
+        timeline_node_store::update_node_generation_notes(&conn, node_id, &notes)
+            .map_err(|error| error.to_string())
+    })
+    .await
+    .map_err(|error| error.to_string())
+    .and_then(|result| result);
+    if let Err(error) = result {
+        tracing::warn!(
+            "Failed to record generation notes for node {}: {error}",
+            node_id.0
+        );
+    }
+}
+
+/// Splice back any user-written anchor the model left out or paraphrased,
+/// reporting each one as an [`ServerEvent::AnchorViolation`].
+fn enforce_anchors(state: &AppState, node_uuid: Uuid, text: String, anchors: &[String]) -> String {
+    let (spliced, missing) = splice_missing_anchors(&text, anchors);
+    for anchor in missing {
+        tracing::warn!("Generation for node {node_uuid} dropped an anchor; splicing it back");
+        let _ = state.events_tx.send(ServerEvent::AnchorViolation {
+            node_id: node_uuid,
+            anchor,
+        });
+    }
+    spliced
+}
+
+/// Report output that runs past its level's target by more than
+/// [`constants::OVER_LENGTH_FACTOR`] as an [`ServerEvent::OutputOverLength`].
+/// The text is still saved.
+fn report_over_length(
+    state: &AppState,
+    config: &AiConfig,
+    node_uuid: Uuid,
+    request: &GenerateRequest,
+    text: &str,
+) {
+    let Some(target_words) = config.target_words(request) else {
+        return;
+    };
+    let words = text.split_whitespace().count();
+    if words as f64 <= f64::from(target_words) * constants::OVER_LENGTH_FACTOR {
+        return;
+    }
+    tracing::warn!(
+        "Generation for node {node_uuid} ran to {words} words against a target of {target_words}"
+    );
+    let _ = state.events_tx.send(ServerEvent::OutputOverLength {
         node_id: node_uuid,
-        system_prompt: prompt.system.clone(),
-        user_prompt: prompt.user.clone(),
+        level: request.target_node.level,
+        words,
+        target_words,
     });
+}
+
+/// Resume a node's existing content where it stops, appending the new text
+/// rather than replacing it. Any lines the model repeats from the end of the
+/// existing content are trimmed first.
+#[tracing::instrument(name = "continue", skip_all, fields(node_id = %node_uuid, model))]
+pub(crate) async fn run_continuation(
+    state: AppState,
+    project_path: PathBuf,
+    node_uuid: Uuid,
+    mut request: GenerateRequest,
+    existing: String,
+) {
+    let node_id = NodeId(node_uuid);
+    let config = state.active_ai_config();
+    tracing::Span::current().record("model", config.model.as_str());
+    let backend = Backend::from_config(&config);
+
+    let (prompt, retrieval) =
+        prepare_generation_prompt(&state, &config, node_uuid, &mut request).await;
+    let prompt = build_continue_content_prompt(&prompt, &existing);
+    announce_generation_prompt(&state, &config, node_uuid, &prompt);
+    if let Err(error) = ensure_prompt_fits(estimate_tokens(&prompt), config.context_limit) {
+        handle_generation_failure(&state, project_path, node_id, node_uuid, error.to_string())
+            .await;
+        return;
+    }
 
     let stream = match backend.generate(&prompt, &config).await {
         Ok(stream) => stream,
@@ -69,52 +293,219 @@ pub(crate) async fn run_generation(
             return;
         }
     };
-
-    let full_text = stream_generated_text(&state, node_uuid, stream).await;
-    if full_text.is_empty() {
-        handle_empty_generation(&state, project_path, node_id, node_uuid).await;
+    let streamed = stream_generated_text(&state, node_uuid, stream, 0).await;
+    record_generation_log(project_path.clone(), node_id, &config, &prompt, &streamed).await;
+    if let Some(empty) = EmptyOutput::detect(&streamed.text) {
+        handle_empty_generation(&state, project_path, node_id, node_uuid, empty).await;
+        return;
+    }
+    let appended = trim_continuation_overlap(&existing, strip_code_fence(&streamed.text));
+    if appended.trim().is_empty() {
+        handle_empty_generation(
+            &state,
+            project_path,
+            node_id,
+            node_uuid,
+            EmptyOutput::RepeatedExisting,
+        )
+        .await;
         return;
     }
 
-    persist_successful_generation(state, project_path, node_id, node_uuid, full_text).await;
+    let _ = state.doc_tx.try_send(DocCommand::AppendNodeContent {
+        node_id,
+        field: ContentField::Content,
+        text: appended.to_owned(),
+        author: CONTINUATION_AUTHOR.into(),
+    });
+    let full_text = format!("{existing}{appended}");
+    persist_successful_generation(
+        state,
+        project_path,
+        node_id,
+        node_uuid,
+        full_text,
+        streamed.finish_reason,
+        retrieval,
+    )
+    .await;
 }
 
-async fn attach_rag_context(
+/// Attach retrieval context, fit the request to the prompt budget, and build
+/// the chat prompt for one node's generation.
+async fn prepare_generation_prompt(
     state: &AppState,
     config: &crate::state::AiConfig,
+    node_uuid: Uuid,
     request: &mut GenerateRequest,
+) -> (ChatPrompt, RetrievalOutcome) {
+    let mode = attach_rag_context(state, config, node_uuid, request).await;
+    let retrieval = RetrievalOutcome {
+        mode,
+        used: !request.rag_context.is_empty(),
+    };
+    apply_sibling_limits(request, config);
+    let trimmed = trim_request_to_budget(request, config.prompt_budget());
+    if !trimmed.is_empty() {
+        tracing::info!("Trimmed prompt context for node {node_uuid}: {trimmed:?}");
+    }
+    (build_chat_prompt(request), retrieval)
+}
+
+fn announce_generation_prompt(
+    state: &AppState,
+    config: &crate::state::AiConfig,
+    node_uuid: Uuid,
+    prompt: &ChatPrompt,
 ) {
+    let prompt_budget = config.prompt_budget();
+    let estimated_tokens = estimate_tokens(prompt);
+    if estimated_tokens > prompt_budget {
+        tracing::warn!(
+            "Prompt for node {node_uuid} is ~{estimated_tokens} tokens, over the {prompt_budget}-token budget"
+        );
+    }
+
+    let _ = state.events_tx.send(ServerEvent::GenerationContext {
+        node_id: node_uuid,
+        system_prompt: prompt.system.clone(),
+        user_prompt: prompt.user.clone(),
+        estimated_tokens,
+    });
+}
+
+/// How a generation's reference context was gathered.
+#[derive(Debug, Clone, Copy)]
+struct RetrievalOutcome {
+    mode: RetrievalMode,
+    /// Whether any reference chunk made it into the request.
+    used: bool,
+}
+
+/// Retrieve reference chunks for the request from the documents scoped to
+/// the target node's arcs or context entities, plus global ones. When
+/// embeddings can't be used the reason is broadcast and BM25 over those
+/// documents' chunks stands in.
+async fn attach_rag_context(
+    state: &AppState,
+    config: &crate::state::AiConfig,
+    node_uuid: Uuid,
+    request: &mut GenerateRequest,
+) -> RetrievalMode {
+    let arc_ids: Vec<ArcId> = request.tagged_arcs.iter().map(|arc| arc.id).collect();
+    let entity_ids: Vec<BibleGraphNodeId> = request
+        .bible_context
+        .iter()
+        .flat_map(|context| &context.payload.nodes)
+        .map(|node| node.node_id.clone())
+        .collect();
+    let references: Vec<ReferenceDocument> = state
+        .project
+        .lock()
+        .as_ref()
+        .map(|project| {
+            project
+                .references
+                .iter()
+                .filter(|doc| doc.scope.includes(&arc_ids, &entity_ids))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    if references.is_empty() {
+        return RetrievalMode::None;
+    }
+    let in_scope: HashSet<ReferenceId> = references.iter().map(|doc| doc.id).collect();
+    let query = request.target_node.content.notes.clone();
+    match embedded_rag_context(state, config, &query, &in_scope).await {
+        Ok(results) => {
+            request.rag_context = results;
+            RetrievalMode::Embedding
+        }
+        Err(reason) => {
+            tracing::warn!("Reference embeddings unavailable for node {node_uuid}: {reason}");
+            let _ = state.events_tx.send(ServerEvent::RagUnavailable {
+                node_id: node_uuid,
+                reason,
+            });
+            let index = LexicalIndex::new(
+                references
+                    .iter()
+                    .flat_map(|doc| {
+                        chunk_document(
+                            doc,
+                            crate::state::constants::REFERENCE_CHUNK_SIZE,
+                            crate::state::constants::REFERENCE_CHUNK_OVERLAP,
+                        )
+                    })
+                    .collect(),
+            );
+            request.rag_context = index
+                .search(&query, crate::state::constants::RAG_TOP_K)
+                .into_iter()
+                .map(|(chunk, score)| rag_chunk(chunk, score))
+                .collect();
+            RetrievalMode::Lexical
+        }
+    }
+}
+
+async fn embedded_rag_context(
+    state: &AppState,
+    config: &crate::state::AiConfig,
+    query: &str,
+    in_scope: &HashSet<ReferenceId>,
+) -> Result<Vec<RagChunk>, String> {
     if state.vector_store.lock().is_empty() {
-        return;
+        return Err("reference material has not been embedded".to_string());
     }
-    let query = &request.target_node.content.notes;
-    let embed_client =
-        EmbeddingClient::new(&config.base_url, crate::state::constants::EMBEDDING_MODEL);
-    if let Ok(query_embedding) = embed_client.embed(query).await {
-        let store = state.vector_store.lock();
-        let results = store.search(&query_embedding, crate::state::constants::RAG_TOP_K);
-        request.rag_context = results
-            .into_iter()
-            .map(|(chunk, score)| RagChunk {
-                source: chunk.document_name.clone(),
-                content: chunk.content.clone(),
-                relevance_score: score,
-            })
-            .collect();
+    let embed_client = EmbeddingClient::new(&config.base_url, &config.embedding_model);
+    let query_embedding = embed_client
+        .embed(query)
+        .await
+        .map_err(|error| format!("embedding request failed: {error}"))?;
+    let store = state.vector_store.lock();
+    let results = store
+        .search_matching(
+            &query_embedding,
+            crate::state::constants::RAG_TOP_K,
+            |chunk| in_scope.contains(&chunk.document_id),
+        )
+        .map_err(|error| error.to_string())?;
+    Ok(results
+        .into_iter()
+        .map(|(chunk, score)| rag_chunk(chunk, score))
+        .collect())
+}
+
+fn rag_chunk(chunk: &ReferenceChunk, relevance_score: f32) -> RagChunk {
+    RagChunk {
+        source: chunk.document_name.clone(),
+        content: chunk.content.clone(),
+        relevance_score,
     }
 }
 
+struct StreamedText {
+    text: String,
+    tokens_generated: usize,
+    finish_reason: Option<FinishReason>,
+}
+
+/// Collect a generation stream, reporting progress with a token count that
+/// starts at `tokens_generated` so continuations keep counting.
 async fn stream_generated_text(
     state: &AppState,
     node_uuid: Uuid,
     mut stream: eidetic_core::ai::backend::GenerateStream,
-) -> String {
+    mut tokens_generated: usize,
+) -> StreamedText {
     let mut full_text = String::new();
-    let mut tokens_generated: usize = 0;
+    let mut finish_reason = None;
 
     while let Some(item) = stream.next().await {
         match item {
-            Ok(token) => {
+            Ok(GenerateChunk::Token(token)) => {
                 full_text.push_str(&token);
                 tokens_generated += 1;
                 let _ = state.events_tx.send(ServerEvent::GenerationProgress {
@@ -123,13 +514,57 @@ async fn stream_generated_text(
                     tokens_generated,
                 });
             }
+            Ok(GenerateChunk::Finished(reason)) => finish_reason = Some(reason),
             Err(e) => {
                 tracing::warn!("Stream error during generation for node {node_uuid}: {e}");
                 break;
             }
         }
     }
-    full_text
+    StreamedText {
+        text: full_text,
+        tokens_generated,
+        finish_reason,
+    }
+}
+
+/// Append the prompt and raw output of a finished stream to the node's
+/// generation log. Failures are logged and never fail the generation.
+async fn record_generation_log(
+    project_path: PathBuf,
+    node_id: NodeId,
+    config: &crate::state::AiConfig,
+    prompt: &ChatPrompt,
+    streamed: &StreamedText,
+) {
+    let timestamp_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default();
+    let entry = NewGenerationLogEntry {
+        node_id,
+        timestamp_ms,
+        model: config.model.clone(),
+        system_prompt: prompt.system.clone(),
+        user_prompt: prompt.user.clone(),
+        output: streamed.text.clone(),
+        prompt_tokens: estimate_tokens(prompt),
+        output_tokens: streamed.tokens_generated,
+    };
+    let result = tokio::task::spawn_blocking(move || {
+        let mut conn =
+            crate::sqlite::open_write_connection(&project_path).map_err(|e| e.to_string())?;
+        generation_log_store::record_generation(&mut conn, &entry).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|result| result);
+    if let Err(error) = result {
+        tracing::warn!(
+            "Failed to record generation log for node {}: {error}",
+            node_id.0
+        );
+    }
 }
 
 async fn handle_generation_failure(
@@ -140,19 +575,16 @@ async fn handle_generation_failure(
     error: String,
 ) {
     tracing::error!("AI generation failed for node {node_uuid}: {error}");
-    if let Err(status_error) =
-        persist_node_content_status(project_path, node_id, ContentStatus::NotesOnly).await
-    {
+    let status = settle_project_node_status(state, node_id);
+    if let Err(status_error) = persist_node_content_status(project_path, node_id, status).await {
         tracing::warn!(
             "Failed to persist generation error status for node {node_uuid}: {status_error}"
         );
     }
-    set_project_node_status(state, node_id, ContentStatus::NotesOnly);
     let _ = state.events_tx.send(ServerEvent::GenerationError {
         node_id: node_uuid,
         error,
     });
-    state.generating.lock().remove(&node_uuid);
 }
 
 async fn handle_empty_generation(
@@ -160,18 +592,16 @@ async fn handle_empty_generation(
     project_path: PathBuf,
     node_id: NodeId,
     node_uuid: Uuid,
+    empty: EmptyOutput,
 ) {
-    if let Err(error) =
-        persist_node_content_status(project_path, node_id, ContentStatus::NotesOnly).await
-    {
+    let status = settle_project_node_status(state, node_id);
+    if let Err(error) = persist_node_content_status(project_path, node_id, status).await {
         tracing::warn!("Failed to persist empty-generation status for node {node_uuid}: {error}");
     }
-    set_project_node_status(state, node_id, ContentStatus::NotesOnly);
     let _ = state.events_tx.send(ServerEvent::GenerationError {
         node_id: node_uuid,
-        error: "AI produced no output".into(),
+        error: empty.reason().into(),
     });
-    state.generating.lock().remove(&node_uuid);
 }
 
 async fn persist_successful_generation(
@@ -180,6 +610,8 @@ async fn persist_successful_generation(
     node_id: NodeId,
     node_uuid: Uuid,
     full_text: String,
+    finish_reason: Option<FinishReason>,
+    retrieval: RetrievalOutcome,
 ) {
     if let Err(error) =
         persist_node_content_status(project_path.clone(), node_id, ContentStatus::HasContent).await
@@ -198,19 +630,27 @@ async fn persist_successful_generation(
             node_id: node_uuid,
             error,
         });
-        state.generating.lock().remove(&node_uuid);
         return;
     }
-    let _ = state
-        .events_tx
-        .send(ServerEvent::GenerationComplete { node_id: node_uuid });
+    let truncated = finish_reason
+        .as_ref()
+        .is_some_and(FinishReason::is_truncated);
+    if truncated {
+        tracing::warn!("Generation for node {node_uuid} stopped at the token limit");
+    }
+    let _ = state.events_tx.send(ServerEvent::GenerationComplete {
+        node_id: node_uuid,
+        truncated,
+        reason: finish_reason.map(|reason| reason.as_str().to_owned()),
+        rag_used: retrieval.used,
+        retrieval_mode: retrieval.mode,
+    });
     let _ = state
         .events_tx
         .send(ServerEvent::NodeUpdated { node_id: node_uuid });
     let _ = state.events_tx.send(ServerEvent::ScriptChanged);
     state.trigger_save();
     generate_scene_recap(&state, node_uuid, &full_text).await;
-    state.generating.lock().remove(&node_uuid);
 }
 
 fn successful_generation_metadata(
@@ -224,7 +664,6 @@ fn successful_generation_metadata(
             node_id: node_uuid,
             error: "no project loaded".into(),
         });
-        state.generating.lock().remove(&node_uuid);
         return None;
     };
     let Ok(node) = project.timeline.node_mut(node_id) else {
@@ -232,10 +671,12 @@ fn successful_generation_metadata(
             node_id: node_uuid,
             error: "node not found".into(),
         });
-        state.generating.lock().remove(&node_uuid);
         return None;
     };
-    node.content.status = ContentStatus::HasContent;
+    if let Err(error) = node.content.transition_to(ContentStatus::HasContent) {
+        tracing::warn!("Generation finished for node {node_uuid} from an odd status: {error}");
+        node.content.status = ContentStatus::HasContent;
+    }
     Some(GeneratedScriptMetadata {
         project_name: project.name.clone(),
         start_ms: node.time_range.start_ms,
@@ -243,12 +684,19 @@ fn successful_generation_metadata(
     })
 }
 
-fn set_project_node_status(state: &AppState, node_id: NodeId, status: ContentStatus) {
+/// Drop a node out of `Generating` after a generation that produced nothing,
+/// returning the status derived from its remaining text.
+fn settle_project_node_status(state: &AppState, node_id: NodeId) -> ContentStatus {
     let mut project_guard = state.project.lock();
-    if let Some(project) = project_guard.as_mut()
-        && let Ok(node) = project.timeline.node_mut(node_id)
+    match project_guard
+        .as_mut()
+        .and_then(|project| project.timeline.node_mut(node_id).ok())
     {
-        node.content.status = status;
+        Some(node) => {
+            node.content.reconcile_status(false);
+            node.content.status
+        }
+        None => ContentStatus::NotesOnly,
     }
 }
 
@@ -278,6 +726,40 @@ async fn persist_generated_script_block(
     .map_err(|error| format!("script persistence task failed: {error}"))?
 }
 
+/// Load the text of a node's generated script block, if it has one.
+pub(crate) async fn load_generated_script_text(
+    project_path: PathBuf,
+    node_uuid: Uuid,
+) -> Result<Option<String>, String> {
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&project_path)
+            .map_err(|error| error.to_string())?;
+        script_store::create_schema(&conn).map_err(|error| error.to_string())?;
+        let document_id = main_script_document_id()?;
+        let block_id = generated_script_block_id(node_uuid)?;
+        let projection = script_store::load_document_projection(&conn, &document_id)
+            .map_err(|error| error.to_string())?;
+        Ok(projection
+            .as_ref()
+            .and_then(|projection| script_document_command::find_block_text(projection, &block_id))
+            .and_then(|value| match value {
+                FieldValue::Text(text) => Some(text),
+                _ => None,
+            }))
+    })
+    .await
+    .map_err(|error| format!("generated script load task failed: {error}"))?
+}
+
+fn main_script_document_id() -> Result<ScriptDocumentId, String> {
+    ScriptDocumentId::new("script.document.main").map_err(|error| error.to_string())
+}
+
+fn generated_script_block_id(node_uuid: Uuid) -> Result<ScriptBlockId, String> {
+    ScriptBlockId::new(format!("script.block.{node_uuid}.generated"))
+        .map_err(|error| error.to_string())
+}
+
 async fn persist_node_content_status(
     project_path: PathBuf,
     node_id: NodeId,
@@ -302,8 +784,7 @@ fn generated_script_block_command(
     Ok(CommandEnvelope {
         id: CommandId(command_id),
         payload: SetScriptBlockCommand {
-            document_id: ScriptDocumentId::new("script.document.main")
-                .map_err(|error| error.to_string())?,
+            document_id: main_script_document_id()?,
             document_title: metadata.project_name,
             document_sort_order: 0,
             segment_id: ScriptSegmentId::new(format!("script.segment.{node_uuid}"))
@@ -313,8 +794,7 @@ fn generated_script_block_command(
             segment_end_ms: metadata.end_ms,
             segment_status: ScriptSegmentStatus::Current,
             segment_sort_order: 0,
-            block_id: ScriptBlockId::new(format!("script.block.{node_uuid}.generated"))
-                .map_err(|error| error.to_string())?,
+            block_id: generated_script_block_id(node_uuid)?,
             block_kind: ScriptBlockKind::Action,
             text: full_text,
             span_provenance: ScriptSpanProvenance::AiGenerated,
@@ -347,7 +827,7 @@ async fn generate_scene_recap(state: &AppState, node_uuid: Uuid, script: &str) {
         (project_path, preceding_recap)
     };
 
-    let config = state.ai_config.lock().clone();
+    let config = state.active_ai_config();
     let backend = Backend::from_config(&config);
     let mut recap_config = config.clone();
     recap_config.max_tokens = 512;
@@ -438,4 +918,149 @@ mod tests {
         assert_eq!(command.payload.block_kind, ScriptBlockKind::Action);
         assert_eq!(command.payload.span_provenance, AiGenerated);
     }
+
+    #[tokio::test]
+    async fn unembedded_references_fall_back_to_lexical_search_and_are_reported() {
+        use eidetic_core::reference::{ReferenceDocument, ReferenceType};
+        use eidetic_core::timeline::node::StoryLevel;
+
+        let state = AppState::new().await;
+        let mut events = state.events_tx.subscribe();
+        let mut project = eidetic_core::Template::MultiCam.build_project("Rag");
+        let scene = project.timeline.nodes_at_level(StoryLevel::Scene)[0].id;
+        project.timeline.node_mut(scene).unwrap().content.notes =
+            "The keeper climbs the lighthouse.".to_string();
+        project.references.push(ReferenceDocument::new(
+            "World",
+            "The lighthouse keeper never sleeps.\n\nMarkets open at noon.",
+            ReferenceType::WorldBuilding,
+        ));
+        let mut request =
+            eidetic_core::ai::prompt::build_generate_request(&project, scene).unwrap();
+        *state.project.lock() = Some(project);
+        let config = state.ai_config.lock().clone();
+
+        let mode = attach_rag_context(&state, &config, scene.0, &mut request).await;
+
+        assert_eq!(mode, RetrievalMode::Lexical);
+        assert_eq!(request.rag_context.len(), 1);
+        assert_eq!(request.rag_context[0].source, "World");
+        match events.try_recv().unwrap() {
+            ServerEvent::RagUnavailable { node_id, reason } => {
+                assert_eq!(node_id, scene.0);
+                assert!(reason.contains("not been embedded"));
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn references_scoped_to_other_arcs_are_not_retrieved() {
+        use eidetic_core::reference::{ReferenceDocument, ReferenceScope, ReferenceType};
+        use eidetic_core::timeline::node::StoryLevel;
+
+        let state = AppState::new().await;
+        let mut project = eidetic_core::Template::MultiCam.build_project("Scoped Rag");
+        let scene = project.timeline.nodes_at_level(StoryLevel::Scene)[0].id;
+        project.timeline.node_mut(scene).unwrap().content.notes =
+            "The keeper climbs the lighthouse.".to_string();
+        let scene_arc = project.arcs[0].id;
+        project.timeline.tag_node(scene, scene_arc);
+        let scoped = |name: &str, arc_id: ArcId| {
+            let mut doc = ReferenceDocument::new(
+                name,
+                format!("{name}: the lighthouse keeper hides a letter."),
+                ReferenceType::CharacterBible,
+            );
+            doc.scope = ReferenceScope {
+                arc_ids: vec![arc_id],
+                entity_ids: Vec::new(),
+            };
+            doc
+        };
+        project.references = vec![
+            scoped("Villain Backstory", ArcId::new()),
+            scoped("Keeper Notes", scene_arc),
+        ];
+        let mut request =
+            eidetic_core::ai::prompt::build_generate_request(&project, scene).unwrap();
+        *state.project.lock() = Some(project);
+        let config = state.ai_config.lock().clone();
+
+        let mode = attach_rag_context(&state, &config, scene.0, &mut request).await;
+
+        assert_eq!(mode, RetrievalMode::Lexical);
+        let sources: Vec<&str> = request
+            .rag_context
+            .iter()
+            .map(|chunk| chunk.source.as_str())
+            .collect();
+        assert_eq!(sources, vec!["Keeper Notes"]);
+    }
+
+    #[tokio::test]
+    async fn outlines_far_past_their_target_are_reported() {
+        use eidetic_core::timeline::node::StoryLevel;
+
+        let state = AppState::new().await;
+        let mut events = state.events_tx.subscribe();
+        let project = eidetic_core::Template::MultiCam.build_project("Over Length Test");
+        let scene = project.timeline.nodes_at_level(StoryLevel::Scene)[0].id;
+        let request = eidetic_core::ai::prompt::build_generate_request(&project, scene).unwrap();
+        let config = AiConfig::default();
+        let target = constants::DEFAULT_SCENE_OUTLINE_WORDS as usize;
+
+        report_over_length(&state, &config, scene.0, &request, &"word ".repeat(target));
+        assert!(events.try_recv().is_err());
+
+        report_over_length(
+            &state,
+            &config,
+            scene.0,
+            &request,
+            &"word ".repeat(target * 2),
+        );
+        match events.try_recv().unwrap() {
+            ServerEvent::OutputOverLength {
+                node_id,
+                level,
+                words,
+                target_words,
+            } => {
+                assert_eq!(node_id, scene.0);
+                assert_eq!(level, StoryLevel::Scene);
+                assert_eq!(words, target * 2);
+                assert_eq!(target_words as usize, target);
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn dropped_anchors_are_spliced_back_and_reported() {
+        let state = AppState::new().await;
+        let mut events = state.events_tx.subscribe();
+        let node_uuid = Uuid::new_v4();
+        let anchors = vec!["Ada drops the locket.".to_string()];
+
+        let text = enforce_anchors(
+            &state,
+            node_uuid,
+            "Ada lets the locket fall.\n\nShe leaves.".to_string(),
+            &anchors,
+        );
+
+        assert_eq!(
+            text,
+            "Ada lets the locket fall.\n\nAda drops the locket.\n\nShe leaves."
+        );
+        match events.try_recv().unwrap() {
+            ServerEvent::AnchorViolation { node_id, anchor } => {
+                assert_eq!(node_id, node_uuid);
+                assert_eq!(anchor, anchors[0]);
+            }
+            other => panic!("unexpected event {other:?}"),
+        }
+        assert!(events.try_recv().is_err());
+    }
 }
@@ -1,16 +1,33 @@
-use eidetic_core::ai::prompt::build_generate_request;
-use eidetic_core::timeline::node::NodeId;
+use eidetic_core::Error;
+use eidetic_core::Project;
+use eidetic_core::ai::prompt::{build_generate_request, ensure_continuable, ensure_generatable};
+use eidetic_core::contracts::BibleGraphNodeId;
+use eidetic_core::timeline::node::{ContentStatus, NodeId, StoryLevel, StoryNode};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::ai_generation_runtime::{mark_node_generating, run_generation};
-use crate::ai_service::{active_sqlite_project, attach_ai_generation_context};
+use crate::ai_generation_runtime::{
+    load_generated_script_text, mark_node_generating, run_continuation, run_generation,
+};
+use crate::ai_service::{
+    active_sqlite_project, apply_ai_bible_context_overrides, attach_ai_generation_context,
+};
 use crate::backend_error::BackendError;
+use crate::command_service_support::{active_project_path, map_history_error};
+use crate::generation_log_store;
 use crate::state::{AppState, ServerEvent};
 
+pub use crate::generation_log_store::GenerationLogEntry;
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct AiGenerateRequest {
     pub node_id: Uuid,
+    /// Bible entities to add to the context in full, even if unreferenced.
+    #[serde(default)]
+    pub include_entity_ids: Vec<BibleGraphNodeId>,
+    /// Bible entities to leave out, even if referenced.
+    #[serde(default)]
+    pub exclude_entity_ids: Vec<BibleGraphNodeId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -19,11 +36,27 @@ pub struct AiGenerateResponse {
     pub node_id: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct AiContinueRequest {
+    pub node_id: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AiGenerationLogRequest {
+    pub node_id: Uuid,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct AiGenerateBatchRequest {
     pub parent_node_id: Uuid,
 }
 
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AiReadyNodesRequest {
+    #[serde(default)]
+    pub level: Option<StoryLevel>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct AiGenerateBatchResponse {
     pub status: String,
@@ -38,33 +71,32 @@ pub async fn start_generation(
     let node_id = NodeId(body.node_id);
     let (mut request, project_path) = {
         let (project, project_path) = active_sqlite_project(state).await?;
-        let node = project
-            .timeline
-            .node(node_id)
-            .map_err(|_| BackendError::not_found(format!("node not found: {}", body.node_id)))?;
-
-        if node.locked {
-            return Err(BackendError::bad_request("node is locked"));
-        }
-        if node.content.notes.trim().is_empty() {
-            return Err(BackendError::bad_request("node has no notes"));
-        }
+        ensure_generatable(project.timeline.node(node_id)?)?;
         if state.generating.lock().contains(&body.node_id) {
-            return Err(BackendError::conflict("generation already in progress"));
+            return Err(Error::GenerationInProgress(body.node_id).into());
         }
 
-        let request = build_generate_request(&project, node_id)
-            .map_err(|error| BackendError::bad_request(error.to_string()))?;
+        let request = build_generate_request(&project, node_id)?;
         (request, project_path)
     };
     attach_ai_generation_context(&mut request, project_path.clone(), node_id).await?;
+    apply_ai_bible_context_overrides(
+        &mut request,
+        project_path.clone(),
+        body.include_entity_ids,
+        body.exclude_entity_ids,
+    )
+    .await?;
 
-    state.generating.lock().insert(body.node_id);
+    let Some(generating) = state.begin_generating(body.node_id) else {
+        return Err(Error::GenerationInProgress(body.node_id).into());
+    };
     mark_node_generating(state, project_path.clone(), node_id, body.node_id).await;
 
     let state_clone = state.clone();
     let node_uuid = body.node_id;
     state.task_supervisor.spawn("ai-generation", async move {
+        let _generating = generating;
         run_generation(state_clone, project_path, node_uuid, request).await;
     });
 
@@ -74,34 +106,133 @@ pub async fn start_generation(
     })
 }
 
+/// Continue a node's existing content from where it stops, for output that
+/// was cut off or a beat too long for one generation.
+pub async fn start_continuation(
+    state: &AppState,
+    body: AiContinueRequest,
+) -> Result<AiGenerateResponse, BackendError> {
+    let node_id = NodeId(body.node_id);
+    let (mut request, project_path, node) = {
+        let (project, project_path) = active_sqlite_project(state).await?;
+        let node = project.timeline.node(node_id)?;
+        if node.locked {
+            return Err(Error::NodeLocked(body.node_id).into());
+        }
+        if state.generating.lock().contains(&body.node_id) {
+            return Err(Error::GenerationInProgress(body.node_id).into());
+        }
+
+        let request = build_generate_request(&project, node_id)?;
+        (request, project_path, node.clone())
+    };
+    let existing = load_generated_script_text(project_path.clone(), body.node_id)
+        .await
+        .map_err(BackendError::Internal)?
+        .unwrap_or_else(|| node.content.content.clone());
+    ensure_continuable(&node, &existing)?;
+    attach_ai_generation_context(&mut request, project_path.clone(), node_id).await?;
+
+    let Some(generating) = state.begin_generating(body.node_id) else {
+        return Err(Error::GenerationInProgress(body.node_id).into());
+    };
+    mark_node_generating(state, project_path.clone(), node_id, body.node_id).await;
+
+    let state_clone = state.clone();
+    let node_uuid = body.node_id;
+    state.task_supervisor.spawn("ai-continuation", async move {
+        let _generating = generating;
+        run_continuation(state_clone, project_path, node_uuid, request, existing).await;
+    });
+
+    Ok(AiGenerateResponse {
+        status: "started".to_string(),
+        node_id: body.node_id.to_string(),
+    })
+}
+
+/// The prompts and outputs of a node's recent generations, newest first.
+pub async fn generation_log(
+    state: &AppState,
+    body: AiGenerationLogRequest,
+) -> Result<Vec<GenerationLogEntry>, BackendError> {
+    let path = active_project_path(state)?;
+    let node_id = NodeId(body.node_id);
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|error| BackendError::internal(error.to_string()))?;
+        generation_log_store::load_generation_log(&conn, node_id).map_err(map_history_error)
+    })
+    .await
+    .map_err(|error| BackendError::internal(format!("generation log task failed: {error}")))?
+}
+
+/// Nodes ready to generate next: notes but no content yet, unlocked, not
+/// generating, and either top-level or under a parent that has content.
+/// Sorted by start time, optionally limited to one level.
+pub fn ready_nodes(
+    state: &AppState,
+    body: AiReadyNodesRequest,
+) -> Result<Vec<StoryNode>, BackendError> {
+    let project = state.project.lock();
+    let project = project.as_ref().ok_or_else(BackendError::no_project)?;
+    let generating = state.generating.lock();
+    Ok(ready_project_nodes(project, body.level, |id| {
+        generating.contains(&id.0)
+    }))
+}
+
+fn ready_project_nodes(
+    project: &Project,
+    level: Option<StoryLevel>,
+    is_generating: impl Fn(NodeId) -> bool,
+) -> Vec<StoryNode> {
+    let timeline = &project.timeline;
+    let parent_ready = |node: &StoryNode| {
+        node.parent_id.is_none_or(|parent_id| {
+            timeline
+                .node(parent_id)
+                .is_ok_and(|parent| parent.content.status == ContentStatus::HasContent)
+        })
+    };
+    let mut nodes: Vec<StoryNode> = timeline
+        .nodes
+        .iter()
+        .filter(|node| level.is_none_or(|level| node.level == level))
+        .filter(|node| node.content.status == ContentStatus::NotesOnly)
+        .filter(|node| !node.locked && !is_generating(node.id))
+        .filter(|node| parent_ready(node))
+        .cloned()
+        .collect();
+    nodes.sort_by_key(|node| node.time_range.start_ms);
+    nodes
+}
+
 pub async fn start_generation_batch(
     state: &AppState,
     body: AiGenerateBatchRequest,
 ) -> Result<AiGenerateBatchResponse, BackendError> {
     let parent_id = NodeId(body.parent_node_id);
-    let child_ids: Vec<Uuid> = {
+    let node_ids: Vec<NodeId> = {
         let (project, _) = active_sqlite_project(state).await?;
-        project
+        let child_ids: Vec<NodeId> = project
             .timeline
             .children_of(parent_id)
             .iter()
-            .map(|node| node.id.0)
-            .collect()
+            .map(|node| node.id)
+            .collect();
+        project
+            .timeline
+            .generation_order(&child_ids)
+            .map_err(|error| BackendError::bad_request(error.to_string()))?
     };
 
-    if child_ids.is_empty() {
+    if node_ids.is_empty() {
         return Err(BackendError::bad_request("no children found for this node"));
     }
 
-    let child_count = child_ids.len();
-    let state_clone = state.clone();
-    state
-        .task_supervisor
-        .spawn("ai-generation-batch", async move {
-            for child_uuid in &child_ids {
-                generate_child_in_batch(state_clone.clone(), *child_uuid).await;
-            }
-        });
+    let child_count = node_ids.len();
+    crate::ai_generation_queue::enqueue_nodes(state, node_ids).await?;
 
     Ok(AiGenerateBatchResponse {
         status: "started".to_string(),
@@ -110,57 +241,69 @@ pub async fn start_generation_batch(
     })
 }
 
-async fn generate_child_in_batch(state: AppState, child_uuid: Uuid) {
-    let child_id = NodeId(child_uuid);
+/// Generate one node for the generation queue worker, returning why it was
+/// skipped or failed. A locked node returns [`Error::NodeLocked`]'s message,
+/// so the worker marks its queue item failed.
+#[tracing::instrument(name = "queued_generation", skip_all, fields(node_id = %node_uuid))]
+pub(crate) async fn generate_queued_node(state: AppState, node_uuid: Uuid) -> Result<(), String> {
+    let node_id = NodeId(node_uuid);
     let (mut request, project_path) = {
         let (project, project_path) = match active_sqlite_project(&state).await {
             Ok(project) => project,
             Err(error) => {
                 let _ = state.events_tx.send(ServerEvent::GenerationError {
-                    node_id: child_uuid,
+                    node_id: node_uuid,
                     error: error.message().to_string(),
                 });
-                return;
+                return Err(error.message().to_string());
             }
         };
-
-        let node = match project.timeline.node(child_id) {
-            Ok(node) => node,
-            Err(_) => return,
-        };
-
+        let node = project
+            .timeline
+            .node(node_id)
+            .map_err(|error| error.to_string())?;
         if node.locked {
-            return;
+            return Err(Error::NodeLocked(node_uuid).to_string());
         }
-
-        let request = match build_generate_request(&project, child_id) {
-            Ok(request) => request,
-            Err(error) => {
-                tracing::error!("Failed to build request for child node {child_uuid}: {error}");
-                return;
-            }
-        };
+        if state.generating.lock().contains(&node_uuid) {
+            return Err(Error::GenerationInProgress(node_uuid).to_string());
+        }
+        let request = build_generate_request(&project, node_id).map_err(|error| {
+            tracing::error!("Failed to build request for queued node {node_uuid}: {error}");
+            error.to_string()
+        })?;
         (request, project_path)
     };
     if let Err(error) =
-        attach_ai_generation_context(&mut request, project_path.clone(), child_id).await
+        attach_ai_generation_context(&mut request, project_path.clone(), node_id).await
     {
         let _ = state.events_tx.send(ServerEvent::GenerationError {
-            node_id: child_uuid,
+            node_id: node_uuid,
             error: error.message().to_string(),
         });
-        return;
+        return Err(error.message().to_string());
     }
 
-    state.generating.lock().insert(child_uuid);
-    mark_node_generating(&state, project_path.clone(), child_id, child_uuid).await;
-    run_generation(state, project_path, child_uuid, request).await;
+    let Some(_generating) = state.begin_generating(node_uuid) else {
+        return Err(Error::GenerationInProgress(node_uuid).to_string());
+    };
+    mark_node_generating(&state, project_path.clone(), node_id, node_uuid).await;
+    if run_generation(state, project_path, node_uuid, request).await {
+        Ok(())
+    } else {
+        Err("generation failed".to_string())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{AiGenerateRequest, start_generation};
+    use super::{
+        AiContinueRequest, AiGenerateRequest, AiGenerationLogRequest, generation_log,
+        ready_project_nodes, start_continuation, start_generation,
+    };
     use crate::state::AppState;
+    use eidetic_core::Template;
+    use eidetic_core::timeline::node::{ContentStatus, StoryLevel};
     use uuid::Uuid;
 
     #[tokio::test]
@@ -171,6 +314,8 @@ mod tests {
             &state,
             AiGenerateRequest {
                 node_id: Uuid::new_v4(),
+                include_entity_ids: Vec::new(),
+                exclude_entity_ids: Vec::new(),
             },
         )
         .await
@@ -178,4 +323,76 @@ mod tests {
 
         assert_eq!(error.message(), "no project loaded");
     }
+
+    #[tokio::test]
+    async fn start_continuation_requires_loaded_project() {
+        let state = AppState::new().await;
+
+        let error = start_continuation(
+            &state,
+            AiContinueRequest {
+                node_id: Uuid::new_v4(),
+            },
+        )
+        .await
+        .expect_err("missing project");
+
+        assert_eq!(error.message(), "no project loaded");
+    }
+
+    #[tokio::test]
+    async fn generation_log_requires_loaded_project() {
+        let state = AppState::new().await;
+
+        let error = generation_log(
+            &state,
+            AiGenerationLogRequest {
+                node_id: Uuid::new_v4(),
+            },
+        )
+        .await
+        .expect_err("missing project");
+
+        assert_eq!(error.message(), "no project loaded");
+    }
+
+    #[test]
+    fn ready_nodes_need_notes_and_a_drafted_parent() {
+        let mut project = Template::MultiCam.build_project("Ready Test");
+        let scenes: Vec<_> = project
+            .timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .iter()
+            .map(|node| (node.id, node.parent_id))
+            .collect();
+        for node in &mut project.timeline.nodes {
+            node.content.status = match node.level {
+                StoryLevel::Scene => ContentStatus::NotesOnly,
+                _ => ContentStatus::HasContent,
+            };
+        }
+        let (locked, _) = scenes[0];
+        let (generating, _) = scenes[1];
+        let (orphaned, orphaned_parent) = scenes[2];
+        project.timeline.node_mut(locked).unwrap().locked = true;
+        project
+            .timeline
+            .node_mut(orphaned_parent.unwrap())
+            .unwrap()
+            .content
+            .status = ContentStatus::NotesOnly;
+
+        let ready = ready_project_nodes(&project, Some(StoryLevel::Scene), |id| id == generating);
+
+        let ready_ids: Vec<_> = ready.iter().map(|node| node.id).collect();
+        assert!(!ready_ids.is_empty());
+        assert!(!ready_ids.contains(&locked));
+        assert!(!ready_ids.contains(&generating));
+        assert!(!ready_ids.contains(&orphaned));
+        assert!(
+            ready
+                .windows(2)
+                .all(|pair| pair[0].time_range.start_ms <= pair[1].time_range.start_ms)
+        );
+    }
 }
@@ -0,0 +1,198 @@
+use eidetic_core::Project;
+use eidetic_core::ai::backend::NotesDriftVerdict;
+use eidetic_core::ai::prompt::parse_notes_drift_verdict;
+use eidetic_core::timeline::node::{NodeId, StoryNode};
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::ai_backends::Backend;
+use crate::ai_service::active_sqlite_project;
+use crate::backend_error::BackendError;
+use crate::prompt_format::build_notes_drift_prompt;
+use crate::state::{AppState, ServerEvent};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AiNotesDriftRequest {
+    pub node_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AiNotesDriftSweepResponse {
+    pub status: String,
+    /// Nodes with both notes and content that will be sent to the model.
+    pub check_count: usize,
+}
+
+/// Ask the model whether one node's content still carries out its notes.
+pub async fn check_notes_drift(
+    state: &AppState,
+    body: AiNotesDriftRequest,
+) -> Result<NotesDriftVerdict, BackendError> {
+    let node = {
+        let (project, _) = active_sqlite_project(state).await?;
+        project.timeline.node(NodeId(body.node_id))?.clone()
+    };
+    if !has_notes_and_content(&node) {
+        return Err(BackendError::bad_request(
+            "node needs both notes and content to check for drift",
+        ));
+    }
+
+    let config = state.active_ai_config();
+    let backend = Backend::from_config(&config);
+    let json_text = backend
+        .generate_json(&build_notes_drift_prompt(&node), &config)
+        .await
+        .map_err(|error| {
+            tracing::error!(
+                "Notes drift check failed for node {}: {error}",
+                body.node_id
+            );
+            BackendError::internal(error.to_string())
+        })?;
+    parse_notes_drift_verdict(&json_text).map_err(|error| {
+        tracing::warn!("Failed to parse notes drift JSON: {error}\nRaw: {json_text}");
+        BackendError::bad_request(format!("failed to parse AI response: {error}"))
+    })
+}
+
+/// Check every node with notes and content in the background. Each drifted
+/// node is reported as a [`ServerEvent::NotesDriftFinding`], followed by one
+/// [`ServerEvent::NotesDriftSweepComplete`].
+pub async fn start_notes_drift_sweep(
+    state: &AppState,
+) -> Result<AiNotesDriftSweepResponse, BackendError> {
+    let (project, _) = active_sqlite_project(state).await?;
+    let nodes = drift_candidates(&project);
+    if nodes.is_empty() {
+        return Err(BackendError::bad_request(
+            "no node has both notes and content",
+        ));
+    }
+
+    let response = AiNotesDriftSweepResponse {
+        status: "started".to_string(),
+        check_count: nodes.len(),
+    };
+    let state_clone = state.clone();
+    state
+        .task_supervisor
+        .spawn("ai-notes-drift-sweep", async move {
+            run_notes_drift_sweep(state_clone, nodes).await;
+        });
+
+    Ok(response)
+}
+
+#[tracing::instrument(name = "notes_drift_sweep", skip_all, fields(model))]
+async fn run_notes_drift_sweep(state: AppState, nodes: Vec<StoryNode>) {
+    let config = state.active_ai_config();
+    tracing::Span::current().record("model", config.model.as_str());
+    let backend = Backend::from_config(&config);
+    let checked_count = nodes.len();
+    let mut drift_count = 0;
+
+    for node in nodes {
+        let verdict = match backend
+            .generate_json(&build_notes_drift_prompt(&node), &config)
+            .instrument(tracing::info_span!("notes_drift_check", node_id = %node.id.0))
+            .await
+        {
+            Ok(json_text) => parse_notes_drift_verdict(&json_text),
+            Err(error) => {
+                tracing::warn!("Notes drift check of node {} failed: {error}", node.id.0);
+                continue;
+            }
+        };
+        match verdict {
+            Ok(verdict) if !verdict.fulfilled => {
+                drift_count += 1;
+                let _ = state.events_tx.send(ServerEvent::NotesDriftFinding {
+                    node_id: node.id.0,
+                    specifics: verdict.specifics,
+                });
+            }
+            Ok(_) => {}
+            Err(error) => {
+                tracing::warn!(
+                    "Failed to parse notes drift verdict for node {}: {error}",
+                    node.id.0
+                );
+            }
+        }
+    }
+
+    let _ = state.events_tx.send(ServerEvent::NotesDriftSweepComplete {
+        checked_count,
+        drift_count,
+    });
+}
+
+/// Nodes with both notes and drafted content, in time order.
+fn drift_candidates(project: &Project) -> Vec<StoryNode> {
+    let mut nodes: Vec<StoryNode> = project
+        .timeline
+        .nodes
+        .iter()
+        .filter(|node| has_notes_and_content(node))
+        .cloned()
+        .collect();
+    nodes.sort_by_key(|node| (node.time_range.start_ms, node.level));
+    nodes
+}
+
+fn has_notes_and_content(node: &StoryNode) -> bool {
+    !node.content.notes.trim().is_empty() && !node.content.content.trim().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use eidetic_core::Template;
+    use eidetic_core::timeline::node::StoryLevel;
+
+    use super::*;
+
+    #[test]
+    fn sweep_covers_nodes_with_notes_and_content_in_time_order() {
+        let mut project = Template::MultiCam.build_project("Notes Drift Test");
+        for node in &mut project.timeline.nodes {
+            node.content.notes.clear();
+            node.content.content.clear();
+        }
+        let scenes: Vec<_> = project
+            .timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .into_iter()
+            .take(3)
+            .map(|node| node.id)
+            .collect();
+        for (node_id, notes, content) in [
+            (
+                scenes[2],
+                "Jake finds the locket.",
+                "Jake searches the pier.",
+            ),
+            (
+                scenes[0],
+                "Amy lies about the theft.",
+                "AMY\nI never touched it.",
+            ),
+            (scenes[1], "Only notes so far.", ""),
+        ] {
+            let node = project.timeline.node_mut(node_id).unwrap();
+            node.content.notes = notes.into();
+            node.content.content = content.into();
+        }
+
+        let candidates: Vec<_> = drift_candidates(&project)
+            .iter()
+            .map(|node| node.id)
+            .collect();
+
+        assert_eq!(candidates, [scenes[0], scenes[2]]);
+        let prompt = build_notes_drift_prompt(project.timeline.node(scenes[2]).unwrap());
+        assert!(prompt.user.contains("NOTES:\nJake finds the locket."));
+        assert!(prompt.user.contains("CONTENT:\nJake searches the pier."));
+    }
+}
@@ -1,19 +1,42 @@
 use std::path::PathBuf;
 
-use eidetic_core::Project;
-use eidetic_core::ai::backend::{ChildPlan, ChildPlanId, ChildProposal, GenerateChildrenRequest};
-use eidetic_core::ai::prompt::{build_generate_children_request, build_generate_request};
+use eidetic_core::ai::backend::{
+    CharacterProposal, ChildPlan, ChildPlanId, GenerateChildrenRequest, PremiseExpansion,
+};
+use eidetic_core::ai::prompt::{
+    build_generate_children_request, build_generate_request, parse_child_plan,
+    parse_premise_expansion,
+};
 use eidetic_core::contracts::{
-    AffectProjection, AffectTarget, AiBibleContextProjection, ProjectionEnvelope,
+    AffectProjection, AffectTarget, AiBibleContextProjection, BibleGraphNodeId,
+    BibleGraphSchemaKey, CanonicalBibleRoot, CommandEnvelope, CreateBibleGraphNodeCommand,
+    CreateStoryArcCommand, EnsureCanonicalBibleRootsCommand, ProjectionEnvelope,
+    SetBibleGraphNodeTextCommand, SetTimelineNodeNotesCommand,
 };
+use eidetic_core::story::arc::{ArcId, StoryArc};
+use eidetic_core::timeline::MIN_CHILD_GAP_MS;
 use eidetic_core::timeline::node::NodeId;
+use eidetic_core::{Error, Project};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::ai_backends::Backend;
+use crate::ai_bible_context_prompt::expand_entity_placeholders;
+use crate::ai_generation_runtime::load_generated_script_text;
 use crate::backend_error::BackendError;
-use crate::prompt_format::{build_chat_prompt, build_decompose_prompt};
-use crate::state::{AiConfig, AppState, BackendType};
+use crate::command_service::{
+    create_bible_graph_node_from_core_command, create_story_arc_from_core_command,
+    ensure_canonical_bible_roots, set_bible_graph_node_text,
+};
+use crate::command_service_timeline::{TimelineCommandResponse, set_timeline_node_notes_as};
+use crate::embeddings::EmbeddingClient;
+use crate::history_store::HistoryStoreError;
+use crate::prompt_format::{
+    ChatPrompt, apply_sibling_limits, build_arc_treatment_prompt, build_brainstorm_prompt,
+    build_chat_prompt, build_decompose_prompt, build_notes_from_content_prompt,
+    build_premise_expansion_prompt, estimate_tokens, trim_request_to_budget,
+};
+use crate::state::{AiConfig, AppState, BackendType, OutlineWordTargets};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AiStatus {
@@ -22,6 +45,9 @@ pub struct AiStatus {
     pub connected: bool,
     pub message: Option<String>,
     pub error: Option<String>,
+    /// Status of each configured fallback, in the order they are tried.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<AiStatus>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
@@ -30,60 +56,232 @@ pub struct AiConfigUpdate {
     pub model: Option<String>,
     pub temperature: Option<f32>,
     pub max_tokens: Option<usize>,
+    pub context_limit: Option<usize>,
+    pub max_siblings: Option<usize>,
+    pub sibling_preview_chars: Option<usize>,
+    pub stop_sequences: Option<Vec<String>>,
+    pub json_mode: Option<bool>,
+    pub auto_continue_truncated: Option<bool>,
+    pub introduction_snapshots: Option<bool>,
+    pub words_per_minute: Option<u32>,
+    pub outline_word_targets: Option<OutlineWordTargets>,
+    pub embedding_model: Option<String>,
     pub base_url: Option<String>,
     pub api_key: Option<Option<String>>,
+    /// `null` removes the fallback; an absent field keeps it.
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub fallback: Option<Option<Box<AiConfig>>>,
+}
+
+/// Wrap any present value, including `null`, so it differs from an absent field.
+fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AiContextPreview {
     pub system: String,
     pub user: String,
+    /// Estimated prompt size in tokens.
+    pub estimated_tokens: usize,
+    /// Tokens available for the prompt after reserving `max_tokens` for output.
+    pub prompt_budget: usize,
+    /// Context sections dropped to fit the budget, in the order they were dropped.
+    pub trimmed_sections: Vec<String>,
+    /// Set when the prompt still exceeds the budget after trimming.
+    pub warning: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct AiGenerateChildrenRequest {
     pub node_id: Uuid,
+    /// Keep the node's existing children and plan only for the time they
+    /// leave uncovered.
+    #[serde(default)]
+    pub preserve_existing: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AiBrainstormNotesRequest {
+    pub node_id: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AiSummarizeToNotesRequest {
+    pub node_id: Uuid,
+    /// Replace notes the node already has instead of refusing.
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AiArcTreatmentRequest {
+    pub arc_id: ArcId,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArcTreatment {
+    pub arc_id: ArcId,
+    pub treatment: String,
+}
+
+/// The accepted subset of a [`PremiseExpansion`] to create.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AiCommitPremiseExpansionRequest {
+    #[serde(default)]
+    pub arcs: Vec<StoryArc>,
+    #[serde(default)]
+    pub characters: Vec<CharacterProposal>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PremiseExpansionCommitResponse {
+    pub arc_ids: Vec<ArcId>,
+    /// Bible graph nodes created for the accepted characters.
+    pub character_node_ids: Vec<BibleGraphNodeId>,
 }
 
+/// Upper bound on output tokens for brainstormed notes; they are a short plan,
+/// not a draft.
+const BRAINSTORM_MAX_TOKENS: usize = 512;
+
 pub async fn get_ai_status(state: &AppState) -> AiStatus {
-    let config = state.ai_config.lock().clone();
+    let config = state.active_ai_config();
     let backend = Backend::from_config(&config);
 
-    match backend.health_check().await {
+    let mut status = ai_status(&config, backend.health_check().await);
+    for (fallback, result) in backend.fallback_health_checks().await {
+        status.fallbacks.push(ai_status(&fallback, result));
+    }
+    status
+}
+
+fn ai_status(
+    config: &AiConfig,
+    result: Result<crate::ai_backends::BackendStatus, eidetic_core::error::Error>,
+) -> AiStatus {
+    match result {
         Ok(status) => AiStatus {
             backend: config.backend_type,
-            model: display_model(&config, &status.model),
+            model: display_model(config, &status.model),
             connected: status.connected,
             message: Some(status.message),
             error: None,
+            fallbacks: Vec::new(),
         },
         Err(error) => AiStatus {
             backend: config.backend_type,
-            model: config.model,
+            model: config.model.clone(),
             connected: false,
             message: None,
             error: Some(error.to_string()),
+            fallbacks: Vec::new(),
         },
     }
 }
 
+/// The models [`preload_models`] warmed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AiPreloadStatus {
+    pub model: String,
+    pub embedding_model: String,
+    /// Set when the embedding model could not be warmed. Generation still works
+    /// without it.
+    pub embedding_error: Option<String>,
+}
+
+/// Send the generation and embedding models a one-token request each so a
+/// server that loads models on demand has them resident before the writer's
+/// first real request.
+pub async fn preload_models(state: &AppState) -> Result<AiPreloadStatus, BackendError> {
+    let mut config = state.active_ai_config();
+    config.max_tokens = 1;
+    config.stop_sequences.clear();
+    let backend = Backend::from_config(&config);
+    let prompt = ChatPrompt {
+        system: String::new(),
+        user: "Hello".to_string(),
+        assistant: None,
+    };
+    backend
+        .generate_full(&prompt, &config)
+        .await
+        .map_err(|error| {
+            tracing::warn!("Model preload failed: {error}");
+            BackendError::internal(error.to_string())
+        })?;
+
+    let embedding_error = EmbeddingClient::new(&config.base_url, &config.embedding_model)
+        .embed("Hello")
+        .await
+        .err();
+    if let Some(error) = &embedding_error {
+        tracing::warn!("Embedding model preload failed: {error}");
+    }
+
+    Ok(AiPreloadStatus {
+        model: config.model,
+        embedding_model: config.embedding_model,
+        embedding_error,
+    })
+}
+
 pub async fn preview_ai_context(
     state: &AppState,
     node_uuid: Uuid,
 ) -> Result<AiContextPreview, BackendError> {
-    let node_id = NodeId(node_uuid);
-    let (project, project_path) = active_sqlite_project(state).await?;
-    let mut request = build_generate_request(&project, node_id)
-        .map_err(|error| BackendError::BadRequest(error.to_string()))?;
-    attach_ai_generation_context(&mut request, project_path, node_id).await?;
-    let prompt = build_chat_prompt(&request);
+    let (prompt, trimmed_sections, config) = prepare_generation_prompt(state, node_uuid).await?;
+    let prompt_budget = config.prompt_budget();
+    let estimated_tokens = estimate_tokens(&prompt);
+    let warning = (estimated_tokens > prompt_budget).then(|| {
+        format!(
+            "prompt is ~{estimated_tokens} tokens, over the {prompt_budget}-token budget; \
+             the model may truncate it"
+        )
+    });
 
     Ok(AiContextPreview {
         system: prompt.system,
         user: prompt.user,
+        estimated_tokens,
+        prompt_budget,
+        trimmed_sections: trimmed_sections.into_iter().map(String::from).collect(),
+        warning,
     })
 }
 
+/// The exact JSON body the configured backend would receive when generating
+/// content for `node_uuid`.
+pub async fn debug_request_body(
+    state: &AppState,
+    node_uuid: Uuid,
+) -> Result<serde_json::Value, BackendError> {
+    let (prompt, _, config) = prepare_generation_prompt(state, node_uuid).await?;
+    let backend = Backend::from_config(&config);
+    Ok(backend.request_body(&prompt, &config).await)
+}
+
+/// Build the chat prompt for a node the same way generation does, applying
+/// the configured sibling limits and prompt budget.
+async fn prepare_generation_prompt(
+    state: &AppState,
+    node_uuid: Uuid,
+) -> Result<(ChatPrompt, Vec<&'static str>, AiConfig), BackendError> {
+    let node_id = NodeId(node_uuid);
+    let (project, project_path) = active_sqlite_project(state).await?;
+    let mut request = build_generate_request(&project, node_id)?;
+    attach_ai_generation_context(&mut request, project_path, node_id).await?;
+
+    let config = state.active_ai_config().for_request(&request);
+    apply_sibling_limits(&mut request, &config);
+    let trimmed_sections = trim_request_to_budget(&mut request, config.prompt_budget());
+    Ok((build_chat_prompt(&request), trimmed_sections, config))
+}
+
 pub async fn generate_children(
     state: &AppState,
     body: AiGenerateChildrenRequest,
@@ -91,21 +289,38 @@ pub async fn generate_children(
     let node_id = NodeId(body.node_id);
     let (mut request, project_path) = {
         let (project, project_path) = active_sqlite_project(state).await?;
-        let node = project
-            .timeline
-            .node(node_id)
-            .map_err(|_| BackendError::not_found(format!("node not found: {}", body.node_id)))?;
+        let node = project.timeline.node(node_id)?;
         if node.content.notes.trim().is_empty() {
-            return Err(BackendError::bad_request("node has no notes"));
+            return Err(Error::MissingNotes(body.node_id).into());
         }
 
-        let request = build_generate_children_request(&project, node_id)
-            .map_err(|error| BackendError::bad_request(error.to_string()))?;
+        let mut request = build_generate_children_request(&project, node_id)?;
+        if body.preserve_existing {
+            request.fill_gaps = project
+                .timeline
+                .find_child_gaps(node_id, MIN_CHILD_GAP_MS)?
+                .into_iter()
+                .map(|gap| gap.time_range)
+                .collect();
+            if request.fill_gaps.is_empty() {
+                return Err(BackendError::bad_request(
+                    "existing children already cover this node",
+                ));
+            }
+            let mut existing_children: Vec<_> = project
+                .timeline
+                .children_of(node_id)
+                .into_iter()
+                .cloned()
+                .collect();
+            existing_children.sort_by_key(|child| child.time_range.start_ms);
+            request.existing_children = existing_children;
+        }
         (request, project_path)
     };
     attach_ai_generation_context_to_children(&mut request, project_path, node_id).await?;
 
-    let config = state.ai_config.lock().clone();
+    let config = state.active_ai_config();
     let backend = Backend::from_config(&config);
     let prompt = build_decompose_prompt(&request);
     let json_text = backend
@@ -119,7 +334,13 @@ pub async fn generate_children(
             BackendError::internal(error.to_string())
         })?;
 
-    let children = parse_child_proposals(&json_text, body.node_id)?;
+    let children = parse_child_plan(&json_text, request.target_child_level).map_err(|error| {
+        tracing::warn!(
+            "Failed to parse child plan JSON for node {}: {error}\nRaw: {json_text}",
+            body.node_id
+        );
+        BackendError::bad_request(format!("failed to parse AI response: {error}"))
+    })?;
     let plan = ChildPlan {
         id: ChildPlanId::new(format!("child_plan.{}", Uuid::new_v4()))
             .expect("generated child plan ids are non-empty"),
@@ -137,6 +358,272 @@ pub async fn generate_children(
     Ok(plan)
 }
 
+/// Draft planning notes for an empty node from its ancestors, arcs, and
+/// neighbours, and store them as the node's notes.
+pub async fn brainstorm_notes(
+    state: &AppState,
+    body: AiBrainstormNotesRequest,
+) -> Result<TimelineCommandResponse, BackendError> {
+    let node_id = NodeId(body.node_id);
+    let (mut request, project_path) = {
+        let (project, project_path) = active_sqlite_project(state).await?;
+        let node = project.timeline.node(node_id)?;
+        if node.locked {
+            return Err(Error::NodeLocked(body.node_id).into());
+        }
+        if !node.content.notes.trim().is_empty() {
+            return Err(BackendError::bad_request("node already has notes"));
+        }
+
+        let request = build_generate_request(&project, node_id)?;
+        (request, project_path)
+    };
+    attach_ai_generation_context(&mut request, project_path, node_id).await?;
+
+    let mut config = state.active_ai_config();
+    apply_sibling_limits(&mut request, &config);
+    trim_request_to_budget(&mut request, config.prompt_budget());
+    config.max_tokens = config.max_tokens.min(BRAINSTORM_MAX_TOKENS);
+
+    let backend = Backend::from_config(&config);
+    let prompt = build_brainstorm_prompt(&request);
+    let notes = backend
+        .generate_full(&prompt, &config)
+        .await
+        .map_err(|error| {
+            tracing::error!("Notes brainstorm failed for node {}: {error}", body.node_id);
+            BackendError::internal(error.to_string())
+        })?
+        .trim()
+        .to_string();
+    if notes.is_empty() {
+        return Err(BackendError::internal("AI returned empty notes"));
+    }
+
+    set_timeline_node_notes_as(
+        state,
+        CommandEnvelope::new(SetTimelineNodeNotesCommand { node_id, notes }),
+        "ai:brainstorm",
+    )
+    .await
+}
+
+/// Backfill a node's planning notes from the content it already has, e.g.
+/// after a Fountain import. Unlike the scene recap, the notes capture what
+/// the node sets out to do rather than where it leaves things.
+pub async fn summarize_to_notes(
+    state: &AppState,
+    body: AiSummarizeToNotesRequest,
+) -> Result<TimelineCommandResponse, BackendError> {
+    let node_id = NodeId(body.node_id);
+    let (mut request, project_path, node_text) = {
+        let (project, project_path) = active_sqlite_project(state).await?;
+        let node = project.timeline.node(node_id)?;
+        if node.locked {
+            return Err(Error::NodeLocked(body.node_id).into());
+        }
+        if !body.overwrite && !node.content.notes.trim().is_empty() {
+            return Err(BackendError::bad_request("node already has notes"));
+        }
+
+        let request = build_generate_request(&project, node_id)?;
+        (request, project_path, node.content.content.clone())
+    };
+    let content = load_generated_script_text(project_path, body.node_id)
+        .await
+        .map_err(BackendError::Internal)?
+        .unwrap_or(node_text);
+    if content.trim().is_empty() {
+        return Err(BackendError::bad_request(
+            "node has no content to summarize",
+        ));
+    }
+
+    let mut config = state.active_ai_config();
+    apply_sibling_limits(&mut request, &config);
+    trim_request_to_budget(&mut request, config.prompt_budget());
+    config.max_tokens = config.max_tokens.min(BRAINSTORM_MAX_TOKENS);
+
+    let backend = Backend::from_config(&config);
+    let prompt = build_notes_from_content_prompt(&request, &content);
+    let notes = backend
+        .generate_full(&prompt, &config)
+        .await
+        .map_err(|error| {
+            tracing::error!("Notes summary failed for node {}: {error}", body.node_id);
+            BackendError::internal(error.to_string())
+        })?
+        .trim()
+        .to_string();
+    if notes.is_empty() {
+        return Err(BackendError::internal("AI returned empty notes"));
+    }
+
+    set_timeline_node_notes_as(
+        state,
+        CommandEnvelope::new(SetTimelineNodeNotesCommand { node_id, notes }),
+        "ai:summarize",
+    )
+    .await
+}
+
+/// Write a prose treatment of one arc's storyline from every node tagged with
+/// it, in time order. The text is returned, not stored.
+pub async fn generate_arc_treatment(
+    state: &AppState,
+    body: AiArcTreatmentRequest,
+) -> Result<ArcTreatment, BackendError> {
+    let (project, _) = active_sqlite_project(state).await?;
+    let arc = project
+        .arcs
+        .iter()
+        .find(|arc| arc.id == body.arc_id)
+        .ok_or_else(|| BackendError::not_found(format!("arc not found: {}", body.arc_id.0)))?;
+    let mut nodes: Vec<_> = project
+        .timeline
+        .nodes_for_arc(arc.id)
+        .into_iter()
+        .filter_map(|node_id| project.timeline.node(node_id).ok())
+        .collect();
+    if nodes.is_empty() {
+        return Err(BackendError::bad_request("arc has no tagged nodes"));
+    }
+    nodes.sort_by_key(|node| (node.time_range.start_ms, node.level, node.time_range.end_ms));
+
+    let config = state.active_ai_config();
+    let backend = Backend::from_config(&config);
+    let prompt = build_arc_treatment_prompt(&project.premise, arc, &nodes);
+    let treatment = backend
+        .generate_full(&prompt, &config)
+        .await
+        .map_err(|error| {
+            tracing::error!("Arc treatment failed for arc {}: {error}", body.arc_id.0);
+            BackendError::internal(error.to_string())
+        })?
+        .trim()
+        .to_string();
+    if treatment.is_empty() {
+        return Err(BackendError::internal("AI returned an empty treatment"));
+    }
+
+    Ok(ArcTreatment {
+        arc_id: body.arc_id,
+        treatment,
+    })
+}
+
+/// Expand the project premise into a logline, arcs, and principal characters.
+/// Nothing is created; the result is for review and
+/// [`commit_premise_expansion`].
+pub async fn expand_premise(state: &AppState) -> Result<PremiseExpansion, BackendError> {
+    let (project, _) = active_sqlite_project(state).await?;
+    if project.premise.trim().is_empty() {
+        return Err(BackendError::bad_request("project has no premise"));
+    }
+
+    let config = state.active_ai_config();
+    let backend = Backend::from_config(&config);
+    let prompt = build_premise_expansion_prompt(&project.premise);
+    let json_text = backend
+        .generate_json(&prompt, &config)
+        .await
+        .map_err(|error| {
+            tracing::error!("Premise expansion failed: {error}");
+            BackendError::internal(error.to_string())
+        })?;
+
+    parse_premise_expansion(&json_text).map_err(|error| {
+        tracing::warn!("Failed to parse premise expansion JSON: {error}\nRaw: {json_text}");
+        BackendError::bad_request(format!("failed to parse AI response: {error}"))
+    })
+}
+
+/// Create the accepted arcs from a premise expansion, and a bible character
+/// under the Characters root for each accepted character.
+pub async fn commit_premise_expansion(
+    state: &AppState,
+    body: AiCommitPremiseExpansionRequest,
+) -> Result<PremiseExpansionCommitResponse, BackendError> {
+    if body.arcs.iter().any(|arc| arc.name.trim().is_empty())
+        || body
+            .characters
+            .iter()
+            .any(|character| character.name.trim().is_empty())
+    {
+        return Err(BackendError::bad_request(
+            "accepted arcs and characters need a name",
+        ));
+    }
+
+    let mut arc_ids = Vec::with_capacity(body.arcs.len());
+    for arc in body.arcs {
+        arc_ids.push(arc.id);
+        create_story_arc_from_core_command(
+            state,
+            CommandEnvelope::new(CreateStoryArcCommand {
+                arc_id: arc.id,
+                parent_arc_id: None,
+                name: arc.name,
+                description: arc.description,
+                arc_type: arc.arc_type,
+                color: arc.color,
+            }),
+        )
+        .await?;
+    }
+
+    let mut character_node_ids = Vec::with_capacity(body.characters.len());
+    if !body.characters.is_empty() {
+        ensure_canonical_bible_roots(
+            state,
+            CommandEnvelope::new(EnsureCanonicalBibleRootsCommand {}),
+        )
+        .await?;
+        let characters_root = CanonicalBibleRoot::Characters.node_id();
+        let existing = crate::projection_service::bible_graph_node_list_projection(state)
+            .await?
+            .payload
+            .nodes
+            .iter()
+            .filter(|node| node.parent_id.as_ref() == Some(&characters_root))
+            .count();
+        let schema_key = BibleGraphSchemaKey::new("character")
+            .map_err(|error| BackendError::internal(error.to_string()))?;
+
+        for (index, character) in body.characters.into_iter().enumerate() {
+            let node_id = BibleGraphNodeId::new(format!("node.character.{}", Uuid::new_v4()))
+                .map_err(|error| BackendError::internal(error.to_string()))?;
+            create_bible_graph_node_from_core_command(
+                state,
+                CommandEnvelope::new(CreateBibleGraphNodeCommand {
+                    node_id: node_id.clone(),
+                    parent_id: Some(characters_root.clone()),
+                    schema_key: schema_key.clone(),
+                    name: character.name,
+                    sort_order: (existing + index).try_into().unwrap_or(u32::MAX),
+                }),
+            )
+            .await?;
+            if !character.description.trim().is_empty() {
+                set_bible_graph_node_text(
+                    state,
+                    CommandEnvelope::new(SetBibleGraphNodeTextCommand {
+                        node_id: node_id.clone(),
+                        text: character.description,
+                    }),
+                )
+                .await?;
+            }
+            character_node_ids.push(node_id);
+        }
+    }
+
+    Ok(PremiseExpansionCommitResponse {
+        arc_ids,
+        character_node_ids,
+    })
+}
+
 pub fn update_ai_config(state: &AppState, update: AiConfigUpdate) -> AiConfig {
     let mut config = state.ai_config.lock();
     if let Some(backend_type) = update.backend_type {
@@ -151,12 +638,50 @@ pub fn update_ai_config(state: &AppState, update: AiConfigUpdate) -> AiConfig {
     if let Some(max_tokens) = update.max_tokens {
         config.max_tokens = max_tokens;
     }
+    if let Some(context_limit) = update.context_limit {
+        config.context_limit = context_limit;
+    }
+    if let Some(max_siblings) = update.max_siblings {
+        config.max_siblings = max_siblings;
+    }
+    if let Some(sibling_preview_chars) = update.sibling_preview_chars {
+        config.sibling_preview_chars = sibling_preview_chars;
+    }
+    if let Some(stop_sequences) = update.stop_sequences {
+        config.stop_sequences = stop_sequences
+            .into_iter()
+            .filter(|stop| !stop.is_empty())
+            .collect();
+    }
+    if let Some(json_mode) = update.json_mode {
+        config.json_mode = json_mode;
+    }
+    if let Some(auto_continue_truncated) = update.auto_continue_truncated {
+        config.auto_continue_truncated = auto_continue_truncated;
+    }
+    if let Some(introduction_snapshots) = update.introduction_snapshots {
+        config.introduction_snapshots = introduction_snapshots;
+    }
+    if let Some(words_per_minute) = update.words_per_minute {
+        config.words_per_minute = words_per_minute;
+    }
+    if let Some(outline_word_targets) = update.outline_word_targets {
+        config.outline_word_targets = outline_word_targets;
+    }
+    if let Some(embedding_model) = update.embedding_model
+        && !embedding_model.trim().is_empty()
+    {
+        config.embedding_model = embedding_model;
+    }
     if let Some(base_url) = update.base_url {
         config.base_url = base_url;
     }
     if let Some(api_key) = update.api_key {
         config.api_key = api_key.filter(|value| !value.is_empty());
     }
+    if let Some(fallback) = update.fallback {
+        config.fallback = fallback;
+    }
     config.clone()
 }
 
@@ -180,11 +705,56 @@ pub(crate) async fn attach_ai_generation_context(
     path: PathBuf,
     node_id: NodeId,
 ) -> Result<(), BackendError> {
-    request.bible_context = Some(load_ai_bible_context_projection(path.clone(), node_id).await?);
+    let bible_context = load_ai_bible_context_projection(path.clone(), node_id).await?;
+    request.target_node.content.notes =
+        expand_entity_placeholders(&request.target_node.content.notes, &bible_context.payload);
+    request.bible_context = Some(bible_context);
     request.affect_context = Some(load_ai_affect_projection(path, node_id).await?);
     Ok(())
 }
 
+/// Apply per-request bible entity overrides to an attached context.
+pub(crate) async fn apply_ai_bible_context_overrides(
+    request: &mut eidetic_core::ai::backend::GenerateRequest,
+    path: PathBuf,
+    include_ids: Vec<BibleGraphNodeId>,
+    exclude_ids: Vec<BibleGraphNodeId>,
+) -> Result<(), BackendError> {
+    if include_ids.is_empty() && exclude_ids.is_empty() {
+        return Ok(());
+    }
+    let Some(mut context) = request.bible_context.take() else {
+        return Ok(());
+    };
+    let context = tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path).map_err(|error| {
+            BackendError::Internal(format!("open AI bible context database failed: {error}"))
+        })?;
+        crate::ai_context_projection::apply_ai_bible_context_overrides(
+            &conn,
+            &mut context.payload,
+            &include_ids,
+            &exclude_ids,
+        )
+        .map_err(|error| match error {
+            HistoryStoreError::InvalidId(message) | HistoryStoreError::InvalidValue(message) => {
+                BackendError::BadRequest(message)
+            }
+            error => BackendError::Internal(error.to_string()),
+        })?;
+        Ok::<_, BackendError>(context)
+    })
+    .await
+    .map_err(|error| {
+        BackendError::Internal(format!("AI bible context override task failed: {error}"))
+    })??;
+    // Forced includes can resolve placeholders the bounded context could not.
+    request.target_node.content.notes =
+        expand_entity_placeholders(&request.target_node.content.notes, &context.payload);
+    request.bible_context = Some(context);
+    Ok(())
+}
+
 async fn load_ai_bible_context_projection(
     path: PathBuf,
     node_id: NodeId,
@@ -229,47 +799,6 @@ async fn load_ai_affect_projection(
     })?
 }
 
-fn parse_child_proposals(
-    json_text: &str,
-    node_id: Uuid,
-) -> Result<Vec<ChildProposal>, BackendError> {
-    match serde_json::from_str::<Vec<ChildProposal>>(json_text) {
-        Ok(children) => Ok(children),
-        Err(_) => parse_wrapped_or_single_child_proposal(json_text, node_id),
-    }
-}
-
-fn parse_wrapped_or_single_child_proposal(
-    json_text: &str,
-    node_id: Uuid,
-) -> Result<Vec<ChildProposal>, BackendError> {
-    #[derive(Deserialize)]
-    struct Wrapped {
-        #[serde(
-            alias = "acts",
-            alias = "beats",
-            alias = "children",
-            alias = "sequences",
-            alias = "scenes"
-        )]
-        items: Vec<ChildProposal>,
-    }
-    match serde_json::from_str::<Wrapped>(json_text) {
-        Ok(wrapped) => Ok(wrapped.items),
-        Err(_) => match serde_json::from_str::<ChildProposal>(json_text) {
-            Ok(single) => Ok(vec![single]),
-            Err(error) => {
-                tracing::warn!(
-                    "Failed to parse child plan JSON for node {node_id}: {error}\nRaw: {json_text}"
-                );
-                Err(BackendError::bad_request(format!(
-                    "failed to parse AI response: {error}"
-                )))
-            }
-        },
-    }
-}
-
 fn display_model(config: &AiConfig, detected_model: &str) -> String {
     if config.model.eq_ignore_ascii_case("auto") || config.model.is_empty() {
         if detected_model.is_empty() {
@@ -285,11 +814,16 @@ fn display_model(config: &AiConfig, detected_model: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::{
-        AiConfigUpdate, AiGenerateChildrenRequest, display_model, generate_children,
-        preview_ai_context, update_ai_config,
+        AiCommitPremiseExpansionRequest, AiConfigUpdate, AiGenerateChildrenRequest,
+        AiSummarizeToNotesRequest, commit_premise_expansion, debug_request_body, display_model,
+        expand_premise, generate_children, preload_models, preview_ai_context, summarize_to_notes,
+        update_ai_config,
     };
-    use crate::state::{AiConfig, AppState, BackendType};
+    use crate::state::{AiConfig, AppState, BackendType, OutlineWordTargets};
     use eidetic_core::Template;
+    use eidetic_core::ai::backend::CharacterProposal;
+    use eidetic_core::contracts::CanonicalBibleRoot;
+    use eidetic_core::story::arc::{ArcType, Color, StoryArc};
     use eidetic_core::timeline::node::ContentStatus;
     use uuid::Uuid;
 
@@ -303,6 +837,20 @@ mod tests {
         assert_eq!(display_model(&config, "served-model"), "served-model");
     }
 
+    #[tokio::test]
+    async fn preload_reports_a_backend_that_cannot_start() {
+        let state = AppState::new().await;
+        *state.ai_config.lock() = AiConfig {
+            backend_type: BackendType::OpenRouter,
+            api_key: None,
+            ..AiConfig::default()
+        };
+
+        let error = preload_models(&state).await.expect_err("no API key");
+
+        assert!(error.message().contains("API key not configured"));
+    }
+
     #[tokio::test]
     async fn update_ai_config_applies_sparse_updates_and_filters_blank_key() {
         let state = AppState::new().await;
@@ -314,8 +862,26 @@ mod tests {
                 model: Some("open-model".to_string()),
                 temperature: Some(0.2),
                 max_tokens: Some(1024),
+                context_limit: Some(8192),
+                max_siblings: Some(3),
+                sibling_preview_chars: Some(80),
+                stop_sequences: Some(vec!["\n\nINT.".to_string(), String::new()]),
+                json_mode: Some(false),
+                auto_continue_truncated: Some(true),
+                introduction_snapshots: Some(true),
+                words_per_minute: Some(180),
+                outline_word_targets: Some(OutlineWordTargets {
+                    act: 400,
+                    ..OutlineWordTargets::default()
+                }),
+                embedding_model: Some("bge-m3".to_string()),
                 base_url: Some("https://example.test/v1".to_string()),
                 api_key: Some(Some(String::new())),
+                fallback: Some(Some(Box::new(AiConfig {
+                    backend_type: BackendType::OpenRouter,
+                    model: "hosted-model".to_string(),
+                    ..AiConfig::default()
+                }))),
             },
         );
 
@@ -323,8 +889,34 @@ mod tests {
         assert_eq!(config.model, "open-model");
         assert_eq!(config.temperature, 0.2);
         assert_eq!(config.max_tokens, 1024);
+        assert_eq!(config.context_limit, 8192);
+        assert_eq!(config.max_siblings, 3);
+        assert_eq!(config.sibling_preview_chars, 80);
+        assert_eq!(config.words_per_minute, 180);
+        assert_eq!(config.outline_word_targets.act, 400);
+        assert_eq!(config.stop_sequences, vec!["\n\nINT.".to_string()]);
+        assert!(!config.json_mode);
+        assert!(config.auto_continue_truncated);
+        assert!(config.introduction_snapshots);
+        assert_eq!(config.embedding_model, "bge-m3");
         assert_eq!(config.base_url, "https://example.test/v1");
         assert_eq!(config.api_key, None);
+        assert_eq!(
+            config
+                .fallback
+                .as_ref()
+                .map(|fallback| fallback.model.as_str()),
+            Some("hosted-model")
+        );
+    }
+
+    #[test]
+    fn ai_config_update_distinguishes_cleared_and_absent_fallback() {
+        let cleared: AiConfigUpdate = serde_json::from_str(r#"{"fallback":null}"#).unwrap();
+        let absent: AiConfigUpdate = serde_json::from_str("{}").unwrap();
+
+        assert!(matches!(cleared.fallback, Some(None)));
+        assert!(absent.fallback.is_none());
     }
 
     #[tokio::test]
@@ -335,6 +927,7 @@ mod tests {
             &state,
             AiGenerateChildrenRequest {
                 node_id: Uuid::new_v4(),
+                preserve_existing: false,
             },
         )
         .await
@@ -343,6 +936,34 @@ mod tests {
         assert_eq!(error.message(), "no project loaded");
     }
 
+    #[tokio::test]
+    async fn summarize_to_notes_needs_content_and_respects_existing_notes() {
+        let path = std::env::temp_dir().join(format!("eidetic-summarize-{}.db", Uuid::new_v4()));
+        let state = AppState::new().await;
+        let mut project = Template::MultiCam.build_project("Summarize Test");
+        let node_id = project.timeline.nodes[0].id;
+        project.timeline.node_mut(node_id).unwrap().content.notes = "Already planned.".into();
+        *state.project.lock() = Some(project);
+        *state.project_path.lock() = Some(path.clone());
+        state.trigger_save();
+        state.force_save().await;
+
+        let request = |overwrite| AiSummarizeToNotesRequest {
+            node_id: node_id.0,
+            overwrite,
+        };
+        let error = summarize_to_notes(&state, request(false))
+            .await
+            .expect_err("existing notes");
+        assert_eq!(error.message(), "node already has notes");
+        let error = summarize_to_notes(&state, request(true))
+            .await
+            .expect_err("no content");
+        assert_eq!(error.message(), "node has no content to summarize");
+
+        let _ = std::fs::remove_file(path);
+    }
+
     #[tokio::test]
     async fn preview_ai_context_hydrates_story_arcs_from_sqlite_when_project_mirror_is_stale() {
         let path =
@@ -385,4 +1006,102 @@ mod tests {
 
         let _ = std::fs::remove_file(path);
     }
+
+    #[tokio::test]
+    async fn debug_request_body_returns_backend_payload_for_node() {
+        let path = std::env::temp_dir().join(format!(
+            "eidetic-ai-service-request-body-{}.db",
+            Uuid::new_v4()
+        ));
+        let state = AppState::new().await;
+        let mut project = Template::MultiCam.build_project("AI Request Body Test");
+        let node_id = project.timeline.node_arcs[0].node_id;
+        let node = project.timeline.node_mut(node_id).expect("tagged node");
+        node.content.notes = "Debug payload notes".to_string();
+        node.content.status = ContentStatus::NotesOnly;
+        crate::persistence::save_project(&project, &path, None)
+            .await
+            .expect("seed project database");
+        *state.project.lock() = Some(project);
+        *state.project_path.lock() = Some(path.clone());
+        *state.ai_config.lock() = AiConfig {
+            backend_type: BackendType::OpenRouter,
+            model: "router-model".to_string(),
+            temperature: 0.3,
+            ..AiConfig::default()
+        };
+
+        let body = debug_request_body(&state, node_id.0)
+            .await
+            .expect("request body");
+
+        assert_eq!(body["model"], "router-model");
+        assert_eq!(body["stream"], true);
+        assert!(
+            body["messages"][1]["content"]
+                .as_str()
+                .expect("user message")
+                .contains("Debug payload notes")
+        );
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn commit_premise_expansion_creates_arcs_and_bible_characters() {
+        let path =
+            std::env::temp_dir().join(format!("eidetic-ai-service-premise-{}.db", Uuid::new_v4()));
+        let state = AppState::new().await;
+        let project = Template::MultiCam.build_project("Premise Test");
+        crate::persistence::save_project(&project, &path, None)
+            .await
+            .expect("seed project database");
+        *state.project.lock() = Some(project);
+        *state.project_path.lock() = Some(path.clone());
+
+        let error = expand_premise(&state).await.expect_err("empty premise");
+        assert_eq!(error.message(), "project has no premise");
+
+        let mut arc = StoryArc::new("Save the Light", ArcType::APlot, Color::A_PLOT);
+        arc.description = "Mara fights the sale.".into();
+        let response = commit_premise_expansion(
+            &state,
+            AiCommitPremiseExpansionRequest {
+                arcs: vec![arc.clone()],
+                characters: vec![CharacterProposal {
+                    name: "Mara".into(),
+                    description: "The last lighthouse keeper.".into(),
+                }],
+            },
+        )
+        .await
+        .expect("commit");
+
+        assert_eq!(response.arc_ids, vec![arc.id]);
+        let (saved, _) = crate::persistence::load_project(&path)
+            .await
+            .expect("reload");
+        let saved_arc = saved
+            .arcs
+            .iter()
+            .find(|saved| saved.id == arc.id)
+            .expect("created arc");
+        assert_eq!(saved_arc.description, "Mara fights the sale.");
+        let nodes = crate::projection_service::bible_graph_node_list_projection(&state)
+            .await
+            .expect("bible nodes")
+            .payload
+            .nodes;
+        let mara = nodes
+            .iter()
+            .find(|node| node.id == response.character_node_ids[0])
+            .expect("created character");
+        assert_eq!(mara.name, "Mara");
+        assert_eq!(
+            mara.parent_id,
+            Some(CanonicalBibleRoot::Characters.node_id())
+        );
+
+        let _ = std::fs::remove_file(path);
+    }
 }
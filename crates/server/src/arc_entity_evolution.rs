@@ -0,0 +1,147 @@
+//! Arc entity evolution: how the bible entities an arc's nodes reference
+//! develop inside the arc's active time window.
+
+use std::collections::HashSet;
+
+use eidetic_core::contracts::{ArcEntityEvolution, ArcEntityEvolutionProjection, BibleGraphNodeId};
+use eidetic_core::story::arc::ArcId;
+use eidetic_core::timeline::Timeline;
+use rusqlite::Connection;
+
+use crate::bible_graph_snapshot_store;
+use crate::bible_graph_store;
+use crate::context_references::referenced_bible_nodes;
+use crate::history_store::HistoryStoreError;
+
+/// Entities count as referenced by a node per [`referenced_bible_nodes`].
+/// Entities are ordered by name; each keeps only the snapshots in the window.
+pub(crate) fn load_arc_entity_evolution(
+    conn: &Connection,
+    timeline: &Timeline,
+    arc_id: ArcId,
+) -> Result<ArcEntityEvolutionProjection, HistoryStoreError> {
+    let Some(window) = timeline.arc_time_window(arc_id) else {
+        return Ok(ArcEntityEvolutionProjection {
+            arc_id,
+            window: None,
+            entities: Vec::new(),
+        });
+    };
+    let referenced: HashSet<BibleGraphNodeId> =
+        referenced_bible_nodes(conn, &timeline.nodes_for_arc(arc_id))?
+            .into_values()
+            .flatten()
+            .collect();
+
+    let mut entities = Vec::new();
+    for node in bible_graph_store::load_node_list_projection(conn)?.nodes {
+        if !referenced.contains(&node.id) {
+            continue;
+        }
+        let snapshots = bible_graph_snapshot_store::load_snapshot_projections(conn, &node.id)?
+            .into_iter()
+            .filter(|projection| window.contains(projection.snapshot.at_ms))
+            .collect();
+        entities.push(ArcEntityEvolution { node, snapshots });
+    }
+    entities.sort_by(|a, b| {
+        a.node
+            .name
+            .cmp(&b.node.name)
+            .then(a.node.id.cmp(&b.node.id))
+    });
+
+    Ok(ArcEntityEvolutionProjection {
+        arc_id,
+        window: Some(window),
+        entities,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::load_arc_entity_evolution;
+    use crate::bible_graph_store;
+    use crate::bible_graph_test_support::{create_node, set_snapshot};
+    use eidetic_core::Template;
+    use eidetic_core::story::arc::ArcId;
+    use eidetic_core::timeline::node::StoryLevel;
+    use rusqlite::Connection;
+
+    #[test]
+    fn returns_referenced_entities_with_snapshots_inside_the_arc_window() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        bible_graph_store::create_schema(&conn).unwrap();
+        let mut timeline = Template::MultiCam.build_project("Evolution").timeline;
+        let scenes: Vec<_> = timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .iter()
+            .map(|node| (node.id, node.time_range))
+            .collect();
+        let arc_id = ArcId::new();
+        timeline.tag_node(scenes[1].0, arc_id);
+        timeline.tag_node(scenes[2].0, arc_id);
+        let window_end = scenes[2].1.end_ms;
+
+        create_node(&mut conn, "node.character.mara", "Mara");
+        create_node(&mut conn, "node.character.jonah", "Jonah");
+        create_node(&mut conn, "node.character.extra", "Extra");
+        set_snapshot(
+            &mut conn,
+            "snapshot.mara.late",
+            "node.character.mara",
+            scenes[2].1.start_ms,
+            Some(scenes[2].0),
+        );
+        set_snapshot(
+            &mut conn,
+            "snapshot.mara.after",
+            "node.character.mara",
+            window_end + 1,
+            None,
+        );
+        set_snapshot(
+            &mut conn,
+            "snapshot.jonah.early",
+            "node.character.jonah",
+            scenes[1].1.start_ms,
+            Some(scenes[1].0),
+        );
+        set_snapshot(
+            &mut conn,
+            "snapshot.extra.outside",
+            "node.character.extra",
+            window_end + 10,
+            Some(scenes[3].0),
+        );
+
+        let projection = load_arc_entity_evolution(&conn, &timeline, arc_id).unwrap();
+
+        let window = projection.window.unwrap();
+        assert_eq!(window.start_ms, scenes[1].1.start_ms);
+        assert_eq!(window.end_ms, window_end);
+        let names: Vec<&str> = projection
+            .entities
+            .iter()
+            .map(|entity| entity.node.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Jonah", "Mara"]);
+        let mara: Vec<&str> = projection.entities[1]
+            .snapshots
+            .iter()
+            .map(|projection| projection.snapshot.id.as_str())
+            .collect();
+        assert_eq!(mara, vec!["snapshot.mara.late"]);
+    }
+
+    #[test]
+    fn untagged_arc_has_no_window_or_entities() {
+        let conn = Connection::open_in_memory().unwrap();
+        let timeline = Template::MultiCam.build_project("Empty Arc").timeline;
+
+        let projection = load_arc_entity_evolution(&conn, &timeline, ArcId::new()).unwrap();
+
+        assert_eq!(projection.window, None);
+        assert!(projection.entities.is_empty());
+    }
+}
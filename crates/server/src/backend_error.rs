@@ -42,3 +42,71 @@ impl BackendError {
         }
     }
 }
+
+impl From<eidetic_core::Error> for BackendError {
+    fn from(error: eidetic_core::Error) -> Self {
+        use eidetic_core::Error;
+
+        let message = error.to_string();
+        match error {
+            Error::TrackNotFound(_)
+            | Error::NodeNotFound(_)
+            | Error::ArcNotFound(_)
+            | Error::EntityNotFound(_)
+            | Error::RelationshipNotFound(_)
+            | Error::EpisodeNotFound(_) => Self::NotFound(message),
+            Error::GenerationInProgress(_) | Error::NodeLocked(_) => Self::Conflict(message),
+            Error::Serialization(_) | Error::AiBackend(_) => Self::Internal(message),
+            Error::InvalidTimeRange { .. }
+            | Error::NodeExceedsTimeline { .. }
+            | Error::SplitOutOfRange { .. }
+            | Error::InvalidHierarchy(_)
+            | Error::MissingNotes(_)
+            | Error::NodeNotGeneratable { .. }
+            | Error::PromptTooLarge { .. }
+            | Error::InvalidStatusTransition { .. }
+            | Error::InvalidSceneNumber(_)
+            | Error::InvalidOperation(_) => Self::BadRequest(message),
+        }
+    }
+}
+
+impl From<crate::persistence::ProjectLockError> for BackendError {
+    fn from(error: crate::persistence::ProjectLockError) -> Self {
+        use crate::persistence::ProjectLockError;
+
+        let message = error.to_string();
+        match error {
+            ProjectLockError::Held(_) => Self::Conflict(message),
+            ProjectLockError::Io(_) => Self::Internal(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BackendError;
+    use uuid::Uuid;
+
+    #[test]
+    fn core_errors_map_to_matching_backend_kinds() {
+        let id = Uuid::new_v4();
+        assert!(matches!(
+            BackendError::from(eidetic_core::Error::NodeNotFound(id)),
+            BackendError::NotFound(_)
+        ));
+        assert!(matches!(
+            BackendError::from(eidetic_core::Error::NodeLocked(id)),
+            BackendError::Conflict(_)
+        ));
+        assert_eq!(
+            BackendError::from(eidetic_core::Error::PromptTooLarge {
+                estimated: 9_000,
+                limit: 8_192
+            }),
+            BackendError::BadRequest(
+                "prompt is ~9000 tokens, over the 8192-token context limit".to_string()
+            )
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use parking_lot::Mutex;
 use tokio::task::JoinHandle;
+use tracing::Instrument;
 
 #[derive(Clone, Default)]
 pub struct BackendTaskSupervisor {
@@ -15,15 +16,21 @@ struct BackendTask {
 }
 
 impl BackendTaskSupervisor {
+    /// Run `future` as a tracked task. The task stays in the caller's span, so
+    /// its log lines keep the node and model context of the work that
+    /// started it.
     pub fn spawn<F>(&self, name: &'static str, future: F)
     where
         F: Future<Output = ()> + Send + 'static,
     {
-        let handle = tokio::spawn(async move {
-            tracing::debug!("backend task started: {name}");
-            future.await;
-            tracing::debug!("backend task stopped: {name}");
-        });
+        let handle = tokio::spawn(
+            async move {
+                tracing::debug!("backend task started: {name}");
+                future.await;
+                tracing::debug!("backend task stopped: {name}");
+            }
+            .in_current_span(),
+        );
         self.tasks.lock().push(BackendTask { name, handle });
     }
 
@@ -97,6 +104,23 @@ mod tests {
         assert_eq!(supervisor.active_task_count(), 0);
     }
 
+    #[tokio::test]
+    async fn supervisor_tasks_inherit_the_callers_span() {
+        let _subscriber = tracing::subscriber::set_default(tracing_subscriber::registry());
+        let supervisor = BackendTaskSupervisor::default();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+
+        tracing::info_span!("generate").in_scope(|| {
+            supervisor.spawn("test-span", async move {
+                let name = tracing::Span::current().metadata().map(|meta| meta.name());
+                let _ = tx.send(name);
+            });
+        });
+
+        assert_eq!(rx.await.unwrap(), Some("generate"));
+        supervisor.shutdown_all().await;
+    }
+
     #[tokio::test]
     async fn supervisor_shutdown_observes_task_panics() {
         let supervisor = BackendTaskSupervisor::default();
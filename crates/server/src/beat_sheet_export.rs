@@ -0,0 +1,181 @@
+//! Beat sheet CSV export: one row per timeline node at a chosen level.
+
+use std::collections::HashMap;
+
+use eidetic_core::Project;
+use eidetic_core::contracts::{BibleGraphNode, BibleGraphNodeId, CanonicalBibleRoot};
+use eidetic_core::timeline::node::{BeatType, NodeId, StoryLevel};
+use eidetic_core::timeline::timing::format_time;
+use rusqlite::Connection;
+
+use crate::bible_graph_store;
+use crate::context_references::referenced_bible_nodes;
+use crate::history_store::HistoryStoreError;
+
+const HEADER: [&str; 11] = [
+    "index",
+    "scene_number",
+    "name",
+    "level",
+    "start",
+    "end",
+    "duration",
+    "arcs",
+    "beat_type",
+    "characters",
+    "notes",
+];
+
+/// Names of the characters each timeline node references, per
+/// [`referenced_bible_nodes`].
+pub(crate) fn load_characters_present(
+    conn: &Connection,
+    node_ids: &[NodeId],
+) -> Result<HashMap<NodeId, Vec<String>>, HistoryStoreError> {
+    bible_graph_store::create_schema(conn)?;
+    let bible_nodes = bible_graph_store::load_node_list_projection(conn)?.nodes;
+    let by_id: HashMap<&BibleGraphNodeId, &BibleGraphNode> =
+        bible_nodes.iter().map(|node| (&node.id, node)).collect();
+    let characters_root = CanonicalBibleRoot::Characters.node_id();
+
+    Ok(referenced_bible_nodes(conn, node_ids)?
+        .into_iter()
+        .map(|(node_id, entities)| {
+            let names = entities
+                .iter()
+                .filter_map(|id| by_id.get(id))
+                .filter(|bible_node| is_under(bible_node, &characters_root, &by_id))
+                .map(|bible_node| bible_node.name.clone())
+                .collect();
+            (node_id, names)
+        })
+        .collect())
+}
+
+fn is_under(
+    node: &BibleGraphNode,
+    root: &BibleGraphNodeId,
+    by_id: &HashMap<&BibleGraphNodeId, &BibleGraphNode>,
+) -> bool {
+    let mut parent = node.parent_id.as_ref();
+    for _ in 0..by_id.len() {
+        match parent {
+            Some(id) if id == root => return true,
+            Some(id) => parent = by_id.get(id).and_then(|node| node.parent_id.as_ref()),
+            None => return false,
+        }
+    }
+    false
+}
+
+pub(crate) fn render_beat_sheet_csv(
+    project: &Project,
+    level: StoryLevel,
+    characters: &HashMap<NodeId, Vec<String>>,
+) -> String {
+    let mut out = csv_row(HEADER.iter().map(|column| column.to_string()));
+
+    for (index, node) in project
+        .timeline
+        .nodes_at_level(level)
+        .into_iter()
+        .enumerate()
+    {
+        let arcs = project
+            .timeline
+            .arcs_for_node(node.id)
+            .into_iter()
+            .filter_map(|arc_id| project.arcs.iter().find(|arc| arc.id == arc_id))
+            .map(|arc| arc.name.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+        let beat_type = match &node.beat_type {
+            Some(BeatType::Custom(label)) => label.clone(),
+            Some(beat_type) => format!("{beat_type:?}"),
+            None => String::new(),
+        };
+        let characters = characters
+            .get(&node.id)
+            .map(|names| names.join("; "))
+            .unwrap_or_default();
+
+        out.push_str(&csv_row([
+            (index + 1).to_string(),
+            project
+                .timeline
+                .scene_number_for(node.id)
+                .unwrap_or_default()
+                .to_string(),
+            node.name.clone(),
+            node.level.label().to_string(),
+            format_time(node.time_range.start_ms),
+            format_time(node.time_range.end_ms),
+            format_time(node.time_range.duration_ms()),
+            arcs,
+            beat_type,
+            characters,
+            node.content.notes.clone(),
+        ]));
+    }
+
+    out
+}
+
+fn csv_row(fields: impl IntoIterator<Item = String>) -> String {
+    let mut row = fields
+        .into_iter()
+        .map(|field| csv_field(&field))
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+/// Quote a field when it contains a delimiter, quote, or line break (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{csv_field, render_beat_sheet_csv};
+    use eidetic_core::Template;
+    use eidetic_core::timeline::node::StoryLevel;
+    use std::collections::HashMap;
+
+    #[test]
+    fn csv_field_quotes_delimiters_and_doubles_quotes() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn beat_sheet_has_one_row_per_node_at_level() {
+        let mut project = Template::MultiCam.build_project("Beat Sheet Test");
+        let scene = project.timeline.nodes_at_level(StoryLevel::Scene)[0].id;
+        project.timeline.node_mut(scene).unwrap().content.notes = "Opens, loudly".into();
+        project.timeline.node_mut(scene).unwrap().scene_number = Some("1A".into());
+        let characters = HashMap::from([(scene, vec!["Mara".to_string(), "Jonah".to_string()])]);
+
+        let csv = render_beat_sheet_csv(&project, StoryLevel::Scene, &characters);
+        let rows: Vec<&str> = csv.split("\r\n").filter(|row| !row.is_empty()).collect();
+
+        assert_eq!(
+            rows[0],
+            "index,scene_number,name,level,start,end,duration,arcs,beat_type,characters,notes"
+        );
+        assert_eq!(
+            rows.len(),
+            project.timeline.nodes_at_level(StoryLevel::Scene).len() + 1
+        );
+        assert!(rows[1].starts_with("1,1A,A: Setup,Scene,0:00,1:00,1:00,"));
+        assert!(rows[2].starts_with("2,,"));
+        assert!(rows[1].ends_with(",Mara; Jonah,\"Opens, loudly\""));
+    }
+}
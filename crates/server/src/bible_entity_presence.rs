@@ -0,0 +1,140 @@
+//! Bible entity presence: the stretches of the timeline where an entity
+//! appears, for the appearance bar beside each entity in the bible.
+
+use std::collections::HashSet;
+
+use eidetic_core::contracts::{BibleEntityPresenceProjection, BibleGraphNodeId};
+use eidetic_core::timeline::Timeline;
+use eidetic_core::timeline::node::{NodeId, StoryLevel};
+use eidetic_core::timeline::timing::merge_time_ranges;
+use rusqlite::Connection;
+
+use crate::bible_graph_snapshot_store;
+use crate::bible_graph_store;
+use crate::context_references::referenced_bible_nodes;
+use crate::history_store::HistoryStoreError;
+
+/// A node references the entity per [`referenced_bible_nodes`]. `level` limits the
+/// nodes considered, since a referenced act would otherwise cover its whole
+/// span. `None` when the entity does not exist.
+pub(crate) fn load_bible_entity_presence(
+    conn: &Connection,
+    timeline: &Timeline,
+    node_id: &BibleGraphNodeId,
+    level: Option<StoryLevel>,
+) -> Result<Option<BibleEntityPresenceProjection>, HistoryStoreError> {
+    bible_graph_store::create_schema(conn)?;
+    let Some(node) = bible_graph_store::load_node(conn, node_id)? else {
+        return Ok(None);
+    };
+
+    let snapshots: Vec<_> = bible_graph_snapshot_store::load_snapshot_projections(conn, node_id)?
+        .into_iter()
+        .map(|projection| projection.snapshot)
+        .collect();
+    let timeline_node_ids: Vec<NodeId> = timeline.nodes.iter().map(|node| node.id).collect();
+    let referencing: HashSet<NodeId> = referenced_bible_nodes(conn, &timeline_node_ids)?
+        .into_iter()
+        .filter(|(_, entities)| entities.contains(node_id))
+        .map(|(timeline_node_id, _)| timeline_node_id)
+        .collect();
+
+    let intervals = merge_time_ranges(
+        timeline
+            .nodes
+            .iter()
+            .filter(|timeline_node| referencing.contains(&timeline_node.id))
+            .filter(|timeline_node| level.is_none_or(|level| timeline_node.level == level))
+            .map(|timeline_node| timeline_node.time_range),
+    );
+
+    Ok(Some(BibleEntityPresenceProjection {
+        node,
+        intervals,
+        snapshots,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::load_bible_entity_presence;
+    use crate::bible_graph_store;
+    use crate::bible_graph_test_support::{create_node, set_snapshot};
+    use eidetic_core::Template;
+    use eidetic_core::contracts::BibleGraphNodeId;
+    use eidetic_core::timeline::node::StoryLevel;
+    use eidetic_core::timeline::timing::TimeRange;
+    use rusqlite::Connection;
+
+    #[test]
+    fn merges_adjacent_referencing_scenes_and_keeps_gaps() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        bible_graph_store::create_schema(&conn).unwrap();
+        let timeline = Template::MultiCam.build_project("Presence").timeline;
+        let scenes: Vec<_> = timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .iter()
+            .map(|node| (node.id, node.time_range))
+            .collect();
+        create_node(&mut conn, "node.character.mara", "Mara");
+        set_snapshot(
+            &mut conn,
+            "snapshot.b",
+            "node.character.mara",
+            scenes[1].1.start_ms,
+            Some(scenes[1].0),
+        );
+        set_snapshot(
+            &mut conn,
+            "snapshot.a",
+            "node.character.mara",
+            scenes[0].1.start_ms,
+            Some(scenes[0].0),
+        );
+        set_snapshot(
+            &mut conn,
+            "snapshot.d",
+            "node.character.mara",
+            scenes[3].1.start_ms,
+            Some(scenes[3].0),
+        );
+        let mara = BibleGraphNodeId::new("node.character.mara").unwrap();
+
+        let presence = load_bible_entity_presence(&conn, &timeline, &mara, Some(StoryLevel::Scene))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            presence.intervals,
+            vec![
+                TimeRange {
+                    start_ms: scenes[0].1.start_ms,
+                    end_ms: scenes[1].1.end_ms,
+                },
+                scenes[3].1,
+            ]
+        );
+        let labels: Vec<&str> = presence
+            .snapshots
+            .iter()
+            .map(|snapshot| snapshot.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["snapshot.a", "snapshot.b", "snapshot.d"]);
+
+        let acts = load_bible_entity_presence(&conn, &timeline, &mara, Some(StoryLevel::Act))
+            .unwrap()
+            .unwrap();
+        assert!(acts.intervals.is_empty());
+    }
+
+    #[test]
+    fn missing_entity_has_no_presence() {
+        let conn = Connection::open_in_memory().unwrap();
+        let timeline = Template::MultiCam.build_project("Presence").timeline;
+        let missing = BibleGraphNodeId::new("node.character.missing").unwrap();
+
+        let presence = load_bible_entity_presence(&conn, &timeline, &missing, None).unwrap();
+
+        assert_eq!(presence, None);
+    }
+}
@@ -0,0 +1,382 @@
+//! Shareable story bible export.
+//!
+//! Flattens the bible graph into entries grouped by canonical root, with
+//! relations resolved to names and every snapshot listed in time order.
+//! Unlike the prompt context, nothing is resolved against a playhead time.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use eidetic_core::contracts::{
+    BIBLE_GRAPH_NODE_TEXT_FIELD_KEY, BIBLE_GRAPH_NODE_TEXT_PART_KEY, BibleGraphNode,
+    BibleGraphNodeId, BibleNodeDetailProjection, FieldValue,
+};
+use eidetic_core::timeline::timing::format_time;
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::ai_bible_context_prompt::{edge_kind_label, field_value_label};
+use crate::bible_graph_store;
+use crate::history_store::HistoryStoreError;
+
+const UNCATEGORIZED: &str = "Uncategorized";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BibleExport {
+    pub project_name: String,
+    pub categories: Vec<BibleExportCategory>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BibleExportCategory {
+    pub name: String,
+    pub entries: Vec<BibleExportEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BibleExportEntry {
+    pub id: String,
+    pub name: String,
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    pub details: Vec<BibleExportDetail>,
+    pub relations: Vec<BibleExportRelation>,
+    pub snapshots: Vec<BibleExportSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BibleExportDetail {
+    pub part: String,
+    pub field: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BibleExportRelation {
+    /// `"outgoing"` or `"incoming"`.
+    pub direction: &'static str,
+    pub kind: String,
+    pub label: String,
+    pub other_id: String,
+    pub other_name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BibleExportSnapshot {
+    pub at_ms: u64,
+    pub label: String,
+    pub details: Vec<BibleExportDetail>,
+}
+
+pub(crate) fn load_bible_export(
+    conn: &Connection,
+    project_name: &str,
+) -> Result<BibleExport, HistoryStoreError> {
+    let nodes = bible_graph_store::load_node_list_projection(conn)?.nodes;
+    let mut details = Vec::new();
+    for node in nodes.iter().filter(|node| !node.system_owned) {
+        if let Some(detail) = bible_graph_store::load_node_detail_projection(conn, &node.id)? {
+            details.push(detail);
+        }
+    }
+    Ok(build_bible_export(project_name, &nodes, details))
+}
+
+pub(crate) fn build_bible_export(
+    project_name: &str,
+    nodes: &[BibleGraphNode],
+    details: Vec<BibleNodeDetailProjection>,
+) -> BibleExport {
+    let by_id: HashMap<&BibleGraphNodeId, &BibleGraphNode> =
+        nodes.iter().map(|node| (&node.id, node)).collect();
+    let name_of = |id: &BibleGraphNodeId| {
+        by_id
+            .get(id)
+            .map(|node| node.name.clone())
+            .unwrap_or_else(|| id.as_str().to_string())
+    };
+
+    let mut categories: Vec<BibleExportCategory> = nodes
+        .iter()
+        .filter(|node| node.system_owned && node.parent_id.is_none())
+        .map(|root| BibleExportCategory {
+            name: root.name.clone(),
+            entries: Vec::new(),
+        })
+        .collect();
+
+    for detail in details {
+        let category = root_name(&detail.node, &by_id)
+            .unwrap_or(UNCATEGORIZED)
+            .to_string();
+        let entry = export_entry(detail, &name_of);
+        match categories.iter_mut().find(|c| c.name == category) {
+            Some(existing) => existing.entries.push(entry),
+            None => categories.push(BibleExportCategory {
+                name: category,
+                entries: vec![entry],
+            }),
+        }
+    }
+    categories.retain(|category| !category.entries.is_empty());
+
+    BibleExport {
+        project_name: project_name.to_string(),
+        categories,
+    }
+}
+
+/// Name of the top-level ancestor, guarding against parent cycles.
+fn root_name<'a>(
+    node: &'a BibleGraphNode,
+    by_id: &HashMap<&BibleGraphNodeId, &'a BibleGraphNode>,
+) -> Option<&'a str> {
+    let mut current = node;
+    for _ in 0..by_id.len() {
+        match current.parent_id.as_ref() {
+            Some(parent_id) => current = by_id.get(parent_id)?,
+            None => return (current.id != node.id).then_some(current.name.as_str()),
+        }
+    }
+    None
+}
+
+fn export_entry(
+    detail: BibleNodeDetailProjection,
+    name_of: &impl Fn(&BibleGraphNodeId) -> String,
+) -> BibleExportEntry {
+    let mut text = None;
+    let mut details = Vec::new();
+    for part in &detail.parts {
+        for field in &part.fields {
+            let Some(value) = field.value.as_ref().and_then(non_empty_label) else {
+                continue;
+            };
+            if part.part.part_key.as_str() == BIBLE_GRAPH_NODE_TEXT_PART_KEY
+                && field.field_key.as_str() == BIBLE_GRAPH_NODE_TEXT_FIELD_KEY
+            {
+                text = Some(value);
+            } else {
+                details.push(BibleExportDetail {
+                    part: part.part.name.clone(),
+                    field: field.field_key.as_str().to_string(),
+                    value,
+                });
+            }
+        }
+    }
+
+    let relations = detail
+        .outgoing_edges
+        .iter()
+        .map(|edge| ("outgoing", edge, &edge.to_node_id))
+        .chain(
+            detail
+                .incoming_edges
+                .iter()
+                .map(|edge| ("incoming", edge, &edge.from_node_id)),
+        )
+        .map(|(direction, edge, other)| BibleExportRelation {
+            direction,
+            kind: edge_kind_label(&edge.edge_kind),
+            label: edge.label.clone(),
+            other_id: other.as_str().to_string(),
+            other_name: name_of(other),
+        })
+        .collect();
+
+    let mut snapshots: Vec<BibleExportSnapshot> = detail
+        .snapshots
+        .iter()
+        .map(|snapshot| BibleExportSnapshot {
+            at_ms: snapshot.snapshot.at_ms,
+            label: snapshot.snapshot.label.clone(),
+            details: snapshot
+                .fields
+                .iter()
+                .filter_map(|field| {
+                    Some(BibleExportDetail {
+                        part: field.part_name.clone(),
+                        field: field.field_key.as_str().to_string(),
+                        value: field.value.as_ref().and_then(non_empty_label)?,
+                    })
+                })
+                .collect(),
+        })
+        .collect();
+    snapshots.sort_by_key(|snapshot| snapshot.at_ms);
+
+    BibleExportEntry {
+        id: detail.node.id.as_str().to_string(),
+        name: detail.node.name,
+        kind: detail.node.schema_key.as_str().to_string(),
+        text,
+        details,
+        relations,
+        snapshots,
+    }
+}
+
+fn non_empty_label(value: &FieldValue) -> Option<String> {
+    let label = field_value_label(value);
+    (!label.trim().is_empty()).then_some(label)
+}
+
+pub(crate) fn render_bible_markdown(export: &BibleExport) -> String {
+    let mut out = format!("# {} — Story Bible\n", export.project_name);
+
+    for category in &export.categories {
+        let _ = write!(out, "\n## {}\n", category.name);
+        for entry in &category.entries {
+            let _ = write!(out, "\n### {}\n\n_{}_\n", entry.name, entry.kind);
+            if let Some(text) = &entry.text {
+                let _ = write!(out, "\n{}\n", text.trim());
+            }
+            if !entry.details.is_empty() {
+                out.push('\n');
+                for detail in &entry.details {
+                    push_detail(&mut out, "", detail);
+                }
+            }
+            if !entry.relations.is_empty() {
+                out.push_str("\n**Relations**\n\n");
+                for relation in &entry.relations {
+                    let arrow = if relation.direction == "outgoing" {
+                        "→"
+                    } else {
+                        "←"
+                    };
+                    let _ = write!(out, "- {arrow} {} ({})", relation.other_name, relation.kind);
+                    if !relation.label.is_empty() {
+                        let _ = write!(out, ": {}", relation.label);
+                    }
+                    out.push('\n');
+                }
+            }
+            if !entry.snapshots.is_empty() {
+                out.push_str("\n**Timeline**\n\n");
+                for snapshot in &entry.snapshots {
+                    let _ = writeln!(
+                        out,
+                        "- {} — {}",
+                        format_time(snapshot.at_ms),
+                        snapshot.label
+                    );
+                    for detail in &snapshot.details {
+                        push_detail(&mut out, "  ", detail);
+                    }
+                }
+            }
+        }
+    }
+
+    out
+}
+
+fn push_detail(out: &mut String, indent: &str, detail: &BibleExportDetail) {
+    let _ = writeln!(
+        out,
+        "{indent}- **{} / {}**: {}",
+        detail.part, detail.field, detail.value
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_bible_export, render_bible_markdown};
+    use eidetic_core::contracts::{
+        BibleGraphEdge, BibleGraphEdgeId, BibleGraphEdgeKind, BibleGraphField, BibleGraphFieldId,
+        BibleGraphFieldKey, BibleGraphNode, BibleGraphNodeId, BibleGraphPart, BibleGraphPartId,
+        BibleGraphPartKey, BibleGraphPartProjection, BibleGraphSchemaKey, BibleGraphSnapshot,
+        BibleGraphSnapshotId, BibleGraphSnapshotProjection, BibleNodeDetailProjection,
+        CanonicalBibleRoot, FieldValue, canonical_bible_root_nodes,
+    };
+
+    fn character(id: &str, name: &str) -> BibleGraphNode {
+        BibleGraphNode {
+            id: BibleGraphNodeId::new(id).unwrap(),
+            parent_id: Some(CanonicalBibleRoot::Characters.node_id()),
+            schema_key: BibleGraphSchemaKey::new("character").unwrap(),
+            name: name.to_string(),
+            system_owned: false,
+            sort_order: 0,
+        }
+    }
+
+    #[test]
+    fn bible_export_groups_by_root_and_resolves_relations() {
+        let mara = character("node.character.mara", "Mara");
+        let jonah = character("node.character.jonah", "Jonah");
+        let mut nodes = canonical_bible_root_nodes();
+        nodes.push(mara.clone());
+        nodes.push(jonah.clone());
+        let detail = BibleNodeDetailProjection {
+            node: mara.clone(),
+            parts: vec![BibleGraphPartProjection {
+                part: BibleGraphPart {
+                    id: BibleGraphPartId::new("part.mara.content").unwrap(),
+                    node_id: mara.id.clone(),
+                    part_key: BibleGraphPartKey::new("content").unwrap(),
+                    name: "Content".to_string(),
+                    system_owned: true,
+                    sort_order: 0,
+                },
+                fields: vec![BibleGraphField {
+                    id: BibleGraphFieldId::new("field.mara.content.text").unwrap(),
+                    part_id: BibleGraphPartId::new("part.mara.content").unwrap(),
+                    field_key: BibleGraphFieldKey::new("text").unwrap(),
+                    value: Some(FieldValue::Text("A lighthouse keeper.".to_string())),
+                    sort_order: 0,
+                }],
+            }],
+            incoming_edges: Vec::new(),
+            outgoing_edges: vec![BibleGraphEdge {
+                id: BibleGraphEdgeId::new("edge.mara.jonah").unwrap(),
+                from_node_id: mara.id.clone(),
+                to_node_id: jonah.id.clone(),
+                edge_kind: BibleGraphEdgeKind::ConflictsWith,
+                label: "old rivals".to_string(),
+                directed: true,
+                sort_order: 0,
+            }],
+            snapshots: vec![
+                BibleGraphSnapshotProjection {
+                    snapshot: BibleGraphSnapshot {
+                        id: BibleGraphSnapshotId::new("snapshot.mara.late").unwrap(),
+                        node_id: mara.id.clone(),
+                        at_ms: 600_000,
+                        label: "Leaves the island".to_string(),
+                        sort_order: 0,
+                        source_node_id: None,
+                    },
+                    fields: Vec::new(),
+                },
+                BibleGraphSnapshotProjection {
+                    snapshot: BibleGraphSnapshot {
+                        id: BibleGraphSnapshotId::new("snapshot.mara.early").unwrap(),
+                        node_id: mara.id.clone(),
+                        at_ms: 90_000,
+                        label: "Meets Jonah".to_string(),
+                        sort_order: 1,
+                        source_node_id: None,
+                    },
+                    fields: Vec::new(),
+                },
+            ],
+        };
+
+        let export = build_bible_export("Pilot", &nodes, vec![detail]);
+        let markdown = render_bible_markdown(&export);
+
+        assert_eq!(export.categories.len(), 1);
+        assert_eq!(export.categories[0].name, "Characters");
+        let entry = &export.categories[0].entries[0];
+        assert_eq!(entry.text.as_deref(), Some("A lighthouse keeper."));
+        assert_eq!(entry.relations[0].other_name, "Jonah");
+        assert_eq!(entry.snapshots[0].label, "Meets Jonah");
+        assert!(markdown.contains("## Characters"));
+        assert!(markdown.contains("- → Jonah (conflicts_with): old rivals"));
+        assert!(markdown.contains("- 1:30 — Meets Jonah"));
+    }
+}
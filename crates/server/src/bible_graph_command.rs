@@ -728,7 +728,7 @@ pub(crate) fn validate_snapshot_field_schema(
     Ok(())
 }
 
-fn node_revision(
+pub(crate) fn node_revision(
     node: &BibleGraphNode,
     event_id: eidetic_core::contracts::ChangeEventId,
 ) -> ObjectRevision {
@@ -768,7 +768,7 @@ fn node_revision(
     revision
 }
 
-fn delete_node_revision(
+pub(crate) fn delete_node_revision(
     node: &BibleGraphNode,
     event_id: eidetic_core::contracts::ChangeEventId,
 ) -> ObjectRevision {
@@ -824,7 +824,7 @@ pub(crate) fn snapshot_revision(
     } else {
         RevisionOperation::Create
     };
-    ObjectRevision::new(
+    let revision = ObjectRevision::new(
         ObjectKind::BibleSnapshot,
         command.snapshot_id.as_str(),
         event_id,
@@ -858,10 +858,21 @@ pub(crate) fn snapshot_revision(
         ),
         old_value,
         command.value.clone(),
-    ))
+    ));
+    match command.source_node_id {
+        Some(source_node_id) => revision.with_field(FieldDelta::new(
+            "source_node_id",
+            None,
+            Some(FieldValue::ObjectRef {
+                kind: ObjectKind::TimelineNode,
+                id: source_node_id.0.to_string(),
+            }),
+        )),
+        None => revision,
+    }
 }
 
-fn edge_revision(
+pub(crate) fn edge_revision(
     edge: &BibleGraphEdge,
     before: Option<&BibleGraphEdge>,
     event_id: eidetic_core::contracts::ChangeEventId,
@@ -916,7 +927,7 @@ fn edge_revision(
         ))
 }
 
-fn delete_edge_revision(
+pub(crate) fn delete_edge_revision(
     edge: &BibleGraphEdge,
     event_id: eidetic_core::contracts::ChangeEventId,
 ) -> ObjectRevision {
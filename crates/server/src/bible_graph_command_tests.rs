@@ -79,6 +79,7 @@ fn snapshot_field_command(
         at_ms: 12_000,
         label: "Sequence 1 state".to_string(),
         snapshot_sort_order: 1,
+        source_node_id: None,
         field_id: BibleGraphSnapshotFieldId::new("snapshot-field.character.status").unwrap(),
         part_key: BibleGraphPartKey::new("profile").unwrap(),
         part_name: "Profile".to_string(),
@@ -641,6 +642,65 @@ fn set_bible_graph_snapshot_field_creates_snapshot_and_updates_projection() {
     assert_eq!(table_count(&conn, "bible_graph_snapshot_fields"), 1);
 }
 
+#[test]
+fn timeline_range_change_reanchors_snapshots_only_when_requested() {
+    let mut conn = memory_connection();
+    crate::timeline_node_store::create_node_schema(&conn).unwrap();
+    let project = eidetic_core::Template::MultiCam.build_project("Reanchor Test");
+    let scene = project
+        .timeline
+        .nodes_at_level(eidetic_core::timeline::node::StoryLevel::Scene)[0]
+        .clone();
+    apply_create_bible_graph_node(&mut conn, &create_command("node.character.ada", "Ada"), 100)
+        .unwrap();
+    let mut snapshot = snapshot_field_command(Some(FieldValue::Text("Rain-soaked".to_string())));
+    snapshot.payload.source_node_id = Some(scene.id);
+    apply_set_bible_graph_snapshot_field(&mut conn, &snapshot, 200).unwrap();
+    let range_command = |start_ms, end_ms, reanchor_snapshots| {
+        CommandEnvelope::new(eidetic_core::contracts::SetTimelineNodeRangeCommand {
+            node_id: scene.id,
+            start_ms,
+            end_ms,
+            reanchor_snapshots,
+        })
+    };
+    let snapshot_at_ms = |conn: &Connection| {
+        crate::bible_graph_snapshot_store::load_snapshot_projections(
+            conn,
+            &BibleGraphNodeId::new("node.character.ada").unwrap(),
+        )
+        .unwrap()[0]
+            .snapshot
+            .at_ms
+    };
+
+    crate::timeline_command::record_set_timeline_node_range_history(
+        &mut conn,
+        &project,
+        &range_command(10_000, 50_000, false),
+        300,
+    )
+    .unwrap();
+    assert_eq!(snapshot_at_ms(&conn), 12_000);
+
+    crate::timeline_command::record_set_timeline_node_range_history(
+        &mut conn,
+        &project,
+        &range_command(30_000, 90_000, true),
+        400,
+    )
+    .unwrap();
+    assert_eq!(snapshot_at_ms(&conn), 60_000);
+    let reanchored_revisions: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM object_revisions WHERE object_kind = 'bible_snapshot'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(reanchored_revisions, 2);
+}
+
 #[test]
 fn duplicate_set_snapshot_field_command_is_idempotent() {
     let mut conn = memory_connection();
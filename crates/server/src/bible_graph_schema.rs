@@ -74,6 +74,7 @@ CREATE TABLE IF NOT EXISTS bible_graph_snapshots (
     at_ms            INTEGER NOT NULL,
     label            TEXT NOT NULL CHECK (label <> ''),
     sort_order       INTEGER NOT NULL,
+    source_node_id   TEXT,
     created_event_id TEXT NOT NULL REFERENCES change_events(id),
     updated_event_id TEXT NOT NULL REFERENCES change_events(id),
     deleted_event_id TEXT REFERENCES change_events(id)
@@ -106,5 +107,24 @@ CREATE INDEX IF NOT EXISTS idx_bible_graph_snapshot_fields_snapshot
 pub(crate) fn create_schema(conn: &Connection) -> Result<(), HistoryStoreError> {
     history_store::create_schema(conn)?;
     conn.execute_batch(BIBLE_GRAPH_SCHEMA_SQL)?;
+    if !snapshots_have_source_node_column(conn)? {
+        conn.execute(
+            "ALTER TABLE bible_graph_snapshots ADD COLUMN source_node_id TEXT",
+            [],
+        )?;
+    }
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_bible_graph_snapshots_source_node
+            ON bible_graph_snapshots(source_node_id)",
+    )?;
     Ok(())
 }
+
+/// Whether `bible_graph_snapshots` has the `source_node_id` column; older
+/// read-only databases may predate it.
+pub(crate) fn snapshots_have_source_node_column(conn: &Connection) -> rusqlite::Result<bool> {
+    conn.prepare(
+        "SELECT 1 FROM pragma_table_info('bible_graph_snapshots') WHERE name = 'source_node_id'",
+    )?
+    .exists([])
+}
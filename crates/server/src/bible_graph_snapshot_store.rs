@@ -3,8 +3,11 @@ use eidetic_core::contracts::{
     BibleGraphSnapshotField, BibleGraphSnapshotFieldId, BibleGraphSnapshotId,
     BibleGraphSnapshotProjection, ChangeEventId, SetBibleGraphSnapshotFieldCommand,
 };
+use eidetic_core::timeline::node::NodeId;
 use rusqlite::{Connection, OptionalExtension, Row, Transaction, params};
+use uuid::Uuid;
 
+use crate::bible_graph_schema::snapshots_have_source_node_column;
 use crate::bible_graph_value_store::SqlGraphFieldValue;
 use crate::history_store::HistoryStoreError;
 
@@ -28,12 +31,17 @@ pub(crate) fn load_snapshot_projections(
     conn: &Connection,
     node_id: &BibleGraphNodeId,
 ) -> Result<Vec<BibleGraphSnapshotProjection>, HistoryStoreError> {
-    let mut statement = conn.prepare(
-        "SELECT id, node_id, at_ms, label, sort_order
+    let source_column = if snapshots_have_source_node_column(conn)? {
+        "source_node_id"
+    } else {
+        "NULL"
+    };
+    let mut statement = conn.prepare(&format!(
+        "SELECT id, node_id, at_ms, label, sort_order, {source_column}
          FROM bible_graph_snapshots
          WHERE node_id = ?1 AND deleted_event_id IS NULL
-         ORDER BY at_ms ASC, sort_order ASC, label ASC, id ASC",
-    )?;
+         ORDER BY at_ms ASC, sort_order ASC, label ASC, id ASC"
+    ))?;
     let rows = statement.query_map([node_id.as_str()], row_to_snapshot)?;
 
     let mut snapshots = Vec::new();
@@ -45,6 +53,48 @@ pub(crate) fn load_snapshot_projections(
     Ok(snapshots)
 }
 
+/// Live snapshots anchored to any of the given timeline nodes, ordered by
+/// owning entity and time.
+pub(crate) fn load_snapshots_for_source_nodes(
+    conn: &Connection,
+    source_node_ids: &[NodeId],
+) -> Result<Vec<BibleGraphSnapshot>, HistoryStoreError> {
+    let mut statement = conn.prepare(
+        "SELECT id, node_id, at_ms, label, sort_order, source_node_id
+         FROM bible_graph_snapshots
+         WHERE source_node_id = ?1 AND deleted_event_id IS NULL
+         ORDER BY node_id ASC, at_ms ASC, sort_order ASC, id ASC",
+    )?;
+
+    let mut snapshots = Vec::new();
+    for source_node_id in source_node_ids {
+        let rows = statement.query_map([source_node_id.0.to_string()], row_to_snapshot)?;
+        for row in rows {
+            snapshots.push(row?);
+        }
+    }
+    Ok(snapshots)
+}
+
+pub(crate) fn set_snapshot_time_in_transaction(
+    tx: &Transaction<'_>,
+    snapshot_id: &BibleGraphSnapshotId,
+    at_ms: u64,
+    event_id: ChangeEventId,
+) -> Result<(), HistoryStoreError> {
+    tx.execute(
+        "UPDATE bible_graph_snapshots
+         SET at_ms = ?2, updated_event_id = ?3
+         WHERE id = ?1 AND deleted_event_id IS NULL",
+        params![
+            snapshot_id.as_str(),
+            to_i64(at_ms, "at_ms")?,
+            event_id.0.to_string(),
+        ],
+    )?;
+    Ok(())
+}
+
 fn node_exists_in_transaction(
     tx: &Transaction<'_>,
     node_id: &BibleGraphNodeId,
@@ -64,6 +114,7 @@ fn upsert_snapshot_in_transaction(
     command: &SetBibleGraphSnapshotFieldCommand,
     event_id: ChangeEventId,
 ) -> Result<(), HistoryStoreError> {
+    let source_node_id = command.source_node_id.map(|node_id| node_id.0.to_string());
     let existing_node_id = tx
         .query_row(
             "SELECT node_id FROM bible_graph_snapshots WHERE id = ?1 AND deleted_event_id IS NULL",
@@ -83,13 +134,15 @@ fn upsert_snapshot_in_transaction(
 
         tx.execute(
             "UPDATE bible_graph_snapshots
-             SET at_ms = ?2, label = ?3, sort_order = ?4, updated_event_id = ?5
+             SET at_ms = ?2, label = ?3, sort_order = ?4, source_node_id = ?5,
+                 updated_event_id = ?6
              WHERE id = ?1",
             params![
                 command.snapshot_id.as_str(),
                 to_i64(command.at_ms, "at_ms")?,
                 command.label,
                 command.snapshot_sort_order as i64,
+                source_node_id,
                 event_id.0.to_string(),
             ],
         )?;
@@ -98,14 +151,16 @@ fn upsert_snapshot_in_transaction(
 
     tx.execute(
         "INSERT INTO bible_graph_snapshots (
-            id, node_id, at_ms, label, sort_order, created_event_id, updated_event_id
-         ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+            id, node_id, at_ms, label, sort_order, source_node_id,
+            created_event_id, updated_event_id
+         ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
         params![
             command.snapshot_id.as_str(),
             command.node_id.as_str(),
             to_i64(command.at_ms, "at_ms")?,
             command.label,
             command.snapshot_sort_order as i64,
+            source_node_id,
             event_id.0.to_string(),
         ],
     )?;
@@ -230,6 +285,11 @@ fn row_to_snapshot(row: &Row<'_>) -> Result<BibleGraphSnapshot, rusqlite::Error>
     let node_id: String = row.get(1)?;
     let at_ms: i64 = row.get(2)?;
     let sort_order: i64 = row.get(4)?;
+    let source_node_id: Option<String> = row.get(5)?;
+    let source_node_id = source_node_id
+        .map(|value| Uuid::parse_str(&value).map(NodeId))
+        .transpose()
+        .map_err(|e| conversion_failure(row, 5, e))?;
 
     Ok(BibleGraphSnapshot {
         id: BibleGraphSnapshotId::new(id).map_err(|e| conversion_failure(row, 0, e))?,
@@ -237,6 +297,7 @@ fn row_to_snapshot(row: &Row<'_>) -> Result<BibleGraphSnapshot, rusqlite::Error>
         at_ms: u64::try_from(at_ms).map_err(|e| conversion_failure(row, 2, e))?,
         label: row.get(3)?,
         sort_order: u32::try_from(sort_order).map_err(|e| conversion_failure(row, 4, e))?,
+        source_node_id,
     })
 }
 
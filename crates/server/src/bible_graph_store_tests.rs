@@ -268,6 +268,7 @@ fn node_detail_projection_includes_snapshots_and_fields() {
         at_ms: 12_000,
         label: "Sequence 1 state".to_string(),
         snapshot_sort_order: 1,
+        source_node_id: None,
         field_id: BibleGraphSnapshotFieldId::new("snapshot-field.beach.weather.current").unwrap(),
         part_key: BibleGraphPartKey::new("weather").unwrap(),
         part_name: "Weather".to_string(),
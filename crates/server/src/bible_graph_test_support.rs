@@ -0,0 +1,50 @@
+//! Bible graph builders shared by store and projection tests.
+
+use eidetic_core::contracts::{
+    BibleGraphFieldKey, BibleGraphNodeId, BibleGraphPartKey, BibleGraphSchemaKey,
+    BibleGraphSnapshotFieldId, BibleGraphSnapshotId, CommandEnvelope, CreateBibleGraphNodeCommand,
+    FieldValue, SetBibleGraphSnapshotFieldCommand,
+};
+use eidetic_core::timeline::node::NodeId;
+use rusqlite::Connection;
+
+use crate::bible_graph_command::{
+    apply_create_bible_graph_node, apply_set_bible_graph_snapshot_field,
+};
+
+/// Create a top-level character node.
+pub(crate) fn create_node(conn: &mut Connection, node_id: &str, name: &str) {
+    let command = CommandEnvelope::new(CreateBibleGraphNodeCommand {
+        node_id: BibleGraphNodeId::new(node_id).unwrap(),
+        parent_id: None,
+        schema_key: BibleGraphSchemaKey::new("character").unwrap(),
+        name: name.to_string(),
+        sort_order: 0,
+    });
+    apply_create_bible_graph_node(conn, &command, 100).unwrap();
+}
+
+/// Set a tagline on `node_id`'s snapshot at `at_ms`, creating the snapshot.
+pub(crate) fn set_snapshot(
+    conn: &mut Connection,
+    snapshot_id: &str,
+    node_id: &str,
+    at_ms: u64,
+    source_node_id: Option<NodeId>,
+) {
+    let command = CommandEnvelope::new(SetBibleGraphSnapshotFieldCommand {
+        snapshot_id: BibleGraphSnapshotId::new(snapshot_id).unwrap(),
+        node_id: BibleGraphNodeId::new(node_id).unwrap(),
+        at_ms,
+        label: snapshot_id.to_string(),
+        snapshot_sort_order: 0,
+        source_node_id,
+        field_id: BibleGraphSnapshotFieldId::new(format!("{snapshot_id}.status")).unwrap(),
+        part_key: BibleGraphPartKey::new("profile").unwrap(),
+        part_name: "Profile".to_string(),
+        field_key: BibleGraphFieldKey::new("tagline").unwrap(),
+        value: Some(FieldValue::Text("changed".to_string())),
+        field_sort_order: 0,
+    });
+    apply_set_bible_graph_snapshot_field(conn, &command, 200).unwrap();
+}
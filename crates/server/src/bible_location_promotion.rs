@@ -0,0 +1,542 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use eidetic_core::Project;
+use eidetic_core::contracts::{
+    BibleGraphNodeCategory, BibleGraphNodeId, BibleGraphSchemaKey, CanonicalBibleRoot,
+    CommandEnvelope, CommandId, CreateBibleGraphNodeCommand, EnsureCanonicalBibleRootsCommand,
+    FieldValue, RecordSemanticDependencyCommand, SemanticDependency, SemanticDependencyEndpoint,
+    SemanticDependencyId, SemanticDependencyKind, SetBibleGraphFieldCommand,
+};
+use eidetic_core::timeline::node::NodeId;
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::ai_service::active_sqlite_project;
+use crate::backend_error::BackendError;
+use crate::bible_graph_command;
+use crate::command_service_bible::map_bible_graph_error;
+use crate::command_service_support::{derived_command_uuid, map_history_error};
+use crate::projection_service_semantic_dependency::map_semantic_dependency_error;
+use crate::semantic_dependency_store::{
+    self, DependencyDirection, DependencyEndpointFilter, SemanticDependencyFilter,
+};
+use crate::state::{AppState, ServerEvent};
+
+const LOCATION_SCHEMA_KEY: &str = "location";
+const ENVIRONMENT_PART_KEY: &str = "environment";
+const DESCRIPTION_FIELD_KEY: &str = "description";
+const SNAPSHOT_LOCATION_FIELD_KEY: &str = "location";
+
+/// Trailing heading segments that name a time of day rather than a place.
+const TIMES_OF_DAY: &[&str] = &[
+    "DAY",
+    "NIGHT",
+    "MORNING",
+    "AFTERNOON",
+    "EVENING",
+    "DAWN",
+    "DUSK",
+    "SUNRISE",
+    "SUNSET",
+    "CONTINUOUS",
+    "LATER",
+    "MOMENTS LATER",
+    "SAME",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PromotedLocation {
+    pub node_id: BibleGraphNodeId,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PromoteLocationsResponse {
+    /// Location entities created for settings the bible did not know yet.
+    pub created: Vec<PromotedLocation>,
+    /// Timeline-node mentions recorded against new or existing locations.
+    pub linked_count: usize,
+}
+
+/// Whether a scene heading places the action inside, outside, or both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Setting {
+    Interior,
+    Exterior,
+    InteriorExterior,
+}
+
+impl Setting {
+    fn merge(self, other: Self) -> Self {
+        if self == other {
+            self
+        } else {
+            Self::InteriorExterior
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Interior => "Interior.",
+            Self::Exterior => "Exterior.",
+            Self::InteriorExterior => "Interior and exterior.",
+        }
+    }
+}
+
+/// A parsed `INT./EXT.` scene heading.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SceneHeading {
+    pub setting: Setting,
+    pub location: String,
+}
+
+/// A place named somewhere in the project, with the timeline nodes that
+/// reference it and how.
+#[derive(Debug, Default)]
+struct LocationCandidate {
+    name: String,
+    setting: Option<Setting>,
+    mentions: Vec<(NodeId, String)>,
+}
+
+/// Create Location entities for every place named by a snapshot `location`
+/// override or a scene heading that the bible does not have yet, and record
+/// a `mentions` dependency from each referencing timeline node.
+pub async fn promote_locations(state: &AppState) -> Result<PromoteLocationsResponse, BackendError> {
+    let (project, path) = active_sqlite_project(state).await?;
+    let response = tokio::task::spawn_blocking(move || promote_locations_at_path(path, &project))
+        .await
+        .map_err(|error| {
+            BackendError::internal(format!("location promotion task failed: {error}"))
+        })??;
+
+    if !response.created.is_empty() || response.linked_count > 0 {
+        let _ = state.events_tx.send(ServerEvent::BibleChanged);
+    }
+    Ok(response)
+}
+
+fn promote_locations_at_path(
+    path: PathBuf,
+    project: &Project,
+) -> Result<PromoteLocationsResponse, BackendError> {
+    let mut conn = crate::sqlite::open_write_connection(&path)
+        .map_err(|e| BackendError::internal(e.to_string()))?;
+    promote_locations_in(&mut conn, project, now_ms())
+}
+
+fn promote_locations_in(
+    conn: &mut Connection,
+    project: &Project,
+    created_at_ms: u64,
+) -> Result<PromoteLocationsResponse, BackendError> {
+    bible_graph_command::apply_ensure_canonical_bible_roots(
+        conn,
+        &CommandEnvelope::new(EnsureCanonicalBibleRootsCommand {}),
+        created_at_ms,
+    )
+    .map_err(map_bible_graph_error)?;
+
+    let mut candidates = BTreeMap::<String, LocationCandidate>::new();
+    collect_snapshot_locations(conn, &mut candidates)?;
+    collect_scene_headings(project, &mut candidates);
+
+    let mut known = known_locations(conn)?;
+    let mut sort_order =
+        crate::bible_graph_store::active_child_count(conn, &CanonicalBibleRoot::Places.node_id())
+            .map_err(map_history_error)?;
+    let mut response = PromoteLocationsResponse {
+        created: Vec::new(),
+        linked_count: 0,
+    };
+
+    for (key, candidate) in candidates {
+        let location_id = match known.get(&key) {
+            Some(location_id) => location_id.clone(),
+            None => {
+                let location_id = create_location(
+                    conn,
+                    &candidate,
+                    sort_order.try_into().unwrap_or(u32::MAX),
+                    created_at_ms,
+                )?;
+                sort_order += 1;
+                known.insert(key, location_id.clone());
+                response.created.push(PromotedLocation {
+                    node_id: location_id.clone(),
+                    name: candidate.name.clone(),
+                });
+                location_id
+            }
+        };
+        for (node_id, rationale) in candidate.mentions {
+            if link_mention(conn, node_id, &location_id, rationale, created_at_ms)? {
+                response.linked_count += 1;
+            }
+        }
+    }
+
+    Ok(response)
+}
+
+/// Parse a screenplay scene heading such as `INT. LIGHTHOUSE - NIGHT`.
+/// A trailing time-of-day segment is dropped from the location name.
+pub(crate) fn parse_scene_heading(line: &str) -> Option<SceneHeading> {
+    let line = line.trim();
+    let upper = line.to_uppercase();
+    let (setting, prefix_len) = [
+        ("INT./EXT.", Setting::InteriorExterior),
+        ("INT/EXT.", Setting::InteriorExterior),
+        ("EXT./INT.", Setting::InteriorExterior),
+        ("I/E.", Setting::InteriorExterior),
+        ("INT.", Setting::Interior),
+        ("EXT.", Setting::Exterior),
+    ]
+    .into_iter()
+    .find(|(prefix, _)| upper.starts_with(prefix))
+    .map(|(prefix, setting)| (setting, prefix.len()))?;
+
+    let mut segments: Vec<&str> = line[prefix_len..].split(" - ").map(str::trim).collect();
+    if segments.len() > 1
+        && segments
+            .last()
+            .is_some_and(|last| TIMES_OF_DAY.contains(&last.to_uppercase().as_str()))
+    {
+        segments.pop();
+    }
+    let location = segments.join(" - ");
+    let location = location.trim_end_matches(['.', ',', ':']).trim();
+    if location.is_empty() {
+        return None;
+    }
+
+    Some(SceneHeading {
+        setting,
+        location: display_name(location),
+    })
+}
+
+/// Headings are conventionally upper case; store them title-cased so they
+/// read like the rest of the bible.
+fn display_name(name: &str) -> String {
+    if name.chars().any(char::is_lowercase) {
+        return name.to_string();
+    }
+    name.split(' ')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_string() + &chars.as_str().to_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn location_key(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    let name = name.strip_prefix("the ").unwrap_or(&name);
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn candidate_for<'a>(
+    candidates: &'a mut BTreeMap<String, LocationCandidate>,
+    name: &str,
+) -> Option<&'a mut LocationCandidate> {
+    let key = location_key(name);
+    if key.is_empty() {
+        return None;
+    }
+    Some(candidates.entry(key).or_insert_with(|| LocationCandidate {
+        name: name.trim().to_string(),
+        ..LocationCandidate::default()
+    }))
+}
+
+fn collect_snapshot_locations(
+    conn: &Connection,
+    candidates: &mut BTreeMap<String, LocationCandidate>,
+) -> Result<(), BackendError> {
+    for node in crate::bible_graph_store::load_node_list_projection(conn)
+        .map_err(map_history_error)?
+        .nodes
+    {
+        if node.system_owned {
+            continue;
+        }
+        let snapshots =
+            crate::bible_graph_snapshot_store::load_snapshot_projections(conn, &node.id)
+                .map_err(map_history_error)?;
+        for projection in snapshots {
+            for field in &projection.fields {
+                if field.field_key.as_str() != SNAPSHOT_LOCATION_FIELD_KEY {
+                    continue;
+                }
+                let Some(FieldValue::Text(name)) = &field.value else {
+                    continue;
+                };
+                let Some(candidate) = candidate_for(candidates, name) else {
+                    continue;
+                };
+                if let Some(node_id) = projection.snapshot.source_node_id {
+                    candidate.mentions.push((
+                        node_id,
+                        format!(
+                            "{} is here in snapshot \"{}\"",
+                            node.name, projection.snapshot.label
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn collect_scene_headings(project: &Project, candidates: &mut BTreeMap<String, LocationCandidate>) {
+    for node in &project.timeline.nodes {
+        for line in node.content.content.lines() {
+            let Some(heading) = parse_scene_heading(line) else {
+                continue;
+            };
+            let Some(candidate) = candidate_for(candidates, &heading.location) else {
+                continue;
+            };
+            candidate.setting = Some(match candidate.setting {
+                Some(setting) => setting.merge(heading.setting),
+                None => heading.setting,
+            });
+            candidate
+                .mentions
+                .push((node.id, format!("Scene heading: {}", line.trim())));
+        }
+    }
+}
+
+fn known_locations(conn: &Connection) -> Result<BTreeMap<String, BibleGraphNodeId>, BackendError> {
+    Ok(crate::bible_graph_store::load_node_list_projection(conn)
+        .map_err(map_history_error)?
+        .nodes
+        .into_iter()
+        .filter(|node| {
+            !node.system_owned
+                && BibleGraphNodeCategory::for_node(node) == BibleGraphNodeCategory::Location
+        })
+        .map(|node| (location_key(&node.name), node.id))
+        .collect())
+}
+
+fn create_location(
+    conn: &mut Connection,
+    candidate: &LocationCandidate,
+    sort_order: u32,
+    created_at_ms: u64,
+) -> Result<BibleGraphNodeId, BackendError> {
+    let command_id = CommandId::new();
+    let node_id = BibleGraphNodeId::new(format!(
+        "node.{LOCATION_SCHEMA_KEY}.{}",
+        derived_command_uuid(command_id, b"bible.node")
+    ))
+    .map_err(|error| BackendError::bad_request(error.to_string()))?;
+    let command = CommandEnvelope {
+        id: command_id,
+        payload: CreateBibleGraphNodeCommand {
+            node_id: node_id.clone(),
+            parent_id: Some(CanonicalBibleRoot::Places.node_id()),
+            schema_key: BibleGraphSchemaKey::new(LOCATION_SCHEMA_KEY)
+                .map_err(|error| BackendError::bad_request(error.to_string()))?,
+            name: candidate.name.clone(),
+            sort_order,
+        },
+    };
+    let (_, projection) =
+        bible_graph_command::apply_create_bible_graph_node(conn, &command, created_at_ms)
+            .map_err(map_bible_graph_error)?;
+
+    let Some(setting) = candidate.setting else {
+        return Ok(node_id);
+    };
+    let description = projection.payload.parts.iter().find_map(|part| {
+        (part.part.part_key.as_str() == ENVIRONMENT_PART_KEY)
+            .then(|| {
+                part.fields
+                    .iter()
+                    .find(|field| field.field_key.as_str() == DESCRIPTION_FIELD_KEY)
+                    .map(|field| (part, field))
+            })
+            .flatten()
+    });
+    if let Some((part, field)) = description {
+        let command = CommandEnvelope::new(SetBibleGraphFieldCommand {
+            node_id: node_id.clone(),
+            part_id: part.part.id.clone(),
+            part_key: part.part.part_key.clone(),
+            part_name: part.part.name.clone(),
+            part_sort_order: part.part.sort_order,
+            field_id: field.id.clone(),
+            field_key: field.field_key.clone(),
+            value: Some(FieldValue::Text(setting.description().to_string())),
+            field_sort_order: field.sort_order,
+        });
+        bible_graph_command::apply_set_bible_graph_field(conn, &command, created_at_ms)
+            .map_err(map_bible_graph_error)?;
+    }
+    Ok(node_id)
+}
+
+/// Record that `node_id` mentions the location, unless it already does.
+/// Returns whether a new dependency was recorded.
+fn link_mention(
+    conn: &mut Connection,
+    node_id: NodeId,
+    location_id: &BibleGraphNodeId,
+    rationale: String,
+    created_at_ms: u64,
+) -> Result<bool, BackendError> {
+    let existing = semantic_dependency_store::load_semantic_dependency_projection(
+        conn,
+        &SemanticDependencyFilter {
+            endpoint: DependencyEndpointFilter {
+                kind: "timeline_node".to_string(),
+                id: node_id.0.to_string(),
+                part_key: None,
+                field_key: None,
+            },
+            direction: DependencyDirection::Source,
+        },
+    )
+    .map_err(map_semantic_dependency_error)?;
+    let target = SemanticDependencyEndpoint::BibleNode {
+        node_id: location_id.clone(),
+    };
+    if existing
+        .payload
+        .dependencies
+        .iter()
+        .any(|dependency| dependency.target == target)
+    {
+        return Ok(false);
+    }
+
+    let command_id = CommandId::new();
+    let command = CommandEnvelope {
+        id: command_id,
+        payload: RecordSemanticDependencyCommand {
+            dependency: SemanticDependency {
+                id: SemanticDependencyId::new(format!(
+                    "dependency.location.{}",
+                    derived_command_uuid(command_id, b"semantic.dependency")
+                ))
+                .map_err(|error| BackendError::bad_request(error.to_string()))?,
+                source: SemanticDependencyEndpoint::TimelineNode { node_id },
+                target,
+                kind: SemanticDependencyKind::Mentions,
+                rationale: Some(rationale),
+                confidence: None,
+                created_at_ms,
+            },
+        },
+    };
+    semantic_dependency_store::record_semantic_dependency(conn, &command, created_at_ms)
+        .map_err(map_semantic_dependency_error)?;
+    Ok(true)
+}
+
+fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use eidetic_core::Template;
+    use eidetic_core::timeline::node::StoryLevel;
+
+    use super::*;
+
+    #[test]
+    fn scene_headings_parse_setting_and_drop_time_of_day() {
+        assert_eq!(
+            parse_scene_heading("  INT. LIGHTHOUSE - KITCHEN - NIGHT"),
+            Some(SceneHeading {
+                setting: Setting::Interior,
+                location: "Lighthouse - Kitchen".into(),
+            })
+        );
+        assert_eq!(
+            parse_scene_heading("EXT. Harbor Road - dawn").map(|heading| heading.location),
+            Some("Harbor Road".into())
+        );
+        assert_eq!(
+            parse_scene_heading("INT./EXT. MARA'S CAR - DAY").map(|heading| heading.setting),
+            Some(Setting::InteriorExterior)
+        );
+        assert_eq!(parse_scene_heading("Mara walks into the interior."), None);
+        assert_eq!(parse_scene_heading("EXT. - NIGHT"), None);
+    }
+
+    #[test]
+    fn promotion_creates_unseen_locations_once_and_links_mentioning_nodes() {
+        let mut project = Template::MultiCam.build_project("Location Promotion Test");
+        let scenes: Vec<_> = project
+            .timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .into_iter()
+            .take(3)
+            .map(|node| node.id)
+            .collect();
+        for (index, text) in [
+            "INT. LIGHTHOUSE - NIGHT\nMara climbs the stairs.",
+            "EXT. LIGHTHOUSE - DAY\nWaves.\n\nEXT. THE HARBOR - DAY",
+            "INT. HARBOR - CONTINUOUS",
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            project
+                .timeline
+                .node_mut(scenes[index])
+                .unwrap()
+                .content
+                .content = text.into();
+        }
+        let mut conn = Connection::open_in_memory().unwrap();
+
+        let first = promote_locations_in(&mut conn, &project, 1_000).unwrap();
+        let names: Vec<_> = first
+            .created
+            .iter()
+            .map(|location| location.name.as_str())
+            .collect();
+        assert_eq!(names, ["The Harbor", "Lighthouse"]);
+        assert_eq!(first.linked_count, 4);
+
+        let lighthouse =
+            crate::bible_graph_store::load_node_detail_projection(&conn, &first.created[1].node_id)
+                .unwrap()
+                .unwrap();
+        assert_eq!(
+            lighthouse.node.parent_id,
+            Some(CanonicalBibleRoot::Places.node_id())
+        );
+        let description = lighthouse
+            .parts
+            .iter()
+            .flat_map(|part| &part.fields)
+            .find(|field| field.field_key.as_str() == DESCRIPTION_FIELD_KEY)
+            .and_then(|field| field.value.clone());
+        assert_eq!(
+            description,
+            Some(FieldValue::Text("Interior and exterior.".into()))
+        );
+
+        let second = promote_locations_in(&mut conn, &project, 2_000).unwrap();
+        assert!(second.created.is_empty());
+        assert_eq!(second.linked_count, 0);
+    }
+}
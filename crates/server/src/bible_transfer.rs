@@ -0,0 +1,579 @@
+//! Story bible transfer between projects.
+//!
+//! [`crate::bible_export`] flattens the bible for reading; this format keeps
+//! schema keys, raw field values and entity ids so one project's bible can
+//! be loaded into another. Imported entities always get fresh ids, and the
+//! parent links, relations and field references between them are remapped
+//! to match. Snapshot anchors to timeline nodes are dropped because those
+//! nodes belong to the source project.
+
+use std::collections::{HashMap, HashSet};
+
+use eidetic_core::contracts::{
+    BibleGraphEdge, BibleGraphEdgeId, BibleGraphEdgeKind, BibleGraphFieldId, BibleGraphFieldKey,
+    BibleGraphNode, BibleGraphNodeId, BibleGraphPartId, BibleGraphPartKey, BibleGraphSchemaKey,
+    BibleGraphSnapshotFieldId, BibleGraphSnapshotId, ChangeEvent, ChangeEventKind, CommandEnvelope,
+    FieldDelta, FieldValue, ObjectKind, ObjectRevision, RevisionOperation,
+    SetBibleGraphEdgeCommand, SetBibleGraphFieldCommand, SetBibleGraphSnapshotFieldCommand,
+    builtin_bible_graph_schema,
+};
+use eidetic_core::id::new_uuid;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::bible_graph_command::{
+    BibleGraphCommandError, delete_edge_revision, delete_node_revision, edge_revision,
+    node_revision, snapshot_revision,
+};
+use crate::bible_graph_edge_store;
+use crate::bible_graph_store;
+use crate::history_store::{self, HistoryStoreError, RecordChangeOutcome};
+
+/// Version written by [`load_bible_transfer`]; newer files are rejected.
+pub const BIBLE_TRANSFER_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BibleTransfer {
+    pub format_version: u32,
+    #[serde(default)]
+    pub entities: Vec<BibleTransferEntity>,
+    #[serde(default)]
+    pub relations: Vec<BibleTransferRelation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BibleTransferEntity {
+    pub id: BibleGraphNodeId,
+    /// Another entity in the file or a canonical root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<BibleGraphNodeId>,
+    pub schema_key: BibleGraphSchemaKey,
+    pub name: String,
+    #[serde(default)]
+    pub sort_order: u32,
+    #[serde(default)]
+    pub fields: Vec<BibleTransferField>,
+    #[serde(default)]
+    pub snapshots: Vec<BibleTransferSnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BibleTransferField {
+    pub part_key: BibleGraphPartKey,
+    pub part_name: String,
+    #[serde(default)]
+    pub part_sort_order: u32,
+    pub field_key: BibleGraphFieldKey,
+    pub value: FieldValue,
+    #[serde(default)]
+    pub sort_order: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BibleTransferSnapshot {
+    pub at_ms: u64,
+    pub label: String,
+    #[serde(default)]
+    pub sort_order: u32,
+    #[serde(default)]
+    pub fields: Vec<BibleTransferField>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BibleTransferRelation {
+    pub from_id: BibleGraphNodeId,
+    pub to_id: BibleGraphNodeId,
+    pub edge_kind: BibleGraphEdgeKind,
+    pub label: String,
+    #[serde(default = "default_directed")]
+    pub directed: bool,
+    #[serde(default)]
+    pub sort_order: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BibleImportMode {
+    /// Add the imported entities beside the current bible.
+    #[default]
+    Merge,
+    /// Delete every current entity and its relations first.
+    Replace,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportBibleTransferCommand {
+    #[serde(default)]
+    pub mode: BibleImportMode,
+    pub bible: BibleTransfer,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BibleImportSummary {
+    pub mode: BibleImportMode,
+    pub removed_entities: usize,
+    pub imported_entities: usize,
+    pub imported_relations: usize,
+    /// Relations with an end that is neither imported nor in this project.
+    pub skipped_relations: usize,
+}
+
+/// Collect every user entity with its set fields, snapshots and outgoing
+/// relations.
+pub(crate) fn load_bible_transfer(conn: &Connection) -> Result<BibleTransfer, HistoryStoreError> {
+    let nodes = bible_graph_store::load_node_list_projection(conn)?.nodes;
+    let live: HashSet<&BibleGraphNodeId> = nodes.iter().map(|node| &node.id).collect();
+    let mut entities = Vec::new();
+    let mut relations = Vec::new();
+    for node in nodes.iter().filter(|node| !node.system_owned) {
+        let Some(detail) = bible_graph_store::load_node_detail_projection(conn, &node.id)? else {
+            continue;
+        };
+        let fields = detail
+            .parts
+            .iter()
+            .flat_map(|part| {
+                part.fields.iter().filter_map(|field| {
+                    Some(BibleTransferField {
+                        part_key: part.part.part_key.clone(),
+                        part_name: part.part.name.clone(),
+                        part_sort_order: part.part.sort_order,
+                        field_key: field.field_key.clone(),
+                        value: field.value.clone()?,
+                        sort_order: field.sort_order,
+                    })
+                })
+            })
+            .collect();
+        let snapshots = detail
+            .snapshots
+            .iter()
+            .map(|snapshot| BibleTransferSnapshot {
+                at_ms: snapshot.snapshot.at_ms,
+                label: snapshot.snapshot.label.clone(),
+                sort_order: snapshot.snapshot.sort_order,
+                fields: snapshot
+                    .fields
+                    .iter()
+                    .filter_map(|field| {
+                        Some(BibleTransferField {
+                            part_key: field.part_key.clone(),
+                            part_name: field.part_name.clone(),
+                            part_sort_order: 0,
+                            field_key: field.field_key.clone(),
+                            value: field.value.clone()?,
+                            sort_order: field.sort_order,
+                        })
+                    })
+                    .collect(),
+            })
+            .collect();
+        relations.extend(
+            detail
+                .outgoing_edges
+                .iter()
+                .filter(|edge| live.contains(&edge.to_node_id))
+                .map(|edge| BibleTransferRelation {
+                    from_id: edge.from_node_id.clone(),
+                    to_id: edge.to_node_id.clone(),
+                    edge_kind: edge.edge_kind.clone(),
+                    label: edge.label.clone(),
+                    directed: edge.directed,
+                    sort_order: edge.sort_order,
+                }),
+        );
+        entities.push(BibleTransferEntity {
+            id: node.id.clone(),
+            parent_id: node.parent_id.clone(),
+            schema_key: node.schema_key.clone(),
+            name: node.name.clone(),
+            sort_order: node.sort_order,
+            fields,
+            snapshots,
+        });
+    }
+    Ok(BibleTransfer {
+        format_version: BIBLE_TRANSFER_FORMAT_VERSION,
+        entities,
+        relations,
+    })
+}
+
+/// Load a transferred bible into the project as one change event.
+///
+/// Parents and relation ends that are neither imported nor still in the
+/// project are dropped; relations are counted as skipped. Missing canonical
+/// roots are created first so imported entities keep their category.
+pub(crate) fn apply_import_bible_transfer(
+    conn: &mut Connection,
+    command: &CommandEnvelope<ImportBibleTransferCommand>,
+    created_at_ms: u64,
+) -> Result<(RecordChangeOutcome, BibleImportSummary), BibleGraphCommandError> {
+    let ImportBibleTransferCommand { mode, bible } = &command.payload;
+    validate_transfer(bible)?;
+    bible_graph_store::create_schema(conn)?;
+
+    let current = bible_graph_store::load_node_list_projection(conn)?.nodes;
+    let (removed_nodes, kept_nodes): (Vec<BibleGraphNode>, Vec<BibleGraphNode>) = current
+        .into_iter()
+        .partition(|node| *mode == BibleImportMode::Replace && !node.system_owned);
+    let mut removed_edges: Vec<BibleGraphEdge> = Vec::new();
+    for node in &removed_nodes {
+        for edge in bible_graph_edge_store::load_outgoing_edges(conn, &node.id)?
+            .into_iter()
+            .chain(bible_graph_edge_store::load_incoming_edges(conn, &node.id)?)
+        {
+            if !removed_edges.iter().any(|removed| removed.id == edge.id) {
+                removed_edges.push(edge);
+            }
+        }
+    }
+    let missing_roots = bible_graph_store::missing_canonical_root_nodes(conn)?;
+    let mut present: HashSet<BibleGraphNodeId> = kept_nodes
+        .into_iter()
+        .chain(missing_roots.iter().cloned())
+        .map(|node| node.id)
+        .collect();
+
+    let plan = plan_import(bible, &present)?;
+    present.extend(plan.nodes.iter().map(|node| node.id.clone()));
+
+    let event = ChangeEvent::new(
+        command.id,
+        ChangeEventKind::UserEdit,
+        format!("import {} bible entities", plan.nodes.len()),
+    )
+    .with_created_at_ms(created_at_ms);
+    let mut revisions = Vec::new();
+    revisions.extend(
+        removed_edges
+            .iter()
+            .map(|edge| delete_edge_revision(edge, event.id)),
+    );
+    revisions.extend(
+        removed_nodes
+            .iter()
+            .map(|node| delete_node_revision(node, event.id)),
+    );
+    revisions.extend(
+        missing_roots
+            .iter()
+            .chain(&plan.nodes)
+            .map(|node| node_revision(node, event.id)),
+    );
+    revisions.extend(plan.fields.iter().map(|field| {
+        ObjectRevision::new(
+            ObjectKind::BiblePartField,
+            field.field_id.as_str(),
+            event.id,
+            RevisionOperation::Update,
+        )
+        .with_field(FieldDelta::new("value", None, field.value.clone()))
+    }));
+    let mut seen_snapshots = HashSet::new();
+    revisions.extend(plan.snapshot_fields.iter().map(|field| {
+        let exists = !seen_snapshots.insert(field.snapshot_id.clone());
+        snapshot_revision(field, None, exists, event.id)
+    }));
+    revisions.extend(
+        plan.edges
+            .iter()
+            .map(|edge| edge_revision(&edge.clone().into_edge(), None, event.id)),
+    );
+
+    let outcome = history_store::record_change_with(
+        conn,
+        command,
+        "bible_graph.import",
+        &event,
+        &revisions,
+        |tx| {
+            for edge in &removed_edges {
+                bible_graph_edge_store::delete_edge_in_transaction(tx, &edge.id, event.id)?;
+            }
+            for node in &removed_nodes {
+                bible_graph_store::delete_node_in_transaction(tx, &node.id, event.id)?;
+            }
+            for node in missing_roots.iter().chain(&plan.nodes) {
+                bible_graph_store::insert_node_in_transaction(tx, node, event.id)?;
+            }
+            for field in &plan.fields {
+                bible_graph_store::set_field_in_transaction(tx, field, event.id)?;
+            }
+            for field in &plan.snapshot_fields {
+                bible_graph_store::set_snapshot_field_in_transaction(tx, field, event.id)?;
+            }
+            for edge in &plan.edges {
+                bible_graph_edge_store::set_edge_in_transaction(tx, edge, event.id)?;
+            }
+            Ok(())
+        },
+    )?;
+
+    Ok((
+        outcome,
+        BibleImportSummary {
+            mode: *mode,
+            removed_entities: removed_nodes.len(),
+            imported_entities: plan.nodes.len(),
+            imported_relations: plan.edges.len(),
+            skipped_relations: bible.relations.len() - plan.edges.len(),
+        },
+    ))
+}
+
+/// Rows to write for an import, with every id already remapped.
+struct ImportPlan {
+    /// Parents before their children.
+    nodes: Vec<BibleGraphNode>,
+    fields: Vec<SetBibleGraphFieldCommand>,
+    snapshot_fields: Vec<SetBibleGraphSnapshotFieldCommand>,
+    edges: Vec<SetBibleGraphEdgeCommand>,
+}
+
+/// Give every entity a fresh id and rewrite the parent links, relations and
+/// bible-node field references that point at other imported entities.
+/// References to nodes in `present` are kept as they are.
+fn plan_import(
+    bible: &BibleTransfer,
+    present: &HashSet<BibleGraphNodeId>,
+) -> Result<ImportPlan, BibleGraphCommandError> {
+    let mut id_map: HashMap<&BibleGraphNodeId, BibleGraphNodeId> = HashMap::new();
+    for entity in &bible.entities {
+        id_map.insert(
+            &entity.id,
+            transfer_id(BibleGraphNodeId::new(format!(
+                "node.{}.{}",
+                entity.schema_key.as_str(),
+                new_uuid()
+            )))?,
+        );
+    }
+    let resolve = |id: &BibleGraphNodeId| {
+        id_map
+            .get(id)
+            .cloned()
+            .or_else(|| present.contains(id).then(|| id.clone()))
+    };
+    let remap_value = |value: &FieldValue| match value {
+        FieldValue::ObjectRef {
+            kind: ObjectKind::BibleNode,
+            id,
+        } => FieldValue::ObjectRef {
+            kind: ObjectKind::BibleNode,
+            id: BibleGraphNodeId::new(id.as_str())
+                .ok()
+                .and_then(|id| id_map.get(&id).cloned())
+                .map_or_else(|| id.clone(), |new_id| new_id.as_str().to_string()),
+        },
+        other => other.clone(),
+    };
+
+    let mut plan = ImportPlan {
+        nodes: Vec::new(),
+        fields: Vec::new(),
+        snapshot_fields: Vec::new(),
+        edges: Vec::new(),
+    };
+    for entity in parents_first(&bible.entities)? {
+        let new_id = id_map[&entity.id].clone();
+        plan.nodes.push(BibleGraphNode {
+            id: new_id.clone(),
+            parent_id: entity.parent_id.as_ref().and_then(resolve),
+            schema_key: entity.schema_key.clone(),
+            name: entity.name.clone(),
+            system_owned: false,
+            sort_order: entity.sort_order,
+        });
+
+        let mut part_ids: HashMap<&BibleGraphPartKey, BibleGraphPartId> = HashMap::new();
+        for field in &entity.fields {
+            let part_id = match part_ids.get(&field.part_key) {
+                Some(part_id) => part_id.clone(),
+                None => {
+                    let part_id =
+                        transfer_id(BibleGraphPartId::new(format!("part.{}", new_uuid())))?;
+                    part_ids.insert(&field.part_key, part_id.clone());
+                    part_id
+                }
+            };
+            plan.fields.push(SetBibleGraphFieldCommand {
+                node_id: new_id.clone(),
+                part_id,
+                part_key: field.part_key.clone(),
+                part_name: field.part_name.clone(),
+                part_sort_order: field.part_sort_order,
+                field_id: transfer_id(BibleGraphFieldId::new(format!("field.{}", new_uuid())))?,
+                field_key: field.field_key.clone(),
+                value: Some(remap_value(&field.value)),
+                field_sort_order: field.sort_order,
+            });
+        }
+
+        for snapshot in &entity.snapshots {
+            let snapshot_id = transfer_id(BibleGraphSnapshotId::new(format!(
+                "snapshot.{}",
+                new_uuid()
+            )))?;
+            for field in &snapshot.fields {
+                plan.snapshot_fields
+                    .push(SetBibleGraphSnapshotFieldCommand {
+                        snapshot_id: snapshot_id.clone(),
+                        node_id: new_id.clone(),
+                        at_ms: snapshot.at_ms,
+                        label: snapshot.label.clone(),
+                        snapshot_sort_order: snapshot.sort_order,
+                        source_node_id: None,
+                        field_id: transfer_id(BibleGraphSnapshotFieldId::new(format!(
+                            "snapshot-field.{}",
+                            new_uuid()
+                        )))?,
+                        part_key: field.part_key.clone(),
+                        part_name: field.part_name.clone(),
+                        field_key: field.field_key.clone(),
+                        value: Some(remap_value(&field.value)),
+                        field_sort_order: field.sort_order,
+                    });
+            }
+        }
+    }
+
+    for relation in &bible.relations {
+        let (Some(from_node_id), Some(to_node_id)) =
+            (resolve(&relation.from_id), resolve(&relation.to_id))
+        else {
+            continue;
+        };
+        plan.edges.push(SetBibleGraphEdgeCommand {
+            edge_id: transfer_id(BibleGraphEdgeId::new(format!("edge.{}", new_uuid())))?,
+            from_node_id,
+            to_node_id,
+            edge_kind: relation.edge_kind.clone(),
+            label: relation.label.clone(),
+            directed: relation.directed,
+            sort_order: relation.sort_order,
+        });
+    }
+    Ok(plan)
+}
+
+/// Order entities so each parent inside the file comes before its children.
+fn parents_first(
+    entities: &[BibleTransferEntity],
+) -> Result<Vec<&BibleTransferEntity>, BibleGraphCommandError> {
+    let in_file: HashSet<&BibleGraphNodeId> = entities.iter().map(|entity| &entity.id).collect();
+    let mut placed: HashSet<&BibleGraphNodeId> = HashSet::new();
+    let mut ordered = Vec::with_capacity(entities.len());
+    while ordered.len() < entities.len() {
+        let before = ordered.len();
+        for entity in entities {
+            let parent_ready = entity
+                .parent_id
+                .as_ref()
+                .is_none_or(|parent| !in_file.contains(parent) || placed.contains(parent));
+            if !placed.contains(&entity.id) && parent_ready {
+                placed.insert(&entity.id);
+                ordered.push(entity);
+            }
+        }
+        if ordered.len() == before {
+            return Err(BibleGraphCommandError::InvalidCommand(
+                "bible entities have a parent cycle".to_string(),
+            ));
+        }
+    }
+    Ok(ordered)
+}
+
+fn validate_transfer(bible: &BibleTransfer) -> Result<(), BibleGraphCommandError> {
+    if bible.format_version > BIBLE_TRANSFER_FORMAT_VERSION {
+        return Err(BibleGraphCommandError::InvalidCommand(format!(
+            "bible format version {} is newer than supported version {}",
+            bible.format_version, BIBLE_TRANSFER_FORMAT_VERSION
+        )));
+    }
+    let mut ids = HashSet::new();
+    for entity in &bible.entities {
+        if !ids.insert(&entity.id) {
+            return Err(BibleGraphCommandError::InvalidCommand(format!(
+                "bible entity id appears twice: {}",
+                entity.id.as_str()
+            )));
+        }
+        if entity.name.trim().is_empty() {
+            return Err(BibleGraphCommandError::InvalidCommand(format!(
+                "bible entity {} has no name",
+                entity.id.as_str()
+            )));
+        }
+        let snapshot_fields = entity
+            .snapshots
+            .iter()
+            .flat_map(|snapshot| &snapshot.fields);
+        for field in entity.fields.iter().chain(snapshot_fields) {
+            validate_transfer_field(&entity.schema_key, field)?;
+        }
+        for snapshot in &entity.snapshots {
+            if snapshot.label.trim().is_empty() {
+                return Err(BibleGraphCommandError::InvalidCommand(format!(
+                    "bible entity {} has a snapshot without a label",
+                    entity.id.as_str()
+                )));
+            }
+            if i64::try_from(snapshot.at_ms).is_err() {
+                return Err(BibleGraphCommandError::InvalidCommand(
+                    "at_ms is too large".to_string(),
+                ));
+            }
+        }
+    }
+    for relation in &bible.relations {
+        if relation.label.trim().is_empty() {
+            return Err(BibleGraphCommandError::InvalidCommand(
+                "label is required".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Fields of built-in schemas must name a part and field the schema has.
+fn validate_transfer_field(
+    schema_key: &BibleGraphSchemaKey,
+    field: &BibleTransferField,
+) -> Result<(), BibleGraphCommandError> {
+    if field.part_name.trim().is_empty() {
+        return Err(BibleGraphCommandError::InvalidCommand(
+            "part_name is required".to_string(),
+        ));
+    }
+    let Some(schema) = builtin_bible_graph_schema(schema_key) else {
+        return Ok(());
+    };
+    let valid = schema
+        .part(&field.part_key)
+        .is_some_and(|part| part.name == field.part_name && part.field(&field.field_key).is_some());
+    if valid {
+        return Ok(());
+    }
+    Err(BibleGraphCommandError::InvalidCommand(format!(
+        "field {}.{} is not valid for bible graph schema {}",
+        field.part_key.as_str(),
+        field.field_key.as_str(),
+        schema_key.as_str()
+    )))
+}
+
+fn transfer_id<T>(
+    id: Result<T, eidetic_core::contracts::BibleGraphContractError>,
+) -> Result<T, BibleGraphCommandError> {
+    id.map_err(|error| BibleGraphCommandError::InvalidCommand(error.to_string()))
+}
+
+fn default_directed() -> bool {
+    true
+}
+
+#[cfg(test)]
+#[path = "bible_transfer_tests.rs"]
+mod tests;
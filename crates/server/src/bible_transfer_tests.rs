@@ -0,0 +1,282 @@
+use super::*;
+use crate::bible_graph_command::{
+    apply_create_bible_graph_node, apply_ensure_canonical_bible_roots, apply_set_bible_graph_edge,
+    apply_set_bible_graph_field, apply_set_bible_graph_snapshot_field,
+};
+use eidetic_core::contracts::{
+    CreateBibleGraphNodeCommand, EnsureCanonicalBibleRootsCommand, SetBibleGraphEdgeCommand,
+};
+
+const CHARACTERS_ROOT: &str = "canonical.characters";
+const PLACES_ROOT: &str = "canonical.places";
+
+fn project_connection() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    bible_graph_store::create_schema(&conn).unwrap();
+    let roots = CommandEnvelope::new(EnsureCanonicalBibleRootsCommand {});
+    apply_ensure_canonical_bible_roots(&mut conn, &roots, 0).unwrap();
+    conn
+}
+
+fn id(value: &str) -> BibleGraphNodeId {
+    BibleGraphNodeId::new(value).unwrap()
+}
+
+fn create_node(conn: &mut Connection, node_id: &str, parent: &str, schema: &str, name: &str) {
+    let command = CommandEnvelope::new(CreateBibleGraphNodeCommand {
+        node_id: id(node_id),
+        parent_id: Some(id(parent)),
+        schema_key: BibleGraphSchemaKey::new(schema).unwrap(),
+        name: name.to_string(),
+        sort_order: 0,
+    });
+    apply_create_bible_graph_node(conn, &command, 0).unwrap();
+}
+
+fn connect(conn: &mut Connection, edge_id: &str, from: &str, to: &str) {
+    let command = CommandEnvelope::new(SetBibleGraphEdgeCommand {
+        edge_id: BibleGraphEdgeId::new(edge_id).unwrap(),
+        from_node_id: id(from),
+        to_node_id: id(to),
+        edge_kind: BibleGraphEdgeKind::LocatedIn,
+        label: "lives at".to_string(),
+        directed: true,
+        sort_order: 0,
+    });
+    apply_set_bible_graph_edge(conn, &command, 0).unwrap();
+}
+
+/// Mara lives at the lighthouse, with a tagline, a home reference and one
+/// snapshot.
+fn source_project() -> Connection {
+    let mut conn = project_connection();
+    create_node(
+        &mut conn,
+        "node.character.mara",
+        CHARACTERS_ROOT,
+        "character",
+        "Mara",
+    );
+    create_node(
+        &mut conn,
+        "node.location.lighthouse",
+        PLACES_ROOT,
+        "location",
+        "Lighthouse",
+    );
+    connect(
+        &mut conn,
+        "edge.mara.home",
+        "node.character.mara",
+        "node.location.lighthouse",
+    );
+    for (field_key, value) in [
+        ("tagline", FieldValue::Text("Keeper of the light".into())),
+        (
+            "summary",
+            FieldValue::ObjectRef {
+                kind: ObjectKind::BibleNode,
+                id: "node.location.lighthouse".into(),
+            },
+        ),
+    ] {
+        let command = CommandEnvelope::new(SetBibleGraphFieldCommand {
+            node_id: id("node.character.mara"),
+            part_id: BibleGraphPartId::new("part.mara.profile").unwrap(),
+            part_key: BibleGraphPartKey::new("profile").unwrap(),
+            part_name: "Profile".to_string(),
+            part_sort_order: 0,
+            field_id: BibleGraphFieldId::new(format!("field.mara.{field_key}")).unwrap(),
+            field_key: BibleGraphFieldKey::new(field_key).unwrap(),
+            value: Some(value),
+            field_sort_order: 0,
+        });
+        apply_set_bible_graph_field(&mut conn, &command, 0).unwrap();
+    }
+    let snapshot = CommandEnvelope::new(SetBibleGraphSnapshotFieldCommand {
+        snapshot_id: BibleGraphSnapshotId::new("snapshot.mara.storm").unwrap(),
+        node_id: id("node.character.mara"),
+        at_ms: 90_000,
+        label: "After the storm".to_string(),
+        snapshot_sort_order: 0,
+        source_node_id: Some(eidetic_core::timeline::node::NodeId::new()),
+        field_id: BibleGraphSnapshotFieldId::new("snapshot-field.mara.storm").unwrap(),
+        part_key: BibleGraphPartKey::new("profile").unwrap(),
+        part_name: "Profile".to_string(),
+        field_key: BibleGraphFieldKey::new("tagline").unwrap(),
+        value: Some(FieldValue::Text("Keeper of a dark light".into())),
+        field_sort_order: 0,
+    });
+    apply_set_bible_graph_snapshot_field(&mut conn, &snapshot, 0).unwrap();
+    conn
+}
+
+fn import(
+    conn: &mut Connection,
+    mode: BibleImportMode,
+    bible: &BibleTransfer,
+) -> Result<BibleImportSummary, BibleGraphCommandError> {
+    let command = CommandEnvelope::new(ImportBibleTransferCommand {
+        mode,
+        bible: bible.clone(),
+    });
+    apply_import_bible_transfer(conn, &command, 0).map(|(_, summary)| summary)
+}
+
+fn user_nodes(conn: &Connection) -> Vec<BibleGraphNode> {
+    bible_graph_store::load_node_list_projection(conn)
+        .unwrap()
+        .nodes
+        .into_iter()
+        .filter(|node| !node.system_owned)
+        .collect()
+}
+
+fn node_named(conn: &Connection, name: &str) -> BibleGraphNode {
+    user_nodes(conn)
+        .into_iter()
+        .find(|node| node.name == name)
+        .unwrap()
+}
+
+#[test]
+fn merge_import_remaps_ids_and_keeps_relations() {
+    let exported = load_bible_transfer(&source_project()).unwrap();
+    let json = serde_json::to_string(&exported).unwrap();
+    let bible: BibleTransfer = serde_json::from_str(&json).unwrap();
+    assert_eq!(bible, exported);
+    assert_eq!(bible.entities.len(), 2);
+    assert_eq!(bible.relations.len(), 1);
+
+    let mut target = source_project();
+    let summary = import(&mut target, BibleImportMode::Merge, &bible).unwrap();
+
+    assert_eq!(
+        summary,
+        BibleImportSummary {
+            mode: BibleImportMode::Merge,
+            removed_entities: 0,
+            imported_entities: 2,
+            imported_relations: 1,
+            skipped_relations: 0,
+        }
+    );
+    let nodes = user_nodes(&target);
+    assert_eq!(nodes.len(), 4);
+    let imported_mara = nodes
+        .iter()
+        .find(|node| node.name == "Mara" && node.id != id("node.character.mara"))
+        .unwrap();
+    let imported_home = nodes
+        .iter()
+        .find(|node| node.name == "Lighthouse" && node.id != id("node.location.lighthouse"))
+        .unwrap();
+    assert_eq!(imported_mara.parent_id, Some(id(CHARACTERS_ROOT)));
+
+    let detail = bible_graph_store::load_node_detail_projection(&target, &imported_mara.id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(detail.outgoing_edges.len(), 1);
+    assert_eq!(detail.outgoing_edges[0].to_node_id, imported_home.id);
+    let values: Vec<_> = detail
+        .parts
+        .iter()
+        .flat_map(|part| &part.fields)
+        .filter_map(|field| field.value.clone())
+        .collect();
+    assert!(values.contains(&FieldValue::Text("Keeper of the light".into())));
+    assert!(values.contains(&FieldValue::ObjectRef {
+        kind: ObjectKind::BibleNode,
+        id: imported_home.id.as_str().to_string(),
+    }));
+    assert_eq!(detail.snapshots.len(), 1);
+    assert_eq!(detail.snapshots[0].snapshot.at_ms, 90_000);
+    assert_eq!(detail.snapshots[0].snapshot.source_node_id, None);
+}
+
+#[test]
+fn replace_import_clears_the_current_bible_first() {
+    let bible = load_bible_transfer(&source_project()).unwrap();
+    let mut target = project_connection();
+    create_node(
+        &mut target,
+        "node.character.ada",
+        CHARACTERS_ROOT,
+        "character",
+        "Ada",
+    );
+    create_node(
+        &mut target,
+        "node.location.beach",
+        PLACES_ROOT,
+        "location",
+        "Beach",
+    );
+    connect(
+        &mut target,
+        "edge.ada.beach",
+        "node.character.ada",
+        "node.location.beach",
+    );
+
+    let summary = import(&mut target, BibleImportMode::Replace, &bible).unwrap();
+
+    assert_eq!(summary.removed_entities, 2);
+    assert_eq!(summary.imported_entities, 2);
+    let names: Vec<_> = user_nodes(&target)
+        .into_iter()
+        .map(|node| node.name)
+        .collect();
+    assert_eq!(names.len(), 2);
+    assert!(names.contains(&"Mara".to_string()) && names.contains(&"Lighthouse".to_string()));
+    assert!(
+        bible_graph_edge_store::load_edge(
+            &target,
+            &BibleGraphEdgeId::new("edge.ada.beach").unwrap()
+        )
+        .unwrap()
+        .is_none()
+    );
+    let mara = node_named(&target, "Mara");
+    assert_eq!(
+        bible_graph_edge_store::load_outgoing_edges(&target, &mara.id)
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn import_skips_dangling_relations_and_rejects_bad_files() {
+    let mut bible = load_bible_transfer(&source_project()).unwrap();
+    bible.relations.push(BibleTransferRelation {
+        from_id: id("node.character.mara"),
+        to_id: id("node.character.stranger"),
+        edge_kind: BibleGraphEdgeKind::References,
+        label: "knows".to_string(),
+        directed: true,
+        sort_order: 0,
+    });
+    let mut target = project_connection();
+
+    let summary = import(&mut target, BibleImportMode::Merge, &bible).unwrap();
+
+    assert_eq!(summary.imported_relations, 1);
+    assert_eq!(summary.skipped_relations, 1);
+
+    let mut duplicated = bible.clone();
+    duplicated.entities.push(duplicated.entities[0].clone());
+    let error = import(&mut target, BibleImportMode::Merge, &duplicated).unwrap_err();
+    assert!(matches!(error, BibleGraphCommandError::InvalidCommand(_)));
+
+    let mut unknown_field = bible;
+    let mara = unknown_field
+        .entities
+        .iter_mut()
+        .find(|entity| entity.name == "Mara")
+        .unwrap();
+    mara.fields[0].field_key = BibleGraphFieldKey::new("weather").unwrap();
+    let error = import(&mut target, BibleImportMode::Replace, &unknown_field).unwrap_err();
+    assert!(matches!(error, BibleGraphCommandError::InvalidCommand(_)));
+    assert_eq!(user_nodes(&target).len(), 2);
+}
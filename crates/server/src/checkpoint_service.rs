@@ -0,0 +1,200 @@
+//! Auto-checkpoints: periodic copies of the active project database, kept in a
+//! `checkpoints` directory beside `project.db` for recovery after a crash or a
+//! regretted edit. Each copy can be opened with `load_project`.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::backend_error::BackendError;
+use crate::persistence;
+use crate::state::{AppState, CheckpointConfig, constants};
+
+const CHECKPOINT_DIR: &str = "checkpoints";
+const AUTO_CHECKPOINT_PREFIX: &str = "autosave-";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CheckpointConfigUpdate {
+    pub interval_minutes: Option<u64>,
+    pub retention: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CheckpointEntry {
+    /// Display label, e.g. `autosave 14:32` (UTC).
+    pub label: String,
+    pub path: PathBuf,
+    /// RFC3339 UTC timestamp the checkpoint was written.
+    pub created_at: String,
+}
+
+pub fn get_checkpoint_config(state: &AppState) -> CheckpointConfig {
+    state.checkpoint_config.lock().clone()
+}
+
+pub fn update_checkpoint_config(
+    state: &AppState,
+    update: CheckpointConfigUpdate,
+) -> Result<CheckpointConfig, BackendError> {
+    if update.retention == Some(0) {
+        return Err(BackendError::bad_request("retention must be at least 1"));
+    }
+    if update
+        .interval_minutes
+        .is_some_and(|minutes| minutes > constants::MAX_CHECKPOINT_INTERVAL_MINUTES)
+    {
+        return Err(BackendError::bad_request(format!(
+            "interval must be at most {} minutes",
+            constants::MAX_CHECKPOINT_INTERVAL_MINUTES
+        )));
+    }
+    let mut config = state.checkpoint_config.lock();
+    if let Some(interval_minutes) = update.interval_minutes {
+        config.interval_minutes = interval_minutes;
+    }
+    if let Some(retention) = update.retention {
+        config.retention = retention;
+    }
+    Ok(config.clone())
+}
+
+/// Auto-checkpoints of the active project, newest first.
+pub fn list_checkpoints(state: &AppState) -> Result<Vec<CheckpointEntry>, BackendError> {
+    let path = state
+        .project_database
+        .active_path()
+        .ok_or_else(BackendError::no_project)?;
+    let mut entries: Vec<CheckpointEntry> = auto_checkpoint_paths(&path)
+        .into_iter()
+        .filter_map(|path| checkpoint_entry(&path))
+        .collect();
+    entries.reverse();
+    Ok(entries)
+}
+
+/// Copy the project database into its checkpoints directory, then prune
+/// auto-checkpoints beyond `retention`, oldest first. Other files in the
+/// directory are left alone.
+pub(crate) fn write_auto_checkpoint(
+    project_path: &Path,
+    retention: usize,
+    now_secs: u64,
+) -> Result<PathBuf, String> {
+    let dir = checkpoint_dir(project_path);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("create checkpoint directory: {e}"))?;
+    let stamp = persistence::format_rfc3339_utc(now_secs).replace(':', "-");
+    let checkpoint = dir.join(format!("{AUTO_CHECKPOINT_PREFIX}{stamp}.db"));
+    let _ = std::fs::remove_file(&checkpoint);
+
+    let conn = crate::sqlite::open_write_connection(project_path)
+        .map_err(|e| format!("open project database: {e}"))?;
+    conn.execute("VACUUM INTO ?1", [checkpoint.to_string_lossy().as_ref()])
+        .map_err(|e| format!("write checkpoint: {e}"))?;
+
+    let checkpoints = auto_checkpoint_paths(project_path);
+    let excess = checkpoints.len().saturating_sub(retention);
+    for stale in &checkpoints[..excess] {
+        if let Err(error) = std::fs::remove_file(stale) {
+            tracing::warn!("failed to prune checkpoint {}: {error}", stale.display());
+        }
+    }
+    Ok(checkpoint)
+}
+
+fn checkpoint_dir(project_path: &Path) -> PathBuf {
+    project_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(CHECKPOINT_DIR)
+}
+
+/// Auto-checkpoint files, oldest first. Their timestamped names sort in
+/// time order.
+fn auto_checkpoint_paths(project_path: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = std::fs::read_dir(checkpoint_dir(project_path)) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| checkpoint_stamp(path).is_some())
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn checkpoint_stamp(path: &Path) -> Option<&str> {
+    path.file_name()?
+        .to_str()?
+        .strip_prefix(AUTO_CHECKPOINT_PREFIX)?
+        .strip_suffix(".db")
+}
+
+fn checkpoint_entry(path: &Path) -> Option<CheckpointEntry> {
+    // Stamps look like `2026-10-17T14-32-05Z`.
+    let (date, time) = checkpoint_stamp(path)?.split_once('T')?;
+    let time = time.replace('-', ":");
+    Some(CheckpointEntry {
+        label: format!("autosave {}", time.get(..5)?),
+        path: path.to_path_buf(),
+        created_at: format!("{date}T{time}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn auto_checkpoints_are_pruned_to_retention_without_touching_other_files() {
+        let state = AppState::new().await;
+        let dir =
+            std::env::temp_dir().join(format!("eidetic-checkpoints-{}", uuid::Uuid::new_v4()));
+        let project_path = dir.join("project.db");
+        let project = eidetic_core::Template::MultiCam.build_project("Checkpoints");
+        persistence::save_project(&project, &project_path, None)
+            .await
+            .unwrap();
+        std::fs::create_dir_all(dir.join(CHECKPOINT_DIR)).unwrap();
+        let manual = dir.join(CHECKPOINT_DIR).join("before rewrite.db");
+        std::fs::write(&manual, b"manual").unwrap();
+
+        // 2026-10-17T14:32:05Z and the two following minutes.
+        for now_secs in [1_792_247_525, 1_792_247_585, 1_792_247_645] {
+            write_auto_checkpoint(&project_path, 2, now_secs).unwrap();
+        }
+
+        state.project_database.set_active_path(project_path);
+        let checkpoints = list_checkpoints(&state).unwrap();
+        let labels: Vec<&str> = checkpoints
+            .iter()
+            .map(|entry| entry.label.as_str())
+            .collect();
+        assert_eq!(labels, ["autosave 14:34", "autosave 14:33"]);
+        assert_eq!(checkpoints[0].created_at, "2026-10-17T14:34:05Z");
+        let (restored, _) = persistence::load_project(&checkpoints[0].path)
+            .await
+            .unwrap();
+        assert_eq!(restored.name, "Checkpoints");
+        assert!(manual.exists());
+
+        let error = update_checkpoint_config(
+            &state,
+            CheckpointConfigUpdate {
+                retention: Some(0),
+                ..CheckpointConfigUpdate::default()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(error, BackendError::BadRequest(_)));
+        let error = update_checkpoint_config(
+            &state,
+            CheckpointConfigUpdate {
+                interval_minutes: Some(u64::MAX),
+                ..CheckpointConfigUpdate::default()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(error, BackendError::BadRequest(_)));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
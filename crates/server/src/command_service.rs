@@ -18,14 +18,21 @@ use crate::state::{AppState, ServerEvent};
 use crate::story_arc_command::{self, StoryArcCommandError};
 use crate::story_arc_store;
 
+pub use crate::bible_location_promotion::{
+    PromoteLocationsResponse, PromotedLocation, promote_locations,
+};
+pub use crate::bible_transfer::{
+    BibleImportMode, BibleImportSummary, BibleTransfer, BibleTransferEntity, BibleTransferField,
+    BibleTransferRelation, BibleTransferSnapshot, ImportBibleTransferCommand,
+};
 pub use crate::command_service_bible::{
     BibleGraphNodeCommandResponse, BibleGraphNodeListCommandResponse,
-    BibleGraphRootsCommandResponse, CreateBibleGraphNodeRequestCommand,
+    BibleGraphRootsCommandResponse, BibleImportResponse, CreateBibleGraphNodeRequestCommand,
     SetBibleGraphEdgeRequestCommand, SetBibleGraphSnapshotFieldRequestCommand,
-    create_bible_graph_node, create_connected_bible_graph_node, delete_bible_graph_edge,
-    delete_bible_graph_node, ensure_canonical_bible_roots, set_bible_graph_edge,
-    set_bible_graph_field, set_bible_graph_node_name, set_bible_graph_node_text,
-    set_bible_graph_snapshot_field,
+    create_bible_graph_node, create_bible_graph_node_from_core_command,
+    create_connected_bible_graph_node, delete_bible_graph_edge, delete_bible_graph_node,
+    ensure_canonical_bible_roots, import_bible, set_bible_graph_edge, set_bible_graph_field,
+    set_bible_graph_node_name, set_bible_graph_node_text, set_bible_graph_snapshot_field,
 };
 pub use crate::command_service_semantic::{
     BibleReferenceProposalCommandResponse, PropagationProposalCommandResponse,
@@ -36,12 +43,16 @@ pub use crate::command_service_semantic::{
 pub use crate::command_service_timeline::{
     ApplyTimelineChildrenRequestCommand, CreateTimelineChildFromParentRequestCommand,
     CreateTimelineNodeRequestCommand, CreateTimelineRelationshipRequestCommand,
-    SplitTimelineNodeRequestCommand, TimelineCommandResponse, apply_timeline_children,
-    create_timeline_child_from_parent, create_timeline_child_from_parent_core_command,
-    create_timeline_node, create_timeline_node_from_core_command, create_timeline_relationship,
+    DuplicateTimelineNodeResponse, NormalizeTimelineLevelResponse, SplitTimelineNodeRequestCommand,
+    TimelineCommandResponse, apply_timeline_children, create_timeline_child_from_parent,
+    create_timeline_child_from_parent_core_command, create_timeline_node,
+    create_timeline_node_from_core_command, create_timeline_relationship,
     create_timeline_relationship_from_core_command, delete_timeline_node,
-    delete_timeline_relationship, set_timeline_node_lock, set_timeline_node_notes,
-    set_timeline_node_range, split_timeline_node, split_timeline_node_from_core_command,
+    delete_timeline_relationship, duplicate_timeline_node, normalize_timeline_level,
+    preview_apply_timeline_children, renumber_timeline_scenes, set_all_tracks_collapsed,
+    set_timeline_node_arc_tag, set_timeline_node_generation_order, set_timeline_node_lock,
+    set_timeline_node_notes, set_timeline_node_pinned, set_timeline_node_range,
+    set_timeline_node_scene_number, split_timeline_node, split_timeline_node_from_core_command,
 };
 
 #[derive(Debug, Serialize)]
@@ -151,7 +162,13 @@ pub async fn create_story_arc(
     state: &AppState,
     command: CreateStoryArcRequestCommand,
 ) -> Result<StoryArcCommandResponse, BackendError> {
-    let command = command.into_core_command();
+    create_story_arc_from_core_command(state, command.into_core_command()).await
+}
+
+pub async fn create_story_arc_from_core_command(
+    state: &AppState,
+    command: CommandEnvelope<CreateStoryArcCommand>,
+) -> Result<StoryArcCommandResponse, BackendError> {
     let path = active_project_path(state)?;
     let response = tokio::task::spawn_blocking(move || create_story_arc_at_path(path, command))
         .await
@@ -10,10 +10,12 @@ use eidetic_core::contracts::{
     SetBibleGraphNodeTextCommand, SetBibleGraphSnapshotFieldCommand,
     builtin_bible_graph_schema_list_projection,
 };
+use eidetic_core::timeline::node::NodeId;
 use serde::{Deserialize, Serialize};
 
 use crate::backend_error::BackendError;
 use crate::bible_graph_command::{self, BibleGraphCommandError};
+use crate::bible_transfer::{self, BibleImportSummary, ImportBibleTransferCommand};
 use crate::command_service_support::{
     active_project_path, derived_command_uuid, map_history_error,
 };
@@ -44,6 +46,12 @@ pub struct BibleGraphNodeListCommandResponse {
     projection: ProjectionEnvelope<BibleGraphNodeListProjection>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct BibleImportResponse {
+    outcome: RecordChangeOutcome,
+    summary: BibleImportSummary,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct CreateBibleGraphNodeRequestCommand {
@@ -104,6 +112,8 @@ struct SetBibleGraphSnapshotFieldRequestPayload {
     #[serde(default)]
     snapshot_sort_order: u32,
     #[serde(default)]
+    source_node_id: Option<NodeId>,
+    #[serde(default)]
     field_id: Option<BibleGraphSnapshotFieldId>,
     part_key: BibleGraphPartKey,
     part_name: String,
@@ -193,6 +203,7 @@ impl SetBibleGraphSnapshotFieldRequestCommand {
                 at_ms: self.payload.at_ms,
                 label: self.payload.label,
                 snapshot_sort_order: self.payload.snapshot_sort_order,
+                source_node_id: self.payload.source_node_id,
                 field_id,
                 part_key: self.payload.part_key,
                 part_name: self.payload.part_name,
@@ -313,7 +324,13 @@ pub async fn create_bible_graph_node(
     state: &AppState,
     command: CreateBibleGraphNodeRequestCommand,
 ) -> Result<BibleGraphNodeCommandResponse, BackendError> {
-    let command = command.into_core_command()?;
+    create_bible_graph_node_from_core_command(state, command.into_core_command()?).await
+}
+
+pub async fn create_bible_graph_node_from_core_command(
+    state: &AppState,
+    command: CommandEnvelope<CreateBibleGraphNodeCommand>,
+) -> Result<BibleGraphNodeCommandResponse, BackendError> {
     let path = active_project_path(state)?;
     let response = tokio::task::spawn_blocking(move || create_bible_node_at_path(path, command))
         .await
@@ -374,6 +391,21 @@ pub async fn ensure_canonical_bible_roots(
     Ok(response)
 }
 
+/// Load a bible exported from another project, merging it beside the
+/// current entities or replacing them.
+pub async fn import_bible(
+    state: &AppState,
+    command: CommandEnvelope<ImportBibleTransferCommand>,
+) -> Result<BibleImportResponse, BackendError> {
+    let path = active_project_path(state)?;
+    let response = tokio::task::spawn_blocking(move || import_bible_at_path(path, command))
+        .await
+        .map_err(|error| BackendError::internal(format!("bible import task failed: {error}")))??;
+
+    let _ = state.events_tx.send(ServerEvent::BibleChanged);
+    Ok(response)
+}
+
 fn create_bible_node_at_path(
     path: PathBuf,
     command: CommandEnvelope<CreateBibleGraphNodeCommand>,
@@ -576,6 +608,18 @@ fn delete_bible_graph_edge_at_path(
     })
 }
 
+fn import_bible_at_path(
+    path: PathBuf,
+    command: CommandEnvelope<ImportBibleTransferCommand>,
+) -> Result<BibleImportResponse, BackendError> {
+    let mut conn = crate::sqlite::open_write_connection(&path)
+        .map_err(|e| BackendError::internal(e.to_string()))?;
+    let (outcome, summary) = bible_transfer::apply_import_bible_transfer(&mut conn, &command, 0)
+        .map_err(map_bible_graph_error)?;
+
+    Ok(BibleImportResponse { outcome, summary })
+}
+
 fn set_bible_graph_snapshot_field_at_path(
     path: PathBuf,
     command: CommandEnvelope<SetBibleGraphSnapshotFieldCommand>,
@@ -592,7 +636,7 @@ fn set_bible_graph_snapshot_field_at_path(
     })
 }
 
-fn map_bible_graph_error(error: BibleGraphCommandError) -> BackendError {
+pub(crate) fn map_bible_graph_error(error: BibleGraphCommandError) -> BackendError {
     match error {
         BibleGraphCommandError::InvalidCommand(message) => BackendError::bad_request(message),
         BibleGraphCommandError::Store(error) => map_history_error(error),
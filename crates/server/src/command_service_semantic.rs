@@ -5,6 +5,7 @@ use eidetic_core::contracts::{
     RejectBibleReferenceProposalCommand, RejectPropagationProposalCommand,
     UpdatePropagationProposalCommand,
 };
+use eidetic_core::timeline::Timeline;
 use serde::Serialize;
 
 use crate::backend_error::BackendError;
@@ -67,12 +68,22 @@ pub async fn accept_bible_reference_proposal(
     command: CommandEnvelope<AcceptBibleReferenceProposalCommand>,
 ) -> Result<BibleReferenceProposalCommandResponse, BackendError> {
     let path = active_project_path(state)?;
-    let response =
-        tokio::task::spawn_blocking(move || accept_bible_reference_proposal_at_path(path, command))
-            .await
-            .map_err(|error| {
-                BackendError::internal(format!("semantic proposal accept task failed: {error}"))
-            })??;
+    let timeline = if state.ai_config.lock().introduction_snapshots {
+        state
+            .project
+            .lock()
+            .as_ref()
+            .map(|project| project.timeline.clone())
+    } else {
+        None
+    };
+    let response = tokio::task::spawn_blocking(move || {
+        accept_bible_reference_proposal_at_path(path, command, timeline)
+    })
+    .await
+    .map_err(|error| {
+        BackendError::internal(format!("semantic proposal accept task failed: {error}"))
+    })??;
 
     if response.outcome == RecordChangeOutcome::Recorded {
         let _ = state.events_tx.send(ServerEvent::SemanticProposalsChanged);
@@ -188,12 +199,17 @@ fn reject_bible_reference_proposal_at_path(
 fn accept_bible_reference_proposal_at_path(
     path: std::path::PathBuf,
     command: CommandEnvelope<AcceptBibleReferenceProposalCommand>,
+    timeline: Option<Timeline>,
 ) -> Result<BibleReferenceProposalCommandResponse, BackendError> {
     let mut conn = crate::sqlite::open_write_connection(&path)
         .map_err(|e| BackendError::internal(e.to_string()))?;
-    let outcome =
-        semantic_proposal_accept::record_accept_bible_reference_proposal(&mut conn, &command, 0)
-            .map_err(map_semantic_proposal_error)?;
+    let outcome = semantic_proposal_accept::record_accept_bible_reference_proposal(
+        &mut conn,
+        &command,
+        timeline.as_ref(),
+        0,
+    )
+    .map_err(map_semantic_proposal_error)?;
     let projection = semantic_proposal_store::load_bible_reference_proposal_list_projection(&conn)
         .map_err(map_semantic_proposal_error)?;
 
@@ -287,6 +303,9 @@ fn map_semantic_proposal_error(error: SemanticProposalStoreError) -> BackendErro
         SemanticProposalStoreError::NotFound(message) => BackendError::not_found(message),
         SemanticProposalStoreError::History(error) => map_history_error(error),
         SemanticProposalStoreError::Sqlite(error) => BackendError::internal(error.to_string()),
+        SemanticProposalStoreError::BibleGraphContract(error) => {
+            BackendError::bad_request(error.to_string())
+        }
     }
 }
 
@@ -1,11 +1,18 @@
+use std::collections::HashMap;
+
 use eidetic_core::contracts::{
-    ApplyTimelineChildCommand, CommandEnvelope, CreateTimelineChildFromParentCommand,
-    CreateTimelineNodeCommand, CreateTimelineRelationshipCommand, DeleteTimelineNodeCommand,
-    DeleteTimelineRelationshipCommand, ObjectKind, ProjectionEnvelope, SetTimelineNodeLockCommand,
-    SetTimelineNodeNotesCommand, SetTimelineNodeRangeCommand, SplitTimelineNodeCommand,
-    TimelineRenderProjection,
+    ApplyTimelineChildCommand, ApplyTimelineChildrenPreview, CommandEnvelope,
+    CreateTimelineChildFromParentCommand, CreateTimelineNodeCommand,
+    CreateTimelineRelationshipCommand, DeleteTimelineNodeCommand,
+    DeleteTimelineRelationshipCommand, DuplicateTimelineNodeCommand, NormalizeTimelineLevelCommand,
+    ObjectKind, ProjectionEnvelope, RenumberTimelineScenesCommand, SetTimelineNodeArcTagCommand,
+    SetTimelineNodeGenerationOrderCommand, SetTimelineNodeLockCommand, SetTimelineNodeNotesCommand,
+    SetTimelineNodePinnedCommand, SetTimelineNodeRangeCommand, SetTimelineNodeSceneNumberCommand,
+    SplitTimelineNodeCommand, TimelineRenderProjection,
 };
 use eidetic_core::timeline::Timeline;
+use eidetic_core::timeline::node::NodeId;
+use eidetic_core::timeline::track::Track;
 use rusqlite::Connection;
 use serde::Serialize;
 
@@ -29,6 +36,25 @@ pub struct TimelineCommandResponse {
     projection: ProjectionEnvelope<TimelineRenderProjection>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct DuplicateTimelineNodeResponse {
+    outcome: RecordChangeOutcome,
+    projection: ProjectionEnvelope<TimelineRenderProjection>,
+    root_node_id: NodeId,
+    /// Source node id to copy id, for every copied node.
+    node_ids: HashMap<NodeId, NodeId>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NormalizeTimelineLevelResponse {
+    /// Absent when no boundary needed snapping and nothing was recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    outcome: Option<RecordChangeOutcome>,
+    projection: ProjectionEnvelope<TimelineRenderProjection>,
+    /// Sibling boundaries snapped together.
+    adjusted: usize,
+}
+
 pub async fn create_timeline_node(
     state: &AppState,
     command: CreateTimelineNodeRequestCommand,
@@ -100,6 +126,7 @@ pub async fn set_timeline_node_range(
     command: CommandEnvelope<SetTimelineNodeRangeCommand>,
 ) -> Result<TimelineCommandResponse, BackendError> {
     let path = active_project_path(state)?;
+    let reanchor_snapshots = command.payload.reanchor_snapshots;
     let project = timeline_command_project(state, &path).await?;
     let response = tokio::task::spawn_blocking(move || {
         let mut conn = crate::sqlite::open_write_connection(&path)
@@ -123,6 +150,9 @@ pub async fn set_timeline_node_range(
 
     if response.outcome == RecordChangeOutcome::Recorded {
         let _ = state.events_tx.send(ServerEvent::TimelineChanged);
+        if reanchor_snapshots {
+            let _ = state.events_tx.send(ServerEvent::BibleChanged);
+        }
         state.trigger_save();
     }
     Ok(response)
@@ -165,9 +195,257 @@ pub async fn set_timeline_node_lock(
     Ok(response)
 }
 
+pub async fn set_timeline_node_pinned(
+    state: &AppState,
+    command: CommandEnvelope<SetTimelineNodePinnedCommand>,
+) -> Result<TimelineCommandResponse, BackendError> {
+    let path = active_project_path(state)?;
+    let project = timeline_command_project(state, &path).await?;
+    let response = tokio::task::spawn_blocking(move || {
+        let mut conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        history_store::create_schema(&conn).map_err(map_history_error)?;
+        let outcome = timeline_command::record_set_timeline_node_pinned_history(
+            &mut conn, &project, &command, 0,
+        )
+        .map_err(map_timeline_command_error)?;
+        let projection = timeline_render_projection_from_current_state(&conn, &project.timeline)
+            .map_err(map_timeline_command_error)?;
+        Ok::<_, BackendError>(TimelineCommandResponse {
+            outcome,
+            projection,
+        })
+    })
+    .await
+    .map_err(|error| {
+        BackendError::internal(format!("timeline node pin command task failed: {error}"))
+    })??;
+
+    if response.outcome == RecordChangeOutcome::Recorded {
+        let _ = state.events_tx.send(ServerEvent::TimelineChanged);
+        state.trigger_save();
+    }
+    Ok(response)
+}
+
+/// Collapse or expand every track row and return the tracks in display
+/// order. Track state is UI state, so no history is recorded.
+pub async fn set_all_tracks_collapsed(
+    state: &AppState,
+    collapsed: bool,
+) -> Result<Vec<Track>, BackendError> {
+    let path = active_project_path(state)?;
+    {
+        let mut guard = state.project.lock();
+        let project = guard.as_mut().ok_or_else(BackendError::no_project)?;
+        for track in &mut project.timeline.tracks {
+            track.collapsed = collapsed;
+        }
+    }
+    // Saving first creates the tracks table for a project not yet written.
+    state.force_save().await;
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        crate::persistence::set_all_tracks_collapsed(&conn, collapsed)
+            .map_err(BackendError::internal)
+    })
+    .await
+    .map_err(|error| BackendError::internal(format!("track collapse task failed: {error}")))??;
+
+    let _ = state.events_tx.send(ServerEvent::TimelineChanged);
+    crate::projection_service::timeline_tracks(state)
+}
+
+pub async fn set_timeline_node_scene_number(
+    state: &AppState,
+    command: CommandEnvelope<SetTimelineNodeSceneNumberCommand>,
+) -> Result<TimelineCommandResponse, BackendError> {
+    let path = active_project_path(state)?;
+    let node_id = command.payload.node_id;
+    let project = timeline_command_project(state, &path).await?;
+    let response = tokio::task::spawn_blocking(move || {
+        let mut conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        history_store::create_schema(&conn).map_err(map_history_error)?;
+        let outcome = timeline_command::record_set_timeline_node_scene_number_history(
+            &mut conn, &project, &command, 0,
+        )
+        .map_err(map_timeline_command_error)?;
+        let projection = timeline_render_projection_from_current_state(&conn, &project.timeline)
+            .map_err(map_timeline_command_error)?;
+        Ok::<_, BackendError>(TimelineCommandResponse {
+            outcome,
+            projection,
+        })
+    })
+    .await
+    .map_err(|error| {
+        BackendError::internal(format!("scene number command task failed: {error}"))
+    })??;
+
+    if response.outcome == RecordChangeOutcome::Recorded {
+        let _ = state.events_tx.send(ServerEvent::TimelineChanged);
+        let _ = state
+            .events_tx
+            .send(ServerEvent::NodeUpdated { node_id: node_id.0 });
+        state.trigger_save();
+    }
+    Ok(response)
+}
+
+pub async fn set_timeline_node_generation_order(
+    state: &AppState,
+    command: CommandEnvelope<SetTimelineNodeGenerationOrderCommand>,
+) -> Result<TimelineCommandResponse, BackendError> {
+    let path = active_project_path(state)?;
+    let node_id = command.payload.node_id;
+    let project = timeline_command_project(state, &path).await?;
+    let response = tokio::task::spawn_blocking(move || {
+        let mut conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        history_store::create_schema(&conn).map_err(map_history_error)?;
+        let outcome = timeline_command::record_set_timeline_node_generation_order_history(
+            &mut conn, &project, &command, 0,
+        )
+        .map_err(map_timeline_command_error)?;
+        let projection = timeline_render_projection_from_current_state(&conn, &project.timeline)
+            .map_err(map_timeline_command_error)?;
+        Ok::<_, BackendError>(TimelineCommandResponse {
+            outcome,
+            projection,
+        })
+    })
+    .await
+    .map_err(|error| {
+        BackendError::internal(format!("generation order command task failed: {error}"))
+    })??;
+
+    if response.outcome == RecordChangeOutcome::Recorded {
+        let _ = state.events_tx.send(ServerEvent::TimelineChanged);
+        let _ = state
+            .events_tx
+            .send(ServerEvent::NodeUpdated { node_id: node_id.0 });
+        state.trigger_save();
+    }
+    Ok(response)
+}
+
+/// Tag or untag a node with an arc, optionally across its whole subtree.
+pub async fn set_timeline_node_arc_tag(
+    state: &AppState,
+    command: CommandEnvelope<SetTimelineNodeArcTagCommand>,
+) -> Result<TimelineCommandResponse, BackendError> {
+    let path = active_project_path(state)?;
+    let project = timeline_command_project(state, &path).await?;
+    let response = tokio::task::spawn_blocking(move || {
+        let mut conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        history_store::create_schema(&conn).map_err(map_history_error)?;
+        let outcome = timeline_command::record_set_timeline_node_arc_tag_history(
+            &mut conn, &project, &command, 0,
+        )
+        .map_err(map_timeline_command_error)?;
+        let projection = timeline_render_projection_from_current_state(&conn, &project.timeline)
+            .map_err(map_timeline_command_error)?;
+        Ok::<_, BackendError>(TimelineCommandResponse {
+            outcome,
+            projection,
+        })
+    })
+    .await
+    .map_err(|error| BackendError::internal(format!("arc tag command task failed: {error}")))??;
+
+    if response.outcome == RecordChangeOutcome::Recorded {
+        let _ = state.events_tx.send(ServerEvent::TimelineChanged);
+        state.trigger_save();
+    }
+    Ok(response)
+}
+
+/// Number scenes in time order, either from scratch or around the numbers
+/// a locked script already has.
+pub async fn renumber_timeline_scenes(
+    state: &AppState,
+    command: CommandEnvelope<RenumberTimelineScenesCommand>,
+) -> Result<TimelineCommandResponse, BackendError> {
+    let path = active_project_path(state)?;
+    let project = timeline_command_project(state, &path).await?;
+    let response = tokio::task::spawn_blocking(move || {
+        let mut conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        history_store::create_schema(&conn).map_err(map_history_error)?;
+        let outcome = timeline_command::record_renumber_timeline_scenes_history(
+            &mut conn, &project, &command, 0,
+        )
+        .map_err(map_timeline_command_error)?;
+        let projection = timeline_render_projection_from_current_state(&conn, &project.timeline)
+            .map_err(map_timeline_command_error)?;
+        Ok::<_, BackendError>(TimelineCommandResponse {
+            outcome,
+            projection,
+        })
+    })
+    .await
+    .map_err(|error| {
+        BackendError::internal(format!("renumber scenes command task failed: {error}"))
+    })??;
+
+    if response.outcome == RecordChangeOutcome::Recorded {
+        let _ = state.events_tx.send(ServerEvent::TimelineChanged);
+        state.trigger_save();
+    }
+    Ok(response)
+}
+
+/// Snap drifted sibling boundaries under one parent back together. Gaps
+/// and overlaps wider than the tolerance are left for manual review.
+pub async fn normalize_timeline_level(
+    state: &AppState,
+    command: CommandEnvelope<NormalizeTimelineLevelCommand>,
+) -> Result<NormalizeTimelineLevelResponse, BackendError> {
+    let path = active_project_path(state)?;
+    let project = timeline_command_project(state, &path).await?;
+    let response = tokio::task::spawn_blocking(move || {
+        let mut conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        history_store::create_schema(&conn).map_err(map_history_error)?;
+        let (outcome, adjusted) = timeline_command::record_normalize_timeline_level_history(
+            &mut conn, &project, &command, 0,
+        )
+        .map_err(map_timeline_command_error)?;
+        let projection = timeline_render_projection_from_current_state(&conn, &project.timeline)
+            .map_err(map_timeline_command_error)?;
+        Ok::<_, BackendError>(NormalizeTimelineLevelResponse {
+            outcome,
+            projection,
+            adjusted,
+        })
+    })
+    .await
+    .map_err(|error| {
+        BackendError::internal(format!("normalize timeline command task failed: {error}"))
+    })??;
+
+    if response.outcome == Some(RecordChangeOutcome::Recorded) {
+        let _ = state.events_tx.send(ServerEvent::TimelineChanged);
+        state.trigger_save();
+    }
+    Ok(response)
+}
+
 pub async fn set_timeline_node_notes(
     state: &AppState,
     command: CommandEnvelope<SetTimelineNodeNotesCommand>,
+) -> Result<TimelineCommandResponse, BackendError> {
+    set_timeline_node_notes_as(state, command, "human:command").await
+}
+
+/// Record a notes change and mirror it into the Y.Doc attributed to `author`.
+pub(crate) async fn set_timeline_node_notes_as(
+    state: &AppState,
+    command: CommandEnvelope<SetTimelineNodeNotesCommand>,
+    author: &str,
 ) -> Result<TimelineCommandResponse, BackendError> {
     let path = active_project_path(state)?;
     let node_id = command.payload.node_id;
@@ -198,7 +476,7 @@ pub async fn set_timeline_node_notes(
             node_id,
             field: crate::ydoc::ContentField::Notes,
             text: notes,
-            author: "human:command".into(),
+            author: author.into(),
         });
         let _ = state.events_tx.send(ServerEvent::TimelineChanged);
         let _ = state
@@ -380,6 +658,30 @@ pub async fn apply_timeline_children(
     Ok(response)
 }
 
+/// Dry run of [`apply_timeline_children`]: the same validation and child
+/// layout, reported without recording anything.
+pub async fn preview_apply_timeline_children(
+    state: &AppState,
+    command: ApplyTimelineChildrenRequestCommand,
+) -> Result<ApplyTimelineChildrenPreview, BackendError> {
+    command.validate()?;
+    let command = command.into_core_command();
+    let path = active_project_path(state)?;
+    let project = timeline_command_project(state, &path).await?;
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        timeline_command::preview_apply_timeline_children(&conn, &project, &command)
+            .map_err(map_timeline_command_error)
+    })
+    .await
+    .map_err(|error| {
+        BackendError::internal(format!(
+            "timeline apply children preview task failed: {error}"
+        ))
+    })?
+}
+
 fn children_have_bible_references(children: &[ApplyTimelineChildCommand]) -> bool {
     children.iter().any(|child| {
         child
@@ -411,6 +713,16 @@ pub async fn split_timeline_node_from_core_command(
 ) -> Result<TimelineCommandResponse, BackendError> {
     let path = active_project_path(state)?;
     let project = timeline_command_project(state, &path).await?;
+    let original_content = project
+        .timeline
+        .node(command.payload.node_id)
+        .map(|node| node.content.clone())
+        .ok();
+    let (original_id, left_id, right_id) = (
+        command.payload.node_id,
+        command.payload.left_node_id,
+        command.payload.right_node_id,
+    );
     let response = tokio::task::spawn_blocking(move || {
         let mut conn = crate::sqlite::open_write_connection(&path)
             .map_err(|e| BackendError::internal(e.to_string()))?;
@@ -431,6 +743,31 @@ pub async fn split_timeline_node_from_core_command(
     })??;
 
     if response.outcome == RecordChangeOutcome::Recorded {
+        // Mirror the split into Y.Doc: the left half inherits the original text.
+        let _ = state
+            .doc_tx
+            .try_send(DocCommand::EnsureNode { node_id: left_id });
+        let _ = state
+            .doc_tx
+            .try_send(DocCommand::EnsureNode { node_id: right_id });
+        if let Some(content) = original_content {
+            for (field, text) in [
+                (crate::ydoc::ContentField::Notes, content.notes),
+                (crate::ydoc::ContentField::Content, content.content),
+            ] {
+                if !text.is_empty() {
+                    let _ = state.doc_tx.try_send(DocCommand::WriteNodeContent {
+                        node_id: left_id,
+                        field,
+                        text,
+                        author: "human:command".into(),
+                    });
+                }
+            }
+        }
+        let _ = state.doc_tx.try_send(DocCommand::RemoveNode {
+            node_id: original_id,
+        });
         let _ = state.events_tx.send(ServerEvent::TimelineChanged);
         let _ = state.events_tx.send(ServerEvent::HierarchyChanged);
         state.trigger_save();
@@ -438,6 +775,81 @@ pub async fn split_timeline_node_from_core_command(
     Ok(response)
 }
 
+pub async fn duplicate_timeline_node(
+    state: &AppState,
+    command: CommandEnvelope<DuplicateTimelineNodeCommand>,
+) -> Result<DuplicateTimelineNodeResponse, BackendError> {
+    let path = active_project_path(state)?;
+    let project = timeline_command_project(state, &path).await?;
+    let contents: HashMap<NodeId, _> = project
+        .timeline
+        .nodes
+        .iter()
+        .map(|node| (node.id, node.content.clone()))
+        .collect();
+    let (outcome, pairs, projection) = tokio::task::spawn_blocking(move || {
+        let mut conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        history_store::create_schema(&conn).map_err(map_history_error)?;
+        let (outcome, pairs) = timeline_command::record_duplicate_timeline_node_history(
+            &mut conn, &project, &command, 0,
+        )
+        .map_err(map_timeline_command_error)?;
+        let projection = timeline_render_projection_from_current_state(&conn, &project.timeline)
+            .map_err(map_timeline_command_error)?;
+        Ok::<_, BackendError>((outcome, pairs, projection))
+    })
+    .await
+    .map_err(|error| {
+        BackendError::internal(format!(
+            "timeline node duplicate command task failed: {error}"
+        ))
+    })??;
+
+    if outcome == RecordChangeOutcome::Recorded {
+        // Mirror each copy into Y.Doc with its source's text. A recursive
+        // copy can outgrow the channel, so wait for room instead of dropping.
+        for (source_id, copy_id) in &pairs {
+            let _ = state
+                .doc_tx
+                .send(DocCommand::EnsureNode { node_id: *copy_id })
+                .await;
+            let Some(content) = contents.get(source_id) else {
+                continue;
+            };
+            for (field, text) in [
+                (crate::ydoc::ContentField::Notes, &content.notes),
+                (crate::ydoc::ContentField::Content, &content.content),
+            ] {
+                if !text.is_empty() {
+                    let _ = state
+                        .doc_tx
+                        .send(DocCommand::WriteNodeContent {
+                            node_id: *copy_id,
+                            field,
+                            text: text.clone(),
+                            author: "human:command".into(),
+                        })
+                        .await;
+                }
+            }
+        }
+        let _ = state.events_tx.send(ServerEvent::TimelineChanged);
+        let _ = state.events_tx.send(ServerEvent::HierarchyChanged);
+        state.trigger_save();
+    }
+    let root_node_id = pairs
+        .first()
+        .map(|(_, copy_id)| *copy_id)
+        .ok_or_else(|| BackendError::internal("duplicate produced no nodes"))?;
+    Ok(DuplicateTimelineNodeResponse {
+        outcome,
+        projection,
+        root_node_id,
+        node_ids: pairs.into_iter().collect(),
+    })
+}
+
 async fn timeline_command_project(
     state: &AppState,
     path: &std::path::Path,
@@ -75,6 +75,8 @@ struct ApplyTimelineChildrenRequestPayload {
     #[serde(default)]
     child_plan_id: Option<ChildPlanId>,
     children: Vec<ApplyTimelineChildRequestPayload>,
+    #[serde(default)]
+    preserve_existing: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -226,6 +228,7 @@ impl ApplyTimelineChildrenRequestCommand {
                         props: child.props,
                     })
                     .collect(),
+                preserve_existing: self.payload.preserve_existing,
             },
         }
     }
@@ -0,0 +1,93 @@
+//! Which bible entities timeline nodes reference. Arc entity evolution, bible
+//! entity presence and the beat sheet's character column all read this.
+
+use std::collections::HashMap;
+
+use eidetic_core::contracts::{BibleGraphNodeId, ContextInfluenceKind};
+use eidetic_core::timeline::node::NodeId;
+use rusqlite::Connection;
+
+use crate::bible_graph_snapshot_store;
+use crate::bible_graph_store;
+use crate::context_influence_store;
+use crate::history_store::HistoryStoreError;
+
+/// The bible entities each of `node_ids` references: those that directly or
+/// indirectly influence its latest context evaluation, or own a snapshot
+/// anchored to it. Every requested node has an entry; influences come first,
+/// in record order, then snapshot owners.
+pub(crate) fn referenced_bible_nodes(
+    conn: &Connection,
+    node_ids: &[NodeId],
+) -> Result<HashMap<NodeId, Vec<BibleGraphNodeId>>, HistoryStoreError> {
+    bible_graph_store::create_schema(conn)?;
+    context_influence_store::create_schema(conn)?;
+
+    let mut referenced: HashMap<NodeId, Vec<BibleGraphNodeId>> = HashMap::new();
+    for &node_id in node_ids {
+        let entities = referenced.entry(node_id).or_default();
+        for record in context_influence_store::load_latest_context_influence_records(conn, node_id)?
+        {
+            if matches!(
+                record.influence_kind,
+                ContextInfluenceKind::Direct | ContextInfluenceKind::Inherited
+            ) && let Some(bible_node_id) = record.bible_node_id
+                && !entities.contains(&bible_node_id)
+            {
+                entities.push(bible_node_id);
+            }
+        }
+    }
+    for snapshot in bible_graph_snapshot_store::load_snapshots_for_source_nodes(conn, node_ids)? {
+        let Some(entities) = snapshot
+            .source_node_id
+            .and_then(|source| referenced.get_mut(&source))
+        else {
+            continue;
+        };
+        if !entities.contains(&snapshot.node_id) {
+            entities.push(snapshot.node_id);
+        }
+    }
+    Ok(referenced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::referenced_bible_nodes;
+    use crate::bible_graph_store;
+    use crate::bible_graph_test_support::{create_node, set_snapshot};
+    use eidetic_core::contracts::BibleGraphNodeId;
+    use eidetic_core::timeline::node::NodeId;
+    use rusqlite::Connection;
+
+    #[test]
+    fn snapshot_owners_count_once_and_every_node_has_an_entry() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        bible_graph_store::create_schema(&conn).unwrap();
+        let (scene, other) = (NodeId::new(), NodeId::new());
+        create_node(&mut conn, "node.character.mara", "Mara");
+        set_snapshot(
+            &mut conn,
+            "snapshot.a",
+            "node.character.mara",
+            0,
+            Some(scene),
+        );
+        set_snapshot(
+            &mut conn,
+            "snapshot.b",
+            "node.character.mara",
+            10,
+            Some(scene),
+        );
+
+        let referenced = referenced_bible_nodes(&conn, &[scene, other]).unwrap();
+
+        assert_eq!(
+            referenced[&scene],
+            vec![BibleGraphNodeId::new("node.character.mara").unwrap()]
+        );
+        assert!(referenced[&other].is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use eidetic_core::contracts::{ScriptBlockKind, ScriptDocumentProjection, ScriptSegmentProjection};
 use eidetic_core::script::element::ScriptElement;
 use eidetic_core::script::format::parse_script_elements;
@@ -44,9 +46,12 @@ fn load_font_family() -> Result<FontFamily<genpdf::fonts::FontData>, String> {
 /// - Character names centered ALL CAPS
 /// - Dialogue indented (center-aligned approximation)
 /// - Transitions right-aligned
+/// - Scene numbers, keyed by segment source node id, on both sides of the
+///   heading
 pub fn generate_screenplay_pdf(
     project_name: &str,
     projection: &ScriptDocumentProjection,
+    scene_numbers: &HashMap<String, String>,
 ) -> Result<Vec<u8>, String> {
     let font_family = load_font_family()?;
 
@@ -71,7 +76,7 @@ pub fn generate_screenplay_pdf(
     // Start new page for content.
     doc.push(PageBreak::new());
 
-    for elem in script_document_elements(projection) {
+    for elem in script_document_elements(projection, scene_numbers) {
         render_element(&mut doc, &elem);
     }
 
@@ -82,15 +87,36 @@ pub fn generate_screenplay_pdf(
 
 pub(crate) fn script_document_elements(
     projection: &ScriptDocumentProjection,
+    scene_numbers: &HashMap<String, String>,
 ) -> Vec<ScriptElement> {
     projection
         .segments
         .iter()
-        .flat_map(segment_elements)
+        .flat_map(|segment| {
+            let mut elements = segment_elements(segment);
+            let number = segment
+                .segment
+                .source_node_id
+                .as_ref()
+                .and_then(|node_id| scene_numbers.get(node_id));
+            if let Some(number) = number {
+                number_first_heading(&mut elements, number);
+            }
+            elements
+        })
         .collect()
 }
 
-fn segment_elements(segment: &ScriptSegmentProjection) -> Vec<ScriptElement> {
+fn number_first_heading(elements: &mut [ScriptElement], number: &str) {
+    if let Some(ScriptElement::SceneHeading(heading)) = elements
+        .iter_mut()
+        .find(|element| matches!(element, ScriptElement::SceneHeading(_)))
+    {
+        *heading = format!("{number}  {heading}  {number}");
+    }
+}
+
+pub(crate) fn segment_elements(segment: &ScriptSegmentProjection) -> Vec<ScriptElement> {
     segment
         .blocks
         .iter()
@@ -134,7 +160,7 @@ fn render_element(doc: &mut Document, elem: &ScriptElement) {
             doc.push(Paragraph::new(s.as_str()));
             doc.push(Break::new(0.3));
         }
-        ScriptElement::Character(s) => {
+        ScriptElement::Character(s) | ScriptElement::DualDialogue(s) => {
             doc.push(Break::new(0.3));
             let p = Paragraph::new(s.to_uppercase()).aligned(Alignment::Center);
             doc.push(p);
@@ -176,7 +202,7 @@ mod tests {
             (ScriptBlockKind::Transition, "CUT TO:"),
         ]);
 
-        let elements = script_document_elements(&projection);
+        let elements = script_document_elements(&projection, &HashMap::new());
 
         assert_eq!(
             elements,
@@ -198,7 +224,7 @@ mod tests {
             (ScriptBlockKind::Action, "EXT. BEACH - DAY\n\nAda runs."),
         ]);
 
-        let elements = script_document_elements(&projection);
+        let elements = script_document_elements(&projection, &HashMap::new());
 
         assert_eq!(
             elements,
@@ -209,6 +235,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn script_document_elements_number_the_first_heading_of_a_numbered_segment() {
+        let projection = script_projection(vec![
+            (ScriptBlockKind::SceneHeading, "EXT. BEACH - DAY"),
+            (ScriptBlockKind::SceneHeading, "EXT. PIER - DAY"),
+        ]);
+        let scene_numbers = HashMap::from([("node.beat.opening".to_string(), "12A".to_string())]);
+
+        let elements = script_document_elements(&projection, &scene_numbers);
+
+        assert_eq!(
+            elements,
+            vec![
+                ScriptElement::SceneHeading("12A  EXT. BEACH - DAY  12A".to_string()),
+                ScriptElement::SceneHeading("EXT. PIER - DAY".to_string()),
+            ]
+        );
+    }
+
     fn script_projection(blocks: Vec<(ScriptBlockKind, &str)>) -> ScriptDocumentProjection {
         ScriptDocumentProjection {
             document: ScriptDocument {
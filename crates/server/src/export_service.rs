@@ -1,7 +1,17 @@
+use std::collections::HashMap;
+
 use eidetic_core::contracts::ScriptDocumentId;
+use eidetic_core::story::arc::ArcId;
+use eidetic_core::timeline::Timeline;
+use eidetic_core::timeline::node::StoryLevel;
 
 use crate::backend_error::BackendError;
+use crate::beat_sheet_export;
+use crate::bible_export::{self, BibleExport};
+use crate::bible_graph_store;
+use crate::bible_transfer::{self, BibleTransfer};
 use crate::export::generate_screenplay_pdf;
+use crate::fountain_export;
 use crate::history_store::HistoryStoreError;
 use crate::script_store;
 use crate::state::AppState;
@@ -9,6 +19,78 @@ use crate::state::AppState;
 const MAIN_SCRIPT_DOCUMENT_ID: &str = "script.document.main";
 
 pub async fn export_pdf(state: &AppState) -> Result<Vec<u8>, BackendError> {
+    let project = state
+        .project_snapshot()
+        .ok_or_else(|| BackendError::BadRequest("no project loaded".to_string()))?;
+    let path = state
+        .project_database
+        .active_path()
+        .ok_or_else(|| BackendError::BadRequest("no project loaded".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|error| BackendError::Internal(error.to_string()))?;
+        script_store::create_schema(&conn).map_err(map_history_error)?;
+        let document_id = ScriptDocumentId::new(MAIN_SCRIPT_DOCUMENT_ID)
+            .map_err(|error| BackendError::BadRequest(error.to_string()))?;
+        let projection = script_store::load_document_projection(&conn, &document_id)
+            .map_err(map_history_error)?
+            .ok_or_else(|| BackendError::NotFound("script document not found".to_string()))?;
+        let scene_numbers = scene_numbers_by_node(&project.timeline);
+        generate_screenplay_pdf(&project.name, &projection, &scene_numbers)
+            .map_err(BackendError::Internal)
+    })
+    .await
+    .map_err(|error| BackendError::Internal(format!("PDF export task failed: {error}")))?
+}
+
+/// Scene number for every node inside a numbered scene, keyed by node id as
+/// script segments store it.
+fn scene_numbers_by_node(timeline: &Timeline) -> HashMap<String, String> {
+    timeline
+        .nodes
+        .iter()
+        .filter_map(|node| {
+            timeline
+                .scene_number_for(node.id)
+                .map(|number| (node.id.0.to_string(), number.to_string()))
+        })
+        .collect()
+}
+
+/// Render the story bible as a Markdown document for sharing.
+pub async fn export_bible_markdown(state: &AppState) -> Result<String, BackendError> {
+    let export = load_bible_export(state).await?;
+    Ok(bible_export::render_bible_markdown(&export))
+}
+
+/// Dump the story bible as structured JSON, independent of the graph storage shape.
+pub async fn export_bible_json(state: &AppState) -> Result<serde_json::Value, BackendError> {
+    let export = load_bible_export(state).await?;
+    serde_json::to_value(export).map_err(|error| BackendError::Internal(error.to_string()))
+}
+
+/// Dump the story bible in the form [`crate::command_service::import_bible`]
+/// loads into another project.
+pub async fn export_bible_transfer(state: &AppState) -> Result<BibleTransfer, BackendError> {
+    let path = state
+        .project_database
+        .active_path()
+        .ok_or_else(|| BackendError::BadRequest("no project loaded".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|error| BackendError::Internal(error.to_string()))?;
+        bible_graph_store::create_schema(&conn).map_err(map_history_error)?;
+        bible_transfer::load_bible_transfer(&conn).map_err(map_history_error)
+    })
+    .await
+    .map_err(|error| {
+        BackendError::Internal(format!("bible transfer export task failed: {error}"))
+    })?
+}
+
+async fn load_bible_export(state: &AppState) -> Result<BibleExport, BackendError> {
     let project_name = {
         let guard = state.project.lock();
         match guard.as_ref() {
@@ -21,6 +103,66 @@ pub async fn export_pdf(state: &AppState) -> Result<Vec<u8>, BackendError> {
         .active_path()
         .ok_or_else(|| BackendError::BadRequest("no project loaded".to_string()))?;
 
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|error| BackendError::Internal(error.to_string()))?;
+        bible_graph_store::create_schema(&conn).map_err(map_history_error)?;
+        bible_export::load_bible_export(&conn, &project_name).map_err(map_history_error)
+    })
+    .await
+    .map_err(|error| BackendError::Internal(format!("bible export task failed: {error}")))?
+}
+
+/// Render a beat sheet CSV with one row per node at `level`.
+pub async fn export_beat_sheet_csv(
+    state: &AppState,
+    level: StoryLevel,
+) -> Result<String, BackendError> {
+    let project = state
+        .project_snapshot()
+        .ok_or_else(|| BackendError::BadRequest("no project loaded".to_string()))?;
+    let path = state
+        .project_database
+        .active_path()
+        .ok_or_else(|| BackendError::BadRequest("no project loaded".to_string()))?;
+
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|error| BackendError::Internal(error.to_string()))?;
+        let node_ids: Vec<_> = project
+            .timeline
+            .nodes_at_level(level)
+            .iter()
+            .map(|node| node.id)
+            .collect();
+        let characters = beat_sheet_export::load_characters_present(&conn, &node_ids)
+            .map_err(map_history_error)?;
+        Ok(beat_sheet_export::render_beat_sheet_csv(
+            &project,
+            level,
+            &characters,
+        ))
+    })
+    .await
+    .map_err(|error| BackendError::Internal(format!("beat sheet export task failed: {error}")))?
+}
+
+/// Render the scenes and beats tagged with one arc as a Fountain document.
+pub async fn export_arc_fountain(state: &AppState, arc_id: ArcId) -> Result<String, BackendError> {
+    let project = state
+        .project_snapshot()
+        .ok_or_else(|| BackendError::BadRequest("no project loaded".to_string()))?;
+    let arc = project
+        .arcs
+        .iter()
+        .find(|arc| arc.id == arc_id)
+        .cloned()
+        .ok_or_else(|| BackendError::NotFound(format!("arc not found: {}", arc_id.0)))?;
+    let path = state
+        .project_database
+        .active_path()
+        .ok_or_else(|| BackendError::BadRequest("no project loaded".to_string()))?;
+
     tokio::task::spawn_blocking(move || {
         let conn = crate::sqlite::open_write_connection(&path)
             .map_err(|error| BackendError::Internal(error.to_string()))?;
@@ -28,12 +170,15 @@ pub async fn export_pdf(state: &AppState) -> Result<Vec<u8>, BackendError> {
         let document_id = ScriptDocumentId::new(MAIN_SCRIPT_DOCUMENT_ID)
             .map_err(|error| BackendError::BadRequest(error.to_string()))?;
         let projection = script_store::load_document_projection(&conn, &document_id)
-            .map_err(map_history_error)?
-            .ok_or_else(|| BackendError::NotFound("script document not found".to_string()))?;
-        generate_screenplay_pdf(&project_name, &projection).map_err(BackendError::Internal)
+            .map_err(map_history_error)?;
+        Ok(fountain_export::render_arc_fountain(
+            &project,
+            &arc,
+            projection.as_ref(),
+        ))
     })
     .await
-    .map_err(|error| BackendError::Internal(format!("PDF export task failed: {error}")))?
+    .map_err(|error| BackendError::Internal(format!("arc Fountain export task failed: {error}")))?
 }
 
 fn map_history_error(error: HistoryStoreError) -> BackendError {
@@ -0,0 +1,234 @@
+//! Fountain export of one arc's storyline: the script of every scene and beat
+//! tagged with the arc, in time order, as a single continuous document.
+
+use std::collections::{HashMap, HashSet};
+
+use eidetic_core::Project;
+use eidetic_core::contracts::{ScriptDocumentProjection, ScriptSegmentProjection};
+use eidetic_core::script::element::ScriptElement;
+use eidetic_core::script::format::parse_script_elements;
+use eidetic_core::story::arc::StoryArc;
+use eidetic_core::timeline::node::{StoryLevel, StoryNode};
+use eidetic_core::timeline::timing::format_time;
+
+use crate::export::segment_elements;
+
+const SCENE_HEADING_PREFIXES: [&str; 6] = ["INT", "EXT", "EST", "INT./EXT", "INT/EXT", "I/E"];
+
+/// Render the arc's scenes and beats as Fountain. A node's script comes from
+/// its script document segments, or from its cached content when it has none.
+/// A `[[note]]` marks each jump forward in time between consecutive nodes.
+pub(crate) fn render_arc_fountain(
+    project: &Project,
+    arc: &StoryArc,
+    projection: Option<&ScriptDocumentProjection>,
+) -> String {
+    let mut segments_by_node: HashMap<&str, Vec<&ScriptSegmentProjection>> = HashMap::new();
+    for segment in projection
+        .iter()
+        .flat_map(|projection| &projection.segments)
+    {
+        if let Some(node_id) = segment.segment.source_node_id.as_deref() {
+            segments_by_node.entry(node_id).or_default().push(segment);
+        }
+    }
+
+    let mut out = format!("Title: {} - {}\n\n", project.name, arc.name);
+    let mut covered_until: Option<u64> = None;
+    for node in arc_script_nodes(project, arc) {
+        let elements: Vec<ScriptElement> =
+            match segments_by_node.get(node.id.0.to_string().as_str()) {
+                Some(segments) => segments
+                    .iter()
+                    .flat_map(|segment| segment_elements(segment))
+                    .collect(),
+                None => parse_script_elements(&node.content.content),
+            };
+        if elements.is_empty() {
+            continue;
+        }
+
+        if let Some(until) = covered_until
+            && node.time_range.start_ms > until
+        {
+            ensure_blank_line(&mut out);
+            out.push_str(&format!(
+                "[[{} later]]\n\n",
+                format_time(node.time_range.start_ms - until)
+            ));
+        }
+        covered_until = Some(
+            covered_until
+                .unwrap_or_default()
+                .max(node.time_range.end_ms),
+        );
+
+        let mut scene_number = project.timeline.scene_number_for(node.id);
+        for element in &elements {
+            let number = match element {
+                ScriptElement::SceneHeading(_) => scene_number.take(),
+                _ => None,
+            };
+            push_element(&mut out, element, number);
+        }
+    }
+    ensure_blank_line(&mut out);
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+/// Scenes and beats tagged with the arc, plus the beats of tagged scenes, in
+/// time order.
+fn arc_script_nodes<'a>(project: &'a Project, arc: &StoryArc) -> Vec<&'a StoryNode> {
+    let mut seen = HashSet::new();
+    let mut nodes = Vec::new();
+    for node_id in project.timeline.nodes_for_arc(arc.id) {
+        let Ok(node) = project.timeline.node(node_id) else {
+            continue;
+        };
+        let beats = match node.level {
+            StoryLevel::Scene => project.timeline.descendants_of(node.id),
+            StoryLevel::Beat => Vec::new(),
+            _ => continue,
+        };
+        for node in std::iter::once(node).chain(beats) {
+            if seen.insert(node.id) {
+                nodes.push(node);
+            }
+        }
+    }
+    nodes.sort_by_key(|node| (node.time_range.start_ms, node.level, node.time_range.end_ms));
+    nodes
+}
+
+fn push_element(out: &mut String, element: &ScriptElement, scene_number: Option<&str>) {
+    match element {
+        ScriptElement::SceneHeading(heading) => {
+            let heading = heading.trim().to_uppercase();
+            ensure_blank_line(out);
+            if !is_standard_heading(&heading) {
+                out.push('.');
+            }
+            out.push_str(&heading);
+            if let Some(number) = scene_number {
+                out.push_str(&format!(" #{number}#"));
+            }
+            out.push_str("\n\n");
+        }
+        ScriptElement::Action(text) => {
+            ensure_blank_line(out);
+            // An all-caps line would otherwise read as a character cue.
+            if !text.chars().any(char::is_lowercase) {
+                out.push('!');
+            }
+            out.push_str(text.trim());
+            out.push_str("\n\n");
+        }
+        ScriptElement::Character(name) => {
+            ensure_blank_line(out);
+            out.push_str(&name.trim().to_uppercase());
+            out.push('\n');
+        }
+        ScriptElement::DualDialogue(name) => {
+            ensure_blank_line(out);
+            out.push_str(&name.trim().to_uppercase());
+            out.push_str(" ^\n");
+        }
+        ScriptElement::Parenthetical(text) => {
+            out.push_str(&format!("({})\n", text.trim()));
+        }
+        ScriptElement::Dialogue(text) => {
+            out.push_str(text.trim());
+            out.push('\n');
+        }
+        ScriptElement::Transition(text) => {
+            let text = text.trim().to_uppercase();
+            ensure_blank_line(out);
+            if !text.ends_with("TO:") {
+                out.push('>');
+            }
+            out.push_str(&text);
+            out.push_str("\n\n");
+        }
+    }
+}
+
+fn is_standard_heading(heading: &str) -> bool {
+    SCENE_HEADING_PREFIXES.iter().any(|prefix| {
+        heading
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with(['.', ' ']))
+    })
+}
+
+/// End `out` with an empty line unless it is empty.
+fn ensure_blank_line(out: &mut String) {
+    if out.is_empty() || out.ends_with("\n\n") {
+        return;
+    }
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eidetic_core::Template;
+
+    #[test]
+    fn arc_fountain_keeps_only_tagged_nodes_and_marks_time_gaps() {
+        let mut project = Template::MultiCam.build_project("Fountain Test");
+        let arc = project.arcs[0].clone();
+        let scenes: Vec<_> = project
+            .timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .iter()
+            .map(|node| node.id)
+            .collect();
+        for node_id in project.timeline.nodes_for_arc(arc.id) {
+            project.timeline.untag_node(node_id, arc.id);
+        }
+        let scripts = [
+            "INT. KITCHEN - DAY\n\nAda drops the locket.\n\nADA\n(quietly)\nNot again.\n\n\
+             BEN (O.S.) ^\nAgain.",
+            "EXT. HARBOR - NIGHT\n\nNobody comes.",
+            "INT. KITCHEN - LATER\n\nUntagged scene.",
+        ];
+        for (node_id, script) in [scenes[0], scenes[2], scenes[1]].into_iter().zip(scripts) {
+            project.timeline.node_mut(node_id).unwrap().content.content = script.to_string();
+        }
+        for (node_id, number) in [(scenes[0], Some("4")), (scenes[2], None)] {
+            project.timeline.node_mut(node_id).unwrap().scene_number = number.map(String::from);
+        }
+        project.timeline.tag_node(scenes[2], arc.id);
+        project.timeline.tag_node(scenes[0], arc.id);
+        let gap = project
+            .timeline
+            .node(scenes[2])
+            .unwrap()
+            .time_range
+            .start_ms
+            - project.timeline.node(scenes[0]).unwrap().time_range.end_ms;
+
+        let fountain = render_arc_fountain(&project, &arc, None);
+
+        assert_eq!(
+            fountain,
+            format!(
+                "Title: Fountain Test - {}\n\n\
+                 INT. KITCHEN - DAY #4#\n\n\
+                 Ada drops the locket.\n\n\
+                 ADA\n(quietly)\nNot again.\n\n\
+                 BEN (O.S.) ^\nAgain.\n\n\
+                 [[{} later]]\n\n\
+                 EXT. HARBOR - NIGHT\n\n\
+                 Nobody comes.\n",
+                arc.name,
+                format_time(gap)
+            )
+        );
+    }
+}
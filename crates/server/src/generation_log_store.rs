@@ -0,0 +1,208 @@
+use eidetic_core::text::truncate_chars;
+use eidetic_core::timeline::node::NodeId;
+use rusqlite::{Connection, Row, params};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::history_store::HistoryStoreError;
+
+/// Most log entries kept per node; older entries are pruned on insert.
+pub(crate) const MAX_GENERATION_LOG_ENTRIES_PER_NODE: usize = 20;
+
+/// Longest prompt or output text stored in one entry, in characters.
+pub(crate) const MAX_GENERATION_LOG_TEXT_CHARS: usize = 32_000;
+
+const TRUNCATION_MARKER: &str = "\n[truncated]";
+
+const GENERATION_LOG_SCHEMA_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS generation_log (
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    node_id       TEXT NOT NULL CHECK (node_id <> ''),
+    timestamp_ms  INTEGER NOT NULL,
+    model         TEXT NOT NULL,
+    system_prompt TEXT NOT NULL,
+    user_prompt   TEXT NOT NULL,
+    output        TEXT NOT NULL,
+    prompt_tokens INTEGER NOT NULL,
+    output_tokens INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_generation_log_node
+    ON generation_log(node_id, id);
+"#;
+
+/// One recorded generation: the prompt sent for a node and what came back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GenerationLogEntry {
+    pub id: i64,
+    pub node_id: Uuid,
+    pub timestamp_ms: u64,
+    pub model: String,
+    pub system_prompt: String,
+    pub user_prompt: String,
+    pub output: String,
+    /// Estimated prompt size.
+    pub prompt_tokens: usize,
+    /// Streamed token count, including automatic continuations.
+    pub output_tokens: usize,
+}
+
+/// A generation to record; ids are assigned by the store.
+#[derive(Debug, Clone)]
+pub(crate) struct NewGenerationLogEntry {
+    pub node_id: NodeId,
+    pub timestamp_ms: u64,
+    pub model: String,
+    pub system_prompt: String,
+    pub user_prompt: String,
+    pub output: String,
+    pub prompt_tokens: usize,
+    pub output_tokens: usize,
+}
+
+pub(crate) fn create_schema(conn: &Connection) -> Result<(), HistoryStoreError> {
+    conn.execute_batch(GENERATION_LOG_SCHEMA_SQL)?;
+    Ok(())
+}
+
+/// Append an entry, truncating long text, and prune the node's log down to
+/// [`MAX_GENERATION_LOG_ENTRIES_PER_NODE`].
+pub(crate) fn record_generation(
+    conn: &mut Connection,
+    entry: &NewGenerationLogEntry,
+) -> Result<(), HistoryStoreError> {
+    create_schema(conn)?;
+    let node_id = entry.node_id.0.to_string();
+    let tx = conn.transaction()?;
+    tx.execute(
+        "INSERT INTO generation_log (
+             node_id, timestamp_ms, model, system_prompt, user_prompt, output,
+             prompt_tokens, output_tokens
+         ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            node_id,
+            entry.timestamp_ms as i64,
+            entry.model,
+            truncate_log_text(&entry.system_prompt),
+            truncate_log_text(&entry.user_prompt),
+            truncate_log_text(&entry.output),
+            entry.prompt_tokens as i64,
+            entry.output_tokens as i64,
+        ],
+    )?;
+    tx.execute(
+        "DELETE FROM generation_log
+         WHERE node_id = ?1
+           AND id NOT IN (
+               SELECT id FROM generation_log
+               WHERE node_id = ?1
+               ORDER BY id DESC
+               LIMIT ?2
+           )",
+        params![node_id, MAX_GENERATION_LOG_ENTRIES_PER_NODE as i64],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Load a node's generation log, newest first.
+pub(crate) fn load_generation_log(
+    conn: &Connection,
+    node_id: NodeId,
+) -> Result<Vec<GenerationLogEntry>, HistoryStoreError> {
+    create_schema(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, node_id, timestamp_ms, model, system_prompt, user_prompt, output,
+                prompt_tokens, output_tokens
+         FROM generation_log
+         WHERE node_id = ?1
+         ORDER BY id DESC",
+    )?;
+    let rows = stmt.query_map([node_id.0.to_string()], read_entry)?;
+    let mut entries = Vec::new();
+    for row in rows {
+        entries.push(row?);
+    }
+    Ok(entries)
+}
+
+fn read_entry(row: &Row<'_>) -> Result<GenerationLogEntry, rusqlite::Error> {
+    let node_id: String = row.get(1)?;
+    let timestamp_ms: i64 = row.get(2)?;
+    let prompt_tokens: i64 = row.get(7)?;
+    let output_tokens: i64 = row.get(8)?;
+
+    Ok(GenerationLogEntry {
+        id: row.get(0)?,
+        node_id: Uuid::parse_str(&node_id).map_err(|error| {
+            rusqlite::Error::FromSqlConversionFailure(
+                1,
+                rusqlite::types::Type::Text,
+                Box::new(error),
+            )
+        })?,
+        timestamp_ms: timestamp_ms.max(0) as u64,
+        model: row.get(3)?,
+        system_prompt: row.get(4)?,
+        user_prompt: row.get(5)?,
+        output: row.get(6)?,
+        prompt_tokens: prompt_tokens.max(0) as usize,
+        output_tokens: output_tokens.max(0) as usize,
+    })
+}
+
+fn truncate_log_text(text: &str) -> String {
+    let truncated = truncate_chars(text, MAX_GENERATION_LOG_TEXT_CHARS);
+    if truncated.len() < text.len() {
+        format!("{truncated}{TRUNCATION_MARKER}")
+    } else {
+        text.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(node_id: NodeId, output: String) -> NewGenerationLogEntry {
+        NewGenerationLogEntry {
+            node_id,
+            timestamp_ms: 1_000,
+            model: "test-model".into(),
+            system_prompt: "system".into(),
+            user_prompt: "user".into(),
+            output,
+            prompt_tokens: 12,
+            output_tokens: 34,
+        }
+    }
+
+    #[test]
+    fn log_keeps_newest_entries_per_node_and_truncates_long_text() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        let node_id = NodeId(Uuid::new_v4());
+        let other_id = NodeId(Uuid::new_v4());
+        record_generation(&mut conn, &entry(other_id, "other".into())).unwrap();
+        for index in 0..MAX_GENERATION_LOG_ENTRIES_PER_NODE + 3 {
+            record_generation(&mut conn, &entry(node_id, format!("take {index}"))).unwrap();
+        }
+        let long = "é".repeat(MAX_GENERATION_LOG_TEXT_CHARS + 5);
+        record_generation(&mut conn, &entry(node_id, long)).unwrap();
+
+        let log = load_generation_log(&conn, node_id).unwrap();
+
+        assert_eq!(log.len(), MAX_GENERATION_LOG_ENTRIES_PER_NODE);
+        assert!(log[0].output.ends_with(TRUNCATION_MARKER));
+        assert_eq!(
+            log[0].output.chars().count(),
+            MAX_GENERATION_LOG_TEXT_CHARS + TRUNCATION_MARKER.chars().count()
+        );
+        assert_eq!(
+            log[1].output,
+            format!("take {}", MAX_GENERATION_LOG_ENTRIES_PER_NODE + 2)
+        );
+        assert_eq!(log[0].node_id, node_id.0);
+        assert_eq!(log[1].prompt_tokens, 12);
+        assert_eq!(log[1].output_tokens, 34);
+        assert_eq!(load_generation_log(&conn, other_id).unwrap().len(), 1);
+    }
+}
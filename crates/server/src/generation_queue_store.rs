@@ -0,0 +1,243 @@
+use eidetic_core::timeline::node::NodeId;
+use rusqlite::{Connection, OptionalExtension, Row, params};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::history_store::HistoryStoreError;
+
+const GENERATION_QUEUE_SCHEMA_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS generation_queue (
+    id      INTEGER PRIMARY KEY AUTOINCREMENT,
+    node_id TEXT NOT NULL UNIQUE CHECK (node_id <> ''),
+    status  TEXT NOT NULL CHECK (status IN ('pending', 'running', 'done', 'failed')),
+    error   TEXT
+);
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenerationQueueStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+impl GenerationQueueStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Done => "done",
+            Self::Failed => "failed",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(Self::Pending),
+            "running" => Some(Self::Running),
+            "done" => Some(Self::Done),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// One node in the generation queue, in queue order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GenerationQueueItem {
+    pub node_id: Uuid,
+    pub status: GenerationQueueStatus,
+    /// 1-based place among pending items; absent once the item has started.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub(crate) fn create_schema(conn: &Connection) -> Result<(), HistoryStoreError> {
+    conn.execute_batch(GENERATION_QUEUE_SCHEMA_SQL)?;
+    Ok(())
+}
+
+/// Append nodes to the end of the queue. Nodes already pending or running
+/// keep their place; finished ones are queued again. Returns how many were added.
+pub(crate) fn enqueue(
+    conn: &mut Connection,
+    node_ids: &[NodeId],
+) -> Result<usize, HistoryStoreError> {
+    create_schema(conn)?;
+    let tx = conn.transaction()?;
+    let mut added = 0;
+    for node_id in node_ids {
+        let node_id = node_id.0.to_string();
+        tx.execute(
+            "DELETE FROM generation_queue
+             WHERE node_id = ?1 AND status IN ('done', 'failed')",
+            [&node_id],
+        )?;
+        added += tx.execute(
+            "INSERT OR IGNORE INTO generation_queue (node_id, status) VALUES (?1, 'pending')",
+            [&node_id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(added)
+}
+
+/// Mark the first pending node running and return it.
+pub(crate) fn claim_next(conn: &mut Connection) -> Result<Option<NodeId>, HistoryStoreError> {
+    create_schema(conn)?;
+    let tx = conn.transaction()?;
+    let next = tx
+        .query_row(
+            "SELECT id, node_id FROM generation_queue
+             WHERE status = 'pending'
+             ORDER BY id
+             LIMIT 1",
+            [],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+        )
+        .optional()?;
+    let Some((id, node_id)) = next else {
+        return Ok(None);
+    };
+    tx.execute(
+        "UPDATE generation_queue SET status = 'running' WHERE id = ?1",
+        [id],
+    )?;
+    tx.commit()?;
+    Ok(Some(NodeId(parse_uuid(&node_id)?)))
+}
+
+/// Record how a running node's generation ended.
+pub(crate) fn finish(
+    conn: &Connection,
+    node_id: NodeId,
+    error: Option<&str>,
+) -> Result<(), HistoryStoreError> {
+    create_schema(conn)?;
+    let status = match error {
+        Some(_) => GenerationQueueStatus::Failed,
+        None => GenerationQueueStatus::Done,
+    };
+    conn.execute(
+        "UPDATE generation_queue SET status = ?2, error = ?3
+         WHERE node_id = ?1 AND status = 'running'",
+        params![node_id.0.to_string(), status.as_str(), error],
+    )?;
+    Ok(())
+}
+
+/// Drop a pending node. Returns false when it was not pending.
+pub(crate) fn cancel(conn: &Connection, node_id: NodeId) -> Result<bool, HistoryStoreError> {
+    create_schema(conn)?;
+    let removed = conn.execute(
+        "DELETE FROM generation_queue WHERE node_id = ?1 AND status = 'pending'",
+        [node_id.0.to_string()],
+    )?;
+    Ok(removed > 0)
+}
+
+/// Put nodes left running by an interrupted worker back at their place in line.
+pub(crate) fn requeue_running(conn: &Connection) -> Result<usize, HistoryStoreError> {
+    create_schema(conn)?;
+    Ok(conn.execute(
+        "UPDATE generation_queue SET status = 'pending' WHERE status = 'running'",
+        [],
+    )?)
+}
+
+pub(crate) fn has_pending(conn: &Connection) -> Result<bool, HistoryStoreError> {
+    create_schema(conn)?;
+    Ok(conn
+        .query_row(
+            "SELECT 1 FROM generation_queue WHERE status = 'pending' LIMIT 1",
+            [],
+            |_| Ok(()),
+        )
+        .optional()?
+        .is_some())
+}
+
+/// Every queued node in queue order.
+pub(crate) fn load_queue(conn: &Connection) -> Result<Vec<GenerationQueueItem>, HistoryStoreError> {
+    create_schema(conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT node_id, status, error FROM generation_queue
+         ORDER BY id",
+    )?;
+    let rows = stmt.query_map([], read_item)?;
+    let mut items = Vec::new();
+    let mut pending = 0;
+    for row in rows {
+        let mut item = row?;
+        if item.status == GenerationQueueStatus::Pending {
+            pending += 1;
+            item.position = Some(pending);
+        }
+        items.push(item);
+    }
+    Ok(items)
+}
+
+fn read_item(row: &Row<'_>) -> Result<GenerationQueueItem, rusqlite::Error> {
+    let node_id: String = row.get(0)?;
+    let status: String = row.get(1)?;
+    let conversion = |index, error: Box<dyn std::error::Error + Send + Sync>| {
+        rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, error)
+    };
+
+    Ok(GenerationQueueItem {
+        node_id: Uuid::parse_str(&node_id).map_err(|error| conversion(0, Box::new(error)))?,
+        status: GenerationQueueStatus::parse(&status)
+            .ok_or_else(|| conversion(1, format!("unknown queue status {status:?}").into()))?,
+        position: None,
+        error: row.get(2)?,
+    })
+}
+
+fn parse_uuid(value: &str) -> Result<Uuid, HistoryStoreError> {
+    Uuid::parse_str(value).map_err(|error| HistoryStoreError::InvalidId(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_drains_in_order_and_survives_an_interrupted_worker() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        let ids: Vec<NodeId> = (0..3).map(|_| NodeId(Uuid::new_v4())).collect();
+
+        assert_eq!(enqueue(&mut conn, &ids).unwrap(), 3);
+        assert_eq!(enqueue(&mut conn, &ids[..1]).unwrap(), 0);
+        assert_eq!(claim_next(&mut conn).unwrap(), Some(ids[0]));
+        finish(&conn, ids[0], None).unwrap();
+        assert_eq!(claim_next(&mut conn).unwrap(), Some(ids[1]));
+        assert!(cancel(&conn, ids[2]).unwrap());
+        assert!(!cancel(&conn, ids[1]).unwrap());
+
+        // A restart leaves the running item behind; it goes back in line.
+        assert_eq!(requeue_running(&conn).unwrap(), 1);
+        let queue = load_queue(&conn).unwrap();
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue[0].status, GenerationQueueStatus::Done);
+        assert_eq!(queue[1].node_id, ids[1].0);
+        assert_eq!(queue[1].position, Some(1));
+
+        assert_eq!(claim_next(&mut conn).unwrap(), Some(ids[1]));
+        finish(&conn, ids[1], Some("backend offline")).unwrap();
+        assert!(!has_pending(&conn).unwrap());
+        assert_eq!(enqueue(&mut conn, &ids[..2]).unwrap(), 2);
+        let queue = load_queue(&conn).unwrap();
+        assert!(
+            queue
+                .iter()
+                .all(|item| item.status == GenerationQueueStatus::Pending)
+        );
+        assert_eq!(queue[0].node_id, ids[0].0);
+        assert_eq!(claim_next(&mut conn).unwrap(), Some(ids[0]));
+    }
+}
@@ -7,24 +7,37 @@ pub mod agent_workflow_harness;
 pub mod agent_workflow_service;
 pub(crate) mod agent_workflow_store;
 pub(crate) mod ai_affect_context_prompt;
+pub mod ai_arc_suggestions;
 pub(crate) mod ai_backends;
 pub(crate) mod ai_bible_context_prompt;
 pub(crate) mod ai_context_projection;
+pub mod ai_continuity_audit;
+pub mod ai_generation_queue;
 pub(crate) mod ai_generation_runtime;
 pub mod ai_generation_service;
+pub mod ai_notes_drift;
 pub mod ai_service;
+pub(crate) mod arc_entity_evolution;
 pub mod backend_error;
 pub mod backend_task;
+pub(crate) mod beat_sheet_export;
+pub(crate) mod bible_entity_presence;
+pub(crate) mod bible_export;
 pub(crate) mod bible_graph_command;
 pub(crate) mod bible_graph_edge_store;
 pub(crate) mod bible_graph_field_store;
 pub(crate) mod bible_graph_schema;
 pub(crate) mod bible_graph_snapshot_store;
 pub(crate) mod bible_graph_store;
+#[cfg(test)]
+pub(crate) mod bible_graph_test_support;
 pub(crate) mod bible_graph_value_store;
+pub(crate) mod bible_location_promotion;
 pub mod bible_render_graph_projection;
 pub(crate) mod bible_render_graph_query;
+pub(crate) mod bible_transfer;
 pub(crate) mod change_review_projection;
+pub mod checkpoint_service;
 pub(crate) mod child_plan_projection_store;
 pub(crate) mod child_plan_store;
 pub mod command_service;
@@ -35,9 +48,13 @@ pub(crate) mod command_service_timeline;
 pub(crate) mod command_service_timeline_requests;
 pub mod context_influence_service;
 pub(crate) mod context_influence_store;
+pub(crate) mod context_references;
 pub(crate) mod embeddings;
 pub(crate) mod export;
 pub mod export_service;
+pub(crate) mod fountain_export;
+pub(crate) mod generation_log_store;
+pub(crate) mod generation_queue_store;
 pub mod graph_proposal_service;
 pub(crate) mod graph_proposal_store;
 pub(crate) mod history_read_store;
@@ -64,6 +81,7 @@ pub(crate) mod script_segment_replace;
 pub(crate) mod script_store;
 pub(crate) mod script_store_codec;
 pub(crate) mod script_store_schema;
+pub mod search_service;
 pub(crate) mod semantic_dependency_store;
 pub(crate) mod semantic_proposal_accept;
 pub(crate) mod semantic_proposal_store;
@@ -78,10 +96,16 @@ pub(crate) mod timeline_command;
 pub(crate) mod timeline_command_history;
 pub(crate) mod timeline_command_history_codec;
 pub(crate) mod timeline_create_intent;
+pub(crate) mod timeline_generation_order_history;
+pub(crate) mod timeline_node_arc_history;
 pub(crate) mod timeline_node_delete_history;
+pub(crate) mod timeline_node_duplicate_history;
 pub(crate) mod timeline_node_split_history;
 pub(crate) mod timeline_node_store;
+pub(crate) mod timeline_normalize_history;
+pub(crate) mod timeline_projection_cache;
 pub(crate) mod timeline_relationship_store;
+pub(crate) mod timeline_scene_number_history;
 pub mod validation;
 pub(crate) mod vector_store;
 pub mod ydoc;
@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 
 use eidetic_core::Project;
 use eidetic_core::contracts::ObjectKind;
+use eidetic_core::project::{Episode, EpisodeHeader, EpisodeId};
 use eidetic_core::reference::{ReferenceDocument, ReferenceType};
 use eidetic_core::story::arc::{ArcId, ArcType, Color, StoryArc};
 use eidetic_core::timeline::Timeline;
@@ -20,6 +21,8 @@ use uuid::Uuid;
 pub struct ProjectEntry {
     pub name: String,
     pub path: PathBuf,
+    /// Last modification time as an RFC3339 UTC timestamp, or empty when the
+    /// filesystem doesn't report one.
     pub modified: String,
 }
 
@@ -48,18 +51,72 @@ pub fn project_save_path(name: &str) -> PathBuf {
 
 // ─── Schema ────────────────────────────────────────────────────────
 
+/// Newest project schema this build reads and writes.
+const CURRENT_SCHEMA_VERSION: u32 = 7;
+
+/// An upgrade from one schema version to the next, run inside a transaction.
+type MigrationStep = fn(&Connection) -> Result<(), String>;
+
+/// Upgrade steps keyed by the version they start from. Older layouts whose
+/// loaders were retired have no entry and are rejected.
+const MIGRATIONS: &[(u32, MigrationStep)] = &[
+    (3, migrate_v3_add_node_columns),
+    (4, migrate_v4_add_episodes),
+    (5, migrate_v5_add_reference_scope),
+    (6, migrate_v6_add_project_ai_settings),
+];
+
+/// Version 4 adds `nodes.scene_number` (and `pinned`, for v3 files that
+/// predate it).
+fn migrate_v3_add_node_columns(conn: &Connection) -> Result<(), String> {
+    crate::timeline_node_store::create_node_schema(conn)
+        .map_err(|e| format!("add node columns: {e}"))
+}
+
+/// Version 5 adds the `episodes` table, seeded with the existing timeline as
+/// the active first episode.
+fn migrate_v4_add_episodes(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(EPISODES_SCHEMA_SQL)
+        .map_err(|e| format!("add episodes table: {e}"))?;
+    if table_has_rows(conn, "episodes")? {
+        return Ok(());
+    }
+    insert_episode(conn, &EpisodeHeader::default(), None)
+}
+
+/// Version 6 adds `reference_documents.scope`; existing documents stay global.
+fn migrate_v5_add_reference_scope(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "ALTER TABLE reference_documents ADD COLUMN scope TEXT NOT NULL DEFAULT '{}'",
+        [],
+    )
+    .map_err(|e| format!("add reference scope column: {e}"))?;
+    Ok(())
+}
+
+/// Version 7 adds `project.ai_settings`; existing projects use the global
+/// AI config.
+fn migrate_v6_add_project_ai_settings(conn: &Connection) -> Result<(), String> {
+    conn.execute(
+        "ALTER TABLE project ADD COLUMN ai_settings TEXT NOT NULL DEFAULT '{}'",
+        [],
+    )
+    .map_err(|e| format!("add project ai settings column: {e}"))?;
+    Ok(())
+}
+
 const SCHEMA_SQL: &str = r#"
 CREATE TABLE IF NOT EXISTS schema_meta (
     key   TEXT PRIMARY KEY,
     value TEXT NOT NULL
 );
-INSERT OR IGNORE INTO schema_meta (key, value) VALUES ('version', '3');
 
 CREATE TABLE IF NOT EXISTS project (
     id                INTEGER PRIMARY KEY CHECK (id = 1),
     name              TEXT NOT NULL,
     premise           TEXT NOT NULL DEFAULT '',
-    total_duration_ms INTEGER NOT NULL
+    total_duration_ms INTEGER NOT NULL,
+    ai_settings       TEXT NOT NULL DEFAULT '{}'
 );
 
 CREATE TABLE IF NOT EXISTS episode_structure (
@@ -97,7 +154,9 @@ CREATE TABLE IF NOT EXISTS nodes (
     name         TEXT NOT NULL,
     content_json TEXT NOT NULL DEFAULT '{}',
     beat_type    TEXT,
-    locked       INTEGER NOT NULL DEFAULT 0
+    locked       INTEGER NOT NULL DEFAULT 0,
+    pinned       INTEGER NOT NULL DEFAULT 0,
+    scene_number TEXT
 );
 CREATE INDEX IF NOT EXISTS idx_nodes_parent ON nodes(parent_id) WHERE parent_id IS NOT NULL;
 CREATE INDEX IF NOT EXISTS idx_nodes_level ON nodes(level);
@@ -119,7 +178,8 @@ CREATE TABLE IF NOT EXISTS reference_documents (
     id       TEXT PRIMARY KEY,
     name     TEXT NOT NULL,
     content  TEXT NOT NULL,
-    doc_type TEXT NOT NULL
+    doc_type TEXT NOT NULL,
+    scope    TEXT NOT NULL DEFAULT '{}'
 );
 
 CREATE TABLE IF NOT EXISTS ydoc_state (
@@ -128,9 +188,30 @@ CREATE TABLE IF NOT EXISTS ydoc_state (
 );
 "#;
 
+/// The active episode's timeline lives in the node tables and its row has no
+/// `timeline_json`; parked episodes carry their whole timeline as JSON.
+const EPISODES_SCHEMA_SQL: &str = r#"
+CREATE TABLE IF NOT EXISTS episodes (
+    id            TEXT PRIMARY KEY,
+    title         TEXT NOT NULL,
+    sort_order    INTEGER NOT NULL DEFAULT 0,
+    active        INTEGER NOT NULL DEFAULT 0,
+    timeline_json TEXT
+);
+"#;
+
 fn create_schema(conn: &Connection) -> Result<(), String> {
     conn.execute_batch(SCHEMA_SQL)
         .map_err(|e| format!("schema error: {e}"))?;
+    conn.execute_batch(EPISODES_SCHEMA_SQL)
+        .map_err(|e| format!("episodes schema error: {e}"))?;
+    conn.execute(
+        "INSERT OR IGNORE INTO schema_meta (key, value) VALUES ('version', ?1)",
+        [CURRENT_SCHEMA_VERSION.to_string()],
+    )
+    .map_err(|e| format!("schema version error: {e}"))?;
+    crate::timeline_node_store::create_node_schema(conn)
+        .map_err(|e| format!("node schema error: {e}"))?;
     crate::history_store::create_schema(conn).map_err(|e| format!("history schema error: {e}"))
 }
 
@@ -143,6 +224,7 @@ fn clear_all_tables(conn: &Connection) -> Result<(), String> {
          DELETE FROM arcs;
          DELETE FROM reference_documents;
          DELETE FROM episode_structure;
+         DELETE FROM episodes;
          DELETE FROM project;
          DELETE FROM ydoc_state;",
     )
@@ -191,48 +273,32 @@ fn save_project_sync(
     clear_all_tables(&tx)?;
 
     // Project metadata.
+    let ai_settings_json = serde_json::to_string(&project.ai_settings)
+        .map_err(|e| format!("serialize ai_settings: {e}"))?;
     tx.execute(
-        "INSERT INTO project (id, name, premise, total_duration_ms) VALUES (1, ?1, ?2, ?3)",
+        "INSERT INTO project (id, name, premise, total_duration_ms, ai_settings)
+         VALUES (1, ?1, ?2, ?3, ?4)",
         params![
             project.name,
             project.premise,
-            timeline.total_duration_ms as i64
+            timeline.total_duration_ms as i64,
+            ai_settings_json,
         ],
     )
     .map_err(|e| format!("insert project: {e}"))?;
 
-    // Episode structure.
-    let segments_json = serde_json::to_string(&timeline.structure.segments)
-        .map_err(|e| format!("serialize segments: {e}"))?;
-    tx.execute(
-        "INSERT INTO episode_structure (id, template_name, segments_json) VALUES (1, ?1, ?2)",
-        params![timeline.structure.template_name, segments_json],
-    )
-    .map_err(|e| format!("insert episode_structure: {e}"))?;
-
     // Arcs.
     for arc in &arcs {
         insert_arc(&tx, arc)?;
     }
 
-    // Tracks.
-    for track in &timeline.tracks {
-        insert_track(&tx, track)?;
-    }
-
-    // Nodes.
-    for node in &timeline.nodes {
-        insert_node(&tx, node)?;
-    }
-
-    // Node-Arc tags.
-    for node_arc in &timeline.node_arcs {
-        insert_node_arc(&tx, node_arc)?;
-    }
+    // Active episode timeline.
+    insert_timeline(&tx, &timeline)?;
 
-    // Relationships.
-    for rel in &timeline.relationships {
-        insert_relationship(&tx, rel)?;
+    // Episodes.
+    insert_episode(&tx, &project.active_episode, None)?;
+    for episode in &project.episodes {
+        insert_episode(&tx, &episode.header, Some(&episode.timeline))?;
     }
 
     // Reference documents.
@@ -343,6 +409,65 @@ fn persisted_tracks_or_project_tracks(
     }
 }
 
+/// Write a timeline into the node tables, which must be empty.
+fn insert_timeline(conn: &Connection, timeline: &Timeline) -> Result<(), String> {
+    // Episode structure.
+    let segments_json = serde_json::to_string(&timeline.structure.segments)
+        .map_err(|e| format!("serialize segments: {e}"))?;
+    conn.execute(
+        "INSERT INTO episode_structure (id, template_name, segments_json) VALUES (1, ?1, ?2)",
+        params![timeline.structure.template_name, segments_json],
+    )
+    .map_err(|e| format!("insert episode_structure: {e}"))?;
+
+    // Tracks.
+    for track in &timeline.tracks {
+        insert_track(conn, track)?;
+    }
+
+    // Nodes.
+    for node in &timeline.nodes {
+        insert_node(conn, node)?;
+    }
+
+    // Node-Arc tags.
+    for node_arc in &timeline.node_arcs {
+        insert_node_arc(conn, node_arc)?;
+    }
+
+    // Relationships.
+    for rel in &timeline.relationships {
+        insert_relationship(conn, rel)?;
+    }
+    Ok(())
+}
+
+/// `timeline` is `None` for the active episode, whose timeline is in the node
+/// tables.
+fn insert_episode(
+    conn: &Connection,
+    header: &EpisodeHeader,
+    timeline: Option<&Timeline>,
+) -> Result<(), String> {
+    let timeline_json = timeline
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| format!("serialize episode timeline: {e}"))?;
+    conn.execute(
+        "INSERT INTO episodes (id, title, sort_order, active, timeline_json)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            header.id.0.to_string(),
+            header.title,
+            header.sort_order,
+            timeline.is_none(),
+            timeline_json
+        ],
+    )
+    .map_err(|e| format!("insert episode: {e}"))?;
+    Ok(())
+}
+
 fn insert_arc(conn: &Connection, arc: &StoryArc) -> Result<(), String> {
     let arc_type_json =
         serde_json::to_string(&arc.arc_type).map_err(|e| format!("serialize arc_type: {e}"))?;
@@ -365,6 +490,14 @@ fn insert_arc(conn: &Connection, arc: &StoryArc) -> Result<(), String> {
     Ok(())
 }
 
+/// Collapse or expand every track row. Saves keep the persisted tracks once
+/// the timeline is stored, so track UI state is written here directly.
+pub(crate) fn set_all_tracks_collapsed(conn: &Connection, collapsed: bool) -> Result<(), String> {
+    conn.execute("UPDATE tracks SET collapsed = ?1", [collapsed as i32])
+        .map_err(|e| format!("update tracks: {e}"))?;
+    Ok(())
+}
+
 fn insert_track(conn: &Connection, track: &Track) -> Result<(), String> {
     let level_str = track.level.label();
     conn.execute(
@@ -396,8 +529,8 @@ fn insert_node(conn: &Connection, node: &StoryNode) -> Result<(), String> {
 
     conn.execute(
         "INSERT INTO nodes (id, parent_id, level, sort_order, start_ms, end_ms,
-                            name, content_json, beat_type, locked)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                            name, content_json, beat_type, locked, pinned, scene_number)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
         params![
             node.id.0.to_string(),
             parent_id,
@@ -409,6 +542,8 @@ fn insert_node(conn: &Connection, node: &StoryNode) -> Result<(), String> {
             content_json,
             beat_type_json,
             node.locked as i32,
+            node.pinned as i32,
+            node.scene_number,
         ],
     )
     .map_err(|e| format!("insert node: {e}"))?;
@@ -447,9 +582,18 @@ fn insert_relationship(conn: &Connection, rel: &Relationship) -> Result<(), Stri
 fn insert_reference_document(conn: &Connection, doc: &ReferenceDocument) -> Result<(), String> {
     let doc_type_json =
         serde_json::to_string(&doc.doc_type).map_err(|e| format!("serialize doc_type: {e}"))?;
+    let scope_json =
+        serde_json::to_string(&doc.scope).map_err(|e| format!("serialize scope: {e}"))?;
     conn.execute(
-        "INSERT INTO reference_documents (id, name, content, doc_type) VALUES (?1, ?2, ?3, ?4)",
-        params![doc.id.0.to_string(), doc.name, doc.content, doc_type_json,],
+        "INSERT INTO reference_documents (id, name, content, doc_type, scope)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            doc.id.0.to_string(),
+            doc.name,
+            doc.content,
+            doc_type_json,
+            scope_json,
+        ],
     )
     .map_err(|e| format!("insert reference_document: {e}"))?;
     Ok(())
@@ -470,15 +614,21 @@ pub async fn load_project(path: &Path) -> Result<(Project, Option<Vec<u8>>), Str
 }
 
 fn load_project_sync(path: &Path) -> Result<(Project, Option<Vec<u8>>), String> {
-    let conn = Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
-        .map_err(|e| format!("sqlite open error: {e}"))?;
+    let mut conn = open_read_only(path)?;
 
     let version = read_schema_version(&conn);
-    if version != 3 {
+    if version > CURRENT_SCHEMA_VERSION {
         return Err(format!(
-            "unsupported project schema version {version}; expected 3"
+            "project was created by a newer version of Eidetic (schema {version}; \
+             this build reads up to {CURRENT_SCHEMA_VERSION})"
         ));
     }
+    if version < CURRENT_SCHEMA_VERSION {
+        let steps = migration_steps(version)?;
+        drop(conn);
+        migrate_to_latest(path, version, &steps)?;
+        conn = open_read_only(path)?;
+    }
 
     let project = load_project_v2(&conn, path)?;
     let ydoc_state = read_ydoc_state(&conn)?;
@@ -486,6 +636,58 @@ fn load_project_sync(path: &Path) -> Result<(Project, Option<Vec<u8>>), String>
     Ok((project, ydoc_state))
 }
 
+fn open_read_only(path: &Path) -> Result<Connection, String> {
+    Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("sqlite open error: {e}"))
+}
+
+/// The upgrade steps from `from_version` to [`CURRENT_SCHEMA_VERSION`], or an
+/// error if any link in the chain is missing. Checked before the file is
+/// opened for writing, so an unsupported project is never touched.
+fn migration_steps(from_version: u32) -> Result<Vec<MigrationStep>, String> {
+    (from_version..CURRENT_SCHEMA_VERSION)
+        .map(|version| {
+            MIGRATIONS
+                .iter()
+                .find(|(from, _)| *from == version)
+                .map(|(_, step)| *step)
+                .ok_or_else(|| {
+                    format!(
+                        "unsupported project schema version {from_version}; \
+                         no migration from version {version} to {}",
+                        version + 1
+                    )
+                })
+        })
+        .collect()
+}
+
+/// Run `steps` in order, each in its own transaction that also records the
+/// version it reaches.
+fn migrate_to_latest(
+    path: &Path,
+    from_version: u32,
+    steps: &[MigrationStep],
+) -> Result<(), String> {
+    let mut conn = crate::sqlite::open_write_connection(path)
+        .map_err(|e| format!("sqlite open error: {e}"))?;
+    for (version, step) in (from_version + 1..).zip(steps) {
+        let tx = conn
+            .transaction()
+            .map_err(|e| format!("transaction error: {e}"))?;
+        step(&tx)?;
+        tx.execute(
+            "INSERT OR REPLACE INTO schema_meta (key, value) VALUES ('version', ?1)",
+            [version.to_string()],
+        )
+        .map_err(|e| format!("record schema version: {e}"))?;
+        tx.commit()
+            .map_err(|e| format!("commit migration to version {version}: {e}"))?;
+        tracing::info!("migrated {} to schema version {version}", path.display());
+    }
+    Ok(())
+}
+
 fn read_schema_version(conn: &Connection) -> u32 {
     conn.query_row(
         "SELECT value FROM schema_meta WHERE key = 'version'",
@@ -509,56 +711,192 @@ fn read_ydoc_state(conn: &Connection) -> Result<Option<Vec<u8>>, String> {
 
 fn load_project_v2(conn: &Connection, path: &Path) -> Result<Project, String> {
     // Project metadata.
-    let (name, premise, total_duration_ms): (String, String, i64) = conn
+    let (name, premise, total_duration_ms, ai_settings_json): (String, String, i64, String) = conn
         .query_row(
-            "SELECT name, premise, total_duration_ms FROM project WHERE id = 1",
+            "SELECT name, premise, total_duration_ms, ai_settings FROM project WHERE id = 1",
             [],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
         )
         .map_err(|e| format!("read project: {e}"))?;
-
-    // Episode structure.
-    let structure = read_episode_structure(conn)?;
+    let ai_settings =
+        serde_json::from_str(&ai_settings_json).map_err(|e| format!("parse ai_settings: {e}"))?;
 
     // Arcs.
     let arcs = read_arcs(conn)?;
 
-    // Tracks.
-    let tracks = read_tracks(conn)?;
+    // Active episode timeline.
+    let mut timeline = read_timeline(conn, total_duration_ms as u64)?;
+    check_timeline_integrity(&mut timeline, path);
 
-    // Nodes.
-    let nodes = read_nodes(conn)?;
-
-    // Node-Arc tags.
-    let node_arcs = read_node_arcs(conn)?;
-
-    // Relationships.
-    let relationships = read_relationships(conn)?;
+    // Episodes.
+    let (active_episode, episodes) = read_episodes(conn)?;
 
     // Reference documents.
     let references = read_reference_documents(conn)?;
 
-    let timeline = Timeline {
-        total_duration_ms: total_duration_ms as u64,
-        tracks,
-        nodes,
-        node_arcs,
-        relationships,
-        structure,
-    };
-
     let project = Project {
         name,
         premise,
         timeline,
+        active_episode,
+        episodes,
         arcs,
         references,
+        ai_settings,
     };
 
     tracing::debug!("loaded project from {}", path.display());
     Ok(project)
 }
 
+/// Read the active episode's timeline from the node tables.
+fn read_timeline(conn: &Connection, total_duration_ms: u64) -> Result<Timeline, String> {
+    Ok(Timeline {
+        total_duration_ms,
+        structure: read_episode_structure(conn)?,
+        tracks: read_tracks(conn)?,
+        nodes: read_nodes(conn)?,
+        node_arcs: read_node_arcs(conn)?,
+        relationships: read_relationships(conn)?,
+    })
+}
+
+/// The active episode's header and the parked episodes. A project saved
+/// before episodes existed has no rows and gets a default header.
+fn read_episodes(conn: &Connection) -> Result<(EpisodeHeader, Vec<Episode>), String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, title, sort_order, timeline_json FROM episodes
+             ORDER BY sort_order, title",
+        )
+        .map_err(|e| format!("prepare episodes: {e}"))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, u32>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })
+        .map_err(|e| format!("query episodes: {e}"))?;
+
+    let mut active = None;
+    let mut episodes = Vec::new();
+    for row in rows {
+        let (id, title, sort_order, timeline_json) =
+            row.map_err(|e| format!("read episode row: {e}"))?;
+        let header = EpisodeHeader {
+            id: EpisodeId(parse_uuid(&id)?),
+            title,
+            sort_order,
+        };
+        match timeline_json {
+            None => active = Some(header),
+            Some(json) => episodes.push(Episode {
+                header,
+                timeline: serde_json::from_str(&json)
+                    .map_err(|e| format!("parse episode timeline {id}: {e}"))?,
+            }),
+        }
+    }
+    Ok((active.unwrap_or_default(), episodes))
+}
+
+// ─── Episodes ──────────────────────────────────────────────────────
+
+/// Make `episode_id` the active episode on disk: the current timeline is
+/// parked as JSON and the target's is written into the node tables, all in
+/// one transaction.
+pub async fn switch_episode(path: &Path, episode_id: EpisodeId) -> Result<(), String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || switch_episode_sync(&path, episode_id))
+        .await
+        .map_err(|e| format!("spawn_blocking error: {e}"))?
+}
+
+fn switch_episode_sync(path: &Path, episode_id: EpisodeId) -> Result<(), String> {
+    let mut conn = crate::sqlite::open_write_connection(path)
+        .map_err(|e| format!("sqlite open error: {e}"))?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| format!("transaction error: {e}"))?;
+
+    let target_json: Option<String> = tx
+        .query_row(
+            "SELECT timeline_json FROM episodes WHERE id = ?1",
+            [episode_id.0.to_string()],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("read episode {}: {e}", episode_id.0))?;
+    let Some(target_json) = target_json else {
+        // Already active.
+        return Ok(());
+    };
+    let target: Timeline = serde_json::from_str(&target_json)
+        .map_err(|e| format!("parse episode timeline {}: {e}", episode_id.0))?;
+
+    let total_duration_ms: i64 = tx
+        .query_row(
+            "SELECT total_duration_ms FROM project WHERE id = 1",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("read project: {e}"))?;
+    let current = read_timeline(&tx, total_duration_ms as u64)?;
+    let current_json =
+        serde_json::to_string(&current).map_err(|e| format!("serialize episode timeline: {e}"))?;
+    tx.execute(
+        "UPDATE episodes SET active = 0, timeline_json = ?1 WHERE timeline_json IS NULL",
+        [current_json],
+    )
+    .map_err(|e| format!("park active episode: {e}"))?;
+
+    tx.execute_batch(
+        "DELETE FROM node_arcs;
+         DELETE FROM relationships;
+         DELETE FROM nodes;
+         DELETE FROM tracks;
+         DELETE FROM episode_structure;",
+    )
+    .map_err(|e| format!("clear active episode: {e}"))?;
+    insert_timeline(&tx, &target)?;
+    tx.execute(
+        "UPDATE project SET total_duration_ms = ?1 WHERE id = 1",
+        [target.total_duration_ms as i64],
+    )
+    .map_err(|e| format!("update project duration: {e}"))?;
+    tx.execute(
+        "UPDATE episodes SET active = 1, timeline_json = NULL WHERE id = ?1",
+        [episode_id.0.to_string()],
+    )
+    .map_err(|e| format!("activate episode: {e}"))?;
+
+    tx.commit().map_err(|e| format!("commit error: {e}"))
+}
+
+/// Rows are read straight into the timeline without `add_node`, so a
+/// hand-edited database can break hierarchy invariants. Log every issue and
+/// merge extra Premise nodes into the earliest one in memory; the file is left as
+/// is until the next save.
+fn check_timeline_integrity(timeline: &mut Timeline, path: &Path) {
+    let issues = timeline.validate_all();
+    if issues.is_empty() {
+        return;
+    }
+    for issue in &issues {
+        tracing::warn!("timeline integrity issue in {}: {issue}", path.display());
+    }
+    let merged = timeline.repair_premise_invariant();
+    if !merged.is_empty() {
+        tracing::warn!(
+            "merged {} extra Premise node(s) in {}",
+            merged.len(),
+            path.display()
+        );
+    }
+}
+
 fn parse_uuid(s: &str) -> Result<Uuid, String> {
     Uuid::parse_str(s).map_err(|e| format!("parse UUID '{s}': {e}"))
 }
@@ -623,8 +961,8 @@ fn read_arcs(conn: &Connection) -> Result<Vec<StoryArc>, String> {
         let parent_arc_id = parent_arc_id_str
             .map(|s| parse_uuid(&s).map(ArcId))
             .transpose()?;
-        let arc_type: ArcType =
-            serde_json::from_str(&arc_type_json).map_err(|e| format!("parse arc_type: {e}"))?;
+        let arc_type: ArcType = serde_json::from_str(&arc_type_json)
+            .map_err(|e| format!("parse arc_type of arc {id_str}: {e}"))?;
         result.push(StoryArc {
             id,
             parent_arc_id,
@@ -670,12 +1008,22 @@ fn read_tracks(conn: &Connection) -> Result<Vec<Track>, String> {
 }
 
 fn read_nodes(conn: &Connection) -> Result<Vec<StoryNode>, String> {
+    // Tolerate node columns missing from hand-edited files rather than failing
+    // the read-only load.
+    let column_or = |column: &'static str, fallback: &'static str| {
+        crate::timeline_node_store::nodes_have_column(conn, column)
+            .map(|present| if present { column } else { fallback })
+            .map_err(|e| format!("inspect nodes: {e}"))
+    };
+    let pinned_column = column_or("pinned", "0")?;
+    let scene_number_column = column_or("scene_number", "NULL")?;
     let mut stmt = conn
-        .prepare(
+        .prepare(&format!(
             "SELECT id, parent_id, level, sort_order, start_ms, end_ms,
-                    name, content_json, beat_type, locked
-             FROM nodes ORDER BY level, start_ms",
-        )
+                    name, content_json, beat_type, locked, {pinned_column},
+                    {scene_number_column}
+             FROM nodes ORDER BY level, start_ms"
+        ))
         .map_err(|e| format!("prepare nodes: {e}"))?;
 
     let rows = stmt
@@ -691,6 +1039,8 @@ fn read_nodes(conn: &Connection) -> Result<Vec<StoryNode>, String> {
                 row.get::<_, String>(7)?,
                 row.get::<_, Option<String>>(8)?,
                 row.get::<_, i32>(9)?,
+                row.get::<_, i32>(10)?,
+                row.get::<_, Option<String>>(11)?,
             ))
         })
         .map_err(|e| format!("query nodes: {e}"))?;
@@ -708,18 +1058,20 @@ fn read_nodes(conn: &Connection) -> Result<Vec<StoryNode>, String> {
             content_json,
             beat_type_json,
             locked,
+            pinned,
+            scene_number,
         ) = row.map_err(|e| format!("read node row: {e}"))?;
 
         let parent_id = parent_id_str
             .map(|s| parse_uuid(&s).map(NodeId))
             .transpose()?;
         let level = parse_story_level(&level_str)?;
-        let content: NodeContent =
-            serde_json::from_str(&content_json).map_err(|e| format!("parse content: {e}"))?;
+        let content: NodeContent = serde_json::from_str(&content_json)
+            .map_err(|e| format!("parse content of node {id_str}: {e}"))?;
         let beat_type: Option<BeatType> = beat_type_json
             .map(|j| serde_json::from_str(&j))
             .transpose()
-            .map_err(|e| format!("parse beat_type: {e}"))?;
+            .map_err(|e| format!("parse beat_type of node {id_str}: {e}"))?;
 
         result.push(StoryNode {
             id: NodeId(parse_uuid(&id_str)?),
@@ -734,6 +1086,8 @@ fn read_nodes(conn: &Connection) -> Result<Vec<StoryNode>, String> {
             content,
             beat_type,
             locked: locked != 0,
+            pinned: pinned != 0,
+            scene_number,
         });
     }
     Ok(result)
@@ -782,7 +1136,7 @@ fn read_relationships(conn: &Connection) -> Result<Vec<Relationship>, String> {
         let (id_str, from_str, to_str, rel_type_json) =
             row.map_err(|e| format!("read relationship row: {e}"))?;
         let rel_type: RelationshipType = serde_json::from_str(&rel_type_json)
-            .map_err(|e| format!("parse relationship_type: {e}"))?;
+            .map_err(|e| format!("parse relationship_type of relationship {id_str}: {e}"))?;
         result.push(Relationship {
             id: RelationshipId(parse_uuid(&id_str)?),
             from_node: NodeId(parse_uuid(&from_str)?),
@@ -795,7 +1149,7 @@ fn read_relationships(conn: &Connection) -> Result<Vec<Relationship>, String> {
 
 fn read_reference_documents(conn: &Connection) -> Result<Vec<ReferenceDocument>, String> {
     let mut stmt = conn
-        .prepare("SELECT id, name, content, doc_type FROM reference_documents")
+        .prepare("SELECT id, name, content, doc_type, scope FROM reference_documents")
         .map_err(|e| format!("prepare reference_documents: {e}"))?;
 
     let rows = stmt
@@ -805,26 +1159,132 @@ fn read_reference_documents(conn: &Connection) -> Result<Vec<ReferenceDocument>,
                 row.get::<_, String>(1)?,
                 row.get::<_, String>(2)?,
                 row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
             ))
         })
         .map_err(|e| format!("query reference_documents: {e}"))?;
 
     let mut result = Vec::new();
     for row in rows {
-        let (id_str, name, content, doc_type_json) =
+        let (id_str, name, content, doc_type_json, scope_json) =
             row.map_err(|e| format!("read reference_document row: {e}"))?;
         let doc_type: ReferenceType =
             serde_json::from_str(&doc_type_json).map_err(|e| format!("parse doc_type: {e}"))?;
+        let scope = serde_json::from_str(&scope_json).map_err(|e| format!("parse scope: {e}"))?;
         result.push(ReferenceDocument {
             id: eidetic_core::reference::ReferenceId(parse_uuid(&id_str)?),
             name,
             content,
             doc_type,
+            scope,
         });
     }
     Ok(result)
 }
 
+// ─── Project Lock ──────────────────────────────────────────────────
+
+/// Name of the lock file a server keeps beside `project.db` while the project
+/// is open, so a second server does not clobber its saves.
+const PROJECT_LOCK_FILE: &str = ".lock";
+
+/// A lock whose owner cannot be checked for liveness is trusted this long.
+const PROJECT_LOCK_STALE_AFTER_SECS: u64 = 12 * 60 * 60;
+
+/// Contents of a project directory's lock file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, serde::Deserialize)]
+pub struct ProjectLock {
+    pub pid: u32,
+    /// When the owning server opened the project, as an RFC3339 UTC timestamp.
+    pub started_at: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProjectLockError {
+    #[error("project is open in another server (pid {}, since {})", .0.pid, .0.started_at)]
+    Held(ProjectLock),
+    #[error("failed to write project lock: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+fn project_lock_path(project_path: &Path) -> PathBuf {
+    project_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(PROJECT_LOCK_FILE)
+}
+
+/// Take the lock for the project at `project_path`. A fresh lock held by
+/// another process is refused unless `force` is set. Stale locks, and locks
+/// this process already holds, are replaced.
+pub fn acquire_project_lock(project_path: &Path, force: bool) -> Result<(), ProjectLockError> {
+    let lock_path = project_lock_path(project_path);
+    if let Some(existing) = read_project_lock(&lock_path)
+        && is_fresh_foreign_lock(&existing, &lock_path)
+    {
+        if !force {
+            return Err(ProjectLockError::Held(existing));
+        }
+        tracing::warn!(
+            "forcing open of {} over lock held by pid {} since {}",
+            project_path.display(),
+            existing.pid,
+            existing.started_at
+        );
+    }
+
+    if let Some(parent) = lock_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let lock = ProjectLock {
+        pid: std::process::id(),
+        started_at: format_rfc3339_utc(unix_now_secs()),
+    };
+    let json =
+        serde_json::to_string(&lock).map_err(|error| std::io::Error::other(error.to_string()))?;
+    std::fs::write(&lock_path, json)?;
+    Ok(())
+}
+
+/// Remove the project's lock if this process holds it.
+pub fn release_project_lock(project_path: &Path) {
+    let lock_path = project_lock_path(project_path);
+    if read_project_lock(&lock_path).is_some_and(|lock| lock.pid == std::process::id())
+        && let Err(error) = std::fs::remove_file(&lock_path)
+    {
+        tracing::warn!(
+            "failed to remove project lock {}: {error}",
+            lock_path.display()
+        );
+    }
+}
+
+fn read_project_lock(lock_path: &Path) -> Option<ProjectLock> {
+    let contents = std::fs::read_to_string(lock_path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn is_fresh_foreign_lock(lock: &ProjectLock, lock_path: &Path) -> bool {
+    if lock.pid == std::process::id() {
+        return false;
+    }
+    if cfg!(target_os = "linux") {
+        return Path::new("/proc").join(lock.pid.to_string()).exists();
+    }
+    std::fs::metadata(lock_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age.as_secs() < PROJECT_LOCK_STALE_AFTER_SECS)
+}
+
+pub(crate) fn unix_now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
 // ─── List Projects ─────────────────────────────────────────────────
 
 /// List saved projects under a base directory.
@@ -851,7 +1311,7 @@ pub async fn list_projects(base_dir: &Path) -> Vec<ProjectEntry> {
             .and_then(|t| {
                 t.duration_since(std::time::UNIX_EPOCH)
                     .ok()
-                    .map(|d| d.as_secs().to_string())
+                    .map(|d| format_rfc3339_utc(d.as_secs()))
             })
             .unwrap_or_default();
 
@@ -871,19 +1331,54 @@ pub async fn list_projects(base_dir: &Path) -> Vec<ProjectEntry> {
     entries
 }
 
+/// Format seconds since the Unix epoch as `YYYY-MM-DDTHH:MM:SSZ`.
+pub(crate) fn format_rfc3339_utc(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let secs_of_day = secs % 86_400;
+    // Civil-from-days conversion over 400-year eras (proleptic Gregorian).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs_of_day / 3_600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    )
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use eidetic_core::Template;
     use eidetic_core::contracts::{
-        CommandEnvelope, DeleteStoryArcCommand, DeleteTimelineNodeCommand,
+        BibleGraphNodeId, CommandEnvelope, DeleteStoryArcCommand, DeleteTimelineNodeCommand,
+        DuplicateTimelineNodeCommand, NormalizeTimelineLevelCommand, RenumberTimelineScenesCommand,
+        SetTimelineNodeArcTagCommand, SetTimelineNodePinnedCommand,
+        SetTimelineNodeSceneNumberCommand,
     };
+    use eidetic_core::reference::{ReferenceDocument, ReferenceScope, ReferenceType};
     use eidetic_core::story::arc::{ArcType, Color, StoryArc};
     use eidetic_core::timeline::Timeline;
+    use eidetic_core::timeline::node::StoryLevel;
     use eidetic_core::timeline::relationship::{Relationship, RelationshipType};
+    use eidetic_core::timeline::scene_numbers::SceneNumbering;
     use eidetic_core::timeline::structure::EpisodeStructure;
     use uuid::Uuid;
 
-    use super::{load_project_sync, save_project_sync};
+    use super::{
+        ProjectLock, ProjectLockError, acquire_project_lock, format_rfc3339_utc, load_project_sync,
+        read_project_lock, release_project_lock, save_project_sync, set_all_tracks_collapsed,
+        switch_episode_sync,
+    };
+    use crate::history_store::RecordChangeOutcome;
 
     fn temp_project_path(label: &str) -> std::path::PathBuf {
         std::env::temp_dir().join(format!("eidetic-persistence-{label}-{}.db", Uuid::new_v4()))
@@ -900,6 +1395,94 @@ mod tests {
         project
     }
 
+    #[test]
+    fn project_ai_settings_round_trip() {
+        let path = temp_project_path("ai-settings");
+        let mut project = project_with_arc("Voice");
+        project.ai_settings.model = Some("show-model".into());
+        project.ai_settings.temperature = Some(0.4);
+        project.ai_settings.system_prompt = Some("Write a deadpan mockumentary.".into());
+
+        save_project_sync(&project, &path, None).expect("save");
+        let (loaded, _) = load_project_sync(&path).expect("load project");
+
+        assert_eq!(loaded.ai_settings, project.ai_settings);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn collapsed_tracks_survive_a_save_from_a_stale_project() {
+        let path = temp_project_path("tracks-collapsed");
+        let project = Template::MultiCam.build_project("Tracks");
+        save_project_sync(&project, &path, None).expect("save");
+
+        let conn = crate::sqlite::open_write_connection(&path).expect("open");
+        set_all_tracks_collapsed(&conn, true).expect("collapse tracks");
+        drop(conn);
+        save_project_sync(&project, &path, None).expect("save again");
+        let (loaded, _) = load_project_sync(&path).expect("load project");
+
+        assert_eq!(loaded.timeline.tracks.len(), project.timeline.tracks.len());
+        assert!(loaded.timeline.tracks.iter().all(|track| track.collapsed));
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn malformed_node_content_names_the_node_and_position() {
+        let path = temp_project_path("bad-content");
+        let project = Template::MultiCam.build_project("Bad Content");
+        save_project_sync(&project, &path, None).expect("save");
+        let node_id = project.timeline.nodes[0].id.0.to_string();
+        let conn = crate::sqlite::open_write_connection(&path).expect("open");
+        conn.execute(
+            "UPDATE nodes SET content_json = ?1 WHERE id = ?2",
+            rusqlite::params!["{\n  \"notes\": 7\n}", node_id],
+        )
+        .expect("corrupt content");
+        drop(conn);
+
+        let error = load_project_sync(&path).expect_err("malformed content");
+
+        assert!(error.contains(&format!("node {node_id}")), "{error}");
+        assert!(error.contains("line 2 column"), "{error}");
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn reference_scopes_round_trip() {
+        let path = temp_project_path("reference-scope");
+        let mut project = project_with_arc("Villain");
+        let mut scoped =
+            ReferenceDocument::new("Backstory", "Secrets.", ReferenceType::CharacterBible);
+        scoped.scope = ReferenceScope {
+            arc_ids: vec![project.arcs[0].id],
+            entity_ids: vec![BibleGraphNodeId::new("node.character.villain").unwrap()],
+        };
+        project.references = vec![
+            scoped.clone(),
+            ReferenceDocument::new("Tone", "Dry.", ReferenceType::StyleGuide),
+        ];
+
+        save_project_sync(&project, &path, None).expect("save");
+        let (loaded, _) = load_project_sync(&path).expect("load project");
+
+        let scope_of = |name: &str| {
+            loaded
+                .references
+                .iter()
+                .find(|doc| doc.name == name)
+                .map(|doc| doc.scope.clone())
+                .unwrap()
+        };
+        assert_eq!(scope_of("Backstory"), scoped.scope);
+        assert!(scope_of("Tone").is_global());
+
+        let _ = std::fs::remove_file(path);
+    }
+
     #[test]
     fn broad_save_preserves_existing_sqlite_story_arcs_when_project_mirror_is_stale() {
         let path = temp_project_path("preserve-arcs");
@@ -1003,6 +1586,328 @@ mod tests {
         let _ = std::fs::remove_file(path);
     }
 
+    #[test]
+    fn duplicated_timeline_subtree_persists_and_replays_with_the_same_ids() {
+        let path = temp_project_path("duplicate-timeline-subtree");
+        let project = Template::MultiCam.build_project("Persistence Test");
+        let act = project.timeline.nodes_at_level(StoryLevel::Act)[0].clone();
+        let command = CommandEnvelope::new(DuplicateTimelineNodeCommand {
+            node_id: act.id,
+            recursive: true,
+            offset_ms: Some(0),
+        });
+
+        save_project_sync(&project, &path, None).expect("initial save");
+        let mut conn = crate::sqlite::open_write_connection(&path).expect("open sqlite");
+        let (outcome, pairs) = crate::timeline_command::record_duplicate_timeline_node_history(
+            &mut conn, &project, &command, 1,
+        )
+        .expect("duplicate timeline node");
+        assert_eq!(outcome, crate::history_store::RecordChangeOutcome::Recorded);
+        assert_eq!(
+            pairs.len(),
+            1 + project.timeline.descendants_of(act.id).len()
+        );
+
+        let (loaded, _) = load_project_sync(&path).expect("load project");
+        let copy = loaded.timeline.node(pairs[0].1).expect("copied node");
+        assert_eq!(copy.name, format!("{} (copy)", act.name));
+        for (source_id, copy_id) in &pairs {
+            assert_eq!(
+                loaded.timeline.arcs_for_node(*source_id),
+                loaded.timeline.arcs_for_node(*copy_id)
+            );
+        }
+
+        let (outcome, replayed) = crate::timeline_command::record_duplicate_timeline_node_history(
+            &mut conn, &loaded, &command, 2,
+        )
+        .expect("replay duplicate");
+        assert_eq!(
+            outcome,
+            crate::history_store::RecordChangeOutcome::AlreadyRecorded
+        );
+        assert_eq!(replayed, pairs);
+
+        drop(conn);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn pinned_nodes_survive_save_and_load_including_pre_pin_databases() {
+        let path = temp_project_path("pinned-nodes");
+        let project = Template::MultiCam.build_project("Persistence Test");
+        let node_id = project.timeline.nodes_at_level(StoryLevel::Scene)[0].id;
+
+        save_project_sync(&project, &path, None).expect("initial save");
+        {
+            let conn = crate::sqlite::open_write_connection(&path).expect("open sqlite");
+            conn.execute("ALTER TABLE nodes DROP COLUMN pinned", [])
+                .expect("drop pinned column");
+        }
+        let (loaded, _) = load_project_sync(&path).expect("load pre-pin project");
+        assert!(loaded.timeline.pinned_nodes().is_empty());
+
+        {
+            let mut conn = crate::sqlite::open_write_connection(&path).expect("open sqlite");
+            crate::timeline_command::record_set_timeline_node_pinned_history(
+                &mut conn,
+                &loaded,
+                &CommandEnvelope::new(SetTimelineNodePinnedCommand {
+                    node_id,
+                    pinned: true,
+                }),
+                1,
+            )
+            .expect("pin node");
+        }
+        save_project_sync(&loaded, &path, None).expect("second save");
+
+        let (loaded, _) = load_project_sync(&path).expect("load project");
+        let pinned: Vec<_> = loaded
+            .timeline
+            .pinned_nodes()
+            .iter()
+            .map(|n| n.id)
+            .collect();
+        assert_eq!(pinned, vec![node_id]);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn scene_numbers_survive_renumbering_save_and_load() {
+        let path = temp_project_path("scene-numbers");
+        let project = Template::MultiCam.build_project("Persistence Test");
+        let scenes: Vec<_> = project
+            .timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .iter()
+            .map(|n| n.id)
+            .collect();
+
+        save_project_sync(&project, &path, None).expect("initial save");
+        {
+            let mut conn = crate::sqlite::open_write_connection(&path).expect("open sqlite");
+            crate::timeline_command::record_renumber_timeline_scenes_history(
+                &mut conn,
+                &project,
+                &CommandEnvelope::new(RenumberTimelineScenesCommand {
+                    mode: SceneNumbering::Sequential,
+                }),
+                1,
+            )
+            .expect("renumber scenes");
+        }
+        let (loaded, _) = load_project_sync(&path).expect("load renumbered project");
+        {
+            let mut conn = crate::sqlite::open_write_connection(&path).expect("open sqlite");
+            crate::timeline_command::record_set_timeline_node_scene_number_history(
+                &mut conn,
+                &loaded,
+                &CommandEnvelope::new(SetTimelineNodeSceneNumberCommand {
+                    node_id: scenes[1],
+                    scene_number: Some(" 2a ".into()),
+                }),
+                2,
+            )
+            .expect("set scene number");
+            let premise = loaded.timeline.nodes_at_level(StoryLevel::Premise)[0].id;
+            let error = crate::timeline_command::record_set_timeline_node_scene_number_history(
+                &mut conn,
+                &loaded,
+                &CommandEnvelope::new(SetTimelineNodeSceneNumberCommand {
+                    node_id: premise,
+                    scene_number: Some("1".into()),
+                }),
+                3,
+            )
+            .expect_err("premise is not a scene");
+            assert!(error.to_string().starts_with("invalid scene number"));
+        }
+        save_project_sync(&loaded, &path, None).expect("second save");
+
+        let (loaded, _) = load_project_sync(&path).expect("load project");
+        let number = |id| loaded.timeline.node(id).unwrap().scene_number.clone();
+        assert_eq!(number(scenes[0]).as_deref(), Some("1"));
+        assert_eq!(number(scenes[1]).as_deref(), Some("2A"));
+        assert_eq!(number(scenes[2]).as_deref(), Some("3"));
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn normalized_boundaries_survive_save_and_load() {
+        let path = temp_project_path("normalize");
+        let mut project = Template::MultiCam.build_project("Persistence Test");
+        let parent = project.timeline.nodes_at_level(StoryLevel::Act)[0].id;
+        let mut children: Vec<_> = project
+            .timeline
+            .children_of(parent)
+            .iter()
+            .map(|n| (n.id, n.level, n.time_range))
+            .collect();
+        children.sort_by_key(|(_, _, range)| range.start_ms);
+        let (first, level, range) = children[0];
+        let next_start = children[1].2.start_ms;
+        project.timeline.node_mut(first).unwrap().time_range.end_ms = next_start - 7;
+        assert_eq!(range.end_ms, next_start);
+        save_project_sync(&project, &path, None).expect("initial save");
+
+        let command = CommandEnvelope::new(NormalizeTimelineLevelCommand {
+            level,
+            parent_id: Some(parent),
+        });
+        {
+            let mut conn = crate::sqlite::open_write_connection(&path).expect("open sqlite");
+            let (outcome, adjusted) =
+                crate::timeline_command::record_normalize_timeline_level_history(
+                    &mut conn, &project, &command, 1,
+                )
+                .expect("normalize level");
+            assert_eq!(outcome, Some(RecordChangeOutcome::Recorded));
+            assert_eq!(adjusted, 1);
+            let (outcome, adjusted) =
+                crate::timeline_command::record_normalize_timeline_level_history(
+                    &mut conn, &project, &command, 2,
+                )
+                .expect("replay normalize");
+            assert_eq!(outcome, Some(RecordChangeOutcome::AlreadyRecorded));
+            assert_eq!(adjusted, 0);
+        }
+
+        let (loaded, _) = load_project_sync(&path).expect("load normalized project");
+        assert_eq!(loaded.timeline.node(first).unwrap().time_range, range);
+
+        // Normalizing a level that is already flush records nothing.
+        let noop = CommandEnvelope::new(NormalizeTimelineLevelCommand {
+            level,
+            parent_id: Some(parent),
+        });
+        let mut conn = crate::sqlite::open_write_connection(&path).expect("open sqlite");
+        let (outcome, adjusted) = crate::timeline_command::record_normalize_timeline_level_history(
+            &mut conn, &loaded, &noop, 3,
+        )
+        .expect("no-op normalize");
+        assert_eq!((outcome, adjusted), (None, 0));
+        assert_eq!(
+            crate::history_store::check_recorded_command(&conn, &noop, "timeline.normalize_level")
+                .expect("check history"),
+            None
+        );
+        drop(conn);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn recursive_arc_tags_survive_save_and_load() {
+        let path = temp_project_path("recursive-arc-tags");
+        let project = Template::MultiCam.build_project("Persistence Test");
+        let act = project.timeline.nodes_at_level(StoryLevel::Act)[0].id;
+        let subtree: Vec<_> = std::iter::once(act)
+            .chain(project.timeline.descendants_of(act).iter().map(|n| n.id))
+            .collect();
+        let arc_id = project.arcs[0].id;
+        save_project_sync(&project, &path, None).expect("initial save");
+
+        let tag = CommandEnvelope::new(SetTimelineNodeArcTagCommand {
+            node_id: act,
+            arc_id,
+            tagged: true,
+            recursive: true,
+        });
+        {
+            let mut conn = crate::sqlite::open_write_connection(&path).expect("open sqlite");
+            crate::timeline_command::record_set_timeline_node_arc_tag_history(
+                &mut conn, &project, &tag, 1,
+            )
+            .expect("tag subtree");
+            assert_eq!(
+                crate::timeline_command::record_set_timeline_node_arc_tag_history(
+                    &mut conn, &project, &tag, 2,
+                )
+                .expect("replay tag"),
+                crate::history_store::RecordChangeOutcome::AlreadyRecorded
+            );
+        }
+        let (tagged, _) = load_project_sync(&path).expect("load tagged project");
+        for node_id in &subtree {
+            let arcs = tagged.timeline.arcs_for_node(*node_id);
+            assert_eq!(arcs.iter().filter(|id| **id == arc_id).count(), 1);
+        }
+
+        {
+            let mut conn = crate::sqlite::open_write_connection(&path).expect("open sqlite");
+            crate::timeline_command::record_set_timeline_node_arc_tag_history(
+                &mut conn,
+                &tagged,
+                &CommandEnvelope::new(SetTimelineNodeArcTagCommand {
+                    node_id: act,
+                    arc_id,
+                    tagged: false,
+                    recursive: true,
+                }),
+                3,
+            )
+            .expect("untag subtree");
+        }
+        let (untagged, _) = load_project_sync(&path).expect("load untagged project");
+        assert!(
+            subtree
+                .iter()
+                .all(|node_id| !untagged.timeline.arcs_for_node(*node_id).contains(&arc_id))
+        );
+        assert!(!untagged.timeline.nodes_for_arc(arc_id).is_empty());
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn episodes_survive_save_switch_and_load() {
+        let path = temp_project_path("episodes");
+        let mut project = Template::MultiCam.build_project("Persistence Test");
+        let pilot = project.active_episode.clone();
+        let pilot_nodes = project.timeline.nodes.len();
+        let template = Template::Animated.build_project("Template");
+        let second_nodes: HashSet<_> = template.timeline.nodes.iter().map(|n| n.id).collect();
+        let second_id = project.add_episode("Episode 2", template.timeline, &template.arcs);
+
+        save_project_sync(&project, &path, None).expect("save project");
+        let (loaded, _) = load_project_sync(&path).expect("load project");
+        assert_eq!(loaded.active_episode, pilot);
+        assert_eq!(loaded.episodes.len(), 1);
+        assert_eq!(loaded.episodes[0].header.id, second_id);
+        assert_eq!(loaded.episodes[0].timeline.nodes.len(), second_nodes.len());
+
+        switch_episode_sync(&path, second_id).expect("switch episode");
+        let (switched, _) = load_project_sync(&path).expect("load switched project");
+        save_project_sync(&switched, &path, None).expect("save switched project");
+        let (switched, _) = load_project_sync(&path).expect("reload switched project");
+
+        assert_eq!(switched.active_episode.id, second_id);
+        let active: HashSet<_> = switched.timeline.nodes.iter().map(|n| n.id).collect();
+        assert_eq!(active, second_nodes);
+        assert_eq!(switched.episodes[0].header, pilot);
+        assert_eq!(switched.episodes[0].timeline.nodes.len(), pilot_nodes);
+        assert!(
+            switched
+                .episode_summaries()
+                .iter()
+                .map(|summary| summary.header.id)
+                .eq([pilot.id, second_id])
+        );
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn project_modified_times_format_as_rfc3339() {
+        assert_eq!(format_rfc3339_utc(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_rfc3339_utc(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_rfc3339_utc(1_709_251_199), "2024-02-29T23:59:59Z");
+    }
+
     #[test]
     fn broad_save_preserves_existing_sqlite_timeline_relationships() {
         let path = temp_project_path("preserve-timeline-relationships");
@@ -1029,4 +1934,71 @@ mod tests {
 
         let _ = std::fs::remove_file(path);
     }
+
+    #[test]
+    fn load_merges_extra_premise_nodes_into_the_earliest() {
+        let path = temp_project_path("extra-premise");
+        let mut project = Template::MultiCam.build_project("Persistence Test");
+        let kept = project.timeline.nodes_at_level(StoryLevel::Premise)[0].clone();
+        let mut extra = eidetic_core::timeline::node::StoryNode::new(
+            "Second premise",
+            StoryLevel::Premise,
+            kept.time_range,
+        );
+        extra.sort_order = kept.sort_order + 1;
+        let extra_id = extra.id;
+        project.timeline.nodes.push(extra);
+
+        save_project_sync(&project, &path, None).expect("save project");
+
+        let (loaded, _) = load_project_sync(&path).expect("load project");
+        let premises = loaded.timeline.nodes_at_level(StoryLevel::Premise);
+        assert_eq!(premises.len(), 1);
+        assert_eq!(premises[0].id, kept.id);
+        assert!(loaded.timeline.node(extra_id).is_err());
+        assert!(loaded.timeline.validate_all().is_empty());
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn project_lock_refuses_live_foreign_owner_unless_forced() {
+        let dir = std::env::temp_dir().join(format!("eidetic-lock-{}", Uuid::new_v4()));
+        let path = dir.join("project.db");
+        let lock_path = dir.join(".lock");
+        let write_lock = |pid: u32| {
+            let lock = ProjectLock {
+                pid,
+                started_at: "2026-01-01T00:00:00Z".into(),
+            };
+            std::fs::write(&lock_path, serde_json::to_string(&lock).unwrap()).unwrap();
+        };
+        let lock_pid = || read_project_lock(&lock_path).map(|lock| lock.pid);
+
+        acquire_project_lock(&path, false).unwrap();
+        assert_eq!(lock_pid(), Some(std::process::id()));
+        acquire_project_lock(&path, false).unwrap();
+
+        if cfg!(target_os = "linux") {
+            // pid 1 is always running; u32::MAX never is.
+            write_lock(1);
+            let error = acquire_project_lock(&path, false).unwrap_err();
+            assert!(matches!(error, ProjectLockError::Held(ref lock) if lock.pid == 1));
+            release_project_lock(&path);
+            assert_eq!(lock_pid(), Some(1));
+            acquire_project_lock(&path, true).unwrap();
+            assert_eq!(lock_pid(), Some(std::process::id()));
+
+            write_lock(u32::MAX);
+            acquire_project_lock(&path, false).unwrap();
+        }
+
+        release_project_lock(&path);
+        assert!(!lock_path.exists());
+        let _ = std::fs::remove_dir_all(dir);
+    }
 }
+
+#[cfg(test)]
+#[path = "persistence_fixture_tests.rs"]
+mod fixture_tests;
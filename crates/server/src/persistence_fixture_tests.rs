@@ -0,0 +1,380 @@
+//! Hand-built database fixtures for `load_project_sync`. They are written with
+//! raw SQL rather than `save_project_sync`, so a save/load change that drifts
+//! from the on-disk format still fails here.
+
+use eidetic_core::story::arc::ArcId;
+use eidetic_core::timeline::node::{BeatType, NodeId, StoryLevel};
+use eidetic_core::timeline::relationship::RelationshipType;
+use eidetic_core::timeline::structure::EpisodeStructure;
+use rusqlite::{Connection, params};
+use uuid::Uuid;
+
+use super::{CURRENT_SCHEMA_VERSION, load_project_sync, migrate_to_latest, read_schema_version};
+
+const PREMISE_ID: &str = "00000000-0000-4000-8000-000000000001";
+const ACT_ID: &str = "00000000-0000-4000-8000-000000000002";
+const SCENE_ID: &str = "00000000-0000-4000-8000-000000000003";
+const BEAT_ID: &str = "00000000-0000-4000-8000-000000000004";
+const ARC_ID: &str = "00000000-0000-4000-8000-0000000000a1";
+const RELATIONSHIP_ID: &str = "00000000-0000-4000-8000-0000000000b1";
+
+/// The version 3 layout from before nodes carried a `pinned` column.
+const PRE_PIN_V3_SCHEMA_SQL: &str = r#"
+CREATE TABLE schema_meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+INSERT INTO schema_meta (key, value) VALUES ('version', '3');
+CREATE TABLE project (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    name TEXT NOT NULL,
+    premise TEXT NOT NULL DEFAULT '',
+    total_duration_ms INTEGER NOT NULL
+);
+CREATE TABLE episode_structure (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    template_name TEXT NOT NULL,
+    segments_json TEXT NOT NULL
+);
+CREATE TABLE arcs (
+    id TEXT PRIMARY KEY,
+    parent_arc_id TEXT,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    arc_type TEXT NOT NULL,
+    color_r INTEGER NOT NULL,
+    color_g INTEGER NOT NULL,
+    color_b INTEGER NOT NULL
+);
+CREATE TABLE tracks (
+    id TEXT PRIMARY KEY,
+    level TEXT NOT NULL,
+    label TEXT NOT NULL,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    collapsed INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE nodes (
+    id TEXT PRIMARY KEY,
+    parent_id TEXT,
+    level TEXT NOT NULL,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    start_ms INTEGER NOT NULL,
+    end_ms INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    content_json TEXT NOT NULL DEFAULT '{}',
+    beat_type TEXT,
+    locked INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE node_arcs (node_id TEXT NOT NULL, arc_id TEXT NOT NULL);
+CREATE TABLE relationships (
+    id TEXT PRIMARY KEY,
+    from_node_id TEXT NOT NULL,
+    to_node_id TEXT NOT NULL,
+    relationship_type TEXT NOT NULL
+);
+CREATE TABLE reference_documents (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    content TEXT NOT NULL,
+    doc_type TEXT NOT NULL
+);
+CREATE TABLE ydoc_state (id INTEGER PRIMARY KEY CHECK (id = 1), state BLOB NOT NULL);
+"#;
+
+/// The clip-based layout that predates story nodes and `schema_meta`.
+const V1_SCHEMA_SQL: &str = r#"
+CREATE TABLE project (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    name TEXT NOT NULL,
+    total_duration_ms INTEGER NOT NULL,
+    characters_json TEXT NOT NULL DEFAULT '[]'
+);
+CREATE TABLE clips (
+    id TEXT PRIMARY KEY,
+    track_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    start_ms INTEGER NOT NULL,
+    end_ms INTEGER NOT NULL
+);
+CREATE TABLE sub_beats (
+    id TEXT PRIMARY KEY,
+    clip_id TEXT NOT NULL,
+    name TEXT NOT NULL
+);
+CREATE TABLE entity_clip_refs (entity_id TEXT NOT NULL, clip_id TEXT NOT NULL);
+INSERT INTO project (id, name, total_duration_ms, characters_json)
+    VALUES (1, 'Clip Era', 1320000, '[{"name":"Ada"}]');
+INSERT INTO clips (id, track_id, name, start_ms, end_ms)
+    VALUES ('clip-1', 'track-a', 'Cold open', 0, 60000);
+INSERT INTO sub_beats (id, clip_id, name) VALUES ('sub-1', 'clip-1', 'Hook');
+INSERT INTO entity_clip_refs (entity_id, clip_id) VALUES ('ada', 'clip-1');
+"#;
+
+fn fixture_path(label: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("eidetic-fixture-{label}-{}.db", Uuid::new_v4()))
+}
+
+fn insert_node(
+    conn: &Connection,
+    id: &str,
+    parent_id: Option<&str>,
+    level: StoryLevel,
+    range: (i64, i64),
+    beat_type: Option<&BeatType>,
+) {
+    let content = serde_json::json!({ "notes": format!("{level} notes"), "status": "NotesOnly" });
+    conn.execute(
+        "INSERT INTO nodes (id, parent_id, level, start_ms, end_ms, name, content_json, beat_type)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            id,
+            parent_id,
+            level.label(),
+            range.0,
+            range.1,
+            format!("{level} node"),
+            content.to_string(),
+            beat_type.map(|beat_type| serde_json::to_string(beat_type).unwrap()),
+        ],
+    )
+    .unwrap();
+}
+
+fn write_pre_pin_v3_fixture(path: &std::path::Path) {
+    let conn = Connection::open(path).unwrap();
+    conn.execute_batch(PRE_PIN_V3_SCHEMA_SQL).unwrap();
+    conn.execute(
+        "INSERT INTO project (id, name, premise, total_duration_ms)
+         VALUES (1, 'Fixture', 'A premise', 1320000)",
+        [],
+    )
+    .unwrap();
+    let structure = EpisodeStructure::standard_30_min();
+    conn.execute(
+        "INSERT INTO episode_structure (id, template_name, segments_json) VALUES (1, ?1, ?2)",
+        params![
+            structure.template_name,
+            serde_json::to_string(&structure.segments).unwrap()
+        ],
+    )
+    .unwrap();
+    conn.execute(
+        "INSERT INTO arcs (id, name, arc_type, color_r, color_g, color_b)
+         VALUES (?1, 'A plot', '\"APlot\"', 10, 20, 30)",
+        [ARC_ID],
+    )
+    .unwrap();
+
+    insert_node(
+        &conn,
+        PREMISE_ID,
+        None,
+        StoryLevel::Premise,
+        (0, 1_320_000),
+        None,
+    );
+    insert_node(
+        &conn,
+        ACT_ID,
+        Some(PREMISE_ID),
+        StoryLevel::Act,
+        (0, 400_000),
+        None,
+    );
+    insert_node(
+        &conn,
+        SCENE_ID,
+        Some(ACT_ID),
+        StoryLevel::Scene,
+        (0, 120_000),
+        None,
+    );
+    insert_node(
+        &conn,
+        BEAT_ID,
+        Some(SCENE_ID),
+        StoryLevel::Beat,
+        (0, 30_000),
+        Some(&BeatType::Setup),
+    );
+
+    for node_id in [SCENE_ID, BEAT_ID] {
+        conn.execute(
+            "INSERT INTO node_arcs (node_id, arc_id) VALUES (?1, ?2)",
+            [node_id, ARC_ID],
+        )
+        .unwrap();
+    }
+    conn.execute(
+        "INSERT INTO relationships (id, from_node_id, to_node_id, relationship_type)
+         VALUES (?1, ?2, ?3, '\"Causal\"')",
+        [RELATIONSHIP_ID, SCENE_ID, BEAT_ID],
+    )
+    .unwrap();
+}
+
+fn node_id(id: &str) -> NodeId {
+    NodeId(Uuid::parse_str(id).unwrap())
+}
+
+#[test]
+fn pre_pin_v3_fixture_migrates_and_loads_levels_parentage_and_arc_tags() {
+    let path = fixture_path("pre-pin-v3");
+    write_pre_pin_v3_fixture(&path);
+
+    let (project, ydoc_state) = load_project_sync(&path).expect("load fixture");
+    let timeline = &project.timeline;
+
+    let conn = Connection::open(&path).unwrap();
+    assert_eq!(read_schema_version(&conn), CURRENT_SCHEMA_VERSION);
+    drop(conn);
+
+    assert!(ydoc_state.is_none());
+    assert_eq!(project.name, "Fixture");
+    assert_eq!(project.premise, "A premise");
+    assert_eq!(timeline.nodes.len(), 4);
+    for (id, level, parent) in [
+        (PREMISE_ID, StoryLevel::Premise, None),
+        (ACT_ID, StoryLevel::Act, Some(PREMISE_ID)),
+        (SCENE_ID, StoryLevel::Scene, Some(ACT_ID)),
+        (BEAT_ID, StoryLevel::Beat, Some(SCENE_ID)),
+    ] {
+        let node = timeline.node(node_id(id)).unwrap();
+        assert_eq!(node.level, level);
+        assert_eq!(node.parent_id, parent.map(node_id));
+        assert!(!node.pinned);
+        assert_eq!(node.scene_number, None);
+        assert_eq!(node.content.notes, format!("{level} notes"));
+    }
+    assert_eq!(
+        timeline.node(node_id(BEAT_ID)).unwrap().beat_type,
+        Some(BeatType::Setup)
+    );
+
+    let arc_id = ArcId(Uuid::parse_str(ARC_ID).unwrap());
+    assert_eq!(project.arcs.len(), 1);
+    assert_eq!(timeline.arcs_for_node(node_id(SCENE_ID)), [arc_id]);
+    assert_eq!(timeline.arcs_for_node(node_id(BEAT_ID)), [arc_id]);
+    assert!(timeline.arcs_for_node(node_id(ACT_ID)).is_empty());
+
+    assert_eq!(timeline.relationships.len(), 1);
+    assert_eq!(timeline.relationships[0].from_node, node_id(SCENE_ID));
+    assert_eq!(
+        timeline.relationships[0].relationship_type,
+        RelationshipType::Causal
+    );
+    assert!(timeline.validate_all().is_empty());
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn clip_era_v1_fixture_is_rejected_without_being_touched() {
+    let path = fixture_path("v1");
+    Connection::open(&path)
+        .unwrap()
+        .execute_batch(V1_SCHEMA_SQL)
+        .unwrap();
+
+    let error = load_project_sync(&path).expect_err("v1 has no loader");
+    assert_eq!(
+        error,
+        "unsupported project schema version 1; no migration from version 1 to 2"
+    );
+
+    let conn = Connection::open(&path).unwrap();
+    let tables: Vec<String> = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' ORDER BY name")
+        .unwrap()
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(
+        tables,
+        ["clips", "entity_clip_refs", "project", "sub_beats"]
+    );
+    let characters: String = conn
+        .query_row("SELECT characters_json FROM project", [], |row| row.get(0))
+        .unwrap();
+    assert_eq!(characters, r#"[{"name":"Ada"}]"#);
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn other_schema_versions_are_rejected() {
+    let path = fixture_path("v2");
+    write_pre_pin_v3_fixture(&path);
+    Connection::open(&path)
+        .unwrap()
+        .execute(
+            "UPDATE schema_meta SET value = '2' WHERE key = 'version'",
+            [],
+        )
+        .unwrap();
+
+    let error = load_project_sync(&path).expect_err("v2 has no loader");
+    assert_eq!(
+        error,
+        "unsupported project schema version 2; no migration from version 2 to 3"
+    );
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn newer_schema_versions_are_rejected_with_a_clear_error() {
+    let path = fixture_path("newer");
+    write_pre_pin_v3_fixture(&path);
+    Connection::open(&path)
+        .unwrap()
+        .execute(
+            "UPDATE schema_meta SET value = ?1 WHERE key = 'version'",
+            [(CURRENT_SCHEMA_VERSION + 1).to_string()],
+        )
+        .unwrap();
+
+    let error = load_project_sync(&path).expect_err("newer schema");
+    assert!(
+        error.starts_with("project was created by a newer version of Eidetic"),
+        "{error}"
+    );
+
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn migration_steps_run_in_order_and_record_each_version() {
+    fn add_pinned(conn: &Connection) -> Result<(), String> {
+        conn.execute(
+            "ALTER TABLE nodes ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0",
+            [],
+        )
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+    }
+    fn pin_beats(conn: &Connection) -> Result<(), String> {
+        conn.execute("UPDATE nodes SET pinned = 1 WHERE level = 'Beat'", [])
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    let path = fixture_path("migrate");
+    write_pre_pin_v3_fixture(&path);
+    Connection::open(&path)
+        .unwrap()
+        .execute(
+            "UPDATE schema_meta SET value = '1' WHERE key = 'version'",
+            [],
+        )
+        .unwrap();
+
+    migrate_to_latest(&path, 1, &[add_pinned, pin_beats]).unwrap();
+
+    let conn = Connection::open(&path).unwrap();
+    assert_eq!(read_schema_version(&conn), 3);
+    drop(conn);
+    let (project, _) = load_project_sync(&path).expect("load migrated project");
+    assert!(project.timeline.node(node_id(BEAT_ID)).unwrap().pinned);
+    assert!(!project.timeline.node(node_id(SCENE_ID)).unwrap().pinned);
+
+    let _ = std::fs::remove_file(path);
+}
@@ -1,18 +1,37 @@
-use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use eidetic_core::Template;
+use eidetic_core::project::{EpisodeId, EpisodeSummary, ProjectAiSettings};
+use eidetic_core::timeline::node::{ContentStatus, NodeId};
 
 use crate::backend_error::BackendError;
+use crate::command_service_support::{active_project_path, map_history_error};
 use crate::persistence;
-use crate::state::AppState;
+use crate::state::{AppState, ProjectDefaults, ServerEvent, constants};
+use crate::timeline_node_store;
 use crate::validation;
-use crate::ydoc::{ContentField, DocCommand};
+use crate::ydoc::{ContentField, DocCommand, NodeText};
 
 #[derive(Deserialize)]
 pub struct CreateProjectRequest {
     pub name: String,
-    /// "multi_cam", "single_cam", or "animated".
-    pub template: String,
+    /// "multi_cam", "single_cam", or "animated"; defaults to
+    /// [`constants::DEFAULT_PROJECT_TEMPLATE`].
+    #[serde(default, alias = "template_name")]
+    pub template: Option<String>,
+    /// Episode length; the template is scaled to fit. Defaults to
+    /// [`ProjectDefaults::episode_duration_ms`].
+    #[serde(default)]
+    pub total_duration_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProjectDefaultsUpdate {
+    pub episode_duration_ms: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -26,9 +45,86 @@ pub struct SaveProjectRequest {
     pub path: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectSortKey {
+    Name,
+    #[default]
+    Modified,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Sorting and paging for the saved-project list. The default is every
+/// project, most recently modified first.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ProjectListQuery {
+    pub sort: ProjectSortKey,
+    pub direction: SortDirection,
+    pub offset: usize,
+    pub limit: Option<usize>,
+    /// Absolute directory to list in place of the default project storage root.
+    pub base_dir: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct LoadProjectRequest {
     pub path: String,
+    /// Open even if another server holds the project's lock.
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Deserialize)]
+pub struct OpenProjectDirectoryRequest {
+    /// Absolute path of a writable directory holding, or to hold, `project.db`.
+    pub path: String,
+    /// Open even if another server holds the project's lock.
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitNodeContentRequest {
+    pub node_id: Uuid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommitNodeContentResponse {
+    pub node_id: Uuid,
+    pub content: String,
+    pub status: ContentStatus,
+}
+
+/// The active project at a glance; see [`current_project`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CurrentProject {
+    pub name: String,
+    /// Where saves go; `None` until the project has a save target.
+    pub path: Option<String>,
+    /// Whether edits are still waiting for the auto-save.
+    pub dirty: bool,
+    pub node_count: usize,
+}
+
+#[derive(Deserialize)]
+pub struct AddEpisodeRequest {
+    pub title: String,
+    /// Template for the new episode's timeline, as for [`CreateProjectRequest`].
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SwitchEpisodeRequest {
+    pub episode_id: uuid::Uuid,
 }
 
 pub async fn create_project(
@@ -37,13 +133,12 @@ pub async fn create_project(
 ) -> Result<serde_json::Value, BackendError> {
     validation::validate_name(&request.name, "project name")?;
 
-    let template = match request.template.as_str() {
-        "single_cam" => Template::SingleCam,
-        "animated" => Template::Animated,
-        _ => Template::MultiCam,
-    };
-
-    let project = template.build_project(request.name);
+    let total_duration_ms = request
+        .total_duration_ms
+        .unwrap_or_else(|| state.project_defaults.lock().episode_duration_ms);
+    let project = template_by_name(request.template.as_deref())?
+        .build_project_with_duration(request.name, total_duration_ms)
+        .map_err(|error| BackendError::bad_request(error.to_string()))?;
     let project_root = persistence::default_project_dir();
     let save_path = validation::validate_project_path(
         persistence::project_save_path(&project.name)
@@ -52,20 +147,91 @@ pub async fn create_project(
         &project_root,
     )?;
     let json = serde_json::to_value(&project).map_err(|e| BackendError::internal(e.to_string()))?;
-    populate_ydoc_from_project(state, &project).await;
+    // Flush pending edits to the outgoing project before replacing it.
+    state.force_save().await;
+    lock_project_path(state, &save_path, false)?;
+    reconcile_ydoc(state, &project, None).await;
     *state.project.lock() = Some(project);
-    state.project_database.set_active_path(save_path);
+    set_active_project_path(state, save_path);
+    state.timeline_projection_cache.invalidate();
     state.trigger_save();
+    let _ = state.events_tx.send(ServerEvent::ProjectChanged);
     Ok(json)
 }
 
+/// Take the lock on `path` unless it is already the active project.
+fn lock_project_path(state: &AppState, path: &Path, force: bool) -> Result<(), BackendError> {
+    if state.project_database.active_path().as_deref() == Some(path) {
+        return Ok(());
+    }
+    Ok(persistence::acquire_project_lock(path, force)?)
+}
+
+/// Make `path` the save target, releasing the lock on the previous project.
+/// The caller has already locked `path`.
+fn set_active_project_path(state: &AppState, path: PathBuf) {
+    if let Some(previous) = state.project_database.active_path()
+        && previous != path
+    {
+        persistence::release_project_lock(&previous);
+    }
+    state.project_database.set_active_path(path);
+}
+
+fn template_by_name(name: Option<&str>) -> Result<Template, BackendError> {
+    match name.unwrap_or(constants::DEFAULT_PROJECT_TEMPLATE) {
+        "multi_cam" => Ok(Template::MultiCam),
+        "single_cam" => Ok(Template::SingleCam),
+        "animated" => Ok(Template::Animated),
+        other => Err(BackendError::bad_request(format!(
+            "unknown template '{other}'; expected multi_cam, single_cam or animated"
+        ))),
+    }
+}
+
+pub fn get_project_defaults(state: &AppState) -> ProjectDefaults {
+    state.project_defaults.lock().clone()
+}
+
+/// Change what new projects default to. A duration the default template
+/// can't be scaled to is rejected here rather than on the next create.
+pub fn update_project_defaults(
+    state: &AppState,
+    update: ProjectDefaultsUpdate,
+) -> Result<ProjectDefaults, BackendError> {
+    if let Some(episode_duration_ms) = update.episode_duration_ms {
+        template_by_name(None)?
+            .build_project_with_duration("", episode_duration_ms)
+            .map_err(|error| BackendError::bad_request(error.to_string()))?;
+    }
+    let mut defaults = state.project_defaults.lock();
+    if let Some(episode_duration_ms) = update.episode_duration_ms {
+        defaults.episode_duration_ms = episode_duration_ms;
+    }
+    Ok(defaults.clone())
+}
+
 pub fn get_project(state: &AppState) -> Result<serde_json::Value, BackendError> {
-    let guard = state.project.lock();
-    let Some(project) = guard.as_ref() else {
-        return Err(BackendError::no_project());
-    };
+    let project = state
+        .project_snapshot()
+        .ok_or_else(BackendError::no_project)?;
 
-    serde_json::to_value(project).map_err(|e| BackendError::internal(e.to_string()))
+    serde_json::to_value(&*project).map_err(|e| BackendError::internal(e.to_string()))
+}
+
+/// The active project, or `None` when no project is loaded.
+pub fn current_project(state: &AppState) -> Option<CurrentProject> {
+    let guard = state.project.lock();
+    let project = guard.as_ref()?;
+    Some(CurrentProject {
+        name: project.name.clone(),
+        path: state
+            .project_database
+            .active_path()
+            .map(|path| path.display().to_string()),
+        dirty: state.has_unsaved_changes(),
+        node_count: project.timeline.nodes.len(),
+    })
 }
 
 pub fn update_project(
@@ -100,23 +266,34 @@ pub async fn save_project(
         return Err(BackendError::no_project());
     };
 
-    let project_root = persistence::default_project_dir();
-    let requested_path = request.path.unwrap_or_else(|| {
-        state
-            .project_database
-            .active_path()
-            .unwrap_or_else(|| persistence::project_save_path(&project.name))
-            .display()
-            .to_string()
-    });
-    let path = validation::validate_project_path(&requested_path, &project_root)?;
+    // The active path may be a directory opened outside the storage root.
+    let path = match (request.path, state.project_database.active_path()) {
+        (None, Some(active_path)) => active_path,
+        (requested_path, _) => {
+            let requested_path = requested_path.unwrap_or_else(|| {
+                persistence::project_save_path(&project.name)
+                    .display()
+                    .to_string()
+            });
+            validation::validate_project_path(&requested_path, &persistence::default_project_dir())?
+        }
+    };
+    save_project_to(state, &project, path, false).await
+}
 
+async fn save_project_to(
+    state: &AppState,
+    project: &eidetic_core::Project,
+    path: PathBuf,
+    force: bool,
+) -> Result<serde_json::Value, BackendError> {
+    lock_project_path(state, &path, force)?;
     let ydoc_state = crate::ydoc::serialize_doc(&state.doc_tx).await;
-    persistence::save_project(&project, &path, ydoc_state)
+    persistence::save_project(project, &path, ydoc_state)
         .await
         .map_err(BackendError::internal)?;
 
-    state.project_database.set_active_path(path.clone());
+    set_active_project_path(state, path.clone());
     Ok(serde_json::json!({ "saved": path.display().to_string() }))
 }
 
@@ -126,22 +303,42 @@ pub async fn load_project(
 ) -> Result<serde_json::Value, BackendError> {
     let project_root = persistence::default_project_dir();
     let path = validation::validate_project_path(&request.path, &project_root)?;
+    load_project_from(state, path, request.force).await
+}
+
+/// Open the project kept in a directory of the user's choosing, such as a
+/// synced folder. A directory without `project.db` yet receives the loaded
+/// project instead. Later saves write to that directory.
+pub async fn open_project_directory(
+    state: &AppState,
+    request: OpenProjectDirectoryRequest,
+) -> Result<serde_json::Value, BackendError> {
+    let path = validation::validate_project_directory(&request.path)?.join("project.db");
+    if path.exists() {
+        return load_project_from(state, path, request.force).await;
+    }
 
-    let (project, ydoc_state) = persistence::load_project(&path)
+    let project = state.project.lock().clone();
+    let Some(project) = project else {
+        return Err(BackendError::bad_request(
+            "project directory has no project.db and no project is loaded to save there",
+        ));
+    };
+    state.force_save().await;
+    save_project_to(state, &project, path, request.force).await?;
+    serde_json::to_value(&project).map_err(|e| BackendError::internal(e.to_string()))
+}
+
+async fn load_project_from(
+    state: &AppState,
+    path: PathBuf,
+    force: bool,
+) -> Result<serde_json::Value, BackendError> {
+    let (mut project, ydoc_state) = persistence::load_project(&path)
         .await
         .map_err(BackendError::bad_request)?;
+    reconcile_node_statuses(state, &mut project);
     let json = serde_json::to_value(&project).map_err(|e| BackendError::internal(e.to_string()))?;
-
-    if let Some(blob) = ydoc_state {
-        if let Err(error) = crate::ydoc::load_doc(&state.doc_tx, blob).await {
-            tracing::warn!("failed to load Y.Doc state, populating from project: {error}");
-            populate_ydoc_from_project(state, &project).await;
-        }
-    } else {
-        populate_ydoc_from_project(state, &project).await;
-    }
-
-    *state.project.lock() = Some(project);
     let save_path = if path
         .extension()
         .is_some_and(|extension| extension == "json")
@@ -150,43 +347,290 @@ pub async fn load_project(
     } else {
         path
     };
-    state.project_database.set_active_path(save_path);
+    // Flush pending edits to the outgoing project before replacing it.
+    state.force_save().await;
+    lock_project_path(state, &save_path, force)?;
+
+    reconcile_ydoc(state, &project, ydoc_state).await;
+
+    *state.project.lock() = Some(project);
+    set_active_project_path(state, save_path);
+    state.timeline_projection_cache.invalidate();
     state.trigger_save();
+    let _ = state.events_tx.send(ServerEvent::ProjectChanged);
+    if let Err(error) = crate::ai_generation_queue::resume_generation_queue(state).await {
+        tracing::warn!("failed to resume generation queue: {}", error.message());
+    }
     Ok(json)
 }
 
-pub async fn list_projects() -> serde_json::Value {
-    let base_dir = persistence::default_project_dir();
-    let entries = persistence::list_projects(&base_dir).await;
-    serde_json::to_value(&entries).unwrap_or_else(|_| serde_json::json!([]))
+pub fn get_project_ai_settings(state: &AppState) -> Result<ProjectAiSettings, BackendError> {
+    let guard = state.project.lock();
+    let Some(project) = guard.as_ref() else {
+        return Err(BackendError::no_project());
+    };
+    Ok(project.ai_settings.clone())
+}
+
+/// Replace the active project's AI overrides. Blank text fields are dropped
+/// so they fall back to the global config.
+pub fn update_project_ai_settings(
+    state: &AppState,
+    settings: ProjectAiSettings,
+) -> Result<ProjectAiSettings, BackendError> {
+    if settings
+        .temperature
+        .is_some_and(|temperature| !temperature.is_finite() || temperature < 0.0)
+    {
+        return Err(BackendError::bad_request(
+            "temperature must be a non-negative number",
+        ));
+    }
+    let settings = settings.normalized();
+    {
+        let mut guard = state.project.lock();
+        let Some(project) = guard.as_mut() else {
+            return Err(BackendError::no_project());
+        };
+        project.ai_settings = settings.clone();
+    }
+    state.trigger_save();
+    Ok(settings)
+}
+
+/// Copy a node's live editor text from the Y.Doc into its content and save
+/// the project now, so a "save draft" never depends on autosave timing.
+pub async fn commit_node_content(
+    state: &AppState,
+    body: CommitNodeContentRequest,
+) -> Result<CommitNodeContentResponse, BackendError> {
+    let node_id = NodeId(body.node_id);
+    let path = active_project_path(state)?;
+    {
+        let guard = state.project.lock();
+        let Some(project) = guard.as_ref() else {
+            return Err(BackendError::no_project());
+        };
+        project.timeline.node(node_id)?;
+    }
+
+    let texts = crate::ydoc::read_all_nodes(&state.doc_tx)
+        .await
+        .ok_or_else(|| BackendError::internal("document manager is unavailable"))?;
+    let content = texts
+        .into_iter()
+        .find(|text| text.node_id == node_id)
+        .map(|text| text.content)
+        .ok_or_else(|| BackendError::not_found("node has no editor text"))?;
+
+    let text = content.clone();
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|error| BackendError::internal(error.to_string()))?;
+        timeline_node_store::update_node_content_text(&conn, node_id, text)
+            .map_err(map_history_error)
+    })
+    .await
+    .map_err(|error| BackendError::internal(format!("content commit task failed: {error}")))??;
+
+    let status = {
+        let mut guard = state.project.lock();
+        let Some(project) = guard.as_mut() else {
+            return Err(BackendError::no_project());
+        };
+        let node = project.timeline.node_mut(node_id)?;
+        node.content.content = content.clone();
+        node.content.refresh_status();
+        node.content.status
+    };
+    let _ = state.events_tx.send(ServerEvent::NodeUpdated {
+        node_id: body.node_id,
+    });
+    state.force_save().await;
+
+    Ok(CommitNodeContentResponse {
+        node_id: body.node_id,
+        content,
+        status,
+    })
+}
+
+pub fn list_episodes(state: &AppState) -> Result<Vec<EpisodeSummary>, BackendError> {
+    let guard = state.project.lock();
+    let project = guard.as_ref().ok_or_else(BackendError::no_project)?;
+    Ok(project.episode_summaries())
+}
+
+/// Add an episode built from a template at the active episode's length. It is
+/// parked; switch to it to edit.
+pub fn add_episode(
+    state: &AppState,
+    request: AddEpisodeRequest,
+) -> Result<EpisodeSummary, BackendError> {
+    validation::validate_name(&request.title, "episode title")?;
+    let mut guard = state.project.lock();
+    let project = guard.as_mut().ok_or_else(BackendError::no_project)?;
+
+    let built = template_by_name(request.template.as_deref())?
+        .build_project_with_duration(request.title.clone(), project.timeline.total_duration_ms)
+        .map_err(|error| BackendError::bad_request(error.to_string()))?;
+    let id = project.add_episode(request.title, built.timeline, &built.arcs);
+    let summary = project
+        .episode_summaries()
+        .into_iter()
+        .find(|summary| summary.header.id == id)
+        .ok_or_else(|| BackendError::internal("added episode is missing"))?;
+    drop(guard);
+    state.trigger_save();
+    Ok(summary)
+}
+
+/// Make another episode the one timeline commands edit. Pending edits are
+/// flushed first; the swap happens on disk and the project is reloaded.
+pub async fn switch_episode(
+    state: &AppState,
+    request: SwitchEpisodeRequest,
+) -> Result<serde_json::Value, BackendError> {
+    let episode_id = EpisodeId(request.episode_id);
+    {
+        let guard = state.project.lock();
+        let project = guard.as_ref().ok_or_else(BackendError::no_project)?;
+        if project.active_episode.id != episode_id
+            && !project
+                .episodes
+                .iter()
+                .any(|episode| episode.header.id == episode_id)
+        {
+            return Err(eidetic_core::Error::EpisodeNotFound(episode_id.0).into());
+        }
+    }
+    let path = state
+        .project_database
+        .active_path()
+        .ok_or_else(|| BackendError::bad_request("save the project before switching episodes"))?;
+
+    state.force_save().await;
+    persistence::switch_episode(&path, episode_id)
+        .await
+        .map_err(BackendError::internal)?;
+    let (mut project, ydoc_state) = persistence::load_project(&path)
+        .await
+        .map_err(BackendError::internal)?;
+    reconcile_node_statuses(state, &mut project);
+    reconcile_ydoc(state, &project, ydoc_state).await;
+    let json = serde_json::to_value(&project).map_err(|e| BackendError::internal(e.to_string()))?;
+    *state.project.lock() = Some(project);
+    let _ = state.events_tx.send(ServerEvent::TimelineChanged);
+    Ok(json)
+}
+
+pub async fn list_projects(query: ProjectListQuery) -> Result<serde_json::Value, BackendError> {
+    let base_dir = match query.base_dir.as_deref() {
+        Some(base_dir) => {
+            let base_dir = PathBuf::from(base_dir.trim());
+            if !base_dir.is_absolute() || !base_dir.is_dir() {
+                return Err(BackendError::bad_request(
+                    "base_dir must be an absolute path to a directory",
+                ));
+            }
+            base_dir
+        }
+        None => persistence::default_project_dir(),
+    };
+    let entries = sort_and_page_projects(persistence::list_projects(&base_dir).await, &query);
+    serde_json::to_value(&entries).map_err(|e| BackendError::internal(e.to_string()))
+}
+
+fn sort_and_page_projects(
+    mut entries: Vec<persistence::ProjectEntry>,
+    query: &ProjectListQuery,
+) -> Vec<persistence::ProjectEntry> {
+    // RFC3339 UTC timestamps order correctly as strings; name breaks ties.
+    entries.sort_by(|a, b| match query.sort {
+        ProjectSortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        ProjectSortKey::Modified => a.modified.cmp(&b.modified).then(a.name.cmp(&b.name)),
+    });
+    if query.direction == SortDirection::Desc {
+        entries.reverse();
+    }
+    entries
+        .into_iter()
+        .skip(query.offset)
+        .take(query.limit.unwrap_or(usize::MAX))
+        .collect()
+}
+
+/// Repair statuses left stale by an interrupted session, such as nodes still
+/// marked `Generating` with no generation running.
+fn reconcile_node_statuses(state: &AppState, project: &mut eidetic_core::Project) {
+    let generating = state.generating.lock().clone();
+    let mut repaired = 0;
+    for node in &mut project.timeline.nodes {
+        if node
+            .content
+            .reconcile_status(generating.contains(&node.id.0))
+        {
+            repaired += 1;
+        }
+    }
+    if repaired > 0 {
+        tracing::info!("reconciled content status for {repaired} node(s) on load");
+    }
 }
 
-async fn populate_ydoc_from_project(state: &AppState, project: &eidetic_core::Project) {
+/// Make the Y.Doc hold `project`'s text and nothing from the project it
+/// replaces. The saved doc state wins where it has text, since it carries
+/// editor text not yet committed to node content; every other field is
+/// filled from the node's saved notes and content.
+async fn reconcile_ydoc(
+    state: &AppState,
+    project: &eidetic_core::Project,
+    ydoc_state: Option<Vec<u8>>,
+) {
+    if let Err(error) = crate::ydoc::reset_doc(&state.doc_tx).await {
+        tracing::warn!("failed to reset Y.Doc before loading a project: {error}");
+    }
+    if let Some(blob) = ydoc_state
+        && let Err(error) = crate::ydoc::load_doc(&state.doc_tx, blob).await
+    {
+        tracing::warn!("failed to load Y.Doc state, populating from project: {error}");
+    }
+    let live: HashMap<NodeId, NodeText> = crate::ydoc::read_all_nodes(&state.doc_tx)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|text| (text.node_id, text))
+        .collect();
+
     for node in &project.timeline.nodes {
         let _ = state
             .doc_tx
             .send(DocCommand::EnsureNode { node_id: node.id })
             .await;
 
-        if !node.content.notes.is_empty() {
+        let live = live.get(&node.id);
+        let fields = [
+            (
+                ContentField::Notes,
+                &node.content.notes,
+                live.map(|text| text.notes.as_str()),
+            ),
+            (
+                ContentField::Content,
+                &node.content.content,
+                live.map(|text| text.content.as_str()),
+            ),
+        ];
+        for (field, saved, live_text) in fields {
+            if saved.is_empty() || live_text.is_some_and(|text| !text.is_empty()) {
+                continue;
+            }
             let _ = state
                 .doc_tx
                 .send(DocCommand::WriteNodeContent {
                     node_id: node.id,
-                    field: ContentField::Notes,
-                    text: node.content.notes.clone(),
-                    author: "system:load".into(),
-                })
-                .await;
-        }
-
-        if !node.content.content.is_empty() {
-            let _ = state
-                .doc_tx
-                .send(DocCommand::WriteNodeContent {
-                    node_id: node.id,
-                    field: ContentField::Content,
-                    text: node.content.content.clone(),
+                    field,
+                    text: saved.clone(),
                     author: "system:load".into(),
                 })
                 .await;
@@ -196,8 +640,61 @@ async fn populate_ydoc_from_project(state: &AppState, project: &eidetic_core::Pr
 
 #[cfg(test)]
 mod tests {
-    use super::{CreateProjectRequest, create_project};
-    use crate::state::AppState;
+    use super::{
+        AddEpisodeRequest, CommitNodeContentRequest, CreateProjectRequest,
+        OpenProjectDirectoryRequest, ProjectDefaultsUpdate, ProjectListQuery, ProjectSortKey,
+        SortDirection, SwitchEpisodeRequest, add_episode, commit_node_content, create_project,
+        current_project, get_project_ai_settings, get_project_defaults, list_episodes,
+        list_projects, open_project_directory, sort_and_page_projects, switch_episode,
+        update_project_ai_settings, update_project_defaults,
+    };
+    use crate::backend_error::BackendError;
+    use crate::persistence::ProjectEntry;
+    use crate::state::{AppState, ServerEvent};
+    use eidetic_core::project::ProjectAiSettings;
+
+    #[tokio::test]
+    async fn project_ai_settings_override_the_global_config_for_generation() {
+        let state = AppState::new().await;
+        state.ai_config.lock().model = "global-model".into();
+        *state.project.lock() = Some(eidetic_core::Template::MultiCam.build_project("Voice"));
+        assert_eq!(
+            get_project_ai_settings(&state).unwrap(),
+            ProjectAiSettings::default()
+        );
+
+        let settings = update_project_ai_settings(
+            &state,
+            ProjectAiSettings {
+                model: Some(" show-model ".into()),
+                temperature: Some(0.3),
+                style_notes: Some("   ".into()),
+                system_prompt: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(settings.model.as_deref(), Some("show-model"));
+        assert_eq!(settings.style_notes, None);
+        let active = state.active_ai_config();
+        assert_eq!(active.model, "show-model");
+        assert_eq!(active.temperature, 0.3);
+        assert_eq!(state.ai_config.lock().model, "global-model");
+
+        let error = update_project_ai_settings(
+            &state,
+            ProjectAiSettings {
+                temperature: Some(f32::NAN),
+                ..ProjectAiSettings::default()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(error, BackendError::BadRequest(_)));
+
+        *state.project.lock() = Some(eidetic_core::Template::MultiCam.build_project("Other"));
+        assert_eq!(state.active_ai_config().model, "global-model");
+        state.shutdown_tasks();
+    }
 
     #[tokio::test]
     async fn create_project_rejects_invalid_name_without_http_boundary() {
@@ -206,7 +703,8 @@ mod tests {
             &state,
             CreateProjectRequest {
                 name: "bad/name".into(),
-                template: "multi_cam".into(),
+                template: Some("multi_cam".into()),
+                total_duration_ms: None,
             },
         )
         .await
@@ -217,4 +715,345 @@ mod tests {
             "project name contains unsupported characters"
         );
     }
+
+    #[tokio::test]
+    async fn create_project_rejects_durations_the_template_cannot_fit() {
+        let state = AppState::new().await;
+        for (total_duration_ms, message) in [
+            (0, "episode duration must be positive"),
+            (10, "is too short to fit"),
+        ] {
+            let error = create_project(
+                &state,
+                CreateProjectRequest {
+                    name: "Short".into(),
+                    template: None,
+                    total_duration_ms: Some(total_duration_ms),
+                },
+            )
+            .await
+            .unwrap_err();
+            assert!(error.message().contains(message), "{}", error.message());
+        }
+    }
+
+    #[tokio::test]
+    async fn create_project_rejects_unknown_templates() {
+        let state = AppState::new().await;
+        let error = create_project(
+            &state,
+            CreateProjectRequest {
+                name: "Typo".into(),
+                template: Some("multicam".into()),
+                total_duration_ms: None,
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(error, BackendError::BadRequest(_)));
+        assert!(error.message().contains("unknown template 'multicam'"));
+    }
+
+    #[tokio::test]
+    async fn project_defaults_reject_durations_the_template_cannot_fit() {
+        let state = AppState::new().await;
+        let updated = update_project_defaults(
+            &state,
+            ProjectDefaultsUpdate {
+                episode_duration_ms: Some(2_640_000),
+            },
+        )
+        .unwrap();
+        assert_eq!(updated.episode_duration_ms, 2_640_000);
+
+        let error = update_project_defaults(
+            &state,
+            ProjectDefaultsUpdate {
+                episode_duration_ms: Some(0),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(error, BackendError::BadRequest(_)));
+        assert_eq!(get_project_defaults(&state).episode_duration_ms, 2_640_000);
+        state.shutdown_tasks();
+    }
+
+    #[test]
+    fn project_list_defaults_to_recent_first_and_pages_after_sorting() {
+        let entries: Vec<ProjectEntry> = [
+            ("beta", "2026-01-02T00:00:00Z"),
+            ("Alpha", "2026-03-01T00:00:00Z"),
+            ("gamma", "2025-12-31T23:59:59Z"),
+        ]
+        .into_iter()
+        .map(|(name, modified)| ProjectEntry {
+            name: name.into(),
+            path: format!("/projects/{name}/project.db").into(),
+            modified: modified.into(),
+        })
+        .collect();
+        let names = |entries: Vec<ProjectEntry>| -> Vec<String> {
+            entries.into_iter().map(|entry| entry.name).collect()
+        };
+
+        assert_eq!(
+            names(sort_and_page_projects(
+                entries.clone(),
+                &ProjectListQuery::default()
+            )),
+            ["Alpha", "beta", "gamma"]
+        );
+        assert_eq!(
+            names(sort_and_page_projects(
+                entries.clone(),
+                &ProjectListQuery {
+                    sort: ProjectSortKey::Name,
+                    direction: SortDirection::Asc,
+                    offset: 1,
+                    limit: Some(1),
+                    base_dir: None,
+                },
+            )),
+            ["beta"]
+        );
+        assert!(
+            sort_and_page_projects(
+                entries,
+                &ProjectListQuery {
+                    offset: 5,
+                    ..ProjectListQuery::default()
+                },
+            )
+            .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn episodes_are_added_to_the_loaded_project() {
+        let state = AppState::new().await;
+        *state.project.lock() = Some(eidetic_core::Template::MultiCam.build_project("Episodes"));
+
+        let added = add_episode(
+            &state,
+            AddEpisodeRequest {
+                title: "Episode 2".into(),
+                template: Some("animated".into()),
+            },
+        )
+        .unwrap();
+
+        let episodes = list_episodes(&state).unwrap();
+        assert_eq!(episodes.len(), 2);
+        assert!(episodes[0].active);
+        assert_eq!(episodes[1], added);
+        assert!(!added.active && added.scene_count > 0);
+        let error = switch_episode(
+            &state,
+            SwitchEpisodeRequest {
+                episode_id: uuid::Uuid::new_v4(),
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(error, BackendError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn project_directory_outside_storage_root_becomes_the_save_target() {
+        let state = AppState::new().await;
+        let directory = std::env::temp_dir().join(format!(
+            "eidetic-project-directory-{}",
+            uuid::Uuid::new_v4()
+        ));
+        let project_dir = directory.join("Synced");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        let request = || OpenProjectDirectoryRequest {
+            path: project_dir.display().to_string(),
+            force: false,
+        };
+
+        let error = open_project_directory(&state, request()).await.unwrap_err();
+        assert!(error.message().contains("no project is loaded"));
+
+        *state.project.lock() = Some(eidetic_core::Template::MultiCam.build_project("Synced"));
+        open_project_directory(&state, request()).await.unwrap();
+        let saved_path = project_dir.canonicalize().unwrap().join("project.db");
+        assert!(saved_path.is_file());
+        assert_eq!(state.project_database.active_path(), Some(saved_path));
+
+        let reopened = open_project_directory(&state, request()).await.unwrap();
+        assert_eq!(reopened["name"], "Synced");
+        let listed = list_projects(ProjectListQuery {
+            base_dir: Some(directory.display().to_string()),
+            ..ProjectListQuery::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(listed[0]["name"], "Synced");
+        let error = list_projects(ProjectListQuery {
+            base_dir: Some("relative".into()),
+            ..ProjectListQuery::default()
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(error, BackendError::BadRequest(_)));
+
+        state.force_save().await;
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn current_project_reports_the_loaded_project_and_announces_loads() {
+        let state = AppState::new().await;
+        assert_eq!(current_project(&state), None);
+        let directory =
+            std::env::temp_dir().join(format!("eidetic-project-current-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let request = || OpenProjectDirectoryRequest {
+            path: directory.display().to_string(),
+            force: false,
+        };
+        *state.project.lock() = Some(eidetic_core::Template::MultiCam.build_project("Current"));
+        open_project_directory(&state, request()).await.unwrap();
+        let mut events = state.events_tx.subscribe();
+
+        open_project_directory(&state, request()).await.unwrap();
+
+        assert!(
+            std::iter::from_fn(|| events.try_recv().ok())
+                .any(|event| matches!(event, ServerEvent::ProjectChanged))
+        );
+        let current = current_project(&state).unwrap();
+        let saved_path = directory.canonicalize().unwrap().join("project.db");
+        assert_eq!(current.name, "Current");
+        assert_eq!(current.path, Some(saved_path.display().to_string()));
+        assert!(current.dirty);
+        let node_count = state.project.lock().as_ref().unwrap().timeline.nodes.len();
+        assert_eq!(current.node_count, node_count);
+
+        state.force_save().await;
+        assert!(!current_project(&state).unwrap().dirty);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn opening_a_project_replaces_the_previous_projects_editor_text() {
+        let state = AppState::new().await;
+        let directory =
+            std::env::temp_dir().join(format!("eidetic-project-ydoc-{}", uuid::Uuid::new_v4()));
+        let mut node_ids = Vec::new();
+        for name in ["First", "Second"] {
+            let mut project = eidetic_core::Template::MultiCam.build_project(name);
+            project.timeline.nodes[0].content.content = format!("{name} project text");
+            node_ids.push(project.timeline.nodes[0].id);
+            *state.project.lock() = Some(project);
+            std::fs::create_dir_all(directory.join(name)).unwrap();
+            open_project_directory(
+                &state,
+                OpenProjectDirectoryRequest {
+                    path: directory.join(name).display().to_string(),
+                    force: false,
+                },
+            )
+            .await
+            .unwrap();
+        }
+
+        for (name, node_id) in ["First", "Second"].into_iter().zip(node_ids) {
+            open_project_directory(
+                &state,
+                OpenProjectDirectoryRequest {
+                    path: directory.join(name).display().to_string(),
+                    force: false,
+                },
+            )
+            .await
+            .unwrap();
+
+            let nodes = crate::ydoc::read_all_nodes(&state.doc_tx).await.unwrap();
+            let node_count = state.project.lock().as_ref().unwrap().timeline.nodes.len();
+            assert_eq!(nodes.len(), node_count);
+            let text = nodes.iter().find(|text| text.node_id == node_id).unwrap();
+            assert_eq!(text.content, format!("{name} project text"));
+        }
+
+        state.force_save().await;
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn project_directory_locked_by_another_server_needs_force() {
+        let state = AppState::new().await;
+        *state.project.lock() = Some(eidetic_core::Template::MultiCam.build_project("Locked"));
+        let directory =
+            std::env::temp_dir().join(format!("eidetic-project-locked-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&directory).unwrap();
+        // pid 1 is always running, so the lock is fresh.
+        std::fs::write(
+            directory.join(".lock"),
+            r#"{"pid":1,"started_at":"2026-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        let request = |force| OpenProjectDirectoryRequest {
+            path: directory.display().to_string(),
+            force,
+        };
+
+        let error = open_project_directory(&state, request(false))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, BackendError::Conflict(_)), "{error:?}");
+        assert!(state.project_database.active_path().is_none());
+
+        open_project_directory(&state, request(true)).await.unwrap();
+        let lock = std::fs::read_to_string(directory.join(".lock")).unwrap();
+        assert!(lock.contains(&format!("\"pid\":{}", std::process::id())));
+        state.force_save().await;
+        state.release_project_lock();
+        assert!(!directory.join(".lock").exists());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[tokio::test]
+    async fn commit_node_content_saves_the_live_editor_text() {
+        use crate::ydoc::{ContentField, DocCommand};
+        use eidetic_core::Template;
+        use eidetic_core::timeline::node::ContentStatus;
+
+        let path = std::env::temp_dir().join(format!(
+            "eidetic-commit-content-{}.db",
+            uuid::Uuid::new_v4()
+        ));
+        let state = AppState::new().await;
+        let project = Template::MultiCam.build_project("Commit Test");
+        let node_id = project.timeline.nodes[0].id;
+        *state.project.lock() = Some(project);
+        *state.project_path.lock() = Some(path.clone());
+        state.force_save().await;
+        state
+            .doc_tx
+            .send(DocCommand::WriteNodeContent {
+                node_id,
+                field: ContentField::Content,
+                text: "INT. LIGHTHOUSE - NIGHT".into(),
+                author: "human:test".into(),
+            })
+            .await
+            .unwrap();
+
+        let response = commit_node_content(&state, CommitNodeContentRequest { node_id: node_id.0 })
+            .await
+            .expect("commit");
+
+        assert_eq!(response.content, "INT. LIGHTHOUSE - NIGHT");
+        assert_eq!(response.status, ContentStatus::HasContent);
+        let (saved, _) = crate::persistence::load_project(&path).await.unwrap();
+        let node = saved.timeline.node(node_id).unwrap();
+        assert_eq!(node.content.content, "INT. LIGHTHOUSE - NIGHT");
+        assert_eq!(node.content.status, ContentStatus::HasContent);
+
+        let _ = std::fs::remove_file(path);
+    }
 }
@@ -1,18 +1,29 @@
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use eidetic_core::ai::backend::ChildPlanListProjection;
+use eidetic_core::ai::backend::{ChildPlan, ChildPlanListProjection, ChildPlanRangePreview};
 use eidetic_core::contracts::{
+    ArcCoverageProjection, ArcEntityEvolutionProjection, BibleEntityPresenceProjection,
     BibleGraphNodeId, BibleGraphNodeListProjection, BibleGraphSchemaListProjection,
     BibleNodeDetailProjection, BibleReferenceProposalListProjection, ChangeReviewProjection,
     ObjectKind, ProjectionEnvelope, PropagationProposalListProjection, ScriptDocumentId,
     ScriptDocumentProjection, SelectedNodeEditorProjection, StoryArcListProjection,
-    StoryArcProgressionProjection, TimelineRenderProjection,
-    builtin_bible_graph_schema_list_projection,
+    StoryArcProgressionProjection, TimelineContextAtProjection, TimelineContextEntity,
+    TimelineOverviewProjection, TimelineRenderProjection,
+    builtin_bible_graph_schema_list_projection, resolved_state_at,
 };
+use eidetic_core::story::arc::ArcId;
 use eidetic_core::story::progression::analyze_all_arcs;
-use eidetic_core::timeline::Timeline;
-use eidetic_core::timeline::node::NodeId;
-use serde::Deserialize;
+use eidetic_core::timeline::beat_palette::BeatTypeWarning;
+use eidetic_core::timeline::node::{NodeId, StoryLevel, StoryNode};
+use eidetic_core::timeline::relationship::{RelationshipType, RelationshipTypeInfo};
+use eidetic_core::timeline::structure::{
+    EpisodeStructure, StructureNormalization, StructureSegment,
+};
+use eidetic_core::timeline::timing::TimeRange;
+use eidetic_core::timeline::track::Track;
+use eidetic_core::timeline::{Timeline, TimelineTreeNode};
+use serde::{Deserialize, Serialize};
 
 use crate::backend_error::BackendError;
 use crate::bible_graph_store;
@@ -49,6 +60,15 @@ pub struct BibleGraphNodeProjectionRequest {
     pub node_id: BibleGraphNodeId,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BibleEntityPresenceRequest {
+    pub node_id: BibleGraphNodeId,
+    /// Only count referencing timeline nodes at this level.
+    #[serde(default)]
+    pub level: Option<StoryLevel>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct SelectedNodeEditorProjectionRequest {
@@ -110,6 +130,37 @@ pub async fn bible_graph_node_list_projection(
         })?
 }
 
+/// Where a bible entity appears on the timeline; see
+/// [`BibleEntityPresenceProjection`].
+pub async fn bible_entity_presence_projection(
+    state: &AppState,
+    request: BibleEntityPresenceRequest,
+) -> Result<BibleEntityPresenceProjection, BackendError> {
+    let path = active_project_path(state)?;
+    let timeline = state
+        .project
+        .lock()
+        .as_ref()
+        .map(|project| project.timeline.clone())
+        .ok_or_else(BackendError::no_project)?;
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        crate::bible_entity_presence::load_bible_entity_presence(
+            &conn,
+            &timeline,
+            &request.node_id,
+            request.level,
+        )
+        .map_err(map_history_error)?
+        .ok_or_else(|| BackendError::not_found("bible graph node not found"))
+    })
+    .await
+    .map_err(|error| {
+        BackendError::internal(format!("bible entity presence task failed: {error}"))
+    })?
+}
+
 pub fn bible_graph_schema_list_projection(
     state: &AppState,
 ) -> Result<ProjectionEnvelope<BibleGraphSchemaListProjection>, BackendError> {
@@ -152,6 +203,31 @@ pub async fn child_plan_list_projection(
         })?
 }
 
+/// Compute the time ranges a child plan would get if applied now, using the
+/// same split as applying it. Lets the UI show exact durations
+/// while the user edits a plan.
+pub async fn child_plan_range_preview(
+    state: &AppState,
+    plan: ChildPlan,
+) -> Result<ChildPlanRangePreview, BackendError> {
+    for child in &plan.children {
+        crate::validation::validate_positive_finite_f32(child.weight, "child weight")?;
+    }
+    let path = active_project_path(state)?;
+    let (project, _) = crate::persistence::load_project(&path)
+        .await
+        .map_err(BackendError::internal)?;
+    let parent = project.timeline.node(plan.parent_node_id).map_err(|_| {
+        BackendError::not_found(format!("node not found: {}", plan.parent_node_id.0))
+    })?;
+    let weights: Vec<f32> = plan.children.iter().map(|child| child.weight).collect();
+
+    Ok(ChildPlanRangePreview {
+        parent_node_id: plan.parent_node_id,
+        ranges: project.timeline.split_child_ranges(parent, &weights),
+    })
+}
+
 pub async fn story_arc_list_projection(
     state: &AppState,
 ) -> Result<ProjectionEnvelope<StoryArcListProjection>, BackendError> {
@@ -172,11 +248,10 @@ pub async fn story_arc_progression_projection(
         .map_err(|error| {
             BackendError::internal(format!("story arc progression task failed: {error}"))
         })??;
-    let guard = state.project.lock();
-    let Some(project) = guard.as_ref() else {
-        return Err(BackendError::no_project());
-    };
-    let mut projection_project = project.clone();
+    let project = state
+        .project_snapshot()
+        .ok_or_else(BackendError::no_project)?;
+    let mut projection_project = Arc::unwrap_or_clone(project);
     projection_project.arcs = arcs;
 
     Ok(ProjectionEnvelope::initial(
@@ -184,6 +259,70 @@ pub async fn story_arc_progression_projection(
     ))
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArcEntityEvolutionQuery {
+    pub arc_id: ArcId,
+}
+
+/// Which bible entities develop within an arc, and how; see
+/// [`ArcEntityEvolutionProjection`].
+pub async fn arc_entity_evolution_projection(
+    state: &AppState,
+    query: ArcEntityEvolutionQuery,
+) -> Result<ArcEntityEvolutionProjection, BackendError> {
+    let path = active_project_path(state)?;
+    let timeline = state
+        .project
+        .lock()
+        .as_ref()
+        .map(|project| project.timeline.clone())
+        .ok_or_else(BackendError::no_project)?;
+    tokio::task::spawn_blocking(move || {
+        let arcs = load_story_arcs_at_path(path.clone())?;
+        if !arcs.iter().any(|arc| arc.id == query.arc_id) {
+            return Err(BackendError::not_found("story arc not found"));
+        }
+        let conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        crate::arc_entity_evolution::load_arc_entity_evolution(&conn, &timeline, query.arc_id)
+            .map_err(map_history_error)
+    })
+    .await
+    .map_err(|error| BackendError::internal(format!("arc entity evolution task failed: {error}")))?
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArcCoverageQuery {
+    pub arc_id: ArcId,
+    /// Gaps this long or shorter are not reported.
+    #[serde(default)]
+    pub min_gap_ms: u64,
+}
+
+/// Where an arc is present on the timeline and where it drops out; see
+/// [`ArcCoverageProjection`].
+pub async fn arc_coverage_projection(
+    state: &AppState,
+    query: ArcCoverageQuery,
+) -> Result<ArcCoverageProjection, BackendError> {
+    let path = active_project_path(state)?;
+    let arcs = tokio::task::spawn_blocking(move || load_story_arcs_at_path(path))
+        .await
+        .map_err(|error| BackendError::internal(format!("arc coverage task failed: {error}")))??;
+    if !arcs.iter().any(|arc| arc.id == query.arc_id) {
+        return Err(BackendError::not_found("story arc not found"));
+    }
+    let guard = state.project.lock();
+    let project = guard.as_ref().ok_or_else(BackendError::no_project)?;
+    Ok(ArcCoverageProjection {
+        arc_id: query.arc_id,
+        coverage: project.timeline.arc_coverage(query.arc_id),
+        gaps: project
+            .timeline
+            .arc_coverage_gaps(query.arc_id, query.min_gap_ms),
+    })
+}
+
 pub async fn change_review_projection(
     state: &AppState,
 ) -> Result<ProjectionEnvelope<ChangeReviewProjection>, BackendError> {
@@ -200,26 +339,299 @@ pub async fn timeline_render_projection(
 ) -> Result<ProjectionEnvelope<TimelineRenderProjection>, BackendError> {
     let path = active_project_path(state)?;
     let selected_node_id = *state.selected_timeline_node_id.lock();
+    let cache = state.timeline_projection_cache.clone();
+    if let Some(mut envelope) = cache.get(&path) {
+        envelope.payload.selected_node_id = selected_node_id;
+        return Ok(envelope);
+    }
+    let generation = cache.generation();
     let (project, _) = crate::persistence::load_project(&path)
         .await
         .map_err(BackendError::internal)?;
-    tokio::task::spawn_blocking(move || {
+    let mut envelope = tokio::task::spawn_blocking(move || {
         let conn = crate::sqlite::open_write_connection(&path)
             .map_err(|e| BackendError::internal(e.to_string()))?;
         let mut projection = TimelineRenderProjection::from_timeline(&project.timeline);
-        projection.selected_node_id = selected_node_id;
         crate::timeline_affect_overlay::apply_timeline_affect_overlays(
             &conn,
             &project.timeline,
             &mut projection,
         )
         .map_err(map_history_error)?;
-        Ok(ProjectionEnvelope::initial(projection))
+        let envelope = ProjectionEnvelope::initial(projection);
+        cache.store(path, generation, envelope.clone());
+        Ok::<_, BackendError>(envelope)
     })
     .await
     .map_err(|error| {
         BackendError::internal(format!("timeline render projection task failed: {error}"))
-    })?
+    })??;
+    envelope.payload.selected_node_id = selected_node_id;
+    Ok(envelope)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimelineRangeQuery {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// Nodes at every level overlapping a time window, sorted by level then start.
+pub async fn timeline_nodes_in_range(
+    state: &AppState,
+    query: TimelineRangeQuery,
+) -> Result<Vec<StoryNode>, BackendError> {
+    let range = TimeRange::new(query.start_ms, query.end_ms)
+        .map_err(|error| BackendError::bad_request(error.to_string()))?;
+    let path = active_project_path(state)?;
+    let (project, _) = crate::persistence::load_project(&path)
+        .await
+        .map_err(BackendError::internal)?;
+    Ok(project
+        .timeline
+        .nodes_in_range(range)
+        .into_iter()
+        .cloned()
+        .collect())
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimelineContextAtQuery {
+    pub at_ms: u64,
+}
+
+/// Active nodes, their arcs, and every bible entity's resolved state at one
+/// instant, read from a single load of the project.
+pub async fn timeline_context_at(
+    state: &AppState,
+    query: TimelineContextAtQuery,
+) -> Result<TimelineContextAtProjection, BackendError> {
+    let path = active_project_path(state)?;
+    let (project, _) = crate::persistence::load_project(&path)
+        .await
+        .map_err(BackendError::internal)?;
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        story_arc_store::create_schema(&conn).map_err(map_history_error)?;
+        let arcs = story_arc_store::load_arcs(&conn).map_err(map_history_error)?;
+        let entities = load_context_entities_at(&conn, query.at_ms).map_err(map_history_error)?;
+        Ok(TimelineContextAtProjection::new(
+            &project.timeline,
+            &arcs,
+            query.at_ms,
+            entities,
+        ))
+    })
+    .await
+    .map_err(|error| BackendError::internal(format!("timeline context task failed: {error}")))?
+}
+
+fn load_context_entities_at(
+    conn: &rusqlite::Connection,
+    at_ms: u64,
+) -> Result<Vec<TimelineContextEntity>, HistoryStoreError> {
+    bible_graph_store::create_schema(conn)?;
+    let mut entities = Vec::new();
+    for node in bible_graph_store::load_node_list_projection(conn)?.nodes {
+        if node.system_owned {
+            continue;
+        }
+        let snapshots =
+            crate::bible_graph_snapshot_store::load_snapshot_projections(conn, &node.id)?;
+        entities.push(TimelineContextEntity {
+            fields: resolved_state_at(&snapshots, at_ms),
+            node_id: node.id,
+            name: node.name,
+        });
+    }
+    Ok(entities)
+}
+
+/// Pinned nodes in timeline order, for a quick-jump list.
+pub async fn timeline_pinned_nodes(state: &AppState) -> Result<Vec<StoryNode>, BackendError> {
+    let path = active_project_path(state)?;
+    let (project, _) = crate::persistence::load_project(&path)
+        .await
+        .map_err(BackendError::internal)?;
+    Ok(project
+        .timeline
+        .pinned_nodes()
+        .into_iter()
+        .cloned()
+        .collect())
+}
+
+/// Setup beats no payoff relationship points away from, in timeline order.
+pub async fn timeline_unpaid_setups(state: &AppState) -> Result<Vec<StoryNode>, BackendError> {
+    let path = active_project_path(state)?;
+    let (project, _) = crate::persistence::load_project(&path)
+        .await
+        .map_err(BackendError::internal)?;
+    project
+        .timeline
+        .unpaid_setups()
+        .into_iter()
+        .map(|node_id| Ok(project.timeline.node(node_id)?.clone()))
+        .collect()
+}
+
+/// Most buckets a timeline overview may be split into.
+pub const MAX_TIMELINE_OVERVIEW_BUCKETS: usize = 1_000;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimelineOverviewQuery {
+    pub level: StoryLevel,
+    pub buckets: usize,
+}
+
+/// The timeline's tracks in display order with their collapsed state, for
+/// track UI that does not need the nodes.
+pub fn timeline_tracks(state: &AppState) -> Result<Vec<Track>, BackendError> {
+    let guard = state.project.lock();
+    let project = guard.as_ref().ok_or_else(BackendError::no_project)?;
+    let mut tracks = project.timeline.tracks.clone();
+    tracks.sort_by_key(|track| track.sort_order);
+    Ok(tracks)
+}
+
+/// The relationship types timeline relationships can be created with; see
+/// [`RelationshipType::catalog`].
+pub fn relationship_types() -> Vec<RelationshipTypeInfo> {
+    RelationshipType::catalog()
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EpisodeStructureValidationRequest {
+    pub segments: Vec<StructureSegment>,
+    pub total_duration_ms: u64,
+}
+
+/// Check a proposed structure without applying it; see
+/// [`EpisodeStructure::normalize`].
+pub fn validate_episode_structure(
+    request: EpisodeStructureValidationRequest,
+) -> Result<StructureNormalization, BackendError> {
+    if request.total_duration_ms == 0 {
+        return Err(BackendError::bad_request(
+            "total_duration_ms must be greater than zero",
+        ));
+    }
+    let structure = EpisodeStructure {
+        template_name: "Custom".to_string(),
+        segments: request.segments,
+    };
+    Ok(structure.normalize(request.total_duration_ms))
+}
+
+/// The episode at one level summarized into equal time buckets; see
+/// [`TimelineOverviewProjection::new`].
+pub async fn timeline_overview(
+    state: &AppState,
+    query: TimelineOverviewQuery,
+) -> Result<TimelineOverviewProjection, BackendError> {
+    if query.buckets == 0 || query.buckets > MAX_TIMELINE_OVERVIEW_BUCKETS {
+        return Err(BackendError::bad_request(format!(
+            "buckets must be between 1 and {MAX_TIMELINE_OVERVIEW_BUCKETS}"
+        )));
+    }
+    let path = active_project_path(state)?;
+    let (project, _) = crate::persistence::load_project(&path)
+        .await
+        .map_err(BackendError::internal)?;
+    tokio::task::spawn_blocking(move || {
+        let conn = crate::sqlite::open_write_connection(&path)
+            .map_err(|e| BackendError::internal(e.to_string()))?;
+        story_arc_store::create_schema(&conn).map_err(map_history_error)?;
+        let arcs = story_arc_store::load_arcs(&conn).map_err(map_history_error)?;
+        Ok(TimelineOverviewProjection::new(
+            &project.timeline,
+            &arcs,
+            query.level,
+            query.buckets,
+        ))
+    })
+    .await
+    .map_err(|error| BackendError::internal(format!("timeline overview task failed: {error}")))?
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimelineTreeQuery {
+    /// Return only this node's subtree.
+    #[serde(default)]
+    pub root: Option<NodeId>,
+}
+
+/// The timeline as nested node trees; see [`Timeline::node_tree`].
+pub async fn timeline_node_tree(
+    state: &AppState,
+    query: TimelineTreeQuery,
+) -> Result<Vec<TimelineTreeNode>, BackendError> {
+    let path = active_project_path(state)?;
+    let (project, _) = crate::persistence::load_project(&path)
+        .await
+        .map_err(BackendError::internal)?;
+    project.timeline.node_tree(query.root).map_err(|_| {
+        BackendError::not_found(format!(
+            "node not found: {}",
+            query.root.map(|id| id.0.to_string()).unwrap_or_default()
+        ))
+    })
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BeatTypeWarningsQuery {
+    pub node_id: NodeId,
+}
+
+/// Beats at or under a node whose type is unusual for their place in the
+/// episode; see [`Timeline::beat_type_warnings`].
+pub async fn beat_type_warnings(
+    state: &AppState,
+    query: BeatTypeWarningsQuery,
+) -> Result<Vec<BeatTypeWarning>, BackendError> {
+    let path = active_project_path(state)?;
+    let (project, _) = crate::persistence::load_project(&path)
+        .await
+        .map_err(BackendError::internal)?;
+    Ok(project.timeline.beat_type_warnings(query.node_id)?)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NodeStalenessQuery {
+    pub node_id: NodeId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NodeStaleness {
+    pub node_id: NodeId,
+    /// The notes changed since the current content was generated.
+    pub stale: bool,
+    /// Whether the content was generated at all; hand-written content is
+    /// never stale.
+    pub generated: bool,
+}
+
+/// Whether a node's notes changed since its content was last generated.
+pub async fn node_staleness(
+    state: &AppState,
+    query: NodeStalenessQuery,
+) -> Result<NodeStaleness, BackendError> {
+    let path = active_project_path(state)?;
+    let (project, _) = crate::persistence::load_project(&path)
+        .await
+        .map_err(BackendError::internal)?;
+    let node = project.timeline.node(query.node_id)?;
+    Ok(NodeStaleness {
+        node_id: node.id,
+        stale: node.content.notes_stale(),
+        generated: node.content.notes_hash_at_generation.is_some(),
+    })
 }
 
 pub async fn selected_node_editor_projection(
@@ -395,6 +807,9 @@ fn map_semantic_proposal_error(error: SemanticProposalStoreError) -> BackendErro
         SemanticProposalStoreError::NotFound(message) => BackendError::not_found(message),
         SemanticProposalStoreError::History(error) => map_history_error(error),
         SemanticProposalStoreError::Sqlite(error) => BackendError::internal(error.to_string()),
+        SemanticProposalStoreError::BibleGraphContract(error) => {
+            BackendError::bad_request(error.to_string())
+        }
     }
 }
 
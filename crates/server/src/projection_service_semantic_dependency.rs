@@ -128,7 +128,7 @@ fn validate_endpoint_filter(
     Ok(())
 }
 
-fn map_semantic_dependency_error(error: SemanticDependencyStoreError) -> BackendError {
+pub(crate) fn map_semantic_dependency_error(error: SemanticDependencyStoreError) -> BackendError {
     match error {
         SemanticDependencyStoreError::InvalidCommand(message) => BackendError::bad_request(message),
         SemanticDependencyStoreError::History(error) => map_history_error(error),
@@ -1,14 +1,22 @@
 use eidetic_core::ai::backend::{GenerateChildrenRequest, GenerateRequest};
-use eidetic_core::timeline::node::StoryLevel;
+use eidetic_core::ai::prompt::limit_siblings;
+use eidetic_core::contracts::BibleGraphSnapshotField;
+use eidetic_core::story::arc::StoryArc;
+use eidetic_core::text::truncate_chars;
+use eidetic_core::timeline::node::{StoryLevel, StoryNode};
 use eidetic_core::timeline::structure::SegmentType;
-use eidetic_core::timeline::timing::TimeRange;
+use eidetic_core::timeline::timing::{TimeRange, format_time};
 
+use crate::state::AiConfig;
 use crate::{ai_affect_context_prompt, ai_bible_context_prompt};
 
 /// A structured chat prompt ready for serialization to any backend API.
 pub(crate) struct ChatPrompt {
     pub system: String,
     pub user: String,
+    /// Output the assistant already wrote, sent as its prior turn so the
+    /// model carries on from where that output stops.
+    pub assistant: Option<String>,
 }
 
 /// Build a chat prompt from a `GenerateRequest`.
@@ -19,13 +27,82 @@ pub(crate) fn build_chat_prompt(request: &GenerateRequest) -> ChatPrompt {
     ChatPrompt {
         system: build_system_message(request),
         user: build_user_message(request),
+        assistant: None,
     }
 }
 
+/// Narrow a request's sibling context to the configured count and preview length.
+pub(crate) fn apply_sibling_limits(request: &mut GenerateRequest, config: &AiConfig) {
+    limit_siblings(request, config.max_siblings);
+    request.sibling_preview_chars = config.sibling_preview_chars;
+}
+
+/// Rough token count for a chat prompt, using the ~4 characters per token
+/// heuristic. Good enough to warn before a prompt overruns the context window.
+pub(crate) fn estimate_tokens(prompt: &ChatPrompt) -> usize {
+    let assistant = prompt.assistant.as_deref().unwrap_or_default();
+    (prompt.system.chars().count() + prompt.user.chars().count() + assistant.chars().count())
+        .div_ceil(4)
+}
+
+/// A named context section and the function that removes it from a request,
+/// returning whether anything was removed.
+type ContextTrim = (&'static str, fn(&mut GenerateRequest) -> bool);
+
+/// Context sections in the order they are dropped when a prompt is over budget.
+const CONTEXT_TRIM_ORDER: [ContextTrim; 7] = [
+    ("following_scripts", |r| {
+        !std::mem::take(&mut r.surrounding_context.following_scripts).is_empty()
+    }),
+    ("following_recaps", |r| {
+        !std::mem::take(&mut r.surrounding_context.following_recaps).is_empty()
+    }),
+    ("rag_context", |r| {
+        !std::mem::take(&mut r.rag_context).is_empty()
+    }),
+    ("bible_context", |r| r.bible_context.take().is_some()),
+    ("siblings", |r| {
+        let target_id = r.target_node.id;
+        let before = r.siblings.len();
+        r.siblings.retain(|sibling| sibling.id == target_id);
+        r.siblings.len() != before
+    }),
+    ("preceding_scripts", |r| {
+        !std::mem::take(&mut r.surrounding_context.preceding_scripts).is_empty()
+    }),
+    ("preceding_recaps", |r| {
+        !std::mem::take(&mut r.surrounding_context.preceding_recaps).is_empty()
+    }),
+];
+
+/// Drop the lowest-priority context sections from `request` until its chat
+/// prompt fits within `budget` tokens.
+///
+/// Sections are removed whole, in this order: following scripts, following
+/// recaps, reference material, bible context, non-target siblings, preceding scripts, then
+/// preceding recaps. Returns the names of the removed sections.
+pub(crate) fn trim_request_to_budget(
+    request: &mut GenerateRequest,
+    budget: usize,
+) -> Vec<&'static str> {
+    let mut trimmed = Vec::new();
+    for (name, trim) in CONTEXT_TRIM_ORDER {
+        if estimate_tokens(&build_chat_prompt(request)) <= budget {
+            break;
+        }
+        if trim(request) {
+            trimmed.push(name);
+        }
+    }
+    trimmed
+}
+
 fn build_system_message(request: &GenerateRequest) -> String {
     let level = request.target_node.level;
 
-    let mut system = if level == StoryLevel::Beat {
+    let mut system = if let Some(system_prompt) = &request.system_prompt {
+        format!("{system_prompt}\n")
+    } else if level == StoryLevel::Beat {
         String::from(
             "You are an experienced TV screenwriter writing a 30-minute comedy/drama episode. \
              Write in standard screenplay format.\n\n\
@@ -79,23 +156,8 @@ fn build_user_message(request: &GenerateRequest) -> String {
         )
     };
 
-    // Arc context.
-    if !request.tagged_arcs.is_empty() {
-        user.push_str("STORY ARCS: ");
-        let arc_strs: Vec<String> = request
-            .tagged_arcs
-            .iter()
-            .map(|a| {
-                let mut s = format!("{} ({:?})", a.name, a.arc_type);
-                if !a.description.is_empty() {
-                    s.push_str(&format!(" — {}", a.description));
-                }
-                s
-            })
-            .collect();
-        user.push_str(&arc_strs.join("; "));
-        user.push('\n');
-    }
+    push_premise(&mut user, &request.premise);
+    push_arc_context(&mut user, request);
 
     // Node info.
     user.push_str(&format!(
@@ -112,23 +174,7 @@ fn build_user_message(request: &GenerateRequest) -> String {
     user.push_str(&request.target_node.content.notes);
     user.push_str("\n\n");
 
-    // Ancestor context (parent, grandparent, etc.).
-    if !request.ancestor_chain.is_empty() {
-        user.push_str("CONTEXT HIERARCHY:\n");
-        for ancestor in &request.ancestor_chain {
-            user.push_str(&format!(
-                "- {} ({}): {}\n",
-                ancestor.name,
-                ancestor.level.label(),
-                if ancestor.content.notes.is_empty() {
-                    "[no notes]"
-                } else {
-                    &ancestor.content.notes
-                },
-            ));
-        }
-        user.push('\n');
-    }
+    push_ancestor_context(&mut user, request);
 
     // Sibling context (same level, same parent).
     if !request.siblings.is_empty() {
@@ -144,8 +190,9 @@ fn build_user_message(request: &GenerateRequest) -> String {
                 ""
             };
             let text = sibling.best_text();
-            let preview = if text.len() > 200 {
-                format!("{}...", &text[..200])
+            let truncated = truncate_chars(text, request.sibling_preview_chars);
+            let preview = if truncated.len() < text.len() {
+                format!("{truncated}...")
             } else {
                 text.to_string()
             };
@@ -171,6 +218,16 @@ fn build_user_message(request: &GenerateRequest) -> String {
         }
     }
 
+    if !request.surrounding_context.following_recaps.is_empty() {
+        user.push_str("LEADS INTO (upcoming, keep consistent):\n\n");
+        for entry in &request.surrounding_context.following_recaps {
+            user.push_str(&format!(
+                "--- {} / {} ---\n{}\n\n",
+                entry.arc_name, entry.node_name, entry.recap,
+            ));
+        }
+    }
+
     if let Some(bible_context) = &request.bible_context {
         ai_bible_context_prompt::append_bible_context(&mut user, bible_context);
     }
@@ -244,6 +301,549 @@ fn build_user_message(request: &GenerateRequest) -> String {
     user
 }
 
+/// The project premise, so the model knows what the whole story is about.
+fn push_premise(user: &mut String, premise: &str) {
+    if premise.is_empty() {
+        return;
+    }
+    user.push_str(&format!("SERIES/EPISODE PREMISE: {premise}\n\n"));
+}
+
+fn push_arc_context(user: &mut String, request: &GenerateRequest) {
+    if request.tagged_arcs.is_empty() {
+        return;
+    }
+    user.push_str("STORY ARCS: ");
+    let arc_strs: Vec<String> = request
+        .tagged_arcs
+        .iter()
+        .map(|a| {
+            let mut s = format!("{} ({:?})", a.name, a.arc_type);
+            if !a.description.is_empty() {
+                s.push_str(&format!(" — {}", a.description));
+            }
+            s
+        })
+        .collect();
+    user.push_str(&arc_strs.join("; "));
+    user.push('\n');
+}
+
+/// Ancestor context (parent, grandparent, etc.).
+fn push_ancestor_context(user: &mut String, request: &GenerateRequest) {
+    if request.ancestor_chain.is_empty() {
+        return;
+    }
+    user.push_str("CONTEXT HIERARCHY:\n");
+    for ancestor in &request.ancestor_chain {
+        user.push_str(&format!(
+            "- {} ({}): {}\n",
+            ancestor.name,
+            ancestor.level.label(),
+            if ancestor.content.notes.is_empty() {
+                "[no notes]"
+            } else {
+                &ancestor.content.notes
+            },
+        ));
+    }
+    user.push('\n');
+}
+
+/// Re-prompt for the rest of a generation that stopped at the token limit,
+/// keeping the original instructions and quoting the partial output.
+pub(crate) fn build_continuation_prompt(prompt: &ChatPrompt, partial: &str) -> ChatPrompt {
+    ChatPrompt {
+        system: prompt.system.clone(),
+        user: format!(
+            "{}\n\nYOUR OUTPUT SO FAR (cut off at the length limit):\n{partial}\n\n\
+             Continue exactly where the output stops. Do not repeat any of it.",
+            prompt.user
+        ),
+        assistant: None,
+    }
+}
+
+/// Build a chat prompt that continues a node's existing content, sent as the
+/// assistant's prior turn so the model resumes mid-draft.
+pub(crate) fn build_continue_content_prompt(prompt: &ChatPrompt, existing: &str) -> ChatPrompt {
+    ChatPrompt {
+        system: prompt.system.clone(),
+        user: format!(
+            "{}\n\nYour draft was cut off. Continue it from exactly where it stops, \
+             without repeating any of it.",
+            prompt.user
+        ),
+        assistant: Some(existing.to_owned()),
+    }
+}
+
+/// Shortest repeated text trimmed from the start of a continuation, unless it
+/// is the whole last line; shorter matches are likely coincidence.
+const MIN_CONTINUATION_OVERLAP_CHARS: usize = 8;
+
+/// Trim whatever a continuation repeats from the end of the existing text,
+/// such as the last line re-emitted before new output.
+pub(crate) fn trim_continuation_overlap<'a>(existing: &str, continuation: &'a str) -> &'a str {
+    let existing = existing.trim_end();
+    let candidate = continuation.trim_start();
+    for (start, _) in existing.char_indices() {
+        let suffix = &existing[start..];
+        if suffix.chars().count() < MIN_CONTINUATION_OVERLAP_CHARS {
+            break;
+        }
+        if let Some(rest) = candidate.strip_prefix(suffix) {
+            return rest;
+        }
+    }
+    if let Some(last_line) = existing.lines().last().map(str::trim)
+        && !last_line.is_empty()
+        && let Some(rest) = candidate.strip_prefix(last_line)
+        && rest.chars().next().is_none_or(char::is_whitespace)
+    {
+        return rest;
+    }
+    continuation
+}
+
+/// Unwrap output the model fenced as a Markdown code block, dropping the
+/// opening fence with its info string and the closing fence if there is one.
+/// Text that does not open with a fence is returned unchanged.
+pub(crate) fn strip_code_fence(text: &str) -> &str {
+    let Some(rest) = text.trim().strip_prefix("```") else {
+        return text;
+    };
+    let body = match rest.split_once('\n') {
+        Some((_info, body)) => body,
+        None => rest.trim_start_matches(|c: char| c.is_ascii_alphanumeric()),
+    };
+    let body = body.trim_end();
+    body.strip_suffix("```").unwrap_or(body).trim()
+}
+
+/// Openings of the chatty lead-in some models put before the prose, such as
+/// "Here's the scene:". Compared lowercase.
+const PREAMBLE_OPENERS: &[&str] = &[
+    "here's",
+    "here is",
+    "below is",
+    "sure",
+    "certainly",
+    "of course",
+    "okay",
+];
+
+/// Longest first line treated as a lead-in rather than script text.
+const MAX_PREAMBLE_CHARS: usize = 120;
+
+/// Clean generated prose before it is stored: drop a one-line lead-in such
+/// as "Here's the scene:" and unwrap a single code fence around the rest.
+/// Only a short first line that opens with a known lead-in phrase and ends
+/// in a colon counts, so real script text is left alone.
+pub(crate) fn clean_prose_output(raw: &str) -> String {
+    let text = raw.trim();
+    let text = match text.split_once('\n') {
+        Some((first, rest)) if is_meta_preamble(first) && !rest.trim().is_empty() => rest,
+        _ => text,
+    };
+    strip_code_fence(text).trim().to_owned()
+}
+
+fn is_meta_preamble(line: &str) -> bool {
+    let line = line.trim();
+    let lower = line.to_lowercase();
+    line.ends_with(':')
+        && line.chars().count() <= MAX_PREAMBLE_CHARS
+        && PREAMBLE_OPENERS
+            .iter()
+            .any(|opener| lower.starts_with(opener))
+}
+
+/// Why a generation left no text to save.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EmptyOutput {
+    /// The stream ended without a single token.
+    NoTokens,
+    /// Tokens arrived, but only whitespace.
+    Whitespace,
+    /// Only a code fence, perhaps after a lead-in, with nothing inside it.
+    EmptyFence,
+    /// A continuation that only repeated the existing text.
+    RepeatedExisting,
+}
+
+impl EmptyOutput {
+    /// Classify raw output that holds no text once cleaned by
+    /// [`clean_prose_output`]; `None` when there is text to save.
+    pub(crate) fn detect(raw: &str) -> Option<Self> {
+        if raw.is_empty() {
+            Some(Self::NoTokens)
+        } else if raw.trim().is_empty() {
+            Some(Self::Whitespace)
+        } else if clean_prose_output(raw).is_empty() {
+            Some(Self::EmptyFence)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn reason(self) -> &'static str {
+        match self {
+            Self::NoTokens => "AI produced no output",
+            Self::Whitespace => "AI produced only whitespace",
+            Self::EmptyFence => "AI produced only an empty code fence",
+            Self::RepeatedExisting => "AI only repeated the existing content",
+        }
+    }
+}
+
+/// Build a chat prompt that drafts planning notes for a node that has none,
+/// deciding what should happen there rather than writing prose.
+pub(crate) fn build_brainstorm_prompt(request: &GenerateRequest) -> ChatPrompt {
+    let level = request.target_node.level;
+    let level_name = level.label().to_lowercase();
+    let system = format!(
+        "You are a story editor planning a TV episode top-down. Given the \
+         surrounding structure, decide what should happen in one {level_name} \
+         and write brief planning notes for it.\n\n\
+         RULES:\n\
+         - Describe events, character goals, and turns — not dialogue or prose.\n\
+         - Serve the parent's intent and the tagged story arcs.\n\
+         - Follow from what comes before and set up what comes after.\n\
+         - Keep it to 2-5 sentences or short bullet points.\n\
+         - Return ONLY the notes, no headings or commentary."
+    );
+
+    let mut user = format!("Plan what should happen in this {level_name}:\n\n");
+    push_premise(&mut user, &request.premise);
+    push_arc_context(&mut user, request);
+    user.push_str(&format!(
+        "{}: {} ({} long)\n",
+        level.label().to_uppercase(),
+        request.target_node.name,
+        format_time(request.time_budget_ms),
+    ));
+    if let Some(ref bt) = request.target_node.beat_type {
+        user.push_str(&format!("BEAT TYPE: {:?}\n", bt));
+    }
+    user.push('\n');
+    push_ancestor_context(&mut user, request);
+
+    let planned: Vec<_> = request
+        .siblings
+        .iter()
+        .filter(|sibling| sibling.id != request.target_node.id)
+        .filter(|sibling| !sibling.content.notes.trim().is_empty())
+        .collect();
+    if !planned.is_empty() {
+        user.push_str(&format!(
+            "OTHER {}S ALREADY PLANNED:\n",
+            level.label().to_uppercase()
+        ));
+        for sibling in planned {
+            user.push_str(&format!("- {}: {}\n", sibling.name, sibling.content.notes));
+        }
+        user.push('\n');
+    }
+
+    for (heading, entries) in [
+        (
+            "WHAT HAS HAPPENED SO FAR:",
+            &request.surrounding_context.preceding_recaps,
+        ),
+        (
+            "WHAT COMES NEXT:",
+            &request.surrounding_context.following_recaps,
+        ),
+    ] {
+        if entries.is_empty() {
+            continue;
+        }
+        user.push_str(heading);
+        user.push('\n');
+        for entry in entries {
+            user.push_str(&format!(
+                "- {} / {}: {}\n",
+                entry.arc_name, entry.node_name, entry.recap
+            ));
+        }
+        user.push('\n');
+    }
+
+    if let Some(bible_context) = &request.bible_context {
+        ai_bible_context_prompt::append_bible_context(&mut user, bible_context);
+    }
+
+    user.push_str(&format!(
+        "Write the planning notes for \"{}\" now.",
+        request.target_node.name
+    ));
+
+    ChatPrompt {
+        system,
+        user,
+        assistant: None,
+    }
+}
+
+/// Build a chat prompt that recovers planning notes from a node's existing
+/// content: the intent behind it, as if the notes had been written first.
+pub(crate) fn build_notes_from_content_prompt(
+    request: &GenerateRequest,
+    content: &str,
+) -> ChatPrompt {
+    let level = request.target_node.level;
+    let level_name = level.label().to_lowercase();
+    let system = format!(
+        "You are a story editor reverse-outlining a TV episode. Given the \
+         finished text of one {level_name}, write the brief planning notes a \
+         writer would have started from.\n\n\
+         RULES:\n\
+         - Capture intent: what happens, what each character wants, and how \
+           the {level_name} turns.\n\
+         - Do not quote dialogue or retell the text line by line.\n\
+         - Note how it serves the parent's intent and the tagged story arcs.\n\
+         - Keep it to 2-5 sentences or short bullet points.\n\
+         - Return ONLY the notes, no headings or commentary."
+    );
+
+    let mut user = format!("Write planning notes for this {level_name}:\n\n");
+    push_premise(&mut user, &request.premise);
+    push_arc_context(&mut user, request);
+    user.push_str(&format!(
+        "{}: {} ({} long)\n\n",
+        level.label().to_uppercase(),
+        request.target_node.name,
+        format_time(request.time_budget_ms),
+    ));
+    push_ancestor_context(&mut user, request);
+    user.push_str("CONTENT:\n");
+    user.push_str(content.trim());
+    user.push_str(&format!(
+        "\n\nWrite the planning notes for \"{}\" now.",
+        request.target_node.name
+    ));
+
+    ChatPrompt {
+        system,
+        user,
+        assistant: None,
+    }
+}
+
+/// Build a JSON-mode chat prompt that expands a project premise into a
+/// logline, A/B/C arcs, and principal characters.
+pub(crate) fn build_premise_expansion_prompt(premise: &str) -> ChatPrompt {
+    let system = String::from(
+        "You are a TV showrunner developing a 30-minute comedy/drama episode from a premise. \
+         Propose the episode's spine.\n\n\
+         Respond with a JSON object of this shape:\n\
+         {\"logline\": \"...\", \
+         \"arcs\": [{\"name\": \"...\", \"description\": \"...\"}], \
+         \"characters\": [{\"name\": \"...\", \"description\": \"...\"}]}\n\n\
+         RULES:\n\
+         - logline: one sentence naming the protagonist, their goal, and the obstacle.\n\
+         - arcs: exactly three, in order — the A-plot (main story), the B-plot \
+         (secondary story), and the C-runner (light recurring thread). Give each a short \
+         name and a 1-2 sentence description.\n\
+         - characters: the 3-6 principal characters, each with a 1-2 sentence description \
+         of who they are and what they want.\n\
+         - Return ONLY the JSON object.",
+    );
+    let user = format!("PREMISE:\n{}\n\nExpand this premise now.", premise.trim());
+    ChatPrompt {
+        system,
+        user,
+        assistant: None,
+    }
+}
+
+/// Longest excerpt of a node's drafted content quoted in an arc treatment
+/// prompt; notes carry the plan, the excerpt only grounds the tone.
+const ARC_TREATMENT_CONTENT_CHARS: usize = 600;
+
+/// Build a chat prompt that writes a prose treatment of one storyline from the
+/// nodes tagged with its arc, given in time order.
+pub(crate) fn build_arc_treatment_prompt(
+    premise: &str,
+    arc: &StoryArc,
+    nodes: &[&StoryNode],
+) -> ChatPrompt {
+    let system = String::from(
+        "You are a TV writer preparing a treatment for a pitch or writers' room. \
+         Given every story node that carries one storyline, write a cohesive prose \
+         treatment of that storyline across the whole episode.\n\n\
+         RULES:\n\
+         - Write several paragraphs of present-tense prose, in story order.\n\
+         - Follow the storyline's turns, character goals, and escalation; skip other plots.\n\
+         - Stay faithful to the notes and drafted content; fill gaps only to connect them.\n\
+         - No dialogue, scene headings, or bullet points.\n\
+         - Return ONLY the treatment, no title or commentary.",
+    );
+
+    let mut user = String::from("Write a treatment for this storyline:\n\n");
+    push_premise(&mut user, premise);
+    user.push_str(&format!("ARC: {} ({:?})\n", arc.name, arc.arc_type));
+    if !arc.description.trim().is_empty() {
+        user.push_str(&format!("DESCRIPTION: {}\n", arc.description.trim()));
+    }
+    user.push_str("\nSTORY NODES IN ORDER:\n");
+    for node in nodes {
+        user.push_str(&format!(
+            "- [{} {}-{}] {}\n",
+            node.level.label(),
+            format_time(node.time_range.start_ms),
+            format_time(node.time_range.end_ms),
+            node.name,
+        ));
+        let notes = node.content.notes.trim();
+        if !notes.is_empty() {
+            user.push_str(&format!("  Notes: {notes}\n"));
+        }
+        let content = node.content.content.trim();
+        if !content.is_empty() {
+            let excerpt = truncate_chars(content, ARC_TREATMENT_CONTENT_CHARS);
+            let ellipsis = if excerpt.len() < content.len() {
+                "..."
+            } else {
+                ""
+            };
+            user.push_str(&format!("  Draft: {excerpt}{ellipsis}\n"));
+        }
+    }
+
+    user.push_str(&format!("\nWrite the treatment for \"{}\" now.", arc.name));
+
+    ChatPrompt {
+        system,
+        user,
+        assistant: None,
+    }
+}
+
+/// Build a JSON-mode chat prompt asking whether a node's content contradicts
+/// an entity's state as established by its snapshots up to that point.
+pub(crate) fn build_continuity_audit_prompt(
+    entity_name: &str,
+    expected_state: &[BibleGraphSnapshotField],
+    node: &StoryNode,
+) -> ChatPrompt {
+    let system = String::from(
+        "You are a script continuity supervisor. Given the established state of one \
+         story entity and a passage of content, decide whether the passage contradicts \
+         that state — for example where the entity is, what it knows, or how it feels.\n\n\
+         Respond with a JSON object of this shape:\n\
+         {\"contradiction\": true, \"explanation\": \"...\"}\n\n\
+         RULES:\n\
+         - Only flag clear contradictions, not details the state leaves open.\n\
+         - A change the passage itself shows happening is not a contradiction.\n\
+         - explanation: one or two sentences naming the conflicting detail; empty when \
+         there is no contradiction.\n\
+         - Return ONLY the JSON object.",
+    );
+
+    let mut user = format!("ENTITY: {entity_name}\n\nESTABLISHED STATE:\n");
+    for field in expected_state {
+        if let Some(value) = &field.value {
+            user.push_str(&format!(
+                "- {} / {}: {}\n",
+                field.part_name,
+                field.field_key.as_str(),
+                ai_bible_context_prompt::field_value_label(value),
+            ));
+        }
+    }
+    user.push_str(&format!(
+        "\n{}: {} (at {})\n{}\n\nDoes this passage contradict the established state of {entity_name}?",
+        node.level.label().to_uppercase(),
+        node.name,
+        format_time(node.time_range.start_ms),
+        node.content.content.trim(),
+    ));
+
+    ChatPrompt {
+        system,
+        user,
+        assistant: None,
+    }
+}
+
+/// Build a chat prompt asking whether a node's drafted content still carries
+/// out its planning notes.
+pub(crate) fn build_notes_drift_prompt(node: &StoryNode) -> ChatPrompt {
+    let system = String::from(
+        "You are a script editor. Given the planning notes for one part of a story and \
+         the content drafted for it, decide whether the content still carries out what \
+         the notes ask for.\n\n\
+         Respond with a JSON object of this shape:\n\
+         {\"fulfilled\": false, \"specifics\": [\"...\"]}\n\n\
+         RULES:\n\
+         - fulfilled is false when the content drops, reverses, or contradicts something \
+         the notes require.\n\
+         - Differences in wording, order, or added detail are not drift.\n\
+         - specifics: one short sentence per note the content fails; empty when fulfilled.\n\
+         - Return ONLY the JSON object.",
+    );
+    let user = format!(
+        "{}: {} (at {})\n\nNOTES:\n{}\n\nCONTENT:\n{}\n\nDoes this content still fulfill the notes?",
+        node.level.label().to_uppercase(),
+        node.name,
+        format_time(node.time_range.start_ms),
+        node.content.notes.trim(),
+        node.content.content.trim(),
+    );
+
+    ChatPrompt {
+        system,
+        user,
+        assistant: None,
+    }
+}
+
+/// Build a chat prompt asking which of the project's arcs a node advances.
+pub(crate) fn build_arc_tag_prompt(node: &StoryNode, arcs: &[StoryArc]) -> ChatPrompt {
+    let system = String::from(
+        "You are a story editor tagging parts of a screenplay with the story arcs they \
+         advance. Given the project's arcs and one part of the story, name each arc that \
+         this part moves forward.\n\n\
+         Respond with a JSON object of this shape:\n\
+         {\"arcs\": [{\"name\": \"...\", \"confidence\": 0.8}]}\n\n\
+         RULES:\n\
+         - Use arc names exactly as listed.\n\
+         - Only include arcs this part advances, not arcs it merely mentions.\n\
+         - confidence is between 0.0 and 1.0.\n\
+         - An empty list is fine when no arc is advanced.\n\
+         - Return ONLY the JSON object.",
+    );
+    let mut user = String::from("ARCS:\n");
+    for arc in arcs {
+        if arc.description.trim().is_empty() {
+            user.push_str(&format!("- {}\n", arc.name));
+        } else {
+            user.push_str(&format!("- {}: {}\n", arc.name, arc.description.trim()));
+        }
+    }
+    user.push_str(&format!(
+        "\n{}: {} (at {})\n",
+        node.level.label().to_uppercase(),
+        node.name,
+        format_time(node.time_range.start_ms),
+    ));
+    if !node.content.notes.trim().is_empty() {
+        user.push_str(&format!("\nNOTES:\n{}\n", node.content.notes.trim()));
+    }
+    if !node.content.content.trim().is_empty() {
+        user.push_str(&format!("\nCONTENT:\n{}\n", node.content.content.trim()));
+    }
+    user.push_str("\nWhich arcs does this part advance?");
+
+    ChatPrompt {
+        system,
+        user,
+        assistant: None,
+    }
+}
+
 /// Build a chat prompt to generate a compact scene recap from a script.
 pub(crate) fn build_recap_prompt(script: &str, preceding_recap: Option<&str>) -> ChatPrompt {
     let system = String::from(
@@ -284,7 +884,11 @@ pub(crate) fn build_recap_prompt(script: &str, preceding_recap: Option<&str>) ->
          Be concise — aim for 100-150 tokens.",
     );
 
-    ChatPrompt { system, user }
+    ChatPrompt {
+        system,
+        user,
+        assistant: None,
+    }
 }
 
 /// Build a chat prompt for decomposing a parent node into children.
@@ -313,6 +917,17 @@ pub(crate) fn build_decompose_prompt(request: &GenerateChildrenRequest) -> ChatP
              - Payoff: Delivers on earlier setup\n\
              - Callback: References earlier material\n\n",
         );
+        if !request.suggested_beat_types.is_empty() {
+            let suggested: Vec<String> = request
+                .suggested_beat_types
+                .iter()
+                .map(|beat_type| format!("{beat_type:?}"))
+                .collect();
+            system.push_str(&format!(
+                "At this point in the episode, favor these beat types: {}.\n\n",
+                suggested.join(", ")
+            ));
+        }
     }
 
     // Premise → Acts: provide the episode's act structure.
@@ -322,7 +937,7 @@ pub(crate) fn build_decompose_prompt(request: &GenerateChildrenRequest) -> ChatP
             system.push_str(
                 "This is a TV episode. You MUST generate one act for each structural \
                              segment below. Each act's name should match the segment label. \
-                             Use the durations as weight guidance.\n\n",
+                             Each act is placed on its segment's time range.\n\n",
             );
             for seg in &structure.segments {
                 let kind = match seg.segment_type {
@@ -356,15 +971,20 @@ pub(crate) fn build_decompose_prompt(request: &GenerateChildrenRequest) -> ChatP
         }
     }
 
+    let count_rule = if request.fill_gaps.is_empty() {
+        format!("Propose 3-7 {child_label}s depending on complexity.")
+    } else {
+        format!("Propose only as many {child_label}s as the uncovered time needs.")
+    };
     system.push_str(&format!(
         "RULES:\n\
-         - Propose 3-7 {}s depending on complexity.\n\
+         - {}\n\
          - Each {} should be a coherent unit of story.\n\
          - Outlines should be 1-2 sentences describing what happens.\n\
          - Weights represent relative duration (1.0 = normal, 0.5 = brief, 2.0 = extended).\n\
          - {}s should flow naturally from one to the next.\n\
          - Return ONLY valid JSON, no commentary.\n",
-        child_label, child_label, child_label,
+        count_rule, child_label, child_label,
     ));
 
     if child_level == StoryLevel::Beat || child_level == StoryLevel::Scene {
@@ -379,6 +999,8 @@ pub(crate) fn build_decompose_prompt(request: &GenerateChildrenRequest) -> ChatP
         child_label,
     );
 
+    push_premise(&mut user, &request.premise);
+
     // Arc context.
     if !request.tagged_arcs.is_empty() {
         user.push_str("STORY ARCS: ");
@@ -419,6 +1041,8 @@ pub(crate) fn build_decompose_prompt(request: &GenerateChildrenRequest) -> ChatP
         duration_sec % 60,
     ));
 
+    push_child_gaps(&mut user, request, &child_label);
+
     // Continuity.
     if !request.surrounding_context.preceding_recaps.is_empty() {
         user.push_str("CONTINUITY CONTEXT:\n");
@@ -466,7 +1090,52 @@ pub(crate) fn build_decompose_prompt(request: &GenerateChildrenRequest) -> ChatP
         child_label, beat_type_field, entity_fields,
     ));
 
-    ChatPrompt { system, user }
+    ChatPrompt {
+        system,
+        user,
+        assistant: None,
+    }
+}
+
+/// List the children being kept and the uncovered time the plan must fill,
+/// when decomposing only into gaps.
+fn push_child_gaps(user: &mut String, request: &GenerateChildrenRequest, child_label: &str) {
+    if request.fill_gaps.is_empty() {
+        return;
+    }
+    let label = child_label.to_uppercase();
+    if !request.existing_children.is_empty() {
+        user.push_str(&format!(
+            "EXISTING {label}S (keep these; do not propose them again):\n"
+        ));
+        for child in &request.existing_children {
+            user.push_str(&format!(
+                "- [{}–{}] {}",
+                format_time(child.time_range.start_ms),
+                format_time(child.time_range.end_ms),
+                child.name,
+            ));
+            let notes = child.content.notes.trim();
+            if !notes.is_empty() {
+                user.push_str(&format!(": {notes}"));
+            }
+            user.push('\n');
+        }
+        user.push('\n');
+    }
+    user.push_str("UNCOVERED TIME TO FILL:\n");
+    for gap in &request.fill_gaps {
+        user.push_str(&format!(
+            "- {}–{} ({} seconds)\n",
+            format_time(gap.start_ms),
+            format_time(gap.end_ms),
+            gap.duration_ms() / 1000,
+        ));
+    }
+    user.push_str(&format!(
+        "\nPropose {child_label}s only for the uncovered time, in order, so they \
+         fill it and fit between the existing {child_label}s.\n\n"
+    ));
 }
 
 #[cfg(test)]
@@ -506,4 +1175,445 @@ mod tests {
         assert!(prompt.user.contains("mood: uneasy"));
         assert!(prompt.user.contains("valence: -250"));
     }
+
+    #[test]
+    fn sibling_previews_respect_configured_length() {
+        let project = Template::MultiCam.build_project("Sibling Test");
+        let target_id = project
+            .timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .into_iter()
+            .find(|n| n.name == "B: Setup")
+            .expect("scene")
+            .id;
+        let mut request = eidetic_core::ai::prompt::build_generate_request(&project, target_id)
+            .expect("generate request");
+        for sibling in &mut request.siblings {
+            sibling.content.content = "é".repeat(50);
+        }
+        let config = AiConfig {
+            max_siblings: 1,
+            sibling_preview_chars: 10,
+            ..AiConfig::default()
+        };
+
+        apply_sibling_limits(&mut request, &config);
+        let prompt = build_chat_prompt(&request);
+
+        assert_eq!(request.siblings.len(), 2);
+        assert!(prompt.user.contains("← YOU ARE HERE"));
+        assert!(
+            prompt
+                .user
+                .contains(&format!("A: Complication: {}...", "é".repeat(10)))
+        );
+        assert!(!prompt.user.contains("C: Beat"));
+    }
+
+    #[test]
+    fn project_ai_settings_replace_system_prompt_and_add_style_notes() {
+        let mut project = Template::MultiCam.build_project("Voice Test");
+        project.ai_settings.system_prompt = Some("You write a deadpan mockumentary.".into());
+        project.ai_settings.style_notes = Some("Talking heads between scenes.".into());
+        let scene = project.timeline.nodes_at_level(StoryLevel::Scene)[0].id;
+        let request =
+            eidetic_core::ai::prompt::build_generate_request(&project, scene).expect("request");
+
+        let prompt = build_chat_prompt(&request);
+
+        assert!(
+            prompt
+                .system
+                .starts_with("You write a deadpan mockumentary.")
+        );
+        assert!(!prompt.system.contains("TV story editor"));
+        assert!(
+            prompt
+                .user
+                .contains("STYLE NOTES: Talking heads between scenes.")
+        );
+    }
+
+    #[test]
+    fn chat_prompt_renders_following_recaps_as_leads_into() {
+        let mut project = Template::MultiCam.build_project("Recap Test");
+        let scenes: Vec<_> = project
+            .timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .iter()
+            .map(|n| (n.name.clone(), n.id))
+            .collect();
+        let target_id = scenes
+            .iter()
+            .find(|(name, _)| name == "B: Setup")
+            .unwrap()
+            .1;
+        let next_id = scenes
+            .iter()
+            .find(|(name, _)| name == "A: Complication")
+            .unwrap()
+            .1;
+        project
+            .timeline
+            .node_mut(next_id)
+            .unwrap()
+            .content
+            .scene_recap = Some("Marcus storms out of the diner.".into());
+
+        let request = eidetic_core::ai::prompt::build_generate_request(&project, target_id)
+            .expect("generate request");
+        let prompt = build_chat_prompt(&request);
+
+        assert!(
+            prompt
+                .user
+                .contains("LEADS INTO (upcoming, keep consistent):")
+        );
+        assert!(prompt.user.contains("Marcus storms out of the diner."));
+    }
+
+    #[test]
+    fn brainstorm_prompt_asks_for_notes_with_ancestor_context() {
+        let mut project = Template::MultiCam.build_project("Brainstorm Prompt Test");
+        let act_id = project.timeline.nodes[0].id;
+        project.timeline.node_mut(act_id).unwrap().content.notes =
+            "Mara learns the lighthouse is being sold.".into();
+        let scene_id = project
+            .timeline
+            .nodes
+            .iter()
+            .find(|node| node.name == "A: Setup")
+            .unwrap()
+            .id;
+
+        let request = eidetic_core::ai::prompt::build_generate_request(&project, scene_id)
+            .expect("generate request");
+        let prompt = build_brainstorm_prompt(&request);
+
+        assert!(prompt.system.contains("planning notes"));
+        assert!(prompt.user.contains("SCENE: A: Setup (1:00 long)"));
+        assert!(prompt.user.contains("CONTEXT HIERARCHY:"));
+        assert!(
+            prompt
+                .user
+                .contains("Mara learns the lighthouse is being sold.")
+        );
+        assert!(!prompt.user.contains("SCENE NOTES:"));
+    }
+
+    #[test]
+    fn notes_from_content_prompt_includes_the_content_and_ancestors() {
+        let mut project = Template::MultiCam.build_project("Reverse Notes Prompt Test");
+        let act_id = project.timeline.nodes[0].id;
+        project.timeline.node_mut(act_id).unwrap().content.notes =
+            "Mara learns the lighthouse is being sold.".into();
+        let scene_id = project
+            .timeline
+            .nodes
+            .iter()
+            .find(|node| node.name == "A: Setup")
+            .unwrap()
+            .id;
+
+        let request = eidetic_core::ai::prompt::build_generate_request(&project, scene_id)
+            .expect("generate request");
+        let prompt =
+            build_notes_from_content_prompt(&request, "  INT. LIGHTHOUSE - NIGHT\nMara reads.  ");
+
+        assert!(prompt.system.contains("planning notes"));
+        assert!(prompt.user.contains("SCENE: A: Setup (1:00 long)"));
+        assert!(
+            prompt
+                .user
+                .contains("Mara learns the lighthouse is being sold.")
+        );
+        assert!(
+            prompt
+                .user
+                .contains("CONTENT:\nINT. LIGHTHOUSE - NIGHT\nMara reads.\n\n")
+        );
+    }
+
+    #[test]
+    fn prompts_include_project_premise_only_when_set() {
+        let mut project = Template::MultiCam.build_project("Premise Prompt Test");
+        let node_id = project.timeline.nodes[0].id;
+        let request = eidetic_core::ai::prompt::build_generate_request(&project, node_id)
+            .expect("generate request");
+        assert!(
+            !build_chat_prompt(&request)
+                .user
+                .contains("SERIES/EPISODE PREMISE")
+        );
+
+        project.premise = "  A keeper fights to save her lighthouse.  ".into();
+        let request = eidetic_core::ai::prompt::build_generate_request(&project, node_id)
+            .expect("generate request");
+        let children = eidetic_core::ai::prompt::build_generate_children_request(&project, node_id)
+            .expect("generate children request");
+
+        let expected = "SERIES/EPISODE PREMISE: A keeper fights to save her lighthouse.\n";
+        assert!(build_chat_prompt(&request).user.contains(expected));
+        assert!(build_brainstorm_prompt(&request).user.contains(expected));
+        assert!(build_decompose_prompt(&children).user.contains(expected));
+    }
+
+    #[test]
+    fn beat_decompose_prompt_suggests_beat_types_for_the_final_act() {
+        let project = Template::MultiCam.build_project("Beat Palette Prompt Test");
+        let last_scene = project
+            .timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .into_iter()
+            .max_by_key(|scene| scene.time_range.start_ms)
+            .unwrap()
+            .id;
+        let request =
+            eidetic_core::ai::prompt::build_generate_children_request(&project, last_scene)
+                .expect("generate children request");
+
+        assert_eq!(request.target_child_level, StoryLevel::Beat);
+        assert!(build_decompose_prompt(&request).system.contains(
+            "At this point in the episode, favor these beat types: Escalation, Climax, \
+             Resolution, Payoff, Callback."
+        ));
+    }
+
+    #[test]
+    fn decompose_prompt_lists_kept_children_and_gaps_when_filling_gaps() {
+        let project = Template::MultiCam.build_project("Gap Prompt Test");
+        let act = project.timeline.nodes_at_level(StoryLevel::Act)[0].clone();
+        let mut request =
+            eidetic_core::ai::prompt::build_generate_children_request(&project, act.id)
+                .expect("generate children request");
+        assert!(
+            !build_decompose_prompt(&request)
+                .user
+                .contains("UNCOVERED TIME TO FILL")
+        );
+
+        let mut kept = StoryNode::new_child(
+            "Harbor Standoff",
+            request.target_child_level,
+            TimeRange::new(0, 60_000).unwrap(),
+            act.id,
+        );
+        kept.content.notes = "Mara refuses to leave.".into();
+        request.existing_children = vec![kept];
+        request.fill_gaps = vec![TimeRange::new(60_000, 150_000).unwrap()];
+        let prompt = build_decompose_prompt(&request);
+
+        assert!(prompt.system.contains("only as many"));
+        assert!(
+            prompt
+                .user
+                .contains("- [0:00–1:00] Harbor Standoff: Mara refuses to leave.\n")
+        );
+        assert!(prompt.user.contains("- 1:00–2:30 (90 seconds)\n"));
+    }
+
+    #[test]
+    fn arc_treatment_prompt_lists_arc_nodes_with_notes_in_order() {
+        let mut project = Template::MultiCam.build_project("Treatment Prompt Test");
+        project.premise = "A keeper fights to save her lighthouse.".into();
+        let arc = project.arcs[0].clone();
+        let scenes: Vec<_> = project
+            .timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .into_iter()
+            .take(2)
+            .map(|node| node.id)
+            .collect();
+        project.timeline.node_mut(scenes[0]).unwrap().content.notes =
+            "Mara learns the lighthouse is being sold.".into();
+        project
+            .timeline
+            .node_mut(scenes[1])
+            .unwrap()
+            .content
+            .content = "x".repeat(700);
+        let nodes: Vec<_> = scenes
+            .iter()
+            .map(|id| project.timeline.node(*id).unwrap())
+            .collect();
+
+        let prompt = build_arc_treatment_prompt(&project.premise, &arc, &nodes);
+
+        assert!(prompt.system.contains("treatment"));
+        assert!(prompt.user.contains(&format!("ARC: {}", arc.name)));
+        assert!(prompt.user.contains("SERIES/EPISODE PREMISE"));
+        let first = prompt.user.find(&nodes[0].name).unwrap();
+        let second = prompt.user.find(&nodes[1].name).unwrap();
+        assert!(first < second);
+        assert!(
+            prompt
+                .user
+                .contains("  Notes: Mara learns the lighthouse is being sold.")
+        );
+        assert!(
+            prompt
+                .user
+                .contains(&format!("  Draft: {}...", "x".repeat(600)))
+        );
+    }
+
+    #[test]
+    fn continuation_prompt_keeps_instructions_and_quotes_partial_output() {
+        let prompt = ChatPrompt {
+            system: "Write a beat.".into(),
+            user: "Beat: Mara climbs.".into(),
+            assistant: None,
+        };
+
+        let continuation = build_continuation_prompt(&prompt, "Mara grips the rail and");
+
+        assert_eq!(continuation.system, "Write a beat.");
+        assert!(continuation.user.starts_with("Beat: Mara climbs."));
+        assert!(
+            continuation
+                .user
+                .contains("Mara grips the rail and\n\nContinue exactly")
+        );
+    }
+
+    #[test]
+    fn continue_content_prompt_sends_existing_content_as_assistant_turn() {
+        let prompt = ChatPrompt {
+            system: "Write a beat.".into(),
+            user: "Beat: Mara climbs.".into(),
+            assistant: None,
+        };
+
+        let continuation = build_continue_content_prompt(&prompt, "Mara grips the rail.");
+
+        assert_eq!(continuation.system, "Write a beat.");
+        assert!(continuation.user.starts_with("Beat: Mara climbs."));
+        assert_eq!(
+            continuation.assistant.as_deref(),
+            Some("Mara grips the rail.")
+        );
+    }
+
+    #[test]
+    fn trim_continuation_overlap_drops_a_repeated_last_line() {
+        let existing = "INT. LIGHTHOUSE - NIGHT\n\nMara grips the rail.\n";
+
+        assert_eq!(
+            trim_continuation_overlap(existing, "Mara grips the rail.\nThe lamp dies."),
+            "\nThe lamp dies."
+        );
+        assert_eq!(
+            trim_continuation_overlap("MARA\n", "MARA\nHold on."),
+            "\nHold on."
+        );
+        assert_eq!(trim_continuation_overlap("She sees a", " cat."), " cat.");
+        assert_eq!(
+            trim_continuation_overlap("MARA\n", "MARAUDERS attack."),
+            "MARAUDERS attack."
+        );
+    }
+
+    #[test]
+    fn strip_code_fence_unwraps_fenced_prose_only() {
+        assert_eq!(
+            strip_code_fence("```fountain\nINT. LIGHTHOUSE - NIGHT\n\nMara grips the rail.\n```\n"),
+            "INT. LIGHTHOUSE - NIGHT\n\nMara grips the rail."
+        );
+        assert_eq!(
+            strip_code_fence("  ```\nMara grips the rail."),
+            "Mara grips the rail."
+        );
+        assert_eq!(
+            strip_code_fence("\nMara says ```hi```."),
+            "\nMara says ```hi```."
+        );
+    }
+
+    #[test]
+    fn clean_prose_output_drops_lead_in_and_fence() {
+        assert_eq!(
+            clean_prose_output(
+                "Here's the scene:\n```fountain\nINT. LIGHTHOUSE - NIGHT\n\nMara grips the rail.\n```"
+            ),
+            "INT. LIGHTHOUSE - NIGHT\n\nMara grips the rail."
+        );
+        assert_eq!(
+            clean_prose_output("Sure! Here is the beat:\n\nMara grips the rail.\n"),
+            "Mara grips the rail."
+        );
+        assert_eq!(
+            clean_prose_output("```\nMara grips the rail.\n```"),
+            "Mara grips the rail."
+        );
+    }
+
+    #[test]
+    fn clean_prose_output_keeps_script_text() {
+        for script in [
+            "INT. LIGHTHOUSE - NIGHT\n\nMara grips the rail.",
+            "MARA\nHere's the thing:\nI never touched it.",
+            "Here's the thing: Mara never touched it.\nShe runs.",
+            "Here is the plan:",
+        ] {
+            assert_eq!(clean_prose_output(script), script);
+        }
+    }
+
+    #[test]
+    fn empty_output_names_why_nothing_was_saved() {
+        assert_eq!(EmptyOutput::detect(""), Some(EmptyOutput::NoTokens));
+        assert_eq!(EmptyOutput::detect(" \n\t"), Some(EmptyOutput::Whitespace));
+        assert_eq!(
+            EmptyOutput::detect("```markdown\n\n```"),
+            Some(EmptyOutput::EmptyFence)
+        );
+        assert_eq!(EmptyOutput::detect("``````"), Some(EmptyOutput::EmptyFence));
+        assert_eq!(EmptyOutput::detect("```\nMara.\n```"), None);
+    }
+
+    #[test]
+    fn estimate_tokens_uses_four_chars_per_token() {
+        let prompt = ChatPrompt {
+            system: "abcd".repeat(10),
+            user: "xy".into(),
+            assistant: Some("zz".into()),
+        };
+
+        assert_eq!(estimate_tokens(&prompt), 11);
+    }
+
+    #[test]
+    fn trim_request_drops_lowest_priority_sections_first() {
+        let project = Template::MultiCam.build_project("Budget Test");
+        let node_id = project.timeline.nodes[0].id;
+        let mut request = eidetic_core::ai::prompt::build_generate_request(&project, node_id)
+            .expect("generate request");
+        request.surrounding_context.following_scripts = vec!["LATER SCENE ".repeat(200)];
+        request.surrounding_context.preceding_scripts = vec!["EARLIER SCENE".into()];
+        let without_following = {
+            let mut trimmed = request.clone();
+            trimmed.surrounding_context.following_scripts.clear();
+            estimate_tokens(&build_chat_prompt(&trimmed))
+        };
+
+        let trimmed = trim_request_to_budget(&mut request, without_following);
+
+        assert_eq!(trimmed, vec!["following_scripts"]);
+        assert!(request.surrounding_context.following_scripts.is_empty());
+        assert_eq!(request.surrounding_context.preceding_scripts.len(), 1);
+    }
+
+    #[test]
+    fn trim_request_leaves_fitting_prompts_alone() {
+        let project = Template::MultiCam.build_project("Budget Test");
+        let node_id = project.timeline.nodes[0].id;
+        let mut request = eidetic_core::ai::prompt::build_generate_request(&project, node_id)
+            .expect("generate request");
+        request.surrounding_context.following_scripts = vec!["LATER SCENE".into()];
+
+        let trimmed = trim_request_to_budget(&mut request, usize::MAX);
+
+        assert!(trimmed.is_empty());
+        assert_eq!(request.surrounding_context.following_scripts.len(), 1);
+    }
 }
@@ -426,6 +426,7 @@ fn set_snapshot_field_command(
         at_ms: snapshot.snapshot.at_ms,
         label: snapshot.snapshot.label.clone(),
         snapshot_sort_order: snapshot.snapshot.sort_order,
+        source_node_id: snapshot.snapshot.source_node_id,
         field_id: field.id.clone(),
         part_key: field.part_key.clone(),
         part_name: field.part_name.clone(),
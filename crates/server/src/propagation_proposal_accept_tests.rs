@@ -388,6 +388,7 @@ fn seed_character_snapshot_field(conn: &mut rusqlite::Connection, value: &str) {
             at_ms: 12_000,
             label: "Sequence 1 state".to_string(),
             snapshot_sort_order: 1,
+            source_node_id: None,
             field_id: BibleGraphSnapshotFieldId::new("snapshot-field.character.status").unwrap(),
             part_key: BibleGraphPartKey::new("profile").unwrap(),
             part_name: "Profile".to_string(),
@@ -1,4 +1,6 @@
-use eidetic_core::reference::{ReferenceDocument, ReferenceId, ReferenceType, chunk_document};
+use eidetic_core::reference::{
+    ReferenceChunk, ReferenceDocument, ReferenceId, ReferenceScope, ReferenceType, chunk_document,
+};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -12,6 +14,16 @@ pub struct UploadReferenceRequest {
     pub name: String,
     pub content: String,
     pub doc_type: String,
+    /// Arcs and entities the document informs; omitted means global.
+    #[serde(default)]
+    pub scope: ReferenceScope,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SetReferenceScopeRequest {
+    pub id: Uuid,
+    #[serde(default)]
+    pub scope: ReferenceScope,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -19,6 +31,14 @@ pub struct DeleteReferenceResponse {
     pub deleted: bool,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReembedReferencesResponse {
+    pub status: String,
+    pub embedding_model: String,
+    pub document_count: usize,
+    pub chunk_count: usize,
+}
+
 pub fn list_references(state: &AppState) -> Result<Vec<ReferenceDocument>, BackendError> {
     let guard = state.project.lock();
     let Some(project) = guard.as_ref() else {
@@ -37,16 +57,13 @@ pub fn upload_reference(
         return Err(BackendError::bad_request("reference content is required"));
     }
 
-    let doc = ReferenceDocument::new(
+    let mut doc = ReferenceDocument::new(
         request.name,
         request.content,
         parse_reference_type(&request.doc_type),
     );
-    let chunks = chunk_document(
-        &doc,
-        crate::state::constants::REFERENCE_CHUNK_SIZE,
-        crate::state::constants::REFERENCE_CHUNK_OVERLAP,
-    );
+    doc.scope = request.scope;
+    let chunks = reference_chunks(&doc);
     let response = doc.clone();
 
     {
@@ -57,29 +74,99 @@ pub fn upload_reference(
         project.references.push(doc);
     }
     state.trigger_save();
+    spawn_reference_embedding(state, "reference-embedding", chunks);
 
+    Ok(response)
+}
+
+/// Limit which generations a reference informs. Chunks keep their document
+/// id, so no re-embedding is needed.
+pub fn set_reference_scope(
+    state: &AppState,
+    request: SetReferenceScopeRequest,
+) -> Result<ReferenceDocument, BackendError> {
+    let ref_id = ReferenceId(request.id);
+    let doc = {
+        let mut guard = state.project.lock();
+        let Some(project) = guard.as_mut() else {
+            return Err(BackendError::no_project());
+        };
+        let Some(doc) = project
+            .references
+            .iter_mut()
+            .find(|reference| reference.id == ref_id)
+        else {
+            return Err(BackendError::not_found(format!(
+                "reference not found: {}",
+                request.id
+            )));
+        };
+        doc.scope = request.scope;
+        doc.clone()
+    };
+    state.trigger_save();
+
+    Ok(doc)
+}
+
+/// Clear the vector store and rebuild it from every reference with the
+/// current embedding model, e.g. after switching models.
+pub fn reembed_all_references(state: &AppState) -> Result<ReembedReferencesResponse, BackendError> {
+    let (document_count, chunks) = {
+        let guard = state.project.lock();
+        let Some(project) = guard.as_ref() else {
+            return Err(BackendError::no_project());
+        };
+        let chunks: Vec<_> = project
+            .references
+            .iter()
+            .flat_map(reference_chunks)
+            .collect();
+        (project.references.len(), chunks)
+    };
+    let embedding_model = state.ai_config.lock().embedding_model.clone();
+    let chunk_count = chunks.len();
+
+    state.vector_store.lock().clear();
+    spawn_reference_embedding(state, "reference-reembedding", chunks);
+
+    Ok(ReembedReferencesResponse {
+        status: "started".to_string(),
+        embedding_model,
+        document_count,
+        chunk_count,
+    })
+}
+
+fn reference_chunks(doc: &ReferenceDocument) -> Vec<ReferenceChunk> {
+    chunk_document(
+        doc,
+        crate::state::constants::REFERENCE_CHUNK_SIZE,
+        crate::state::constants::REFERENCE_CHUNK_OVERLAP,
+    )
+}
+
+fn spawn_reference_embedding(state: &AppState, name: &'static str, chunks: Vec<ReferenceChunk>) {
     let state_clone = state.clone();
-    state
-        .task_supervisor
-        .spawn("reference-embedding", async move {
-            let config = state_clone.ai_config.lock().clone();
-            let client =
-                EmbeddingClient::new(&config.base_url, crate::state::constants::EMBEDDING_MODEL);
-
-            for chunk in chunks {
-                match client.embed(&chunk.content).await {
-                    Ok(embedding) => {
-                        state_clone.vector_store.lock().insert(chunk, embedding);
-                    }
-                    Err(error) => {
-                        tracing::warn!("Failed to embed chunk: {error}");
-                    }
+    state.task_supervisor.spawn(name, async move {
+        let config = state_clone.ai_config.lock().clone();
+        let client = EmbeddingClient::new(&config.base_url, &config.embedding_model);
+
+        for chunk in chunks {
+            let embedding = match client.embed(&chunk.content).await {
+                Ok(embedding) => embedding,
+                Err(error) => {
+                    tracing::warn!("Failed to embed chunk: {error}");
+                    continue;
                 }
+            };
+            if let Err(error) = state_clone.vector_store.lock().insert(chunk, embedding) {
+                tracing::warn!("Stopped embedding reference material: {error}");
+                return;
             }
-            tracing::info!("Reference material embedding complete");
-        });
-
-    Ok(response)
+        }
+        tracing::info!("Reference material embedding complete");
+    });
 }
 
 pub fn delete_reference(
@@ -118,10 +205,14 @@ fn parse_reference_type(value: &str) -> ReferenceType {
 
 #[cfg(test)]
 mod tests {
-    use super::{UploadReferenceRequest, list_references, upload_reference};
+    use super::{
+        SetReferenceScopeRequest, UploadReferenceRequest, list_references, reembed_all_references,
+        set_reference_scope, upload_reference,
+    };
     use crate::state::AppState;
     use eidetic_core::Template;
-    use eidetic_core::reference::ReferenceType;
+    use eidetic_core::reference::{ReferenceScope, ReferenceType};
+    use eidetic_core::story::arc::ArcId;
 
     #[tokio::test]
     async fn list_references_requires_loaded_project() {
@@ -143,6 +234,7 @@ mod tests {
                 name: "Tone Guide".into(),
                 content: "Keep scene turns precise.".into(),
                 doc_type: "StyleGuide".into(),
+                scope: ReferenceScope::default(),
             },
         )
         .expect("reference upload should succeed");
@@ -152,4 +244,82 @@ mod tests {
 
         state.shutdown_tasks();
     }
+
+    #[tokio::test]
+    async fn set_reference_scope_updates_the_document() {
+        let state = AppState::new().await;
+        *state.project.lock() = Some(Template::MultiCam.build_project("Scope Test"));
+        let reference = upload_reference(
+            &state,
+            UploadReferenceRequest {
+                name: "Villain Backstory".into(),
+                content: "Raised in the lighthouse.".into(),
+                doc_type: "CharacterBible".into(),
+                scope: ReferenceScope::default(),
+            },
+        )
+        .unwrap();
+        let scope = ReferenceScope {
+            arc_ids: vec![ArcId::new()],
+            entity_ids: Vec::new(),
+        };
+
+        let updated = set_reference_scope(
+            &state,
+            SetReferenceScopeRequest {
+                id: reference.id.0,
+                scope: scope.clone(),
+            },
+        )
+        .expect("scope update");
+
+        assert_eq!(updated.scope, scope);
+        assert_eq!(list_references(&state).unwrap()[0].scope, scope);
+        let missing = set_reference_scope(
+            &state,
+            SetReferenceScopeRequest {
+                id: uuid::Uuid::new_v4(),
+                scope,
+            },
+        )
+        .expect_err("unknown reference");
+        assert!(missing.message().starts_with("reference not found"));
+
+        state.shutdown_tasks();
+    }
+
+    #[tokio::test]
+    async fn reembed_all_references_rechunks_every_reference() {
+        let state = AppState::new().await;
+        assert_eq!(
+            reembed_all_references(&state)
+                .expect_err("missing project")
+                .message(),
+            "no project loaded"
+        );
+        *state.project.lock() = Some(Template::MultiCam.build_project("Reembed Test"));
+        for name in ["Tone Guide", "World Notes"] {
+            upload_reference(
+                &state,
+                UploadReferenceRequest {
+                    name: name.into(),
+                    content: "Keep scene turns precise.".into(),
+                    doc_type: "StyleGuide".into(),
+                    scope: ReferenceScope::default(),
+                },
+            )
+            .unwrap();
+        }
+
+        let response = reembed_all_references(&state).expect("re-embed should start");
+
+        assert_eq!(response.document_count, 2);
+        assert_eq!(response.chunk_count, 2);
+        assert_eq!(
+            response.embedding_model,
+            crate::state::constants::DEFAULT_EMBEDDING_MODEL
+        );
+
+        state.shutdown_tasks();
+    }
 }
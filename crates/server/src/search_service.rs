@@ -0,0 +1,200 @@
+//! Text search over node notes and content.
+//!
+//! `Saved` searches the last SQLite snapshot; `Live` searches the Y.Doc so
+//! results include collaborative edits not yet written by auto-save.
+
+use eidetic_core::timeline::node::{NodeId, StoryLevel, StoryNode};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::backend_error::BackendError;
+use crate::command_service_support::active_project_path;
+use crate::state::AppState;
+
+/// Characters of context kept on each side of a match in a snippet.
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+
+/// Which copy of the text to search.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSource {
+    /// Current Y.Doc text, including unsaved edits.
+    Live,
+    /// Text as of the last save.
+    #[default]
+    Saved,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContentSearchRequest {
+    pub query: String,
+    #[serde(default)]
+    pub source: SearchSource,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContentSearchHit {
+    pub node_id: Uuid,
+    pub name: String,
+    pub level: StoryLevel,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// `"notes"` or `"content"`.
+    pub field: &'static str,
+    pub snippet: String,
+}
+
+pub async fn search_content(
+    state: &AppState,
+    request: ContentSearchRequest,
+) -> Result<Vec<ContentSearchHit>, BackendError> {
+    let query = request.query.trim().to_lowercase();
+    if query.is_empty() {
+        return Err(BackendError::bad_request("search query must not be empty"));
+    }
+
+    let (nodes, texts) = match request.source {
+        SearchSource::Live => {
+            let nodes = state
+                .project
+                .lock()
+                .as_ref()
+                .map(|project| project.timeline.nodes.clone())
+                .ok_or_else(BackendError::no_project)?;
+            let texts = crate::ydoc::read_all_nodes(&state.doc_tx)
+                .await
+                .ok_or_else(|| BackendError::internal("Y.Doc manager unavailable"))?
+                .into_iter()
+                .map(|text| (text.node_id, text.notes, text.content))
+                .collect();
+            (nodes, texts)
+        }
+        SearchSource::Saved => {
+            let path = active_project_path(state)?;
+            let (project, _) = crate::persistence::load_project(&path)
+                .await
+                .map_err(BackendError::internal)?;
+            let texts = project
+                .timeline
+                .nodes
+                .iter()
+                .map(|node| {
+                    (
+                        node.id,
+                        node.content.notes.clone(),
+                        node.content.content.clone(),
+                    )
+                })
+                .collect();
+            (project.timeline.nodes, texts)
+        }
+    };
+
+    Ok(search_texts(&nodes, texts, &query))
+}
+
+fn search_texts(
+    nodes: &[StoryNode],
+    texts: Vec<(NodeId, String, String)>,
+    query: &str,
+) -> Vec<ContentSearchHit> {
+    let mut hits = Vec::new();
+    for (node_id, notes, content) in texts {
+        let Some(node) = nodes.iter().find(|node| node.id == node_id) else {
+            continue;
+        };
+        for (field, text) in [("notes", &notes), ("content", &content)] {
+            if let Some(snippet) = match_snippet(text, query) {
+                hits.push(ContentSearchHit {
+                    node_id: node.id.0,
+                    name: node.name.clone(),
+                    level: node.level,
+                    start_ms: node.time_range.start_ms,
+                    end_ms: node.time_range.end_ms,
+                    field,
+                    snippet,
+                });
+            }
+        }
+    }
+    hits.sort_by_key(|hit| (hit.start_ms, hit.level));
+    hits
+}
+
+/// Snippet around the first case-insensitive match of `query` (already
+/// lowercased), or `None` if `text` doesn't contain it.
+fn match_snippet(text: &str, query: &str) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let needle: Vec<char> = query.chars().collect();
+    let at = lower
+        .windows(needle.len())
+        .position(|window| window == needle.as_slice())?;
+
+    let start = at.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let end = (at + needle.len() + SNIPPET_CONTEXT_CHARS).min(chars.len());
+    let mut snippet: String = chars[start..end].iter().collect();
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    Some(snippet.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContentSearchRequest, SearchSource, search_content};
+    use crate::state::AppState;
+    use crate::ydoc::{ContentField, DocCommand};
+    use eidetic_core::Template;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn live_search_sees_unsaved_doc_edits() {
+        let path =
+            std::env::temp_dir().join(format!("eidetic-search-service-{}.db", Uuid::new_v4()));
+        let state = AppState::new().await;
+        let project = Template::MultiCam.build_project("Search Test");
+        let node_id = project.timeline.nodes[0].id;
+        crate::persistence::save_project(&project, &path, None)
+            .await
+            .expect("seed project database");
+        *state.project.lock() = Some(project);
+        *state.project_path.lock() = Some(path.clone());
+        state
+            .doc_tx
+            .send(DocCommand::WriteNodeContent {
+                node_id,
+                field: ContentField::Content,
+                text: "The lighthouse keeper finally speaks.".into(),
+                author: "human:test".into(),
+            })
+            .await
+            .unwrap();
+
+        let search = |source| {
+            search_content(
+                &state,
+                ContentSearchRequest {
+                    query: "LIGHTHOUSE".into(),
+                    source,
+                },
+            )
+        };
+        let live = search(SearchSource::Live).await.unwrap();
+        let saved = search(SearchSource::Saved).await.unwrap();
+
+        assert_eq!(live.len(), 1);
+        assert_eq!(live[0].node_id, node_id.0);
+        assert_eq!(live[0].field, "content");
+        assert!(live[0].snippet.contains("lighthouse keeper"));
+        assert!(saved.is_empty());
+
+        let _ = std::fs::remove_file(path);
+    }
+}
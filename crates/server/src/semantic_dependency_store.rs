@@ -1,4 +1,3 @@
-#[cfg(test)]
 use eidetic_core::contracts::{
     ChangeEvent, ChangeEventKind, CommandEnvelope, FieldDelta, FieldValue, ObjectRevision,
     RecordSemanticDependencyCommand, RevisionOperation,
@@ -8,10 +7,8 @@ use eidetic_core::contracts::{
     SemanticDependencyEndpoint, SemanticDependencyId, SemanticDependencyProjection,
 };
 use rusqlite::{Connection, Row, params};
-#[cfg(test)]
 use rusqlite::{OptionalExtension, Transaction};
 
-#[cfg(test)]
 use crate::history_store::RecordChangeOutcome;
 use crate::history_store::{self, HistoryStoreError};
 
@@ -69,7 +66,6 @@ pub(crate) fn create_schema(conn: &Connection) -> Result<(), SemanticDependencyS
     Ok(())
 }
 
-#[cfg(test)]
 pub(crate) fn record_semantic_dependency(
     conn: &mut Connection,
     command: &CommandEnvelope<RecordSemanticDependencyCommand>,
@@ -130,7 +126,6 @@ pub(crate) fn load_semantic_dependency_projection(
     }
 }
 
-#[cfg(test)]
 fn validate_dependency(
     dependency: &SemanticDependency,
 ) -> Result<(), SemanticDependencyStoreError> {
@@ -144,7 +139,6 @@ fn validate_dependency(
     Ok(())
 }
 
-#[cfg(test)]
 fn dependency_exists(
     conn: &Connection,
     dependency_id: &SemanticDependencyId,
@@ -159,7 +153,6 @@ fn dependency_exists(
     .map_err(SemanticDependencyStoreError::from)
 }
 
-#[cfg(test)]
 fn insert_dependency_in_transaction(
     tx: &Transaction<'_>,
     dependency: &SemanticDependency,
@@ -268,7 +261,6 @@ fn row_to_dependency(row: &Row<'_>) -> Result<SemanticDependency, SemanticDepend
     })
 }
 
-#[cfg(test)]
 fn dependency_revision(
     dependency: &SemanticDependency,
     event_id: eidetic_core::contracts::ChangeEventId,
@@ -312,7 +304,6 @@ fn dependency_revision(
     })
 }
 
-#[cfg(test)]
 fn endpoint_label(endpoint: &SemanticDependencyEndpoint) -> String {
     let sql = SqlEndpoint::from_endpoint(endpoint);
     match (sql.part_key, sql.field_key) {
@@ -333,7 +324,6 @@ struct SqlEndpoint {
 }
 
 impl SqlEndpoint {
-    #[cfg(test)]
     fn from_endpoint(endpoint: &SemanticDependencyEndpoint) -> Self {
         match endpoint {
             SemanticDependencyEndpoint::TimelineNode { node_id } => Self {
@@ -415,7 +405,6 @@ impl SqlEndpoint {
     }
 }
 
-#[cfg(test)]
 fn encode_string_enum<T>(value: &T) -> Result<String, HistoryStoreError>
 where
     T: serde::Serialize,
@@ -1,18 +1,26 @@
 use eidetic_core::contracts::{
-    AcceptBibleReferenceProposalCommand, BibleGraphNode, BibleGraphNodeId, BibleReferenceKind,
+    AcceptBibleReferenceProposalCommand, BibleGraphFieldKey, BibleGraphNode, BibleGraphNodeId,
+    BibleGraphPartKey, BibleGraphSnapshotFieldId, BibleGraphSnapshotId, BibleReferenceKind,
     BibleReferenceProposal, CanonicalBibleRoot, ChangeEvent, ChangeEventKind, CommandEnvelope,
     FieldDelta, FieldValue, ObjectKind, ObjectRevision, RevisionOperation, SemanticProposalStatus,
+    SetBibleGraphSnapshotFieldCommand, builtin_bible_graph_schema,
 };
+use eidetic_core::timeline::Timeline;
 use rusqlite::Connection;
 use serde::Serialize;
 
+use crate::bible_graph_command::snapshot_revision;
 use crate::bible_graph_store;
 use crate::history_store::{self, HistoryStoreError, RecordChangeOutcome};
 use crate::semantic_proposal_store::{self, SemanticProposalStoreError};
 
+/// Accept a pending bible reference, creating or linking its entity. With a
+/// `timeline`, an entity that has no snapshots yet also gets an introduction
+/// snapshot at the midpoint of the proposal's source node.
 pub(crate) fn record_accept_bible_reference_proposal(
     conn: &mut Connection,
     command: &CommandEnvelope<AcceptBibleReferenceProposalCommand>,
+    timeline: Option<&Timeline>,
     created_at_ms: u64,
 ) -> Result<RecordChangeOutcome, SemanticProposalStoreError> {
     semantic_proposal_store::create_schema(conn)?;
@@ -38,6 +46,10 @@ pub(crate) fn record_accept_bible_reference_proposal(
     }
 
     let target = accept_target(conn, &proposal, &command.payload)?;
+    let introduction = match timeline {
+        Some(timeline) => introduction_snapshot(conn, &proposal, &target, timeline)?,
+        None => None,
+    };
 
     let event = ChangeEvent::new(
         command.id,
@@ -46,13 +58,16 @@ pub(crate) fn record_accept_bible_reference_proposal(
     )
     .with_created_at_ms(created_at_ms);
     let proposal_revision = proposal_accept_revision(&proposal, target.node_id(), event.id)?;
-    let revisions = match &target {
+    let mut revisions = match &target {
         AcceptTarget::Create(node) => vec![
             proposal_revision,
             accepted_bible_node_revision(node, event.id),
         ],
         AcceptTarget::LinkExisting(_) => vec![proposal_revision],
     };
+    if let Some(introduction) = &introduction {
+        revisions.push(snapshot_revision(introduction, None, false, event.id));
+    }
 
     Ok(history_store::record_change_with(
         conn,
@@ -67,16 +82,94 @@ pub(crate) fn record_accept_bible_reference_proposal(
                 SemanticProposalStatus::Pending,
                 SemanticProposalStatus::Accepted,
             )?;
-            match &target {
-                AcceptTarget::Create(node) => {
-                    bible_graph_store::insert_node_in_transaction(tx, node, event.id)
+            if let AcceptTarget::Create(node) = &target {
+                bible_graph_store::insert_node_in_transaction(tx, node, event.id)?;
+            }
+            match &introduction {
+                Some(introduction) => {
+                    bible_graph_store::set_snapshot_field_in_transaction(tx, introduction, event.id)
                 }
-                AcceptTarget::LinkExisting(_) => Ok(()),
+                None => Ok(()),
             }
         },
     )?)
 }
 
+const INTRODUCTION_LABEL: &str = "Introduced";
+
+/// The snapshot that gives an entity its first temporal anchor, or `None`
+/// when it already has snapshots, the source node is gone, or its schema is
+/// not a built-in one. It records the tagline, or the schema's first field
+/// when there is none, at its current value so resolved state has a baseline
+/// before any later snapshot. An empty field records the label instead.
+fn introduction_snapshot(
+    conn: &Connection,
+    proposal: &BibleReferenceProposal,
+    target: &AcceptTarget,
+    timeline: &Timeline,
+) -> Result<Option<SetBibleGraphSnapshotFieldCommand>, SemanticProposalStoreError> {
+    let Ok(source) = timeline.node(proposal.source_node_id) else {
+        return Ok(None);
+    };
+    let (schema_key, current_parts) = match target {
+        AcceptTarget::Create(node) => (node.schema_key.clone(), Vec::new()),
+        AcceptTarget::LinkExisting(node_id) => {
+            let Some(existing) = bible_graph_store::load_node_detail_projection(conn, node_id)?
+            else {
+                return Ok(None);
+            };
+            if !existing.snapshots.is_empty() {
+                return Ok(None);
+            }
+            (existing.node.schema_key, existing.parts)
+        }
+    };
+    let Some(schema) = builtin_bible_graph_schema(&schema_key) else {
+        return Ok(None);
+    };
+    let tagline = schema.parts.iter().find_map(|part| {
+        part.fields
+            .iter()
+            .find(|field| field.field_key == "tagline")
+            .map(|field| (part, field))
+    });
+    let Some((part, field)) = tagline.or_else(|| {
+        schema
+            .parts
+            .first()
+            .and_then(|part| part.fields.first().map(|field| (part, field)))
+    }) else {
+        return Ok(None);
+    };
+
+    let value = current_parts
+        .iter()
+        .filter(|projection| projection.part.part_key.as_str() == part.part_key)
+        .flat_map(|projection| &projection.fields)
+        .find(|current| current.field_key.as_str() == field.field_key)
+        .and_then(|current| current.value.clone())
+        .unwrap_or_else(|| FieldValue::Text(INTRODUCTION_LABEL.to_string()));
+    let node_id = target.node_id();
+    let snapshot_id = format!("snapshot.introduced.{}", node_id.as_str());
+    Ok(Some(SetBibleGraphSnapshotFieldCommand {
+        field_id: BibleGraphSnapshotFieldId::new(format!(
+            "{snapshot_id}.{}.{}",
+            part.part_key, field.field_key
+        ))?,
+        snapshot_id: BibleGraphSnapshotId::new(snapshot_id)?,
+        node_id: node_id.clone(),
+        at_ms: source.time_range.start_ms + source.time_range.duration_ms() / 2,
+        label: INTRODUCTION_LABEL.to_string(),
+        snapshot_sort_order: 0,
+        source_node_id: Some(source.id),
+        part_key: BibleGraphPartKey::new(part.part_key)?,
+        part_name: part.name.to_string(),
+        field_key: BibleGraphFieldKey::new(field.field_key)?,
+        value: Some(value),
+        field_sort_order: field.sort_order,
+    }))
+}
+
 enum AcceptTarget {
     Create(BibleGraphNode),
     LinkExisting(BibleGraphNodeId),
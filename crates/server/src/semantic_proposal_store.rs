@@ -395,6 +395,8 @@ pub(crate) enum SemanticProposalStoreError {
     History(#[from] HistoryStoreError),
     #[error(transparent)]
     Sqlite(#[from] rusqlite::Error),
+    #[error(transparent)]
+    BibleGraphContract(#[from] eidetic_core::contracts::BibleGraphContractError),
 }
 
 #[cfg(test)]
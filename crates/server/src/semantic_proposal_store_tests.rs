@@ -1,10 +1,11 @@
 use eidetic_core::contracts::{
-    AcceptBibleReferenceProposalCommand, BibleGraphNodeId, BibleGraphSchemaKey, BibleReferenceKind,
+    AcceptBibleReferenceProposalCommand, BibleGraphFieldKey, BibleGraphNodeId, BibleGraphPartKey,
+    BibleGraphSchemaKey, BibleGraphSnapshotFieldId, BibleGraphSnapshotId, BibleReferenceKind,
     CommandEnvelope, CreateBibleGraphNodeCommand, CreateBibleReferenceProposalCommand,
-    EnsureCanonicalBibleRootsCommand, ObjectKind, RejectBibleReferenceProposalCommand,
-    SemanticProposalId, SemanticProposalStatus,
+    EnsureCanonicalBibleRootsCommand, FieldValue, ObjectKind, RejectBibleReferenceProposalCommand,
+    SemanticProposalId, SemanticProposalStatus, SetBibleGraphSnapshotFieldCommand,
 };
-use eidetic_core::timeline::node::NodeId;
+use eidetic_core::timeline::node::{NodeId, StoryLevel};
 use rusqlite::Connection;
 
 use crate::history_store::{self, RecordChangeOutcome};
@@ -138,7 +139,7 @@ fn accepts_pending_bible_reference_proposal_by_creating_bible_node() {
     let accept = accept_command("proposal.child.harbor", "node.location.storm-harbor", None);
 
     let outcome = crate::semantic_proposal_accept::record_accept_bible_reference_proposal(
-        &mut conn, &accept, 43,
+        &mut conn, &accept, None, 43,
     )
     .unwrap();
     let proposals = load_bible_reference_proposals(&conn).unwrap();
@@ -183,11 +184,11 @@ fn accept_command_replays_without_second_node_insert() {
     );
 
     let first = crate::semantic_proposal_accept::record_accept_bible_reference_proposal(
-        &mut conn, &accept, 43,
+        &mut conn, &accept, None, 43,
     )
     .unwrap();
     let second = crate::semantic_proposal_accept::record_accept_bible_reference_proposal(
-        &mut conn, &accept, 43,
+        &mut conn, &accept, None, 43,
     )
     .unwrap();
     let proposals = load_bible_reference_proposals(&conn).unwrap();
@@ -223,7 +224,7 @@ fn accept_command_can_link_existing_bible_node() {
     let accept = accept_command("proposal.child.ada", "node.character.ada", None);
 
     let outcome = crate::semantic_proposal_accept::record_accept_bible_reference_proposal(
-        &mut conn, &accept, 43,
+        &mut conn, &accept, None, 43,
     )
     .unwrap();
     let proposals = load_bible_reference_proposals(&conn).unwrap();
@@ -252,6 +253,103 @@ fn accept_command_can_link_existing_bible_node() {
     );
 }
 
+#[test]
+fn accept_with_timeline_introduces_new_entity_at_source_midpoint() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    ensure_roots(&mut conn);
+    let timeline = eidetic_core::Template::MultiCam
+        .build_project("Intro")
+        .timeline;
+    let scene = timeline.nodes_at_level(StoryLevel::Scene)[1].clone();
+    let mut create = create_command("proposal.child.ada", "Ada", BibleReferenceKind::Character);
+    create.payload.source_node_id = scene.id;
+    record_create_bible_reference_proposal(&mut conn, &create, 42).unwrap();
+    let accept = accept_command("proposal.child.ada", "node.character.ada", None);
+
+    crate::semantic_proposal_accept::record_accept_bible_reference_proposal(
+        &mut conn,
+        &accept,
+        Some(&timeline),
+        43,
+    )
+    .unwrap();
+    let node = crate::bible_graph_store::load_node_detail_projection(
+        &conn,
+        &BibleGraphNodeId::new("node.character.ada").unwrap(),
+    )
+    .unwrap()
+    .expect("accepted bible node");
+
+    assert_eq!(node.snapshots.len(), 1);
+    let introduction = &node.snapshots[0];
+    assert_eq!(introduction.snapshot.label, "Introduced");
+    assert_eq!(introduction.snapshot.source_node_id, Some(scene.id));
+    assert_eq!(
+        introduction.snapshot.at_ms,
+        scene.time_range.start_ms + scene.time_range.duration_ms() / 2
+    );
+    assert_eq!(introduction.fields[0].field_key.as_str(), "tagline");
+    assert_eq!(
+        introduction.fields[0].value,
+        Some(FieldValue::Text("Introduced".to_string()))
+    );
+}
+
+#[test]
+fn accept_with_timeline_skips_entities_that_already_have_snapshots() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    let timeline = eidetic_core::Template::MultiCam
+        .build_project("Intro")
+        .timeline;
+    let scene = timeline.nodes_at_level(StoryLevel::Scene)[0].clone();
+    let existing_node = CommandEnvelope::new(CreateBibleGraphNodeCommand {
+        node_id: BibleGraphNodeId::new("node.character.ada").unwrap(),
+        parent_id: None,
+        schema_key: BibleGraphSchemaKey::new("character").unwrap(),
+        name: "Ada".to_string(),
+        sort_order: 10,
+    });
+    crate::bible_graph_command::apply_create_bible_graph_node(&mut conn, &existing_node, 40)
+        .unwrap();
+    let snapshot = CommandEnvelope::new(SetBibleGraphSnapshotFieldCommand {
+        snapshot_id: BibleGraphSnapshotId::new("snapshot.ada.later").unwrap(),
+        node_id: BibleGraphNodeId::new("node.character.ada").unwrap(),
+        at_ms: 90_000,
+        label: "Later".to_string(),
+        snapshot_sort_order: 0,
+        source_node_id: None,
+        field_id: BibleGraphSnapshotFieldId::new("snapshot.ada.later.tagline").unwrap(),
+        part_key: BibleGraphPartKey::new("profile").unwrap(),
+        part_name: "Profile".to_string(),
+        field_key: BibleGraphFieldKey::new("tagline").unwrap(),
+        value: Some(FieldValue::Text("Changed".to_string())),
+        field_sort_order: 0,
+    });
+    crate::bible_graph_command::apply_set_bible_graph_snapshot_field(&mut conn, &snapshot, 41)
+        .unwrap();
+    let mut create = create_command("proposal.child.ada", "Ada", BibleReferenceKind::Character);
+    create.payload.source_node_id = scene.id;
+    record_create_bible_reference_proposal(&mut conn, &create, 42).unwrap();
+    let accept = accept_command("proposal.child.ada", "node.character.ada", None);
+
+    crate::semantic_proposal_accept::record_accept_bible_reference_proposal(
+        &mut conn,
+        &accept,
+        Some(&timeline),
+        43,
+    )
+    .unwrap();
+    let node = crate::bible_graph_store::load_node_detail_projection(
+        &conn,
+        &BibleGraphNodeId::new("node.character.ada").unwrap(),
+    )
+    .unwrap()
+    .expect("linked bible node");
+
+    assert_eq!(node.snapshots.len(), 1);
+    assert_eq!(node.snapshots[0].snapshot.label, "Later");
+}
+
 #[test]
 fn accept_command_requires_existing_canonical_parent() {
     let mut conn = Connection::open_in_memory().unwrap();
@@ -260,7 +358,7 @@ fn accept_command_requires_existing_canonical_parent() {
     let accept = accept_command("proposal.child.ada", "node.character.ada", None);
 
     let error = crate::semantic_proposal_accept::record_accept_bible_reference_proposal(
-        &mut conn, &accept, 43,
+        &mut conn, &accept, None, 43,
     )
     .unwrap_err();
 
@@ -286,7 +384,7 @@ fn accept_command_requires_pending_proposal() {
     let accept = accept_command("proposal.child.ring", "node.prop.signal-ring", None);
 
     let error = crate::semantic_proposal_accept::record_accept_bible_reference_proposal(
-        &mut conn, &accept, 44,
+        &mut conn, &accept, None, 44,
     )
     .unwrap_err();
 
@@ -1,9 +1,14 @@
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use eidetic_core::Project;
-use eidetic_core::timeline::node::NodeId;
+use eidetic_core::ai::backend::GenerateRequest;
+use eidetic_core::contracts::BibleGraphNodeId;
+use eidetic_core::project::ProjectAiSettings;
+use eidetic_core::reference::RetrievalMode;
+use eidetic_core::timeline::node::{NodeId, StoryLevel};
+use eidetic_core::{Project, Template};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
@@ -11,34 +16,75 @@ use tokio::sync::broadcast;
 use crate::backend_task::BackendTaskSupervisor;
 use crate::persistence;
 use crate::project_database::ProjectDatabase;
+use crate::timeline_projection_cache::TimelineProjectionCache;
 use crate::vector_store::VectorStore;
 use crate::ydoc::{self, DocCommand, DocUpdate};
 use pumas_library::ModelLibrary;
 
 /// Server configuration constants.
 pub mod constants {
+    /// Template for new projects when the request omits one.
+    pub const DEFAULT_PROJECT_TEMPLATE: &str = "multi_cam";
     /// Default AI model identifier. "auto" means detect the external server's available model.
     pub const DEFAULT_AI_MODEL: &str = "auto";
     /// Default AI temperature.
     pub const DEFAULT_TEMPERATURE: f32 = 0.7;
     /// Default max tokens for generation.
     pub const DEFAULT_MAX_TOKENS: usize = 4096;
+    /// Default screenplay words per minute of screen time: one page a minute
+    /// at about 250 words a page.
+    pub const DEFAULT_WORDS_PER_MINUTE: u32 = 250;
+    /// Output tokens allowed per expected word of a beat, covering tokenizer
+    /// overhead and screenplay formatting.
+    pub const BEAT_TOKENS_PER_WORD: f64 = 1.5;
+    /// Fewest output tokens a time-budgeted beat generation is given.
+    pub const MIN_BEAT_MAX_TOKENS: usize = 256;
+    /// Most output tokens a time-budgeted beat generation is given.
+    pub const MAX_BEAT_MAX_TOKENS: usize = 8_192;
+    /// Default outline length targets in words for the levels above beats.
+    pub const DEFAULT_PREMISE_OUTLINE_WORDS: u32 = 300;
+    pub const DEFAULT_ACT_OUTLINE_WORDS: u32 = 300;
+    pub const DEFAULT_SEQUENCE_OUTLINE_WORDS: u32 = 200;
+    pub const DEFAULT_SCENE_OUTLINE_WORDS: u32 = 150;
+    /// How far past its target word count an output may run before it is
+    /// reported as over length. Outline generations get output tokens for
+    /// this many times their target, so the model has room to finish.
+    pub const OVER_LENGTH_FACTOR: f64 = 1.5;
+    /// Default model context window in tokens, used to budget prompts.
+    pub const DEFAULT_CONTEXT_LIMIT: usize = 16_384;
+    /// Default number of siblings (nearest in time) included in a generation prompt.
+    pub const DEFAULT_MAX_SIBLINGS: usize = 6;
+    /// Default characters of each sibling's text included in a generation prompt.
+    pub const DEFAULT_SIBLING_PREVIEW_CHARS: usize = 200;
     /// Default Pumas llama.cpp OpenAI-compatible base URL.
     pub const DEFAULT_LLAMACPP_URL: &str = "http://127.0.0.1:18080/v1";
     /// Reference document chunk size in characters.
     pub const REFERENCE_CHUNK_SIZE: usize = 500;
     /// Reference document chunk overlap in characters.
     pub const REFERENCE_CHUNK_OVERLAP: usize = 50;
-    /// Embedding model name.
-    pub const EMBEDDING_MODEL: &str = "nomic-embed-text";
+    /// Default embedding model name for reference material.
+    pub const DEFAULT_EMBEDDING_MODEL: &str = "nomic-embed-text";
     /// Number of top RAG results to include.
     pub const RAG_TOP_K: usize = 3;
+    /// Quiet period after the last mutation before auto-save writes.
+    pub const AUTOSAVE_QUIET_MS: u64 = 2_000;
+    /// Longest auto-save may be postponed by a continuous burst of edits.
+    pub const AUTOSAVE_MAX_DELAY_MS: u64 = 10_000;
+    /// Auto-checkpoints kept per project when the config does not say.
+    pub const DEFAULT_CHECKPOINT_RETENTION: usize = 12;
+    /// How often the auto-checkpoint task checks whether one is due.
+    pub const CHECKPOINT_POLL_SECS: u64 = 30;
+    /// Longest accepted auto-checkpoint interval: one week.
+    pub const MAX_CHECKPOINT_INTERVAL_MINUTES: u64 = 7 * 24 * 60;
 }
 
 /// Events broadcast to desktop event subscribers after mutations.
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ServerEvent {
+    /// A different project became active; clients should drop what they
+    /// hold for the previous one and reload.
+    ProjectChanged,
     TimelineChanged,
     HierarchyChanged,
     NodeUpdated {
@@ -49,6 +95,7 @@ pub enum ServerEvent {
         node_id: uuid::Uuid,
         system_prompt: String,
         user_prompt: String,
+        estimated_tokens: usize,
     },
     GenerationProgress {
         node_id: uuid::Uuid,
@@ -57,12 +104,65 @@ pub enum ServerEvent {
     },
     GenerationComplete {
         node_id: uuid::Uuid,
+        /// Set when the output stopped at the token limit and may be cut off.
+        truncated: bool,
+        /// The provider's finish reason, when it reported one.
+        reason: Option<String>,
+        /// Whether reference material made it into the prompt.
+        rag_used: bool,
+        retrieval_mode: RetrievalMode,
     },
     GenerationError {
         node_id: uuid::Uuid,
         error: String,
     },
+    /// The generation queue worker finished a queued node; `error` says why
+    /// it failed when it did.
+    GenerationQueueItemFinished {
+        node_id: uuid::Uuid,
+        error: Option<String>,
+    },
     BibleChanged,
+    /// Reference retrieval couldn't use embeddings for a generation; lexical
+    /// search stood in.
+    RagUnavailable {
+        node_id: uuid::Uuid,
+        reason: String,
+    },
+    /// A generation ran well past its level's target length; see
+    /// [`AiConfig::target_words`].
+    OutputOverLength {
+        node_id: uuid::Uuid,
+        level: StoryLevel,
+        words: usize,
+        target_words: u32,
+    },
+    /// A generation dropped a user-written anchor; it was spliced back into
+    /// the saved text.
+    AnchorViolation {
+        node_id: uuid::Uuid,
+        anchor: String,
+    },
+    /// A continuity audit found content contradicting an entity's snapshots.
+    ContinuityFinding {
+        node_id: uuid::Uuid,
+        entity_id: BibleGraphNodeId,
+        entity_name: String,
+        explanation: String,
+    },
+    ContinuityAuditComplete {
+        checked_count: usize,
+        finding_count: usize,
+    },
+    /// A notes-drift sweep found content that no longer carries out its notes.
+    NotesDriftFinding {
+        node_id: uuid::Uuid,
+        specifics: Vec<String>,
+    },
+    NotesDriftSweepComplete {
+        checked_count: usize,
+        drift_count: usize,
+    },
     ScriptChanged,
     SemanticProposalsChanged,
     ContextInfluenceChanged {
@@ -91,8 +191,50 @@ pub struct AiConfig {
     pub model: String,
     pub temperature: f32,
     pub max_tokens: usize,
+    /// Model context window in tokens. Prompts plus `max_tokens` must fit.
+    #[serde(default = "default_context_limit")]
+    pub context_limit: usize,
+    /// Siblings nearest in time to include in generation prompts.
+    #[serde(default = "default_max_siblings")]
+    pub max_siblings: usize,
+    /// Characters of each sibling's text to include in generation prompts.
+    #[serde(default = "default_sibling_preview_chars")]
+    pub sibling_preview_chars: usize,
+    /// Sequences that end generation early, e.g. `"\n\nINT."` to stop a beat
+    /// from running into the next scene heading.
+    #[serde(default)]
+    pub stop_sequences: Vec<String>,
+    /// Ask the backend for native structured JSON output on JSON requests.
+    /// Disable for servers that reject `response_format`.
+    #[serde(default = "default_json_mode")]
+    pub json_mode: bool,
+    /// Re-prompt with the partial output when a generation stops at the
+    /// token limit.
+    #[serde(default)]
+    pub auto_continue_truncated: bool,
+    /// Anchor an "Introduced" snapshot on the scene where an accepted bible
+    /// reference first links an entity that has no snapshots yet.
+    #[serde(default)]
+    pub introduction_snapshots: bool,
+    /// Screenplay words per minute of screen time, used to size beat
+    /// generations from their time budget. `0` gives every beat the flat
+    /// `max_tokens`.
+    #[serde(default = "default_words_per_minute")]
+    pub words_per_minute: u32,
+    /// Outline length targets for the levels above beats, which get short
+    /// outlines rather than script. Beats are sized by `words_per_minute`.
+    #[serde(default)]
+    pub outline_word_targets: OutlineWordTargets,
+    /// Model used to embed reference material and RAG queries. Switching it
+    /// requires re-embedding references, since vector dimensions differ.
+    #[serde(default = "default_embedding_model")]
+    pub embedding_model: String,
     pub base_url: String,
     pub api_key: Option<String>,
+    /// Backend to try when this one cannot start a request, e.g. a hosted
+    /// model behind a local one. Only its connection settings are used.
+    #[serde(default)]
+    pub fallback: Option<Box<AiConfig>>,
 }
 
 impl Default for AiConfig {
@@ -102,12 +244,198 @@ impl Default for AiConfig {
             model: constants::DEFAULT_AI_MODEL.into(),
             temperature: constants::DEFAULT_TEMPERATURE,
             max_tokens: constants::DEFAULT_MAX_TOKENS,
+            context_limit: constants::DEFAULT_CONTEXT_LIMIT,
+            max_siblings: constants::DEFAULT_MAX_SIBLINGS,
+            sibling_preview_chars: constants::DEFAULT_SIBLING_PREVIEW_CHARS,
+            stop_sequences: Vec::new(),
+            json_mode: default_json_mode(),
+            auto_continue_truncated: false,
+            introduction_snapshots: false,
+            words_per_minute: constants::DEFAULT_WORDS_PER_MINUTE,
+            outline_word_targets: OutlineWordTargets::default(),
+            embedding_model: default_embedding_model(),
             base_url: constants::DEFAULT_LLAMACPP_URL.into(),
             api_key: None,
+            fallback: None,
+        }
+    }
+}
+
+/// Target outline length in words per level. `0` leaves a level untargeted:
+/// it keeps the flat `max_tokens` and is never reported as over length.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutlineWordTargets {
+    pub premise: u32,
+    pub act: u32,
+    pub sequence: u32,
+    pub scene: u32,
+}
+
+impl Default for OutlineWordTargets {
+    fn default() -> Self {
+        Self {
+            premise: constants::DEFAULT_PREMISE_OUTLINE_WORDS,
+            act: constants::DEFAULT_ACT_OUTLINE_WORDS,
+            sequence: constants::DEFAULT_SEQUENCE_OUTLINE_WORDS,
+            scene: constants::DEFAULT_SCENE_OUTLINE_WORDS,
+        }
+    }
+}
+
+impl OutlineWordTargets {
+    pub fn for_level(&self, level: StoryLevel) -> Option<u32> {
+        let words = match level {
+            StoryLevel::Premise => self.premise,
+            StoryLevel::Act => self.act,
+            StoryLevel::Sequence => self.sequence,
+            StoryLevel::Scene => self.scene,
+            StoryLevel::Beat => return None,
+        };
+        (words > 0).then_some(words)
+    }
+}
+
+/// Cadence of automatic restore points for the active project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointConfig {
+    /// Minutes between auto-checkpoints of a project with new edits. `0`
+    /// turns auto-checkpoints off.
+    pub interval_minutes: u64,
+    /// Auto-checkpoints kept per project; older ones are pruned.
+    pub retention: usize,
+}
+
+/// Settings new projects take when the create request leaves them out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectDefaults {
+    /// Episode content length; the template is scaled to fit.
+    pub episode_duration_ms: u64,
+}
+
+impl Default for ProjectDefaults {
+    fn default() -> Self {
+        Self {
+            episode_duration_ms: Template::EPISODE_DURATION_MS,
+        }
+    }
+}
+
+impl Default for CheckpointConfig {
+    fn default() -> Self {
+        Self {
+            interval_minutes: 0,
+            retention: constants::DEFAULT_CHECKPOINT_RETENTION,
         }
     }
 }
 
+fn default_embedding_model() -> String {
+    constants::DEFAULT_EMBEDDING_MODEL.into()
+}
+
+fn default_context_limit() -> usize {
+    constants::DEFAULT_CONTEXT_LIMIT
+}
+
+fn default_max_siblings() -> usize {
+    constants::DEFAULT_MAX_SIBLINGS
+}
+
+fn default_sibling_preview_chars() -> usize {
+    constants::DEFAULT_SIBLING_PREVIEW_CHARS
+}
+
+fn default_json_mode() -> bool {
+    true
+}
+
+fn default_words_per_minute() -> u32 {
+    constants::DEFAULT_WORDS_PER_MINUTE
+}
+
+impl AiConfig {
+    /// Tokens available for the prompt once `max_tokens` is reserved for output.
+    pub fn prompt_budget(&self) -> usize {
+        self.context_limit.saturating_sub(self.max_tokens)
+    }
+
+    /// This config with a project's model and temperature overrides applied.
+    pub fn with_project_settings(&self, settings: &ProjectAiSettings) -> AiConfig {
+        AiConfig {
+            model: settings.model.clone().unwrap_or_else(|| self.model.clone()),
+            temperature: settings.temperature.unwrap_or(self.temperature),
+            ..self.clone()
+        }
+    }
+
+    /// This config with `max_tokens` sized for `request`. Beats get tokens
+    /// for the words their time budget calls for, within the beat bounds.
+    /// Targeted outline levels get room for their target with the over-length
+    /// margin, never more than the flat `max_tokens`; other levels keep it.
+    pub fn for_request(&self, request: &GenerateRequest) -> AiConfig {
+        let Some(words) = self.target_words(request) else {
+            return self.clone();
+        };
+        let max_tokens = if request.target_node.level == StoryLevel::Beat {
+            ((f64::from(words) * constants::BEAT_TOKENS_PER_WORD) as usize).clamp(
+                constants::MIN_BEAT_MAX_TOKENS,
+                constants::MAX_BEAT_MAX_TOKENS,
+            )
+        } else {
+            let tokens =
+                f64::from(words) * constants::BEAT_TOKENS_PER_WORD * constants::OVER_LENGTH_FACTOR;
+            (tokens as usize).min(self.max_tokens)
+        };
+        AiConfig {
+            max_tokens,
+            ..self.clone()
+        }
+    }
+
+    /// The length in words `request`'s output should aim for: the outline
+    /// target above beats, or the words a beat's time budget calls for.
+    /// `None` when the level is untargeted.
+    pub fn target_words(&self, request: &GenerateRequest) -> Option<u32> {
+        if request.target_node.level != StoryLevel::Beat {
+            return self
+                .outline_word_targets
+                .for_level(request.target_node.level);
+        }
+        if self.words_per_minute == 0 {
+            return None;
+        }
+        let words = request.time_budget_ms as f64 / 60_000.0 * f64::from(self.words_per_minute);
+        Some(words as u32)
+    }
+
+    /// These request settings aimed at `other`'s backend, model, URL and key.
+    pub fn with_connection_of(&self, other: &AiConfig) -> AiConfig {
+        AiConfig {
+            backend_type: other.backend_type,
+            model: other.model.clone(),
+            base_url: other.base_url.clone(),
+            api_key: other.api_key.clone(),
+            fallback: None,
+            ..self.clone()
+        }
+    }
+}
+
+/// Keeps a node in [`AppState::generating`] while held. Removal happens on
+/// drop, so a generation task that is aborted or panics cannot leave the
+/// node stuck as in progress.
+pub(crate) struct GeneratingGuard {
+    generating: Arc<Mutex<HashSet<uuid::Uuid>>>,
+    node_id: uuid::Uuid,
+}
+
+impl Drop for GeneratingGuard {
+    fn drop(&mut self) {
+        self.generating.lock().remove(&self.node_id);
+    }
+}
+
 /// Shared application state, wrapped in an Arc for desktop command adapters.
 #[derive(Clone)]
 pub struct AppState {
@@ -130,7 +458,13 @@ pub struct AppState {
     /// In-memory vector store for RAG reference material.
     pub vector_store: Arc<Mutex<VectorStore>>,
     /// Channel to signal the auto-save background task.
-    save_tx: tokio::sync::mpsc::Sender<()>,
+    save_tx: tokio::sync::mpsc::Sender<SaveRequest>,
+    pub checkpoint_config: Arc<Mutex<CheckpointConfig>>,
+    pub project_defaults: Arc<Mutex<ProjectDefaults>>,
+    /// Set by every save signal and cleared when an auto-checkpoint is written.
+    checkpoint_pending: Arc<AtomicBool>,
+    /// Set by every save signal and cleared when the auto-save task writes.
+    unsaved_changes: Arc<AtomicBool>,
     /// Model library from Pumas for listing available local models.
     pub model_library: Option<Arc<ModelLibrary>>,
     /// Backend-owned transient timeline selection projected to renderers and UI.
@@ -139,6 +473,10 @@ pub struct AppState {
     pub timeline_playhead_ms: Arc<Mutex<u64>>,
     /// Owns long-running backend tasks so desktop shutdown can stop them.
     pub task_supervisor: BackendTaskSupervisor,
+    /// Timeline render projection reused across reads until the timeline changes.
+    pub(crate) timeline_projection_cache: Arc<TimelineProjectionCache>,
+    /// Set while a task is draining the persistent generation queue.
+    pub(crate) generation_queue_worker: Arc<AtomicBool>,
 }
 
 impl AppState {
@@ -150,6 +488,8 @@ impl AppState {
         let project_path = Arc::new(Mutex::new(None::<PathBuf>));
         let project_database = ProjectDatabase::new(project_path.clone());
         let task_supervisor = BackendTaskSupervisor::default();
+        let timeline_projection_cache =
+            Arc::new(TimelineProjectionCache::new(events_tx.subscribe()));
 
         // Spawn the debounced auto-save background task.
         let save_project = project.clone();
@@ -160,9 +500,30 @@ impl AppState {
 
         // Start auto-save (needs doc_tx to serialize Y.Doc state).
         let save_doc_tx = doc_tx.clone();
+        let unsaved_changes = Arc::new(AtomicBool::new(false));
         task_supervisor.spawn(
             "auto-save",
-            auto_save_task(save_rx, save_project, save_path, save_doc_tx),
+            auto_save_task(
+                save_rx,
+                save_project,
+                save_path,
+                save_doc_tx,
+                timeline_projection_cache.clone(),
+                unsaved_changes.clone(),
+            ),
+        );
+
+        let checkpoint_config = Arc::new(Mutex::new(CheckpointConfig::default()));
+        let checkpoint_pending = Arc::new(AtomicBool::new(false));
+        task_supervisor.spawn(
+            "auto-checkpoint",
+            auto_checkpoint_task(
+                project.clone(),
+                project_path.clone(),
+                checkpoint_config.clone(),
+                checkpoint_pending.clone(),
+                save_tx.clone(),
+            ),
         );
 
         // Initialize the Pumas model library (optional — best-effort).
@@ -180,11 +541,48 @@ impl AppState {
             project_database,
             vector_store: Arc::new(Mutex::new(VectorStore::new())),
             save_tx,
+            checkpoint_config,
+            project_defaults: Arc::new(Mutex::new(ProjectDefaults::default())),
+            checkpoint_pending,
+            unsaved_changes,
             model_library,
             selected_timeline_node_id: Arc::new(Mutex::new(None)),
             timeline_playhead_ms: Arc::new(Mutex::new(0)),
             task_supervisor,
+            timeline_projection_cache,
+            generation_queue_worker: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// The global AI config with the active project's overrides applied;
+    /// what generation requests should use.
+    pub fn active_ai_config(&self) -> AiConfig {
+        let config = self.ai_config.lock().clone();
+        match self.project.lock().as_ref() {
+            Some(project) => config.with_project_settings(&project.ai_settings),
+            None => config,
+        }
+    }
+
+    /// A read-only copy of the active project, cloned under the project lock
+    /// and released before returning. Long reads such as exports and
+    /// analyses walk the copy so edits are not blocked meanwhile; they do not
+    /// see edits made after the call.
+    pub fn project_snapshot(&self) -> Option<Arc<Project>> {
+        let project = self.project.lock().clone();
+        project.map(Arc::new)
+    }
+
+    /// Mark `node_id` as generating until the returned guard drops, or `None`
+    /// if it already is.
+    pub(crate) fn begin_generating(&self, node_id: uuid::Uuid) -> Option<GeneratingGuard> {
+        if !self.generating.lock().insert(node_id) {
+            return None;
         }
+        Some(GeneratingGuard {
+            generating: Arc::clone(&self.generating),
+            node_id,
+        })
     }
 
     pub fn select_timeline_node(&self, node_id: Option<NodeId>) {
@@ -271,44 +669,309 @@ impl AppState {
         None
     }
 
-    /// Signal that the project has been mutated and should be auto-saved.
+    /// Mark the project dirty. The write happens once edits have been quiet
+    /// for [`constants::AUTOSAVE_QUIET_MS`], so bursts coalesce into one save.
     pub fn trigger_save(&self) {
-        let _ = self.save_tx.try_send(());
+        self.checkpoint_pending.store(true, Ordering::Relaxed);
+        self.unsaved_changes.store(true, Ordering::Relaxed);
+        // A full channel already holds a pending signal, so dropping is fine.
+        let _ = self.save_tx.try_send(SaveRequest::Dirty);
     }
+
+    /// Whether edits are waiting for the auto-save to write them.
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.unsaved_changes.load(Ordering::Relaxed)
+    }
+
+    /// Drop this server's lock on the active project. Call on shutdown after
+    /// the final save.
+    pub fn release_project_lock(&self) {
+        if let Some(path) = self.project_database.active_path() {
+            persistence::release_project_lock(&path);
+        }
+    }
+
+    /// Save immediately, skipping the debounce, and wait for the write to
+    /// finish. Use before switching projects or shutting down.
+    pub async fn force_save(&self) {
+        let (ack_tx, ack_rx) = tokio::sync::oneshot::channel();
+        if self.save_tx.send(SaveRequest::Flush(ack_tx)).await.is_ok() {
+            let _ = ack_rx.await;
+        }
+    }
+}
+
+/// Signals consumed by the auto-save task.
+enum SaveRequest {
+    /// The project changed; save after the quiet period.
+    Dirty,
+    /// Save now and acknowledge once written.
+    Flush(tokio::sync::oneshot::Sender<()>),
 }
 
 /// Background task that debounces save signals and writes to disk.
 async fn auto_save_task(
-    mut rx: tokio::sync::mpsc::Receiver<()>,
+    mut rx: tokio::sync::mpsc::Receiver<SaveRequest>,
     project: Arc<Mutex<Option<Project>>>,
     project_path: Arc<Mutex<Option<PathBuf>>>,
     doc_tx: tokio::sync::mpsc::Sender<ydoc::DocCommand>,
+    timeline_projection_cache: Arc<TimelineProjectionCache>,
+    unsaved_changes: Arc<AtomicBool>,
 ) {
-    loop {
-        // Wait for the first save signal.
-        if rx.recv().await.is_none() {
+    let quiet = std::time::Duration::from_millis(constants::AUTOSAVE_QUIET_MS);
+    let max_delay = std::time::Duration::from_millis(constants::AUTOSAVE_MAX_DELAY_MS);
+
+    // Wait for the first signal of a burst.
+    while let Some(request) = rx.recv().await {
+        let mut flush = match request {
+            SaveRequest::Dirty => None,
+            SaveRequest::Flush(ack) => Some(ack),
+        };
+
+        // Debounce: every further signal restarts the quiet period, up to
+        // the max delay since the burst began.
+        let deadline = tokio::time::Instant::now() + max_delay;
+        let mut closed = false;
+        while flush.is_none() {
+            let wake = (tokio::time::Instant::now() + quiet).min(deadline);
+            match tokio::time::timeout_at(wake, rx.recv()).await {
+                Ok(Some(SaveRequest::Dirty)) => {}
+                Ok(Some(SaveRequest::Flush(ack))) => flush = Some(ack),
+                Ok(None) => {
+                    closed = true;
+                    break;
+                }
+                Err(_) => break,
+            }
+        }
+
+        // Cleared before writing so edits made during the save stay unsaved.
+        unsaved_changes.store(false, Ordering::Relaxed);
+        save_now(&project, &project_path, &doc_tx).await;
+        // Saving can rewrite timeline tables without a matching event.
+        timeline_projection_cache.invalidate();
+        if let Some(ack) = flush {
+            let _ = ack.send(());
+        }
+        if closed {
             break;
         }
+    }
+}
 
-        // Debounce: wait 2 seconds, draining any additional signals.
-        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-        while rx.try_recv().is_ok() {}
-
-        // Perform the save.
-        let (proj_json, path) = {
-            let guard = project.lock();
-            let path_guard = project_path.lock();
-            match (guard.as_ref(), path_guard.clone()) {
-                (Some(p), Some(path)) => (p.clone(), path),
-                _ => continue,
-            }
+/// Background task that writes an auto-checkpoint of the active project once
+/// the configured interval has passed with edits since the last one.
+async fn auto_checkpoint_task(
+    project: Arc<Mutex<Option<Project>>>,
+    project_path: Arc<Mutex<Option<PathBuf>>>,
+    config: Arc<Mutex<CheckpointConfig>>,
+    pending: Arc<AtomicBool>,
+    save_tx: tokio::sync::mpsc::Sender<SaveRequest>,
+) {
+    let mut ticker = tokio::time::interval(std::time::Duration::from_secs(
+        constants::CHECKPOINT_POLL_SECS,
+    ));
+    let mut last_checkpoint = tokio::time::Instant::now();
+    loop {
+        ticker.tick().await;
+        let config = config.lock().clone();
+        let interval = std::time::Duration::from_secs(config.interval_minutes.saturating_mul(60));
+        if config.interval_minutes == 0 || last_checkpoint.elapsed() < interval {
+            continue;
+        }
+        let path = project_path.lock().clone();
+        let Some(path) = path.filter(|_| project.lock().is_some()) else {
+            continue;
         };
+        if !pending.swap(false, Ordering::Relaxed) {
+            continue;
+        }
+        last_checkpoint = tokio::time::Instant::now();
 
-        // Serialize Y.Doc state alongside structural data.
-        let ydoc_state = ydoc::serialize_doc(&doc_tx).await;
+        // Checkpoint what the user sees, not the last debounced save.
+        let (ack_tx, ack_rx) = tokio::sync::oneshot::channel();
+        if save_tx.send(SaveRequest::Flush(ack_tx)).await.is_ok() {
+            let _ = ack_rx.await;
+        }
+        let written = tokio::task::spawn_blocking(move || {
+            crate::checkpoint_service::write_auto_checkpoint(
+                &path,
+                config.retention,
+                persistence::unix_now_secs(),
+            )
+        })
+        .await;
+        match written {
+            Ok(Ok(checkpoint)) => tracing::info!("wrote checkpoint {}", checkpoint.display()),
+            Ok(Err(error)) => tracing::error!("auto-checkpoint failed: {error}"),
+            Err(error) => tracing::error!("auto-checkpoint task failed: {error}"),
+        }
+    }
+}
 
-        if let Err(e) = persistence::save_project(&proj_json, &path, ydoc_state).await {
-            tracing::error!("auto-save failed: {e}");
+async fn save_now(
+    project: &Mutex<Option<Project>>,
+    project_path: &Mutex<Option<PathBuf>>,
+    doc_tx: &tokio::sync::mpsc::Sender<ydoc::DocCommand>,
+) {
+    let (proj_json, path) = {
+        let guard = project.lock();
+        let path_guard = project_path.lock();
+        match (guard.as_ref(), path_guard.clone()) {
+            (Some(p), Some(path)) => (p.clone(), path),
+            _ => return,
         }
+    };
+
+    // Serialize Y.Doc state alongside structural data.
+    let ydoc_state = ydoc::serialize_doc(doc_tx).await;
+
+    if let Err(e) = persistence::save_project(&proj_json, &path, ydoc_state).await {
+        tracing::error!("auto-save failed: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AiConfig, AppState, constants};
+    use eidetic_core::Template;
+    use eidetic_core::ai::prompt::build_generate_request;
+    use eidetic_core::timeline::node::StoryLevel;
+
+    #[tokio::test]
+    async fn force_save_writes_without_waiting_for_debounce() {
+        let path =
+            std::env::temp_dir().join(format!("eidetic-force-save-{}.db", uuid::Uuid::new_v4()));
+        let state = AppState::new().await;
+        *state.project.lock() = Some(Template::MultiCam.build_project("Force Save Test"));
+        *state.project_path.lock() = Some(path.clone());
+
+        state.trigger_save();
+        state.force_save().await;
+
+        assert!(path.exists());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn project_snapshot_releases_the_lock_and_ignores_later_edits() {
+        let state = AppState::new().await;
+        assert!(state.project_snapshot().is_none());
+        *state.project.lock() = Some(Template::MultiCam.build_project("Snapshot Test"));
+
+        let snapshot = state.project_snapshot().expect("snapshot");
+        let mut guard = state.project.try_lock().expect("lock released");
+        guard.as_mut().unwrap().name = "Renamed".to_string();
+        drop(guard);
+
+        assert_eq!(snapshot.name, "Snapshot Test");
+        assert_eq!(state.project_snapshot().unwrap().name, "Renamed");
+    }
+
+    #[tokio::test]
+    async fn aborted_generation_tasks_release_their_node() {
+        let state = AppState::new().await;
+        let node_id = uuid::Uuid::new_v4();
+        let generating = state.begin_generating(node_id).expect("first claim");
+        assert!(state.begin_generating(node_id).is_none());
+
+        state.task_supervisor.spawn("stuck-generation", async move {
+            let _generating = generating;
+            std::future::pending::<()>().await;
+        });
+        assert!(state.generating.lock().contains(&node_id));
+        state.task_supervisor.shutdown_all().await;
+
+        assert!(!state.generating.lock().contains(&node_id));
+        assert!(state.begin_generating(node_id).is_some());
+    }
+
+    #[test]
+    fn beat_max_tokens_follow_the_time_budget() {
+        let project = Template::MultiCam.build_project("Budget Test");
+        let scene = project.timeline.nodes_at_level(StoryLevel::Scene)[0].id;
+        let mut request = build_generate_request(&project, scene).unwrap();
+        let config = AiConfig::default();
+
+        request.target_node.level = StoryLevel::Beat;
+        request.time_budget_ms = 60_000;
+        let one_minute = config.for_request(&request).max_tokens;
+        request.time_budget_ms = 120_000;
+        assert_eq!(config.for_request(&request).max_tokens, one_minute * 2);
+
+        request.time_budget_ms = 1_000;
+        assert_eq!(
+            config.for_request(&request).max_tokens,
+            constants::MIN_BEAT_MAX_TOKENS
+        );
+        request.time_budget_ms = 3_600_000;
+        assert_eq!(
+            config.for_request(&request).max_tokens,
+            constants::MAX_BEAT_MAX_TOKENS
+        );
+
+        let flat = AiConfig {
+            words_per_minute: 0,
+            ..AiConfig::default()
+        };
+        assert_eq!(flat.for_request(&request).max_tokens, flat.max_tokens);
+    }
+
+    #[test]
+    fn outline_levels_get_tokens_for_their_word_target() {
+        let project = Template::MultiCam.build_project("Outline Budget Test");
+        let scene = project.timeline.nodes_at_level(StoryLevel::Scene)[0].id;
+        let mut request = build_generate_request(&project, scene).unwrap();
+        let config = AiConfig::default();
+
+        assert_eq!(
+            config.target_words(&request),
+            Some(constants::DEFAULT_SCENE_OUTLINE_WORDS)
+        );
+        let scene_tokens = config.for_request(&request).max_tokens;
+        request.target_node.level = StoryLevel::Act;
+        let act_tokens = config.for_request(&request).max_tokens;
+        assert!(scene_tokens < act_tokens);
+        assert!(act_tokens < config.max_tokens);
+
+        let mut untargeted = AiConfig::default();
+        untargeted.outline_word_targets.act = 0;
+        assert_eq!(untargeted.target_words(&request), None);
+        assert_eq!(
+            untargeted.for_request(&request).max_tokens,
+            untargeted.max_tokens
+        );
+    }
+
+    #[test]
+    fn partial_fallback_config_takes_defaults_for_missing_fields() {
+        let config: AiConfig = serde_json::from_value(serde_json::json!({
+            "backend_type": "llama_cpp",
+            "model": "local",
+            "temperature": 0.7,
+            "max_tokens": 512,
+            "base_url": "http://localhost:8080",
+            "fallback": {
+                "backend_type": "open_router",
+                "model": "hosted",
+                "temperature": 0.7,
+                "max_tokens": 512,
+                "base_url": "https://openrouter.ai/api/v1",
+            },
+        }))
+        .expect("partial config");
+
+        let defaults = AiConfig::default();
+        let fallback = config.fallback.as_deref().expect("fallback");
+        assert_eq!(config.context_limit, defaults.context_limit);
+        assert_eq!(fallback.context_limit, defaults.context_limit);
+        assert_eq!(fallback.max_siblings, defaults.max_siblings);
+        assert_eq!(
+            fallback.sibling_preview_chars,
+            defaults.sibling_preview_chars
+        );
+        assert!(fallback.stop_sequences.is_empty());
+        assert!(fallback.json_mode);
+        assert_eq!(fallback.model, "hosted");
     }
 }
@@ -2,17 +2,20 @@ use std::collections::HashSet;
 
 use eidetic_core::Project;
 use eidetic_core::contracts::{
-    ApplyTimelineChildCommand, ApplyTimelineChildrenCommand, BibleReferenceKind,
-    BibleReferenceProposal, ChangeEvent, ChangeEventId, ChangeEventKind, CommandEnvelope,
-    CreateBibleReferenceProposalCommand, FieldDelta, FieldValue, ObjectKind, ObjectRevision,
-    RevisionOperation, SemanticProposalId,
+    ApplyTimelineChildCommand, ApplyTimelineChildPreview, ApplyTimelineChildrenCommand,
+    ApplyTimelineChildrenPreview, ApplyTimelineReferencePreview, BibleGraphNode,
+    BibleGraphSchemaKey, BibleReferenceKind, BibleReferenceProposal, ChangeEvent, ChangeEventId,
+    ChangeEventKind, CommandEnvelope, CreateBibleReferenceProposalCommand, FieldDelta, FieldValue,
+    ObjectKind, ObjectRevision, RevisionOperation, SemanticProposalId,
 };
 use eidetic_core::story::arc::ArcId;
+use eidetic_core::timeline::MIN_CHILD_GAP_MS;
 use eidetic_core::timeline::node::{ContentStatus, NodeId, StoryLevel, StoryNode};
 use eidetic_core::timeline::relationship::Relationship;
-use eidetic_core::timeline::timing::TimeRange;
+use eidetic_core::timeline::timing::{TimeRange, distribute_weighted_ranges_into_gaps};
 use rusqlite::Connection;
 
+use crate::bible_graph_store;
 use crate::history_store::{self, RecordChangeOutcome};
 use crate::semantic_proposal_store;
 use crate::timeline_child_plan_apply;
@@ -37,8 +40,21 @@ pub(crate) fn record_apply_timeline_children_history(
         return Ok(outcome);
     }
 
-    let child_plan = validate_and_plan_children(project, command)?;
-    let existing_children = collect_removed_children(project, command.payload.parent_id);
+    let preserve_existing = command.payload.preserve_existing;
+    let mut child_plan = validate_and_plan_children(project, command)?;
+    let renumbered_children = if preserve_existing {
+        interleave_sort_orders(
+            project.timeline.children_of(command.payload.parent_id),
+            &mut child_plan,
+        )
+    } else {
+        Vec::new()
+    };
+    let existing_children = if preserve_existing {
+        Vec::new()
+    } else {
+        collect_removed_children(project, command.payload.parent_id)
+    };
     let removed_node_ids: Vec<_> = existing_children.iter().map(|node| node.id).collect();
     let removed_relationships: Vec<_> = project
         .timeline
@@ -56,12 +72,13 @@ pub(crate) fn record_apply_timeline_children_history(
 
     let parent = project.timeline.node(command.payload.parent_id)?;
     let parent_arc_ids = project.timeline.arcs_for_node(parent.id);
-    let event = ChangeEvent::new(
-        command.id,
-        ChangeEventKind::UserEdit,
-        format!("replace timeline children for {}", parent.name),
-    )
-    .with_created_at_ms(created_at_ms);
+    let summary = if preserve_existing {
+        format!("fill timeline child gaps for {}", parent.name)
+    } else {
+        format!("replace timeline children for {}", parent.name)
+    };
+    let event = ChangeEvent::new(command.id, ChangeEventKind::UserEdit, summary)
+        .with_created_at_ms(created_at_ms);
     let mut revisions = Vec::new();
     for node in existing_children {
         revisions.push(deleted_node_revision(
@@ -73,6 +90,9 @@ pub(crate) fn record_apply_timeline_children_history(
     for relationship in removed_relationships {
         revisions.push(deleted_relationship_revision(relationship, event.id)?);
     }
+    for (node, sort_order) in &renumbered_children {
+        revisions.push(sort_order_revision(node, *sort_order, event.id));
+    }
     for planned_child in &child_plan {
         revisions.push(created_child_revision(
             planned_child.child,
@@ -96,7 +116,12 @@ pub(crate) fn record_apply_timeline_children_history(
         revisions.push(revision);
     }
     let mut next_timeline = project.timeline.clone();
-    next_timeline.clear_children_of(command.payload.parent_id)?;
+    if !preserve_existing {
+        next_timeline.clear_children_of(command.payload.parent_id)?;
+    }
+    for (node, sort_order) in &renumbered_children {
+        next_timeline.node_mut(node.id)?.sort_order = *sort_order;
+    }
     for planned_child in &child_plan {
         let mut node = StoryNode::new_child(
             &planned_child.child.name,
@@ -107,9 +132,7 @@ pub(crate) fn record_apply_timeline_children_history(
         node.id = planned_child.child.node_id;
         node.sort_order = planned_child.sort_order;
         node.content.notes = planned_child.child.outline.clone();
-        if !node.content.notes.is_empty() {
-            node.content.status = ContentStatus::NotesOnly;
-        }
+        node.content.refresh_status();
         node.beat_type = planned_child.child.beat_type.clone();
 
         next_timeline.add_node(node)?;
@@ -145,6 +168,70 @@ pub(crate) fn record_apply_timeline_children_history(
     )?)
 }
 
+/// Validate a child plan and report what applying it would do: the computed
+/// child ranges, the nodes a replace would delete, and for each bible
+/// reference whether an entity with that name already exists to link to.
+/// Nothing is written.
+pub(crate) fn preview_apply_timeline_children(
+    conn: &Connection,
+    project: &Project,
+    command: &CommandEnvelope<ApplyTimelineChildrenCommand>,
+) -> Result<ApplyTimelineChildrenPreview, TimelineCommandError> {
+    let child_plan = validate_and_plan_children(project, command)?;
+    let removed_node_ids = if command.payload.preserve_existing {
+        Vec::new()
+    } else {
+        collect_removed_children(project, command.payload.parent_id)
+            .into_iter()
+            .map(|node| node.id)
+            .collect()
+    };
+
+    bible_graph_store::create_schema(conn)?;
+    let entities = bible_graph_store::load_node_list_projection(conn)?.nodes;
+    let bible_references = bible_reference_proposals_for_children(command, 0)
+        .into_iter()
+        .map(|proposal| ApplyTimelineReferencePreview {
+            existing_node_id: existing_reference_target(
+                &entities,
+                &proposal.proposed_schema_key,
+                &proposal.reference_text,
+            )
+            .map(|node| node.id.clone()),
+            source_node_id: proposal.source_node_id,
+            reference_kind: proposal.reference_kind,
+            reference_text: proposal.reference_text,
+        })
+        .collect();
+
+    Ok(ApplyTimelineChildrenPreview {
+        children: child_plan
+            .iter()
+            .map(|planned| ApplyTimelineChildPreview {
+                node_id: planned.child.node_id,
+                name: planned.child.name.clone(),
+                start_ms: planned.time_range.start_ms,
+                end_ms: planned.time_range.end_ms,
+            })
+            .collect(),
+        removed_node_ids,
+        bible_references,
+    })
+}
+
+/// The entity a reference names: same proposed schema, same name ignoring
+/// case and surrounding whitespace.
+fn existing_reference_target<'a>(
+    entities: &'a [BibleGraphNode],
+    schema_key: &BibleGraphSchemaKey,
+    reference_text: &str,
+) -> Option<&'a BibleGraphNode> {
+    let name = reference_text.trim().to_lowercase();
+    entities
+        .iter()
+        .find(|node| &node.schema_key == schema_key && node.name.trim().to_lowercase() == name)
+}
+
 fn bible_reference_proposals_for_children(
     command: &CommandEnvelope<ApplyTimelineChildrenCommand>,
     created_at_ms: u64,
@@ -265,35 +352,68 @@ fn validate_and_plan_children<'a>(
         return Ok(Vec::new());
     }
 
-    let total_weight: f32 = command
-        .payload
-        .children
-        .iter()
-        .map(|child| child.weight.max(0.1))
-        .sum();
-    let parent_duration = parent.time_range.end_ms - parent.time_range.start_ms;
-    let mut cursor = parent.time_range.start_ms;
+    let weights: Vec<f32> = command.payload.children.iter().map(|c| c.weight).collect();
+    let ranges = if command.payload.preserve_existing {
+        let gaps: Vec<TimeRange> = project
+            .timeline
+            .find_child_gaps(parent.id, MIN_CHILD_GAP_MS)?
+            .into_iter()
+            .map(|gap| gap.time_range)
+            .collect();
+        if gaps.is_empty() {
+            return Err(TimelineCommandError::Core(
+                eidetic_core::Error::InvalidOperation(
+                    "existing children already cover the parent".to_string(),
+                ),
+            ));
+        }
+        distribute_weighted_ranges_into_gaps(&gaps, &weights)
+    } else {
+        project.timeline.split_child_ranges(parent, &weights)
+    };
     let mut plan = Vec::with_capacity(command.payload.children.len());
-    for (index, child) in command.payload.children.iter().enumerate() {
-        let weight = child.weight.max(0.1);
-        let duration = if index == command.payload.children.len() - 1 {
-            parent.time_range.end_ms - cursor
-        } else {
-            ((weight / total_weight) * parent_duration as f32) as u64
-        };
-        let end_ms = (cursor + duration).min(parent.time_range.end_ms);
-        let time_range = TimeRange::new(cursor, end_ms)?;
+    for (index, (child, time_range)) in command.payload.children.iter().zip(ranges).enumerate() {
+        time_range.validate()?;
         plan.push(PlannedChild {
             child,
             level: child_level,
             time_range,
             sort_order: index as u32,
         });
-        cursor = end_ms;
     }
     Ok(plan)
 }
 
+/// Number the planned children in among the existing ones by start time,
+/// returning the existing children whose sort order changes.
+fn interleave_sort_orders<'a>(
+    existing: Vec<&'a StoryNode>,
+    plan: &mut [PlannedChild<'_>],
+) -> Vec<(&'a StoryNode, u32)> {
+    let mut slots: Vec<(u64, bool, usize)> = existing
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (node.time_range.start_ms, false, index))
+        .chain(
+            plan.iter()
+                .enumerate()
+                .map(|(index, planned)| (planned.time_range.start_ms, true, index)),
+        )
+        .collect();
+    slots.sort_by_key(|&(start_ms, planned, _)| (start_ms, planned));
+
+    let mut renumbered = Vec::new();
+    for (sort_order, (_, planned, index)) in slots.into_iter().enumerate() {
+        let sort_order = sort_order as u32;
+        if planned {
+            plan[index].sort_order = sort_order;
+        } else if existing[index].sort_order != sort_order {
+            renumbered.push((existing[index], sort_order));
+        }
+    }
+    renumbered
+}
+
 fn collect_removed_children(project: &Project, parent_id: NodeId) -> Vec<&StoryNode> {
     let mut removed = Vec::new();
     for child in project.timeline.children_of(parent_id) {
@@ -378,6 +498,24 @@ fn deleted_node_revision(
     Ok(revision)
 }
 
+fn sort_order_revision(
+    node: &StoryNode,
+    sort_order: u32,
+    event_id: ChangeEventId,
+) -> ObjectRevision {
+    ObjectRevision::new(
+        ObjectKind::TimelineNode,
+        node.id.0.to_string(),
+        event_id,
+        RevisionOperation::Update,
+    )
+    .with_field(FieldDelta::new(
+        "sort_order",
+        Some(FieldValue::Integer(node.sort_order as i64)),
+        Some(FieldValue::Integer(sort_order as i64)),
+    ))
+}
+
 fn deleted_relationship_revision(
     relationship: &Relationship,
     event_id: ChangeEventId,
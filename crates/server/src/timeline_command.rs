@@ -9,7 +9,7 @@ use eidetic_core::contracts::{
 #[cfg(test)]
 use eidetic_core::project::Project;
 #[cfg(test)]
-use eidetic_core::timeline::node::{ContentStatus, StoryNode};
+use eidetic_core::timeline::node::StoryNode;
 #[cfg(test)]
 use eidetic_core::timeline::relationship::Relationship;
 #[cfg(test)]
@@ -17,14 +17,24 @@ use eidetic_core::timeline::timing::TimeRange;
 use thiserror::Error;
 
 use crate::history_store::HistoryStoreError;
-pub(crate) use crate::timeline_children_history::record_apply_timeline_children_history;
+pub(crate) use crate::timeline_children_history::{
+    preview_apply_timeline_children, record_apply_timeline_children_history,
+};
 pub(crate) use crate::timeline_command_history::{
     record_create_timeline_node_history, record_create_timeline_relationship_history,
     record_delete_timeline_relationship_history, record_set_timeline_node_lock_history,
-    record_set_timeline_node_notes_history, record_set_timeline_node_range_history,
+    record_set_timeline_node_notes_history, record_set_timeline_node_pinned_history,
+    record_set_timeline_node_range_history,
 };
+pub(crate) use crate::timeline_generation_order_history::record_set_timeline_node_generation_order_history;
+pub(crate) use crate::timeline_node_arc_history::record_set_timeline_node_arc_tag_history;
 pub(crate) use crate::timeline_node_delete_history::record_delete_timeline_node_history;
+pub(crate) use crate::timeline_node_duplicate_history::record_duplicate_timeline_node_history;
 pub(crate) use crate::timeline_node_split_history::record_split_timeline_node_history;
+pub(crate) use crate::timeline_normalize_history::record_normalize_timeline_level_history;
+pub(crate) use crate::timeline_scene_number_history::{
+    record_renumber_timeline_scenes_history, record_set_timeline_node_scene_number_history,
+};
 
 #[cfg(test)]
 pub(crate) fn apply_set_timeline_node_range(
@@ -104,9 +114,7 @@ pub(crate) fn apply_set_timeline_node_notes(
         .node_mut(command.payload.node_id)
         .map_err(TimelineCommandError::Core)?;
     node.content.notes = command.payload.notes.clone();
-    if !node.content.notes.is_empty() && node.content.status == ContentStatus::Empty {
-        node.content.status = ContentStatus::NotesOnly;
-    }
+    node.content.refresh_status();
 
     Ok(ProjectionEnvelope::initial(
         TimelineRenderProjection::from_timeline(&project.timeline),
@@ -153,7 +161,8 @@ pub(crate) fn apply_timeline_children(
     command: &CommandEnvelope<ApplyTimelineChildrenCommand>,
 ) -> Result<ProjectionEnvelope<TimelineRenderProjection>, TimelineCommandError> {
     let parent_id = command.payload.parent_id;
-    let (parent_range, child_level) = {
+    let weights: Vec<f32> = command.payload.children.iter().map(|c| c.weight).collect();
+    let (ranges, child_level) = {
         let parent = project.timeline.node(parent_id)?;
         let child_level = parent.level.child_level().ok_or_else(|| {
             eidetic_core::Error::InvalidHierarchy(format!(
@@ -161,7 +170,10 @@ pub(crate) fn apply_timeline_children(
                 parent.level
             ))
         })?;
-        (parent.time_range, child_level)
+        (
+            project.timeline.split_child_ranges(parent, &weights),
+            child_level,
+        )
     };
 
     project.timeline.clear_children_of(parent_id)?;
@@ -172,39 +184,21 @@ pub(crate) fn apply_timeline_children(
         ));
     }
 
-    let total_weight: f32 = command
-        .payload
-        .children
-        .iter()
-        .map(|child| child.weight.max(0.1))
-        .sum();
-    let parent_duration = parent_range.end_ms - parent_range.start_ms;
     let parent_arc_ids = project.timeline.arcs_for_node(parent_id);
-    let mut cursor = parent_range.start_ms;
 
-    for (index, child) in command.payload.children.iter().enumerate() {
-        let weight = child.weight.max(0.1);
-        let duration = if index == command.payload.children.len() - 1 {
-            parent_range.end_ms - cursor
-        } else {
-            ((weight / total_weight) * parent_duration as f32) as u64
-        };
-        let end_ms = (cursor + duration).min(parent_range.end_ms);
-        let time_range = TimeRange::new(cursor, end_ms)?;
+    for (index, (child, time_range)) in command.payload.children.iter().zip(ranges).enumerate() {
+        time_range.validate()?;
         let mut node = StoryNode::new_child(&child.name, child_level, time_range, parent_id);
         node.id = child.node_id;
         node.sort_order = index as u32;
         node.content.notes = child.outline.clone();
-        if !node.content.notes.is_empty() {
-            node.content.status = ContentStatus::NotesOnly;
-        }
+        node.content.refresh_status();
         node.beat_type = child.beat_type.clone();
 
         project.timeline.add_node(node)?;
         for arc_id in &parent_arc_ids {
             project.timeline.tag_node(child.node_id, *arc_id);
         }
-        cursor = end_ms;
     }
 
     Ok(ProjectionEnvelope::initial(
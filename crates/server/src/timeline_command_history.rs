@@ -1,14 +1,17 @@
 use eidetic_core::Project;
 use eidetic_core::contracts::{
-    ChangeEvent, ChangeEventKind, CommandEnvelope, CreateTimelineNodeCommand,
+    BibleGraphSnapshot, ChangeEvent, ChangeEventKind, CommandEnvelope, CreateTimelineNodeCommand,
     CreateTimelineRelationshipCommand, DeleteTimelineRelationshipCommand, FieldDelta, FieldValue,
     ObjectKind, ObjectRevision, RevisionOperation, SetTimelineNodeLockCommand,
-    SetTimelineNodeNotesCommand, SetTimelineNodeRangeCommand,
+    SetTimelineNodeNotesCommand, SetTimelineNodePinnedCommand, SetTimelineNodeRangeCommand,
 };
 use eidetic_core::timeline::node::{ContentStatus, StoryLevel, StoryNode};
+use eidetic_core::timeline::relationship::RelationshipType;
 use eidetic_core::timeline::timing::TimeRange;
 use rusqlite::Connection;
 
+use crate::bible_graph_schema;
+use crate::bible_graph_snapshot_store;
 use crate::history_store::{self, RecordChangeOutcome};
 use crate::timeline_command::TimelineCommandError;
 use crate::timeline_command_history_codec::{
@@ -42,33 +45,99 @@ pub(crate) fn record_set_timeline_node_range_history(
         format!("set timeline node range {}", node.name),
     )
     .with_created_at_ms(created_at_ms);
-    let revision = ObjectRevision::new(
-        ObjectKind::TimelineNode,
-        command.payload.node_id.0.to_string(),
-        event.id,
-        RevisionOperation::Update,
-    )
-    .with_field(FieldDelta::new(
-        "start_ms",
-        Some(FieldValue::Integer(node.time_range.start_ms as i64)),
-        Some(FieldValue::Integer(command.payload.start_ms as i64)),
-    ))
-    .with_field(FieldDelta::new(
-        "end_ms",
-        Some(FieldValue::Integer(node.time_range.end_ms as i64)),
-        Some(FieldValue::Integer(command.payload.end_ms as i64)),
-    ));
+    let mut revisions = vec![
+        ObjectRevision::new(
+            ObjectKind::TimelineNode,
+            command.payload.node_id.0.to_string(),
+            event.id,
+            RevisionOperation::Update,
+        )
+        .with_field(FieldDelta::new(
+            "start_ms",
+            Some(FieldValue::Integer(node.time_range.start_ms as i64)),
+            Some(FieldValue::Integer(command.payload.start_ms as i64)),
+        ))
+        .with_field(FieldDelta::new(
+            "end_ms",
+            Some(FieldValue::Integer(node.time_range.end_ms as i64)),
+            Some(FieldValue::Integer(command.payload.end_ms as i64)),
+        )),
+    ];
+
+    let reanchored = if command.payload.reanchor_snapshots {
+        bible_graph_schema::create_schema(conn)?;
+        reanchored_snapshot_times(conn, project, &next_timeline.nodes)?
+    } else {
+        Vec::new()
+    };
+    for (snapshot, at_ms) in &reanchored {
+        revisions.push(
+            ObjectRevision::new(
+                ObjectKind::BibleSnapshot,
+                snapshot.id.as_str(),
+                event.id,
+                RevisionOperation::Update,
+            )
+            .with_field(FieldDelta::new(
+                "at_ms",
+                Some(FieldValue::Integer(snapshot.at_ms as i64)),
+                Some(FieldValue::Integer(*at_ms as i64)),
+            )),
+        );
+    }
 
     Ok(history_store::record_change_with(
         conn,
         command,
         "timeline.node_range",
         &event,
-        &[revision],
-        |tx| timeline_node_store::upsert_nodes_in_transaction(tx, &next_timeline.nodes),
+        &revisions,
+        |tx| {
+            timeline_node_store::upsert_nodes_in_transaction(tx, &next_timeline.nodes)?;
+            for (snapshot, at_ms) in &reanchored {
+                bible_graph_snapshot_store::set_snapshot_time_in_transaction(
+                    tx,
+                    &snapshot.id,
+                    *at_ms,
+                    event.id,
+                )?;
+            }
+            Ok(())
+        },
     )?)
 }
 
+/// Snapshots anchored to nodes whose range changed, paired with the new
+/// midpoint of their source node. Snapshots already at that time are skipped.
+fn reanchored_snapshot_times(
+    conn: &Connection,
+    project: &Project,
+    next_nodes: &[StoryNode],
+) -> Result<Vec<(BibleGraphSnapshot, u64)>, TimelineCommandError> {
+    let moved: Vec<&StoryNode> = next_nodes
+        .iter()
+        .filter(|next| {
+            project
+                .timeline
+                .node(next.id)
+                .is_ok_and(|before| before.time_range != next.time_range)
+        })
+        .collect();
+    let moved_ids: Vec<_> = moved.iter().map(|node| node.id).collect();
+
+    let snapshots = bible_graph_snapshot_store::load_snapshots_for_source_nodes(conn, &moved_ids)?;
+    Ok(snapshots
+        .into_iter()
+        .filter_map(|snapshot| {
+            let source = moved
+                .iter()
+                .find(|node| Some(node.id) == snapshot.source_node_id)?;
+            let midpoint = source.time_range.start_ms + source.time_range.duration_ms() / 2;
+            (snapshot.at_ms != midpoint).then_some((snapshot, midpoint))
+        })
+        .collect())
+}
+
 pub(crate) fn record_set_timeline_node_lock_history(
     conn: &mut Connection,
     project: &Project,
@@ -112,6 +181,49 @@ pub(crate) fn record_set_timeline_node_lock_history(
     )?)
 }
 
+pub(crate) fn record_set_timeline_node_pinned_history(
+    conn: &mut Connection,
+    project: &Project,
+    command: &CommandEnvelope<SetTimelineNodePinnedCommand>,
+    created_at_ms: u64,
+) -> Result<RecordChangeOutcome, TimelineCommandError> {
+    if let Some(outcome) =
+        history_store::check_recorded_command(conn, command, "timeline.node_pin")?
+    {
+        return Ok(outcome);
+    }
+
+    let node = project.timeline.node(command.payload.node_id)?;
+    let event = ChangeEvent::new(
+        command.id,
+        ChangeEventKind::UserEdit,
+        format!("set timeline node pin {}", node.name),
+    )
+    .with_created_at_ms(created_at_ms);
+    let revision = ObjectRevision::new(
+        ObjectKind::TimelineNode,
+        command.payload.node_id.0.to_string(),
+        event.id,
+        RevisionOperation::Update,
+    )
+    .with_field(FieldDelta::new(
+        "pinned",
+        Some(FieldValue::Bool(node.pinned)),
+        Some(FieldValue::Bool(command.payload.pinned)),
+    ));
+    let mut next_timeline = project.timeline.clone();
+    next_timeline.node_mut(command.payload.node_id)?.pinned = command.payload.pinned;
+
+    Ok(history_store::record_change_with(
+        conn,
+        command,
+        "timeline.node_pin",
+        &event,
+        &[revision],
+        |tx| timeline_node_store::upsert_nodes_in_transaction(tx, &next_timeline.nodes),
+    )?)
+}
+
 pub(crate) fn record_set_timeline_node_notes_history(
     conn: &mut Connection,
     project: &Project,
@@ -131,12 +243,10 @@ pub(crate) fn record_set_timeline_node_notes_history(
         format!("set timeline node notes {}", node.name),
     )
     .with_created_at_ms(created_at_ms);
-    let new_status =
-        if !command.payload.notes.is_empty() && node.content.status == ContentStatus::Empty {
-            ContentStatus::NotesOnly
-        } else {
-            node.content.status
-        };
+    let mut next_content = node.content.clone();
+    next_content.notes = command.payload.notes.clone();
+    next_content.refresh_status();
+    let new_status = next_content.status;
     let mut revision = ObjectRevision::new(
         ObjectKind::TimelineNode,
         command.payload.node_id.0.to_string(),
@@ -192,6 +302,11 @@ pub(crate) fn record_create_timeline_relationship_history(
             ),
         ));
     }
+    if command.payload.relationship_type == RelationshipType::Payoff {
+        project
+            .timeline
+            .validate_payoff(command.payload.from_node_id, command.payload.to_node_id)?;
+    }
     if project
         .timeline
         .relationships
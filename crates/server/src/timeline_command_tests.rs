@@ -1,12 +1,14 @@
 use eidetic_core::Template;
 use eidetic_core::contracts::{
-    ApplyTimelineChildCommand, ApplyTimelineChildrenCommand, CommandEnvelope, CommandId,
-    CreateTimelineNodeCommand, CreateTimelineRelationshipCommand, DeleteTimelineNodeCommand,
+    ApplyTimelineChildCommand, ApplyTimelineChildrenCommand, BibleGraphNodeId, BibleGraphSchemaKey,
+    CommandEnvelope, CommandId, CreateBibleGraphNodeCommand, CreateTimelineNodeCommand,
+    CreateTimelineRelationshipCommand, DeleteTimelineNodeCommand,
     DeleteTimelineRelationshipCommand, SetTimelineNodeLockCommand, SetTimelineNodeNotesCommand,
     SetTimelineNodeRangeCommand, SplitTimelineNodeCommand,
 };
-use eidetic_core::timeline::node::{ContentStatus, NodeId};
+use eidetic_core::timeline::node::{ContentStatus, NodeId, StoryLevel, StoryNode};
 use eidetic_core::timeline::relationship::{Relationship, RelationshipId, RelationshipType};
+use eidetic_core::timeline::timing::distribute_weighted_ranges;
 
 use crate::timeline_command::{
     apply_create_timeline_node, apply_create_timeline_relationship, apply_delete_timeline_node,
@@ -25,6 +27,7 @@ fn set_timeline_node_range_updates_projection() {
             node_id,
             start_ms: 1_000,
             end_ms: 2_000,
+            reanchor_snapshots: false,
         },
     };
 
@@ -50,6 +53,7 @@ fn set_timeline_node_range_rejects_invalid_range() {
             node_id,
             start_ms: 2_000,
             end_ms: 1_000,
+            reanchor_snapshots: false,
         },
     };
 
@@ -102,6 +106,124 @@ fn split_timeline_node_returns_projection_without_original_node() {
     );
 }
 
+#[test]
+fn split_timeline_node_keeps_content_on_left_node() {
+    let mut project = Template::MultiCam.build_project("Timeline Command Test");
+    let node_id = project.timeline.nodes[0].id;
+    {
+        let node = project.timeline.node_mut(node_id).unwrap();
+        node.content.notes = "Original notes".into();
+        node.content.scene_recap = Some("Recap".into());
+    }
+    let node = project.timeline.node(node_id).unwrap().clone();
+    let left_node_id = NodeId::new();
+    let right_node_id = NodeId::new();
+    let command = CommandEnvelope {
+        id: CommandId::new(),
+        payload: SplitTimelineNodeCommand {
+            node_id,
+            at_ms: node.time_range.start_ms + node.time_range.duration_ms() / 2,
+            left_node_id,
+            right_node_id,
+        },
+    };
+
+    apply_split_timeline_node(&mut project, &command).unwrap();
+
+    let left = project.timeline.node(left_node_id).unwrap();
+    assert_eq!(left.content.notes, "Original notes");
+    assert_eq!(left.content.scene_recap.as_deref(), Some("Recap"));
+    let right = project.timeline.node(right_node_id).unwrap();
+    assert!(right.content.notes.is_empty());
+    assert!(right.content.scene_recap.is_none());
+}
+
+fn split_command(
+    node: &StoryNode,
+    left_node_id: NodeId,
+    right_node_id: NodeId,
+) -> CommandEnvelope<SplitTimelineNodeCommand> {
+    CommandEnvelope {
+        id: CommandId::new(),
+        payload: SplitTimelineNodeCommand {
+            node_id: node.id,
+            at_ms: node.time_range.start_ms + node.time_range.duration_ms() / 2,
+            left_node_id,
+            right_node_id,
+        },
+    }
+}
+
+#[test]
+fn split_timeline_node_repoints_relationships_by_time() {
+    let mut project = Template::MultiCam.build_project("Timeline Command Test");
+    let act_two = project
+        .timeline
+        .nodes
+        .iter()
+        .find(|node| node.level == StoryLevel::Act && node.name == "Act Two")
+        .unwrap()
+        .clone();
+    let cold_open = project.timeline.nodes[0].id;
+    let tag = project
+        .timeline
+        .nodes
+        .iter()
+        .find(|node| node.level == StoryLevel::Act && node.name == "Tag")
+        .unwrap()
+        .id;
+    let earlier = Relationship::new(act_two.id, cold_open, RelationshipType::Causal);
+    let later = Relationship::new(tag, act_two.id, RelationshipType::Causal);
+    let (earlier_id, later_id) = (earlier.id, later.id);
+    project.timeline.add_relationship(earlier).unwrap();
+    project.timeline.add_relationship(later).unwrap();
+    let (left_node_id, right_node_id) = (NodeId::new(), NodeId::new());
+
+    apply_split_timeline_node(
+        &mut project,
+        &split_command(&act_two, left_node_id, right_node_id),
+    )
+    .unwrap();
+
+    let relationship = |id| {
+        project
+            .timeline
+            .relationships
+            .iter()
+            .find(|relationship| relationship.id == id)
+            .unwrap()
+    };
+    assert_eq!(relationship(earlier_id).from_node, left_node_id);
+    assert_eq!(relationship(earlier_id).to_node, cold_open);
+    assert_eq!(relationship(later_id).from_node, tag);
+    assert_eq!(relationship(later_id).to_node, right_node_id);
+}
+
+#[test]
+fn split_timeline_node_keeps_self_relationship_on_left_node() {
+    let mut project = Template::MultiCam.build_project("Timeline Command Test");
+    let node = project.timeline.nodes[0].clone();
+    let relationship = Relationship::new(node.id, node.id, RelationshipType::Thematic);
+    let relationship_id = relationship.id;
+    project.timeline.add_relationship(relationship).unwrap();
+    let (left_node_id, right_node_id) = (NodeId::new(), NodeId::new());
+
+    apply_split_timeline_node(
+        &mut project,
+        &split_command(&node, left_node_id, right_node_id),
+    )
+    .unwrap();
+
+    let relationship = project
+        .timeline
+        .relationships
+        .iter()
+        .find(|relationship| relationship.id == relationship_id)
+        .unwrap();
+    assert_eq!(relationship.from_node, left_node_id);
+    assert_eq!(relationship.to_node, left_node_id);
+}
+
 #[test]
 fn delete_timeline_node_returns_projection_without_deleted_subtree() {
     let mut project = Template::MultiCam.build_project("Timeline Command Test");
@@ -277,6 +399,7 @@ fn apply_timeline_children_replaces_existing_children() {
                     props: Vec::new(),
                 },
             ],
+            preserve_existing: false,
         },
     };
 
@@ -367,3 +490,202 @@ fn delete_timeline_relationship_returns_projection_without_relationship() {
             .all(|relationship| relationship.relationship_id != relationship_id)
     );
 }
+
+#[test]
+fn apply_timeline_children_preserving_existing_fills_only_gaps() {
+    let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+    crate::history_store::create_schema(&conn).unwrap();
+    crate::timeline_node_store::create_node_schema(&conn).unwrap();
+    let mut project = Template::MultiCam.build_project("Timeline Command Test");
+    let parent = project.timeline.nodes_at_level(StoryLevel::Act)[0].clone();
+    project.timeline.clear_children_of(parent.id).unwrap();
+    let child_level = parent.level.child_level().unwrap();
+    let ranges = distribute_weighted_ranges(parent.time_range, &[1.0, 1.0, 1.0]);
+    let mut kept = Vec::new();
+    for (index, range) in [ranges[0], ranges[2]].into_iter().enumerate() {
+        let mut child = StoryNode::new_child("Written scene", child_level, range, parent.id);
+        child.sort_order = index as u32;
+        kept.push(child.id);
+        project.timeline.add_node(child).unwrap();
+    }
+    let new_child_id = NodeId::new();
+    let command = CommandEnvelope {
+        id: CommandId::new(),
+        payload: ApplyTimelineChildrenCommand {
+            parent_id: parent.id,
+            child_plan_id: None,
+            children: vec![ApplyTimelineChildCommand {
+                node_id: new_child_id,
+                name: "Gap filler".to_string(),
+                outline: "Bridges the written scenes".to_string(),
+                weight: 1.0,
+                beat_type: None,
+                characters: Vec::new(),
+                location: None,
+                props: Vec::new(),
+            }],
+            preserve_existing: true,
+        },
+    };
+
+    crate::timeline_command::record_apply_timeline_children_history(
+        &mut conn, &project, &command, 0,
+    )
+    .unwrap();
+
+    let nodes = crate::timeline_node_store::load_nodes(&conn).unwrap();
+    let sort_order = |id: NodeId| nodes.iter().find(|node| node.id == id).unwrap().sort_order;
+    let filler = nodes.iter().find(|node| node.id == new_child_id).unwrap();
+    assert_eq!(filler.time_range, ranges[1]);
+    assert_eq!(
+        [
+            sort_order(kept[0]),
+            sort_order(new_child_id),
+            sort_order(kept[1])
+        ],
+        [0, 1, 2]
+    );
+
+    let mut covered = project.clone();
+    covered.timeline.add_node(filler.clone()).unwrap();
+    let mut again = command.clone();
+    again.id = CommandId::new();
+    again.payload.children[0].node_id = NodeId::new();
+    assert!(
+        crate::timeline_command::record_apply_timeline_children_history(
+            &mut conn, &covered, &again, 0,
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn preview_apply_timeline_children_reports_ranges_and_entity_links_without_writing() {
+    let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+    crate::history_store::create_schema(&conn).unwrap();
+    crate::timeline_node_store::create_node_schema(&conn).unwrap();
+    crate::bible_graph_command::apply_create_bible_graph_node(
+        &mut conn,
+        &CommandEnvelope::new(CreateBibleGraphNodeCommand {
+            node_id: BibleGraphNodeId::new("node.character.ada").unwrap(),
+            parent_id: None,
+            schema_key: BibleGraphSchemaKey::new("character").unwrap(),
+            name: "Ada".to_string(),
+            sort_order: 0,
+        }),
+        0,
+    )
+    .unwrap();
+    let project = Template::MultiCam.build_project("Timeline Command Test");
+    let parent = project.timeline.nodes_at_level(StoryLevel::Act)[0].clone();
+    let existing_children = project.timeline.children_of(parent.id).len();
+    let child = |name: &str, characters: &[&str]| ApplyTimelineChildCommand {
+        node_id: NodeId::new(),
+        name: name.to_string(),
+        outline: String::new(),
+        weight: 1.0,
+        beat_type: None,
+        characters: characters.iter().map(|name| name.to_string()).collect(),
+        location: None,
+        props: Vec::new(),
+    };
+    let command = CommandEnvelope {
+        id: CommandId::new(),
+        payload: ApplyTimelineChildrenCommand {
+            parent_id: parent.id,
+            child_plan_id: None,
+            children: vec![
+                child("Arrival", &[" ada ", "Bram"]),
+                child("Departure", &[]),
+            ],
+            preserve_existing: false,
+        },
+    };
+
+    let preview =
+        crate::timeline_command::preview_apply_timeline_children(&conn, &project, &command)
+            .unwrap();
+
+    let ranges = distribute_weighted_ranges(parent.time_range, &[1.0, 1.0]);
+    let previewed: Vec<_> = preview
+        .children
+        .iter()
+        .map(|child| (child.name.as_str(), child.start_ms, child.end_ms))
+        .collect();
+    assert_eq!(
+        previewed,
+        [
+            ("Arrival", ranges[0].start_ms, ranges[0].end_ms),
+            ("Departure", ranges[1].start_ms, ranges[1].end_ms),
+        ]
+    );
+    assert!(preview.removed_node_ids.len() >= existing_children);
+    let links: Vec<_> = preview
+        .bible_references
+        .iter()
+        .map(|reference| {
+            (
+                reference.reference_text.as_str(),
+                reference.existing_node_id.as_ref().map(|id| id.as_str()),
+            )
+        })
+        .collect();
+    assert_eq!(links, [("ada", Some("node.character.ada")), ("Bram", None)]);
+    assert!(
+        crate::timeline_node_store::load_nodes(&conn)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn apply_timeline_children_snaps_premise_acts_to_structure_segments() {
+    let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+    crate::history_store::create_schema(&conn).unwrap();
+    crate::timeline_node_store::create_node_schema(&conn).unwrap();
+    let mut project = Template::MultiCam.build_project("Timeline Command Test");
+    let premise = project.timeline.nodes_at_level(StoryLevel::Premise)[0].clone();
+    project.timeline.clear_children_of(premise.id).unwrap();
+    let segments: Vec<_> = project
+        .timeline
+        .structure
+        .act_segments()
+        .map(|segment| segment.time_range)
+        .collect();
+    let children: Vec<ApplyTimelineChildCommand> = segments
+        .iter()
+        .enumerate()
+        .map(|(index, _)| ApplyTimelineChildCommand {
+            node_id: NodeId::new(),
+            name: format!("Act {index}"),
+            outline: String::new(),
+            weight: if index == 0 { 5.0 } else { 1.0 },
+            beat_type: None,
+            characters: Vec::new(),
+            location: None,
+            props: Vec::new(),
+        })
+        .collect();
+    let act_ids: Vec<NodeId> = children.iter().map(|child| child.node_id).collect();
+    let command = CommandEnvelope {
+        id: CommandId::new(),
+        payload: ApplyTimelineChildrenCommand {
+            parent_id: premise.id,
+            child_plan_id: None,
+            children,
+            preserve_existing: false,
+        },
+    };
+
+    crate::timeline_command::record_apply_timeline_children_history(
+        &mut conn, &project, &command, 0,
+    )
+    .unwrap();
+
+    let nodes = crate::timeline_node_store::load_nodes(&conn).unwrap();
+    let ranges: Vec<_> = act_ids
+        .iter()
+        .map(|id| nodes.iter().find(|node| node.id == *id).unwrap().time_range)
+        .collect();
+    assert_eq!(ranges, segments);
+}
@@ -0,0 +1,136 @@
+use eidetic_core::Project;
+use eidetic_core::contracts::{
+    ChangeEvent, ChangeEventKind, CommandEnvelope, FieldDelta, FieldValue, ObjectKind,
+    ObjectRevision, RevisionOperation, SetTimelineNodeGenerationOrderCommand,
+};
+use eidetic_core::timeline::node::NodeId;
+use rusqlite::Connection;
+
+use crate::history_store::{self, RecordChangeOutcome};
+use crate::timeline_command::TimelineCommandError;
+use crate::timeline_node_store;
+
+pub(crate) fn record_set_timeline_node_generation_order_history(
+    conn: &mut Connection,
+    project: &Project,
+    command: &CommandEnvelope<SetTimelineNodeGenerationOrderCommand>,
+    created_at_ms: u64,
+) -> Result<RecordChangeOutcome, TimelineCommandError> {
+    if let Some(outcome) =
+        history_store::check_recorded_command(conn, command, "timeline.node_generation_order")?
+    {
+        return Ok(outcome);
+    }
+
+    let payload = &command.payload;
+    let mut depends_on = payload.depends_on.clone();
+    depends_on.dedup();
+    project
+        .timeline
+        .validate_generation_dependencies(payload.node_id, &depends_on)?;
+    let node = project.timeline.node(payload.node_id)?;
+    let event = ChangeEvent::new(
+        command.id,
+        ChangeEventKind::UserEdit,
+        format!("set generation order {}", node.name),
+    )
+    .with_created_at_ms(created_at_ms);
+    let revision = ObjectRevision::new(
+        ObjectKind::TimelineNode,
+        payload.node_id.0.to_string(),
+        event.id,
+        RevisionOperation::Update,
+    )
+    .with_field(FieldDelta::new(
+        "generation_priority",
+        node.content
+            .generation_priority
+            .map(|priority| FieldValue::Integer(priority.into())),
+        payload
+            .generation_priority
+            .map(|priority| FieldValue::Integer(priority.into())),
+    ))
+    .with_field(FieldDelta::new(
+        "depends_on",
+        encode_node_ids(&node.content.depends_on),
+        encode_node_ids(&depends_on),
+    ));
+    let mut next_timeline = project.timeline.clone();
+    let content = &mut next_timeline.node_mut(payload.node_id)?.content;
+    content.generation_priority = payload.generation_priority;
+    content.depends_on = depends_on;
+
+    Ok(history_store::record_change_with(
+        conn,
+        command,
+        "timeline.node_generation_order",
+        &event,
+        &[revision],
+        |tx| timeline_node_store::upsert_nodes_in_transaction(tx, &next_timeline.nodes),
+    )?)
+}
+
+/// Comma-separated node ids; `None` for an empty list.
+fn encode_node_ids(node_ids: &[NodeId]) -> Option<FieldValue> {
+    (!node_ids.is_empty()).then(|| {
+        FieldValue::Text(
+            node_ids
+                .iter()
+                .map(|id| id.0.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::record_set_timeline_node_generation_order_history;
+    use crate::history_store::{self, RecordChangeOutcome};
+    use crate::timeline_command::TimelineCommandError;
+    use crate::timeline_node_store;
+    use eidetic_core::Template;
+    use eidetic_core::contracts::{CommandEnvelope, SetTimelineNodeGenerationOrderCommand};
+    use eidetic_core::timeline::node::StoryLevel;
+    use rusqlite::Connection;
+
+    #[test]
+    fn records_generation_order_and_rejects_cycles() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        history_store::create_schema(&conn).unwrap();
+        let mut project = Template::MultiCam.build_project("Generation Order");
+        let scenes: Vec<_> = project
+            .timeline
+            .nodes_at_level(StoryLevel::Scene)
+            .iter()
+            .take(2)
+            .map(|node| node.id)
+            .collect();
+        let command = CommandEnvelope::new(SetTimelineNodeGenerationOrderCommand {
+            node_id: scenes[0],
+            generation_priority: Some(2),
+            depends_on: vec![scenes[1]],
+        });
+
+        let outcome =
+            record_set_timeline_node_generation_order_history(&mut conn, &project, &command, 0)
+                .unwrap();
+
+        assert_eq!(outcome, RecordChangeOutcome::Recorded);
+        let nodes = timeline_node_store::load_nodes(&conn).unwrap();
+        let saved = nodes.iter().find(|node| node.id == scenes[0]).unwrap();
+        assert_eq!(saved.content.generation_priority, Some(2));
+        assert_eq!(saved.content.depends_on, vec![scenes[1]]);
+
+        project.timeline.nodes = nodes;
+        let cycle = CommandEnvelope::new(SetTimelineNodeGenerationOrderCommand {
+            node_id: scenes[1],
+            generation_priority: None,
+            depends_on: vec![scenes[0]],
+        });
+        let error =
+            record_set_timeline_node_generation_order_history(&mut conn, &project, &cycle, 0)
+                .unwrap_err();
+        assert!(matches!(error, TimelineCommandError::Core(_)));
+    }
+}
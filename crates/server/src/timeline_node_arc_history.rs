@@ -0,0 +1,87 @@
+use eidetic_core::Project;
+use eidetic_core::contracts::{
+    ChangeEvent, ChangeEventKind, CommandEnvelope, FieldDelta, FieldValue, ObjectKind,
+    ObjectRevision, RevisionOperation, SetTimelineNodeArcTagCommand,
+};
+use rusqlite::Connection;
+
+use crate::history_store::{self, RecordChangeOutcome};
+use crate::timeline_command::TimelineCommandError;
+use crate::timeline_command_history_codec::encode_arc_ids;
+use crate::timeline_node_store;
+
+/// Records one `arc_ids` revision per node whose tags change.
+pub(crate) fn record_set_timeline_node_arc_tag_history(
+    conn: &mut Connection,
+    project: &Project,
+    command: &CommandEnvelope<SetTimelineNodeArcTagCommand>,
+    created_at_ms: u64,
+) -> Result<RecordChangeOutcome, TimelineCommandError> {
+    if let Some(outcome) =
+        history_store::check_recorded_command(conn, command, "timeline.node_arc_tag")?
+    {
+        return Ok(outcome);
+    }
+
+    let payload = &command.payload;
+    let node = project.timeline.node(payload.node_id)?;
+    let arc = project
+        .arcs
+        .iter()
+        .find(|arc| arc.id == payload.arc_id)
+        .ok_or(eidetic_core::Error::ArcNotFound(payload.arc_id.0))?;
+    let mut next_timeline = project.timeline.clone();
+    let changed = if payload.recursive {
+        if payload.tagged {
+            next_timeline.tag_node_recursive(node.id, arc.id)?
+        } else {
+            next_timeline.untag_node_recursive(node.id, arc.id)?
+        }
+    } else if payload.tagged != project.timeline.arcs_for_node(node.id).contains(&arc.id) {
+        if payload.tagged {
+            next_timeline.tag_node(node.id, arc.id);
+        } else {
+            next_timeline.untag_node(node.id, arc.id);
+        }
+        vec![node.id]
+    } else {
+        Vec::new()
+    };
+
+    let verb = if payload.tagged { "tag" } else { "untag" };
+    let event = ChangeEvent::new(
+        command.id,
+        ChangeEventKind::UserEdit,
+        format!("{verb} {} with arc {}", node.name, arc.name),
+    )
+    .with_created_at_ms(created_at_ms);
+    let revisions = changed
+        .iter()
+        .map(|&node_id| {
+            Ok(ObjectRevision::new(
+                ObjectKind::TimelineNode,
+                node_id.0.to_string(),
+                event.id,
+                RevisionOperation::Update,
+            )
+            .with_field(FieldDelta::new(
+                "arc_ids",
+                Some(FieldValue::Text(encode_arc_ids(
+                    &project.timeline.arcs_for_node(node_id),
+                )?)),
+                Some(FieldValue::Text(encode_arc_ids(
+                    &next_timeline.arcs_for_node(node_id),
+                )?)),
+            )))
+        })
+        .collect::<Result<Vec<_>, TimelineCommandError>>()?;
+
+    Ok(history_store::record_change_with(
+        conn,
+        command,
+        "timeline.node_arc_tag",
+        &event,
+        &revisions,
+        |tx| timeline_node_store::replace_node_arcs_in_transaction(tx, &next_timeline.node_arcs),
+    )?)
+}
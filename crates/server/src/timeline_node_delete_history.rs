@@ -165,7 +165,7 @@ fn deleted_node_revision(
     Ok(revision)
 }
 
-fn deleted_relationship_revision(
+pub(crate) fn deleted_relationship_revision(
     relationship: &Relationship,
     event_id: ChangeEventId,
 ) -> Result<ObjectRevision, TimelineCommandError> {
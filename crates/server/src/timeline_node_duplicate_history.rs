@@ -0,0 +1,228 @@
+use eidetic_core::Project;
+use eidetic_core::contracts::{
+    ChangeEvent, ChangeEventId, ChangeEventKind, CommandEnvelope, DuplicateTimelineNodeCommand,
+    FieldDelta, FieldValue, ObjectKind, ObjectRevision, RevisionOperation,
+};
+use eidetic_core::timeline::node::{NodeId, StoryNode};
+use eidetic_core::timeline::relationship::Relationship;
+use rusqlite::Connection;
+
+use crate::command_service_support::derived_command_uuid;
+use crate::history_store::{self, RecordChangeOutcome};
+use crate::timeline_command::TimelineCommandError;
+use crate::timeline_command_history_codec::{
+    encode_arc_ids, encode_beat_type, encode_content_status, encode_relationship_type,
+    encode_story_level,
+};
+use crate::timeline_node_store;
+use crate::timeline_relationship_store;
+
+/// Records a node duplication and returns `(source, copy)` node id pairs,
+/// the duplicated node first. Copy ids are derived from the command id so a
+/// replayed command maps to the same nodes.
+pub(crate) fn record_duplicate_timeline_node_history(
+    conn: &mut Connection,
+    project: &Project,
+    command: &CommandEnvelope<DuplicateTimelineNodeCommand>,
+    created_at_ms: u64,
+) -> Result<(RecordChangeOutcome, Vec<(NodeId, NodeId)>), TimelineCommandError> {
+    let node = project.timeline.node(command.payload.node_id)?;
+    if let Some(outcome) =
+        history_store::check_recorded_command(conn, command, "timeline.node_duplicate")?
+    {
+        return Ok((outcome, recorded_duplicate_pairs(project, command, node)));
+    }
+
+    let offset_ms = command
+        .payload
+        .offset_ms
+        .unwrap_or(node.time_range.duration_ms() as i64);
+    let mut next_timeline = project.timeline.clone();
+    let pairs = next_timeline.duplicate_node(
+        command.payload.node_id,
+        command.payload.recursive,
+        offset_ms,
+        |source| duplicate_id(command, source),
+    )?;
+
+    let event = ChangeEvent::new(
+        command.id,
+        ChangeEventKind::UserEdit,
+        format!("duplicate timeline node {}", node.name),
+    )
+    .with_created_at_ms(created_at_ms);
+    let mut revisions = Vec::with_capacity(pairs.len());
+    for (_, copy_id) in &pairs {
+        let copy = next_timeline.node(*copy_id)?;
+        revisions.push(duplicated_node_revision(
+            copy,
+            &next_timeline.arcs_for_node(copy.id),
+            event.id,
+        )?);
+    }
+    let relationships: Vec<Relationship> = next_timeline
+        .relationships
+        .iter()
+        .filter(|relationship| {
+            !project
+                .timeline
+                .relationships
+                .iter()
+                .any(|existing| existing.id == relationship.id)
+        })
+        .cloned()
+        .collect();
+    for relationship in &relationships {
+        revisions.push(duplicated_relationship_revision(relationship, event.id)?);
+    }
+
+    let copies: Vec<StoryNode> = pairs
+        .iter()
+        .map(|(_, copy_id)| next_timeline.node(*copy_id).cloned())
+        .collect::<Result<_, _>>()?;
+    let outcome = history_store::record_change_with(
+        conn,
+        command,
+        "timeline.node_duplicate",
+        &event,
+        &revisions,
+        |tx| {
+            timeline_node_store::upsert_nodes_in_transaction(tx, &copies)?;
+            timeline_node_store::replace_node_arcs_in_transaction(tx, &next_timeline.node_arcs)?;
+            timeline_relationship_store::upsert_relationships_in_transaction(tx, &relationships)
+        },
+    )?;
+    Ok((outcome, pairs))
+}
+
+/// The pairs a previously recorded duplication produced.
+fn recorded_duplicate_pairs(
+    project: &Project,
+    command: &CommandEnvelope<DuplicateTimelineNodeCommand>,
+    node: &StoryNode,
+) -> Vec<(NodeId, NodeId)> {
+    let mut sources = vec![node];
+    if command.payload.recursive {
+        sources.extend(project.timeline.descendants_of(node.id));
+    }
+    sources
+        .into_iter()
+        .map(|source| (source.id, NodeId(duplicate_id(command, source.id.0))))
+        .collect()
+}
+
+fn duplicate_id(
+    command: &CommandEnvelope<DuplicateTimelineNodeCommand>,
+    source: uuid::Uuid,
+) -> uuid::Uuid {
+    derived_command_uuid(
+        command.id,
+        &[b"timeline.duplicate".as_slice(), source.as_bytes()].concat(),
+    )
+}
+
+fn duplicated_node_revision(
+    node: &StoryNode,
+    arc_ids: &[eidetic_core::story::arc::ArcId],
+    event_id: ChangeEventId,
+) -> Result<ObjectRevision, TimelineCommandError> {
+    let mut revision = ObjectRevision::new(
+        ObjectKind::TimelineNode,
+        node.id.0.to_string(),
+        event_id,
+        RevisionOperation::Create,
+    )
+    .with_field(FieldDelta::new(
+        "name",
+        None,
+        Some(FieldValue::Text(node.name.clone())),
+    ))
+    .with_field(FieldDelta::new(
+        "parent_id",
+        None,
+        node.parent_id
+            .map(|parent_id| FieldValue::Text(parent_id.0.to_string())),
+    ))
+    .with_field(FieldDelta::new(
+        "level",
+        None,
+        Some(FieldValue::Text(encode_story_level(node.level))),
+    ))
+    .with_field(FieldDelta::new(
+        "start_ms",
+        None,
+        Some(FieldValue::Integer(node.time_range.start_ms as i64)),
+    ))
+    .with_field(FieldDelta::new(
+        "end_ms",
+        None,
+        Some(FieldValue::Integer(node.time_range.end_ms as i64)),
+    ))
+    .with_field(FieldDelta::new(
+        "sort_order",
+        None,
+        Some(FieldValue::Integer(node.sort_order as i64)),
+    ))
+    .with_field(FieldDelta::new(
+        "locked",
+        None,
+        Some(FieldValue::Bool(node.locked)),
+    ))
+    .with_field(FieldDelta::new(
+        "content_status",
+        None,
+        Some(FieldValue::Text(encode_content_status(node.content.status))),
+    ));
+
+    if !node.content.notes.is_empty() {
+        revision = revision.with_field(FieldDelta::new(
+            "notes",
+            None,
+            Some(FieldValue::Text(node.content.notes.clone())),
+        ));
+    }
+    if let Some(beat_type) = &node.beat_type {
+        revision = revision.with_field(FieldDelta::new(
+            "beat_type",
+            None,
+            Some(FieldValue::Text(encode_beat_type(beat_type)?)),
+        ));
+    }
+    if !arc_ids.is_empty() {
+        revision = revision.with_field(FieldDelta::new(
+            "arc_ids",
+            None,
+            Some(FieldValue::Text(encode_arc_ids(arc_ids)?)),
+        ));
+    }
+    Ok(revision)
+}
+
+fn duplicated_relationship_revision(
+    relationship: &Relationship,
+    event_id: ChangeEventId,
+) -> Result<ObjectRevision, TimelineCommandError> {
+    Ok(ObjectRevision::new(
+        ObjectKind::TimelineRelationship,
+        relationship.id.0.to_string(),
+        event_id,
+        RevisionOperation::Create,
+    )
+    .with_field(FieldDelta::new(
+        "from_node_id",
+        None,
+        Some(FieldValue::Text(relationship.from_node.0.to_string())),
+    ))
+    .with_field(FieldDelta::new(
+        "to_node_id",
+        None,
+        Some(FieldValue::Text(relationship.to_node.0.to_string())),
+    ))
+    .with_field(FieldDelta::new(
+        "relationship_type",
+        None,
+        Some(FieldValue::Text(encode_relationship_type(
+            &relationship.relationship_type,
+        )?)),
+    )))
+}
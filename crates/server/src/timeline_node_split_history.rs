@@ -13,6 +13,7 @@ use crate::timeline_command::TimelineCommandError;
 use crate::timeline_command_history_codec::{
     encode_arc_ids, encode_beat_type, encode_content_status, encode_story_level,
 };
+use crate::timeline_node_delete_history::deleted_relationship_revision;
 use crate::timeline_node_store;
 use crate::timeline_relationship_store;
 
@@ -49,6 +50,8 @@ pub(crate) fn record_split_timeline_node_history(
             node.sort_order,
             node.locked,
             node.beat_type.as_ref(),
+            &node.content.notes,
+            node.content.status,
             &arc_ids,
             event.id,
         )?,
@@ -62,6 +65,8 @@ pub(crate) fn record_split_timeline_node_history(
             node.sort_order + 1,
             node.locked,
             node.beat_type.as_ref(),
+            "",
+            ContentStatus::Empty,
             &arc_ids,
             event.id,
         )?,
@@ -75,15 +80,6 @@ pub(crate) fn record_split_timeline_node_history(
             event.id,
         ));
     }
-    for relationship in &project.timeline.relationships {
-        if relationship.from_node == node.id || relationship.to_node == node.id {
-            revisions.push(split_relationship_revision(
-                relationship,
-                command,
-                event.id,
-            )?);
-        }
-    }
     let mut next_timeline = project.timeline.clone();
     next_timeline.split_node(
         command.payload.node_id,
@@ -91,6 +87,27 @@ pub(crate) fn record_split_timeline_node_history(
         command.payload.left_node_id,
         command.payload.right_node_id,
     )?;
+    let mut dropped_relationship_ids = Vec::new();
+    for relationship in &project.timeline.relationships {
+        if relationship.from_node != node.id && relationship.to_node != node.id {
+            continue;
+        }
+        match next_timeline
+            .relationships
+            .iter()
+            .find(|next| next.id == relationship.id)
+        {
+            Some(next) => revisions.push(split_relationship_revision(
+                relationship,
+                (next.from_node, next.to_node),
+                event.id,
+            )?),
+            None => {
+                dropped_relationship_ids.push(relationship.id);
+                revisions.push(deleted_relationship_revision(relationship, event.id)?);
+            }
+        }
+    }
 
     Ok(history_store::record_change_with(
         conn,
@@ -99,6 +116,10 @@ pub(crate) fn record_split_timeline_node_history(
         &event,
         &revisions,
         |tx| {
+            timeline_relationship_store::delete_relationships_in_transaction(
+                tx,
+                &dropped_relationship_ids,
+            )?;
             timeline_node_store::delete_nodes_in_transaction(tx, &[command.payload.node_id])?;
             timeline_node_store::upsert_nodes_in_transaction(tx, &next_timeline.nodes)?;
             timeline_node_store::replace_node_arcs_in_transaction(tx, &next_timeline.node_arcs)?;
@@ -228,6 +249,8 @@ fn split_created_node_revision(
     sort_order: u32,
     locked: bool,
     beat_type: Option<&BeatType>,
+    notes: &str,
+    content_status: ContentStatus,
     arc_ids: &[ArcId],
     event_id: ChangeEventId,
 ) -> Result<ObjectRevision, TimelineCommandError> {
@@ -271,11 +294,16 @@ fn split_created_node_revision(
     .with_field(FieldDelta::new(
         "content_status",
         None,
-        Some(FieldValue::Text(encode_content_status(
-            ContentStatus::Empty,
-        ))),
+        Some(FieldValue::Text(encode_content_status(content_status))),
     ));
 
+    if !notes.is_empty() {
+        revision = revision.with_field(FieldDelta::new(
+            "notes",
+            None,
+            Some(FieldValue::Text(notes.to_string())),
+        ));
+    }
     if let Some(beat_type) = beat_type {
         revision = revision.with_field(FieldDelta::new(
             "beat_type",
@@ -327,7 +355,7 @@ fn split_child_reparent_revision(
 
 fn split_relationship_revision(
     relationship: &Relationship,
-    command: &CommandEnvelope<SplitTimelineNodeCommand>,
+    (from_node, to_node): (NodeId, NodeId),
     event_id: ChangeEventId,
 ) -> Result<ObjectRevision, TimelineCommandError> {
     let mut revision = ObjectRevision::new(
@@ -337,20 +365,18 @@ fn split_relationship_revision(
         RevisionOperation::Update,
     );
 
-    if relationship.from_node == command.payload.node_id {
+    if relationship.from_node != from_node {
         revision = revision.with_field(FieldDelta::new(
             "from_node_id",
             Some(FieldValue::Text(relationship.from_node.0.to_string())),
-            Some(FieldValue::Text(command.payload.left_node_id.0.to_string())),
+            Some(FieldValue::Text(from_node.0.to_string())),
         ));
     }
-    if relationship.to_node == command.payload.node_id {
+    if relationship.to_node != to_node {
         revision = revision.with_field(FieldDelta::new(
             "to_node_id",
             Some(FieldValue::Text(relationship.to_node.0.to_string())),
-            Some(FieldValue::Text(
-                command.payload.right_node_id.0.to_string(),
-            )),
+            Some(FieldValue::Text(to_node.0.to_string())),
         ));
     }
 
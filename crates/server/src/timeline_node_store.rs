@@ -19,7 +19,9 @@ CREATE TABLE IF NOT EXISTS nodes (
     name         TEXT NOT NULL,
     content_json TEXT NOT NULL DEFAULT '{}',
     beat_type    TEXT,
-    locked       INTEGER NOT NULL DEFAULT 0
+    locked       INTEGER NOT NULL DEFAULT 0,
+    pinned       INTEGER NOT NULL DEFAULT 0,
+    scene_number TEXT
 );
 CREATE INDEX IF NOT EXISTS idx_nodes_parent ON nodes(parent_id) WHERE parent_id IS NOT NULL;
 CREATE INDEX IF NOT EXISTS idx_nodes_level ON nodes(level);
@@ -31,6 +33,29 @@ CREATE TABLE IF NOT EXISTS node_arcs (
 );
 "#;
 
+/// Create the node tables, adding columns introduced after the first schema
+/// to databases written before them.
+pub(crate) fn create_node_schema(conn: &Connection) -> Result<(), HistoryStoreError> {
+    conn.execute_batch(TIMELINE_NODE_SCHEMA_SQL)?;
+    if !nodes_have_column(conn, "pinned")? {
+        conn.execute(
+            "ALTER TABLE nodes ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0",
+            [],
+        )?;
+    }
+    if !nodes_have_column(conn, "scene_number")? {
+        conn.execute("ALTER TABLE nodes ADD COLUMN scene_number TEXT", [])?;
+    }
+    Ok(())
+}
+
+/// Whether the `nodes` table has `column`; older read-only databases may
+/// predate it.
+pub(crate) fn nodes_have_column(conn: &Connection, column: &str) -> rusqlite::Result<bool> {
+    conn.prepare("SELECT 1 FROM pragma_table_info('nodes') WHERE name = ?1")?
+        .exists([column])
+}
+
 type StoryNodeRow = (
     String,
     Option<String>,
@@ -42,13 +67,15 @@ type StoryNodeRow = (
     String,
     Option<String>,
     i32,
+    i32,
+    Option<String>,
 );
 
 pub(crate) fn upsert_nodes_in_transaction(
     tx: &Transaction<'_>,
     nodes: &[StoryNode],
 ) -> Result<(), HistoryStoreError> {
-    tx.execute_batch(TIMELINE_NODE_SCHEMA_SQL)?;
+    create_node_schema(tx)?;
 
     for node in nodes {
         upsert_node(tx, node)?;
@@ -68,8 +95,9 @@ fn upsert_node(tx: &Transaction<'_>, node: &StoryNode) -> Result<(), HistoryStor
 
     tx.execute(
         "INSERT INTO nodes (
-             id, parent_id, level, sort_order, start_ms, end_ms, name, content_json, beat_type, locked
-         ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             id, parent_id, level, sort_order, start_ms, end_ms, name, content_json, beat_type, locked,
+             pinned, scene_number
+         ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
          ON CONFLICT(id) DO UPDATE SET
              parent_id = excluded.parent_id,
              level = excluded.level,
@@ -79,7 +107,9 @@ fn upsert_node(tx: &Transaction<'_>, node: &StoryNode) -> Result<(), HistoryStor
              name = excluded.name,
              content_json = excluded.content_json,
              beat_type = excluded.beat_type,
-             locked = excluded.locked",
+             locked = excluded.locked,
+             pinned = excluded.pinned,
+             scene_number = excluded.scene_number",
         params![
             node.id.0.to_string(),
             parent_id,
@@ -91,6 +121,8 @@ fn upsert_node(tx: &Transaction<'_>, node: &StoryNode) -> Result<(), HistoryStor
             content_json,
             beat_type_json,
             node.locked as i64,
+            node.pinned as i64,
+            node.scene_number,
         ],
     )?;
 
@@ -101,7 +133,7 @@ pub(crate) fn delete_nodes_in_transaction(
     tx: &Transaction<'_>,
     node_ids: &[NodeId],
 ) -> Result<(), HistoryStoreError> {
-    tx.execute_batch(TIMELINE_NODE_SCHEMA_SQL)?;
+    create_node_schema(tx)?;
 
     for node_id in node_ids {
         tx.execute(
@@ -118,7 +150,7 @@ pub(crate) fn replace_node_arcs_in_transaction(
     tx: &Transaction<'_>,
     node_arcs: &[NodeArc],
 ) -> Result<(), HistoryStoreError> {
-    tx.execute_batch(TIMELINE_NODE_SCHEMA_SQL)?;
+    create_node_schema(tx)?;
     tx.execute("DELETE FROM node_arcs", [])?;
 
     for node_arc in node_arcs {
@@ -135,10 +167,10 @@ pub(crate) fn replace_node_arcs_in_transaction(
 }
 
 pub(crate) fn load_nodes(conn: &Connection) -> Result<Vec<StoryNode>, HistoryStoreError> {
-    conn.execute_batch(TIMELINE_NODE_SCHEMA_SQL)?;
+    create_node_schema(conn)?;
     let mut stmt = conn.prepare(
         "SELECT id, parent_id, level, sort_order, start_ms, end_ms,
-                name, content_json, beat_type, locked
+                name, content_json, beat_type, locked, pinned, scene_number
          FROM nodes ORDER BY level, start_ms",
     )?;
     let rows = stmt.query_map([], |row| {
@@ -153,6 +185,8 @@ pub(crate) fn load_nodes(conn: &Connection) -> Result<Vec<StoryNode>, HistorySto
             row.get::<_, String>(7)?,
             row.get::<_, Option<String>>(8)?,
             row.get::<_, i32>(9)?,
+            row.get::<_, i32>(10)?,
+            row.get::<_, Option<String>>(11)?,
         ))
     })?;
 
@@ -169,6 +203,8 @@ pub(crate) fn load_nodes(conn: &Connection) -> Result<Vec<StoryNode>, HistorySto
             content_json,
             beat_type_json,
             locked,
+            pinned,
+            scene_number,
         ) = row?;
         nodes.push(StoryNode {
             id: NodeId(parse_uuid(&id)?),
@@ -187,6 +223,8 @@ pub(crate) fn load_nodes(conn: &Connection) -> Result<Vec<StoryNode>, HistorySto
                 .map(|beat_type| serde_json::from_str::<BeatType>(&beat_type))
                 .transpose()?,
             locked: locked != 0,
+            pinned: pinned != 0,
+            scene_number,
         });
     }
 
@@ -197,25 +235,26 @@ pub(crate) fn load_node_ancestor_stack(
     conn: &Connection,
     target_node_id: NodeId,
 ) -> Result<Vec<StoryNode>, HistoryStoreError> {
-    conn.execute_batch(TIMELINE_NODE_SCHEMA_SQL)?;
+    create_node_schema(conn)?;
     let mut stmt = conn.prepare(
         "WITH RECURSIVE stack(
             id, parent_id, level, sort_order, start_ms, end_ms,
-            name, content_json, beat_type, locked, depth
+            name, content_json, beat_type, locked, pinned, scene_number, depth
          ) AS (
             SELECT id, parent_id, level, sort_order, start_ms, end_ms,
-                name, content_json, beat_type, locked, 0
+                name, content_json, beat_type, locked, pinned, scene_number, 0
             FROM nodes
             WHERE id = ?1
             UNION ALL
             SELECT parent.id, parent.parent_id, parent.level, parent.sort_order,
                 parent.start_ms, parent.end_ms, parent.name, parent.content_json,
-                parent.beat_type, parent.locked, stack.depth + 1
+                parent.beat_type, parent.locked, parent.pinned, parent.scene_number,
+                stack.depth + 1
             FROM nodes parent
             INNER JOIN stack ON stack.parent_id = parent.id
          )
          SELECT id, parent_id, level, sort_order, start_ms, end_ms,
-            name, content_json, beat_type, locked
+            name, content_json, beat_type, locked, pinned, scene_number
          FROM stack
          ORDER BY depth DESC",
     )?;
@@ -231,6 +270,8 @@ pub(crate) fn load_node_ancestor_stack(
             row.get::<_, String>(7)?,
             row.get::<_, Option<String>>(8)?,
             row.get::<_, i32>(9)?,
+            row.get::<_, i32>(10)?,
+            row.get::<_, Option<String>>(11)?,
         ))
     })?;
 
@@ -242,7 +283,7 @@ pub(crate) fn load_node_ancestor_stack(
 }
 
 pub(crate) fn load_node_arcs(conn: &Connection) -> Result<Vec<NodeArc>, HistoryStoreError> {
-    conn.execute_batch(TIMELINE_NODE_SCHEMA_SQL)?;
+    create_node_schema(conn)?;
     let mut stmt = conn.prepare("SELECT node_id, arc_id FROM node_arcs")?;
     let rows = stmt.query_map([], |row| {
         Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
@@ -272,6 +313,8 @@ fn story_node_from_row(row: StoryNodeRow) -> Result<StoryNode, HistoryStoreError
         content_json,
         beat_type_json,
         locked,
+        pinned,
+        scene_number,
     ) = row;
     Ok(StoryNode {
         id: NodeId(parse_uuid(&id)?),
@@ -290,6 +333,8 @@ fn story_node_from_row(row: StoryNodeRow) -> Result<StoryNode, HistoryStoreError
             .map(|beat_type| serde_json::from_str::<BeatType>(&beat_type))
             .transpose()?,
         locked: locked != 0,
+        pinned: pinned != 0,
+        scene_number,
     })
 }
 
@@ -313,12 +358,34 @@ pub(crate) fn update_node_scene_recap(
     })
 }
 
+/// Replace a node's content text, re-deriving its status from the new text.
+pub(crate) fn update_node_content_text(
+    conn: &Connection,
+    node_id: NodeId,
+    text: String,
+) -> Result<(), HistoryStoreError> {
+    update_node_content(conn, node_id, |content| {
+        content.content = text;
+        content.refresh_status();
+    })
+}
+
+pub(crate) fn update_node_generation_notes(
+    conn: &Connection,
+    node_id: NodeId,
+    notes: &str,
+) -> Result<(), HistoryStoreError> {
+    update_node_content(conn, node_id, |content| {
+        content.record_generation_notes(notes);
+    })
+}
+
 fn update_node_content(
     conn: &Connection,
     node_id: NodeId,
     update: impl FnOnce(&mut NodeContent),
 ) -> Result<(), HistoryStoreError> {
-    conn.execute_batch(TIMELINE_NODE_SCHEMA_SQL)?;
+    create_node_schema(conn)?;
     let content_json = conn
         .query_row(
             "SELECT content_json FROM nodes WHERE id = ?1",
@@ -406,6 +473,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn records_generation_notes_for_staleness() {
+        let conn = Connection::open_in_memory().expect("open sqlite");
+        let mut node = StoryNode::new(
+            "Scene",
+            StoryLevel::Scene,
+            TimeRange::new(0, 1_000).expect("range"),
+        );
+        node.content.notes = "Ada leaves.".to_string();
+        let node_id = node.id;
+        let tx = conn.unchecked_transaction().expect("transaction");
+        upsert_nodes_in_transaction(&tx, &[node]).expect("seed node");
+        tx.commit().expect("commit");
+
+        update_node_generation_notes(&conn, node_id, "Ada leaves.").expect("record notes");
+
+        let mut nodes = load_nodes(&conn).expect("load nodes");
+        assert!(nodes[0].content.notes_hash_at_generation.is_some());
+        assert!(!nodes[0].content.notes_stale());
+        nodes[0].content.notes = "Ada stays.".to_string();
+        assert!(nodes[0].content.notes_stale());
+    }
+
     #[test]
     fn loads_node_ancestor_stack_from_root_to_target() {
         let conn = Connection::open_in_memory().expect("open sqlite");
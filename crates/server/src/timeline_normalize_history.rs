@@ -0,0 +1,86 @@
+use eidetic_core::Project;
+use eidetic_core::contracts::{
+    ChangeEvent, ChangeEventKind, CommandEnvelope, FieldDelta, FieldValue,
+    NormalizeTimelineLevelCommand, ObjectKind, ObjectRevision, RevisionOperation,
+};
+use eidetic_core::timeline::node::StoryNode;
+use rusqlite::Connection;
+
+use crate::history_store::{self, RecordChangeOutcome};
+use crate::timeline_command::TimelineCommandError;
+use crate::timeline_node_store;
+
+/// Records one revision per node whose range the snap moved, descendants
+/// pulled inside a trimmed parent included, and returns how many sibling
+/// boundaries were snapped. A replayed command snaps nothing new. When no
+/// boundary needs snapping nothing is recorded and the outcome is `None`,
+/// so the history gains no empty entry.
+pub(crate) fn record_normalize_timeline_level_history(
+    conn: &mut Connection,
+    project: &Project,
+    command: &CommandEnvelope<NormalizeTimelineLevelCommand>,
+    created_at_ms: u64,
+) -> Result<(Option<RecordChangeOutcome>, usize), TimelineCommandError> {
+    if let Some(outcome) =
+        history_store::check_recorded_command(conn, command, "timeline.normalize_level")?
+    {
+        return Ok((Some(outcome), 0));
+    }
+    if let Some(parent_id) = command.payload.parent_id {
+        project.timeline.node(parent_id)?;
+    }
+
+    let mut next_timeline = project.timeline.clone();
+    let adjusted = next_timeline.normalize_level(command.payload.level, command.payload.parent_id);
+    if adjusted == 0 {
+        return Ok((None, 0));
+    }
+    let event = ChangeEvent::new(
+        command.id,
+        ChangeEventKind::UserEdit,
+        format!("normalize {adjusted} {} boundaries", command.payload.level),
+    )
+    .with_created_at_ms(created_at_ms);
+    let changed: Vec<StoryNode> = next_timeline
+        .nodes
+        .into_iter()
+        .filter(|node| {
+            project
+                .timeline
+                .node(node.id)
+                .is_ok_and(|before| before.time_range != node.time_range)
+        })
+        .collect();
+    let mut revisions = Vec::with_capacity(changed.len());
+    for node in &changed {
+        let before = project.timeline.node(node.id)?.time_range;
+        revisions.push(
+            ObjectRevision::new(
+                ObjectKind::TimelineNode,
+                node.id.0.to_string(),
+                event.id,
+                RevisionOperation::Update,
+            )
+            .with_field(FieldDelta::new(
+                "start_ms",
+                Some(FieldValue::Integer(before.start_ms as i64)),
+                Some(FieldValue::Integer(node.time_range.start_ms as i64)),
+            ))
+            .with_field(FieldDelta::new(
+                "end_ms",
+                Some(FieldValue::Integer(before.end_ms as i64)),
+                Some(FieldValue::Integer(node.time_range.end_ms as i64)),
+            )),
+        );
+    }
+
+    let outcome = history_store::record_change_with(
+        conn,
+        command,
+        "timeline.normalize_level",
+        &event,
+        &revisions,
+        |tx| timeline_node_store::upsert_nodes_in_transaction(tx, &changed),
+    )?;
+    Ok((Some(outcome), adjusted))
+}
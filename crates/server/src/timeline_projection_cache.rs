@@ -0,0 +1,148 @@
+use std::path::{Path, PathBuf};
+
+use eidetic_core::contracts::{ProjectionEnvelope, TimelineRenderProjection};
+use parking_lot::Mutex;
+use tokio::sync::broadcast::{self, error::TryRecvError};
+
+use crate::state::ServerEvent;
+
+/// The last timeline render projection read from disk, reused until the
+/// timeline changes. Invalidation is driven by the server event stream (clip
+/// content status and arc tags change with node and story events too), drained
+/// on every lookup so an event sent before a read is always seen by it, plus
+/// explicit calls for writes that do not broadcast (auto-save, project load,
+/// affect edits). Every invalidation bumps a generation, so a projection
+/// computed from data read before one is not stored after it.
+pub(crate) struct TimelineProjectionCache {
+    inner: Mutex<CacheState>,
+}
+
+struct CacheState {
+    events: broadcast::Receiver<ServerEvent>,
+    entry: Option<(PathBuf, ProjectionEnvelope<TimelineRenderProjection>)>,
+    generation: u64,
+}
+
+impl CacheState {
+    fn invalidate(&mut self) {
+        self.entry = None;
+        self.generation += 1;
+    }
+
+    fn drain_events(&mut self) {
+        loop {
+            match self.events.try_recv() {
+                Ok(
+                    ServerEvent::ProjectChanged
+                    | ServerEvent::TimelineChanged
+                    | ServerEvent::HierarchyChanged
+                    | ServerEvent::NodeUpdated { .. }
+                    | ServerEvent::StoryChanged
+                    | ServerEvent::ScriptChanged,
+                )
+                | Err(TryRecvError::Lagged(_)) => self.invalidate(),
+                Ok(_) => {}
+                Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+            }
+        }
+    }
+}
+
+impl TimelineProjectionCache {
+    pub(crate) fn new(events: broadcast::Receiver<ServerEvent>) -> Self {
+        Self {
+            inner: Mutex::new(CacheState {
+                events,
+                entry: None,
+                generation: 0,
+            }),
+        }
+    }
+
+    /// The cached projection for `path`, if nothing has changed since it was stored.
+    pub(crate) fn get(&self, path: &Path) -> Option<ProjectionEnvelope<TimelineRenderProjection>> {
+        let mut state = self.inner.lock();
+        state.drain_events();
+        state
+            .entry
+            .as_ref()
+            .filter(|(cached_path, _)| cached_path == path)
+            .map(|(_, projection)| projection.clone())
+    }
+
+    /// Capture before reading the data a projection is computed from, and
+    /// pass to [`Self::store`].
+    pub(crate) fn generation(&self) -> u64 {
+        let mut state = self.inner.lock();
+        state.drain_events();
+        state.generation
+    }
+
+    /// Keep `projection` unless the cache was invalidated after `generation`
+    /// was captured, in which case it may be stale and is dropped.
+    pub(crate) fn store(
+        &self,
+        path: PathBuf,
+        generation: u64,
+        projection: ProjectionEnvelope<TimelineRenderProjection>,
+    ) {
+        let mut state = self.inner.lock();
+        state.drain_events();
+        if state.generation == generation {
+            state.entry = Some((path, projection));
+        }
+    }
+
+    pub(crate) fn invalidate(&self) {
+        self.inner.lock().invalidate();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eidetic_core::Template;
+
+    #[test]
+    fn cache_is_dropped_by_timeline_events_and_path_changes() {
+        let (events_tx, events_rx) = broadcast::channel(4);
+        let cache = TimelineProjectionCache::new(events_rx);
+        let path = PathBuf::from("a.db");
+        let timeline = Template::MultiCam.build_project("Cache Test").timeline;
+        let projection =
+            ProjectionEnvelope::initial(TimelineRenderProjection::from_timeline(&timeline));
+
+        cache.store(path.clone(), cache.generation(), projection);
+        let _ = events_tx.send(ServerEvent::BibleChanged);
+        assert!(cache.get(&path).is_some());
+        assert!(cache.get(Path::new("b.db")).is_none());
+
+        let _ = events_tx.send(ServerEvent::TimelineChanged);
+        assert!(cache.get(&path).is_none());
+    }
+
+    #[test]
+    fn projection_computed_before_an_invalidation_is_not_stored() {
+        let (events_tx, events_rx) = broadcast::channel(4);
+        let cache = TimelineProjectionCache::new(events_rx);
+        let path = PathBuf::from("a.db");
+        let timeline = Template::MultiCam.build_project("Cache Test").timeline;
+        let projection =
+            ProjectionEnvelope::initial(TimelineRenderProjection::from_timeline(&timeline));
+
+        let generation = cache.generation();
+        cache.invalidate();
+        cache.store(path.clone(), generation, projection.clone());
+        assert!(cache.get(&path).is_none());
+
+        let generation = cache.generation();
+        let _ = events_tx.send(ServerEvent::NodeUpdated {
+            node_id: uuid::Uuid::new_v4(),
+        });
+        cache.store(path.clone(), generation, projection.clone());
+        assert!(cache.get(&path).is_none());
+
+        cache.store(path.clone(), cache.generation(), projection);
+        assert!(cache.get(&path).is_some());
+    }
+}
@@ -0,0 +1,127 @@
+use eidetic_core::Project;
+use eidetic_core::contracts::{
+    ChangeEvent, ChangeEventKind, CommandEnvelope, FieldDelta, FieldValue, ObjectKind,
+    ObjectRevision, RenumberTimelineScenesCommand, RevisionOperation,
+    SetTimelineNodeSceneNumberCommand,
+};
+use eidetic_core::timeline::node::{NodeId, StoryLevel};
+use eidetic_core::timeline::scene_numbers::normalize_scene_number;
+use rusqlite::Connection;
+
+use crate::history_store::{self, RecordChangeOutcome};
+use crate::timeline_command::TimelineCommandError;
+use crate::timeline_node_store;
+
+pub(crate) fn record_set_timeline_node_scene_number_history(
+    conn: &mut Connection,
+    project: &Project,
+    command: &CommandEnvelope<SetTimelineNodeSceneNumberCommand>,
+    created_at_ms: u64,
+) -> Result<RecordChangeOutcome, TimelineCommandError> {
+    if let Some(outcome) =
+        history_store::check_recorded_command(conn, command, "timeline.node_scene_number")?
+    {
+        return Ok(outcome);
+    }
+
+    let node = project.timeline.node(command.payload.node_id)?;
+    if node.level != StoryLevel::Scene {
+        return Err(eidetic_core::Error::InvalidSceneNumber(format!(
+            "only Scene nodes are numbered, not {} node {}",
+            node.level, node.name
+        ))
+        .into());
+    }
+    let scene_number = match command.payload.scene_number.as_deref() {
+        Some(raw) => normalize_scene_number(raw)?,
+        None => None,
+    };
+    let event = ChangeEvent::new(
+        command.id,
+        ChangeEventKind::UserEdit,
+        format!("set scene number {}", node.name),
+    )
+    .with_created_at_ms(created_at_ms);
+    let revision = scene_number_revision(
+        command.payload.node_id,
+        &event,
+        node.scene_number.clone(),
+        scene_number.clone(),
+    );
+    let mut next_timeline = project.timeline.clone();
+    next_timeline
+        .node_mut(command.payload.node_id)?
+        .scene_number = scene_number;
+
+    Ok(history_store::record_change_with(
+        conn,
+        command,
+        "timeline.node_scene_number",
+        &event,
+        &[revision],
+        |tx| timeline_node_store::upsert_nodes_in_transaction(tx, &next_timeline.nodes),
+    )?)
+}
+
+/// Records one revision per scene whose number changes.
+pub(crate) fn record_renumber_timeline_scenes_history(
+    conn: &mut Connection,
+    project: &Project,
+    command: &CommandEnvelope<RenumberTimelineScenesCommand>,
+    created_at_ms: u64,
+) -> Result<RecordChangeOutcome, TimelineCommandError> {
+    if let Some(outcome) =
+        history_store::check_recorded_command(conn, command, "timeline.renumber_scenes")?
+    {
+        return Ok(outcome);
+    }
+
+    let plan = project.timeline.plan_scene_numbers(command.payload.mode);
+    let event = ChangeEvent::new(
+        command.id,
+        ChangeEventKind::UserEdit,
+        format!("renumber {} scenes", plan.len()),
+    )
+    .with_created_at_ms(created_at_ms);
+    let mut revisions = Vec::with_capacity(plan.len());
+    let mut changed = Vec::with_capacity(plan.len());
+    for (node_id, number) in plan {
+        let mut node = project.timeline.node(node_id)?.clone();
+        revisions.push(scene_number_revision(
+            node_id,
+            &event,
+            node.scene_number.take(),
+            Some(number.clone()),
+        ));
+        node.scene_number = Some(number);
+        changed.push(node);
+    }
+
+    Ok(history_store::record_change_with(
+        conn,
+        command,
+        "timeline.renumber_scenes",
+        &event,
+        &revisions,
+        |tx| timeline_node_store::upsert_nodes_in_transaction(tx, &changed),
+    )?)
+}
+
+fn scene_number_revision(
+    node_id: NodeId,
+    event: &ChangeEvent,
+    before: Option<String>,
+    after: Option<String>,
+) -> ObjectRevision {
+    ObjectRevision::new(
+        ObjectKind::TimelineNode,
+        node_id.0.to_string(),
+        event.id,
+        RevisionOperation::Update,
+    )
+    .with_field(FieldDelta::new(
+        "scene_number",
+        before.map(FieldValue::Text),
+        after.map(FieldValue::Text),
+    ))
+}
@@ -78,6 +78,41 @@ pub fn validate_project_path(input: &str, root: &Path) -> Result<PathBuf, Backen
     Ok(candidate)
 }
 
+/// Resolve a user-chosen project directory outside the storage root, such as a
+/// synced folder. The directory must exist and be writable, and holds the
+/// project as `project.db`. Returns the directory.
+pub fn validate_project_directory(input: &str) -> Result<PathBuf, BackendError> {
+    if input.trim().is_empty() {
+        return Err(BackendError::bad_request("path is required"));
+    }
+    let path = Path::new(input.trim());
+    if !path.is_absolute() {
+        return Err(BackendError::bad_request(
+            "project directory must be absolute",
+        ));
+    }
+    let directory = path.canonicalize().map_err(|e| {
+        BackendError::bad_request(format!("failed to resolve project directory: {e}"))
+    })?;
+    if !directory.is_dir() {
+        return Err(BackendError::bad_request(
+            "project directory is not a directory",
+        ));
+    }
+    if directory.join("project.db").is_dir() {
+        return Err(BackendError::bad_request(
+            "project directory has a project.db directory in place of a project file",
+        ));
+    }
+
+    let probe = directory.join(format!(".eidetic-write-check-{}", uuid::Uuid::new_v4()));
+    std::fs::File::create(&probe).map_err(|e| {
+        BackendError::bad_request(format!("project directory is not writable: {e}"))
+    })?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(directory)
+}
+
 fn canonical_or_lexical(path: &Path) -> std::io::Result<PathBuf> {
     if path.exists() {
         path.canonicalize()
@@ -115,7 +150,7 @@ fn normalize_path(path: PathBuf) -> PathBuf {
 
 #[cfg(test)]
 mod tests {
-    use super::{validate_name, validate_project_path};
+    use super::{validate_name, validate_project_directory, validate_project_path};
     use std::fs;
     use std::path::PathBuf;
 
@@ -188,4 +223,25 @@ mod tests {
         let resolved = validate_project_path("episode/project.db", &root).unwrap();
         assert_eq!(resolved, root.join("episode/project.db"));
     }
+
+    #[test]
+    fn validate_project_directory_accepts_writable_directory_outside_root() {
+        let directory = temp_dir("project-directory");
+
+        let resolved = validate_project_directory(directory.to_str().unwrap()).unwrap();
+        assert_eq!(resolved, directory.canonicalize().unwrap());
+        assert_eq!(fs::read_dir(&directory).unwrap().count(), 0);
+
+        let err = validate_project_directory("relative/dir").unwrap_err();
+        assert_eq!(err.message(), "project directory must be absolute");
+        let err =
+            validate_project_directory(directory.join("missing").to_str().unwrap()).unwrap_err();
+        assert!(
+            err.message()
+                .starts_with("failed to resolve project directory")
+        );
+        fs::create_dir(directory.join("project.db")).unwrap();
+        assert!(validate_project_directory(directory.to_str().unwrap()).is_err());
+        fs::remove_dir_all(directory).unwrap();
+    }
 }
@@ -1,9 +1,21 @@
 use std::collections::HashMap;
+use thiserror::Error;
 use uuid::Uuid;
 
 use eidetic_core::reference::{ReferenceChunk, ReferenceId};
 
-/// In-memory vector store for reference material chunks.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum VectorStoreError {
+    /// The embedding model changed since the store was built.
+    #[error(
+        "embedding has {found} dimensions but stored references use {expected}; \
+         re-embed references with the current embedding model"
+    )]
+    DimensionMismatch { expected: usize, found: usize },
+}
+
+/// In-memory vector store for reference material chunks. Every vector in
+/// the store shares one dimension, fixed by the first insert.
 pub struct VectorStore {
     entries: HashMap<Uuid, (ReferenceChunk, Vec<f32>)>,
 }
@@ -16,8 +28,24 @@ impl VectorStore {
     }
 
     /// Insert a chunk with its embedding vector.
-    pub fn insert(&mut self, chunk: ReferenceChunk, embedding: Vec<f32>) {
+    pub fn insert(
+        &mut self,
+        chunk: ReferenceChunk,
+        embedding: Vec<f32>,
+    ) -> Result<(), VectorStoreError> {
+        self.check_dimension(embedding.len())?;
         self.entries.insert(chunk.id, (chunk, embedding));
+        Ok(())
+    }
+
+    /// Remove every chunk, e.g. before re-embedding with a new model.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Dimension shared by the stored vectors, if any are stored.
+    pub fn dimension(&self) -> Option<usize> {
+        self.entries.values().next().map(|(_, emb)| emb.len())
     }
 
     /// Remove all chunks belonging to a document.
@@ -27,22 +55,48 @@ impl VectorStore {
     }
 
     /// Search for the top-k most similar chunks to a query embedding.
-    pub fn search(&self, query: &[f32], top_k: usize) -> Vec<(&ReferenceChunk, f32)> {
+    pub fn search(
+        &self,
+        query: &[f32],
+        top_k: usize,
+    ) -> Result<Vec<(&ReferenceChunk, f32)>, VectorStoreError> {
+        self.search_matching(query, top_k, |_| true)
+    }
+
+    /// Like [`Self::search`], ranking only the chunks `keep` accepts so
+    /// out-of-scope documents don't crowd the top-k.
+    pub fn search_matching(
+        &self,
+        query: &[f32],
+        top_k: usize,
+        keep: impl Fn(&ReferenceChunk) -> bool,
+    ) -> Result<Vec<(&ReferenceChunk, f32)>, VectorStoreError> {
+        self.check_dimension(query.len())?;
         let mut scored: Vec<(&ReferenceChunk, f32)> = self
             .entries
             .values()
+            .filter(|(chunk, _)| keep(chunk))
             .map(|(chunk, emb)| (chunk, cosine_similarity(query, emb)))
             .collect();
 
         scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
         scored.truncate(top_k);
-        scored
+        Ok(scored)
     }
 
     /// Check if the store has any entries.
     pub fn is_empty(&self) -> bool {
         self.entries.is_empty()
     }
+
+    fn check_dimension(&self, found: usize) -> Result<(), VectorStoreError> {
+        match self.dimension() {
+            Some(expected) if expected != found => {
+                Err(VectorStoreError::DimensionMismatch { expected, found })
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
@@ -82,4 +136,38 @@ mod tests {
         let score = cosine_similarity(&a, &b);
         assert!(score.abs() < 1e-6);
     }
+
+    fn chunk(document_id: ReferenceId) -> ReferenceChunk {
+        ReferenceChunk {
+            id: Uuid::new_v4(),
+            document_id,
+            document_name: "Guide".into(),
+            content: "text".into(),
+            offset: 0,
+        }
+    }
+
+    #[test]
+    fn mismatched_dimensions_are_rejected_until_cleared() {
+        let document_id = ReferenceId(Uuid::new_v4());
+        let mut store = VectorStore::new();
+        store
+            .insert(chunk(document_id), vec![1.0, 0.0, 0.0])
+            .unwrap();
+
+        let mismatch = VectorStoreError::DimensionMismatch {
+            expected: 3,
+            found: 2,
+        };
+        assert_eq!(
+            store.insert(chunk(document_id), vec![1.0, 0.0]),
+            Err(mismatch.clone())
+        );
+        assert_eq!(store.search(&[1.0, 0.0], 3).unwrap_err(), mismatch);
+        assert_eq!(store.search(&[1.0, 0.0, 0.0], 3).unwrap().len(), 1);
+
+        store.clear();
+        store.insert(chunk(document_id), vec![1.0, 0.0]).unwrap();
+        assert_eq!(store.dimension(), Some(2));
+    }
 }
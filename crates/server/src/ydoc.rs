@@ -22,7 +22,6 @@ use std::sync::Arc;
 
 use tokio::sync::{broadcast, mpsc, oneshot};
 use tracing;
-#[cfg(test)]
 use yrs::GetString;
 use yrs::types::Attrs;
 use yrs::updates::decoder::Decode;
@@ -55,6 +54,14 @@ pub struct NodeTextSnapshot {
     pub attributed_spans: Vec<AttributedSpan>,
 }
 
+/// Live text of one node, as read from Y.Doc.
+#[derive(Debug, Clone)]
+pub struct NodeText {
+    pub node_id: NodeId,
+    pub notes: String,
+    pub content: String,
+}
+
 /// A contiguous span of text with a single author.
 #[cfg(test)]
 #[derive(Debug, Clone)]
@@ -95,23 +102,40 @@ pub enum DocCommand {
         text: String,
         author: String,
     },
+    /// Append text to a node's field without replacing what is there
+    /// (used by AI continuation).
+    AppendNodeContent {
+        node_id: NodeId,
+        field: ContentField,
+        text: String,
+        author: String,
+    },
     /// Read text content for a node.
     #[cfg(test)]
     ReadNodeContent {
         node_id: NodeId,
         reply: oneshot::Sender<NodeTextSnapshot>,
     },
+    /// Read the text of every node in the doc.
+    ReadAllNodes {
+        reply: oneshot::Sender<Vec<NodeText>>,
+    },
     /// Ensure a node entry exists in Y.Doc when a timeline node is created.
     EnsureNode { node_id: NodeId },
     /// Remove a node entry from Y.Doc when a timeline node is deleted.
     RemoveNode { node_id: NodeId },
     /// Serialize full doc state for persistence.
     Serialize { reply: oneshot::Sender<Vec<u8>> },
-    /// Load doc state from persistence (replaces current doc content).
+    /// Load doc state from persistence, merged into the current doc.
     Load {
         state: Vec<u8>,
         reply: oneshot::Sender<Result<(), String>>,
     },
+    /// Replace the doc with an empty one, dropping every node's text. Run
+    /// before loading another project: every project's doc is written as
+    /// client 0, so merging a second project's state into the first's would
+    /// skip updates the doc believes it already has.
+    Reset { reply: oneshot::Sender<()> },
 }
 
 /// Channel capacity for the doc command queue.
@@ -147,19 +171,7 @@ async fn run_doc_manager(
     mut rx: mpsc::Receiver<DocCommand>,
     update_tx: broadcast::Sender<DocUpdate>,
 ) {
-    // Use client_id = 0 for the server's own doc.
-    let doc = Doc::with_options(Options {
-        client_id: 0,
-        skip_gc: false,
-        ..Options::default()
-    });
-
-    // Pre-create the root maps so they exist for all operations.
-    {
-        let mut txn = doc.transact_mut();
-        let _ = txn.get_or_insert_map("nodes");
-        let _ = txn.get_or_insert_map("project_text");
-    }
+    let mut doc = new_server_doc();
 
     // Subscribe to doc updates for broadcasting to document update subscribers.
     // We capture updates via observe_update_v1 and forward them.
@@ -167,18 +179,7 @@ async fn run_doc_manager(
     // sequentially, we track which command triggered the update to set the
     // correct origin_client.
     let pending_origin = Arc::new(std::sync::Mutex::new(0u64));
-    let pending_origin_clone = pending_origin.clone();
-    let update_tx_clone = update_tx.clone();
-
-    let _update_sub = doc
-        .observe_update_v1(move |_txn, event| {
-            let origin = *pending_origin_clone.lock().unwrap();
-            let _ = update_tx_clone.send(DocUpdate {
-                origin_client: origin,
-                data: event.update.clone(),
-            });
-        })
-        .expect("failed to subscribe to doc updates");
+    let mut _update_sub = observe_doc_updates(&doc, &pending_origin, &update_tx);
 
     tracing::info!("Y.Doc manager started");
 
@@ -227,12 +228,26 @@ async fn run_doc_manager(
                 write_node_field(&doc, &node_id, field, &text, &author);
             }
 
+            DocCommand::AppendNodeContent {
+                node_id,
+                field,
+                text,
+                author,
+            } => {
+                *pending_origin.lock().unwrap() = 0;
+                append_to_node_field(&doc, &node_id, field, &text, &author);
+            }
+
             #[cfg(test)]
             DocCommand::ReadNodeContent { node_id, reply } => {
                 let snapshot = read_node_snapshot(&doc, &node_id);
                 let _ = reply.send(snapshot);
             }
 
+            DocCommand::ReadAllNodes { reply } => {
+                let _ = reply.send(read_all_nodes_text(&doc));
+            }
+
             DocCommand::EnsureNode { node_id } => {
                 ensure_node_exists(&doc, &node_id);
             }
@@ -251,6 +266,12 @@ async fn run_doc_manager(
                 let result = load_doc_state(&doc, &state);
                 let _ = reply.send(result);
             }
+
+            DocCommand::Reset { reply } => {
+                doc = new_server_doc();
+                _update_sub = observe_doc_updates(&doc, &pending_origin, &update_tx);
+                let _ = reply.send(());
+            }
         }
     }
     tracing::info!("Y.Doc manager shutting down");
@@ -260,6 +281,43 @@ async fn run_doc_manager(
 // Internal helpers
 // ──────────────────────────────────────────────
 
+/// An empty server doc with its root maps in place.
+fn new_server_doc() -> Doc {
+    // Use client_id = 0 for the server's own doc.
+    let doc = Doc::with_options(Options {
+        client_id: 0,
+        skip_gc: false,
+        ..Options::default()
+    });
+
+    // Pre-create the root maps so they exist for all operations.
+    {
+        let mut txn = doc.transact_mut();
+        let _ = txn.get_or_insert_map("nodes");
+        let _ = txn.get_or_insert_map("project_text");
+    }
+    doc
+}
+
+/// Forward every update to `doc` to the broadcast, tagged with the client
+/// whose command is being applied.
+fn observe_doc_updates(
+    doc: &Doc,
+    pending_origin: &Arc<std::sync::Mutex<u64>>,
+    update_tx: &broadcast::Sender<DocUpdate>,
+) -> yrs::Subscription {
+    let pending_origin = pending_origin.clone();
+    let update_tx = update_tx.clone();
+    doc.observe_update_v1(move |_txn, event| {
+        let origin = *pending_origin.lock().unwrap();
+        let _ = update_tx.send(DocUpdate {
+            origin_client: origin,
+            data: event.update.clone(),
+        });
+    })
+    .expect("failed to subscribe to doc updates")
+}
+
 /// Get or create the Y.Map for a node inside the "nodes" root map.
 fn get_or_create_node_map(
     nodes_map: &MapRef,
@@ -329,8 +387,7 @@ fn write_node_field(doc: &Doc, node_id: &NodeId, field: ContentField, text: &str
     }
 }
 
-/// Append text to a node field (used for AI token streaming).
-#[cfg(test)]
+/// Append text to a node field (used for AI token streaming and continuation).
 fn append_to_node_field(
     doc: &Doc,
     node_id: &NodeId,
@@ -394,8 +451,29 @@ fn read_node_snapshot(doc: &Doc, node_id: &NodeId) -> NodeTextSnapshot {
     }
 }
 
+/// Read notes and content for every node entry in the doc.
+fn read_all_nodes_text(doc: &Doc) -> Vec<NodeText> {
+    let txn = doc.transact();
+    let Some(nodes) = txn.get_map("nodes") else {
+        return Vec::new();
+    };
+    let keys: Vec<String> = nodes.keys(&txn).map(|key| key.to_string()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let node_id = NodeId(uuid::Uuid::parse_str(&key).ok()?);
+            match nodes.get(&txn, &key) {
+                Some(yrs::Out::YMap(node_map)) => Some(NodeText {
+                    node_id,
+                    notes: read_text_field(&node_map, &txn, "notes"),
+                    content: read_text_field(&node_map, &txn, "content"),
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
 /// Read plain text from a Y.Text field within a node map.
-#[cfg(test)]
 fn read_text_field(node_map: &MapRef, txn: &yrs::Transaction<'_>, field_name: &str) -> String {
     match node_map.get(txn, field_name) {
         Some(yrs::Out::YText(text)) => text.get_string(txn),
@@ -494,6 +572,16 @@ pub async fn serialize_doc(doc_tx: &mpsc::Sender<DocCommand>) -> Option<Vec<u8>>
     reply_rx.await.ok()
 }
 
+/// Helper: read the live text of every node.
+pub async fn read_all_nodes(doc_tx: &mpsc::Sender<DocCommand>) -> Option<Vec<NodeText>> {
+    let (reply_tx, reply_rx) = oneshot::channel();
+    doc_tx
+        .send(DocCommand::ReadAllNodes { reply: reply_tx })
+        .await
+        .ok()?;
+    reply_rx.await.ok()
+}
+
 /// Helper: get state vector for sync handshake.
 pub async fn get_state_vector(doc_tx: &mpsc::Sender<DocCommand>) -> Option<Vec<u8>> {
     let (reply_tx, reply_rx) = oneshot::channel();
@@ -532,6 +620,18 @@ pub async fn load_doc(doc_tx: &mpsc::Sender<DocCommand>, state: Vec<u8>) -> Resu
         .map_err(|_| "doc manager reply dropped".to_string())?
 }
 
+/// Helper: replace the doc with an empty one and wait until it is in place.
+pub async fn reset_doc(doc_tx: &mpsc::Sender<DocCommand>) -> Result<(), String> {
+    let (reply_tx, reply_rx) = oneshot::channel();
+    doc_tx
+        .send(DocCommand::Reset { reply: reply_tx })
+        .await
+        .map_err(|_| "doc manager channel closed".to_string())?;
+    reply_rx
+        .await
+        .map_err(|_| "doc manager reply dropped".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -734,4 +834,39 @@ mod tests {
         drop(doc_tx);
         supervisor.shutdown_all().await;
     }
+
+    #[tokio::test]
+    async fn reset_lets_another_projects_state_load_in_full() {
+        let supervisor = BackendTaskSupervisor::default();
+        let (doc_tx, _update_tx) = spawn_doc_manager(&supervisor);
+        let (other_tx, _other_update_tx) = spawn_doc_manager(&supervisor);
+        let first = NodeId(Uuid::new_v4());
+        let second = NodeId(Uuid::new_v4());
+        for (tx, node_id, text) in [
+            (&doc_tx, first, "First project"),
+            (&other_tx, second, "Second"),
+        ] {
+            tx.send(DocCommand::WriteNodeContent {
+                node_id,
+                field: ContentField::Content,
+                text: text.into(),
+                author: "test:1".into(),
+            })
+            .await
+            .unwrap();
+        }
+        let other_state = serialize_doc(&other_tx).await.unwrap();
+
+        reset_doc(&doc_tx).await.unwrap();
+        load_doc(&doc_tx, other_state).await.unwrap();
+
+        let nodes = read_all_nodes(&doc_tx).await.unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].node_id, second);
+        assert_eq!(nodes[0].content, "Second");
+
+        drop(doc_tx);
+        drop(other_tx);
+        supervisor.shutdown_all().await;
+    }
 }
@@ -1,10 +1,21 @@
-use eidetic_core::ai::backend::ChildPlan;
+use eidetic_core::ai::backend::{ChildPlan, NotesDriftVerdict, PremiseExpansion};
+use eidetic_core::timeline::node::StoryNode;
+use eidetic_server::ai_arc_suggestions::{self, AiArcSuggestionRequest, SuggestedArcTag};
+use eidetic_server::ai_continuity_audit::{self, AiContinuityAuditResponse};
+use eidetic_server::ai_generation_queue::{
+    self, AiQueueCancelRequest, AiQueueEnqueueRequest, GenerationQueueItem,
+};
 use eidetic_server::ai_generation_service::{
-    self, AiGenerateBatchRequest, AiGenerateBatchResponse, AiGenerateRequest, AiGenerateResponse,
+    self, AiContinueRequest, AiGenerateBatchRequest, AiGenerateBatchResponse, AiGenerateRequest,
+    AiGenerateResponse, AiGenerationLogRequest, AiReadyNodesRequest, GenerationLogEntry,
 };
+use eidetic_server::ai_notes_drift::{self, AiNotesDriftRequest, AiNotesDriftSweepResponse};
 use eidetic_server::ai_service::{
-    self, AiConfigUpdate, AiContextPreview, AiGenerateChildrenRequest, AiStatus,
+    self, AiArcTreatmentRequest, AiBrainstormNotesRequest, AiCommitPremiseExpansionRequest,
+    AiConfigUpdate, AiContextPreview, AiGenerateChildrenRequest, AiPreloadStatus, AiStatus,
+    AiSummarizeToNotesRequest, ArcTreatment, PremiseExpansionCommitResponse,
 };
+use eidetic_server::command_service::TimelineCommandResponse;
 use eidetic_server::state::{AiConfig, AppState};
 use tauri::Manager;
 use uuid::Uuid;
@@ -17,6 +28,14 @@ pub async fn ai_status(app: tauri::AppHandle) -> AiStatus {
     ai_service::get_ai_status(&state).await
 }
 
+#[tauri::command]
+pub async fn ai_preload(app: tauri::AppHandle) -> Result<AiPreloadStatus, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_service::preload_models(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub fn ai_config_update(app: tauri::AppHandle, updates: AiConfigUpdate) -> AiConfig {
     let state = app.state::<AppState>();
@@ -34,6 +53,17 @@ pub async fn ai_context_preview(
         .map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub async fn ai_debug_request_body(
+    app: tauri::AppHandle,
+    node_id: Uuid,
+) -> Result<serde_json::Value, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_service::debug_request_body(&state, node_id)
+        .await
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub async fn ai_generate_content(
     app: tauri::AppHandle,
@@ -45,6 +75,70 @@ pub async fn ai_generate_content(
         .map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub async fn ai_continue_content(
+    app: tauri::AppHandle,
+    request: AiContinueRequest,
+) -> Result<AiGenerateResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_generation_service::start_continuation(&state, request)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn ai_generation_log(
+    app: tauri::AppHandle,
+    request: AiGenerationLogRequest,
+) -> Result<Vec<GenerationLogEntry>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_generation_service::generation_log(&state, request)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub fn ai_ready_nodes(
+    app: tauri::AppHandle,
+    request: Option<AiReadyNodesRequest>,
+) -> Result<Vec<StoryNode>, CommandError> {
+    let state = app.state::<AppState>();
+    ai_generation_service::ready_nodes(&state, request.unwrap_or_default())
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn ai_generation_queue(
+    app: tauri::AppHandle,
+) -> Result<Vec<GenerationQueueItem>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_generation_queue::generation_queue(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn ai_generation_enqueue(
+    app: tauri::AppHandle,
+    request: AiQueueEnqueueRequest,
+) -> Result<Vec<GenerationQueueItem>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_generation_queue::enqueue_generation(&state, request)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn ai_generation_cancel(
+    app: tauri::AppHandle,
+    request: AiQueueCancelRequest,
+) -> Result<Vec<GenerationQueueItem>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_generation_queue::cancel_queued_generation(&state, request)
+        .await
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub async fn ai_generate_children(
     app: tauri::AppHandle,
@@ -56,6 +150,58 @@ pub async fn ai_generate_children(
         .map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub async fn ai_brainstorm_notes(
+    app: tauri::AppHandle,
+    request: AiBrainstormNotesRequest,
+) -> Result<TimelineCommandResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_service::brainstorm_notes(&state, request)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn ai_summarize_to_notes(
+    app: tauri::AppHandle,
+    request: AiSummarizeToNotesRequest,
+) -> Result<TimelineCommandResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_service::summarize_to_notes(&state, request)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn ai_arc_treatment(
+    app: tauri::AppHandle,
+    request: AiArcTreatmentRequest,
+) -> Result<ArcTreatment, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_service::generate_arc_treatment(&state, request)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn ai_expand_premise(app: tauri::AppHandle) -> Result<PremiseExpansion, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_service::expand_premise(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn ai_expand_premise_commit(
+    app: tauri::AppHandle,
+    request: AiCommitPremiseExpansionRequest,
+) -> Result<PremiseExpansionCommitResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_service::commit_premise_expansion(&state, request)
+        .await
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub async fn ai_generate_batch(
     app: tauri::AppHandle,
@@ -66,3 +212,45 @@ pub async fn ai_generate_batch(
         .await
         .map_err(CommandError::from)
 }
+
+#[tauri::command]
+pub async fn ai_continuity_audit(
+    app: tauri::AppHandle,
+) -> Result<AiContinuityAuditResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_continuity_audit::start_continuity_audit(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn ai_notes_drift(
+    app: tauri::AppHandle,
+    request: AiNotesDriftRequest,
+) -> Result<NotesDriftVerdict, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_notes_drift::check_notes_drift(&state, request)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn ai_notes_drift_sweep(
+    app: tauri::AppHandle,
+) -> Result<AiNotesDriftSweepResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_notes_drift::start_notes_drift_sweep(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn ai_suggest_arcs(
+    app: tauri::AppHandle,
+    request: AiArcSuggestionRequest,
+) -> Result<Vec<SuggestedArcTag>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    ai_arc_suggestions::suggest_arc_tags(&state, request)
+        .await
+        .map_err(CommandError::from)
+}
@@ -118,3 +118,24 @@ pub async fn command_bible_graph_roots(
         .await
         .map_err(CommandError::from)
 }
+
+#[tauri::command]
+pub async fn command_bible_promote_locations(
+    app: tauri::AppHandle,
+) -> Result<command_service::PromoteLocationsResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    command_service::promote_locations(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn command_bible_import(
+    app: tauri::AppHandle,
+    command: CommandEnvelope<command_service::ImportBibleTransferCommand>,
+) -> Result<command_service::BibleImportResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    command_service::import_bible(&state, command)
+        .await
+        .map_err(CommandError::from)
+}
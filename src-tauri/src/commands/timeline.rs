@@ -1,7 +1,11 @@
 use eidetic_core::contracts::{
-    CommandEnvelope, DeleteTimelineNodeCommand, DeleteTimelineRelationshipCommand,
-    SetTimelineNodeLockCommand, SetTimelineNodeNotesCommand, SetTimelineNodeRangeCommand,
+    ApplyTimelineChildrenPreview, CommandEnvelope, DeleteTimelineNodeCommand,
+    DeleteTimelineRelationshipCommand, DuplicateTimelineNodeCommand, NormalizeTimelineLevelCommand,
+    RenumberTimelineScenesCommand, SetTimelineNodeArcTagCommand,
+    SetTimelineNodeGenerationOrderCommand, SetTimelineNodeLockCommand, SetTimelineNodeNotesCommand,
+    SetTimelineNodePinnedCommand, SetTimelineNodeRangeCommand, SetTimelineNodeSceneNumberCommand,
 };
+use eidetic_core::timeline::track::Track;
 use eidetic_server::command_service;
 use eidetic_server::projection_service;
 use eidetic_server::state::AppState;
@@ -47,6 +51,72 @@ pub async fn command_timeline_node_range(
         .map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub async fn command_timeline_node_pin(
+    app: tauri::AppHandle,
+    command: CommandEnvelope<SetTimelineNodePinnedCommand>,
+) -> Result<command_service::TimelineCommandResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    command_service::set_timeline_node_pinned(&state, command)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn command_timeline_node_scene_number(
+    app: tauri::AppHandle,
+    command: CommandEnvelope<SetTimelineNodeSceneNumberCommand>,
+) -> Result<command_service::TimelineCommandResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    command_service::set_timeline_node_scene_number(&state, command)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn command_timeline_node_generation_order(
+    app: tauri::AppHandle,
+    command: CommandEnvelope<SetTimelineNodeGenerationOrderCommand>,
+) -> Result<command_service::TimelineCommandResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    command_service::set_timeline_node_generation_order(&state, command)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn command_timeline_node_arc_tag(
+    app: tauri::AppHandle,
+    command: CommandEnvelope<SetTimelineNodeArcTagCommand>,
+) -> Result<command_service::TimelineCommandResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    command_service::set_timeline_node_arc_tag(&state, command)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn command_timeline_renumber_scenes(
+    app: tauri::AppHandle,
+    command: CommandEnvelope<RenumberTimelineScenesCommand>,
+) -> Result<command_service::TimelineCommandResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    command_service::renumber_timeline_scenes(&state, command)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn command_timeline_normalize(
+    app: tauri::AppHandle,
+    command: CommandEnvelope<NormalizeTimelineLevelCommand>,
+) -> Result<command_service::NormalizeTimelineLevelResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    command_service::normalize_timeline_level(&state, command)
+        .await
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub async fn command_timeline_node_lock(
     app: tauri::AppHandle,
@@ -113,6 +183,17 @@ pub async fn command_timeline_apply_children(
         .map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub async fn command_timeline_apply_children_preview(
+    app: tauri::AppHandle,
+    command: command_service::ApplyTimelineChildrenRequestCommand,
+) -> Result<ApplyTimelineChildrenPreview, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    command_service::preview_apply_timeline_children(&state, command)
+        .await
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub async fn command_timeline_split_node(
     app: tauri::AppHandle,
@@ -124,6 +205,28 @@ pub async fn command_timeline_split_node(
         .map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub async fn command_timeline_duplicate_node(
+    app: tauri::AppHandle,
+    command: CommandEnvelope<DuplicateTimelineNodeCommand>,
+) -> Result<command_service::DuplicateTimelineNodeResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    command_service::duplicate_timeline_node(&state, command)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn command_timeline_tracks_collapsed(
+    app: tauri::AppHandle,
+    collapsed: bool,
+) -> Result<Vec<Track>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    command_service::set_all_tracks_collapsed(&state, collapsed)
+        .await
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub async fn command_timeline_playhead(
     app: tauri::AppHandle,
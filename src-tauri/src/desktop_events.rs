@@ -364,7 +364,8 @@ fn graph_renderer_command_selected_node_update(
 fn should_refresh_graph_renderer_projection(event: &ServerEvent) -> bool {
     matches!(
         event,
-        ServerEvent::BibleChanged
+        ServerEvent::ProjectChanged
+            | ServerEvent::BibleChanged
             | ServerEvent::HierarchyChanged
             | ServerEvent::StoryChanged
             | ServerEvent::TimelineChanged
@@ -378,7 +379,8 @@ fn should_refresh_graph_renderer_projection(event: &ServerEvent) -> bool {
 fn should_refresh_timeline_renderer_projection(event: &ServerEvent) -> bool {
     matches!(
         event,
-        ServerEvent::TimelineChanged
+        ServerEvent::ProjectChanged
+            | ServerEvent::TimelineChanged
             | ServerEvent::HierarchyChanged
             | ServerEvent::ContextInfluenceChanged { .. }
             | ServerEvent::TimelineSelectionChanged { .. }
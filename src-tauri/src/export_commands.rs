@@ -1,5 +1,8 @@
-use eidetic_server::export_service;
+use eidetic_core::story::arc::ArcId;
+use eidetic_core::timeline::node::StoryLevel;
+use eidetic_server::backend_error::BackendError;
 use eidetic_server::state::AppState;
+use eidetic_server::{command_service, export_service};
 use tauri::Manager;
 
 use crate::error::CommandError;
@@ -11,3 +14,57 @@ pub async fn export_pdf(app: tauri::AppHandle) -> Result<Vec<u8>, CommandError>
         .await
         .map_err(CommandError::from)
 }
+
+#[tauri::command]
+pub async fn export_bible_markdown(app: tauri::AppHandle) -> Result<String, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    export_service::export_bible_markdown(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn export_bible_json(app: tauri::AppHandle) -> Result<serde_json::Value, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    export_service::export_bible_json(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn export_bible_transfer(
+    app: tauri::AppHandle,
+) -> Result<command_service::BibleTransfer, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    export_service::export_bible_transfer(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn export_beats_csv(
+    app: tauri::AppHandle,
+    level: Option<String>,
+) -> Result<String, CommandError> {
+    let level = match level {
+        Some(level) => level
+            .parse::<StoryLevel>()
+            .map_err(|error| BackendError::bad_request(error.to_string()))?,
+        None => StoryLevel::Scene,
+    };
+    let state = app.state::<AppState>().inner().clone();
+    export_service::export_beat_sheet_csv(&state, level)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn export_arc_fountain(
+    app: tauri::AppHandle,
+    arc_id: ArcId,
+) -> Result<String, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    export_service::export_arc_fountain(&state, arc_id)
+        .await
+        .map_err(CommandError::from)
+}
@@ -80,9 +80,11 @@ pub fn run() {
                 {
                     let _ = timeline_owner.stop();
                 }
-                let task_supervisor = window.state::<AppState>().task_supervisor.clone();
+                let app_state = window.state::<AppState>().inner().clone();
                 tauri::async_runtime::spawn(async move {
-                    task_supervisor.shutdown_all().await;
+                    app_state.force_save().await;
+                    app_state.release_project_lock();
+                    app_state.task_supervisor.shutdown_all().await;
                 });
             }
         })
@@ -90,18 +92,54 @@ pub fn run() {
             health::desktop_health,
             project_commands::project_create,
             project_commands::project_get,
+            project_commands::project_current,
             project_commands::project_update,
+            project_commands::project_ai_settings,
+            project_commands::project_ai_settings_update,
             project_commands::project_save,
+            project_commands::project_commit_node_content,
             project_commands::project_load,
+            project_commands::project_open_directory,
             project_commands::project_list,
+            project_commands::project_defaults,
+            project_commands::project_defaults_update,
+            project_commands::project_checkpoint_config,
+            project_commands::project_checkpoint_config_update,
+            project_commands::project_checkpoints,
+            project_commands::project_episodes,
+            project_commands::project_episode_add,
+            project_commands::project_episode_switch,
+            project_commands::project_search_content,
             ai_commands::ai_status,
+            ai_commands::ai_preload,
             ai_commands::ai_config_update,
             ai_commands::ai_context_preview,
+            ai_commands::ai_debug_request_body,
             ai_commands::ai_generate_content,
+            ai_commands::ai_continue_content,
+            ai_commands::ai_generation_log,
+            ai_commands::ai_ready_nodes,
+            ai_commands::ai_generation_queue,
+            ai_commands::ai_generation_enqueue,
+            ai_commands::ai_generation_cancel,
             ai_commands::ai_generate_children,
+            ai_commands::ai_brainstorm_notes,
+            ai_commands::ai_summarize_to_notes,
+            ai_commands::ai_arc_treatment,
+            ai_commands::ai_expand_premise,
+            ai_commands::ai_expand_premise_commit,
             ai_commands::ai_generate_batch,
+            ai_commands::ai_continuity_audit,
+            ai_commands::ai_notes_drift,
+            ai_commands::ai_notes_drift_sweep,
+            ai_commands::ai_suggest_arcs,
             model_commands::model_list,
             export_commands::export_pdf,
+            export_commands::export_bible_markdown,
+            export_commands::export_bible_json,
+            export_commands::export_bible_transfer,
+            export_commands::export_beats_csv,
+            export_commands::export_arc_fountain,
             graph_renderer_commands::graph_renderer_open,
             graph_renderer_commands::graph_renderer_focus,
             graph_renderer_commands::graph_renderer_close,
@@ -118,7 +156,9 @@ pub fn run() {
             timeline_renderer_commands::timeline_renderer_close,
             reference_commands::reference_list,
             reference_commands::reference_upload,
+            reference_commands::reference_set_scope,
             reference_commands::reference_delete,
+            reference_commands::reference_reembed_all,
             commands::object_script_story::command_object_field,
             commands::object_script_story::command_script_block,
             commands::object_script_story::command_script_lock,
@@ -139,6 +179,8 @@ pub fn run() {
             commands::bible::command_bible_graph_delete_edge,
             commands::bible::command_bible_graph_snapshot_field,
             commands::bible::command_bible_graph_roots,
+            commands::bible::command_bible_promote_locations,
+            commands::bible::command_bible_import,
             commands::context::command_context_evaluation,
             commands::semantic::command_bible_reference_proposal_create,
             commands::semantic::command_bible_reference_proposal_reject,
@@ -151,15 +193,25 @@ pub fn run() {
             commands::timeline::command_timeline_create_child_from_parent,
             commands::timeline::command_timeline_node_range,
             commands::timeline::command_timeline_node_lock,
+            commands::timeline::command_timeline_node_pin,
+            commands::timeline::command_timeline_node_scene_number,
+            commands::timeline::command_timeline_node_generation_order,
+            commands::timeline::command_timeline_renumber_scenes,
+            commands::timeline::command_timeline_normalize,
+            commands::timeline::command_timeline_tracks_collapsed,
+            commands::timeline::command_timeline_node_arc_tag,
             commands::timeline::command_timeline_node_notes,
             commands::timeline::command_timeline_delete_node,
             commands::timeline::command_timeline_create_relationship,
             commands::timeline::command_timeline_delete_relationship,
             commands::timeline::command_timeline_apply_children,
+            commands::timeline::command_timeline_apply_children_preview,
             commands::timeline::command_timeline_split_node,
+            commands::timeline::command_timeline_duplicate_node,
             commands::timeline::command_timeline_playhead,
             projections::story_script::projection_object_field,
             projections::story_script::projection_script_document,
+            projections::bible::projection_bible_entity_presence,
             projections::bible::projection_bible_graph_node,
             projections::bible::projection_bible_graph_nodes,
             projections::bible::projection_bible_graph_schemas,
@@ -170,12 +222,26 @@ pub fn run() {
             projections::semantic::projection_propagation_proposals,
             projections::semantic::projection_semantic_dependencies,
             projections::semantic::projection_child_plans,
+            projections::semantic::projection_child_plan_ranges,
             projections::story_script::projection_story_arcs,
             projections::story_script::projection_story_arc_progression,
+            projections::story_script::projection_arc_coverage,
+            projections::story_script::projection_arc_entity_evolution,
             projections::story_script::projection_change_review,
             projections::affect::projection_affect,
             projections::affect::projection_affect_proposals,
             projections::timeline::projection_timeline_render,
+            projections::timeline::projection_timeline_nodes_in_range,
+            projections::timeline::projection_timeline_pinned,
+            projections::timeline::projection_timeline_unpaid_setups,
+            projections::timeline::projection_timeline_context_at,
+            projections::timeline::projection_timeline_tree,
+            projections::timeline::projection_timeline_overview,
+            projections::timeline::projection_timeline_relationship_types,
+            projections::timeline::projection_timeline_tracks,
+            projections::timeline::projection_timeline_structure_validate,
+            projections::timeline::projection_beat_type_warnings,
+            projections::timeline::projection_node_staleness,
             projections::timeline::projection_selected_node
         ])
         .run(tauri::generate_context!())
@@ -1,7 +1,13 @@
+use eidetic_core::project::{EpisodeSummary, ProjectAiSettings};
+use eidetic_server::checkpoint_service::{self, CheckpointConfigUpdate, CheckpointEntry};
 use eidetic_server::project_service::{
-    self, CreateProjectRequest, LoadProjectRequest, SaveProjectRequest, UpdateProjectRequest,
+    self, AddEpisodeRequest, CommitNodeContentRequest, CommitNodeContentResponse,
+    CreateProjectRequest, CurrentProject, LoadProjectRequest, OpenProjectDirectoryRequest,
+    ProjectDefaultsUpdate, ProjectListQuery, SaveProjectRequest, SwitchEpisodeRequest,
+    UpdateProjectRequest,
 };
-use eidetic_server::state::AppState;
+use eidetic_server::search_service::{self, ContentSearchHit, ContentSearchRequest, SearchSource};
+use eidetic_server::state::{AppState, CheckpointConfig, ProjectDefaults};
 use tauri::Manager;
 
 use crate::error::CommandError;
@@ -10,12 +16,20 @@ use crate::error::CommandError;
 pub async fn project_create(
     app: tauri::AppHandle,
     name: String,
-    template: String,
+    template: Option<String>,
+    total_duration_ms: Option<u64>,
 ) -> Result<serde_json::Value, CommandError> {
     let state = app.state::<AppState>().inner().clone();
-    project_service::create_project(&state, CreateProjectRequest { name, template })
-        .await
-        .map_err(CommandError::from)
+    project_service::create_project(
+        &state,
+        CreateProjectRequest {
+            name,
+            template,
+            total_duration_ms,
+        },
+    )
+    .await
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -24,6 +38,12 @@ pub fn project_get(app: tauri::AppHandle) -> Result<serde_json::Value, CommandEr
     project_service::get_project(&state).map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub fn project_current(app: tauri::AppHandle) -> Result<Option<CurrentProject>, CommandError> {
+    let state = app.state::<AppState>();
+    Ok(project_service::current_project(&state))
+}
+
 #[tauri::command]
 pub fn project_update(
     app: tauri::AppHandle,
@@ -35,6 +55,21 @@ pub fn project_update(
         .map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub fn project_ai_settings(app: tauri::AppHandle) -> Result<ProjectAiSettings, CommandError> {
+    let state = app.state::<AppState>();
+    project_service::get_project_ai_settings(&state).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub fn project_ai_settings_update(
+    app: tauri::AppHandle,
+    settings: ProjectAiSettings,
+) -> Result<ProjectAiSettings, CommandError> {
+    let state = app.state::<AppState>();
+    project_service::update_project_ai_settings(&state, settings).map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub async fn project_save(
     app: tauri::AppHandle,
@@ -46,18 +81,130 @@ pub async fn project_save(
         .map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub async fn project_commit_node_content(
+    app: tauri::AppHandle,
+    request: CommitNodeContentRequest,
+) -> Result<CommitNodeContentResponse, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    project_service::commit_node_content(&state, request)
+        .await
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub async fn project_load(
     app: tauri::AppHandle,
     path: String,
+    force: Option<bool>,
+) -> Result<serde_json::Value, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    let force = force.unwrap_or_default();
+    project_service::load_project(&state, LoadProjectRequest { path, force })
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn project_open_directory(
+    app: tauri::AppHandle,
+    path: String,
+    force: Option<bool>,
 ) -> Result<serde_json::Value, CommandError> {
     let state = app.state::<AppState>().inner().clone();
-    project_service::load_project(&state, LoadProjectRequest { path })
+    let force = force.unwrap_or_default();
+    project_service::open_project_directory(&state, OpenProjectDirectoryRequest { path, force })
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn project_list(
+    query: Option<ProjectListQuery>,
+) -> Result<serde_json::Value, CommandError> {
+    project_service::list_projects(query.unwrap_or_default())
         .await
         .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn project_list() -> serde_json::Value {
-    project_service::list_projects().await
+pub fn project_defaults(app: tauri::AppHandle) -> ProjectDefaults {
+    let state = app.state::<AppState>();
+    project_service::get_project_defaults(&state)
+}
+
+#[tauri::command]
+pub fn project_defaults_update(
+    app: tauri::AppHandle,
+    updates: ProjectDefaultsUpdate,
+) -> Result<ProjectDefaults, CommandError> {
+    let state = app.state::<AppState>();
+    project_service::update_project_defaults(&state, updates).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub fn project_checkpoint_config(app: tauri::AppHandle) -> CheckpointConfig {
+    let state = app.state::<AppState>();
+    checkpoint_service::get_checkpoint_config(&state)
+}
+
+#[tauri::command]
+pub fn project_checkpoint_config_update(
+    app: tauri::AppHandle,
+    updates: CheckpointConfigUpdate,
+) -> Result<CheckpointConfig, CommandError> {
+    let state = app.state::<AppState>();
+    checkpoint_service::update_checkpoint_config(&state, updates).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub fn project_checkpoints(app: tauri::AppHandle) -> Result<Vec<CheckpointEntry>, CommandError> {
+    let state = app.state::<AppState>();
+    checkpoint_service::list_checkpoints(&state).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub fn project_episodes(app: tauri::AppHandle) -> Result<Vec<EpisodeSummary>, CommandError> {
+    let state = app.state::<AppState>();
+    project_service::list_episodes(&state).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub fn project_episode_add(
+    app: tauri::AppHandle,
+    title: String,
+    template: Option<String>,
+) -> Result<EpisodeSummary, CommandError> {
+    let state = app.state::<AppState>();
+    project_service::add_episode(&state, AddEpisodeRequest { title, template })
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn project_episode_switch(
+    app: tauri::AppHandle,
+    episode_id: uuid::Uuid,
+) -> Result<serde_json::Value, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    project_service::switch_episode(&state, SwitchEpisodeRequest { episode_id })
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn project_search_content(
+    app: tauri::AppHandle,
+    query: String,
+    source: Option<SearchSource>,
+) -> Result<Vec<ContentSearchHit>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    search_service::search_content(
+        &state,
+        ContentSearchRequest {
+            query,
+            source: source.unwrap_or_default(),
+        },
+    )
+    .await
+    .map_err(CommandError::from)
 }
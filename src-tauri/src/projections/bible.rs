@@ -1,9 +1,12 @@
 use eidetic_core::contracts::{
-    BibleGraphNodeListProjection, BibleGraphSchemaListProjection, BibleNodeDetailProjection,
-    BibleRenderGraphProjection, BibleRenderGraphProjectionRequest, ProjectionEnvelope,
+    BibleEntityPresenceProjection, BibleGraphNodeListProjection, BibleGraphSchemaListProjection,
+    BibleNodeDetailProjection, BibleRenderGraphProjection, BibleRenderGraphProjectionRequest,
+    ProjectionEnvelope,
 };
 use eidetic_server::bible_render_graph_projection;
-use eidetic_server::projection_service::{self, BibleGraphNodeProjectionRequest};
+use eidetic_server::projection_service::{
+    self, BibleEntityPresenceRequest, BibleGraphNodeProjectionRequest,
+};
 use eidetic_server::state::AppState;
 use tauri::Manager;
 
@@ -20,6 +23,17 @@ pub async fn projection_bible_graph_node(
         .map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub async fn projection_bible_entity_presence(
+    app: tauri::AppHandle,
+    query: BibleEntityPresenceRequest,
+) -> Result<BibleEntityPresenceProjection, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::bible_entity_presence_projection(&state, query)
+        .await
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub async fn projection_bible_graph_nodes(
     app: tauri::AppHandle,
@@ -1,4 +1,4 @@
-use eidetic_core::ai::backend::ChildPlanListProjection;
+use eidetic_core::ai::backend::{ChildPlan, ChildPlanListProjection, ChildPlanRangePreview};
 use eidetic_core::contracts::{
     BibleReferenceProposalListProjection, ProjectionEnvelope, PropagationProposalListProjection,
     SemanticDependencyProjection,
@@ -49,3 +49,14 @@ pub async fn projection_child_plans(
         .await
         .map_err(CommandError::from)
 }
+
+#[tauri::command]
+pub async fn projection_child_plan_ranges(
+    app: tauri::AppHandle,
+    plan: ChildPlan,
+) -> Result<ChildPlanRangePreview, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::child_plan_range_preview(&state, plan)
+        .await
+        .map_err(CommandError::from)
+}
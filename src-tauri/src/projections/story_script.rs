@@ -1,9 +1,11 @@
 use eidetic_core::contracts::{
-    ChangeReviewProjection, ProjectionEnvelope, ScriptDocumentProjection, StoryArcListProjection,
+    ArcCoverageProjection, ArcEntityEvolutionProjection, ChangeReviewProjection,
+    ProjectionEnvelope, ScriptDocumentProjection, StoryArcListProjection,
     StoryArcProgressionProjection,
 };
 use eidetic_server::projection_service::{
-    self, ObjectFieldProjectionRequest, ScriptDocumentProjectionRequest,
+    self, ArcCoverageQuery, ArcEntityEvolutionQuery, ObjectFieldProjectionRequest,
+    ScriptDocumentProjectionRequest,
 };
 use eidetic_server::state::AppState;
 use tauri::Manager;
@@ -61,3 +63,25 @@ pub async fn projection_change_review(
         .await
         .map_err(CommandError::from)
 }
+
+#[tauri::command]
+pub async fn projection_arc_coverage(
+    app: tauri::AppHandle,
+    query: ArcCoverageQuery,
+) -> Result<ArcCoverageProjection, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::arc_coverage_projection(&state, query)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn projection_arc_entity_evolution(
+    app: tauri::AppHandle,
+    query: ArcEntityEvolutionQuery,
+) -> Result<ArcEntityEvolutionProjection, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::arc_entity_evolution_projection(&state, query)
+        .await
+        .map_err(CommandError::from)
+}
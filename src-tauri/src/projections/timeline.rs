@@ -1,7 +1,18 @@
 use eidetic_core::contracts::{
-    ProjectionEnvelope, SelectedNodeEditorProjection, TimelineRenderProjection,
+    ProjectionEnvelope, SelectedNodeEditorProjection, TimelineContextAtProjection,
+    TimelineOverviewProjection, TimelineRenderProjection,
+};
+use eidetic_core::timeline::TimelineTreeNode;
+use eidetic_core::timeline::beat_palette::BeatTypeWarning;
+use eidetic_core::timeline::node::StoryNode;
+use eidetic_core::timeline::relationship::RelationshipTypeInfo;
+use eidetic_core::timeline::structure::StructureNormalization;
+use eidetic_core::timeline::track::Track;
+use eidetic_server::projection_service::{
+    self, BeatTypeWarningsQuery, EpisodeStructureValidationRequest, NodeStaleness,
+    NodeStalenessQuery, SelectedNodeEditorProjectionRequest, TimelineContextAtQuery,
+    TimelineOverviewQuery, TimelineRangeQuery, TimelineTreeQuery,
 };
-use eidetic_server::projection_service::{self, SelectedNodeEditorProjectionRequest};
 use eidetic_server::state::AppState;
 use tauri::Manager;
 
@@ -17,6 +28,110 @@ pub async fn projection_timeline_render(
         .map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub async fn projection_timeline_nodes_in_range(
+    app: tauri::AppHandle,
+    query: TimelineRangeQuery,
+) -> Result<Vec<StoryNode>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::timeline_nodes_in_range(&state, query)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn projection_timeline_pinned(
+    app: tauri::AppHandle,
+) -> Result<Vec<StoryNode>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::timeline_pinned_nodes(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn projection_timeline_unpaid_setups(
+    app: tauri::AppHandle,
+) -> Result<Vec<StoryNode>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::timeline_unpaid_setups(&state)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn projection_timeline_context_at(
+    app: tauri::AppHandle,
+    query: TimelineContextAtQuery,
+) -> Result<TimelineContextAtProjection, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::timeline_context_at(&state, query)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn projection_timeline_tree(
+    app: tauri::AppHandle,
+    query: Option<TimelineTreeQuery>,
+) -> Result<Vec<TimelineTreeNode>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::timeline_node_tree(&state, query.unwrap_or_default())
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub fn projection_timeline_relationship_types() -> Vec<RelationshipTypeInfo> {
+    projection_service::relationship_types()
+}
+
+#[tauri::command]
+pub fn projection_timeline_tracks(app: tauri::AppHandle) -> Result<Vec<Track>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::timeline_tracks(&state).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub fn projection_timeline_structure_validate(
+    request: EpisodeStructureValidationRequest,
+) -> Result<StructureNormalization, CommandError> {
+    projection_service::validate_episode_structure(request).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn projection_timeline_overview(
+    app: tauri::AppHandle,
+    query: TimelineOverviewQuery,
+) -> Result<TimelineOverviewProjection, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::timeline_overview(&state, query)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn projection_beat_type_warnings(
+    app: tauri::AppHandle,
+    query: BeatTypeWarningsQuery,
+) -> Result<Vec<BeatTypeWarning>, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::beat_type_warnings(&state, query)
+        .await
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub async fn projection_node_staleness(
+    app: tauri::AppHandle,
+    query: NodeStalenessQuery,
+) -> Result<NodeStaleness, CommandError> {
+    let state = app.state::<AppState>().inner().clone();
+    projection_service::node_staleness(&state, query)
+        .await
+        .map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub async fn projection_selected_node(
     app: tauri::AppHandle,
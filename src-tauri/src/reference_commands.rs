@@ -1,5 +1,8 @@
 use eidetic_core::reference::ReferenceDocument;
-use eidetic_server::reference_service::{self, DeleteReferenceResponse, UploadReferenceRequest};
+use eidetic_server::reference_service::{
+    self, DeleteReferenceResponse, ReembedReferencesResponse, SetReferenceScopeRequest,
+    UploadReferenceRequest,
+};
 use eidetic_server::state::AppState;
 use tauri::Manager;
 use uuid::Uuid;
@@ -21,6 +24,15 @@ pub fn reference_upload(
     reference_service::upload_reference(&state, request).map_err(CommandError::from)
 }
 
+#[tauri::command]
+pub fn reference_set_scope(
+    app: tauri::AppHandle,
+    request: SetReferenceScopeRequest,
+) -> Result<ReferenceDocument, CommandError> {
+    let state = app.state::<AppState>();
+    reference_service::set_reference_scope(&state, request).map_err(CommandError::from)
+}
+
 #[tauri::command]
 pub fn reference_delete(
     app: tauri::AppHandle,
@@ -29,3 +41,11 @@ pub fn reference_delete(
     let state = app.state::<AppState>();
     reference_service::delete_reference(&state, id).map_err(CommandError::from)
 }
+
+#[tauri::command]
+pub fn reference_reembed_all(
+    app: tauri::AppHandle,
+) -> Result<ReembedReferencesResponse, CommandError> {
+    let state = app.state::<AppState>();
+    reference_service::reembed_all_references(&state).map_err(CommandError::from)
+}
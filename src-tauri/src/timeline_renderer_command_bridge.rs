@@ -141,6 +141,7 @@ fn timeline_renderer_mutation_command(
                 node_id,
                 start_ms,
                 end_ms,
+                reanchor_snapshots: false,
             }),
         )),
         TimelineRendererCommand::DeleteNode { node_id } => {
@@ -1,12 +1,37 @@
+import type { BibleGraphNodeId } from './bibleGraphTypes.js';
+import type { ArcId, StoryArc } from './storyArcTypes.js';
+
 export type BackendType = 'llama_cpp' | 'open_router';
 
+export interface OutlineWordTargets {
+  premise: number;
+  act: number;
+  sequence: number;
+  scene: number;
+}
+
 export interface AiConfig {
   backend_type: BackendType;
   model: string;
   temperature: number;
   max_tokens: number;
+  context_limit: number;
+  max_siblings: number;
+  sibling_preview_chars: number;
+  stop_sequences: string[];
+  json_mode: boolean;
+  auto_continue_truncated: boolean;
+  /** Anchor an "Introduced" snapshot where an accepted reference first links an entity. */
+  introduction_snapshots: boolean;
+  /** Sizes beat generations from their time budget; 0 uses the flat max_tokens. */
+  words_per_minute: number;
+  /** Outline length targets above beats; 0 leaves a level untargeted. */
+  outline_word_targets: OutlineWordTargets;
+  embedding_model: string;
   base_url: string;
   api_key: string | null;
+  /** Tried when this backend cannot start a request; only its connection settings are used. */
+  fallback?: AiConfig | null;
 }
 
 export interface AiStatus {
@@ -15,6 +40,23 @@ export interface AiStatus {
   connected: boolean;
   message?: string;
   error?: string;
+  fallbacks?: AiStatus[];
+}
+
+export interface AiPreloadStatus {
+  model: string;
+  embedding_model: string;
+  /** Set when the embedding model could not be warmed. */
+  embedding_error?: string | null;
+}
+
+export interface AiContextPreview {
+  system: string;
+  user: string;
+  estimated_tokens: number;
+  prompt_budget: number;
+  trimmed_sections: string[];
+  warning: string | null;
 }
 
 export interface ModelEntry {
@@ -30,3 +72,73 @@ export interface ModelListResponse {
   models: ModelEntry[];
   total_count: number;
 }
+
+export interface CharacterProposal {
+  name: string;
+  description: string;
+}
+
+export interface PremiseExpansion {
+  logline: string;
+  arcs: StoryArc[];
+  characters: CharacterProposal[];
+}
+
+export interface ArcTreatment {
+  arc_id: ArcId;
+  treatment: string;
+}
+
+export interface ContinuityAuditResponse {
+  status: string;
+  entity_count: number;
+  check_count: number;
+}
+
+export interface NotesDriftVerdict {
+  fulfilled: boolean;
+  /** Each part of the notes the content drops or contradicts. */
+  specifics: string[];
+}
+
+/** An arc the model judges a node to advance, returned for review before tagging. */
+export interface SuggestedArcTag {
+  arc_id: string;
+  arc_name: string;
+  /** From 0 to 1. */
+  confidence: number;
+  already_tagged: boolean;
+}
+
+export interface NotesDriftSweepResponse {
+  status: string;
+  check_count: number;
+}
+
+export interface GenerationLogEntry {
+  id: number;
+  node_id: string;
+  timestamp_ms: number;
+  model: string;
+  system_prompt: string;
+  user_prompt: string;
+  output: string;
+  prompt_tokens: number;
+  output_tokens: number;
+}
+
+export type GenerationQueueStatus = 'pending' | 'running' | 'done' | 'failed';
+
+/** One node in the persistent generation queue, in queue order. */
+export interface GenerationQueueItem {
+  node_id: string;
+  status: GenerationQueueStatus;
+  /** 1-based place among pending items. */
+  position?: number;
+  error?: string;
+}
+
+export interface PremiseExpansionCommitResponse {
+  arc_ids: ArcId[];
+  character_node_ids: BibleGraphNodeId[];
+}
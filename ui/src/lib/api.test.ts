@@ -1,21 +1,49 @@
 import { afterEach, describe, expect, it, vi } from 'vitest';
 
 import {
+  cancelQueuedGeneration,
+  commitNodeContent,
+  checkNotesDrift,
+  continueContent,
+  addEpisode,
   createProject,
   deleteReference,
+  enqueueGeneration,
+  exportArcFountain,
+  exportBibleTransfer,
   exportPdf,
+  generateArcTreatment,
   generateBatch,
   generateContent,
   generateChildren,
   getAiContext,
   getAiStatus,
+  getCheckpointConfig,
+  getGenerationLog,
+  getGenerationQueue,
+  getReadyNodes,
+  getCurrentProject,
   getProject,
+  getProjectAiSettings,
+  listCheckpoints,
+  listEpisodes,
   listModels,
   listProjects,
   listReferences,
+  openProjectDirectory,
+  preloadAiModels,
+  reembedReferences,
+  setReferenceScope,
   saveProject,
+  startContinuityAudit,
+  startNotesDriftSweep,
+  suggestArcTags,
+  summarizeToNotes,
+  switchEpisode,
   updateAiConfig,
+  updateCheckpointConfig,
   updateProject,
+  updateProjectAiSettings,
   uploadReference,
 } from './api.js';
 
@@ -67,6 +95,8 @@ describe('api request handling', () => {
     await updateProject({ name: 'Renamed', premise: 'New premise' });
     await saveProject('/tmp/project.db');
     await listProjects();
+    await getProjectAiSettings();
+    await updateProjectAiSettings({ model: 'show-model', temperature: 0.4 });
 
     expect(invoke).toHaveBeenNthCalledWith(1, 'project_create', {
       name: 'Created',
@@ -80,9 +110,112 @@ describe('api request handling', () => {
       path: '/tmp/project.db',
     });
     expect(invoke).toHaveBeenNthCalledWith(4, 'project_list', undefined);
+    expect(invoke).toHaveBeenNthCalledWith(5, 'project_ai_settings', undefined);
+    expect(invoke).toHaveBeenNthCalledWith(6, 'project_ai_settings_update', {
+      settings: { model: 'show-model', temperature: 0.4 },
+    });
     expect(fetchMock).not.toHaveBeenCalled();
   });
 
+  it('passes a custom episode duration when creating a project', async () => {
+    const invoke = vi.fn().mockResolvedValue({ name: 'Short', premise: '' });
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await createProject('Short', 'animated', 660_000);
+
+    expect(invoke).toHaveBeenCalledWith('project_create', {
+      name: 'Short',
+      template: 'animated',
+      totalDurationMs: 660_000,
+    });
+  });
+
+  it('passes project list sorting and paging to the desktop command', async () => {
+    const invoke = vi.fn().mockResolvedValue([]);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await listProjects({ sort: 'name', direction: 'asc', offset: 20, limit: 10 });
+
+    expect(invoke).toHaveBeenCalledWith('project_list', {
+      query: { sort: 'name', direction: 'asc', offset: 20, limit: 10 },
+    });
+  });
+
+  it('opens a project directory and lists projects outside the default storage', async () => {
+    const invoke = vi.fn().mockResolvedValue([]);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await openProjectDirectory('/home/ada/Dropbox/Pilot');
+    await listProjects({ base_dir: '/home/ada/Dropbox' });
+    await openProjectDirectory('/home/ada/Dropbox/Pilot', true);
+
+    expect(invoke).toHaveBeenNthCalledWith(1, 'project_open_directory', {
+      path: '/home/ada/Dropbox/Pilot',
+    });
+    expect(invoke).toHaveBeenNthCalledWith(2, 'project_list', {
+      query: { base_dir: '/home/ada/Dropbox' },
+    });
+    expect(invoke).toHaveBeenNthCalledWith(3, 'project_open_directory', {
+      path: '/home/ada/Dropbox/Pilot',
+      force: true,
+    });
+  });
+
+  it('reads and updates auto-checkpoint settings through desktop commands', async () => {
+    const invoke = vi.fn().mockResolvedValue([]);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await getCheckpointConfig();
+    await updateCheckpointConfig({ interval_minutes: 15, retention: 8 });
+    await listCheckpoints();
+
+    expect(invoke).toHaveBeenNthCalledWith(1, 'project_checkpoint_config', undefined);
+    expect(invoke).toHaveBeenNthCalledWith(2, 'project_checkpoint_config_update', {
+      updates: { interval_minutes: 15, retention: 8 },
+    });
+    expect(invoke).toHaveBeenNthCalledWith(3, 'project_checkpoints', undefined);
+  });
+
+  it('lists, adds and switches episodes through desktop commands', async () => {
+    const invoke = vi.fn().mockResolvedValue([]);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await listEpisodes();
+    await addEpisode('Episode 2');
+    await addEpisode('Episode 3', 'animated');
+    await switchEpisode('episode-2');
+
+    expect(invoke).toHaveBeenNthCalledWith(1, 'project_episodes', undefined);
+    expect(invoke).toHaveBeenNthCalledWith(2, 'project_episode_add', { title: 'Episode 2' });
+    expect(invoke).toHaveBeenNthCalledWith(3, 'project_episode_add', {
+      title: 'Episode 3',
+      template: 'animated',
+    });
+    expect(invoke).toHaveBeenNthCalledWith(4, 'project_episode_switch', {
+      episodeId: 'episode-2',
+    });
+  });
+
   it('uses desktop AI status and config commands when Tauri transport is available', async () => {
     const invoke = vi
       .fn()
@@ -97,6 +230,16 @@ describe('api request handling', () => {
         model: 'served-model',
         temperature: 0.4,
         max_tokens: 2048,
+        context_limit: 16384,
+        max_siblings: 6,
+        sibling_preview_chars: 200,
+        stop_sequences: [],
+        json_mode: true,
+        auto_continue_truncated: false,
+        introduction_snapshots: false,
+        words_per_minute: 250,
+        outline_word_targets: { premise: 300, act: 300, sequence: 200, scene: 150 },
+        embedding_model: 'nomic-embed-text',
         base_url: 'http://127.0.0.1:18080/v1',
         api_key: null,
       });
@@ -118,6 +261,24 @@ describe('api request handling', () => {
     expect(fetchMock).not.toHaveBeenCalled();
   });
 
+  it('preloads AI models through the desktop command', async () => {
+    const status = {
+      model: 'served-model',
+      embedding_model: 'nomic-embed-text',
+      embedding_error: null,
+    };
+    const invoke = vi.fn().mockResolvedValue(status);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(preloadAiModels()).resolves.toEqual(status);
+
+    expect(invoke).toHaveBeenCalledWith('ai_preload', undefined);
+  });
+
   it('uses the desktop AI context command when Tauri transport is available', async () => {
     const invoke = vi.fn().mockResolvedValue({
       system: 'system prompt',
@@ -155,13 +316,210 @@ describe('api request handling', () => {
     vi.stubGlobal('fetch', fetchMock);
 
     await generateChildren('00000000-0000-0000-0000-000000000001');
+    await generateChildren('00000000-0000-0000-0000-000000000001', true);
 
-    expect(invoke).toHaveBeenCalledWith('ai_generate_children', {
-      request: { node_id: '00000000-0000-0000-0000-000000000001' },
+    expect(invoke).toHaveBeenNthCalledWith(1, 'ai_generate_children', {
+      request: { node_id: '00000000-0000-0000-0000-000000000001', preserve_existing: false },
+    });
+    expect(invoke).toHaveBeenNthCalledWith(2, 'ai_generate_children', {
+      request: { node_id: '00000000-0000-0000-0000-000000000001', preserve_existing: true },
     });
     expect(fetchMock).not.toHaveBeenCalled();
   });
 
+  it('requests an arc treatment through the desktop command', async () => {
+    const invoke = vi.fn().mockResolvedValue({
+      arc_id: '00000000-0000-0000-0000-000000000003',
+      treatment: 'Mara fights to keep the lighthouse.',
+    });
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(generateArcTreatment('00000000-0000-0000-0000-000000000003')).resolves.toEqual({
+      arc_id: '00000000-0000-0000-0000-000000000003',
+      treatment: 'Mara fights to keep the lighthouse.',
+    });
+    expect(invoke).toHaveBeenCalledWith('ai_arc_treatment', {
+      request: { arc_id: '00000000-0000-0000-0000-000000000003' },
+    });
+  });
+
+  it('starts a continuity audit through the desktop command', async () => {
+    const invoke = vi
+      .fn()
+      .mockResolvedValue({ status: 'started', entity_count: 2, check_count: 5 });
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(startContinuityAudit()).resolves.toEqual({
+      status: 'started',
+      entity_count: 2,
+      check_count: 5,
+    });
+    expect(invoke).toHaveBeenCalledWith('ai_continuity_audit', undefined);
+  });
+
+  it('checks notes drift for one node and sweeps the project', async () => {
+    const invoke = vi
+      .fn()
+      .mockResolvedValueOnce({ fulfilled: false, specifics: ['Jake never finds the locket.'] })
+      .mockResolvedValueOnce({ status: 'started', check_count: 4 });
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(checkNotesDrift('node-1')).resolves.toEqual({
+      fulfilled: false,
+      specifics: ['Jake never finds the locket.'],
+    });
+    await expect(startNotesDriftSweep()).resolves.toEqual({ status: 'started', check_count: 4 });
+
+    expect(invoke).toHaveBeenNthCalledWith(1, 'ai_notes_drift', {
+      request: { node_id: 'node-1' },
+    });
+    expect(invoke).toHaveBeenNthCalledWith(2, 'ai_notes_drift_sweep', undefined);
+  });
+
+  it('uses the desktop arc suggestion command when Tauri transport is available', async () => {
+    const suggestion = {
+      arc_id: 'arc-1',
+      arc_name: 'The Heist',
+      confidence: 0.9,
+      already_tagged: false,
+    };
+    const invoke = vi.fn().mockResolvedValue([suggestion]);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(suggestArcTags('node-1')).resolves.toEqual([suggestion]);
+
+    expect(invoke).toHaveBeenCalledWith('ai_suggest_arcs', {
+      request: { node_id: 'node-1' },
+    });
+  });
+
+  it('uses the desktop bible transfer export when Tauri transport is available', async () => {
+    const bible = { format_version: 1, entities: [], relations: [] };
+    const invoke = vi.fn().mockResolvedValue(bible);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(exportBibleTransfer()).resolves.toEqual(bible);
+
+    expect(invoke).toHaveBeenCalledWith('export_bible_transfer', undefined);
+  });
+
+  it('uses the desktop generation log command when Tauri transport is available', async () => {
+    const invoke = vi.fn().mockResolvedValue([]);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(getGenerationLog('00000000-0000-0000-0000-000000000001')).resolves.toEqual([]);
+    expect(invoke).toHaveBeenCalledWith('ai_generation_log', {
+      request: { node_id: '00000000-0000-0000-0000-000000000001' },
+    });
+  });
+
+  it('uses the desktop current project command', async () => {
+    const current = { name: 'Pilot', path: '/tmp/pilot/project.db', dirty: true, node_count: 12 };
+    const invoke = vi.fn().mockResolvedValueOnce(current).mockResolvedValueOnce(null);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(getCurrentProject()).resolves.toEqual(current);
+    await expect(getCurrentProject()).resolves.toBeNull();
+
+    expect(invoke).toHaveBeenCalledWith('project_current', undefined);
+  });
+
+  it('uses the desktop node content commit command', async () => {
+    const committed = { node_id: 'node-1', content: 'INT. HOUSE', status: 'HasContent' };
+    const invoke = vi.fn().mockResolvedValue(committed);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(commitNodeContent('node-1')).resolves.toEqual(committed);
+
+    expect(invoke).toHaveBeenCalledWith('project_commit_node_content', {
+      request: { node_id: 'node-1' },
+    });
+  });
+
+  it('uses the desktop notes summary command', async () => {
+    const invoke = vi.fn().mockResolvedValue({});
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await summarizeToNotes('node-1', true);
+
+    expect(invoke).toHaveBeenCalledWith('ai_summarize_to_notes', {
+      request: { node_id: 'node-1', overwrite: true },
+    });
+  });
+
+  it('uses the desktop ready nodes command with an optional level', async () => {
+    const invoke = vi.fn().mockResolvedValue([]);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(getReadyNodes()).resolves.toEqual([]);
+    await getReadyNodes('Scene');
+
+    expect(invoke).toHaveBeenNthCalledWith(1, 'ai_ready_nodes', { request: {} });
+    expect(invoke).toHaveBeenNthCalledWith(2, 'ai_ready_nodes', { request: { level: 'Scene' } });
+  });
+
+  it('uses the desktop generation queue commands', async () => {
+    const queue = [{ node_id: 'node-1', status: 'pending', position: 1 }];
+    const invoke = vi.fn().mockResolvedValue(queue);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(enqueueGeneration(['node-1'])).resolves.toEqual(queue);
+    await expect(getGenerationQueue()).resolves.toEqual(queue);
+    await cancelQueuedGeneration('node-1');
+
+    expect(invoke).toHaveBeenNthCalledWith(1, 'ai_generation_enqueue', {
+      request: { node_ids: ['node-1'] },
+    });
+    expect(invoke).toHaveBeenNthCalledWith(2, 'ai_generation_queue', undefined);
+    expect(invoke).toHaveBeenNthCalledWith(3, 'ai_generation_cancel', {
+      request: { node_id: 'node-1' },
+    });
+  });
+
   it('uses desktop script generation commands when Tauri transport is available', async () => {
     const invoke = vi
       .fn()
@@ -173,6 +531,10 @@ describe('api request handling', () => {
         status: 'started',
         parent_node_id: '00000000-0000-0000-0000-000000000002',
         child_count: 3,
+      })
+      .mockResolvedValueOnce({
+        status: 'started',
+        node_id: '00000000-0000-0000-0000-000000000001',
       });
     vi.stubGlobal('window', {
       __TAURI__: {
@@ -184,6 +546,7 @@ describe('api request handling', () => {
 
     await generateContent('00000000-0000-0000-0000-000000000001');
     await generateBatch('00000000-0000-0000-0000-000000000002');
+    await continueContent('00000000-0000-0000-0000-000000000001');
 
     expect(invoke).toHaveBeenNthCalledWith(1, 'ai_generate_content', {
       request: { node_id: '00000000-0000-0000-0000-000000000001' },
@@ -191,9 +554,37 @@ describe('api request handling', () => {
     expect(invoke).toHaveBeenNthCalledWith(2, 'ai_generate_batch', {
       request: { parent_node_id: '00000000-0000-0000-0000-000000000002' },
     });
+    expect(invoke).toHaveBeenNthCalledWith(3, 'ai_continue_content', {
+      request: { node_id: '00000000-0000-0000-0000-000000000001' },
+    });
     expect(fetchMock).not.toHaveBeenCalled();
   });
 
+  it('passes bible entity overrides with a generate request', async () => {
+    const invoke = vi.fn().mockResolvedValue({
+      status: 'started',
+      node_id: '00000000-0000-0000-0000-000000000001',
+    });
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await generateContent('00000000-0000-0000-0000-000000000001', {
+      include: ['node.character.ada'],
+      exclude: ['node.place.beach'],
+    });
+
+    expect(invoke).toHaveBeenCalledWith('ai_generate_content', {
+      request: {
+        node_id: '00000000-0000-0000-0000-000000000001',
+        include_entity_ids: ['node.character.ada'],
+        exclude_entity_ids: ['node.place.beach'],
+      },
+    });
+  });
+
   it('uses the desktop model list command when Tauri transport is available', async () => {
     const invoke = vi.fn().mockResolvedValue({
       models: [],
@@ -238,6 +629,20 @@ describe('api request handling', () => {
     await expect(blob.arrayBuffer()).resolves.toEqual(Uint8Array.from([37, 80, 68, 70]).buffer);
   });
 
+  it('exports one arc as Fountain through the desktop command', async () => {
+    const invoke = vi.fn().mockResolvedValue('Title: Pilot - Main Plot\n');
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    const blob = await exportArcFountain('arc-1');
+
+    expect(invoke).toHaveBeenCalledWith('export_arc_fountain', { arcId: 'arc-1' });
+    await expect(blob.text()).resolves.toBe('Title: Pilot - Main Plot\n');
+  });
+
   it('uses desktop reference commands when Tauri transport is available', async () => {
     const invoke = vi
       .fn()
@@ -248,7 +653,13 @@ describe('api request handling', () => {
         content: 'Keep turns precise.',
         doc_type: 'StyleGuide',
       })
-      .mockResolvedValueOnce({ deleted: true });
+      .mockResolvedValueOnce({ deleted: true })
+      .mockResolvedValueOnce({
+        status: 'started',
+        embedding_model: 'nomic-embed-text',
+        document_count: 0,
+        chunk_count: 0,
+      });
     vi.stubGlobal('window', {
       __TAURI__: {
         core: { invoke },
@@ -260,6 +671,11 @@ describe('api request handling', () => {
     await listReferences();
     await uploadReference('Tone Guide', 'Keep turns precise.', 'StyleGuide');
     await deleteReference('00000000-0000-0000-0000-000000000001');
+    await reembedReferences();
+    await uploadReference('Backstory', 'Raised at sea.', 'CharacterBible', {
+      arc_ids: ['arc-villain'],
+    });
+    await setReferenceScope('00000000-0000-0000-0000-000000000002', {});
 
     expect(invoke).toHaveBeenNthCalledWith(1, 'reference_list', undefined);
     expect(invoke).toHaveBeenNthCalledWith(2, 'reference_upload', {
@@ -272,6 +688,18 @@ describe('api request handling', () => {
     expect(invoke).toHaveBeenNthCalledWith(3, 'reference_delete', {
       id: '00000000-0000-0000-0000-000000000001',
     });
+    expect(invoke).toHaveBeenNthCalledWith(4, 'reference_reembed_all', undefined);
+    expect(invoke).toHaveBeenNthCalledWith(5, 'reference_upload', {
+      request: {
+        name: 'Backstory',
+        content: 'Raised at sea.',
+        doc_type: 'CharacterBible',
+        scope: { arc_ids: ['arc-villain'] },
+      },
+    });
+    expect(invoke).toHaveBeenNthCalledWith(6, 'reference_set_scope', {
+      request: { id: '00000000-0000-0000-0000-000000000002', scope: {} },
+    });
     expect(fetchMock).not.toHaveBeenCalled();
   });
 
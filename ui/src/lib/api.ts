@@ -1,22 +1,100 @@
-import type { AiConfig, AiStatus, ModelListResponse } from './aiTypes.js';
-import type { ChildPlan } from './childPlanningTypes.js';
+import type {
+  AiConfig,
+  AiContextPreview,
+  AiPreloadStatus,
+  AiStatus,
+  ArcTreatment,
+  CharacterProposal,
+  ContinuityAuditResponse,
+  GenerationLogEntry,
+  GenerationQueueItem,
+  ModelListResponse,
+  NotesDriftSweepResponse,
+  NotesDriftVerdict,
+  PremiseExpansion,
+  PremiseExpansionCommitResponse,
+  SuggestedArcTag,
+} from './aiTypes.js';
+import type { BibleExport, BibleTransfer } from './bibleGraphTypes.js';
+import type { ChildPlan, ChildPlanRangePreview } from './childPlanningTypes.js';
 import { invokeDesktop } from './desktopTransport.js';
-import type { Project, ReferenceDocument, ReferenceType } from './projectTypes.js';
+import type {
+  CheckpointConfig,
+  CheckpointEntry,
+  ContentSearchHit,
+  CurrentProject,
+  EpisodeSummary,
+  Project,
+  ProjectAiSettings,
+  ProjectDefaults,
+  ProjectEntry,
+  ProjectListQuery,
+  ReembedReferencesResponse,
+  ReferenceDocument,
+  ReferenceScope,
+  ReferenceType,
+  SearchSource,
+} from './projectTypes.js';
+import type { TimelineCommandResponse } from './timelineCommandTypes.js';
+import type { StoryArc } from './storyArcTypes.js';
+import type { CommittedNodeContent, StoryLevel, StoryNode } from './timelineTypes.js';
 
 // --- Project ---
 
-export function createProject(name: string, template: string): Promise<Project> {
-  return invokeDesktop<Project>('project_create', { name, template });
+/** `totalDurationMs` scales the template; it defaults to {@link getProjectDefaults}. */
+export function createProject(
+  name: string,
+  template: string,
+  totalDurationMs?: number,
+): Promise<Project> {
+  return invokeDesktop<Project>('project_create', {
+    name,
+    template,
+    ...(totalDurationMs === undefined ? {} : { totalDurationMs }),
+  });
 }
 
 export function getProject(): Promise<Project> {
   return invokeDesktop<Project>('project_get');
 }
 
+/** The active project's name, save path and unsaved state; `null` when none is open. */
+export function getCurrentProject(): Promise<CurrentProject | null> {
+  return invokeDesktop<CurrentProject | null>('project_current');
+}
+
 export function updateProject(updates: { name?: string; premise?: string }): Promise<Project> {
   return invokeDesktop<Project>('project_update', updates);
 }
 
+export function getProjectAiSettings(): Promise<ProjectAiSettings> {
+  return invokeDesktop<ProjectAiSettings>('project_ai_settings');
+}
+
+/** Replace the project's AI overrides; blank or missing fields use the global config. */
+export function updateProjectAiSettings(settings: ProjectAiSettings): Promise<ProjectAiSettings> {
+  return invokeDesktop<ProjectAiSettings>('project_ai_settings_update', { settings });
+}
+
+// --- Episodes ---
+
+export function listEpisodes(): Promise<EpisodeSummary[]> {
+  return invokeDesktop<EpisodeSummary[]>('project_episodes');
+}
+
+/** The new episode is parked; switch to it to edit its timeline. */
+export function addEpisode(title: string, template?: string): Promise<EpisodeSummary> {
+  return invokeDesktop<EpisodeSummary>('project_episode_add', {
+    title,
+    ...(template === undefined ? {} : { template }),
+  });
+}
+
+/** Resolves to the reloaded project with `episodeId` active. */
+export function switchEpisode(episodeId: string): Promise<Project> {
+  return invokeDesktop<Project>('project_episode_switch', { episodeId });
+}
+
 // --- References ---
 
 function referenceTypeToWireValue(docType: ReferenceType): string {
@@ -31,21 +109,83 @@ export function uploadReference(
   name: string,
   content: string,
   docType: ReferenceType,
+  scope?: ReferenceScope,
 ): Promise<ReferenceDocument> {
   const doc_type = referenceTypeToWireValue(docType);
   return invokeDesktop<ReferenceDocument>('reference_upload', {
-    request: { name, content, doc_type },
+    request: { name, content, doc_type, ...(scope === undefined ? {} : { scope }) },
   });
 }
 
+/** Limit a reference to generations for the given arcs or entities; `{}` makes it global. */
+export function setReferenceScope(id: string, scope: ReferenceScope): Promise<ReferenceDocument> {
+  return invokeDesktop<ReferenceDocument>('reference_set_scope', { request: { id, scope } });
+}
+
 export function deleteReference(id: string): Promise<{ deleted: boolean }> {
   return invokeDesktop<{ deleted: boolean }>('reference_delete', { id });
 }
 
+/** Rebuild reference embeddings with the current embedding model. */
+export function reembedReferences(): Promise<ReembedReferencesResponse> {
+  return invokeDesktop<ReembedReferencesResponse>('reference_reembed_all');
+}
+
 // --- AI ---
 
-export function generateContent(nodeId: string): Promise<{ status: string; node_id: string }> {
+/** Bible entities to force into, or keep out of, one generation's context. */
+export interface BibleEntityOverrides {
+  include?: string[];
+  exclude?: string[];
+}
+
+export function generateContent(
+  nodeId: string,
+  entities: BibleEntityOverrides = {},
+): Promise<{ status: string; node_id: string }> {
   return invokeDesktop<{ status: string; node_id: string }>('ai_generate_content', {
+    request: {
+      node_id: nodeId,
+      ...(entities.include ? { include_entity_ids: entities.include } : {}),
+      ...(entities.exclude ? { exclude_entity_ids: entities.exclude } : {}),
+    },
+  });
+}
+
+export function continueContent(nodeId: string): Promise<{ status: string; node_id: string }> {
+  return invokeDesktop<{ status: string; node_id: string }>('ai_continue_content', {
+    request: { node_id: nodeId },
+  });
+}
+
+/** Recent prompts and outputs for a node, newest first. */
+export function getGenerationLog(nodeId: string): Promise<GenerationLogEntry[]> {
+  return invokeDesktop<GenerationLogEntry[]>('ai_generation_log', {
+    request: { node_id: nodeId },
+  });
+}
+
+/** Nodes with notes, no content and a drafted parent, in time order. */
+export function getReadyNodes(level?: StoryLevel): Promise<StoryNode[]> {
+  return invokeDesktop<StoryNode[]>('ai_ready_nodes', {
+    request: level ? { level } : {},
+  });
+}
+
+export function getGenerationQueue(): Promise<GenerationQueueItem[]> {
+  return invokeDesktop<GenerationQueueItem[]>('ai_generation_queue');
+}
+
+/** Queue nodes for generation, one at a time, in the order given. */
+export function enqueueGeneration(nodeIds: string[]): Promise<GenerationQueueItem[]> {
+  return invokeDesktop<GenerationQueueItem[]>('ai_generation_enqueue', {
+    request: { node_ids: nodeIds },
+  });
+}
+
+/** Remove a node that has not started generating yet. */
+export function cancelQueuedGeneration(nodeId: string): Promise<GenerationQueueItem[]> {
+  return invokeDesktop<GenerationQueueItem[]>('ai_generation_cancel', {
     request: { node_id: nodeId },
   });
 }
@@ -54,22 +194,71 @@ export function getAiStatus(): Promise<AiStatus> {
   return invokeDesktop<AiStatus>('ai_status');
 }
 
+/** Load the generation and embedding models before the first request. */
+export function preloadAiModels(): Promise<AiPreloadStatus> {
+  return invokeDesktop<AiPreloadStatus>('ai_preload');
+}
+
 export function updateAiConfig(updates: Partial<AiConfig>): Promise<AiConfig> {
   return invokeDesktop<AiConfig>('ai_config_update', { updates });
 }
 
-export function getAiContext(nodeId: string): Promise<{ system: string; user: string }> {
-  return invokeDesktop<{ system: string; user: string }>('ai_context_preview', {
+export function getAiContext(nodeId: string): Promise<AiContextPreview> {
+  return invokeDesktop<AiContextPreview>('ai_context_preview', {
     nodeId,
   });
 }
 
-export function generateChildren(nodeId: string): Promise<ChildPlan> {
+export function getAiRequestBody(nodeId: string): Promise<unknown> {
+  return invokeDesktop<unknown>('ai_debug_request_body', { nodeId });
+}
+
+export function generateChildren(nodeId: string, preserveExisting = false): Promise<ChildPlan> {
   return invokeDesktop<ChildPlan>('ai_generate_children', {
+    request: { node_id: nodeId, preserve_existing: preserveExisting },
+  });
+}
+
+export function brainstormNotes(nodeId: string): Promise<TimelineCommandResponse> {
+  return invokeDesktop<TimelineCommandResponse>('ai_brainstorm_notes', {
     request: { node_id: nodeId },
   });
 }
 
+/** Backfills a node's planning notes from its existing content. */
+export function summarizeToNotes(
+  nodeId: string,
+  overwrite = false,
+): Promise<TimelineCommandResponse> {
+  return invokeDesktop<TimelineCommandResponse>('ai_summarize_to_notes', {
+    request: { node_id: nodeId, overwrite },
+  });
+}
+
+/** Drafts a prose treatment of one arc's storyline; nothing is stored. */
+export function generateArcTreatment(arcId: string): Promise<ArcTreatment> {
+  return invokeDesktop<ArcTreatment>('ai_arc_treatment', {
+    request: { arc_id: arcId },
+  });
+}
+
+export function expandPremise(): Promise<PremiseExpansion> {
+  return invokeDesktop<PremiseExpansion>('ai_expand_premise');
+}
+
+export function commitPremiseExpansion(
+  arcs: StoryArc[],
+  characters: CharacterProposal[],
+): Promise<PremiseExpansionCommitResponse> {
+  return invokeDesktop<PremiseExpansionCommitResponse>('ai_expand_premise_commit', {
+    request: { arcs, characters },
+  });
+}
+
+export function previewChildPlanRanges(plan: ChildPlan): Promise<ChildPlanRangePreview> {
+  return invokeDesktop<ChildPlanRangePreview>('projection_child_plan_ranges', { plan });
+}
+
 export function generateBatch(
   parentNodeId: string,
 ): Promise<{ status: string; parent_node_id: string; child_count: number }> {
@@ -81,6 +270,32 @@ export function generateBatch(
   );
 }
 
+/**
+ * Starts a background continuity audit; findings arrive as `continuity_finding`
+ * events followed by `continuity_audit_complete`.
+ */
+export function startContinuityAudit(): Promise<ContinuityAuditResponse> {
+  return invokeDesktop<ContinuityAuditResponse>('ai_continuity_audit');
+}
+
+/** Asks whether a node's content still carries out its planning notes. */
+export function checkNotesDrift(nodeId: string): Promise<NotesDriftVerdict> {
+  return invokeDesktop<NotesDriftVerdict>('ai_notes_drift', { request: { node_id: nodeId } });
+}
+
+/** Asks which arcs a node advances; nothing is tagged until the writer accepts. */
+export function suggestArcTags(nodeId: string): Promise<SuggestedArcTag[]> {
+  return invokeDesktop<SuggestedArcTag[]>('ai_suggest_arcs', { request: { node_id: nodeId } });
+}
+
+/**
+ * Starts a background notes-drift sweep; drifted nodes arrive as
+ * `notes_drift_finding` events followed by `notes_drift_sweep_complete`.
+ */
+export function startNotesDriftSweep(): Promise<NotesDriftSweepResponse> {
+  return invokeDesktop<NotesDriftSweepResponse>('ai_notes_drift_sweep');
+}
+
 // --- Model Library ---
 
 export function listModels(params?: {
@@ -106,16 +321,92 @@ export async function exportPdf(): Promise<Blob> {
   return new Blob([Uint8Array.from(bytes)], { type: 'application/pdf' });
 }
 
+export async function exportBibleMarkdown(): Promise<Blob> {
+  const markdown = await invokeDesktop<string>('export_bible_markdown');
+  return new Blob([markdown], { type: 'text/markdown' });
+}
+
+export function exportBibleJson(): Promise<BibleExport> {
+  return invokeDesktop<BibleExport>('export_bible_json');
+}
+
+export function exportBibleTransfer(): Promise<BibleTransfer> {
+  return invokeDesktop<BibleTransfer>('export_bible_transfer');
+}
+
+export async function exportBeatSheetCsv(level: StoryLevel = 'Scene'): Promise<Blob> {
+  const csv = await invokeDesktop<string>('export_beats_csv', { level });
+  return new Blob([csv], { type: 'text/csv' });
+}
+
+/** One arc's scenes and beats, in time order, as a Fountain screenplay. */
+export async function exportArcFountain(arcId: string): Promise<Blob> {
+  const fountain = await invokeDesktop<string>('export_arc_fountain', { arcId });
+  return new Blob([fountain], { type: 'text/plain' });
+}
+
 // --- Persistence ---
 
 export function saveProject(path?: string): Promise<{ saved?: string; error?: string }> {
   return invokeDesktop<{ saved?: string; error?: string }>('project_save', { path });
 }
 
-export function loadProject(path: string): Promise<Project> {
-  return invokeDesktop<Project>('project_load', { path });
+/** Write a node's live editor text into its content and save the project immediately. */
+export function commitNodeContent(nodeId: string): Promise<CommittedNodeContent> {
+  return invokeDesktop<CommittedNodeContent>('project_commit_node_content', {
+    request: { node_id: nodeId },
+  });
+}
+
+/**
+ * Open a saved project. It fails with a conflict while another server holds the project's lock;
+ * `force` takes the lock over.
+ */
+export function loadProject(path: string, force?: boolean): Promise<Project> {
+  return invokeDesktop<Project>('project_load', force ? { path, force } : { path });
+}
+
+/**
+ * Open the project in any writable directory, such as a synced folder. A directory with no
+ * `project.db` yet receives the loaded project. Later saves write there. `force` takes over
+ * another server's lock, as for {@link loadProject}.
+ */
+export function openProjectDirectory(path: string, force?: boolean): Promise<Project> {
+  return invokeDesktop<Project>('project_open_directory', force ? { path, force } : { path });
+}
+
+export function listProjects(query?: ProjectListQuery): Promise<ProjectEntry[]> {
+  return invokeDesktop<ProjectEntry[]>('project_list', query ? { query } : undefined);
+}
+
+export function getProjectDefaults(): Promise<ProjectDefaults> {
+  return invokeDesktop<ProjectDefaults>('project_defaults');
+}
+
+export function updateProjectDefaults(updates: Partial<ProjectDefaults>): Promise<ProjectDefaults> {
+  return invokeDesktop<ProjectDefaults>('project_defaults_update', { updates });
+}
+
+export function getCheckpointConfig(): Promise<CheckpointConfig> {
+  return invokeDesktop<CheckpointConfig>('project_checkpoint_config');
+}
+
+export function updateCheckpointConfig(
+  updates: Partial<CheckpointConfig>,
+): Promise<CheckpointConfig> {
+  return invokeDesktop<CheckpointConfig>('project_checkpoint_config_update', { updates });
 }
 
-export function listProjects(): Promise<{ name: string; path: string; modified: string }[]> {
-  return invokeDesktop<{ name: string; path: string; modified: string }[]>('project_list');
+/** Auto-checkpoints of the open project, newest first. */
+export function listCheckpoints(): Promise<CheckpointEntry[]> {
+  return invokeDesktop<CheckpointEntry[]>('project_checkpoints');
+}
+
+// --- Search ---
+
+export function searchContent(
+  query: string,
+  source: SearchSource = 'saved',
+): Promise<ContentSearchHit[]> {
+  return invokeDesktop<ContentSearchHit[]>('project_search_content', { query, source });
 }
@@ -1,4 +1,5 @@
 import type { CommandOutcome, FieldValue, ProjectionEnvelope } from './projectionTypes.js';
+import type { TimeRange } from './timelineTypes.js';
 
 export type BibleGraphNodeId = string;
 export type BibleGraphPartId = string;
@@ -71,6 +72,7 @@ export interface SetBibleGraphSnapshotFieldCommand {
   at_ms: number;
   label: string;
   snapshot_sort_order?: number;
+  source_node_id?: string | null;
   field_id?: BibleGraphSnapshotFieldId;
   part_key: BibleGraphPartKey;
   part_name: string;
@@ -132,6 +134,7 @@ export interface BibleGraphSnapshot {
   at_ms: number;
   label: string;
   sort_order: number;
+  source_node_id?: string | null;
 }
 
 export interface BibleGraphSnapshotField {
@@ -162,6 +165,12 @@ export interface BibleNodeDetailProjection {
   snapshots: BibleGraphSnapshotProjection[];
 }
 
+export interface BibleEntityPresenceProjection {
+  node: BibleGraphNode;
+  intervals: TimeRange[];
+  snapshots: BibleGraphSnapshot[];
+}
+
 export interface BibleGraphNodeListProjection {
   nodes: BibleGraphNode[];
 }
@@ -250,3 +259,95 @@ export interface BibleGraphNodeListCommandResponse {
   outcome: CommandOutcome;
   projection: ProjectionEnvelope<BibleGraphNodeListProjection>;
 }
+
+export interface PromotedLocation {
+  node_id: BibleGraphNodeId;
+  name: string;
+}
+
+export interface PromoteLocationsResponse {
+  created: PromotedLocation[];
+  linked_count: number;
+}
+
+export interface BibleExportDetail {
+  part: string;
+  field: string;
+  value: string;
+}
+
+export interface BibleExportEntry {
+  id: string;
+  name: string;
+  kind: string;
+  text?: string;
+  details: BibleExportDetail[];
+  relations: {
+    direction: 'outgoing' | 'incoming';
+    kind: string;
+    label: string;
+    other_id: string;
+    other_name: string;
+  }[];
+  snapshots: { at_ms: number; label: string; details: BibleExportDetail[] }[];
+}
+
+export interface BibleExport {
+  project_name: string;
+  categories: { name: string; entries: BibleExportEntry[] }[];
+}
+
+export interface BibleTransferField {
+  part_key: BibleGraphPartKey;
+  part_name: string;
+  part_sort_order: number;
+  field_key: BibleGraphFieldKey;
+  value: FieldValue;
+  sort_order: number;
+}
+
+export interface BibleTransferEntity {
+  id: BibleGraphNodeId;
+  parent_id?: BibleGraphNodeId | null;
+  schema_key: BibleGraphSchemaKey;
+  name: string;
+  sort_order: number;
+  fields: BibleTransferField[];
+  snapshots: { at_ms: number; label: string; sort_order: number; fields: BibleTransferField[] }[];
+}
+
+export interface BibleTransferRelation {
+  from_id: BibleGraphNodeId;
+  to_id: BibleGraphNodeId;
+  edge_kind: BibleGraphEdgeKind;
+  label: string;
+  directed: boolean;
+  sort_order: number;
+}
+
+/** Portable story bible file, imported into another project with fresh ids. */
+export interface BibleTransfer {
+  format_version: number;
+  entities: BibleTransferEntity[];
+  relations: BibleTransferRelation[];
+}
+
+export type BibleImportMode = 'merge' | 'replace';
+
+export interface ImportBibleTransferCommand {
+  mode: BibleImportMode;
+  bible: BibleTransfer;
+}
+
+export interface BibleImportSummary {
+  mode: BibleImportMode;
+  removed_entities: number;
+  imported_entities: number;
+  imported_relations: number;
+  skipped_relations: number;
+}
+
+export interface BibleImportResponse {
+  outcome: CommandOutcome;
+  summary: BibleImportSummary;
+}
@@ -1,4 +1,4 @@
-import type { BeatType, NodeId, StoryLevel } from './timelineTypes.js';
+import type { BeatType, NodeId, StoryLevel, TimeRange } from './timelineTypes.js';
 
 export interface ChildProposal {
   name: string;
@@ -16,3 +16,8 @@ export interface ChildPlan {
   target_child_level: StoryLevel;
   children: ChildProposal[];
 }
+
+export interface ChildPlanRangePreview {
+  parent_node_id: NodeId;
+  ranges: TimeRange[];
+}
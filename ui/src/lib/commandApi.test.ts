@@ -15,20 +15,31 @@ import {
   deleteStoryArc,
   deleteTimelineNode,
   deleteTimelineRelationship,
+  duplicateTimelineNode,
   ensureCanonicalBibleRoots,
+  importBible,
+  normalizeTimelineLevel,
+  previewTimelineChildren,
+  promoteLocations,
   recordContextEvaluation,
   rejectAffectProposal,
+  renumberTimelineScenes,
   setAffectValue,
   setBibleGraphEdge,
   setBibleGraphField,
   setBibleGraphNodeName,
   setBibleGraphSnapshotField,
   setObjectField,
+  setAllTracksCollapsed,
   setStoryArcMetadata,
   setTimelinePlayhead,
+  setTimelineNodeArcTag,
+  setTimelineNodeGenerationOrder,
   setTimelineNodeLock,
   setTimelineNodeNotes,
+  setTimelineNodePinned,
   setTimelineNodeRange,
+  setTimelineNodeSceneNumber,
   splitTimelineNode,
 } from './commandApi.js';
 
@@ -526,6 +537,172 @@ describe('command api helpers', () => {
     expect(fetchMock).not.toHaveBeenCalled();
   });
 
+  it('uses desktop timeline node pin commands when Tauri transport is available', async () => {
+    const response = {
+      outcome: 'recorded',
+      projection: {
+        version: 1,
+        payload: {
+          total_duration_ms: 120_000,
+          tracks: [],
+          clips: [],
+          relationships: [],
+        },
+      },
+    };
+    const invoke = vi.fn().mockResolvedValue(response);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+    const fetchMock = vi.fn();
+    vi.stubGlobal('fetch', fetchMock);
+
+    await expect(
+      setTimelineNodePinned(
+        {
+          node_id: 'node.scene.beach',
+          pinned: true,
+        },
+        'command-timeline-pin-1',
+      ),
+    ).resolves.toEqual(response);
+
+    expect(invoke).toHaveBeenCalledWith('command_timeline_node_pin', {
+      command: {
+        id: 'command-timeline-pin-1',
+        payload: {
+          node_id: 'node.scene.beach',
+          pinned: true,
+        },
+      },
+    });
+    expect(fetchMock).not.toHaveBeenCalled();
+  });
+
+  it('uses desktop scene numbering commands when Tauri transport is available', async () => {
+    const response = {
+      outcome: 'recorded',
+      projection: {
+        version: 1,
+        payload: {
+          total_duration_ms: 120_000,
+          tracks: [],
+          clips: [],
+          relationships: [],
+        },
+      },
+    };
+    const invoke = vi.fn().mockResolvedValue(response);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(
+      setTimelineNodeSceneNumber(
+        { node_id: 'node.scene.beach', scene_number: '12A' },
+        'command-scene-number-1',
+      ),
+    ).resolves.toEqual(response);
+    await expect(
+      renumberTimelineScenes({ mode: 'locked' }, 'command-renumber-1'),
+    ).resolves.toEqual(response);
+
+    expect(invoke).toHaveBeenCalledWith('command_timeline_node_scene_number', {
+      command: {
+        id: 'command-scene-number-1',
+        payload: { node_id: 'node.scene.beach', scene_number: '12A' },
+      },
+    });
+    expect(invoke).toHaveBeenCalledWith('command_timeline_renumber_scenes', {
+      command: { id: 'command-renumber-1', payload: { mode: 'locked' } },
+    });
+  });
+
+  it('uses the desktop timeline normalize command when Tauri transport is available', async () => {
+    const response = {
+      outcome: 'recorded',
+      projection: {
+        version: 4,
+        payload: { total_duration_ms: 60000, tracks: [], clips: [], relationships: [] },
+      },
+      adjusted: 2,
+    };
+    const invoke = vi.fn().mockResolvedValue(response);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(
+      normalizeTimelineLevel({ level: 'Scene', parent_id: 'act-1' }, 'command-normalize-1'),
+    ).resolves.toEqual(response);
+
+    expect(invoke).toHaveBeenCalledWith('command_timeline_normalize', {
+      command: { id: 'command-normalize-1', payload: { level: 'Scene', parent_id: 'act-1' } },
+    });
+  });
+
+  it('uses the desktop track collapse command for collapse and expand all', async () => {
+    const invoke = vi.fn().mockResolvedValue([]);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+
+    await expect(setAllTracksCollapsed(true)).resolves.toEqual([]);
+    await expect(setAllTracksCollapsed(false)).resolves.toEqual([]);
+
+    expect(invoke).toHaveBeenCalledWith('command_timeline_tracks_collapsed', { collapsed: true });
+    expect(invoke).toHaveBeenCalledWith('command_timeline_tracks_collapsed', { collapsed: false });
+  });
+
+  it('uses the desktop arc tag command for recursive tagging', async () => {
+    const invoke = vi.fn().mockResolvedValue({ outcome: 'recorded' });
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+    const payload = {
+      node_id: 'node.act.one',
+      arc_id: 'arc.a-plot',
+      tagged: true,
+      recursive: true,
+    };
+
+    await setTimelineNodeArcTag(payload, 'command-arc-tag-1');
+
+    expect(invoke).toHaveBeenCalledWith('command_timeline_node_arc_tag', {
+      command: { id: 'command-arc-tag-1', payload },
+    });
+  });
+
+  it('uses the desktop generation order command', async () => {
+    const invoke = vi.fn().mockResolvedValue({ outcome: 'recorded' });
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+    const payload = {
+      node_id: 'node.scene.flashback',
+      generation_priority: 1,
+      depends_on: ['node.scene.reveal'],
+    };
+
+    await setTimelineNodeGenerationOrder(payload, 'command-generation-order-1');
+
+    expect(invoke).toHaveBeenCalledWith('command_timeline_node_generation_order', {
+      command: { id: 'command-generation-order-1', payload },
+    });
+  });
+
   it('uses desktop timeline node notes commands when Tauri transport is available', async () => {
     const response = {
       outcome: 'recorded',
@@ -618,6 +795,46 @@ describe('command api helpers', () => {
     expect(fetchMock).not.toHaveBeenCalled();
   });
 
+  it('uses desktop timeline duplicate node commands when Tauri transport is available', async () => {
+    const response = {
+      outcome: 'recorded',
+      projection: {
+        version: 1,
+        payload: {
+          total_duration_ms: 120_000,
+          tracks: [],
+          clips: [],
+          relationships: [],
+        },
+      },
+      root_node_id: 'node.scene.beach.copy',
+      node_ids: { 'node.scene.beach': 'node.scene.beach.copy' },
+    };
+    const invoke = vi.fn().mockResolvedValue(response);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+    const fetchMock = vi.fn();
+    vi.stubGlobal('fetch', fetchMock);
+
+    await expect(
+      duplicateTimelineNode(
+        { node_id: 'node.scene.beach', recursive: true, offset_ms: 5_000 },
+        'command-timeline-duplicate-1',
+      ),
+    ).resolves.toEqual(response);
+
+    expect(invoke).toHaveBeenCalledWith('command_timeline_duplicate_node', {
+      command: {
+        id: 'command-timeline-duplicate-1',
+        payload: { node_id: 'node.scene.beach', recursive: true, offset_ms: 5_000 },
+      },
+    });
+    expect(fetchMock).not.toHaveBeenCalled();
+  });
+
   it('uses desktop timeline playhead commands when Tauri transport is available', async () => {
     const response = { position_ms: 42_500 };
     const invoke = vi.fn().mockResolvedValue(response);
@@ -839,6 +1056,50 @@ describe('command api helpers', () => {
     expect(fetchMock).not.toHaveBeenCalled();
   });
 
+  it('previews timeline children through the desktop command', async () => {
+    const preview = {
+      children: [
+        { node_id: 'node.scene.first', name: 'First child', start_ms: 0, end_ms: 60_000 },
+      ],
+      removed_node_ids: ['node.scene.old'],
+      bible_references: [
+        {
+          source_node_id: 'node.scene.first',
+          reference_kind: 'character',
+          reference_text: 'Ada',
+          existing_node_id: 'node.character.ada',
+        },
+      ],
+    };
+    const invoke = vi.fn().mockResolvedValue(preview);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+    const payload = {
+      parent_id: 'node.sequence.opening',
+      children: [
+        {
+          node_id: 'node.scene.first',
+          name: 'First child',
+          outline: '',
+          weight: 1,
+          beat_type: null,
+          characters: ['Ada'],
+        },
+      ],
+    };
+
+    await expect(previewTimelineChildren(payload, 'command-children-preview-1')).resolves.toEqual(
+      preview,
+    );
+
+    expect(invoke).toHaveBeenCalledWith('command_timeline_apply_children_preview', {
+      command: { id: 'command-children-preview-1', payload },
+    });
+  });
+
   it('uses desktop bible graph node create command when Tauri transport is available', async () => {
     const response = {
       outcome: 'recorded',
@@ -965,6 +1226,58 @@ describe('command api helpers', () => {
     expect(fetchMock).not.toHaveBeenCalled();
   });
 
+  it('uses desktop location promotion command when Tauri transport is available', async () => {
+    const response = {
+      created: [{ node_id: 'node.location.harbor', name: 'Harbor' }],
+      linked_count: 2,
+    };
+    const invoke = vi.fn().mockResolvedValue(response);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+    const fetchMock = vi.fn();
+    vi.stubGlobal('fetch', fetchMock);
+
+    await expect(promoteLocations()).resolves.toEqual(response);
+
+    expect(invoke).toHaveBeenCalledWith('command_bible_promote_locations', undefined);
+    expect(fetchMock).not.toHaveBeenCalled();
+  });
+
+  it('uses desktop bible import command when Tauri transport is available', async () => {
+    const bible = { format_version: 1, entities: [], relations: [] };
+    const response = {
+      outcome: 'recorded',
+      summary: {
+        mode: 'replace',
+        removed_entities: 3,
+        imported_entities: 0,
+        imported_relations: 0,
+        skipped_relations: 0,
+      },
+    };
+    const invoke = vi.fn().mockResolvedValue(response);
+    vi.stubGlobal('window', {
+      __TAURI__: {
+        core: { invoke },
+      },
+    });
+    const fetchMock = vi.fn();
+    vi.stubGlobal('fetch', fetchMock);
+
+    await expect(importBible(bible, 'replace', 'command-import-1')).resolves.toEqual(response);
+
+    expect(invoke).toHaveBeenCalledWith('command_bible_import', {
+      command: {
+        id: 'command-import-1',
+        payload: { mode: 'replace', bible },
+      },
+    });
+    expect(fetchMock).not.toHaveBeenCalled();
+  });
+
   it('uses desktop bible graph node delete command when Tauri transport is available', async () => {
     const response = {
       outcome: 'recorded',
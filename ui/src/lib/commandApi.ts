@@ -3,10 +3,15 @@ import type {
   BibleGraphNodeId,
   BibleGraphNodeListCommandResponse,
   BibleGraphRootsCommandResponse,
+  BibleImportMode,
+  BibleImportResponse,
+  BibleTransfer,
   CreateBibleGraphNodeCommand,
   DeleteBibleGraphEdgeCommand,
   DeleteBibleGraphNodeCommand,
   EnsureCanonicalBibleRootsCommand,
+  ImportBibleTransferCommand,
+  PromoteLocationsResponse,
   SetBibleGraphEdgeCommand,
   SetBibleGraphFieldCommand,
   SetBibleGraphNodeNameCommand,
@@ -66,10 +71,19 @@ export {
   createTimelineRelationship,
   deleteTimelineNode,
   deleteTimelineRelationship,
+  duplicateTimelineNode,
+  normalizeTimelineLevel,
+  previewTimelineChildren,
+  renumberTimelineScenes,
+  setAllTracksCollapsed,
   setTimelinePlayhead,
+  setTimelineNodeArcTag,
+  setTimelineNodeGenerationOrder,
   setTimelineNodeLock,
   setTimelineNodeNotes,
+  setTimelineNodePinned,
   setTimelineNodeRange,
+  setTimelineNodeSceneNumber,
   splitTimelineNode,
 } from './timelineCommandApi.js';
 
@@ -255,6 +269,23 @@ export function ensureCanonicalBibleRoots(
   });
 }
 
+export function promoteLocations(): Promise<PromoteLocationsResponse> {
+  return invokeDesktop<PromoteLocationsResponse>('command_bible_promote_locations');
+}
+
+export function importBible(
+  bible: BibleTransfer,
+  mode: BibleImportMode = 'merge',
+  commandId = createCommandId(),
+): Promise<BibleImportResponse> {
+  const command: CommandEnvelope<ImportBibleTransferCommand> = {
+    id: commandId,
+    payload: { mode, bible },
+  };
+
+  return invokeDesktop<BibleImportResponse>('command_bible_import', { command });
+}
+
 export function recordContextEvaluation(
   payload: RecordContextEvaluationCommand,
   commandId = createCommandId(),
@@ -1,4 +1,6 @@
-import type { Timeline } from './timelineTypes.js';
+import type { BibleGraphNodeId } from './bibleGraphTypes.js';
+import type { ArcId } from './storyArcTypes.js';
+import type { StoryLevel, Timeline } from './timelineTypes.js';
 
 export type ReferenceId = string;
 
@@ -9,16 +11,121 @@ export type ReferenceType =
   | 'PreviousEpisode'
   | { Custom: string };
 
+/** Arcs and entities a reference informs; an empty or missing scope is global. */
+export interface ReferenceScope {
+  arc_ids?: ArcId[];
+  entity_ids?: BibleGraphNodeId[];
+}
+
 export interface ReferenceDocument {
   id: ReferenceId;
   name: string;
   content: string;
   doc_type: ReferenceType;
+  scope?: ReferenceScope;
+}
+
+export interface ReembedReferencesResponse {
+  status: string;
+  embedding_model: string;
+  document_count: number;
+  chunk_count: number;
+}
+
+export interface EpisodeHeader {
+  id: string;
+  title: string;
+  sort_order: number;
+}
+
+/** An episode other than the active one, with its whole timeline. */
+export interface Episode extends EpisodeHeader {
+  timeline: Timeline;
+}
+
+export interface EpisodeSummary extends EpisodeHeader {
+  active: boolean;
+  scene_count: number;
+  total_duration_ms: number;
 }
 
 export interface Project {
   name: string;
   premise: string;
+  /** The active episode's timeline. */
   timeline: Timeline;
+  active_episode?: EpisodeHeader;
+  episodes?: Episode[];
   references?: ReferenceDocument[];
+  ai_settings?: ProjectAiSettings;
+}
+
+/** Per-project AI overrides; unset fields use the global AI config. */
+export interface ProjectAiSettings {
+  model?: string | null;
+  temperature?: number | null;
+  /** Added to every generation prompt. */
+  style_notes?: string | null;
+  /** Replaces the built-in system prompt. */
+  system_prompt?: string | null;
+}
+
+/** The active project at a glance; `dirty` while edits wait for the auto-save. */
+export interface CurrentProject {
+  name: string;
+  path: string | null;
+  dirty: boolean;
+  node_count: number;
+}
+
+export interface ProjectEntry {
+  name: string;
+  path: string;
+  /** RFC3339 UTC timestamp; empty when the filesystem doesn't report one. */
+  modified: string;
+}
+
+/** What new projects get when `createProject` leaves a setting out. */
+export interface ProjectDefaults {
+  /** Episode content length; the template is scaled to fit. */
+  episode_duration_ms: number;
+}
+
+/** Automatic restore points; `interval_minutes: 0` turns them off. */
+export interface CheckpointConfig {
+  interval_minutes: number;
+  /** Auto-checkpoints kept per project; older ones are pruned. */
+  retention: number;
+}
+
+export interface CheckpointEntry {
+  /** e.g. `autosave 14:32` (UTC). */
+  label: string;
+  /** Database copy; open it with `loadProject` to restore. */
+  path: string;
+  /** RFC3339 UTC timestamp. */
+  created_at: string;
+}
+
+/** Defaults to every project, most recently modified first. */
+export interface ProjectListQuery {
+  sort?: 'name' | 'modified';
+  direction?: 'asc' | 'desc';
+  offset?: number;
+  limit?: number;
+  /** Absolute directory to list in place of the default project storage. */
+  base_dir?: string;
+}
+
+/** `live` searches the collaborative doc; `saved` searches the last save. */
+export type SearchSource = 'live' | 'saved';
+
+export interface ContentSearchHit {
+  node_id: string;
+  name: string;
+  level: StoryLevel;
+  start_ms: number;
+  end_ms: number;
+  field: 'notes' | 'content';
+  snippet: string;
 }
@@ -3,7 +3,11 @@ import { afterEach, describe, expect, it, vi } from 'vitest';
 import {
   getAffectProposalListProjection,
   getAffectProjection,
+  getArcCoverage,
+  getArcEntityEvolution,
+  getBeatTypeWarnings,
   getBibleGraphNodeListProjection,
+  getBibleEntityPresence,
   getBibleGraphNodeProjection,
   getBibleGraphSchemaListProjection,
   getBibleReferenceProposalListProjection,
@@ -11,13 +15,23 @@ import {
   getChangeReviewProjection,
   getContextInfluenceProjection,
   getContextStackProjection,
+  getNodeStaleness,
   getObjectFieldProjection,
   getPropagationProposalListProjection,
   getScriptDocumentProjection,
   getSelectedNodeEditorProjection,
   getStoryArcListProjection,
   getStoryArcProgressionProjection,
+  getPinnedTimelineNodes,
+  getTimelineContextAt,
+  getTimelineNodesInRange,
+  getRelationshipTypes,
+  getTimelineTracks,
+  validateEpisodeStructure,
+  getTimelineOverview,
+  getTimelineTree,
   getTimelineRenderProjection,
+  getUnpaidSetups,
 } from './projectionApi.js';
 
 function installDesktopInvoke(response: unknown) {
@@ -115,6 +129,21 @@ describe('projection api helpers', () => {
     });
   });
 
+  it('uses the desktop bible entity presence command for an entity', async () => {
+    const response = {
+      node: { id: 'node.character.mara' },
+      intervals: [{ start_ms: 0, end_ms: 60_000 }],
+      snapshots: [],
+    };
+    const invoke = installDesktopInvoke(response);
+
+    await expect(getBibleEntityPresence('node.character.mara', 'Scene')).resolves.toEqual(response);
+
+    expect(invoke).toHaveBeenCalledWith('projection_bible_entity_presence', {
+      query: { node_id: 'node.character.mara', level: 'Scene' },
+    });
+  });
+
   it('uses the desktop bible graph node list projection command', async () => {
     const response = { version: 3, payload: { nodes: [] } };
     const invoke = installDesktopInvoke(response);
@@ -266,6 +295,35 @@ describe('projection api helpers', () => {
     expect(invoke).toHaveBeenCalledWith('projection_story_arc_progression', undefined);
   });
 
+  it('uses the desktop arc coverage command with a gap threshold', async () => {
+    const response = {
+      arc_id: 'arc-1',
+      coverage: [
+        { start_ms: 0, end_ms: 60_000 },
+        { start_ms: 600_000, end_ms: 660_000 },
+      ],
+      gaps: [{ start_ms: 60_000, end_ms: 600_000 }],
+    };
+    const invoke = installDesktopInvoke(response);
+
+    await expect(getArcCoverage('arc-1', 300_000)).resolves.toEqual(response);
+
+    expect(invoke).toHaveBeenCalledWith('projection_arc_coverage', {
+      query: { arc_id: 'arc-1', min_gap_ms: 300_000 },
+    });
+  });
+
+  it('uses the desktop arc entity evolution command for an arc', async () => {
+    const response = { arc_id: 'arc-1', entities: [] };
+    const invoke = installDesktopInvoke(response);
+
+    await expect(getArcEntityEvolution('arc-1')).resolves.toEqual(response);
+
+    expect(invoke).toHaveBeenCalledWith('projection_arc_entity_evolution', {
+      query: { arc_id: 'arc-1' },
+    });
+  });
+
   it('uses the desktop timeline render projection command', async () => {
     const response = {
       version: 4,
@@ -283,6 +341,131 @@ describe('projection api helpers', () => {
     expect(invoke).toHaveBeenCalledWith('projection_timeline_render', undefined);
   });
 
+  it('uses the desktop timeline range query command', async () => {
+    const invoke = installDesktopInvoke([]);
+
+    await expect(getTimelineNodesInRange(30_000, 90_000)).resolves.toEqual([]);
+
+    expect(invoke).toHaveBeenCalledWith('projection_timeline_nodes_in_range', {
+      query: { start_ms: 30_000, end_ms: 90_000 },
+    });
+  });
+
+  it('uses the desktop timeline context-at command', async () => {
+    const context = { at_ms: 45_000, nodes: [], arcs: [], entities: [] };
+    const invoke = installDesktopInvoke(context);
+
+    await expect(getTimelineContextAt(45_000)).resolves.toEqual(context);
+
+    expect(invoke).toHaveBeenCalledWith('projection_timeline_context_at', {
+      query: { at_ms: 45_000 },
+    });
+  });
+
+  it('uses the desktop pinned timeline nodes command', async () => {
+    const invoke = installDesktopInvoke([]);
+
+    await expect(getPinnedTimelineNodes()).resolves.toEqual([]);
+
+    expect(invoke).toHaveBeenCalledWith('projection_timeline_pinned', undefined);
+  });
+
+  it('uses the desktop unpaid setups command', async () => {
+    const invoke = installDesktopInvoke([]);
+
+    await expect(getUnpaidSetups()).resolves.toEqual([]);
+
+    expect(invoke).toHaveBeenCalledWith('projection_timeline_unpaid_setups', undefined);
+  });
+
+  it('uses the desktop timeline tree command for a subtree', async () => {
+    const invoke = installDesktopInvoke([]);
+
+    await expect(getTimelineTree('node-1')).resolves.toEqual([]);
+
+    expect(invoke).toHaveBeenCalledWith('projection_timeline_tree', {
+      query: { root: 'node-1' },
+    });
+  });
+
+  it('uses the desktop timeline overview command', async () => {
+    const overview = { level: 'Scene', duration_ms: 60_000, buckets: [] };
+    const invoke = installDesktopInvoke(overview);
+
+    await expect(getTimelineOverview('Scene', 12)).resolves.toEqual(overview);
+
+    expect(invoke).toHaveBeenCalledWith('projection_timeline_overview', {
+      query: { level: 'Scene', buckets: 12 },
+    });
+  });
+
+  it('uses the desktop relationship types command', async () => {
+    const types = [
+      { id: 'Causal', label: 'Causal', description: '', directed: true, parameters: [] },
+    ];
+    const invoke = installDesktopInvoke(types);
+
+    await expect(getRelationshipTypes()).resolves.toEqual(types);
+
+    expect(invoke).toHaveBeenCalledWith('projection_timeline_relationship_types', undefined);
+  });
+
+  it('uses the desktop timeline tracks projection', async () => {
+    const tracks = [
+      { id: 'track-1', level: 'Scene', label: 'Scenes', sort_order: 0, collapsed: true },
+    ];
+    const invoke = installDesktopInvoke(tracks);
+
+    await expect(getTimelineTracks()).resolves.toEqual(tracks);
+
+    expect(invoke).toHaveBeenCalledWith('projection_timeline_tracks', undefined);
+  });
+
+  it('uses the desktop structure validation projection', async () => {
+    const segments = [
+      {
+        segment_type: 'Act' as const,
+        time_range: { start_ms: 10_000, end_ms: 60_000 },
+        label: 'Act One',
+      },
+    ];
+    const normalization = {
+      issues: [
+        { kind: 'gap', after: null, before: 'Act One', range: { start_ms: 0, end_ms: 10_000 } },
+      ],
+      tiles_cleanly: false,
+      normalized: { template_name: 'Custom', segments },
+    };
+    const invoke = installDesktopInvoke(normalization);
+
+    await expect(validateEpisodeStructure(segments, 60_000)).resolves.toEqual(normalization);
+
+    expect(invoke).toHaveBeenCalledWith('projection_timeline_structure_validate', {
+      request: { segments, total_duration_ms: 60_000 },
+    });
+  });
+
+  it('uses the desktop beat type warnings command for a node', async () => {
+    const invoke = installDesktopInvoke([]);
+
+    await expect(getBeatTypeWarnings('node-1')).resolves.toEqual([]);
+
+    expect(invoke).toHaveBeenCalledWith('projection_beat_type_warnings', {
+      query: { node_id: 'node-1' },
+    });
+  });
+
+  it('uses the desktop node staleness command for a node', async () => {
+    const response = { node_id: 'node-1', stale: true, generated: true };
+    const invoke = installDesktopInvoke(response);
+
+    await expect(getNodeStaleness('node-1')).resolves.toEqual(response);
+
+    expect(invoke).toHaveBeenCalledWith('projection_node_staleness', {
+      query: { node_id: 'node-1' },
+    });
+  });
+
   it('uses the desktop selected node projection command with a node id', async () => {
     const response = { version: 5, payload: { node: null } };
     const invoke = installDesktopInvoke(response);
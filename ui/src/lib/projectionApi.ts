@@ -4,6 +4,7 @@ import type {
   AffectProposalListProjection,
 } from './affectTypes.js';
 import type {
+  BibleEntityPresenceProjection,
   BibleGraphNodeId,
   BibleGraphNodeListProjection,
   BibleRenderGraphProjection,
@@ -23,8 +24,27 @@ import type { PropagationProposalListProjection } from './propagationProposalTyp
 import type { ScriptDocumentId, ScriptDocumentProjection } from './scriptTypes.js';
 import type { SelectedNodeEditorProjection } from './selectedNodeEditorTypes.js';
 import type { BibleReferenceProposalListProjection } from './semanticProposalTypes.js';
-import type { StoryArcListProjection, StoryArcProgressionProjection } from './storyArcTypes.js';
-import type { NodeId } from './timelineTypes.js';
+import type {
+  ArcCoverageProjection,
+  ArcEntityEvolutionProjection,
+  ArcId,
+  StoryArcListProjection,
+  StoryArcProgressionProjection,
+} from './storyArcTypes.js';
+import type {
+  BeatTypeWarning,
+  NodeId,
+  NodeStaleness,
+  RelationshipTypeInfo,
+  StoryLevel,
+  StoryNode,
+  StructureNormalization,
+  StructureSegment,
+  TimelineContextAtProjection,
+  TimelineOverviewProjection,
+  TimelineTreeNode,
+  Track,
+} from './timelineTypes.js';
 import type { TimelineRenderProjection } from './timelineRenderTypes.js';
 import { invokeDesktop } from './desktopTransport.js';
 
@@ -81,6 +101,16 @@ export function getBibleGraphNodeProjection({
   );
 }
 
+/** Merged timeline spans where an entity appears, plus its snapshot markers. */
+export function getBibleEntityPresence(
+  nodeId: BibleGraphNodeId,
+  level?: StoryLevel,
+): Promise<BibleEntityPresenceProjection> {
+  return invokeDesktop<BibleEntityPresenceProjection>('projection_bible_entity_presence', {
+    query: { node_id: nodeId, level: level ?? null },
+  });
+}
+
 export function getBibleGraphNodeListProjection(): Promise<
   ProjectionEnvelope<BibleGraphNodeListProjection>
 > {
@@ -162,12 +192,95 @@ export function getStoryArcProgressionProjection(): Promise<
   );
 }
 
+/** The arc's merged coverage and the gaps longer than `minGapMs` between it. */
+export function getArcCoverage(arcId: ArcId, minGapMs = 0): Promise<ArcCoverageProjection> {
+  return invokeDesktop<ArcCoverageProjection>('projection_arc_coverage', {
+    query: { arc_id: arcId, min_gap_ms: minGapMs },
+  });
+}
+
+export function getArcEntityEvolution(arcId: ArcId): Promise<ArcEntityEvolutionProjection> {
+  return invokeDesktop<ArcEntityEvolutionProjection>('projection_arc_entity_evolution', {
+    query: { arc_id: arcId },
+  });
+}
+
 export function getTimelineRenderProjection(): Promise<
   ProjectionEnvelope<TimelineRenderProjection>
 > {
   return invokeDesktop<ProjectionEnvelope<TimelineRenderProjection>>('projection_timeline_render');
 }
 
+export function getTimelineNodesInRange(startMs: number, endMs: number): Promise<StoryNode[]> {
+  return invokeDesktop<StoryNode[]>('projection_timeline_nodes_in_range', {
+    query: { start_ms: startMs, end_ms: endMs },
+  });
+}
+
+/** Active nodes, arcs and resolved entity states under a playhead position. */
+export function getTimelineContextAt(atMs: number): Promise<TimelineContextAtProjection> {
+  return invokeDesktop<TimelineContextAtProjection>('projection_timeline_context_at', {
+    query: { at_ms: atMs },
+  });
+}
+
+export function getPinnedTimelineNodes(): Promise<StoryNode[]> {
+  return invokeDesktop<StoryNode[]>('projection_timeline_pinned');
+}
+
+/** Setup beats with no payoff relationship yet, in timeline order. */
+export function getUnpaidSetups(): Promise<StoryNode[]> {
+  return invokeDesktop<StoryNode[]>('projection_timeline_unpaid_setups');
+}
+
+export function getTimelineTree(root?: NodeId): Promise<TimelineTreeNode[]> {
+  return invokeDesktop<TimelineTreeNode[]>('projection_timeline_tree', {
+    query: { root: root ?? null },
+  });
+}
+
+/** The relationship types the backend accepts, for relationship pickers. */
+export function getRelationshipTypes(): Promise<RelationshipTypeInfo[]> {
+  return invokeDesktop<RelationshipTypeInfo[]>('projection_timeline_relationship_types');
+}
+
+/** The timeline's tracks in display order with their collapsed state. */
+export function getTimelineTracks(): Promise<Track[]> {
+  return invokeDesktop<Track[]>('projection_timeline_tracks');
+}
+
+/** Check a proposed episode structure without applying it. */
+export function validateEpisodeStructure(
+  segments: StructureSegment[],
+  totalDurationMs: number,
+): Promise<StructureNormalization> {
+  return invokeDesktop<StructureNormalization>('projection_timeline_structure_validate', {
+    request: { segments, total_duration_ms: totalDurationMs },
+  });
+}
+
+/** Nodes at `level` summarized into `buckets` equal time slices, for a minimap. */
+export function getTimelineOverview(
+  level: StoryLevel,
+  buckets: number,
+): Promise<TimelineOverviewProjection> {
+  return invokeDesktop<TimelineOverviewProjection>('projection_timeline_overview', {
+    query: { level, buckets },
+  });
+}
+
+export function getBeatTypeWarnings(nodeId: NodeId): Promise<BeatTypeWarning[]> {
+  return invokeDesktop<BeatTypeWarning[]>('projection_beat_type_warnings', {
+    query: { node_id: nodeId },
+  });
+}
+
+export function getNodeStaleness(nodeId: NodeId): Promise<NodeStaleness> {
+  return invokeDesktop<NodeStaleness>('projection_node_staleness', {
+    query: { node_id: nodeId },
+  });
+}
+
 export function getSelectedNodeEditorProjection({
   node_id,
 }: SelectedNodeEditorProjectionKey = {}): Promise<
@@ -23,6 +23,8 @@ export interface SelectedNodeEditorNode {
   content_status: ContentStatus;
   beat_type?: BeatType | null;
   locked: boolean;
+  /** The notes changed since the content was generated. */
+  stale?: boolean;
 }
 
 export interface SelectedNodeEditorSummary {
@@ -1,15 +1,53 @@
 import type { GraphRendererCommand } from './graphRendererTypes.js';
+import type { StoryLevel } from './timelineTypes.js';
+
+/** How a generation's reference context was retrieved. */
+export type RetrievalMode = 'none' | 'embedding' | 'lexical';
 
 export type ServerMessage =
+  | { type: 'project_changed' }
   | { type: 'timeline_changed' }
   | { type: 'hierarchy_changed' }
   | { type: 'story_changed' }
   | { type: 'node_updated'; node_id: string }
-  | { type: 'generation_context'; node_id: string; system_prompt: string; user_prompt: string }
+  | {
+      type: 'generation_context';
+      node_id: string;
+      system_prompt: string;
+      user_prompt: string;
+      estimated_tokens: number;
+    }
   | { type: 'generation_progress'; node_id: string; token: string; tokens_generated: number }
-  | { type: 'generation_complete'; node_id: string }
+  | {
+      type: 'generation_complete';
+      node_id: string;
+      truncated: boolean;
+      reason: string | null;
+      rag_used: boolean;
+      retrieval_mode: RetrievalMode;
+    }
   | { type: 'generation_error'; node_id: string; error: string }
+  | { type: 'generation_queue_item_finished'; node_id: string; error: string | null }
   | { type: 'bible_changed' }
+  | { type: 'rag_unavailable'; node_id: string; reason: string }
+  | {
+      type: 'output_over_length';
+      node_id: string;
+      level: StoryLevel;
+      words: number;
+      target_words: number;
+    }
+  | { type: 'anchor_violation'; node_id: string; anchor: string }
+  | {
+      type: 'continuity_finding';
+      node_id: string;
+      entity_id: string;
+      entity_name: string;
+      explanation: string;
+    }
+  | { type: 'continuity_audit_complete'; checked_count: number; finding_count: number }
+  | { type: 'notes_drift_finding'; node_id: string; specifics: string[] }
+  | { type: 'notes_drift_sweep_complete'; checked_count: number; drift_count: number }
   | { type: 'semantic_proposals_changed' }
   | { type: 'context_influence_changed'; target_node_id: string }
   | { type: 'script_changed' }
@@ -19,6 +19,8 @@ export const editorState = $state<{
   lastGenerationNodeId: NodeId | null;
   /** Error from a failed generation. */
   generationError: string | null;
+  /** Warning from a generation that finished but may be incomplete. */
+  generationWarning: string | null;
   /** Parent node ID during batch child generation. */
   batchParentNodeId: NodeId | null;
   /** Total number of children in batch generation. */
@@ -34,6 +36,7 @@ export const editorState = $state<{
   generationContext: null,
   lastGenerationNodeId: null,
   generationError: null,
+  generationWarning: null,
   batchParentNodeId: null,
   batchTotalCount: 0,
   batchCompletedCount: 0,
@@ -48,6 +51,7 @@ export function resetEditorState(): void {
   editorState.generationContext = null;
   editorState.lastGenerationNodeId = null;
   editorState.generationError = null;
+  editorState.generationWarning = null;
   editorState.batchParentNodeId = null;
   editorState.batchTotalCount = 0;
   editorState.batchCompletedCount = 0;
@@ -60,6 +64,7 @@ export function startGeneration(nodeId: string): void {
   editorState.streamingTokenCount = 0;
   editorState.generationContext = null;
   editorState.generationError = null;
+  editorState.generationWarning = null;
   editorState.batchParentNodeId = null;
 }
 
@@ -73,6 +78,7 @@ export function startBatchGeneration(parentNodeId: string): void {
   editorState.streamingTokenCount = 0;
   editorState.generationContext = null;
   editorState.generationError = null;
+  editorState.generationWarning = null;
 }
 
 /** Update the expected batch total count (called after API response). */
@@ -96,9 +102,12 @@ export function appendStreamingToken(nodeId: string, token: string, count: numbe
   }
 }
 
-/** Finalize a completed generation. */
-export function completeGeneration(nodeId: string): void {
+/** Finalize a completed generation, warning when it stopped at the token limit. */
+export function completeGeneration(nodeId: string, truncated = false): void {
   if (editorState.streamingNodeId === nodeId) {
+    editorState.generationWarning = truncated
+      ? 'Output hit the token limit and may be cut off.'
+      : null;
     editorState.streamingNodeId = null;
     editorState.streamingText = '';
   }
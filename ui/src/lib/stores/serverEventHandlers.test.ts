@@ -8,6 +8,7 @@ import { refreshBibleRenderGraphProjection } from './bibleRenderGraphProjection.
 import { clearProjectionRefreshQueue } from './projectionRefreshQueue.js';
 import { completeGeneration, editorState } from './editor.svelte.js';
 import { applyGraphRendererCommand } from './graphRendererCommands.js';
+import { notify } from './notifications.svelte.js';
 import { timelineState } from './timeline.svelte.js';
 
 vi.mock('./timelineRenderProjection.svelte.js', () => ({
@@ -62,11 +63,16 @@ vi.mock('./graphRendererCommands.js', () => ({
   applyGraphRendererCommand: vi.fn(),
 }));
 
+vi.mock('./notifications.svelte.js', () => ({
+  notify: vi.fn(),
+}));
+
 const refreshTimelineRenderProjectionMock = vi.mocked(refreshTimelineRenderProjection);
 const refreshScriptDocumentProjectionMock = vi.mocked(refreshScriptDocumentProjection);
 const refreshBibleRenderGraphProjectionMock = vi.mocked(refreshBibleRenderGraphProjection);
 const completeGenerationMock = vi.mocked(completeGeneration);
 const applyGraphRendererCommandMock = vi.mocked(applyGraphRendererCommand);
+const notifyMock = vi.mocked(notify);
 
 class MockServerEventClient {
   readonly handlers = new Map<ServerMessage['type'], (data: ServerMessage) => void>();
@@ -111,6 +117,21 @@ beforeEach(() => {
 });
 
 describe('backend event projection handlers', () => {
+  it('clears the selection and reloads projections when the project changes', async () => {
+    const events = new MockServerEventClient();
+    setupServerEventHandlers(events as never);
+    editorState.selectedNodeId = 'node.scene.beach';
+
+    events.emit({ type: 'project_changed' });
+
+    expect(editorState.selectedNodeId).toBeNull();
+    await vi.waitFor(() => {
+      expect(refreshTimelineRenderProjectionMock).toHaveBeenCalledTimes(1);
+      expect(refreshScriptDocumentProjectionMock).toHaveBeenCalledTimes(1);
+      expect(refreshBibleRenderGraphProjectionMock).toHaveBeenCalledTimes(1);
+    });
+  });
+
   it('refreshes timeline projections for timeline events', async () => {
     const events = new MockServerEventClient();
     setupServerEventHandlers(events as never);
@@ -152,15 +173,39 @@ describe('backend event projection handlers', () => {
     const events = new MockServerEventClient();
     setupServerEventHandlers(events as never);
 
-    events.emit({ type: 'generation_complete', node_id: 'node.beat.one' });
+    events.emit({
+      type: 'generation_complete',
+      node_id: 'node.beat.one',
+      truncated: true,
+      reason: 'length',
+      rag_used: false,
+      retrieval_mode: 'none',
+    });
 
     await vi.waitFor(() => {
       expect(refreshTimelineRenderProjectionMock).toHaveBeenCalledTimes(1);
       expect(refreshScriptDocumentProjectionMock).toHaveBeenCalledWith({
         document_id: 'script.document.main',
       });
-      expect(completeGenerationMock).toHaveBeenCalledWith('node.beat.one');
+      expect(completeGenerationMock).toHaveBeenCalledWith('node.beat.one', true);
+    });
+  });
+
+  it('warns when reference retrieval falls back to lexical search', () => {
+    const events = new MockServerEventClient();
+    setupServerEventHandlers(events as never);
+
+    events.emit({
+      type: 'rag_unavailable',
+      node_id: 'node.beat.one',
+      reason: 'embedding request failed',
     });
+
+    expect(notifyMock).toHaveBeenCalledWith(
+      'warning',
+      'Reference search fell back to lexical matching: embedding request failed',
+      6000,
+    );
   });
 
   it('refreshes selected context graph projections for context influence changes', async () => {
@@ -22,6 +22,7 @@ import { refreshPropagationProposalListProjection } from './propagationProposalP
 import { refreshChangeReviewProjection } from './changeReviewProjection.svelte.js';
 import { clearProjectionRefreshQueue, requestProjectionRefresh } from './projectionRefreshQueue.js';
 import { applyGraphRendererCommand } from './graphRendererCommands.js';
+import { notify } from './notifications.svelte.js';
 import { timelineState } from './timeline.svelte.js';
 
 const SCRIPT_DOCUMENT_KEY = `script-document:${MAIN_SCRIPT_DOCUMENT_ID}`;
@@ -77,6 +78,20 @@ function refreshChangeReview() {
 /** Register backend event handlers that update Svelte stores. */
 export function setupServerEventHandlers(events: ServerEventClient): () => void {
   const unsubscribers = [
+    events.on('project_changed', async () => {
+      editorState.selectedNodeId = null;
+      await Promise.all([
+        refreshTimelineRender(),
+        refreshMainScriptDocument(),
+        refreshStoryArcs(),
+        refreshBibleNodeList(),
+        refreshBibleRenderGraph(),
+        refreshSemanticProposals(),
+        refreshPropagationProposals(),
+        refreshChangeReview(),
+      ]);
+    }),
+
     events.on('timeline_changed', async () => {
       await refreshTimelineRender();
     }),
@@ -103,13 +118,17 @@ export function setupServerEventHandlers(events: ServerEventClient): () => void
 
     events.on('generation_complete', async (data) => {
       await Promise.all([refreshTimelineRender(), refreshMainScriptDocument()]);
-      completeGeneration(data.node_id);
+      completeGeneration(data.node_id, data.truncated);
     }),
 
     events.on('generation_error', (data) => {
       setGenerationError(data.node_id, data.error);
     }),
 
+    events.on('rag_unavailable', (data) => {
+      notify('warning', `Reference search fell back to lexical matching: ${data.reason}`, 6000);
+    }),
+
     events.on('bible_changed', async () => {
       await Promise.all([refreshBibleNodeList(), refreshBibleRenderGraph(), refreshChangeReview()]);
     }),
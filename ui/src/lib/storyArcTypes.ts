@@ -1,4 +1,6 @@
+import type { BibleGraphNode, BibleGraphSnapshotProjection } from './bibleGraphTypes.js';
 import type { CommandOutcome, ProjectionEnvelope } from './projectionTypes.js';
+import type { TimeRange } from './timelineTypes.js';
 
 export type ArcId = string;
 
@@ -45,6 +47,27 @@ export interface StoryArcProgressionProjection {
   progressions: ArcProgression[];
 }
 
+/** How the bible entities an arc's nodes reference develop while the arc is active. */
+export interface ArcEntityEvolutionProjection {
+  arc_id: ArcId;
+  /** Earliest start to latest end of the arc's nodes; absent when no node carries the arc. */
+  window?: TimeRange;
+  entities: ArcEntityEvolution[];
+}
+
+/** Where an arc is present on the timeline and the gaps where it drops out. */
+export interface ArcCoverageProjection {
+  arc_id: ArcId;
+  coverage: TimeRange[];
+  gaps: TimeRange[];
+}
+
+export interface ArcEntityEvolution {
+  node: BibleGraphNode;
+  /** Snapshots inside the arc's window, oldest first. */
+  snapshots: BibleGraphSnapshotProjection[];
+}
+
 export interface CreateStoryArcCommand {
   arc_id?: ArcId;
   parent_arc_id?: ArcId | null;
@@ -1,14 +1,25 @@
 import type { CommandEnvelope } from './projectionTypes.js';
+import type { Track } from './timelineTypes.js';
 import type {
   ApplyTimelineChildrenCommand,
+  ApplyTimelineChildrenPreview,
   CreateTimelineChildFromParentCommand,
   CreateTimelineNodeCommand,
   CreateTimelineRelationshipCommand,
   DeleteTimelineNodeCommand,
   DeleteTimelineRelationshipCommand,
+  DuplicateTimelineNodeCommand,
+  DuplicateTimelineNodeResponse,
+  NormalizeTimelineLevelCommand,
+  NormalizeTimelineLevelResponse,
+  RenumberTimelineScenesCommand,
+  SetTimelineNodeArcTagCommand,
+  SetTimelineNodeGenerationOrderCommand,
   SetTimelineNodeLockCommand,
   SetTimelineNodeNotesCommand,
+  SetTimelineNodePinnedCommand,
   SetTimelineNodeRangeCommand,
+  SetTimelineNodeSceneNumberCommand,
   SplitTimelineNodeCommand,
   TimelineCommandResponse,
   TimelinePlayheadCommandResponse,
@@ -72,6 +83,20 @@ export function applyTimelineChildren(
   return invokeDesktop<TimelineCommandResponse>('command_timeline_apply_children', { command });
 }
 
+export function previewTimelineChildren(
+  payload: ApplyTimelineChildrenCommand,
+  commandId = createCommandId(),
+): Promise<ApplyTimelineChildrenPreview> {
+  const command: CommandEnvelope<ApplyTimelineChildrenCommand> = {
+    id: commandId,
+    payload,
+  };
+
+  return invokeDesktop<ApplyTimelineChildrenPreview>('command_timeline_apply_children_preview', {
+    command,
+  });
+}
+
 export function createTimelineRelationship(
   payload: CreateTimelineRelationshipCommand,
   commandId = createCommandId(),
@@ -112,6 +137,88 @@ export function setTimelineNodeLock(
   return invokeDesktop<TimelineCommandResponse>('command_timeline_node_lock', { command });
 }
 
+export function setTimelineNodePinned(
+  payload: SetTimelineNodePinnedCommand,
+  commandId = createCommandId(),
+): Promise<TimelineCommandResponse> {
+  const command: CommandEnvelope<SetTimelineNodePinnedCommand> = {
+    id: commandId,
+    payload,
+  };
+
+  return invokeDesktop<TimelineCommandResponse>('command_timeline_node_pin', { command });
+}
+
+export function setTimelineNodeSceneNumber(
+  payload: SetTimelineNodeSceneNumberCommand,
+  commandId = createCommandId(),
+): Promise<TimelineCommandResponse> {
+  const command: CommandEnvelope<SetTimelineNodeSceneNumberCommand> = {
+    id: commandId,
+    payload,
+  };
+
+  return invokeDesktop<TimelineCommandResponse>('command_timeline_node_scene_number', {
+    command,
+  });
+}
+
+export function setTimelineNodeGenerationOrder(
+  payload: SetTimelineNodeGenerationOrderCommand,
+  commandId = createCommandId(),
+): Promise<TimelineCommandResponse> {
+  const command: CommandEnvelope<SetTimelineNodeGenerationOrderCommand> = {
+    id: commandId,
+    payload,
+  };
+
+  return invokeDesktop<TimelineCommandResponse>('command_timeline_node_generation_order', {
+    command,
+  });
+}
+
+export function renumberTimelineScenes(
+  payload: RenumberTimelineScenesCommand = {},
+  commandId = createCommandId(),
+): Promise<TimelineCommandResponse> {
+  const command: CommandEnvelope<RenumberTimelineScenesCommand> = {
+    id: commandId,
+    payload,
+  };
+
+  return invokeDesktop<TimelineCommandResponse>('command_timeline_renumber_scenes', { command });
+}
+
+/** Snap sibling boundaries that drifted a few ms apart back together. */
+export function normalizeTimelineLevel(
+  payload: NormalizeTimelineLevelCommand,
+  commandId = createCommandId(),
+): Promise<NormalizeTimelineLevelResponse> {
+  const command: CommandEnvelope<NormalizeTimelineLevelCommand> = {
+    id: commandId,
+    payload,
+  };
+
+  return invokeDesktop<NormalizeTimelineLevelResponse>('command_timeline_normalize', { command });
+}
+
+/** Collapse or expand every track; resolves to the tracks in display order. */
+export function setAllTracksCollapsed(collapsed: boolean): Promise<Track[]> {
+  return invokeDesktop<Track[]>('command_timeline_tracks_collapsed', { collapsed });
+}
+
+export function setTimelineNodeArcTag(
+  payload: SetTimelineNodeArcTagCommand,
+  commandId = createCommandId(),
+): Promise<TimelineCommandResponse> {
+  const command: CommandEnvelope<SetTimelineNodeArcTagCommand> = {
+    id: commandId,
+    payload,
+  };
+
+  return invokeDesktop<TimelineCommandResponse>('command_timeline_node_arc_tag', { command });
+}
+
 export function setTimelineNodeNotes(
   payload: SetTimelineNodeNotesCommand,
   commandId = createCommandId(),
@@ -136,6 +243,20 @@ export function splitTimelineNode(
   return invokeDesktop<TimelineCommandResponse>('command_timeline_split_node', { command });
 }
 
+export function duplicateTimelineNode(
+  payload: DuplicateTimelineNodeCommand,
+  commandId = createCommandId(),
+): Promise<DuplicateTimelineNodeResponse> {
+  const command: CommandEnvelope<DuplicateTimelineNodeCommand> = {
+    id: commandId,
+    payload,
+  };
+
+  return invokeDesktop<DuplicateTimelineNodeResponse>('command_timeline_duplicate_node', {
+    command,
+  });
+}
+
 export function deleteTimelineNode(
   payload: DeleteTimelineNodeCommand,
   commandId = createCommandId(),
@@ -1,11 +1,14 @@
 import type { BeatType, RelationshipId, RelationshipType, StoryLevel } from './timelineTypes.js';
 import type { CommandOutcome, ProjectionEnvelope } from './projectionTypes.js';
 import type { TimelineRenderProjection } from './timelineRenderTypes.js';
+import type { BibleGraphNodeId } from './bibleGraphTypes.js';
+import type { BibleReferenceKind } from './semanticProposalTypes.js';
 
 export interface SetTimelineNodeRangeCommand {
   node_id: string;
   start_ms: number;
   end_ms: number;
+  reanchor_snapshots?: boolean;
 }
 
 export interface SplitTimelineNodeCommand {
@@ -15,6 +18,13 @@ export interface SplitTimelineNodeCommand {
   right_node_id?: string;
 }
 
+export interface DuplicateTimelineNodeCommand {
+  node_id: string;
+  recursive?: boolean;
+  /** Shift applied to the copy; defaults to the node's duration. */
+  offset_ms?: number;
+}
+
 export interface DeleteTimelineNodeCommand {
   node_id: string;
 }
@@ -24,6 +34,48 @@ export interface SetTimelineNodeLockCommand {
   locked: boolean;
 }
 
+export interface SetTimelineNodePinnedCommand {
+  node_id: string;
+  pinned: boolean;
+}
+
+/** Set or clear (null or blank) a scene's production number. */
+export interface SetTimelineNodeSceneNumberCommand {
+  node_id: string;
+  scene_number: string | null;
+}
+
+/** How batch generation orders a node; rejected when `depends_on` forms a cycle. */
+export interface SetTimelineNodeGenerationOrderCommand {
+  node_id: string;
+  generation_priority: number | null;
+  depends_on: string[];
+}
+
+/**
+ * `sequential` numbers every scene 1, 2, 3…; `locked` keeps existing numbers and
+ * letters new scenes (12, 12A, 13).
+ */
+export type SceneNumbering = 'sequential' | 'locked';
+
+export interface RenumberTimelineScenesCommand {
+  mode?: SceneNumbering;
+}
+
+/** Without `parent_id`, the parentless nodes at `level` are normalized. */
+export interface NormalizeTimelineLevelCommand {
+  level: StoryLevel;
+  parent_id?: string | null;
+}
+
+/** With `recursive`, the tag is added to or removed from every descendant too. */
+export interface SetTimelineNodeArcTagCommand {
+  node_id: string;
+  arc_id: string;
+  tagged: boolean;
+  recursive?: boolean;
+}
+
 export interface SetTimelineNodeNotesCommand {
   node_id: string;
   notes: string;
@@ -48,6 +100,8 @@ export interface ApplyTimelineChildrenCommand {
   parent_id: string;
   child_plan_id?: string;
   children: ApplyTimelineChildCommand[];
+  /** Keep existing children and place the new ones in the time they leave uncovered. */
+  preserve_existing?: boolean;
 }
 
 export interface ApplyTimelineChildCommand {
@@ -61,6 +115,28 @@ export interface ApplyTimelineChildCommand {
   props?: string[];
 }
 
+/** What applying a child plan would do, computed without writing anything. */
+export interface ApplyTimelineChildrenPreview {
+  children: ApplyTimelineChildPreview[];
+  removed_node_ids: string[];
+  bible_references: ApplyTimelineReferencePreview[];
+}
+
+export interface ApplyTimelineChildPreview {
+  node_id: string;
+  name: string;
+  start_ms: number;
+  end_ms: number;
+}
+
+export interface ApplyTimelineReferencePreview {
+  source_node_id: string;
+  reference_kind: BibleReferenceKind;
+  reference_text: string;
+  /** Entity accepting the reference would link to; absent means a new one. */
+  existing_node_id?: BibleGraphNodeId;
+}
+
 export interface CreateTimelineRelationshipCommand {
   relationship_id?: RelationshipId;
   from_node_id: string;
@@ -77,6 +153,20 @@ export interface TimelineCommandResponse {
   projection: ProjectionEnvelope<TimelineRenderProjection>;
 }
 
+export interface DuplicateTimelineNodeResponse extends TimelineCommandResponse {
+  root_node_id: string;
+  /** Source node id to copy id. */
+  node_ids: Record<string, string>;
+}
+
+export interface NormalizeTimelineLevelResponse {
+  /** Absent when no boundary needed snapping and nothing was recorded. */
+  outcome?: CommandOutcome;
+  projection: ProjectionEnvelope<TimelineRenderProjection>;
+  /** Sibling boundaries snapped together. */
+  adjusted: number;
+}
+
 export interface TimelinePlayheadCommandResponse {
   position_ms: number;
 }
@@ -1,4 +1,5 @@
-import type { ArcId } from './storyArcTypes.js';
+import type { BibleGraphNodeId, BibleGraphSnapshotField } from './bibleGraphTypes.js';
+import type { ArcId, Color, StoryArc } from './storyArcTypes.js';
 
 export type TrackId = string;
 export type NodeId = string;
@@ -34,6 +35,49 @@ export interface StoryNode {
   content: NodeContent;
   beat_type: BeatType | null;
   locked: boolean;
+  /** Bookmarked for quick navigation. */
+  pinned: boolean;
+  /** Production scene number ("12", "12A"); Scene nodes only. */
+  scene_number?: string;
+}
+
+/** A node with its arc tags and children inline (`projection_timeline_tree`). */
+export interface TimelineTreeNode extends StoryNode {
+  arc_ids: ArcId[];
+  children: TimelineTreeNode[];
+}
+
+/** Everything in effect at one instant (`projection_timeline_context_at`). */
+export interface TimelineContextAtProjection {
+  at_ms: number;
+  /** Active nodes, top level first. */
+  nodes: StoryNode[];
+  arcs: StoryArc[];
+  entities: TimelineContextEntity[];
+}
+
+/** One level summarized into equal time buckets (`projection_timeline_overview`). */
+export interface TimelineOverviewProjection {
+  level: StoryLevel;
+  duration_ms: number;
+  buckets: TimelineOverviewBucket[];
+}
+
+export interface TimelineOverviewBucket {
+  start_ms: number;
+  end_ms: number;
+  /** Nodes overlapping the bucket; long nodes count in every bucket they span. */
+  node_count: number;
+  dominant_arc_id?: ArcId;
+  dominant_arc_color?: Color;
+  /** Content words, split across buckets by each node's overlap. */
+  word_count: number;
+}
+
+export interface TimelineContextEntity {
+  node_id: BibleGraphNodeId;
+  name: string;
+  fields: BibleGraphSnapshotField[];
 }
 
 export interface NodeArc {
@@ -56,16 +100,45 @@ export type BeatType =
   | 'Callback'
   | { Custom: string };
 
+/** A beat whose type is unusual for its place in the episode. */
+export interface BeatTypeWarning {
+  node_id: NodeId;
+  node_name: string;
+  beat_type: BeatType;
+  segment_label: string;
+  expected: BeatType[];
+  message: string;
+}
+
+/** Whether a node's notes changed since its content was generated. */
+export interface NodeStaleness {
+  node_id: NodeId;
+  stale: boolean;
+  generated: boolean;
+}
+
 export interface NodeContent {
   notes: string;
   /** Script/outline text. Replaces the old generated_text + user_refined_text split. */
   content: string;
   status: ContentStatus;
   scene_recap?: string | null;
+  notes_hash_at_generation?: string | null;
+  /** Batch generation order among ready siblings; lower goes first. */
+  generation_priority?: number | null;
+  /** Nodes whose content must be generated before this one's. */
+  depends_on?: NodeId[];
 }
 
 export type ContentStatus = 'Empty' | 'NotesOnly' | 'Generating' | 'HasContent';
 
+/** A node's editor text as written into its content by `project_commit_node_content`. */
+export interface CommittedNodeContent {
+  node_id: NodeId;
+  content: string;
+  status: ContentStatus;
+}
+
 export interface Relationship {
   id: RelationshipId;
   from_node: NodeId;
@@ -73,7 +146,29 @@ export interface Relationship {
   relationship_type: RelationshipType;
 }
 
-export type RelationshipType = 'Causal' | { Convergence: { arc_ids: ArcId[] } } | 'Thematic';
+/** `Payoff` runs from the node planting a setup to the node paying it off. */
+export type RelationshipType =
+  | 'Causal'
+  | { Convergence: { arc_ids: ArcId[] } }
+  | 'Thematic'
+  | 'Payoff';
+
+/** A relationship type as listed by `projection_timeline_relationship_types`. */
+export interface RelationshipTypeInfo {
+  /** The variant name: the bare string form, or the key of the object form. */
+  id: string;
+  label: string;
+  description: string;
+  /** Whether `from_node` and `to_node` play different roles. */
+  directed: boolean;
+  parameters: RelationshipTypeParameter[];
+}
+
+export interface RelationshipTypeParameter {
+  name: string;
+  kind: 'arc_ids';
+  description: string;
+}
 
 export interface EpisodeStructure {
   template_name: string;
@@ -88,6 +183,20 @@ export interface StructureSegment {
 
 export type SegmentType = 'ColdOpen' | 'MainTitles' | 'Act' | 'CommercialBreak' | 'Tag';
 
+/** Where a structure fails to tile the episode once its segments are sorted. */
+export type StructureIssue =
+  | { kind: 'gap'; after: string | null; before: string | null; range: TimeRange }
+  | { kind: 'overlap'; first: string; second: string; range: TimeRange }
+  | { kind: 'inverted'; label: string; range: TimeRange }
+  | { kind: 'overrun'; label: string; range: TimeRange };
+
+export interface StructureNormalization {
+  issues: StructureIssue[];
+  tiles_cleanly: boolean;
+  /** Sorted segments with boundaries snapped to tile the episode exactly. */
+  normalized: EpisodeStructure;
+}
+
 export interface TimelineGap {
   level: StoryLevel;
   time_range: TimeRange;
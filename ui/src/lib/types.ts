@@ -13,12 +13,14 @@ export { colorToHex } from './storyArcTypes.js';
 
 export type {
   BeatType,
+  BeatTypeWarning,
   CharacterId,
   ContentStatus,
   EpisodeStructure,
   NodeArc,
   NodeContent,
   NodeId,
+  NodeStaleness,
   Relationship,
   RelationshipId,
   RelationshipType,